@@ -0,0 +1,186 @@
+#![feature(test)]
+
+//! the streaming benches of the hashes and MACs over the multi-megabyte messages, the message is written by the
+//! `STREAM_CHUNK_LEN` chunks, so the benches exercise the block processing loop rather than the padding of the short
+//! messages. The throughput is reported by the `MB/s` column, and the heap allocations of one message are counted by
+//! the `CountingAlloc` and printed to the stderr(run with the `--nocapture`).
+//!
+//! the message length is 4 MiB by default, it can be changed by the `RCRYPTO_BENCH_STREAM_MIB` environment variable,
+//! e.g. `RCRYPTO_BENCH_STREAM_MIB=1 cargo +nightly bench --features nightly --bench stream -- kmac --nocapture`.
+
+extern crate test;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use test::Bencher;
+use rcrypto::{Digest, MD5, SHA, SHA3, SM3, HMAC, CMAC, AES, Poly1305, ZUCMac};
+use rcrypto::sha3::{Shake128, Shake256, CShake128, KMAC128, KMAC256, ParallelHash128, KangarooTwelve};
+
+const STREAM_MIB: usize = 4;
+const STREAM_CHUNK_LEN: usize = 16 << 10;
+
+struct CountingAlloc;
+
+static ALLOC_COUNT: AtomicUsize = AtomicUsize::new(0);
+static ALLOC_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOC_COUNT.fetch_add(1, Ordering::Relaxed);
+        ALLOC_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOC_COUNT.fetch_add(1, Ordering::Relaxed);
+        ALLOC_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn stream_once<D: Digest>(d: &mut D, msg: &[u8], digest: &mut Vec<u8>) {
+    msg.chunks(STREAM_CHUNK_LEN).for_each(|chunk| d.write(chunk));
+    d.checksum(digest);
+    d.reset();
+}
+
+fn stream_len() -> usize {
+    std::env::var("RCRYPTO_BENCH_STREAM_MIB").ok().and_then(|x| x.parse::<usize>().ok()).filter(|&x| x > 0)
+        .unwrap_or(STREAM_MIB) << 20
+}
+
+fn bench_stream<D: Digest>(b: &mut Bencher, name: &str, mut d: D) {
+    let len = stream_len();
+    let msg = (0..len).map(|i| (i as u8).wrapping_mul(0x9d)).collect::<Vec<_>>();
+    let mut digest = Vec::with_capacity(128);
+
+    // warm up the internal buffers, then count the allocations of one message
+    stream_once(&mut d, msg.as_slice(), &mut digest);
+    let (count, bytes) = (ALLOC_COUNT.load(Ordering::Relaxed), ALLOC_BYTES.load(Ordering::Relaxed));
+    stream_once(&mut d, msg.as_slice(), &mut digest);
+    eprintln!("{}: {} allocations({} bytes) per {} MiB message", name,
+        ALLOC_COUNT.load(Ordering::Relaxed) - count, ALLOC_BYTES.load(Ordering::Relaxed) - bytes, len >> 20);
+
+    b.bytes = len as u64;
+    b.iter(|| {
+        stream_once(&mut d, msg.as_slice(), &mut digest);
+        test::black_box(&digest);
+    });
+}
+
+#[bench]
+fn md5_stream(b: &mut Bencher) {
+    bench_stream(b, "MD5", MD5::new());
+}
+
+#[bench]
+fn sha1_stream(b: &mut Bencher) {
+    bench_stream(b, "SHA-1", SHA::sha1());
+}
+
+#[bench]
+fn sha256_stream(b: &mut Bencher) {
+    bench_stream(b, "SHA-256", SHA::sha256());
+}
+
+#[bench]
+fn sha512_stream(b: &mut Bencher) {
+    bench_stream(b, "SHA-512", SHA::sha512());
+}
+
+#[bench]
+fn sm3_stream(b: &mut Bencher) {
+    bench_stream(b, "SM3", SM3::new());
+}
+
+#[bench]
+fn sha3_256_stream(b: &mut Bencher) {
+    bench_stream(b, "SHA3-256", SHA3::sha256());
+}
+
+#[bench]
+fn sha3_512_stream(b: &mut Bencher) {
+    bench_stream(b, "SHA3-512", SHA3::sha512());
+}
+
+#[bench]
+fn shake128_stream(b: &mut Bencher) {
+    bench_stream(b, "SHAKE128", Shake128::new(256));
+}
+
+#[bench]
+fn shake256_stream(b: &mut Bencher) {
+    bench_stream(b, "SHAKE256", Shake256::new(512));
+}
+
+#[bench]
+fn cshake128_stream(b: &mut Bencher) {
+    bench_stream(b, "cSHAKE128", CShake128::new(256, b"", b"rcrypto bench"));
+}
+
+#[bench]
+fn parallel_hash128_stream(b: &mut Bencher) {
+    bench_stream(b, "ParallelHash128", ParallelHash128::new(8192, 256, b"").unwrap());
+}
+
+#[bench]
+fn k12_stream(b: &mut Bencher) {
+    bench_stream(b, "KangarooTwelve", KangarooTwelve::new(256, b""));
+}
+
+#[bench]
+fn hmac_sha256_stream(b: &mut Bencher) {
+    bench_stream(b, "HMAC-SHA-256", HMAC::new(vec![0x0b; 32], SHA::sha256()).unwrap());
+}
+
+#[bench]
+fn hmac_sha512_stream(b: &mut Bencher) {
+    bench_stream(b, "HMAC-SHA-512", HMAC::new(vec![0x0b; 64], SHA::sha512()).unwrap());
+}
+
+#[bench]
+fn hmac_sm3_stream(b: &mut Bencher) {
+    bench_stream(b, "HMAC-SM3", HMAC::new(vec![0x0b; 32], SM3::new()).unwrap());
+}
+
+#[bench]
+fn hmac_sha3_256_stream(b: &mut Bencher) {
+    bench_stream(b, "HMAC-SHA3-256", HMAC::new(vec![0x0b; 32], SHA3::sha256()).unwrap());
+}
+
+#[bench]
+fn hmac_sha3_512_stream(b: &mut Bencher) {
+    bench_stream(b, "HMAC-SHA3-512", HMAC::new(vec![0x0b; 64], SHA3::sha512()).unwrap());
+}
+
+#[bench]
+fn kmac128_stream(b: &mut Bencher) {
+    bench_stream(b, "KMAC128", KMAC128::new(&[0x0b; 32], 256, b""));
+}
+
+#[bench]
+fn kmac256_stream(b: &mut Bencher) {
+    bench_stream(b, "KMAC256", KMAC256::new(&[0x0b; 32], 512, b""));
+}
+
+#[bench]
+fn cmac_aes128_stream(b: &mut Bencher) {
+    bench_stream(b, "CMAC-AES-128", CMAC::new(AES::aes_128([0x2b; 16])).unwrap());
+}
+
+#[bench]
+fn poly1305_stream(b: &mut Bencher) {
+    bench_stream(b, "Poly1305", Poly1305::new([0x2b; 32]));
+}
+
+#[bench]
+fn zuc_mac_stream(b: &mut Bencher) {
+    bench_stream(b, "128-EIA3", ZUCMac::new(0x561eb2dd, 0x14, false, [0x2b; 16]));
+}
@@ -1,5 +1,9 @@
 
 fn main() {
+    for name in ["rcrypto_aes", "rcrypto_sse2", "rcrypto_sha"].iter() {
+        println!("cargo:rustc-check-cfg=cfg({}, values(\"support\"))", name);
+    }
+
     if std::is_x86_feature_detected!("aes") {
         println!("cargo:rustc-cfg=rcrypto_aes=\"support\"");
     }
@@ -0,0 +1,48 @@
+//! The block ciphers and the block cipher modes
+//! FIPS 197 Appendix C, FIPS 46-3, NIST SP 800-67 r2, GB/T 32907-2016, NIST SP 800-38A F.2.1
+
+use rcrypto::encoding::Hex;
+use rcrypto::cipher_mode::{CBC, EmptyPadding, FixedInitialVec};
+use rcrypto::{Cipher, AES, DES, SM4, TDES};
+
+fn check<C: Cipher>(name: &str, cipher: &C, plaintext: &str, ciphertext: &str) {
+    let hex = Hex::lower();
+    let (txt, expected) = (hex.decode(plaintext).unwrap(), hex.decode(ciphertext).unwrap());
+    let (mut cipher_txt, mut buf) = (Vec::new(), Vec::new());
+
+    cipher.encrypt(&mut cipher_txt, txt.as_slice()).unwrap();
+    assert_eq!(cipher_txt, expected, "encrypt-{}", name);
+    cipher.decrypt(&mut buf, cipher_txt.as_slice()).unwrap();
+    assert_eq!(buf, txt, "decrypt-{}", name);
+    println!("{:<12}: {}", name, hex.encode(cipher_txt.as_slice()));
+}
+
+fn main() {
+    let hex = Hex::lower();
+    let txt = "00112233445566778899aabbccddeeff";
+
+    check("AES-128", &AES::new(hex.decode("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap(),
+        txt, "69c4e0d86a7b0430d8cdb78070b4c55a");
+    check("AES-192", &AES::new(hex.decode("000102030405060708090a0b0c0d0e0f1011121314151617").unwrap()).unwrap(),
+        txt, "dda97ca4864cdfe06eaf70a0ec0d7191");
+    check("AES-256", &AES::new(hex.decode("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap()).unwrap(),
+        txt, "8ea2b7ca516745bfeafc49904b496089");
+
+    check("DES", &DES::new(0x133457799BBCDFF1u64.to_be_bytes()), "0123456789abcdef", "85e813540f0ab405");
+    check("TDES", &TDES::new(0x0123456789ABCDEFu64.to_be_bytes(), 0x23456789ABCDEF01u64.to_be_bytes(), 0x456789ABCDEF0123u64.to_be_bytes()),
+        &hex.encode(b"The qufc"), "a826fd8ce53b855f");
+
+    check("SM4", &SM4::new(0x0123456789abcdeffedcba9876543210u128.to_be_bytes()),
+        "0123456789abcdeffedcba9876543210", "681edf34d206965e86b3e94f536e4246");
+
+    let aes = AES::new(hex.decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap()).unwrap();
+    let iv = FixedInitialVec::new(hex.decode("000102030405060708090a0b0c0d0e0f").unwrap());
+    check("AES-128-CBC", &CBC::new(aes, EmptyPadding, iv).unwrap(),
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+        "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2");
+}
+
+#[test]
+fn run() {
+    main()
+}
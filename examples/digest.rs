@@ -0,0 +1,37 @@
+//! The one-shot digests of the message `abc`
+//! FIPS 180-4, FIPS 202, RFC 1321, GB/T 32905-2016
+
+use rcrypto::encoding::Hex;
+use rcrypto::sha::{SHA1, SHA224, SHA256, SHA384, SHA512, SHA512T224, SHA512T256};
+use rcrypto::{sha3, MD5, SM3};
+
+fn main() {
+    let hex = Hex::lower();
+    // (name, digest, expected)
+    let cases: [(&str, Vec<u8>, &str); 13] = [
+        ("MD5", MD5::digest(b"abc"), "900150983cd24fb0d6963f7d28e17f72"),
+        ("SHA-1", SHA1::digest(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d"),
+        ("SHA-224", SHA224::digest(b"abc"), "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"),
+        ("SHA-256", SHA256::digest(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        ("SHA-384", SHA384::digest(b"abc"), "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"),
+        ("SHA-512", SHA512::digest(b"abc"), "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+        ("SHA-512/224", SHA512T224::digest(b"abc"), "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"),
+        ("SHA-512/256", SHA512T256::digest(b"abc"), "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+        ("SHA3-224", sha3::SHA224::digest(b"abc"), "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"),
+        ("SHA3-256", sha3::SHA256::digest(b"abc"), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+        ("SHA3-384", sha3::SHA384::digest(b"abc"), "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"),
+        ("SHA3-512", sha3::SHA512::digest(b"abc"), "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"),
+        ("SM3", SM3::digest(b"abc"), "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"),
+    ];
+
+    for (name, digest, expected) in cases.iter() {
+        let digest = hex.encode(digest.as_slice());
+        assert_eq!(digest.as_str(), *expected, "{}", name);
+        println!("{:<12}: {}", name, digest);
+    }
+}
+
+#[test]
+fn run() {
+    main()
+}
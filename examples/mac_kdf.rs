@@ -0,0 +1,41 @@
+//! The message authentication codes and the key derivation functions
+//! RFC 4231 4.2, NIST SP 800-38B D.1, GM/T 0001-2012, RFC 6070
+
+use rcrypto::encoding::Hex;
+use rcrypto::kdf::PBKDF2;
+use rcrypto::sha::{SHA1, SHA256};
+use rcrypto::{Digest, AES, CMAC, HMAC, ZUC};
+
+fn check(name: &str, computed: &[u8], expected: &str) {
+    let computed = Hex::lower().encode(computed);
+    assert_eq!(computed.as_str(), expected, "{}", name);
+    println!("{:<12}: {}", name, computed);
+}
+
+fn main() {
+    let hex = Hex::lower();
+    let mut buf = Vec::new();
+
+    let mut hmac = HMAC::new(vec![0x0bu8; 20], SHA256::new()).unwrap();
+    hmac.write(b"Hi There");
+    hmac.checksum(&mut buf);
+    check("HMAC-SHA256", buf.as_slice(), "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7");
+
+    let mut cmac = CMAC::new(AES::new(hex.decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap()).unwrap()).unwrap();
+    cmac.write(hex.decode("6bc1bee22e409f96e93d7e117393172a").unwrap().as_slice());
+    cmac.checksum(&mut buf);
+    check("AES-CMAC", buf.as_slice(), "070a16b46b4d4144f79bdd9dd04a287c");
+
+    let mut zuc = ZUC::new([0u8; 16], [0u8; 16]);
+    zuc.keystream_bytes(2, &mut buf, true);
+    check("ZUC", buf.as_slice(), "27bede74018082da");
+
+    let mut pbkdf2 = PBKDF2::new(SHA1::new(), 4096).unwrap();
+    pbkdf2.derive(b"password", b"salt", 20, &mut buf).unwrap();
+    check("PBKDF2", buf.as_slice(), "4b007901b765489abead49d926f721d065a429c1");
+}
+
+#[test]
+fn run() {
+    main()
+}
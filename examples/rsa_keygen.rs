@@ -0,0 +1,43 @@
+//! The RSA multi-prime key generation with the progress report and the cancellation
+//!
+//! `cargo run --example rsa_keygen -- [bits_len] [n_primes] [max_candidates]`, the key generation is cancelled when
+//! more than the `max_candidates` prime candidates are rejected.
+
+use std::time::Instant;
+use rcrypto::rsa::{PrivateKey, KeygenProgress};
+use rcrypto::CryptoErrorKind;
+use rmath::rand::{CryptoRand, DefaultSeed};
+
+fn keygen(bits_len: usize, n_primes: usize, max_candidates: usize) {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let (start, mut found) = (Instant::now(), 0);
+    let key = PrivateKey::generate_multi_prime_key_with_progress(n_primes, bits_len, 19, &mut rd, |p: &KeygenProgress| {
+        if p.primes != found {
+            found = p.primes;
+            println!("[{:>8.2?}] prime {}/{} found, {} candidates rejected", start.elapsed(), p.primes, p.n_primes, p.candidates);
+        }
+        p.candidates <= max_candidates
+    });
+
+    match key {
+        Ok(key) => {
+            key.is_valid().unwrap();
+            println!("{}-byte RSA key with {} primes generated in {:.2?}", key.modulus_len(), n_primes, start.elapsed());
+        },
+        Err(e) if e.kind() == CryptoErrorKind::Cancelled => {
+            println!("cancelled after {:.2?}: {}", start.elapsed(), e);
+        },
+        Err(e) => panic!("{}", e),
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).map(|x| x.parse::<usize>().expect("the arguments must be the numbers")).collect::<Vec<_>>();
+    keygen(args.first().copied().unwrap_or(1024), args.get(1).copied().unwrap_or(2), args.get(2).copied().unwrap_or(usize::MAX));
+}
+
+#[test]
+fn run() {
+    keygen(1024, 2, usize::MAX);
+    keygen(2048, 2, 8);
+}
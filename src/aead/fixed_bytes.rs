@@ -0,0 +1,117 @@
+//! The fixed length nonce, IV and authentication tag
+//!
+//! `Nonce<N>`, `Iv<N>` and `Tag<N>` are the `N` bytes arrays, the length is checked at the compile time by the
+//! `new([u8; N])`, or at the construction by the `from_slice`, so the wrong length is rejected before it reaches
+//! the cipher. The `Tag` is compared in the constant time.
+
+use std::fmt::{Debug, Formatter};
+use rmath::rand::Source;
+use crate::{CryptoError, CryptoErrorKind};
+
+macro_rules! impl_fixed_bytes {
+    ($Type0: ident, $NAME: literal) => {
+        impl<const N: usize> $Type0<N> {
+            pub const LEN: usize = N;
+
+            pub fn new(bytes: [u8; N]) -> Self {
+                Self(bytes)
+            }
+
+            /// the `bytes` must be `N` bytes
+            pub fn from_slice(bytes: &[u8]) -> Result<Self, CryptoError> {
+                if bytes.len() != N {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                        format!("The {} must be {} bytes, but got {} bytes", $NAME, N, bytes.len())));
+                }
+
+                let mut b = [0u8; N];
+                b.copy_from_slice(bytes);
+                Ok(Self(b))
+            }
+
+            pub fn as_bytes(&self) -> &[u8; N] {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> [u8; N] {
+                self.0
+            }
+        }
+
+        impl<const N: usize> From<[u8; N]> for $Type0<N> {
+            fn from(bytes: [u8; N]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl<const N: usize> AsRef<[u8]> for $Type0<N> {
+            fn as_ref(&self) -> &[u8] {
+                self.0.as_ref()
+            }
+        }
+
+        impl<const N: usize> Debug for $Type0<N> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}<{}>(", stringify!($Type0), N)?;
+                self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))?;
+                write!(f, ")")
+            }
+        }
+    };
+}
+
+/// The `N` bytes nonce of the AEAD, e.g. the `Nonce<12>` of the AES-GCM and ChaCha20-Poly1305
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Nonce<const N: usize>([u8; N]);
+
+/// The `N` bytes initialization vector of the block cipher modes, e.g. the `Iv<16>` of the AES-CBC
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Iv<const N: usize>([u8; N]);
+
+/// The `N` bytes authentication tag, the `==` is constant-time
+#[derive(Clone, Copy)]
+pub struct Tag<const N: usize>([u8; N]);
+
+impl_fixed_bytes!(Nonce, "nonce");
+impl_fixed_bytes!(Iv, "IV");
+impl_fixed_bytes!(Tag, "tag");
+
+fn random_bytes<R: Source<u32>, const N: usize>(rd: &mut R) -> Result<[u8; N], CryptoError> {
+    let mut b = [0u8; N];
+    for chunk in b.chunks_mut(4) {
+        let r = rd.gen().map_err(|e| CryptoError::new(CryptoErrorKind::RandError, e))?.to_be_bytes();
+        chunk.copy_from_slice(&r[..chunk.len()]);
+    }
+    Ok(b)
+}
+
+impl<const N: usize> Nonce<N> {
+    /// the random nonce, the nonce of the 96 bits or less should not be random under the long-lived key, the
+    /// collision probability reaches the $2^{-32}$ after the $2^{32}$ nonces(SP 800-38D 8.3), use the
+    /// `NonceSequence` instead.
+    pub fn random<R: Source<u32>>(rd: &mut R) -> Result<Self, CryptoError> {
+        random_bytes(rd).map(Self)
+    }
+}
+
+impl<const N: usize> Iv<N> {
+    /// the unpredictable IV of the CBC/CFB(SP 800-38A Appendix C)
+    pub fn random<R: Source<u32>>(rd: &mut R) -> Result<Self, CryptoError> {
+        random_bytes(rd).map(Self)
+    }
+}
+
+impl<const N: usize> Tag<N> {
+    /// the constant-time comparison with the `tag` of any length, the different lengths are not equal
+    pub fn ct_eq(&self, tag: &[u8]) -> bool {
+        tag.len() == N && self.0.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) == 0
+    }
+}
+
+impl<const N: usize> PartialEq for Tag<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other.0.as_ref())
+    }
+}
+
+impl<const N: usize> Eq for Tag<N> {}
\ No newline at end of file
@@ -0,0 +1,23 @@
+//! AEAD
+//!
+//! STREAM: Online Authenticated-Encryption and its Nonce-Reuse Misuse-Resistance
+//! https://eprint.iacr.org/2015/189.pdf
+//!
+//! The nonce sequence for the record protocols(TLS-like)
+//!
+//! The fixed length `Nonce`, `Iv` and `Tag`
+
+mod fixed_bytes;
+pub use fixed_bytes::{Nonce, Iv, Tag};
+
+mod stream;
+pub use stream::{EncryptingWriter, DecryptingReader};
+
+mod nonce_sequence;
+pub use nonce_sequence::{NonceSequence, CounterNonceSequence, SealingKey, OpeningKey};
+
+#[cfg(all(test, feature = "sha2"))]
+mod stream_test;
+
+#[cfg(all(test, feature = "sha2"))]
+mod nonce_sequence_test;
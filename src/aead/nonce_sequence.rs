@@ -0,0 +1,165 @@
+//! The nonce sequence for the record protocols
+//!
+//! The `SealingKey`/`OpeningKey` take the nonce of each record from the `NonceSequence`, so the caller
+//! cannot pass the same nonce twice under one key by mistake.
+//!
+//! `CounterNonceSequence`: nonce = base ⊕ (0...0 || counter), where the `counter` is the big-endian record
+//! sequence number which is started from 0,
+//! - fixed prefix(RFC 5116 3.2, RFC 5288): base = prefix || 0...0, i.e. nonce = prefix || counter;
+//! - TLS 1.3(RFC 8446 5.3): base = the per-connection IV, the counter is 64-bit;
+
+use crate::{AEAD, CryptoError, CryptoErrorKind};
+
+const MAX_COUNTER_LEN: usize = 8;
+
+/// A sequence of the unique nonces
+pub trait NonceSequence {
+    /// output the nonce of the next record to the `nonce`. The same nonce must never be output twice,
+    /// an error need to be returned if the sequence is exhausted.
+    fn advance(&mut self, nonce: &mut Vec<u8>) -> Result<(), CryptoError>;
+}
+
+/// The counter based nonce sequence
+#[derive(Clone)]
+pub struct CounterNonceSequence {
+    base: Vec<u8>,
+    counter_len: usize,
+    counter: u64,
+    is_exhausted: bool,
+}
+
+impl CounterNonceSequence {
+    /// nonce = `prefix || counter`, the counter is `min(nonce_len - prefix.len(), 8)` bytes, and the
+    /// bytes between the prefix and the counter are 0;
+    pub fn new(prefix: &[u8], nonce_len: usize) -> Result<Self, CryptoError> {
+        if prefix.len() >= nonce_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The nonce prefix must be shorter than the nonce length {} in bytes", nonce_len)));
+        }
+
+        let mut base = prefix.to_vec();
+        base.resize(nonce_len, 0);
+        Ok(Self {
+            base,
+            counter_len: std::cmp::min(nonce_len - prefix.len(), MAX_COUNTER_LEN),
+            counter: 0,
+            is_exhausted: false,
+        })
+    }
+
+    /// nonce = `iv ⊕ (0...0 || counter)` with the 64-bit counter as the TLS 1.3 record, the `iv` must be
+    /// at least 8 bytes;
+    pub fn tls13(iv: &[u8]) -> Result<Self, CryptoError> {
+        if iv.len() < MAX_COUNTER_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The IV length must be great than or equal to {} in bytes", MAX_COUNTER_LEN)));
+        }
+
+        Ok(Self {
+            base: iv.to_vec(),
+            counter_len: MAX_COUNTER_LEN,
+            counter: 0,
+            is_exhausted: false,
+        })
+    }
+
+    /// the sequence number of the next record
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// the maximum count of the records
+    fn max_counter(&self) -> u64 {
+        if self.counter_len >= MAX_COUNTER_LEN {
+            u64::MAX
+        } else {
+            (1u64 << (self.counter_len << 3)) - 1
+        }
+    }
+}
+
+impl NonceSequence for CounterNonceSequence {
+    fn advance(&mut self, nonce: &mut Vec<u8>) -> Result<(), CryptoError> {
+        if self.is_exhausted {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The nonce sequence is exhausted"));
+        }
+
+        nonce.clear();
+        nonce.extend_from_slice(self.base.as_slice());
+        let len = nonce.len();
+        nonce[(len - self.counter_len)..].iter_mut().zip(self.counter.to_be_bytes()[(MAX_COUNTER_LEN - self.counter_len)..].iter())
+            .for_each(|(a, &b)| *a ^= b);
+
+        if self.counter == self.max_counter() {
+            self.is_exhausted = true;
+        } else {
+            self.counter += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The AEAD key which seals the records with the nonces from the `NonceSequence` in order
+pub struct SealingKey<A: AEAD, N: NonceSequence> {
+    aead: A,
+    nonces: N,
+    nonce: Vec<u8>,
+}
+
+impl<A: AEAD, N: NonceSequence> SealingKey<A, N> {
+    pub fn new(aead: A, nonces: N) -> Self {
+        Self {
+            nonce: Vec::with_capacity(aead.nonce_len()),
+            aead,
+            nonces,
+        }
+    }
+
+    /// seal the next record, the `ciphertext||tag` will output to the `dst`
+    pub fn seal(&mut self, dst: &mut Vec<u8>, aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        advance_nonce(&self.aead, &mut self.nonces, &mut self.nonce)?;
+        self.aead.seal(dst, self.nonce.as_slice(), aad, plaintext)
+    }
+
+    pub fn nonce_sequence(&self) -> &N {
+        &self.nonces
+    }
+}
+
+/// The AEAD key which opens the records with the nonces from the `NonceSequence` in order.
+/// The nonce is consumed even if the record failed to open, the connection should be closed in that case.
+pub struct OpeningKey<A: AEAD, N: NonceSequence> {
+    aead: A,
+    nonces: N,
+    nonce: Vec<u8>,
+}
+
+impl<A: AEAD, N: NonceSequence> OpeningKey<A, N> {
+    pub fn new(aead: A, nonces: N) -> Self {
+        Self {
+            nonce: Vec::with_capacity(aead.nonce_len()),
+            aead,
+            nonces,
+        }
+    }
+
+    /// open the next record, nothing will output to the `dst` if the verification failed
+    pub fn open(&mut self, dst: &mut Vec<u8>, aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        advance_nonce(&self.aead, &mut self.nonces, &mut self.nonce)?;
+        self.aead.open(dst, self.nonce.as_slice(), aad, ciphertext)
+    }
+
+    pub fn nonce_sequence(&self) -> &N {
+        &self.nonces
+    }
+}
+
+fn advance_nonce<A: AEAD, N: NonceSequence>(aead: &A, nonces: &mut N, nonce: &mut Vec<u8>) -> Result<(), CryptoError> {
+    nonces.advance(nonce)?;
+    if nonce.len() != aead.nonce_len() {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("Wrong nonce length: {}, the AEAD nonce length must be {} in bytes", nonce.len(), aead.nonce_len())))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::aead::{NonceSequence, CounterNonceSequence, SealingKey, OpeningKey};
+use crate::aead::stream_test::TestAEAD;
+
+#[test]
+fn counter_nonce_sequence() {
+    let mut nonce = Vec::new();
+    let mut ns = CounterNonceSequence::new(&[0xaa, 0xbb, 0xcc, 0xdd], 12).unwrap();
+    ns.advance(&mut nonce).unwrap();
+    assert_eq!(nonce, vec![0xaa, 0xbb, 0xcc, 0xdd, 0, 0, 0, 0, 0, 0, 0, 0]);
+    ns.advance(&mut nonce).unwrap();
+    assert_eq!(nonce, vec![0xaa, 0xbb, 0xcc, 0xdd, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(ns.counter(), 2);
+    
+    // RFC 8446 Appendix B style: iv ⊕ seq
+    let iv = [0x5du8, 0x31, 0x3e, 0xb2, 0x67, 0x12, 0x76, 0xee, 0x13, 0x00, 0x0b, 0x30];
+    let mut ns = CounterNonceSequence::tls13(iv.as_ref()).unwrap();
+    ns.advance(&mut nonce).unwrap();
+    assert_eq!(nonce, iv.to_vec());
+    ns.advance(&mut nonce).unwrap();
+    assert_eq!(nonce, vec![0x5d, 0x31, 0x3e, 0xb2, 0x67, 0x12, 0x76, 0xee, 0x13, 0x00, 0x0b, 0x31]);
+    
+    // the 1 byte counter is exhausted after 256 nonces
+    let mut ns = CounterNonceSequence::new(&[1u8; 11], 12).unwrap();
+    let mut nonces = Vec::new();
+    for _ in 0..256 {
+        ns.advance(&mut nonce).unwrap();
+        nonces.push(nonce.clone());
+    }
+    assert!(ns.advance(&mut nonce).is_err());
+    assert!(ns.advance(&mut nonce).is_err());
+    nonces.sort();
+    nonces.dedup();
+    assert_eq!(nonces.len(), 256);
+    
+    assert!(CounterNonceSequence::new(&[0u8; 12], 12).is_err());
+    assert!(CounterNonceSequence::tls13(&[0u8; 7]).is_err());
+}
+
+#[test]
+fn sealing_opening_key() {
+    let aead = TestAEAD { key: b"record test key".to_vec() };
+    let prefix = [7u8; 4];
+    let mut sk = SealingKey::new(aead.clone(), CounterNonceSequence::new(prefix.as_ref(), 12).unwrap());
+    let mut ok = OpeningKey::new(aead.clone(), CounterNonceSequence::new(prefix.as_ref(), 12).unwrap());
+    
+    let (mut records, mut ct, mut pt) = (Vec::new(), Vec::new(), Vec::new());
+    for i in 0..4u8 {
+        sk.seal(&mut ct, &[0x17, i], vec![i; i as usize * 10].as_slice()).unwrap();
+        records.push(ct.clone());
+    }
+    assert_eq!(sk.nonce_sequence().counter(), 4);
+    // the same plaintext is sealed with the different nonces
+    sk.seal(&mut ct, &[], &[]).unwrap();
+    let ct0 = ct.clone();
+    sk.seal(&mut ct, &[], &[]).unwrap();
+    assert_ne!(ct0, ct);
+    
+    for (i, r) in records.iter().enumerate() {
+        ok.open(&mut pt, &[0x17, i as u8], r.as_slice()).unwrap();
+        assert_eq!(pt, vec![i as u8; i * 10], "case: {}", i);
+    }
+    
+    // the replayed or reordered record fails to open
+    assert!(ok.open(&mut pt, &[0x17, 0], records[0].as_slice()).is_err());
+    
+    // the nonce length not match
+    let mut sk = SealingKey::new(aead, CounterNonceSequence::new(prefix.as_ref(), 16).unwrap());
+    assert!(sk.seal(&mut ct, &[], &[]).is_err());
+}
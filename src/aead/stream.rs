@@ -0,0 +1,210 @@
+//! STREAM construction
+//!
+//! The plaintext is split into chunks with the same size(the last chunk may be shorter or empty),
+//! each chunk is sealed by the AEAD with the nonce `nonce_prefix || counter || last_flag`, where
+//! `counter` is the 32-bit big-endian chunk index and `last_flag` is `1` only for the last chunk.
+//! So any truncation, reordering or splicing of the chunks will fail to open.
+//!
+//! The length of `nonce_prefix` is `AEAD::nonce_len() - 5` bytes, it must be unique for each stream under
+//! the same key, and the caller need to transmit it alongside the stream.
+
+use crate::{AEAD, CryptoError, CryptoErrorKind};
+use std::io::{Read, Write};
+
+const STREAM_COUNTER_LEN: usize = 4;
+const STREAM_FLAG_LEN: usize = 1;
+
+struct StreamNonce {
+    nonce: Vec<u8>,
+    counter: u32,
+    is_exhausted: bool,
+}
+
+impl StreamNonce {
+    fn new<A: AEAD>(aead: &A, nonce_prefix: &[u8]) -> Result<Self, CryptoError> {
+        let nonce_len = aead.nonce_len();
+        if nonce_len < (STREAM_COUNTER_LEN + STREAM_FLAG_LEN) || nonce_prefix.len() != (nonce_len - STREAM_COUNTER_LEN - STREAM_FLAG_LEN) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The length of nonce prefix must be {} in bytes", nonce_len.saturating_sub(STREAM_COUNTER_LEN + STREAM_FLAG_LEN))))
+        } else {
+            let mut nonce = Vec::with_capacity(nonce_len);
+            nonce.extend_from_slice(nonce_prefix);
+            nonce.resize(nonce_len, 0);
+            Ok(Self {
+                nonce,
+                counter: 0,
+                is_exhausted: false,
+            })
+        }
+    }
+
+    /// return the nonce of current chunk, and then move to the next chunk
+    fn next(&mut self, is_last: bool) -> Result<&[u8], CryptoError> {
+        if self.is_exhausted {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The STREAM chunk counter overflowed"));
+        }
+
+        let (prefix_len, len) = (self.nonce.len() - STREAM_COUNTER_LEN - STREAM_FLAG_LEN, self.nonce.len());
+        self.nonce[prefix_len..(prefix_len + STREAM_COUNTER_LEN)].copy_from_slice(self.counter.to_be_bytes().as_ref());
+        self.nonce[len - 1] = is_last as u8;
+
+        match self.counter.checked_add(1) {
+            Some(c) => self.counter = c,
+            None => self.is_exhausted = true,
+        }
+
+        Ok(self.nonce.as_slice())
+    }
+}
+
+fn into_io_err(e: CryptoError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+}
+
+/// The STREAM encryptor, `finish` must be called to write the last chunk, otherwise the
+/// stream will be treated as truncated by the `DecryptingReader`.
+pub struct EncryptingWriter<W: Write, A: AEAD> {
+    writer: W,
+    aead: A,
+    nonce: StreamNonce,
+    chunk_size: usize,
+    buf: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl<W: Write, A: AEAD> EncryptingWriter<W, A> {
+    /// `chunk_size`: the length in bytes of the plaintext chunk, it must be great than 0;
+    pub fn new(writer: W, aead: A, nonce_prefix: &[u8], chunk_size: usize) -> Result<Self, CryptoError> {
+        if chunk_size == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The chunk size must be great than 0"));
+        }
+
+        let nonce = StreamNonce::new(&aead, nonce_prefix)?;
+        Ok(Self {
+            writer,
+            nonce,
+            chunk_size,
+            buf: Vec::with_capacity(chunk_size),
+            ciphertext: Vec::with_capacity(chunk_size + aead.tag_len()),
+            aead,
+        })
+    }
+
+    fn seal_chunk(&mut self, is_last: bool) -> Result<(), CryptoError> {
+        let len = std::cmp::min(self.chunk_size, self.buf.len());
+        let nonce = self.nonce.next(is_last)?;
+        self.aead.seal(&mut self.ciphertext, nonce, &[], &self.buf[..len])?;
+        self.buf.drain(..len);
+
+        self.writer.write_all(self.ciphertext.as_slice()).map_err(|e| {
+            CryptoError::new(CryptoErrorKind::OuterErr, e)
+        })
+    }
+
+    /// seal the last chunk and return the inner writer
+    pub fn finish(mut self) -> Result<W, CryptoError> {
+        self.seal_chunk(true)?;
+        self.writer.flush().map_err(|e| {
+            CryptoError::new(CryptoErrorKind::OuterErr, e)
+        })?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write, A: AEAD> Write for EncryptingWriter<W, A> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        // the last full chunk is hold back until more data arrived, because it may be the last chunk
+        while self.buf.len() > self.chunk_size {
+            self.seal_chunk(false).map_err(into_io_err)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// The STREAM decryptor, an error with `std::io::ErrorKind::InvalidData` will be returned
+/// if any chunk failed to verify or the stream is truncated.
+pub struct DecryptingReader<R: Read, A: AEAD> {
+    reader: R,
+    aead: A,
+    nonce: StreamNonce,
+    chunk_size: usize,
+    buf: Vec<u8>,
+    plaintext: Vec<u8>,
+    pos: usize,
+    is_eof: bool,
+    is_finish: bool,
+}
+
+impl<R: Read, A: AEAD> DecryptingReader<R, A> {
+    /// `chunk_size`: the length in bytes of the plaintext chunk, it need to be the same as the `EncryptingWriter`;
+    pub fn new(reader: R, aead: A, nonce_prefix: &[u8], chunk_size: usize) -> Result<Self, CryptoError> {
+        if chunk_size == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The chunk size must be great than 0"));
+        }
+
+        let nonce = StreamNonce::new(&aead, nonce_prefix)?;
+        Ok(Self {
+            reader,
+            nonce,
+            chunk_size,
+            buf: Vec::with_capacity(chunk_size + aead.tag_len() + 1),
+            plaintext: Vec::with_capacity(chunk_size),
+            pos: 0,
+            is_eof: false,
+            is_finish: false,
+            aead,
+        })
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn open_chunk(&mut self) -> std::io::Result<()> {
+        let ct_len = self.chunk_size + self.aead.tag_len();
+
+        // read one more byte to determine whether the current chunk is the last chunk or not
+        let mut tmp = [0u8; 4096];
+        while !self.is_eof && self.buf.len() <= ct_len {
+            let want = std::cmp::min(tmp.len(), ct_len + 1 - self.buf.len());
+            match self.reader.read(&mut tmp[..want]) {
+                Ok(0) => self.is_eof = true,
+                Ok(n) => self.buf.extend_from_slice(&tmp[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(e),
+            }
+        }
+
+        let is_last = self.buf.len() <= ct_len;
+        let len = std::cmp::min(ct_len, self.buf.len());
+        let nonce = self.nonce.next(is_last).map_err(into_io_err)?;
+        self.aead.open(&mut self.plaintext, nonce, &[], &self.buf[..len]).map_err(into_io_err)?;
+        self.buf.drain(..len);
+        self.pos = 0;
+        self.is_finish = is_last;
+
+        Ok(())
+    }
+}
+
+impl<R: Read, A: AEAD> Read for DecryptingReader<R, A> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos >= self.plaintext.len() {
+            if self.is_finish {
+                return Ok(0);
+            }
+            self.open_chunk()?;
+        }
+
+        let len = std::cmp::min(buf.len(), self.plaintext.len() - self.pos);
+        buf[..len].copy_from_slice(&self.plaintext[self.pos..(self.pos + len)]);
+        self.pos += len;
+        Ok(len)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::aead::{EncryptingWriter, DecryptingReader};
+use crate::{AEAD, CryptoError, CryptoErrorKind, HMAC, Digest};
+use crate::sha::SHA256;
+use std::io::{Read, Write};
+
+/// Encrypt-then-MAC over HMAC-SHA256 which is only used to test the STREAM framing
+#[derive(Clone)]
+pub(super) struct TestAEAD {
+    pub(super) key: Vec<u8>,
+}
+
+impl TestAEAD {
+    fn keystream(&self, nonce: &[u8], len: usize) -> Vec<u8> {
+        let (mut ks, mut block) = (Vec::with_capacity(len + 32), Vec::new());
+        let mut hmac = HMAC::new(self.key.clone(), SHA256::new()).unwrap();
+        let mut i = 0u32;
+        while ks.len() < len {
+            hmac.reset();
+            hmac.write(nonce);
+            hmac.write(i.to_be_bytes().as_ref());
+            hmac.checksum(&mut block);
+            ks.extend_from_slice(block.as_slice());
+            i += 1;
+        }
+        ks.truncate(len);
+        ks
+    }
+
+    fn tag(&self, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let mut tag = Vec::new();
+        let mut hmac = HMAC::new(self.key.clone(), SHA256::new()).unwrap();
+        hmac.write(&[0xffu8]);
+        hmac.write(nonce);
+        hmac.write((aad.len() as u64).to_be_bytes().as_ref());
+        hmac.write(aad);
+        hmac.write(ciphertext);
+        hmac.checksum(&mut tag);
+        tag.truncate(self.tag_len());
+        tag
+    }
+}
+
+impl AEAD for TestAEAD {
+    fn nonce_len(&self) -> usize {
+        12
+    }
+
+    fn tag_len(&self) -> usize {
+        16
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        let ks = self.keystream(nonce, plaintext.len());
+        dst.extend(plaintext.iter().zip(ks.iter()).map(|(&a, &b)| a ^ b));
+        let tag = self.tag(nonce, aad, dst.as_slice());
+        dst.extend(tag);
+        Ok(())
+    }
+
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if ciphertext.len() < self.tag_len() {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "too short"));
+        }
+        let (ct, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len());
+        if self.tag(nonce, aad, ct).as_slice() != tag {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "tag not match"));
+        }
+        let ks = self.keystream(nonce, ct.len());
+        dst.extend(ct.iter().zip(ks.iter()).map(|(&a, &b)| a ^ b));
+        Ok(())
+    }
+}
+
+fn stream_encrypt(aead: &TestAEAD, prefix: &[u8], chunk_size: usize, msg: &[u8]) -> Vec<u8> {
+    let mut w = EncryptingWriter::new(Vec::new(), aead.clone(), prefix, chunk_size).unwrap();
+    // write with an odd step to cross the chunk boundary
+    for m in msg.chunks(7) {
+        w.write_all(m).unwrap();
+    }
+    w.finish().unwrap()
+}
+
+fn stream_decrypt(aead: &TestAEAD, prefix: &[u8], chunk_size: usize, ct: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut r = DecryptingReader::new(ct, aead.clone(), prefix, chunk_size).unwrap();
+    let mut buf = Vec::new();
+    r.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+#[test]
+fn stream_round_trip() {
+    let aead = TestAEAD { key: b"stream test key".to_vec() };
+    let prefix = [7u8; 7];
+    let chunk_size = 32;
+
+    for &len in [0usize, 1, 31, 32, 33, 64, 100, 1000].iter() {
+        let msg = (0..len).map(|x| x as u8).collect::<Vec<_>>();
+        let ct = stream_encrypt(&aead, prefix.as_ref(), chunk_size, msg.as_slice());
+        let chunks = if len == 0 { 1 } else { len.div_ceil(chunk_size) };
+        assert_eq!(ct.len(), len + chunks * aead.tag_len(), "case: {}", len);
+
+        let pt = stream_decrypt(&aead, prefix.as_ref(), chunk_size, ct.as_slice()).unwrap();
+        assert_eq!(pt, msg, "case: {}", len);
+    }
+}
+
+#[test]
+fn stream_tamper() {
+    let aead = TestAEAD { key: b"stream test key".to_vec() };
+    let prefix = [1u8; 7];
+    let chunk_size = 16;
+    let ct_chunk = chunk_size + aead.tag_len();
+    let msg = (0..80u8).collect::<Vec<_>>();
+    let ct = stream_encrypt(&aead, prefix.as_ref(), chunk_size, msg.as_slice());
+    assert_eq!(ct.len(), 5 * ct_chunk);
+
+    // truncated at the chunk boundary
+    let e = stream_decrypt(&aead, prefix.as_ref(), chunk_size, &ct[..(4 * ct_chunk)]).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+
+    // truncated in the chunk
+    assert!(stream_decrypt(&aead, prefix.as_ref(), chunk_size, &ct[..(ct.len() - 1)]).is_err());
+
+    // reordering
+    let mut reorder = ct.clone();
+    reorder[..ct_chunk].copy_from_slice(&ct[ct_chunk..(2 * ct_chunk)]);
+    reorder[ct_chunk..(2 * ct_chunk)].copy_from_slice(&ct[..ct_chunk]);
+    assert!(stream_decrypt(&aead, prefix.as_ref(), chunk_size, reorder.as_slice()).is_err());
+
+    // appending
+    let mut append = ct.clone();
+    append.extend_from_slice(&ct[..ct_chunk]);
+    assert!(stream_decrypt(&aead, prefix.as_ref(), chunk_size, append.as_slice()).is_err());
+
+    // flipping
+    let mut flip = ct.clone();
+    flip[ct_chunk + 3] ^= 1;
+    assert!(stream_decrypt(&aead, prefix.as_ref(), chunk_size, flip.as_slice()).is_err());
+
+    // wrong nonce prefix
+    assert!(stream_decrypt(&aead, [2u8; 7].as_ref(), chunk_size, ct.as_slice()).is_err());
+
+    // invalid nonce prefix length
+    assert!(EncryptingWriter::new(Vec::new(), aead.clone(), [0u8; 8].as_ref(), chunk_size).is_err());
+}
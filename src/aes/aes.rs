@@ -43,6 +43,26 @@ impl AES {
     }
 }
 
+#[cfg(feature = "keyschedule")]
+impl AES {
+    /// the expanded encryption round keys `w[0..4*(Nr+1)]`(FIPS 197 5.2) in bytes, every round key of the 16 bytes is
+    /// in the same byte order as the state. They're as secret as the key, e.g. they can be cached in the shared memory
+    /// of the worker processes.
+    pub fn round_keys(&self) -> Vec<u8> {
+        self.enc_round_keys()
+    }
+
+    /// the AES by the pre-expanded encryption round keys which are exported by the `round_keys`, `rk` must have a valid
+    /// length in bytes(AES-128: 176, AES-192: 208, AES-256: 240), the decryption round keys are derived from the `rk`.
+    pub fn from_round_keys(rk: &[u8]) -> std::result::Result<Self, CryptoError> {
+        match rk.len() {
+            176 | 208 | 240 => Ok(Self::from_enc_round_keys(rk, rk.len() / AES_BLOCK_SIZE - 1)),
+            _ => Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                                      format!("Wrong round keys length: {}, the AES round keys length(in bytes) only can be the 176/208/240", rk.len())))
+        }
+    }
+}
+
 impl Cipher for AES {
     type Output = usize;
     
@@ -124,6 +144,47 @@ mod tests {
         assert!(AES::new(vec![0,0,1,2,3]).is_err());
     }
 
+    #[cfg(feature = "keyschedule")]
+    #[test]
+    fn aes_round_keys() {
+        // Appendix A.1, A.2, A.3
+        let cases: [(Vec<u8>, [u8; 4], [u8; 4]); 3] = [
+            (
+                vec![0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c],
+                [0xa0, 0xfa, 0xfe, 0x17], [0xb6, 0x63, 0x0c, 0xa6],
+            ),
+            (
+                vec![0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79, 0xe5,
+                    0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b],
+                [0xfe, 0x0c, 0x91, 0xf7], [0x01, 0x00, 0x22, 0x02],
+            ),
+            (
+                vec![0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81,
+                    0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4],
+                [0x9b, 0xa3, 0x54, 0x11], [0x70, 0x6c, 0x63, 0x1e],
+            ),
+        ];
+
+        for (key, first, last) in cases.iter() {
+            let cipher = AES::new(key.clone()).unwrap();
+            let rk = cipher.round_keys();
+            assert_eq!(rk.len(), (cipher.nr + 1) * 16);
+            assert_eq!(&rk[..key.len()], key.as_slice());
+            assert_eq!(&rk[key.len()..(key.len() + 4)], first.as_ref());
+            assert_eq!(&rk[(rk.len() - 4)..], last.as_ref());
+
+            let other = AES::from_round_keys(rk.as_slice()).unwrap();
+            let (pt, mut ct, mut buf) = ([0x5au8; 16], Vec::new(), Vec::new());
+            cipher.encrypt(&mut ct, pt.as_ref()).unwrap();
+            other.encrypt(&mut buf, pt.as_ref()).unwrap();
+            assert_eq!(ct, buf);
+            other.decrypt(&mut buf, ct.as_slice()).unwrap();
+            assert_eq!(buf.as_slice(), pt.as_ref());
+        }
+
+        assert!(AES::from_round_keys(&[0u8; 160]).is_err());
+    }
+
     #[test]
     fn aes128() {
         let cases = [
@@ -14,6 +14,9 @@ use core::arch::x86_64 as march;
 use std::intrinsics::transmute;
 use crate::aes::aes::AES_BLOCK_SIZE;
 
+/// the backend name which is reported by the `capability` report
+pub(crate) const AES_BACKEND: &str = "AES-NI";
+
 #[derive(Clone)]
 pub struct AES {
     // big endian
@@ -112,8 +115,11 @@ impl AES {
 
     #[target_feature(enable = "aes", enable = "sse2")]
     unsafe fn key_schedule_192(key: &[u8], enc: &mut Vec<march::__m128i>) {
-        let mut temp1 = march::_mm_loadu_si128(transmute(key.as_ptr()));
-        let mut temp3= march::_mm_loadu_si128(transmute(key.as_ptr().offset(16)));
+        // the second 128 bits load must not read over the end of the 24 bytes key, only its lower 64 bits are used
+        let mut buf = [0u8; 32];
+        buf[..24].copy_from_slice(&key[..24]);
+        let mut temp1 = march::_mm_loadu_si128(transmute::<*const u8, *const march::__m128i>(buf.as_ptr()));
+        let mut temp3= march::_mm_loadu_si128(transmute::<*const u8, *const march::__m128i>(buf.as_ptr().offset(16)));
         enc.push(temp1);
         let key_temp= temp3;
         let mut temp2= march::_mm_aeskeygenassist_si128 (temp3,0x1);
@@ -255,6 +261,34 @@ impl AES {
         }
     }
 
+    /// the encryption round keys in bytes
+    #[cfg(feature = "keyschedule")]
+    pub(super) fn enc_round_keys(&self) -> Vec<u8> {
+        let mut rk = vec![0u8; self.enc_ks.len() * AES_BLOCK_SIZE];
+        rk.chunks_exact_mut(AES_BLOCK_SIZE).zip(self.enc_ks.iter()).for_each(|(r, &e)| unsafe {
+            march::_mm_storeu_si128(r.as_mut_ptr() as *mut march::__m128i, e);
+        });
+        rk
+    }
+
+    /// `rk` is the `(nr + 1) * 16` bytes encryption round keys
+    #[cfg(feature = "keyschedule")]
+    pub(super) fn from_enc_round_keys(rk: &[u8], nr: usize) -> Self {
+        let (mut enc_ks, mut dec_ks) = (Vec::with_capacity(nr+1), Vec::with_capacity(nr+1));
+        unsafe {
+            rk.chunks_exact(AES_BLOCK_SIZE).for_each(|r| {
+                enc_ks.push(march::_mm_loadu_si128(r.as_ptr() as *const march::__m128i));
+            });
+            Self::gen_dec_key(&enc_ks, nr, &mut dec_ks);
+        }
+
+        AES {
+            enc_ks,
+            dec_ks,
+            nr,
+        }
+    }
+
     pub fn aes_128(key: [u8; 16]) -> Self {
         let nr = 10;
         let (mut enc_ks, mut dec_ks) = (Vec::with_capacity(nr+1), Vec::with_capacity(nr+1));
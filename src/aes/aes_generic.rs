@@ -1,6 +1,9 @@
 use crate::aes::const_tables as mct;
 use crate::crypto_err::{CryptoError, CryptoErrorKind};
 
+/// the backend name which is reported by the `capability` report
+pub(crate) const AES_BACKEND: &str = "generic";
+
 #[derive(Clone)]
 pub struct AES {
     // big endian
@@ -54,6 +57,13 @@ impl AES {
             enc[i] = enc[i - nk] ^ t;
         });
 
+        Self::dec_key_schedule(enc, dec);
+    }
+
+    /// the round keys of the equivalent inverse cipher
+    fn dec_key_schedule(enc: &[u32], dec: &mut Vec<u32>) {
+        let n = enc.len();
+        dec.clear();
         dec.resize(n, 0);
         let mut i = 0;
         while i < n {
@@ -73,6 +83,27 @@ impl AES {
         }
     }
 
+    /// the encryption round keys in bytes
+    #[cfg(feature = "keyschedule")]
+    pub(super) fn enc_round_keys(&self) -> Vec<u8> {
+        let mut rk = Vec::with_capacity(self.enc_ks.len() << 2);
+        self.enc_ks.iter().for_each(|w| rk.extend_from_slice(w.to_be_bytes().as_ref()));
+        rk
+    }
+
+    /// `rk` is the `(nr + 1) * 16` bytes encryption round keys
+    #[cfg(feature = "keyschedule")]
+    pub(super) fn from_enc_round_keys(rk: &[u8], nr: usize) -> Self {
+        let enc_ks = rk.chunks_exact(4).map(|w| u32::from_be_bytes([w[0], w[1], w[2], w[3]])).collect::<Vec<_>>();
+        let mut dec_ks = Vec::with_capacity(enc_ks.len());
+        Self::dec_key_schedule(enc_ks.as_slice(), &mut dec_ks);
+        Self {
+            enc_ks,
+            dec_ks,
+            nr,
+        }
+    }
+
     pub(super) fn crypt_block(&self, dst: &mut Vec<u8>, pb: &[u8]) {
         let (mut s, mut itr) = ([0u32; 4], pb.iter());
         s.iter_mut().for_each(|a| {
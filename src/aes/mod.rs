@@ -21,12 +21,16 @@ mod const_tables;
 mod aes_generic;
 #[cfg(not(all(rcrypto_aes = "support", any(target_arch = "x86", target_arch = "x86_64"))))]
 pub use aes_generic::AES;
+#[cfg(not(all(rcrypto_aes = "support", any(target_arch = "x86", target_arch = "x86_64"))))]
+pub(crate) use aes_generic::AES_BACKEND;
 
 
 #[cfg(all(rcrypto_aes = "support", any(target_arch = "x86", target_arch = "x86_64")))]
 mod aes_amd64;
 #[cfg(all(rcrypto_aes = "support", any(target_arch = "x86", target_arch = "x86_64")))]
 pub use aes_amd64::AES;
+#[cfg(all(rcrypto_aes = "support", any(target_arch = "x86", target_arch = "x86_64")))]
+pub(crate) use aes_amd64::AES_BACKEND;
 
 mod aes;
 
@@ -0,0 +1,113 @@
+use crate::Digest;
+#[cfg(feature = "parallel")]
+use crate::{CryptoError, CryptoErrorKind};
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// the minimum total length(in bytes) of the messages which are hashed by the worker threads
+pub const BATCH_PARALLEL_MIN_LEN: usize = 256 << 10;
+
+/// the messages which are taken by the worker at once
+#[cfg(feature = "parallel")]
+const BATCH_TAKE_LEN: usize = 16;
+
+fn checksum_of<D: Digest>(md: &mut D, message: &[u8]) -> Vec<u8> {
+    let mut checksum = Vec::with_capacity((md.bits_len() + 7) >> 3);
+    md.reset();
+    md.write(message);
+    md.checksum(&mut checksum);
+    checksum
+}
+
+fn digest_sequential<D: Digest + Clone, M: AsRef<[u8]>>(md: &D, messages: &[M]) -> Vec<Vec<u8>> {
+    let mut md = md.clone();
+    messages.iter().map(|m| checksum_of(&mut md, m.as_ref())).collect()
+}
+
+/// hash the `messages` by the `threads` worker threads, the messages are taken by the workers in the small groups,
+/// so the long and short messages are balanced between the workers.
+#[cfg(feature = "parallel")]
+fn digest_threads<D: Digest + Clone + Send, M: AsRef<[u8]> + Sync>(md: &D, messages: &[M], threads: usize) -> Vec<Vec<u8>> {
+    let next = AtomicUsize::new(0);
+    let mut checksums = vec![Vec::new(); messages.len()];
+    std::thread::scope(|s| {
+        let workers = (0..threads.min(messages.len())).map(|_| {
+            let (next, mut md) = (&next, md.clone());
+            s.spawn(move || {
+                let mut done = Vec::new();
+                loop {
+                    let start = next.fetch_add(BATCH_TAKE_LEN, Ordering::AcqRel);
+                    if start >= messages.len() {
+                        break done;
+                    }
+                    let end = (start + BATCH_TAKE_LEN).min(messages.len());
+                    (start..end).for_each(|i| done.push((i, checksum_of(&mut md, messages[i].as_ref()))));
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        for w in workers {
+            for (i, c) in w.join().expect("The batch hashing worker panicked") {
+                checksums[i] = c;
+            }
+        }
+    });
+    checksums
+}
+
+/// hash every message by the clone of the `md`, and return the checksums in the order of the `messages`.
+/// The `md` is reset before each message, so it's the prototype of the parameters(e.g. the digest length of the
+/// XOF), the data which has been written to the `md` is ignored.
+#[cfg(not(feature = "parallel"))]
+pub fn digest_many<D, I, M>(md: &D, messages: I) -> Vec<Vec<u8>>
+    where D: Digest + Clone + Send, I: IntoIterator<Item=M>, M: AsRef<[u8]> + Sync {
+    let messages = messages.into_iter().collect::<Vec<_>>();
+    digest_sequential(md, messages.as_slice())
+}
+
+/// hash every message by the clone of the `md`, and return the checksums in the order of the `messages`.
+/// The `md` is reset before each message, so it's the prototype of the parameters(e.g. the digest length of the
+/// XOF), the data which has been written to the `md` is ignored. The batch which is not less than the
+/// `BATCH_PARALLEL_MIN_LEN` bytes is hashed by the worker threads of the `std::thread::available_parallelism`.
+#[cfg(feature = "parallel")]
+pub fn digest_many<D, I, M>(md: &D, messages: I) -> Vec<Vec<u8>>
+    where D: Digest + Clone + Send, I: IntoIterator<Item=M>, M: AsRef<[u8]> + Sync {
+    let messages = messages.into_iter().collect::<Vec<_>>();
+    let len = messages.iter().fold(0usize, |l, m| l.saturating_add(m.as_ref().len()));
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    if threads < 2 || messages.len() < 2 || len < BATCH_PARALLEL_MIN_LEN {
+        digest_sequential(md, messages.as_slice())
+    } else {
+        digest_threads(md, messages.as_slice(), threads)
+    }
+}
+
+/// `digest_many` by the `threads` worker threads no matter how long the batch is
+#[cfg(feature = "parallel")]
+pub fn digest_many_parallel<D, I, M>(md: &D, messages: I, threads: usize) -> Result<Vec<Vec<u8>>, CryptoError>
+    where D: Digest + Clone + Send, I: IntoIterator<Item=M>, M: AsRef<[u8]> + Sync {
+    if threads == 0 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The number of threads must be great than 0"));
+    }
+
+    let messages = messages.into_iter().collect::<Vec<_>>();
+    if threads == 1 {
+        Ok(digest_sequential(md, messages.as_slice()))
+    } else {
+        Ok(digest_threads(md, messages.as_slice(), threads))
+    }
+}
+
+/// compute the tags of the `messages` by the clones of the keyed `mac`(e.g. the `HMAC`, `CMAC`, `KMAC`), see the
+/// `digest_many`. The `reset` of the MAC keeps the key, so all the tags are computed by the same key.
+pub fn mac_many<D, I, M>(mac: &D, messages: I) -> Vec<Vec<u8>>
+    where D: Digest + Clone + Send, I: IntoIterator<Item=M>, M: AsRef<[u8]> + Sync {
+    digest_many(mac, messages)
+}
+
+/// `mac_many` by the `threads` worker threads, see the `digest_many_parallel`
+#[cfg(feature = "parallel")]
+pub fn mac_many_parallel<D, I, M>(mac: &D, messages: I, threads: usize) -> Result<Vec<Vec<u8>>, CryptoError>
+    where D: Digest + Clone + Send, I: IntoIterator<Item=M>, M: AsRef<[u8]> + Sync {
+    digest_many_parallel(mac, messages, threads)
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use crate::batch::{digest_many, mac_many, BATCH_PARALLEL_MIN_LEN};
+use crate::sha::{SHA256, SHA512};
+use crate::{Digest, HMAC};
+
+fn messages() -> Vec<Vec<u8>> {
+    // the short messages and the long messages which exceed the parallel threshold
+    (0..100usize).map(|i| {
+        let len = if i % 10 == 0 { (BATCH_PARALLEL_MIN_LEN >> 3) + i } else { i * 7 };
+        (0..len).map(|j| (i + j) as u8).collect()
+    }).collect()
+}
+
+fn expected<D: Digest>(mut md: D, messages: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    messages.iter().map(|m| {
+        let mut c = Vec::new();
+        md.reset();
+        md.write(m.as_slice());
+        md.checksum(&mut c);
+        c
+    }).collect()
+}
+
+#[test]
+fn batch_digest_mac() {
+    let msgs = messages();
+    assert!(msgs.iter().map(|m| m.len()).sum::<usize>() >= BATCH_PARALLEL_MIN_LEN);
+
+    // the data written to the prototype is ignored
+    let mut md = SHA256::new();
+    md.write(b"ignored");
+    assert_eq!(digest_many(&md, msgs.iter()), expected(SHA256::new(), msgs.as_slice()));
+    assert_eq!(digest_many(&SHA512::new(), vec![b"abc".as_ref()]), expected(SHA512::new(), &[b"abc".to_vec()]));
+    assert!(digest_many(&SHA256::new(), Vec::<Vec<u8>>::new()).is_empty());
+
+    let hmac = HMAC::new(b"batch key".to_vec(), SHA256::new()).unwrap();
+    let tags = mac_many(&hmac, msgs.iter());
+    assert_eq!(tags, expected(hmac.clone(), msgs.as_slice()));
+    assert_ne!(tags, digest_many(&SHA256::new(), msgs.iter()));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn batch_digest_mac_parallel() {
+    use crate::batch::{digest_many_parallel, mac_many_parallel};
+
+    let msgs = messages();
+    let (want, hmac) = (expected(SHA256::new(), msgs.as_slice()), HMAC::new(b"batch key".to_vec(), SHA256::new()).unwrap());
+    let tags = expected(hmac.clone(), msgs.as_slice());
+    for threads in [1usize, 2, 3, 8, 200].iter() {
+        assert_eq!(digest_many_parallel(&SHA256::new(), msgs.iter(), *threads).unwrap(), want);
+        assert_eq!(mac_many_parallel(&hmac, msgs.iter(), *threads).unwrap(), tags);
+    }
+    assert!(digest_many_parallel(&SHA256::new(), msgs.iter(), 0).is_err());
+    assert!(digest_many_parallel(&SHA256::new(), Vec::<Vec<u8>>::new(), 4).unwrap().is_empty());
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+//! The batch hashing and MAC
+//!
+//! `digest_many` and `mac_many` hash the messages by the clones of the prototype digester, and return the checksums
+//! in the order of the messages, e.g. the leaves of the certificate transparency log and the chunks of the dedup index.
+//! With the `parallel` feature, the batch which is not less than the `BATCH_PARALLEL_MIN_LEN` bytes is distributed over
+//! the worker threads of the available parallelism; the small batch and the batch without the `parallel` feature are
+//! hashed sequentially. The checksums don't depend on the number of the threads.
+
+mod batch;
+pub use batch::{digest_many, mac_many, BATCH_PARALLEL_MIN_LEN};
+#[cfg(feature = "parallel")]
+pub use batch::{digest_many_parallel, mac_many_parallel};
+
+#[cfg(all(test, feature = "sha2"))]
+mod batch_test;
\ No newline at end of file
@@ -0,0 +1,25 @@
+use crate::capability::CapabilityReport;
+use std::time::Duration;
+
+#[test]
+fn capability_report() {
+    let report = CapabilityReport::new();
+    assert_eq!(report.version(), env!("CARGO_PKG_VERSION"));
+    assert!(report.throughputs().is_empty());
+    assert_eq!(report.is_feature_enabled("legacy"), cfg!(feature = "legacy"));
+    assert_eq!(report.is_feature_enabled("mlock"), cfg!(feature = "mlock"));
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    assert!(!report.cpu().neon());
+
+    let report = CapabilityReport::with_bench(Duration::from_millis(2));
+    assert_eq!(report.throughputs().len(), report.backends().len());
+    for (b, t) in report.backends().iter().zip(report.throughputs().iter()) {
+        assert_eq!(b.primitive, t.primitive);
+        assert!(t.bytes > 0 && t.elapsed >= Duration::from_millis(2));
+        assert!(t.bytes_per_sec() > 0.0);
+    }
+
+    let s = report.to_string();
+    assert!(s.contains("capability report"));
+    assert!(s.lines().filter(|l| l.contains("[bench]")).count() == report.throughputs().len());
+}
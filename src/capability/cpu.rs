@@ -0,0 +1,62 @@
+/// The CPU features which are relevant to the cryptographic primitives, they're detected at runtime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CpuFeatures {
+    aes_ni: bool,
+    sha_ni: bool,
+    pclmul: bool,
+    neon: bool,
+}
+
+impl CpuFeatures {
+    /// detect the features of the current CPU, the features of the other architectures are false
+    pub fn detect() -> Self {
+        #[allow(unused_mut)]
+        let mut f = Self::default();
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            f.aes_ni = std::is_x86_feature_detected!("aes");
+            f.sha_ni = std::is_x86_feature_detected!("sha");
+            f.pclmul = std::is_x86_feature_detected!("pclmulqdq");
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            f.neon = std::arch::is_aarch64_feature_detected!("neon");
+        }
+
+        f
+    }
+
+    /// the AES new instructions of the x86
+    pub fn aes_ni(&self) -> bool {
+        self.aes_ni
+    }
+
+    /// the SHA extensions of the x86
+    pub fn sha_ni(&self) -> bool {
+        self.sha_ni
+    }
+
+    /// the carry-less multiplication of the x86
+    pub fn pclmul(&self) -> bool {
+        self.pclmul
+    }
+
+    /// the Advanced SIMD of the aarch64
+    pub fn neon(&self) -> bool {
+        self.neon
+    }
+}
+
+impl std::fmt::Display for CpuFeatures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flags = [("aes-ni", self.aes_ni), ("sha-ni", self.sha_ni), ("pclmul", self.pclmul), ("neon", self.neon)];
+        let detected = flags.iter().filter(|x| x.1).map(|x| x.0).collect::<Vec<_>>();
+        if detected.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", detected.join(", "))
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! The runtime capability report
+//!
+//! The `CapabilityReport` records the CPU features which are detected at runtime(AES-NI, SHA-NI, PCLMULQDQ, NEON),
+//! the backends which are compiled into the primitives, the enabled cargo features(the active algorithm policy),
+//! and optionally the throughput of the key primitives which is measured by a quick bench. It can be logged at the
+//! service startup to explain the performance differences across the machines.
+//!
+//! The accelerated backend is selected at the build time(see the `build.rs`), so the backend is reported separately
+//! from the detected CPU features, e.g. the AES-NI backend is not used if the crate is built on the machine without
+//! the AES-NI.
+//!
+//! # Example
+//!
+//! ```Rust
+//! use rcrypto::capability::CapabilityReport;
+//! use std::time::Duration;
+//!
+//! let report = CapabilityReport::with_bench(Duration::from_millis(20));
+//! println!("{}", report);
+//! ```
+
+mod cpu;
+pub use cpu::CpuFeatures;
+
+mod report;
+pub use report::{CapabilityReport, Backend, Throughput};
+
+#[cfg(test)]
+mod capability_test;
\ No newline at end of file
@@ -0,0 +1,251 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use crate::capability::CpuFeatures;
+
+/// the buffer length of the quick bench
+const BENCH_BUF_LEN: usize = 4096;
+
+/// The implementation which is compiled into the primitive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backend {
+    pub primitive: &'static str,
+    /// e.g. `AES-NI`, `generic`
+    pub implementation: &'static str,
+    /// the backend uses the hardware acceleration
+    pub is_accelerated: bool,
+}
+
+/// The throughput which is measured by the quick bench
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Throughput {
+    pub primitive: &'static str,
+    pub bytes: usize,
+    pub elapsed: Duration,
+}
+
+impl Throughput {
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64().max(1e-9)
+    }
+
+    pub fn mib_per_sec(&self) -> f64 {
+        self.bytes_per_sec() / (1u64 << 20) as f64
+    }
+}
+
+/// The capability report of the crate on the current machine
+#[derive(Clone, Debug)]
+pub struct CapabilityReport {
+    version: &'static str,
+    cpu: CpuFeatures,
+    backends: Vec<Backend>,
+    features: Vec<&'static str>,
+    throughputs: Vec<Throughput>,
+}
+
+impl CapabilityReport {
+    /// the report without the bench
+    pub fn new() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            cpu: CpuFeatures::detect(),
+            backends: Self::compiled_backends(),
+            features: Self::enabled_features(),
+            throughputs: Vec::new(),
+        }
+    }
+
+    /// the report with the throughput of the key primitives, every primitive runs for about the `duration_per_primitive`,
+    /// so the bench takes about `duration_per_primitive * backends().len()`.
+    pub fn with_bench(duration_per_primitive: Duration) -> Self {
+        let mut report = Self::new();
+        report.throughputs = Self::quick_bench(duration_per_primitive);
+        report
+    }
+
+    /// the crate version
+    pub fn version(&self) -> &'static str {
+        self.version
+    }
+
+    /// the detected CPU features
+    pub fn cpu(&self) -> &CpuFeatures {
+        &self.cpu
+    }
+
+    /// the backends of the enabled primitives
+    pub fn backends(&self) -> &[Backend] {
+        self.backends.as_slice()
+    }
+
+    /// the enabled cargo features, e.g. the `legacy` means the insecure legacy algorithms are available
+    pub fn features(&self) -> &[&'static str] {
+        self.features.as_slice()
+    }
+
+    pub fn is_feature_enabled(&self, feature: &str) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// the measured throughputs, it's empty if the report is created by the `new`
+    pub fn throughputs(&self) -> &[Throughput] {
+        self.throughputs.as_slice()
+    }
+
+    /// the accelerated backend is compiled in, but the current CPU doesn't support it(e.g. the binary is built on
+    /// the other machine), the primitive will raise the illegal instruction.
+    pub fn is_backend_mismatch(&self) -> bool {
+        self.backends.iter().any(|b| b.implementation == "AES-NI" && !self.cpu.aes_ni())
+    }
+
+    fn compiled_backends() -> Vec<Backend> {
+        #[allow(unused_mut)]
+        let mut backends = Vec::new();
+
+        #[cfg(feature = "aes")]
+        {
+            let implementation = crate::aes::AES_BACKEND;
+            backends.push(Backend { primitive: "AES", implementation, is_accelerated: implementation != "generic" });
+        }
+
+        #[cfg(feature = "sha2")]
+        backends.push(Backend { primitive: "SHA-256", implementation: "generic", is_accelerated: false });
+
+        #[cfg(all(feature = "aes", feature = "modes"))]
+        backends.push(Backend { primitive: "AES-128-GCM", implementation: "generic(constant-time GHASH)", is_accelerated: false });
+
+        #[cfg(feature = "chacha")]
+        backends.push(Backend { primitive: "ChaCha20", implementation: "generic", is_accelerated: false });
+
+        #[cfg(feature = "sm")]
+        backends.push(Backend { primitive: "SM4", implementation: "generic", is_accelerated: false });
+
+        #[cfg(feature = "research")]
+        {
+            backends.push(Backend { primitive: "Speck128/128", implementation: "generic", is_accelerated: false });
+            backends.push(Backend { primitive: "Simon128/128", implementation: "generic", is_accelerated: false });
+        }
+
+        backends
+    }
+
+    fn enabled_features() -> Vec<&'static str> {
+        let features = [
+            ("aes", cfg!(feature = "aes")), ("des", cfg!(feature = "des")), ("md5", cfg!(feature = "md5")),
+            ("sha2", cfg!(feature = "sha2")), ("sha3", cfg!(feature = "sha3")), ("sm", cfg!(feature = "sm")),
+            ("chacha", cfg!(feature = "chacha")), ("modes", cfg!(feature = "modes")), ("kdf", cfg!(feature = "kdf")),
+            ("rsa", cfg!(feature = "rsa")), ("dsa", cfg!(feature = "dsa")), ("ec", cfg!(feature = "ec")),
+            ("webauthn", cfg!(feature = "webauthn")), ("pin", cfg!(feature = "pin")), ("legacy", cfg!(feature = "legacy")),
+            ("webcrypto", cfg!(feature = "webcrypto")), ("evp", cfg!(feature = "evp")), ("envelope", cfg!(feature = "envelope")),
+            ("hash", cfg!(feature = "hash")), ("sse", cfg!(feature = "sse")), ("dae", cfg!(feature = "dae")), ("sharing", cfg!(feature = "sharing")), ("timelock", cfg!(feature = "timelock")), ("oprf", cfg!(feature = "oprf")), ("estream", cfg!(feature = "estream")), ("xoodyak", cfg!(feature = "xoodyak")), ("grain", cfg!(feature = "grain")), ("x25519", cfg!(feature = "x25519")), ("sm2", cfg!(feature = "sm2")), ("schnorr", cfg!(feature = "schnorr")), ("merkle", cfg!(feature = "merkle")), ("research", cfg!(feature = "research")), ("keyschedule", cfg!(feature = "keyschedule")), ("testutil", cfg!(feature = "testutil")), ("parallel", cfg!(feature = "parallel")), ("cache", cfg!(feature = "cache")),
+            ("mmap", cfg!(feature = "mmap")), ("mlock", cfg!(feature = "mlock")),
+        ];
+        features.iter().filter(|x| x.1).map(|x| x.0).collect()
+    }
+
+    /// run the `f` over the bench buffer until the `duration` elapsed
+    #[allow(unused)]
+    fn bench<F: FnMut(&[u8], &mut Vec<u8>)>(primitive: &'static str, duration: Duration, mut f: F) -> Throughput {
+        let (buf, mut out) = (vec![0x5au8; BENCH_BUF_LEN], Vec::with_capacity(BENCH_BUF_LEN + 64));
+        let (start, mut bytes) = (Instant::now(), 0);
+        loop {
+            f(buf.as_slice(), &mut out);
+            bytes += buf.len();
+            let elapsed = start.elapsed();
+            if elapsed >= duration {
+                return Throughput { primitive, bytes, elapsed };
+            }
+        }
+    }
+
+    #[allow(unused)]
+    fn quick_bench(duration: Duration) -> Vec<Throughput> {
+        let mut throughputs = Vec::new();
+
+        #[cfg(feature = "aes")]
+        {
+            use crate::{AES, Cipher};
+            let cipher = AES::aes_128([0x2b; 16]);
+            throughputs.push(Self::bench("AES", duration, |buf, out| {
+                buf.chunks_exact(16).for_each(|b| { cipher.encrypt(out, b).unwrap(); });
+            }));
+        }
+
+        #[cfg(feature = "sha2")]
+        {
+            use crate::{Digest, sha::SHA256};
+            let mut sha = SHA256::new();
+            throughputs.push(Self::bench("SHA-256", duration, |buf, out| {
+                sha.write(buf);
+                sha.checksum(out);
+                sha.reset();
+            }));
+        }
+
+        #[cfg(all(feature = "aes", feature = "modes"))]
+        {
+            use crate::AES;
+            use crate::cipher_mode::GCM;
+            let gcm = GCM::new(AES::aes_128([0x2b; 16]), 16).unwrap();
+            throughputs.push(Self::bench("AES-128-GCM", duration, |buf, out| {
+                gcm.encrypt(out, &[0u8; 12], &[], buf).unwrap();
+            }));
+        }
+
+        #[cfg(feature = "chacha")]
+        {
+            use crate::ChaCha20;
+            throughputs.push(Self::bench("ChaCha20", duration, |buf, out| {
+                ChaCha20::new([0x2b; 32], [0u8; 12], 1).xor_key_stream(out, buf).unwrap();
+            }));
+        }
+
+        #[cfg(feature = "sm")]
+        {
+            use crate::{SM4, Cipher};
+            let cipher = SM4::new([0x2b; 16]);
+            throughputs.push(Self::bench("SM4", duration, |buf, out| {
+                buf.chunks_exact(16).for_each(|b| { cipher.encrypt(out, b).unwrap(); });
+            }));
+        }
+
+        #[cfg(feature = "research")]
+        {
+            use crate::{Speck, Simon, Cipher};
+            let speck = Speck::new(16, &[0x2b; 16]).unwrap();
+            throughputs.push(Self::bench("Speck128/128", duration, |buf, out| {
+                buf.chunks_exact(16).for_each(|b| { speck.encrypt(out, b).unwrap(); });
+            }));
+            let simon = Simon::new(16, &[0x2b; 16]).unwrap();
+            throughputs.push(Self::bench("Simon128/128", duration, |buf, out| {
+                buf.chunks_exact(16).for_each(|b| { simon.encrypt(out, b).unwrap(); });
+            }));
+        }
+
+        throughputs
+    }
+}
+
+impl Default for CapabilityReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for CapabilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "rcrypto {} capability report:", self.version)?;
+        writeln!(f, "  cpu: {}", self.cpu)?;
+        for b in self.backends.iter() {
+            writeln!(f, "  [backend] {}: {}", b.primitive, b.implementation)?;
+        }
+        if self.is_backend_mismatch() {
+            writeln!(f, "  [warn] the accelerated backend is not supported by the current CPU")?;
+        }
+        writeln!(f, "  features: {}", self.features.join(", "))?;
+        for t in self.throughputs.iter() {
+            writeln!(f, "  [bench] {}: {:.2} MiB/s", t.primitive, t.mib_per_sec())?;
+        }
+        Ok(())
+    }
+}
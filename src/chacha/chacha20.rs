@@ -0,0 +1,173 @@
+use crate::{StreamCipher, CryptoError, CryptoErrorKind};
+
+pub(crate) const CHACHA_CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+pub(super) const CHACHA_BLOCK_LEN: usize = 64;
+
+#[inline]
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]); x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]); x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]); x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]); x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+/// the ChaCha block function of the `rounds`(8, 12 or 20) rounds, the output words are not serialized
+pub(crate) fn chacha_block_words(state: &[u32; 16], rounds: usize) -> [u32; 16] {
+    let mut x = *state;
+    for _ in 0..(rounds >> 1) {
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 1, 5, 9, 13);
+        quarter_round(&mut x, 2, 6, 10, 14);
+        quarter_round(&mut x, 3, 7, 11, 15);
+        quarter_round(&mut x, 0, 5, 10, 15);
+        quarter_round(&mut x, 1, 6, 11, 12);
+        quarter_round(&mut x, 2, 7, 8, 13);
+        quarter_round(&mut x, 3, 4, 9, 14);
+    }
+
+    x.iter_mut().zip(state.iter()).for_each(|(a, &b)| *a = a.wrapping_add(b));
+    x
+}
+
+macro_rules! chacha_impl {
+    ($(#[$doc: meta])* $name: ident, $rounds: literal) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            // key || counter || nonce
+            state: [u32; 16],
+            // the unused key stream of the last block
+            key_stream: Vec<u8>,
+            // the counter is exhausted
+            is_exhausted: bool,
+            // the initial block counter for the `reset`
+            init_counter: u32,
+        }
+
+        impl $name {
+            pub fn new(key: [u8; 32], nonce: [u8; 12], counter: u32) -> Self {
+                Self::from_slice(key.as_ref(), nonce.as_ref(), counter).unwrap()
+            }
+
+            pub fn from_slice(key: &[u8], nonce: &[u8], counter: u32) -> Result<Self, CryptoError> {
+                if key.len() != 32 || nonce.len() != 12 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                        format!("The {} key must be 32 bytes and the nonce must be 12 bytes, but got {} and {}", stringify!($name), key.len(), nonce.len())));
+                }
+
+                let mut state = [0u32; 16];
+                state[..4].copy_from_slice(CHACHA_CONSTANTS.as_ref());
+                state[4..12].iter_mut().zip(key.chunks(4)).for_each(|(s, k)| *s = u32::from_le_bytes([k[0], k[1], k[2], k[3]]));
+                state[12] = counter;
+                state[13..].iter_mut().zip(nonce.chunks(4)).for_each(|(s, n)| *s = u32::from_le_bytes([n[0], n[1], n[2], n[3]]));
+
+                Ok(Self { state, key_stream: Vec::with_capacity(CHACHA_BLOCK_LEN), is_exhausted: false, init_counter: counter })
+            }
+
+            /// the number of the rounds
+            pub const fn rounds() -> usize {
+                $rounds
+            }
+
+            /// the block counter of the next key stream block
+            pub fn counter(&self) -> u32 {
+                self.state[12]
+            }
+
+            /// reset the key stream to the fresh `nonce`(12 bytes) and the block counter `counter` under the same key, it's
+            /// the way to encrypt the next message. The `nonce` equal to the current nonce is rejected.
+            pub fn reset_with_nonce(&mut self, nonce: &[u8], counter: u32) -> Result<(), CryptoError> {
+                if nonce.len() != 12 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                        format!("The {} nonce must be 12 bytes, but got {}", stringify!($name), nonce.len())));
+                }
+
+                let mut words = [0u32; 3];
+                words.iter_mut().zip(nonce.chunks(4)).for_each(|(s, n)| *s = u32::from_le_bytes([n[0], n[1], n[2], n[3]]));
+                if words == self.state[13..] {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                        format!("The {} nonce must be fresh, it's the same as the current nonce", stringify!($name))));
+                }
+
+                self.state[12] = counter;
+                self.state[13..].copy_from_slice(words.as_ref());
+                self.init_counter = counter;
+                self.key_stream.clear();
+                self.is_exhausted = false;
+                Ok(())
+            }
+
+            /// the block function of the current counter
+            pub fn block(&self) -> [u8; CHACHA_BLOCK_LEN] {
+                let x = chacha_block_words(&self.state, $rounds);
+                let mut out = [0u8; CHACHA_BLOCK_LEN];
+                out.chunks_mut(4).zip(x.iter()).for_each(|(o, &a)| o.copy_from_slice(a.to_le_bytes().as_ref()));
+                out
+            }
+
+            fn next_block(&mut self) -> Result<[u8; CHACHA_BLOCK_LEN], CryptoError> {
+                if self.is_exhausted {
+                    return Err(CryptoError::new(CryptoErrorKind::LimitExceeded, format!("The {} block counter is exhausted", stringify!($name))));
+                }
+                let block = self.block();
+                self.state[12] = self.state[12].wrapping_add(1);
+                self.is_exhausted = self.state[12] == 0;
+                Ok(block)
+            }
+
+            /// xor the `data` with the key stream and output to the `dst`, the key stream is continuous between the calls.
+            pub fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError> {
+                dst.clear();
+                let len = self.key_stream.len().min(data.len());
+                dst.extend(data[..len].iter().zip(self.key_stream.drain(..len)).map(|(&a, b)| a ^ b));
+
+                for block in data[len..].chunks(CHACHA_BLOCK_LEN) {
+                    let ks = self.next_block()?;
+                    dst.extend(block.iter().zip(ks.iter()).map(|(&a, &b)| a ^ b));
+                    self.key_stream.extend_from_slice(&ks[block.len()..]);
+                }
+                Ok(())
+            }
+        }
+
+        impl StreamCipher for $name {
+            fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError> {
+                $name::xor_key_stream(self, dst, data)
+            }
+
+            /// # Security
+            ///
+            /// the key stream of the construction-time nonce is generated again, only re-process the same message after
+            /// the `reset`, and encrypt the next message by the `reset_with_nonce` with a fresh nonce.
+            fn reset(&mut self) {
+                self.state[12] = self.init_counter;
+                self.key_stream.clear();
+                self.is_exhausted = false;
+            }
+        }
+    };
+}
+
+chacha_impl!(
+    /// ChaCha20 stream cipher
+    /// RFC 8439 2.4
+    ///
+    /// the 256 bits key, the 96 bits nonce and the 32 bits block counter, the key stream of one nonce is
+    /// not more than $2^{32}$ blocks(256 GiB).
+    ChaCha20, 20
+);
+
+chacha_impl!(
+    /// ChaCha12 stream cipher, the reduced-round ChaCha with the same key, nonce and counter layout as the ChaCha20
+    ///
+    /// the 12 rounds keep the large security margin against the known attacks, for the performance sensitive
+    /// protocols which specify it explicitly(e.g. the Adiantum).
+    ChaCha12, 12
+);
+
+chacha_impl!(
+    /// ChaCha8 stream cipher, the reduced-round ChaCha with the same key, nonce and counter layout as the ChaCha20
+    ///
+    /// the 8 rounds have the smallest security margin, prefer the ChaCha20 unless the protocol specifies the ChaCha8.
+    ChaCha8, 8
+);
@@ -0,0 +1,109 @@
+use crate::{CryptoError, CryptoErrorKind, Digest, AEAD};
+use crate::chacha::{ChaCha20, Poly1305};
+use crate::chacha::chacha20::CHACHA_BLOCK_LEN;
+
+const CHACHA20_POLY1305_NONCE_LEN: usize = 12;
+const CHACHA20_POLY1305_TAG_LEN: usize = 16;
+/// the plaintext of one nonce is not more than $(2^{32}-1)$ blocks, the block 0 generates the Poly1305 key
+const CHACHA20_POLY1305_MAX_DATA_LEN: u64 = ((1u64 << 32) - 1) * (CHACHA_BLOCK_LEN as u64);
+
+/// ChaCha20-Poly1305 AEAD
+/// RFC 8439 2.8
+///
+/// the Poly1305 key is the first 32 bytes of the ChaCha20 block 0, the plaintext is encrypted from the block 1,
+/// the tag is `Poly1305(aad || pad16 || ciphertext || pad16 || [len(aad)]_64 || [len(ciphertext)]_64)`,
+/// where the lengths are little-endian.
+#[derive(Clone)]
+pub struct ChaCha20Poly1305 {
+    key: [u8; 32],
+}
+
+impl ChaCha20Poly1305 {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    pub fn from_slice(key: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != 32 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the ChaCha20-Poly1305 key must be 32 bytes", key.len())))
+        } else {
+            let mut k = [0u8; 32];
+            k.copy_from_slice(key);
+            Ok(Self { key: k })
+        }
+    }
+
+    /// the ChaCha20 of the block 1 and the Poly1305 with the one-time key from the block 0
+    fn cipher(&self, nonce: &[u8], data_len: usize) -> Result<(ChaCha20, Poly1305), CryptoError> {
+        if nonce.len() != CHACHA20_POLY1305_NONCE_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong nonce length: {}, the ChaCha20-Poly1305 nonce must be 12 bytes", nonce.len())));
+        } else if (data_len as u64) > CHACHA20_POLY1305_MAX_DATA_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded,
+                format!("The data length {} is great than the 2^32-1 ChaCha20 blocks", data_len)));
+        }
+
+        let chacha = ChaCha20::from_slice(self.key.as_ref(), nonce, 0)?;
+        let poly = Poly1305::from_slice(&chacha.block()[..32])?;
+        Ok((ChaCha20::from_slice(self.key.as_ref(), nonce, 1)?, poly))
+    }
+
+    fn tag(mut poly: Poly1305, aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let pad = [0u8; 16];
+        poly.write(aad);
+        poly.write(&pad[..((16 - (aad.len() & 15)) & 15)]);
+        poly.write(ciphertext);
+        poly.write(&pad[..((16 - (ciphertext.len() & 15)) & 15)]);
+        poly.write((aad.len() as u64).to_le_bytes().as_ref());
+        poly.write((ciphertext.len() as u64).to_le_bytes().as_ref());
+
+        let mut tag = Vec::with_capacity(CHACHA20_POLY1305_TAG_LEN);
+        poly.checksum(&mut tag);
+        tag
+    }
+
+    /// encrypt and authenticate the `plaintext` and `aad`, the `ciphertext||tag` will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        let (mut chacha, poly) = self.cipher(nonce, plaintext.len())?;
+        chacha.xor_key_stream(dst, plaintext)?;
+        let tag = Self::tag(poly, aad, dst.as_slice());
+        dst.extend_from_slice(tag.as_slice());
+        Ok(())
+    }
+
+    /// verify and decrypt the `ciphertext||tag`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if ciphertext.len() < CHACHA20_POLY1305_TAG_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the tag"));
+        }
+        let (ct, tag) = ciphertext.split_at(ciphertext.len() - CHACHA20_POLY1305_TAG_LEN);
+        let (mut chacha, poly) = self.cipher(nonce, ct.len())?;
+
+        let expected = Self::tag(poly, aad, ct);
+        if expected.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ChaCha20-Poly1305 tag not match"))
+        } else {
+            chacha.xor_key_stream(dst, ct)
+        }
+    }
+}
+
+impl AEAD for ChaCha20Poly1305 {
+    fn nonce_len(&self) -> usize {
+        CHACHA20_POLY1305_NONCE_LEN
+    }
+
+    fn tag_len(&self) -> usize {
+        CHACHA20_POLY1305_TAG_LEN
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, nonce, aad, plaintext)
+    }
+
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.decrypt(dst, nonce, aad, ciphertext)
+    }
+}
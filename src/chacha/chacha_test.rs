@@ -0,0 +1,143 @@
+use crate::{ChaCha20, ChaCha12, ChaCha8, Poly1305, ChaCha20Poly1305, Digest, AEAD, StreamCipher, CryptoErrorKind};
+use crate::aead::{Nonce, Tag};
+use crate::encoding::Hex;
+use crate::test_util::hex;
+
+const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+#[test]
+fn chacha20() {
+    // RFC 8439 2.3.2, 2.4.2
+    let key = (0u8..32).collect::<Vec<_>>();
+    let chacha = ChaCha20::from_slice(key.as_slice(), hex("000000090000004a00000000").as_slice(), 1).unwrap();
+    assert_eq!(chacha.block().to_vec(), hex("10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4ed2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"));
+
+    let tgt = hex("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d");
+    let nonce = hex("000000000000004a00000000");
+    let (mut c, mut buf) = (Vec::new(), Vec::new());
+    for &step in [1usize, 7, 64, 65, SUNSCREEN.len()].iter() {
+        let mut chacha = ChaCha20::from_slice(key.as_slice(), nonce.as_slice(), 1).unwrap();
+        c.clear();
+        for m in SUNSCREEN.chunks(step) {
+            chacha.xor_key_stream(&mut buf, m).unwrap();
+            c.extend_from_slice(buf.as_slice());
+        }
+        assert_eq!(c, tgt, "step: {}", step);
+        assert_eq!(chacha.counter(), 3);
+    }
+
+    let mut chacha = ChaCha20::from_slice(key.as_slice(), nonce.as_slice(), u32::MAX).unwrap();
+    assert!(chacha.xor_key_stream(&mut buf, &[0u8; 64]).is_ok());
+    assert_eq!(chacha.xor_key_stream(&mut buf, &[0u8; 1]).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    StreamCipher::reset(&mut chacha);
+    assert_eq!(chacha.counter(), u32::MAX);
+    assert!(chacha.xor_key_stream(&mut buf, &[0u8; 64]).is_ok());
+    assert_eq!(chacha.reset_with_nonce(nonce.as_slice(), 1).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(chacha.reset_with_nonce(&nonce[1..], 1).is_err());
+    let mut fresh = nonce.clone();
+    fresh[0] ^= 1;
+    chacha.reset_with_nonce(fresh.as_slice(), 1).unwrap();
+    let mut other = ChaCha20::from_slice(key.as_slice(), fresh.as_slice(), 1).unwrap();
+    let mut tgt = Vec::new();
+    other.xor_key_stream(&mut tgt, SUNSCREEN).unwrap();
+    chacha.xor_key_stream(&mut buf, SUNSCREEN).unwrap();
+    assert_eq!((buf.as_slice(), chacha.counter()), (tgt.as_slice(), 3));
+    assert!(ChaCha20::from_slice(&key[1..], nonce.as_slice(), 0).is_err());
+}
+
+#[test]
+fn chacha_reduced() {
+    // the zero key and the zero nonce
+    assert_eq!(ChaCha20::new([0u8; 32], [0u8; 12], 0).block().to_vec(), hex("76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586"));
+    assert_eq!(ChaCha12::new([0u8; 32], [0u8; 12], 0).block().to_vec(), hex("9bf49a6a0755f953811fce125f2683d50429c3bb49e074147e0089a52eae155f0564f879d27ae3c02ce82834acfa8c793a629f2ca0de6919610be82f411326be"));
+    assert_eq!(ChaCha8::new([0u8; 32], [0u8; 12], 0).block().to_vec(), hex("3e00ef2f895f40d67f5bb8e81f09a5a12c840ec3ce9a7f3b181be188ef711a1e984ce172b9216f419f445367456d5619314a42a3da86b001387bfdb80e0cfe42"));
+    assert_eq!((ChaCha20::rounds(), ChaCha12::rounds(), ChaCha8::rounds()), (20, 12, 8));
+
+    let key = (0u8..32).collect::<Vec<_>>();
+    let nonce = hex("000000000000004a00000000");
+    let (data, mut buf) = ([0u8; 128], Vec::new());
+    let mut chacha = ChaCha12::from_slice(key.as_slice(), nonce.as_slice(), 1).unwrap();
+    chacha.xor_key_stream(&mut buf, &data[..70]).unwrap();
+    let mut ks = buf.clone();
+    chacha.xor_key_stream(&mut buf, &data[70..]).unwrap();
+    ks.extend_from_slice(buf.as_slice());
+    assert_eq!(ks, hex("c126863f9577559308796ff81a44655bd352630c35bd4beccbad4b6fdd7b608f8ba8301c3a1e8f0643571dbe21583d5f622a60f4321e1243b88a4796306f9122116dfc0ae8f83bc3ac1b0dac1966a79f1cc09fada6bfb33bfaaa0a9101e5f7a454c689ced448424d3d53935e00d37e50be76c1b4d844c79e2574114bb9d34270"));
+    let mut chacha = ChaCha8::from_slice(key.as_slice(), nonce.as_slice(), 1).unwrap();
+    chacha.xor_key_stream(&mut buf, data.as_ref()).unwrap();
+    assert_eq!(buf, hex("bc08fed3f82c571c5e7a70866588aee281ee18680869a9c2af9f4e244a4a563761b2dfe8a747dafd532f8496553311589abd3ec1eb4576054477a7295b82cbb72872607d86b93d80e3e7fea72806341fa1118239138e2e78d2a997d40f51647fcb7729a690cf215ad44474ab0d6c09bb8adc497ebd6d34010937d25eead815da"));
+    assert_eq!(chacha.counter(), 3);
+
+    assert_eq!(ChaCha8::from_slice(&key[1..], nonce.as_slice(), 0).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    let mut chacha = ChaCha12::new([0u8; 32], [0u8; 12], u32::MAX);
+    assert!(chacha.xor_key_stream(&mut buf, &data[..64]).is_ok());
+    assert_eq!(chacha.xor_key_stream(&mut buf, &data[..1]).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+}
+
+#[test]
+fn poly1305() {
+    // RFC 8439 2.5.2, and the edge cases of the reduction mod 2^130-5
+    let mut key2 = [0u8; 32];
+    key2[0] = 2;
+    let mut key1 = [0u8; 32];
+    key1[0] = 1;
+    let mut m3 = vec![0xffu8; 48];
+    m3[16] = 0xfb;
+    m3[32..].iter_mut().enumerate().for_each(|(i, x)| *x = if i == 0 { 3 } else { 0 });
+    let cases = [
+        (hex("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b"), b"Cryptographic Forum Research Group".to_vec(), "a8061dc1305136c6c22b8baf0c0127a9"),
+        (key2.to_vec(), vec![0xffu8; 16], "03000000000000000000000000000000"),
+        (key1.to_vec(), m3, "02000000000000000000000000000000"),
+        (vec![0xffu8; 32], vec![0xffu8; 1000], "de9406b10e7023bcd692ff687f4cbc7f"),
+    ];
+
+    let mut tag = Vec::new();
+    for (i, (k, m, tgt)) in cases.iter().enumerate() {
+        let mut poly = Poly1305::from_slice(k.as_slice()).unwrap();
+        for &step in [1usize, 15, 16, 17, m.len()].iter() {
+            poly.reset();
+            m.chunks(step).for_each(|x| poly.write(x));
+            poly.checksum(&mut tag);
+            assert_eq!(Hex::lower().encode(tag.as_slice()), *tgt, "case: {}, step: {}", i, step);
+        }
+    }
+    assert!(Poly1305::from_slice(&[0u8; 16]).is_err());
+}
+
+#[test]
+fn chacha20_poly1305() {
+    // RFC 8439 2.8.2
+    let key = hex("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f");
+    let (nonce, aad) = (hex("070000004041424344454647"), hex("50515253c0c1c2c3c4c5c6c7"));
+    let tgt = hex("d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691");
+
+    let aead = ChaCha20Poly1305::from_slice(key.as_slice()).unwrap();
+    let (mut c, mut p) = (Vec::new(), Vec::new());
+    aead.seal(&mut c, nonce.as_slice(), aad.as_slice(), SUNSCREEN).unwrap();
+    assert_eq!(c, tgt);
+    aead.open(&mut p, nonce.as_slice(), aad.as_slice(), c.as_slice()).unwrap();
+    assert_eq!(p.as_slice(), SUNSCREEN);
+
+    let mut bad = c.clone();
+    bad[0] ^= 1;
+    assert_eq!(aead.open(&mut p, nonce.as_slice(), aad.as_slice(), bad.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(p.is_empty());
+    assert!(aead.open(&mut p, nonce.as_slice(), &aad[1..], c.as_slice()).is_err());
+    assert!(aead.open(&mut p, nonce.as_slice(), aad.as_slice(), &c[..15]).is_err());
+    assert!(aead.seal(&mut c, &nonce[1..], b"", b"").is_err());
+
+    aead.seal(&mut c, nonce.as_slice(), b"", b"").unwrap();
+    assert_eq!((c.len(), aead.nonce_len(), aead.tag_len()), (16, 12, 16));
+    aead.open(&mut p, nonce.as_slice(), b"", c.as_slice()).unwrap();
+    assert!(p.is_empty());
+    assert!(ChaCha20Poly1305::from_slice(&key[1..]).is_err());
+
+    // the detached tag is the last 16 bytes of the `seal`
+    let nonce = Nonce::<12>::from_slice(nonce.as_slice()).unwrap();
+    let tag: Tag<16> = aead.seal_detached(&mut c, &nonce, aad.as_slice(), SUNSCREEN).unwrap();
+    assert_eq!(c.as_slice(), &tgt[..SUNSCREEN.len()]);
+    assert!(tag.ct_eq(&tgt[SUNSCREEN.len()..]));
+    aead.open_detached(&mut p, &nonce, aad.as_slice(), c.as_slice(), &tag).unwrap();
+    assert_eq!(p.as_slice(), SUNSCREEN);
+    assert_eq!(aead.open_detached(&mut p, &nonce, b"", c.as_slice(), &tag).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert_eq!(aead.seal_detached::<12, 12>(&mut c, &nonce, b"", b"").unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+}
@@ -0,0 +1,17 @@
+//! ChaCha20, Poly1305 and the ChaCha20-Poly1305 AEAD
+//! RFC 8439
+//!
+//! The reduced-round ChaCha12 and ChaCha8 are also provided.
+
+mod chacha20;
+pub use chacha20::{ChaCha20, ChaCha12, ChaCha8};
+pub(crate) use chacha20::{chacha_block_words, CHACHA_CONSTANTS};
+
+mod poly1305;
+pub use poly1305::Poly1305;
+
+mod chacha20_poly1305;
+pub use chacha20_poly1305::ChaCha20Poly1305;
+
+#[cfg(test)]
+mod chacha_test;
\ No newline at end of file
@@ -0,0 +1,177 @@
+use crate::{CryptoError, CryptoErrorKind, Digest};
+
+const POLY1305_BLOCK_LEN: usize = 16;
+
+/// Poly1305 one-time authenticator
+/// RFC 8439 2.5
+///
+/// the 256 bits one-time key `r || s`, the tag is `((Σ c_i·r^{q-i+1}) mod (2^130 - 5) + s) mod 2^128`.
+/// The key must not be used to authenticate more than one message, the `reset` only clears the written message.
+#[derive(Clone)]
+pub struct Poly1305 {
+    // the clamped r in the 26 bits limbs
+    r: [u32; 5],
+    s: [u32; 4],
+    // the accumulator in the 26 bits limbs
+    h: [u32; 5],
+    buf: Vec<u8>,
+    tag: Option<[u8; POLY1305_BLOCK_LEN]>,
+}
+
+impl Poly1305 {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self::from_slice(key.as_ref()).unwrap()
+    }
+
+    pub fn from_slice(key: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != 32 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the Poly1305 key must be 32 bytes", key.len())));
+        }
+
+        let le = |i: usize| u32::from_le_bytes([key[i], key[i + 1], key[i + 2], key[i + 3]]);
+        // r &= 0x0ffffffc0ffffffc0ffffffc0fffffff
+        let r = [
+            le(0) & 0x3ffffff,
+            (le(3) >> 2) & 0x3ffff03,
+            (le(6) >> 4) & 0x3ffc0ff,
+            (le(9) >> 6) & 0x3f03fff,
+            (le(12) >> 8) & 0x00fffff,
+        ];
+        let s = [le(16), le(20), le(24), le(28)];
+
+        Ok(Self { r, s, h: [0u32; 5], buf: Vec::with_capacity(POLY1305_BLOCK_LEN), tag: None })
+    }
+
+    /// h = (h + c)·r mod (2^130 - 5), the `hibit` is the 2^128 bit of the block
+    fn block(&mut self, c: &[u8], hibit: u32) {
+        let le = |i: usize| u32::from_le_bytes([c[i], c[i + 1], c[i + 2], c[i + 3]]);
+        let (r, h) = (&self.r, &mut self.h);
+        let (s1, s2, s3, s4) = (r[1] * 5, r[2] * 5, r[3] * 5, r[4] * 5);
+
+        let h0 = (h[0] + (le(0) & 0x3ffffff)) as u64;
+        let h1 = (h[1] + ((le(3) >> 2) & 0x3ffffff)) as u64;
+        let h2 = (h[2] + ((le(6) >> 4) & 0x3ffffff)) as u64;
+        let h3 = (h[3] + ((le(9) >> 6) & 0x3ffffff)) as u64;
+        let h4 = (h[4] + ((le(12) >> 8) | (hibit << 24))) as u64;
+        let (r0, r1, r2, r3, r4) = (r[0] as u64, r[1] as u64, r[2] as u64, r[3] as u64, r[4] as u64);
+        let (s1, s2, s3, s4) = (s1 as u64, s2 as u64, s3 as u64, s4 as u64);
+
+        let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+        let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+        let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+        d1 += d0 >> 26;
+        d2 += d1 >> 26;
+        d3 += d2 >> 26;
+        d4 += d3 >> 26;
+        let h0 = (d0 & 0x3ffffff) + (d4 >> 26) * 5;
+        let h1 = (d1 & 0x3ffffff) + (h0 >> 26);
+        *h = [(h0 & 0x3ffffff) as u32, h1 as u32, (d2 & 0x3ffffff) as u32, (d3 & 0x3ffffff) as u32, (d4 & 0x3ffffff) as u32];
+    }
+
+    fn finalize(&mut self) -> [u8; POLY1305_BLOCK_LEN] {
+        if !self.buf.is_empty() {
+            let mut c = [0u8; POLY1305_BLOCK_LEN];
+            c[..self.buf.len()].copy_from_slice(self.buf.as_slice());
+            c[self.buf.len()] = 1;
+            self.block(c.as_ref(), 0);
+            self.buf.clear();
+        }
+
+        // the full carry
+        let mut h = self.h;
+        for i in 1..5 {
+            h[i] += h[i - 1] >> 26;
+            h[i - 1] &= 0x3ffffff;
+        }
+        h[0] += (h[4] >> 26) * 5;
+        h[4] &= 0x3ffffff;
+        h[1] += h[0] >> 26;
+        h[0] &= 0x3ffffff;
+
+        // g = h + 5 - 2^130, select the h if the g is negative
+        let mut g = [0u32; 5];
+        let mut carry = 5u32;
+        for i in 0..5 {
+            g[i] = h[i] + carry;
+            carry = g[i] >> 26;
+            g[i] &= 0x3ffffff;
+        }
+        // carry is 1 if h >= 2^130 - 5
+        let mask = 0u32.wrapping_sub(carry);
+        h.iter_mut().zip(g.iter()).for_each(|(a, &b)| *a = (*a & !mask) | (b & mask));
+
+        // h mod 2^128 + s
+        let h = [
+            h[0] | (h[1] << 26),
+            (h[1] >> 6) | (h[2] << 20),
+            (h[2] >> 12) | (h[3] << 14),
+            (h[3] >> 18) | (h[4] << 8),
+        ];
+        let mut tag = [0u8; POLY1305_BLOCK_LEN];
+        let mut f = 0u64;
+        for (i, t) in tag.chunks_mut(4).enumerate() {
+            f = (h[i] as u64) + (self.s[i] as u64) + (f >> 32);
+            t.copy_from_slice((f as u32).to_le_bytes().as_ref());
+        }
+        tag
+    }
+}
+
+impl Digest for Poly1305 {
+    fn block_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn bits_len(&self) -> usize {
+        POLY1305_BLOCK_LEN << 3
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        if self.tag.is_some() {
+            self.reset();
+        }
+
+        let mut data = data;
+        if !self.buf.is_empty() {
+            let l = (POLY1305_BLOCK_LEN - self.buf.len()).min(data.len());
+            self.buf.extend_from_slice(&data[..l]);
+            data = &data[l..];
+            if self.buf.len() < POLY1305_BLOCK_LEN {
+                return;
+            }
+            let c = std::mem::take(&mut self.buf);
+            self.block(c.as_slice(), 1);
+            self.buf = c;
+            self.buf.clear();
+        }
+
+        let mut itr = data.chunks_exact(POLY1305_BLOCK_LEN);
+        for c in &mut itr {
+            self.block(c, 1);
+        }
+        self.buf.extend_from_slice(itr.remainder());
+    }
+
+    fn checksum(&mut self, digest: &mut Vec<u8>) {
+        let tag = match self.tag {
+            Some(t) => t,
+            None => {
+                let t = self.finalize();
+                self.tag = Some(t);
+                t
+            },
+        };
+        digest.clear();
+        digest.extend_from_slice(tag.as_ref());
+    }
+
+    fn reset(&mut self) {
+        self.h = [0u32; 5];
+        self.buf.clear();
+        self.tag = None;
+    }
+}
@@ -1,8 +1,18 @@
 //! A trait for cryptography
 
-use crate::crypto_err::CryptoError;
+use crate::crypto_err::{CryptoError, CryptoErrorKind};
+use crate::aead::{Nonce, Tag};
 
 /// A trait for cryptography algorithms
+///
+/// The wrong length contract of the block ciphers: the key or the data block of the wrong length is rejected by the
+/// `CryptoErrorKind::InvalidParameter`, it never panics or reads out of the bounds of the input, and the `dst` is
+/// unchanged if the error returned. The `test_util::check_block_cipher` checks this contract.
+///
+/// The reuse contract of the cipher modes(CBC, CFB, OFB, CTR, GCM, CCM and their streams): the `reset` returns the mode
+/// to the state right after the construction(the construction-time IV or the counter reset), the modes are `Clone` if
+/// the block cipher is, and the `cipher_mode::ModeBuilder` builds them from one configured block cipher. The `reset`
+/// doesn't make a fresh IV, see the `# Security` of the `StreamCipher::reset`.
 pub trait Cipher {
     type Output;
     /// The cryptography algorithm used data block size(in bytes) for plaintext, `None` means that there is
@@ -18,7 +28,29 @@ pub trait Cipher {
     fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<Self::Output, CryptoError>;
 }
 
+/// A trait for the synchronous stream ciphers, the key stream is continuous between the calls, so the data can be
+/// processed by the chunks of any length.
+pub trait StreamCipher {
+    /// xor the `data` with the key stream and output to the `dst`, the encryption and the decryption are the same.
+    fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError>;
+    
+    /// reset the key stream to the initial position of the construction-time key, IV and counter.
+    ///
+    /// # Security
+    ///
+    /// The same key stream is generated again after the `reset`, it only allows to re-process the same message, e.g.
+    /// decrypt what was just encrypted. Encrypting a different message after the `reset` reuses the key stream, and
+    /// the xor of the two ciphertexts is the xor of the two plaintexts. Encrypt the next message by the
+    /// `reset_with_iv`(the `reset_with_nonce` of the ChaCha, the `reset_with_count` of the ZUC) with a fresh IV.
+    fn reset(&mut self);
+}
+
 /// A trait for message digest algorithm used in the cryptography
+///
+/// The reuse contract of the digests, MACs and XOFs: the `reset` returns the digester to the state right after the
+/// construction, the construction-time parameters(the key, the digest length, the customization string, etc.) are
+/// kept, so one digester can be reused for many messages without the key setup again. All the digesters of this crate
+/// are `Clone`, the clone of the configured(and maybe partially written) digester is the cheap way to pool them.
 pub trait Digest {
     
     /// used for HMAC, `None` means that the digest algorithm doesn't support used in the HMAC.
@@ -30,11 +62,18 @@ pub trait Digest {
     /// write byte data to the Digester
     fn write(&mut self, data: &[u8]);
     
+    /// write the scattered buffers to the Digester in order, it's equivalent to write the concatenation
+    /// of the `bufs`, e.g. the packet header and payload can be written without copying them together.
+    fn write_vectored(&mut self, bufs: &[&[u8]]) {
+        bufs.iter().for_each(|data| self.write(data));
+    }
+    
     /// compute the checksum for all data in the digester, the checksum will be same  
     /// if no new data write to the digester 
     fn checksum(&mut self, digest: &mut Vec<u8>);
     
-    /// reset internal state of the Digester to the init state
+    /// reset internal state of the Digester to the init state, it's equivalent to the digester which is just
+    /// constructed by the same parameters.
     fn reset(&mut self);
 }
 
@@ -43,6 +82,22 @@ pub trait DigestXOF: Digest {
     fn set_digest_len(&mut self, bits_len: usize);
 }
 
+/// Advanced: export and import the chaining value of the Merkle–Damgård hash function at the block boundary.
+///
+/// The mid-state is the chaining value(encoded in the same byte order as the digest) and the processed
+/// message length in bytes. It can be used to hand over the hashing of a long message between the workers, and
+/// the digest of `M` is the mid-state of `M || pad(M)`, that's why the length-extension attack works: never
+/// use `H(secret || message)` as the MAC, use the HMAC instead.
+pub trait DigestMidState: Digest + Sized {
+    /// the processed message length must be a multiple of the block size, and the digest must not be
+    /// finalized by the `checksum`.
+    fn export_mid_state(&self) -> Result<(Vec<u8>, u64), CryptoError>;
+    
+    /// construct the digester which has processed `len` bytes message and whose chaining value is the `state`,
+    /// the `len` must be a multiple of the block size.
+    fn from_mid_state(state: &[u8], len: u64) -> Result<Self, CryptoError>;
+}
+
 /// A trait for signature algorithms
 pub trait Signature<T> {
     type Output;
@@ -50,4 +105,72 @@ pub trait Signature<T> {
     fn sign(&mut self, signature: &mut T, message: &[u8]) -> Result<Self::Output, CryptoError>;
     
     fn verify(&mut self, signature: &T, message: &[u8]) -> Result<Self::Output, CryptoError>;
-}
\ No newline at end of file
+}
+/// A trait for authenticated encryption with associated data(AEAD) algorithms
+pub trait AEAD {
+    /// the nonce length(in bytes) the algorithm required
+    fn nonce_len(&self) -> usize;
+    
+    /// the authentication tag length(in bytes) which is appended to the ciphertext
+    fn tag_len(&self) -> usize;
+    
+    /// To encrypt and authenticate the `plaintext` and `aad`, the `ciphertext||tag` will output to the `dst`.
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError>;
+    
+    /// To verify and decrypt the `ciphertext||tag`, nothing will output to the `dst` if the verification failed.
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError>;
+    
+    /// the `seal` with the typed nonce, the `InvalidParameter` error is returned before the encryption if the `N` is not
+    /// the `nonce_len`.
+    fn seal_with_nonce<const N: usize>(&self, dst: &mut Vec<u8>, nonce: &Nonce<N>, aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError>
+        where Self: Sized {
+        check_nonce_len(self, N)?;
+        self.seal(dst, nonce.as_ref(), aad, plaintext)
+    }
+    
+    /// the `open` with the typed nonce, see the `seal_with_nonce`
+    fn open_with_nonce<const N: usize>(&self, dst: &mut Vec<u8>, nonce: &Nonce<N>, aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError>
+        where Self: Sized {
+        check_nonce_len(self, N)?;
+        self.open(dst, nonce.as_ref(), aad, ciphertext)
+    }
+    
+    /// the `seal_with_nonce` with the detached tag, the ciphertext will output to the `dst` and the tag is returned, the
+    /// `InvalidParameter` error is returned before the encryption if the `T` is not the `tag_len`.
+    fn seal_detached<const N: usize, const T: usize>(&self, dst: &mut Vec<u8>, nonce: &Nonce<N>, aad: &[u8], plaintext: &[u8]) -> Result<Tag<T>, CryptoError>
+        where Self: Sized {
+        check_tag_len(self, T)?;
+        self.seal_with_nonce(dst, nonce, aad, plaintext)?;
+        let tag = Tag::from_slice(&dst[(dst.len() - T)..])?;
+        dst.truncate(dst.len() - T);
+        Ok(tag)
+    }
+    
+    /// verify the detached `tag` and decrypt the `ciphertext`, see the `seal_detached`
+    fn open_detached<const N: usize, const T: usize>(&self, dst: &mut Vec<u8>, nonce: &Nonce<N>, aad: &[u8], ciphertext: &[u8], tag: &Tag<T>) -> Result<(), CryptoError>
+        where Self: Sized {
+        check_tag_len(self, T)?;
+        let mut data = Vec::with_capacity(ciphertext.len() + T);
+        data.extend_from_slice(ciphertext);
+        data.extend_from_slice(tag.as_ref());
+        self.open_with_nonce(dst, nonce, aad, data.as_slice())
+    }
+}
+
+fn check_tag_len<A: AEAD>(aead: &A, len: usize) -> Result<(), CryptoError> {
+    if aead.tag_len() == len {
+        Ok(())
+    } else {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The {} tag is {} bytes, but got the Tag<{}>", std::any::type_name::<A>(), aead.tag_len(), len)))
+    }
+}
+
+fn check_nonce_len<A: AEAD>(aead: &A, len: usize) -> Result<(), CryptoError> {
+    if aead.nonce_len() == len {
+        Ok(())
+    } else {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The {} nonce must be {} bytes, but got the Nonce<{}>", std::any::type_name::<A>(), aead.nonce_len(), len)))
+    }
+}
@@ -0,0 +1,90 @@
+//! The builder of the construction-time parameters of the CBC, CFB, OFB, CTR, GCM and CCM
+//!
+//! the builder keeps the block cipher(the key setup is done once) and it's not consumed by the `build_*` functions,
+//! so one configured builder can create the modes for the pool. The padding, the IV generator and the counter
+//! are the per-instance parameters of the `build_*` functions. The defaults: the CFB segment is the block size,
+//! the GCM and CCM tag is 16 bytes, and the CCM nonce is 12 bytes.
+
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::cipher_mode::{CBC, CFB, OFB, CTR, GCM, CCM, CiphertextStealing, Padding, EmptyPadding, InitialVec, Counter};
+
+const DEFAULT_TAG_LEN: usize = 16;
+const DEFAULT_CCM_NONCE_LEN: usize = 12;
+
+#[derive(Clone)]
+pub struct ModeBuilder<C> {
+    cipher: C,
+    segment_bits_len: Option<usize>,
+    cts: Option<CiphertextStealing>,
+    tag_len: usize,
+    nonce_len: usize,
+}
+
+impl<C: Cipher + Clone> ModeBuilder<C> {
+    pub fn new(cipher: C) -> Self {
+        Self {
+            cipher,
+            segment_bits_len: None,
+            cts: None,
+            tag_len: DEFAULT_TAG_LEN,
+            nonce_len: DEFAULT_CCM_NONCE_LEN,
+        }
+    }
+
+    /// the segment size `s` in bits of the CFB
+    pub fn segment_bits_len(mut self, bits_len: usize) -> Self {
+        self.segment_bits_len = Some(bits_len);
+        self
+    }
+
+    /// the ciphertext stealing variant of the CBC, it's required by the `build_cbc_cts`
+    pub fn ciphertext_stealing(mut self, cts: CiphertextStealing) -> Self {
+        self.cts = Some(cts);
+        self
+    }
+
+    /// the tag length in bytes of the GCM and CCM
+    pub fn tag_len(mut self, tag_len: usize) -> Self {
+        self.tag_len = tag_len;
+        self
+    }
+
+    /// the nonce length in bytes of the CCM
+    pub fn nonce_len(mut self, nonce_len: usize) -> Self {
+        self.nonce_len = nonce_len;
+        self
+    }
+
+    pub fn build_cbc<P, IV>(&self, padding: P, iv: IV) -> Result<CBC<C, P, IV>, CryptoError>
+        where P: 'static + Padding, IV: InitialVec<C> {
+        CBC::new(self.cipher.clone(), padding, iv)
+    }
+
+    pub fn build_cbc_cts<IV: InitialVec<C>>(&self, iv: IV) -> Result<CBC<C, EmptyPadding, IV>, CryptoError> {
+        let cts = self.cts.ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidParameter,
+            "The CBC ciphertext stealing variant is not set"))?;
+        CBC::new_cts(self.cipher.clone(), cts, iv)
+    }
+
+    pub fn build_cfb<P, IV>(&self, padding: P, iv: IV) -> Result<CFB<C, P, IV>, CryptoError>
+        where P: 'static + Padding, IV: InitialVec<C> {
+        let s = self.segment_bits_len.unwrap_or(self.cipher.block_size().unwrap_or(1) << 3);
+        CFB::new(self.cipher.clone(), padding, iv, s)
+    }
+
+    pub fn build_ofb<IV: InitialVec<C>>(&self, iv: IV) -> Result<OFB<C, IV>, CryptoError> {
+        OFB::new(self.cipher.clone(), iv)
+    }
+
+    pub fn build_ctr<T: Counter>(&self, counter: T) -> Result<CTR<C, T>, CryptoError> {
+        CTR::new(self.cipher.clone(), counter)
+    }
+
+    pub fn build_gcm(&self) -> Result<GCM<C>, CryptoError> {
+        GCM::new(self.cipher.clone(), self.tag_len)
+    }
+
+    pub fn build_ccm(&self) -> Result<CCM<C>, CryptoError> {
+        CCM::new(self.cipher.clone(), self.tag_len, self.nonce_len)
+    }
+}
\ No newline at end of file
@@ -7,21 +7,54 @@
 //! P-1 = CIPH^{-1}_{K}(C_1) \oplus IV;
 //! P_j = CIPH^{-1}_{K}(C_j) \oplus C_{j-1}	 for j = 2 … n
 //! $$
+//!
+//! CBC-CS1/CBC-CS2/CBC-CS3(Addendum to NIST SP 800-38A, the ciphertext stealing):
+//! the plaintext which is not less than one block is encrypted without padding, the last partial block $P_n^*$ is
+//! padded by zeros, and the leftmost $d$ bytes $C_{n-1}^*$ of the penultimate ciphertext block are output. The variants
+//! only differ in the order of the last two blocks:
+//! - CS1: $C_{n-1}^* || C_n$;
+//! - CS2: the same as the CS1 if the plaintext is a multiple of the block length, otherwise $C_n || C_{n-1}^*$;
+//! - CS3: always $C_n || C_{n-1}^*$(the Kerberos AES-CTS, RFC 3962);
 
 use std::cell::Cell;
 use crate::{Cipher, CryptoError, CryptoErrorKind};
-use crate::cipher_mode::{Padding, InitialVec, EncryptStream, Pond, DecryptStream};
+use crate::cipher_mode::{Padding, EmptyPadding, InitialVec, EncryptStream, Pond, DecryptStream};
 use std::marker::PhantomData;
 
+/// The ciphertext stealing variant of the CBC mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CiphertextStealing {
+    CS1,
+    CS2,
+    CS3,
+}
+
 pub struct CBC<C, P, IV> {
     buf: Cell<Vec<u8>>,
     cur_iv: Vec<u8>,
+    init_iv: Vec<u8>,
     cipher: C,
     padding: P,
     iv: IV,
+    cts: Option<CiphertextStealing>,
     phd: PhantomData<*const u8>,
 }
 
+impl<C, IV> CBC<C, EmptyPadding, IV>
+    where C: Cipher, IV: InitialVec<C> {
+
+    /// the CBC with the ciphertext stealing, the data length must not be less than the block length of the block cipher.
+    pub fn new_cts(c: C, cts: CiphertextStealing, iv: IV) -> Result<Self, CryptoError> {
+        if c.block_size().is_none() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The ciphertext stealing needs the block cipher"));
+        }
+
+        let mut cbc = Self::new(c, EmptyPadding::new(), iv)?;
+        cbc.cts = Some(cts);
+        Ok(cbc)
+    }
+}
+
 impl<C, P, IV> CBC<C, P, IV> 
     where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
     
@@ -39,13 +72,20 @@ impl<C, P, IV> CBC<C, P, IV>
         
         Ok(Self {
             buf: Cell::new(Vec::with_capacity(block_len)),
+            init_iv: cur_iv.clone(),
             cur_iv,
             cipher: c,
             padding: p,
             iv,
+            cts: None,
             phd: PhantomData,
         })
     }
+
+    /// the ciphertext stealing variant, `None` means the padding is used
+    pub fn ciphertext_stealing(&self) -> Option<CiphertextStealing> {
+        self.cts
+    }
     
     /// update initialization vectors
     pub fn update_iv(&mut self) -> Result<&Vec<u8>, CryptoError> {
@@ -63,6 +103,31 @@ impl<C, P, IV> CBC<C, P, IV>
         }
     }
     
+    /// reset to the state right after the construction: the IV is restored to the construction-time IV.
+    ///
+    /// # Security
+    ///
+    /// the construction-time IV is reused after the `reset`, it only allows to re-process the same message, e.g.
+    /// decrypt what was just encrypted. Encrypt the next message by the `update_iv` or the `reset_with_iv`.
+    pub fn reset(&mut self) {
+        self.cur_iv.clear();
+        self.cur_iv.extend_from_slice(self.init_iv.as_slice());
+    }
+    
+    /// reset to the fresh `iv` for the next message, the following `reset` restores this IV. The `iv` equal to the
+    /// construction-time IV or the current IV is rejected.
+    pub fn reset_with_iv(&mut self, iv: Vec<u8>) -> Result<(), CryptoError> {
+        if iv == self.init_iv || iv == self.cur_iv {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                                        "The IV must be fresh, it's the same as the construction-time IV or the current IV"));
+        }
+        
+        self.set_iv(iv)?;
+        self.init_iv.clear();
+        self.init_iv.extend_from_slice(self.cur_iv.as_slice());
+        Ok(())
+    }
+    
     pub fn cur_iv(&self) -> Vec<u8> {
         self.cur_iv.clone()
     }
@@ -111,6 +176,109 @@ impl<C, P, IV> CBC<C, P, IV>
             *a = (*a) ^ b;
         });
     }
+
+    /// the stream buffers the last two blocks for the ciphertext stealing
+    fn max_buffered_len(&self) -> usize {
+        let block_len = self.cipher.block_size().unwrap_or(1);
+        if self.cts.is_some() { block_len << 1 } else { block_len }
+    }
+
+    /// encrypt the full blocks and append to the `dst`, the `iv` is updated to the last ciphertext block
+    fn encrypt_blocks(&self, dst: &mut Vec<u8>, data: &[u8], iv: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let block_len = self.cipher.block_size().unwrap_or(1);
+        let mut txt = Vec::with_capacity(block_len);
+        for block in data.chunks_exact(block_len) {
+            Self::xor_iv(block, iv);
+            self.cipher.encrypt(&mut txt, iv.as_slice())?;
+            iv.clear();
+            iv.extend_from_slice(txt.as_slice());
+            dst.extend_from_slice(txt.as_slice());
+        }
+        Ok(())
+    }
+
+    /// decrypt the full blocks and append to the `dst`, the `iv` is updated to the last ciphertext block
+    fn decrypt_blocks(&self, dst: &mut Vec<u8>, data: &[u8], iv: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let block_len = self.cipher.block_size().unwrap_or(1);
+        let mut txt = Vec::with_capacity(block_len);
+        for block in data.chunks_exact(block_len) {
+            self.cipher.decrypt(&mut txt, block)?;
+            dst.extend(txt.iter().zip(iv.iter()).map(|(&a, &b)| a ^ b));
+            iv.clear();
+            iv.extend_from_slice(block);
+        }
+        Ok(())
+    }
+
+    /// the last two ciphertext blocks are swapped
+    fn is_cts_swapped(cts: CiphertextStealing, partial_len: usize) -> bool {
+        cts == CiphertextStealing::CS3 || (cts == CiphertextStealing::CS2 && partial_len != 0)
+    }
+
+    fn encrypt_cts(&self, dst: &mut Vec<u8>, data: &[u8], cts: CiphertextStealing, iv: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let block_len = self.cipher.block_size().unwrap_or(1);
+        if data.len() < block_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong plaintext length: {}, the CBC-CS plaintext length(in bytes) must not be less than {}", data.len(), block_len)));
+        }
+
+        let n = data.len().div_ceil(block_len);
+        let mut padded = data.to_vec();
+        padded.resize(n * block_len, 0);
+        let start = dst.len();
+        self.encrypt_blocks(dst, padded.as_slice(), iv)?;
+
+        let partial_len = data.len() % block_len;
+        if n > 1 && (partial_len != 0 || Self::is_cts_swapped(cts, partial_len)) {
+            let tail = dst.split_off(start + (n - 2) * block_len);
+            let (c_prev, c_last) = tail.split_at(block_len);
+            let d = if partial_len == 0 { block_len } else { partial_len };
+            if Self::is_cts_swapped(cts, partial_len) {
+                dst.extend_from_slice(c_last);
+                dst.extend_from_slice(&c_prev[..d]);
+            } else {
+                dst.extend_from_slice(&c_prev[..d]);
+                dst.extend_from_slice(c_last);
+            }
+        }
+        Ok(())
+    }
+
+    fn decrypt_cts(&self, dst: &mut Vec<u8>, data: &[u8], cts: CiphertextStealing, iv: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let block_len = self.cipher.block_size().unwrap_or(1);
+        if data.len() < block_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong ciphertext length: {}, the CBC-CS ciphertext length(in bytes) must not be less than {}", data.len(), block_len)));
+        }
+
+        let n = data.len().div_ceil(block_len);
+        if n == 1 {
+            return self.decrypt_blocks(dst, data, iv);
+        }
+
+        // recover the full penultimate block by the tail of the CIPH^{-1}(C_n), then decrypt as the CBC
+        let partial_len = data.len() % block_len;
+        let d = if partial_len == 0 { block_len } else { partial_len };
+        let (prefix, tail) = data.split_at((n - 2) * block_len);
+        let (c_prev, c_last) = if Self::is_cts_swapped(cts, partial_len) {
+            (&tail[block_len..], &tail[..block_len])
+        } else {
+            (&tail[..d], &tail[d..])
+        };
+
+        let mut z = Vec::with_capacity(block_len);
+        self.cipher.decrypt(&mut z, c_last)?;
+        let mut full = Vec::with_capacity(n * block_len);
+        full.extend_from_slice(prefix);
+        full.extend_from_slice(c_prev);
+        full.extend_from_slice(&z[d..]);
+        full.extend_from_slice(c_last);
+
+        let start = dst.len();
+        self.decrypt_blocks(dst, full.as_slice(), iv)?;
+        dst.truncate(start + data.len());
+        Ok(())
+    }
 }
 
 impl<C, P, IV> Cipher for CBC<C, P, IV>
@@ -123,6 +291,12 @@ impl<C, P, IV> Cipher for CBC<C, P, IV>
     }
 
     fn encrypt(&self, dst: &mut Vec<u8>, plaintext_block: &[u8]) -> Result<usize, CryptoError> {
+        if let Some(cts) = self.cts {
+            dst.clear();
+            self.encrypt_cts(dst, plaintext_block, cts, &mut self.cur_iv.clone())?;
+            return Ok(dst.len());
+        }
+
         let block_len = self.cipher.block_size().unwrap_or(1);
         let mut data = plaintext_block;
         let mut cur_iv = self.cur_iv.to_vec();
@@ -153,8 +327,11 @@ impl<C, P, IV> Cipher for CBC<C, P, IV>
         while !data.is_empty() {
             let len = std::cmp::min(block_len, data.len());
             let tmp = &data[..len];
-            match self.cipher.encrypt(txt, tmp) {
+            Self::xor_iv(tmp, &mut cur_iv);
+            match self.cipher.encrypt(txt, cur_iv.as_slice()) {
                 Ok(_) => {
+                    cur_iv.clear();
+                    cur_iv.extend_from_slice(txt.as_slice());
                     dst.append(txt);
                     data = &data[len..];
                 },
@@ -168,6 +345,12 @@ impl<C, P, IV> Cipher for CBC<C, P, IV>
     }
 
     fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<usize, CryptoError> {
+        if let Some(cts) = self.cts {
+            dst.clear();
+            self.decrypt_cts(dst, cipher_block, cts, &mut self.cur_iv.clone())?;
+            return Ok(dst.len());
+        }
+
         let block_size = self.cipher.block_size().unwrap_or(1);
 
         if (cipher_block.len() % block_size) != 0 {
@@ -208,9 +391,11 @@ impl<C, P, IV> Clone for CBC<C, P, IV>
         CBC {
             buf: Cell::new(Vec::with_capacity(self.cipher.block_size().unwrap_or(1))),
             cur_iv: self.cur_iv.clone(),
+            init_iv: self.init_iv.clone(),
             cipher: self.cipher.clone(),
             padding: self.padding.clone(),
             iv: self.iv.clone(),
+            cts: self.cts,
             phd: PhantomData,
         }
     }
@@ -234,6 +419,8 @@ impl_cipher_iv!(CBCEncrypt, cbc);
 impl_fn_reset_iv!(CBCEncrypt, cbc);
 impl_cipher_iv!(CBCDecrypt, cbc);
 impl_fn_reset_iv!(CBCDecrypt, cbc);
+impl_stream_state_iv!(CBCEncrypt, cbc, STATE_MODE_CBC_ENCRYPT, max_buffered_len);
+impl_stream_state_iv!(CBCDecrypt, cbc, STATE_MODE_CBC_DECRYPT, max_buffered_len);
 
 impl<C, P, IV> CBCEncrypt<C, P, IV> 
     where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
@@ -243,11 +430,56 @@ impl<C, P, IV> CBCEncrypt<C, P, IV>
             cur.push(a ^ b);
         });
     }
+
+    /// the last two blocks are buffered until the `finish`
+    fn write_cts(&mut self, data: &[u8]) -> Result<Pond<'_>, CryptoError> {
+        let block_len = self.cbc.cipher.block_size().unwrap_or(1);
+        self.data.extend_from_slice(data);
+        let n = self.data.len().saturating_sub(block_len << 1).div_ceil(block_len);
+        let rest = self.data.split_off(n * block_len);
+        self.cbc.encrypt_blocks(&mut self.pond, self.data.as_slice(), &mut self.ij)?;
+        self.data = rest;
+        Ok(Pond::new(&mut self.pond, false))
+    }
+
+    fn finish_cts(&mut self, cts: CiphertextStealing) -> Result<Pond<'_>, CryptoError> {
+        self.cbc.encrypt_cts(&mut self.pond, self.data.as_slice(), cts, &mut self.ij)?;
+        self.ij.clear();
+        self.ij.extend(self.cbc.cur_iv.iter());
+        self.data.clear();
+        Ok(Pond::new(&mut self.pond, true))
+    }
+}
+
+impl<C, P, IV> CBCDecrypt<C, P, IV>
+    where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
+    /// the last two blocks are buffered until the `finish`
+    fn write_cts(&mut self, data: &[u8]) -> Result<Pond<'_>, CryptoError> {
+        let block_len = self.cbc.cipher.block_size().unwrap_or(1);
+        self.data.extend_from_slice(data);
+        let n = self.data.len().saturating_sub(block_len << 1).div_ceil(block_len);
+        let rest = self.data.split_off(n * block_len);
+        self.cbc.decrypt_blocks(&mut self.pond, self.data.as_slice(), &mut self.ij)?;
+        self.data = rest;
+        Ok(Pond::new(&mut self.pond, false))
+    }
+
+    fn finish_cts(&mut self, cts: CiphertextStealing) -> Result<Pond<'_>, CryptoError> {
+        self.cbc.decrypt_cts(&mut self.pond, self.data.as_slice(), cts, &mut self.ij)?;
+        self.ij.clear();
+        self.ij.extend(self.cbc.cur_iv.iter());
+        self.data.clear();
+        Ok(Pond::new(&mut self.pond, true))
+    }
 }
 
 impl<C, P, IV> EncryptStream for CBCEncrypt<C, P, IV> 
     where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
     fn write(&mut self, data: &[u8]) -> Result<Pond, CryptoError> {
+        if self.cbc.cts.is_some() {
+            return self.write_cts(data);
+        }
+
         let block_len = self.cbc.cipher.block_size().unwrap_or(1);
         let mut data = data;
         
@@ -293,6 +525,10 @@ impl<C, P, IV> EncryptStream for CBCEncrypt<C, P, IV>
     }
 
     fn finish(&mut self) -> Result<Pond, CryptoError> {
+        if let Some(cts) = self.cbc.cts {
+            return self.finish_cts(cts);
+        }
+
         self.cbc.padding.padding(&mut self.data);
 
         let block_len = self.cbc.cipher.block_size().unwrap_or(1);
@@ -304,8 +540,7 @@ impl<C, P, IV> EncryptStream for CBCEncrypt<C, P, IV>
             Self::xor_iv(txt, tmp, &self.ij);
             match self.cbc.cipher.encrypt(&mut self.ij, txt.as_slice()) {
                 Ok(_) => {
-                    self.pond.append(&mut self.ij);
-                    self.ij.extend(self.cbc.cur_iv.iter());
+                    self.pond.extend(self.ij.iter());
                     data = &data[len..];
                 },
                 Err(e) => {
@@ -314,6 +549,8 @@ impl<C, P, IV> EncryptStream for CBCEncrypt<C, P, IV>
             }
         }
         
+        self.ij.clear();
+        self.ij.extend(self.cbc.cur_iv.iter());
         self.data.clear();
         Ok(Pond::new(&mut self.pond, true))
     }
@@ -323,6 +560,10 @@ impl<C, P, IV> EncryptStream for CBCEncrypt<C, P, IV>
 impl<C, P, IV> DecryptStream for CBCDecrypt<C, P, IV>
     where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
     fn write(&mut self, data: &[u8]) -> Result<Pond, CryptoError> {
+        if self.cbc.cts.is_some() {
+            return self.write_cts(data);
+        }
+
         let block_len = self.cbc.cipher.block_size().unwrap_or(1);
         
         if data.is_empty() {
@@ -358,6 +599,10 @@ impl<C, P, IV> DecryptStream for CBCDecrypt<C, P, IV>
     }
 
     fn finish(&mut self) -> Result<Pond, CryptoError> {
+        if let Some(cts) = self.cbc.cts {
+            return self.finish_cts(cts);
+        }
+
         let txt = self.cbc.get_buf();
         match self.cbc.cipher.decrypt(txt, self.data.as_slice()) {
             Ok(_) => {
@@ -0,0 +1,199 @@
+//! CCM(Counter with CBC-MAC)
+//! NIST SP 800-38C
+//!
+//! B_0 = flags || N || Q, flags = Adata || [(t-2)/2]_3 || [q-1]_3;
+//! T = MSB_t(CBC-MAC(B_0 || encode(A) || P)), C = (P xor MSB_{len(P)}(S_1 || S_2 || ...)) || (T xor MSB_t(S_0)),
+//! where the counter block Ctr_i = [q-1]_8 || N || [i]_{8q}, S_i = CIPH_K(Ctr_i).
+
+use crate::{Cipher, CryptoError, CryptoErrorKind, AEAD};
+use crate::cipher_mode::DataLimit;
+use crate::cipher_mode::limits::limit_err;
+
+const CCM_BLOCK_LEN: usize = 16;
+
+pub struct CCM<C> {
+    cipher: C,
+    tag_len: usize,
+    nonce_len: usize,
+}
+
+impl<C: Cipher> CCM<C> {
+    /// `cipher`: the block cipher with the 128 bits block size, e.g. AES;
+    /// `tag_len`: the tag length(in bytes) `t`, it must be one of the 4, 6, 8, 10, 12, 14, 16;
+    /// `nonce_len`: the nonce length(in bytes) `n`, it must be in the range [7, 13], the maximum plaintext
+    /// length is $2^{8(15-n)} - 1$;
+    pub fn new(cipher: C, tag_len: usize, nonce_len: usize) -> Result<Self, CryptoError> {
+        if cipher.block_size() != Some(CCM_BLOCK_LEN) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The CCM only support the block cipher with the 128 bits block size"))
+        } else if !(4..=16).contains(&tag_len) || (tag_len & 1) != 0 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Wrong tag length: {}, it must be one of the 4, 6, 8, 10, 12, 14, 16", tag_len)))
+        } else if !(7..=13).contains(&nonce_len) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Wrong nonce length: {}, it must be in the range [7, 13]", nonce_len)))
+        } else {
+            Ok(Self { cipher, tag_len, nonce_len })
+        }
+    }
+
+    /// the CCM keeps no state between the messages, so the reset is no-op, it's for the uniform reuse of the modes.
+    pub fn reset(&mut self) {}
+
+    fn check_params(&self, nonce: &[u8], data_len: usize) -> Result<(), CryptoError> {
+        if nonce.len() != self.nonce_len {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong nonce length: {}, the nonce length must be {}", nonce.len(), self.nonce_len)))
+        } else if (data_len as u128) > self.max_data_len() {
+            limit_err(format!("The data is too long for the nonce length {}", self.nonce_len))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// the maximum payload length of one nonce: $2^{8q} - 1$, q = 15 - n
+    fn max_data_len(&self) -> u128 {
+        (1u128 << ((15 - self.nonce_len) << 3)) - 1
+    }
+
+    fn encrypt_block(&self, block: &[u8], buf: &mut Vec<u8>) -> Result<(), CryptoError> {
+        self.cipher.encrypt(buf, block)?;
+        Ok(())
+    }
+
+    /// the counter block Ctr_i
+    fn counter_block(&self, nonce: &[u8], i: u64) -> [u8; CCM_BLOCK_LEN] {
+        let mut ctr = [0u8; CCM_BLOCK_LEN];
+        ctr[0] = (14 - self.nonce_len) as u8;
+        ctr[1..=self.nonce_len].copy_from_slice(nonce);
+        let i = i.to_be_bytes();
+        let q = 15 - self.nonce_len;
+        ctr[(CCM_BLOCK_LEN - q.min(8))..].copy_from_slice(&i[(8 - q.min(8))..]);
+        ctr
+    }
+
+    /// the unencrypted tag T
+    fn cbc_mac(&self, nonce: &[u8], aad: &[u8], payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let q = 15 - self.nonce_len;
+        let mut b = Vec::with_capacity(CCM_BLOCK_LEN + aad.len() + payload.len() + 42);
+
+        // B_0
+        let adata = if aad.is_empty() { 0u8 } else { 0x40 };
+        b.push(adata | ((((self.tag_len - 2) >> 1) as u8) << 3) | ((q - 1) as u8));
+        b.extend_from_slice(nonce);
+        let plen = (payload.len() as u64).to_be_bytes();
+        b.resize(CCM_BLOCK_LEN - q.min(8), 0);
+        b.extend_from_slice(&plen[(8 - q.min(8))..]);
+
+        // the encoding of the associated data length
+        let alen = aad.len() as u64;
+        if alen > 0 {
+            if alen < 0xff00 {
+                b.extend_from_slice((alen as u16).to_be_bytes().as_ref());
+            } else if alen <= (u32::MAX as u64) {
+                b.extend_from_slice(&[0xff, 0xfe]);
+                b.extend_from_slice((alen as u32).to_be_bytes().as_ref());
+            } else {
+                b.extend_from_slice(&[0xff, 0xff]);
+                b.extend_from_slice(alen.to_be_bytes().as_ref());
+            }
+            b.extend_from_slice(aad);
+            b.resize(b.len().div_ceil(CCM_BLOCK_LEN) * CCM_BLOCK_LEN, 0);
+        }
+        b.extend_from_slice(payload);
+        b.resize(b.len().div_ceil(CCM_BLOCK_LEN) * CCM_BLOCK_LEN, 0);
+
+        let (mut y, mut buf) = ([0u8; CCM_BLOCK_LEN], Vec::with_capacity(CCM_BLOCK_LEN));
+        for block in b.chunks(CCM_BLOCK_LEN) {
+            y.iter_mut().zip(block.iter()).for_each(|(a, &b)| *a ^= b);
+            self.encrypt_block(y.as_ref(), &mut buf)?;
+            y.copy_from_slice(buf.as_slice());
+        }
+
+        Ok(y[..self.tag_len].to_vec())
+    }
+
+    /// xor the `data` with the key stream S_1 || S_2 ||...
+    fn ctr_crypt(&self, nonce: &[u8], data: &[u8], dst: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let mut buf = Vec::with_capacity(CCM_BLOCK_LEN);
+        for (i, block) in data.chunks(CCM_BLOCK_LEN).enumerate() {
+            self.encrypt_block(self.counter_block(nonce, (i + 1) as u64).as_ref(), &mut buf)?;
+            dst.extend(block.iter().zip(buf.iter()).map(|(&a, &b)| a ^ b));
+        }
+        Ok(())
+    }
+
+    /// T xor MSB_t(S_0)
+    fn encrypt_tag(&self, nonce: &[u8], tag: &mut [u8]) -> Result<(), CryptoError> {
+        let mut s0 = Vec::with_capacity(CCM_BLOCK_LEN);
+        self.encrypt_block(self.counter_block(nonce, 0).as_ref(), &mut s0)?;
+        tag.iter_mut().zip(s0.iter()).for_each(|(a, &b)| *a ^= b);
+        Ok(())
+    }
+
+    /// encrypt and authenticate the `plaintext` and `aad`, the `ciphertext||tag` will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.check_params(nonce, plaintext.len())?;
+
+        dst.clear();
+        let mut tag = self.cbc_mac(nonce, aad, plaintext)?;
+        self.ctr_crypt(nonce, plaintext, dst)?;
+        self.encrypt_tag(nonce, tag.as_mut_slice())?;
+        dst.extend_from_slice(tag.as_slice());
+        Ok(())
+    }
+
+    /// verify and decrypt the `ciphertext||tag`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if ciphertext.len() < self.tag_len {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the tag"));
+        }
+        let (ct, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len);
+        self.check_params(nonce, ct.len())?;
+
+        self.ctr_crypt(nonce, ct, dst)?;
+        let mut expected = self.cbc_mac(nonce, aad, dst.as_slice())?;
+        self.encrypt_tag(nonce, expected.as_mut_slice())?;
+
+        if expected.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            dst.iter_mut().for_each(|x| *x = 0);
+            dst.clear();
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The CCM tag not match"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<C: Cipher> AEAD for CCM<C> {
+    fn nonce_len(&self) -> usize {
+        self.nonce_len
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, nonce, aad, plaintext)
+    }
+
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.decrypt(dst, nonce, aad, ciphertext)
+    }
+}
+
+impl<C: Cipher> DataLimit for CCM<C> {
+    /// the maximum payload length of one nonce
+    fn remaining_bytes(&self) -> Option<u128> {
+        Some(self.max_data_len())
+    }
+}
+
+impl<C: Cipher + Clone> Clone for CCM<C> {
+    fn clone(&self) -> Self {
+        Self {
+            cipher: self.cipher.clone(),
+            tag_len: self.tag_len,
+            nonce_len: self.nonce_len,
+        }
+    }
+}
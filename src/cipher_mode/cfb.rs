@@ -9,6 +9,7 @@ pub struct CFB<C, P, IV> {
     s: usize,
     buf: Cell<Vec<u8>>,
     cur_iv: Vec<u8>,
+    init_iv: Vec<u8>,
     cipher: C,
     padding: P,
     iv: IV,
@@ -53,6 +54,7 @@ impl<C, P, IV> CFB<C, P, IV>
         let block_len = c.block_size().unwrap_or(1);
         Ok(Self {
             buf: Cell::new(Vec::with_capacity(block_len)),
+            init_iv: curiv.clone(),
             cur_iv: curiv,
             cipher: c,
             padding: p,
@@ -78,6 +80,31 @@ impl<C, P, IV> CFB<C, P, IV>
         }
     }
     
+    /// reset to the state right after the construction: the IV is restored to the construction-time IV.
+    ///
+    /// # Security
+    ///
+    /// the construction-time IV is reused after the `reset`, it only allows to re-process the same message, e.g.
+    /// decrypt what was just encrypted. Encrypt the next message by the `update_iv` or the `reset_with_iv`.
+    pub fn reset(&mut self) {
+        self.cur_iv.clear();
+        self.cur_iv.extend_from_slice(self.init_iv.as_slice());
+    }
+    
+    /// reset to the fresh `iv` for the next message, the following `reset` restores this IV. The `iv` equal to the
+    /// construction-time IV or the current IV is rejected.
+    pub fn reset_with_iv(&mut self, iv: Vec<u8>) -> Result<(), CryptoError> {
+        if iv == self.init_iv || iv == self.cur_iv {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                                        "The IV must be fresh, it's the same as the construction-time IV or the current IV"));
+        }
+        
+        self.set_iv(iv)?;
+        self.init_iv.clear();
+        self.init_iv.extend_from_slice(self.cur_iv.as_slice());
+        Ok(())
+    }
+    
     pub fn cur_iv(&self) -> Vec<u8> {
         self.cur_iv.clone()
     }
@@ -225,6 +252,7 @@ impl<C, P, IV> Clone for CFB<C, P, IV>
             s: self.s,
             buf: Cell::new(Vec::with_capacity(self.cipher.block_size().unwrap_or(1))),
             cur_iv: self.cur_iv.clone(),
+            init_iv: self.init_iv.clone(),
             cipher: self.cipher.clone(),
             padding: self.padding.clone(),
             iv: self.iv.clone(),
@@ -251,6 +279,8 @@ impl_cipher_iv!(CFBEncrypt, cfb);
 impl_fn_reset_iv!(CFBEncrypt, cfb);
 impl_cipher_iv!(CFBDecrypt, cfb);
 impl_fn_reset_iv!(CFBDecrypt, cfb);
+impl_stream_state_iv!(CFBEncrypt, cfb, STATE_MODE_CFB_ENCRYPT);
+impl_stream_state_iv!(CFBDecrypt, cfb, STATE_MODE_CFB_DECRYPT);
 
 impl<C, P, IV> EncryptStream for CFBEncrypt<C, P, IV> 
     where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
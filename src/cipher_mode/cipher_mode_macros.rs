@@ -101,3 +101,105 @@ macro_rules! impl_fn_reset_ofb {
     };
 }
 
+
+
+macro_rules! impl_stream_state {
+    ($Type0: ident, $INS: ident, $MODE: ident) => {
+        impl<C, P> $crate::cipher_mode::StreamState for $Type0<C, P>
+            where C: Cipher, P: 'static + Padding {
+            fn save_state(&self) -> Result<Vec<u8>, CryptoError> {
+                $crate::cipher_mode::stream_state::encode_state($crate::cipher_mode::stream_state::$MODE, 
+                    self.$INS.block_size().unwrap_or(1), &[self.data.as_slice(), self.pond.as_slice()])
+            }
+            
+            fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError> {
+                let block_len = self.$INS.block_size().unwrap_or(1);
+                let mut fields = $crate::cipher_mode::stream_state::decode_state(state, 
+                    $crate::cipher_mode::stream_state::$MODE, block_len, 2)?;
+                if fields[0].len() > block_len {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid stream state: the buffered data too long"));
+                }
+                
+                self.pond = fields.pop().unwrap();
+                self.data = fields.pop().unwrap();
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! impl_stream_state_iv {
+    ($Type0: ident, $INS: ident, $MODE: ident) => {
+        impl<C, P, IV> $crate::cipher_mode::StreamState for $Type0<C, P, IV>
+            where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
+            fn save_state(&self) -> Result<Vec<u8>, CryptoError> {
+                $crate::cipher_mode::stream_state::encode_state($crate::cipher_mode::stream_state::$MODE, 
+                    self.$INS.block_size().unwrap_or(1), &[self.data.as_slice(), self.pond.as_slice(), self.ij.as_slice()])
+            }
+            
+            fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError> {
+                let block_len = self.$INS.block_size().unwrap_or(1);
+                let mut fields = $crate::cipher_mode::stream_state::decode_state(state, 
+                    $crate::cipher_mode::stream_state::$MODE, block_len, 3)?;
+                if fields[0].len() > block_len || fields[2].len() != self.$INS.cur_iv.len() {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid stream state: wrong buffered data or chaining value length"));
+                }
+                
+                self.ij = fields.pop().unwrap();
+                self.pond = fields.pop().unwrap();
+                self.data = fields.pop().unwrap();
+                Ok(())
+            }
+        }
+    };
+    // the buffered data is not more than the `$BUF_LEN` of the mode, rather than the block length
+    ($Type0: ident, $INS: ident, $MODE: ident, $BUF_LEN: ident) => {
+        impl<C, P, IV> $crate::cipher_mode::StreamState for $Type0<C, P, IV>
+            where C: Cipher, P: 'static + Padding, IV: InitialVec<C> {
+            fn save_state(&self) -> Result<Vec<u8>, CryptoError> {
+                $crate::cipher_mode::stream_state::encode_state($crate::cipher_mode::stream_state::$MODE, 
+                    self.$INS.block_size().unwrap_or(1), &[self.data.as_slice(), self.pond.as_slice(), self.ij.as_slice()])
+            }
+            
+            fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError> {
+                let block_len = self.$INS.block_size().unwrap_or(1);
+                let mut fields = $crate::cipher_mode::stream_state::decode_state(state, 
+                    $crate::cipher_mode::stream_state::$MODE, block_len, 3)?;
+                if fields[0].len() > self.$INS.$BUF_LEN() || fields[2].len() != self.$INS.cur_iv.len() {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid stream state: wrong buffered data or chaining value length"));
+                }
+                
+                self.ij = fields.pop().unwrap();
+                self.pond = fields.pop().unwrap();
+                self.data = fields.pop().unwrap();
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! impl_stream_state_ofb {
+    ($Type0: ident, $INS: ident, $MODE: ident) => {
+        impl<C, IV> $crate::cipher_mode::StreamState for $Type0<C, IV>
+            where C: Cipher, IV: InitialVec<C> {
+            fn save_state(&self) -> Result<Vec<u8>, CryptoError> {
+                $crate::cipher_mode::stream_state::encode_state($crate::cipher_mode::stream_state::$MODE, 
+                    self.$INS.block_size().unwrap_or(1), &[self.data.as_slice(), self.pond.as_slice(), self.ij.as_slice()])
+            }
+            
+            fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError> {
+                let block_len = self.$INS.block_size().unwrap_or(1);
+                let mut fields = $crate::cipher_mode::stream_state::decode_state(state, 
+                    $crate::cipher_mode::stream_state::$MODE, block_len, 3)?;
+                if fields[0].len() > block_len || fields[2].len() != self.$INS.cur_iv.len() {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid stream state: wrong buffered data or chaining value length"));
+                }
+                
+                self.ij = fields.pop().unwrap();
+                self.pond = fields.pop().unwrap();
+                self.data = fields.pop().unwrap();
+                Ok(())
+            }
+        }
+    };
+}
@@ -1,6 +1,10 @@
-use crate::cipher_mode::{ECB, EmptyPadding, EncryptStream, DecryptStream, CBC, DefaultInitialVec, CFB, OFB, DefaultCounter, CTR};
-use crate::{TDES, Cipher};
-use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::cipher_mode::{ECB, EmptyPadding, EncryptStream, DecryptStream, CBC, CiphertextStealing, DefaultInitialVec, CFB, OFB, DefaultCounter, CTR, PKCS7Padding, FixedInitialVec, StreamState, CtrDrbg, DrbgInitialVec, InitialVec, IvCounterStore, FileIvCounterStore, CounterInitialVec, StoredCounter, CCM, GCM, XTS, KeyWrap, DataLimit, KeyUsageLimit, FF1, NumeralAlphabet, SIV, ModeBuilder};
+use crate::{TDES, Cipher, AEAD, CryptoError, CryptoErrorKind};
+use crate::encoding::Hex;
+use crate::aead::{Nonce, Iv, Tag};
+use rmath::rand::{CryptoRand, DefaultSeed, Source};
+use std::rc::Rc;
+use std::cell::RefCell;
 use crate::aes::AES;
 
 #[test]
@@ -190,6 +194,109 @@ fn cbc_test() {
     }
 }
 
+#[test]
+fn cbc_pkcs7() {
+    let cases = [
+        (
+            vec![0x6BC1BEE2u32, 0x2E409F96, 0xE93D7E11, 0x7393172A, 0xAE2D8A57, 0x1E03AC9C, 0x9EB76FAC, 0x45AF8E51, 0x30C81C46, 0xA35CE411,],
+            vec![0x7649ABACu32, 0x8119B246, 0xCEE98E9B, 0x12E9197D, 0x5086CB9B, 0x507219EE, 0x95DB113A, 0x917678B2, 0x5CACA761, 0x45CFFC65, 0xF7483608, 0xC531AEF1,],
+        ),
+        (
+            vec![0x6BC1BEE2u32, 0x2E409F96, 0xE93D7E11, 0x7393172A, 0xAE2D8A57, 0x1E03AC9C, 0x9EB76FAC, 0x45AF8E51, 0x30C81C46, 0xA35CE411, 0xE5FBC119, 0x1A0A52EF,],
+            vec![0x7649ABACu32, 0x8119B246, 0xCEE98E9B, 0x12E9197D, 0x5086CB9B, 0x507219EE, 0x95DB113A, 0x917678B2, 0x73BED6B8, 0xE3C1743B, 0x7116E69E, 0x22229516, 0xF6ECCDA3, 0x27BF8E5E, 0xC43718B0, 0x039ADCEB,],
+        ),
+    ];
+
+    let key = vec![0x2Bu8, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C];
+    let iv = (0..16u8).collect::<Vec<_>>();
+    let (mut buf, mut tmp, mut txt) = (Vec::new(), Vec::new(), Vec::new());
+    for (i, ele) in cases.iter().enumerate() {
+        let aes = AES::new(key.clone()).unwrap();
+        let p = PKCS7Padding::new(&aes);
+        let cm = CBC::new(aes, p, FixedInitialVec::new(iv.clone())).unwrap();
+        txt.clear();
+        ele.0.iter().for_each(|&x| txt.extend_from_slice(x.to_be_bytes().as_ref()));
+        tmp.clear();
+        ele.1.iter().for_each(|&x| tmp.extend_from_slice(x.to_be_bytes().as_ref()));
+
+        cm.encrypt(&mut buf, txt.as_slice()).unwrap();
+        assert_eq!(tmp, buf, "encrypt-case: {}", i);
+        cm.decrypt(&mut buf, tmp.as_slice()).unwrap();
+        assert_eq!(txt, buf, "decrypt-case: {}", i);
+
+        let (mut cm_encrypt, mut cm_decrypt) = (cm.clone().encrypt_stream(), cm.decrypt_stream());
+        txt.chunks(7).for_each(|x| {cm_encrypt.write(x).unwrap();});
+        buf.clear();
+        cm_encrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(tmp, buf, "encrypt-stream-case: {}", i);
+        
+        tmp.chunks(5).for_each(|x| {cm_decrypt.write(x).unwrap();});
+        buf.clear();
+        cm_decrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(txt, buf, "decrypt-stream-case: {}", i);
+    }
+    
+    let aes = AES::new(key).unwrap();
+    let p = PKCS7Padding::new(&aes);
+    let cm = CBC::new(aes, p, FixedInitialVec::new(iv)).unwrap();
+    cm.encrypt(&mut tmp, &[0x80u8; 32]).unwrap();
+    let n = tmp.len();
+    tmp[n - 17] ^= 0x01;
+    assert!(cm.decrypt(&mut buf, tmp.as_slice()).is_err());
+}
+
+#[test]
+fn cbc_cts() {
+    // RFC 3962 Appendix B(CS3), the CS1 and CS2 differ in the order of the last two blocks
+    let cases = [
+        (CiphertextStealing::CS3, 17, "c6353568f2bf8cb4d8a580362da7ff7f97"),
+        (CiphertextStealing::CS3, 31, "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5"),
+        (CiphertextStealing::CS3, 32, "39312523a78662d5be7fcbcc98ebf5a897687268d6ecccc0c07b25e25ecfe584"),
+        (CiphertextStealing::CS3, 47, "97687268d6ecccc0c07b25e25ecfe584b3fffd940c16a18c1b5549d2f838029e39312523a78662d5be7fcbcc98ebf5"),
+        (CiphertextStealing::CS3, 48, "97687268d6ecccc0c07b25e25ecfe5849dad8bbb96c4cdc03bc103e1a194bbd839312523a78662d5be7fcbcc98ebf5a8"),
+        (CiphertextStealing::CS3, 64, "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a84807efe836ee89a526730dbc2f7bc8409dad8bbb96c4cdc03bc103e1a194bbd8"),
+        (CiphertextStealing::CS1, 17, "97c6353568f2bf8cb4d8a580362da7ff7f"),
+        (CiphertextStealing::CS1, 47, "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5b3fffd940c16a18c1b5549d2f838029e"),
+        (CiphertextStealing::CS1, 48, "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a89dad8bbb96c4cdc03bc103e1a194bbd8"),
+        (CiphertextStealing::CS2, 31, "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5"),
+        (CiphertextStealing::CS2, 32, "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a8"),
+        (CiphertextStealing::CS2, 16, "97687268d6ecccc0c07b25e25ecfe584"),
+    ];
+
+    let key = b"chicken teriyaki".to_vec();
+    let msg = b"I would like the General Gau's Chicken, please, and wonton soup.";
+    let mut buf = Vec::new();
+    for (i, ele) in cases.iter().enumerate() {
+        let cm = CBC::new_cts(AES::new(key.clone()).unwrap(), ele.0, FixedInitialVec::new(vec![0u8; 16])).unwrap();
+        assert_eq!(cm.ciphertext_stealing(), Some(ele.0));
+        let (txt, tgt) = (&msg[..ele.1], Hex::lower().decode(ele.2).unwrap());
+
+        cm.encrypt(&mut buf, txt).unwrap();
+        assert_eq!(tgt, buf, "encrypt-case: {}", i);
+        cm.decrypt(&mut buf, tgt.as_slice()).unwrap();
+        assert_eq!(txt, buf.as_slice(), "decrypt-case: {}", i);
+
+        let (mut cm_encrypt, mut cm_decrypt) = (cm.clone().encrypt_stream(), cm.decrypt_stream());
+        buf.clear();
+        txt.chunks(7).for_each(|x| { cm_encrypt.write(x).unwrap().draw_off(&mut buf); });
+        cm_encrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(tgt, buf, "encrypt-stream-case: {}", i);
+
+        buf.clear();
+        tgt.chunks(5).for_each(|x| { cm_decrypt.write(x).unwrap().draw_off(&mut buf); });
+        cm_decrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(txt, buf.as_slice(), "decrypt-stream-case: {}", i);
+    }
+
+    let cm = CBC::new_cts(AES::new(key).unwrap(), CiphertextStealing::CS3, FixedInitialVec::new(vec![0u8; 16])).unwrap();
+    assert_eq!(cm.encrypt(&mut buf, &msg[..15]).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(cm.decrypt(&mut buf, &msg[..15]).is_err());
+    let mut cm_encrypt = cm.encrypt_stream();
+    cm_encrypt.write(&msg[..40]).unwrap();
+    let state = cm_encrypt.save_state().unwrap();
+    cm_encrypt.restore_state(state.as_slice()).unwrap();
+}
+
 #[test]
 fn cfb_test() {
     let cases = [
@@ -451,4 +558,736 @@ fn ctr_test() {
         });
         assert_eq!(tmp, buf, "decrypt-case: {}", i);
     }
+}
+#[test]
+fn stream_state() {
+    let key = vec![0x2Bu8, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C];
+    let iv = (0..16u8).collect::<Vec<_>>();
+    let txt = (0..100u8).collect::<Vec<_>>();
+    let (mut tgt, mut buf) = (Vec::new(), Vec::new());
+    
+    // CBC: suspend in the middle of the block, and the undrawn output is kept in the state
+    let aes = AES::new(key.clone()).unwrap();
+    let p = PKCS7Padding::new(&aes);
+    let cbc = CBC::new(aes, p, FixedInitialVec::new(iv.clone())).unwrap();
+    cbc.encrypt(&mut tgt, txt.as_slice()).unwrap();
+    for &n in [0usize, 7, 16, 37, 100].iter() {
+        let mut cm_encrypt = cbc.clone().encrypt_stream();
+        buf.clear();
+        cm_encrypt.write(&txt[..(n / 2)]).unwrap().draw_off(&mut buf);
+        let _ = cm_encrypt.write(&txt[(n / 2)..n]).unwrap();
+        let state = cm_encrypt.save_state().unwrap();
+        drop(cm_encrypt);
+        
+        let mut cm_encrypt = cbc.clone().encrypt_stream();
+        cm_encrypt.restore_state(state.as_slice()).unwrap();
+        cm_encrypt.write(&txt[n..]).unwrap().draw_off(&mut buf);
+        cm_encrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(buf, tgt, "cbc-encrypt-case: {}", n);
+        
+        let mut cm_decrypt = cbc.clone().decrypt_stream();
+        buf.clear();
+        cm_decrypt.write(&tgt[..n]).unwrap().draw_off(&mut buf);
+        let state = cm_decrypt.save_state().unwrap();
+        let mut cm_decrypt = cbc.clone().decrypt_stream();
+        cm_decrypt.restore_state(state.as_slice()).unwrap();
+        cm_decrypt.write(&tgt[n..]).unwrap().draw_off(&mut buf);
+        cm_decrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(buf, txt, "cbc-decrypt-case: {}", n);
+    }
+    
+    // CTR
+    let ctr = CTR::new(AES::new(key.clone()).unwrap(), DefaultCounter::new(iv.clone(), 128).unwrap()).unwrap();
+    ctr.encrypt(&mut tgt, txt.as_slice()).unwrap();
+    for &n in [0usize, 7, 16, 37, 100].iter() {
+        let mut cm_encrypt = ctr.clone().encrypt_stream();
+        buf.clear();
+        cm_encrypt.write(&txt[..n]).unwrap().draw_off(&mut buf);
+        let state = cm_encrypt.save_state().unwrap();
+        let mut cm_encrypt = ctr.clone().encrypt_stream();
+        cm_encrypt.restore_state(state.as_slice()).unwrap();
+        cm_encrypt.write(&txt[n..]).unwrap().draw_off(&mut buf);
+        cm_encrypt.finish().unwrap().draw_off(&mut buf);
+        assert_eq!(buf, tgt, "ctr-case: {}", n);
+    }
+    
+    // the corrupted, truncated or mismatched state
+    let mut cm_encrypt = cbc.clone().encrypt_stream();
+    let _ = cm_encrypt.write(&txt[..20]).unwrap();
+    let mut state = cm_encrypt.save_state().unwrap();
+    let mut cm = cbc.clone().encrypt_stream();
+    assert!(cm.restore_state(&state[..(state.len() - 1)]).is_err());
+    assert!(cbc.clone().decrypt_stream().restore_state(state.as_slice()).is_err());
+    assert!(ctr.clone().encrypt_stream().restore_state(state.as_slice()).is_err());
+    state[10] ^= 1;
+    assert!(cm.restore_state(state.as_slice()).is_err());
+    buf.clear();
+    cm.finish().unwrap().draw_off(&mut buf);
+    assert_eq!(buf.len(), 16);
+}
+
+#[test]
+fn ccm_aes() {
+    // NIST SP 800-38C Appendix C
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    let key = hex("404142434445464748494a4b4c4d4e4f");
+    let cases = [
+        (4, "10111213141516", "0001020304050607", "20212223", "7162015b4dac255d"),
+        (6, "1011121314151617", "000102030405060708090a0b0c0d0e0f", "202122232425262728292a2b2c2d2e2f", 
+            "d2a1f0e051ea5f62081a7792073d593d1fc64fbfaccd"),
+        (8, "101112131415161718191a1b", "000102030405060708090a0b0c0d0e0f10111213", 
+            "202122232425262728292a2b2c2d2e2f3031323334353637", "e3b201a9f5b71a7a9b1ceaeccd97e70b6176aad9a4428aa5484392fbc1b09951"),
+    ];
+    
+    let (mut c, mut p) = (Vec::new(), Vec::new());
+    for (i, &(t, n, a, m, tgt)) in cases.iter().enumerate() {
+        let (n, a, m) = (hex(n), hex(a), hex(m));
+        let ccm = CCM::new(AES::new(key.clone()).unwrap(), t, n.len()).unwrap();
+        ccm.encrypt(&mut c, n.as_slice(), a.as_slice(), m.as_slice()).unwrap();
+        assert_eq!(c, hex(tgt), "case: {}", i);
+        ccm.open(&mut p, n.as_slice(), a.as_slice(), c.as_slice()).unwrap();
+        assert_eq!(p, m, "case: {}", i);
+        
+        let mut bad = c.clone();
+        *bad.last_mut().unwrap() ^= 1;
+        assert_eq!(ccm.decrypt(&mut p, n.as_slice(), a.as_slice(), bad.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(p.is_empty());
+        assert!(ccm.decrypt(&mut p, n.as_slice(), &a[1..], c.as_slice()).is_err());
+    }
+    
+    // the empty associated data, and the long associated data with the 0xff 0xfe length encoding
+    let ccm = CCM::new(AES::new(key.clone()).unwrap(), 16, 13).unwrap();
+    let (n, a) = (vec![0x5au8; 13], vec![0xa5u8; 0x10000]);
+    for aad in [&a[..0], &a[..0xfeff], a.as_slice()].iter() {
+        ccm.seal(&mut c, n.as_slice(), aad, b"the plaintext").unwrap();
+        ccm.open(&mut p, n.as_slice(), aad, c.as_slice()).unwrap();
+        assert_eq!((c.len(), p.as_slice()), (13 + 16, b"the plaintext".as_ref()));
+    }
+    
+    assert!(CCM::new(AES::new(key.clone()).unwrap(), 5, 13).is_err());
+    assert!(CCM::new(AES::new(key.clone()).unwrap(), 16, 14).is_err());
+    assert!(CCM::new(TDES::new([1u8; 8], [2u8; 8], [3u8; 8]), 16, 13).is_err());
+    assert!(ccm.seal(&mut c, &n[1..], b"", b"").is_err());
+    // the plaintext length is limited to 2^16-1 by the 13 bytes nonce
+    assert!(ccm.seal(&mut c, n.as_slice(), b"", a.as_slice()).is_err());
+}
+
+#[test]
+fn gcm_aes() {
+    // The Galois/Counter Mode of Operation(GCM), Appendix B test case 1-6
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    let (k, a) = ("feffe9928665731c6d6a8f9467308308", "feedfacedeadbeeffeedfacedeadbeefabaddad2");
+    let p = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+    let cases = [
+        ("00000000000000000000000000000000", "000000000000000000000000", "", "", "", "58e2fccefa7e3061367f1d57a4e7455a"),
+        ("00000000000000000000000000000000", "000000000000000000000000", "", "00000000000000000000000000000000",
+            "0388dace60b6a392f328c2b971b2fe78", "ab6e47d42cec13bdf53a67b21257bddf"),
+        (k, "cafebabefacedbaddecaf888", a, p,
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+            "5bc94fbc3221a5db94fae95ae7121a47"),
+        (k, "cafebabefacedbad", a, p,
+            "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c742373806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598",
+            "3612d2e79e3b0785561be14aaca2fccb"),
+        (k, "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b", a, p,
+            "8ce24998625615b603a033aca13fb894be9112a5c3a211a8ba262a3cca7e2ca701e4a9a4fba43c90ccdcb281d48c7c6fd62875d2aca417034c34aee5",
+            "619cc5aefffe0bfa462af43c1699d050"),
+    ];
+    
+    let (mut c, mut m) = (Vec::new(), Vec::new());
+    for (i, &(k, n, a, p, ct, tag)) in cases.iter().enumerate() {
+        let (n, a, p, ct, tag) = (hex(n), hex(a), hex(p), hex(ct), hex(tag));
+        let gcm = GCM::new(AES::new(hex(k)).unwrap(), 16).unwrap();
+        let t = gcm.encrypt_detached(&mut c, n.as_slice(), a.as_slice(), p.as_slice()).unwrap();
+        assert_eq!((&c, &t), (&ct, &tag), "case: {}", i);
+        gcm.decrypt_detached(&mut m, n.as_slice(), a.as_slice(), c.as_slice(), t.as_slice()).unwrap();
+        assert_eq!(m, p, "case: {}", i);
+        
+        gcm.seal(&mut c, n.as_slice(), a.as_slice(), p.as_slice()).unwrap();
+        assert_eq!((&c[..ct.len()], &c[ct.len()..]), (ct.as_slice(), tag.as_slice()), "case: {}", i);
+        gcm.open(&mut m, n.as_slice(), a.as_slice(), c.as_slice()).unwrap();
+        assert_eq!(m, p, "case: {}", i);
+        
+        let mut bad = tag.clone();
+        bad[0] ^= 1;
+        assert_eq!(gcm.decrypt_detached(&mut m, n.as_slice(), a.as_slice(), ct.as_slice(), bad.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(m.is_empty());
+        assert!(gcm.decrypt_detached(&mut m, n.as_slice(), a.as_slice(), ct.as_slice(), &tag[1..]).is_err());
+        
+        // the truncated tag is the prefix of the full tag
+        let gcm12 = GCM::new(AES::new(hex(k)).unwrap(), 12).unwrap();
+        let t = gcm12.encrypt_detached(&mut c, n.as_slice(), a.as_slice(), p.as_slice()).unwrap();
+        assert_eq!(t.as_slice(), &tag[..12], "case: {}", i);
+    }
+    
+    // GMAC with the associated data fed partially
+    let gcm = GCM::new(AES::new(hex(k)).unwrap(), 16).unwrap();
+    let (n, a) = (hex("cafebabefacedbaddecaf888"), hex(&format!("{}{}{}{}01", a, a, a, a)));
+    let tag = hex("47604c6ed24b53f632e77953c72ec14e");
+    assert_eq!(gcm.mac(n.as_slice(), a.as_slice()).unwrap(), tag);
+    for &step in [1usize, 7, 16, 33].iter() {
+        let mut ctx = gcm.context(n.as_slice()).unwrap();
+        a.chunks(step).for_each(|x| ctx.update_aad(x).unwrap());
+        assert_eq!(ctx.finalize_mac().unwrap(), tag, "step: {}", step);
+        
+        let mut ctx = gcm.context(n.as_slice()).unwrap();
+        a.chunks(step).for_each(|x| ctx.update_aad(x).unwrap());
+        let t = ctx.encrypt(&mut c, hex(p).as_slice()).unwrap();
+        assert_eq!(t, gcm.encrypt_detached(&mut m, n.as_slice(), a.as_slice(), hex(p).as_slice()).unwrap(), "step: {}", step);
+    }
+    gcm.verify_mac(n.as_slice(), a.as_slice(), tag.as_slice()).unwrap();
+    assert_eq!(gcm.verify_mac(n.as_slice(), &a[1..], tag.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    
+    assert!(GCM::new(AES::new(hex(k)).unwrap(), 10).is_err());
+    assert!(GCM::new(TDES::new([1u8; 8], [2u8; 8], [3u8; 8]), 16).is_err());
+    assert!(gcm.mac(b"", b"").is_err());
+    assert!(gcm.decrypt(&mut m, n.as_slice(), b"", &tag[1..]).is_err());
+    assert_eq!(gcm.remaining_bytes(), Some((1u128 << 36) - 32));
+}
+
+#[cfg(feature = "sm")]
+#[test]
+fn gcm_sm4() {
+    // RFC 8998 Appendix A.1
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    let (n, a) = (hex("00001234567800000000abcd"), hex("feedfacedeadbeeffeedfacedeadbeefabaddad2"));
+    let p = hex("aaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccddddddddddddddddeeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa");
+    let ct = hex("17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d");
+    let tag = hex("83de3541e4c2b58177e065a9bf7b62ec");
+    
+    let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
+    let gcm = GCM::new_sm4(key, 16).unwrap();
+    let (mut c, mut m) = (Vec::new(), Vec::new());
+    let t = gcm.encrypt_detached(&mut c, n.as_slice(), a.as_slice(), p.as_slice()).unwrap();
+    assert_eq!((c.as_slice(), t.as_slice()), (ct.as_slice(), tag.as_slice()));
+    gcm.open(&mut m, n.as_slice(), a.as_slice(), [ct.as_slice(), tag.as_slice()].concat().as_slice()).unwrap();
+    assert_eq!(m, p);
+    
+    let mut bad = ct.clone();
+    bad[0] ^= 0x80;
+    assert_eq!(gcm.decrypt_detached(&mut m, n.as_slice(), a.as_slice(), bad.as_slice(), tag.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(GCM::new_sm4(key, 11).is_err());
+}
+
+#[test]
+fn xts_aes() {
+    // IEEE P1619 Annex B, Vector 2 and Vector 15-17
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    let key2 = "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0";
+    let pt = (0u8..47).collect::<Vec<_>>();
+    let cases = [
+        ("1111111111111111111111111111111122222222222222222222222222222222", 0x3333333333u128, vec![0x44u8; 32], 
+            "c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0"),
+        (key2, 0x123456789a, pt[..17].to_vec(), "6c1625db4671522d3d7599601de7ca09ed"),
+        (key2, 0x123456789a, pt[..20].to_vec(), "9d84c813f719aa2c7be3f66171c7c5c2edbf9dac"),
+        (key2, 0x123456789a, pt.clone(), 
+            "edbf9dace45d6f6a7306e64be5dd824bd97664618cdc61663450da4bc5be6d762538f5724fcf24249ac111ab45ad39"),
+    ];
+    
+    let (mut c, mut p) = (Vec::new(), Vec::new());
+    for (i, (key, sector, m, tgt)) in cases.iter().enumerate() {
+        let xts = XTS::new_aes(hex(key)).unwrap();
+        xts.encrypt_sector(&mut c, *sector, m.as_slice()).unwrap();
+        assert_eq!(c, hex(tgt), "case: {}", i);
+        xts.decrypt(&mut p, &sector.to_le_bytes(), c.as_slice()).unwrap();
+        assert_eq!(&p, m, "case: {}", i);
+    }
+    
+    let xts = XTS::new_aes(hex(key2)).unwrap();
+    assert!(xts.encrypt_sector(&mut c, 0, &pt[..15]).is_err());
+    assert!(XTS::new_aes(vec![0x11; 32]).is_err());
+    assert!(XTS::new_aes(vec![0x11; 48]).is_err());
+}
+
+#[test]
+fn key_wrap_aes() {
+    // RFC 3394 4.1, 4.2 and RFC 5649 6
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    let cases = [
+        ("000102030405060708090a0b0c0d0e0f", "00112233445566778899aabbccddeeff", "1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5"),
+        ("000102030405060708090a0b0c0d0e0f1011121314151617", "00112233445566778899aabbccddeeff0001020304050607",
+            "031d33264e15d33268f24ec260743edce1c6c7ddee725a936ba814915c6762d2"),
+    ];
+    
+    let (mut c, mut p) = (Vec::new(), Vec::new());
+    for (i, &(kek, key, tgt)) in cases.iter().enumerate() {
+        let kw = KeyWrap::new(AES::new(hex(kek)).unwrap()).unwrap();
+        kw.wrap(&mut c, hex(key).as_slice()).unwrap();
+        assert_eq!(c, hex(tgt), "case: {}", i);
+        kw.unwrap(&mut p, c.as_slice()).unwrap();
+        assert_eq!(p, hex(key), "case: {}", i);
+        
+        c[3] ^= 1;
+        assert_eq!(kw.unwrap(&mut p, c.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(p.is_empty());
+    }
+    
+    let cases = [
+        ("c37b7e6492584340bed12207808941155068f738", "138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a"),
+        ("466f7250617369", "afbeb0f07dfbf5419200f2ccb50bb24f"),
+    ];
+    let kw = KeyWrap::new(AES::new(hex("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8")).unwrap()).unwrap();
+    for (i, &(key, tgt)) in cases.iter().enumerate() {
+        kw.wrap_with_padding(&mut c, hex(key).as_slice()).unwrap();
+        assert_eq!(c, hex(tgt), "case: {}", i);
+        kw.unwrap_with_padding(&mut p, c.as_slice()).unwrap();
+        assert_eq!(p, hex(key), "case: {}", i);
+        
+        *c.last_mut().unwrap() ^= 1;
+        assert_eq!(kw.unwrap_with_padding(&mut p, c.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(p.is_empty());
+    }
+    
+    // the KW ciphertext is not a valid KWP ciphertext
+    kw.wrap(&mut c, hex("00112233445566778899aabbccddeeff").as_slice()).unwrap();
+    assert!(kw.unwrap_with_padding(&mut p, c.as_slice()).is_err());
+    assert!(kw.wrap(&mut c, &[0u8; 12]).is_err());
+    assert!(kw.wrap_with_padding(&mut c, &[]).is_err());
+}
+
+#[test]
+fn ff1_aes() {
+    // NIST SP 800-38G FF1 samples
+    let cases = [
+        ("2B7E151628AED2A6ABF7158809CF4F3C", 10, "", "0123456789", "2433477484"),
+        ("2B7E151628AED2A6ABF7158809CF4F3C", 10, "39383736353433323130", "0123456789", "6124200773"),
+        ("2B7E151628AED2A6ABF7158809CF4F3C", 36, "3737373770717273373737", "0123456789abcdefghi", "a9tv40mll9kdu509eum"),
+        ("2B7E151628AED2A6ABF7158809CF4F3CEF4359D8D580AA4F", 10, "", "0123456789", "2830668132"),
+        ("2B7E151628AED2A6ABF7158809CF4F3CEF4359D8D580AA4F7F036D6F04FC6A94", 10, "", "0123456789", "6657667009"),
+        ("2B7E151628AED2A6ABF7158809CF4F3CEF4359D8D580AA4F7F036D6F04FC6A94", 36, "3737373770717273373737", "0123456789abcdefghi", "xs8a0azh2avyalyzuwd"),
+        // d > 16
+        ("2B7E151628AED2A6ABF7158809CF4F3C", 10, "39383736353433323130", "012345678901234567890123456789012345678901234567890123456789",
+            "173876027003238647201725993080113835930345769649828155960140"),
+    ];
+
+    let (alphabet, mut buf) = (NumeralAlphabet::base36(), Vec::new());
+    for (i, ele) in cases.iter().enumerate() {
+        let hex = Hex::upper();
+        let ff1 = FF1::new(AES::new(hex.decode(ele.0).unwrap()).unwrap(), ele.1).unwrap();
+        let tweak = hex.decode(ele.2).unwrap();
+        let (x, y) = (alphabet.encode(ele.3).unwrap(), alphabet.encode(ele.4).unwrap());
+
+        ff1.encrypt(&mut buf, tweak.as_slice(), x.as_slice()).unwrap();
+        assert_eq!(alphabet.decode(buf.as_slice()).unwrap(), ele.4, "encrypt-case: {}", i);
+        ff1.decrypt(&mut buf, tweak.as_slice(), y.as_slice()).unwrap();
+        assert_eq!(buf, x, "decrypt-case: {}", i);
+    }
+
+    let key = (0..16u8).collect::<Vec<_>>();
+    let (ff1, digits) = (FF1::new(AES::new(key.clone()).unwrap(), 10).unwrap(), NumeralAlphabet::decimal());
+    assert_eq!(ff1.min_len(), 6);
+    let pan = digits.encode("4111111111111111").unwrap();
+    ff1.encrypt(&mut buf, b"merchant", pan.as_slice()).unwrap();
+    assert_eq!(buf.len(), pan.len());
+    assert!(buf.iter().all(|&x| x < 10));
+    let ct = buf.clone();
+    ff1.decrypt(&mut buf, b"merchant", ct.as_slice()).unwrap();
+    assert_eq!(buf, pan);
+
+    assert!(ff1.encrypt(&mut buf, b"", &pan[..5]).is_err());
+    assert!(ff1.encrypt(&mut buf, b"", &[1, 2, 3, 4, 5, 10]).is_err());
+    assert!(FF1::new(AES::new(key.clone()).unwrap(), 1).is_err());
+    assert!(FF1::new(AES::new(key).unwrap(), (1 << 16) + 1).is_err());
+    assert!(digits.encode("12a").is_err());
+    assert!(NumeralAlphabet::new("0120").is_err());
+}
+
+#[test]
+fn siv_aes() {
+    // RFC 5297 Appendix A, (key, ad, plaintext, V || C)
+    let cases = [
+        ("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff", vec!["101112131415161718191a1b1c1d1e1f2021222324252627"],
+            "112233445566778899aabbccddee", "85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c"),
+        ("7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f", vec!["00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100",
+            "102030405060708090a0", "09f911029d74e35bd84156c5635688c0"],
+            "7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553",
+            "7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d"),
+        // AES-256-SIV, the empty plaintext
+        ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+            vec!["6164"], "", "3db5bcde33d1d9b72935c72e1a4d9fcc"),
+    ];
+
+    let hex = Hex::lower();
+    let mut buf = Vec::new();
+    for (i, ele) in cases.iter().enumerate() {
+        let key = hex.decode(ele.0).unwrap();
+        let (k1, k2) = key.split_at(key.len() >> 1);
+        let siv = SIV::new(AES::new(k1.to_vec()).unwrap(), AES::new(k2.to_vec()).unwrap()).unwrap();
+        let ad = ele.1.iter().map(|x| hex.decode(x).unwrap()).collect::<Vec<_>>();
+        let ad = ad.iter().map(|x| x.as_slice()).collect::<Vec<_>>();
+        let (txt, tgt) = (hex.decode(ele.2).unwrap(), hex.decode(ele.3).unwrap());
+
+        siv.encrypt(&mut buf, ad.as_slice(), txt.as_slice()).unwrap();
+        assert_eq!(buf, tgt, "encrypt-case: {}", i);
+        siv.decrypt(&mut buf, ad.as_slice(), tgt.as_slice()).unwrap();
+        assert_eq!(buf, txt, "decrypt-case: {}", i);
+
+        let tag = siv.seal_detached(&mut buf, ad.as_slice(), txt.as_slice()).unwrap();
+        assert_eq!(tag.as_ref(), &tgt[..16], "seal_detached-case: {}", i);
+        assert_eq!(buf.as_slice(), &tgt[16..], "seal_detached-case: {}", i);
+        let c = buf.clone();
+        siv.open_detached(&mut buf, ad.as_slice(), c.as_slice(), &tag).unwrap();
+        assert_eq!(buf, txt, "open_detached-case: {}", i);
+        let mut bad_tag = tag.as_ref().to_vec();
+        bad_tag[0] ^= 1;
+        let bad_tag = Tag::<16>::from_slice(bad_tag.as_slice()).unwrap();
+        assert_eq!(siv.open_detached(&mut buf, ad.as_slice(), c.as_slice(), &bad_tag).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+
+        let mut tampered = tgt.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(siv.decrypt(&mut buf, ad.as_slice(), tampered.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(buf.is_empty());
+        assert!(siv.decrypt(&mut buf, &ad[..(ad.len() - 1)], tgt.as_slice()).is_err());
+    }
+}
+
+#[test]
+fn cipher_mode_limits() {
+    let tdes = TDES::new(0x0123456789ABCDEFu64.to_be_bytes(), 0x23456789ABCDEF01u64.to_be_bytes(), 0x456789ABCDEF0123u64.to_be_bytes());
+    let (data, mut dst) = ([0u8; 24], Vec::new());
+    
+    // the 64 bits counter wraps after 2^64 blocks
+    let ctr = CTR::new(tdes.clone(), DefaultCounter::new(vec![0xffu8; 8], 64).unwrap()).unwrap();
+    assert_eq!(ctr.remaining_bytes(), Some((1u128 << 64) * 8));
+    ctr.encrypt(&mut dst, &data[..20]).unwrap();
+    assert_eq!(ctr.remaining_bytes(), Some(((1u128 << 64) - 3) * 8));
+    
+    // CTR over the 64 bits block cipher, the key usage is limited to 2^20 blocks
+    let limited = KeyUsageLimit::new(tdes.clone());
+    assert_eq!(limited.remaining_bytes(), Some(8 << 20));
+    let limited = KeyUsageLimit::with_max_blocks(tdes, 4);
+    let ctr = CTR::new(limited, DefaultCounter::new(vec![0u8; 8], 64).unwrap()).unwrap();
+    ctr.encrypt(&mut dst, &data[..16]).unwrap();
+    ctr.encrypt(&mut dst, &data[..16]).unwrap();
+    assert_eq!(ctr.encrypt(&mut dst, &data[..1]).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    
+    let cbc = CBC::new(KeyUsageLimit::with_max_blocks(AES::new(vec![0u8; 16]).unwrap(), 2), EmptyPadding::new(),
+        FixedInitialVec::new(vec![0u8; 16])).unwrap();
+    cbc.encrypt(&mut dst, &[0u8; 32]).unwrap();
+    assert_eq!(cbc.encrypt(&mut dst, &[0u8; 16]).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    
+    // the per-nonce payload limit 2^{8q}-1
+    let ccm = CCM::new(AES::new(vec![0u8; 16]).unwrap(), 16, 13).unwrap();
+    assert_eq!(ccm.remaining_bytes(), Some((1 << 16) - 1));
+    assert_eq!(ccm.encrypt(&mut dst, &[0u8; 13], &[], vec![0u8; 1 << 16].as_slice()).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert!(ccm.encrypt(&mut dst, &[0u8; 13], &[], vec![0u8; (1 << 16) - 1].as_slice()).is_ok());
+    
+    let xts = XTS::new_aes((0u8..32).collect()).unwrap();
+    assert_eq!(xts.remaining_bytes(), Some(16 << 20));
+}
+
+#[test]
+fn typed_nonce_iv_tag() {
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    assert_eq!(Nonce::<12>::from_slice(&[0u8; 11]).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(Iv::<16>::from_slice(&[0u8; 17]).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(Tag::<16>::LEN, 16);
+    
+    // The Galois/Counter Mode of Operation(GCM), Appendix B test case 2
+    let gcm = GCM::new(AES::new(vec![0u8; 16]).unwrap(), 16).unwrap();
+    let (n, p) = (Nonce::new([0u8; 12]), [0u8; 16]);
+    let (mut c, mut m) = (Vec::new(), Vec::new());
+    let tag: Tag<16> = gcm.seal_detached(&mut c, &n, b"", &p).unwrap();
+    assert_eq!(c, hex("0388dace60b6a392f328c2b971b2fe78"));
+    assert!(tag.ct_eq(hex("ab6e47d42cec13bdf53a67b21257bddf").as_slice()));
+    assert!(!tag.ct_eq(&hex("ab6e47d42cec13bdf53a67b21257bddf")[..15]));
+    assert_eq!(tag, Tag::from_slice(hex("ab6e47d42cec13bdf53a67b21257bddf").as_slice()).unwrap());
+    gcm.open_detached(&mut m, &n, b"", c.as_slice(), &tag).unwrap();
+    assert_eq!(m, p);
+    assert!(gcm.seal_detached::<12, 12>(&mut c, &n, b"", &p).is_err());
+    
+    let mut bad = tag.into_inner();
+    bad[0] ^= 1;
+    assert_ne!(tag, Tag::new(bad));
+    assert_eq!(gcm.open_detached(&mut m, &n, b"", c.as_slice(), &Tag::new(bad)).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    
+    gcm.seal_with_nonce(&mut c, &n, b"", &p).unwrap();
+    gcm.open_with_nonce(&mut m, &n, b"", c.as_slice()).unwrap();
+    assert_eq!(m, p);
+    assert_eq!(gcm.seal_with_nonce(&mut c, &Nonce::new([0u8; 8]), b"", &p).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    
+    // the CCM detached tag, SP 800-38C C.1 example 1
+    let ccm = CCM::new(AES::new(hex("404142434445464748494a4b4c4d4e4f")).unwrap(), 4, 7).unwrap();
+    let n = Nonce::<7>::from_slice(hex("10111213141516").as_slice()).unwrap();
+    let tag: Tag<4> = ccm.seal_detached(&mut c, &n, &hex("0001020304050607"), &hex("20212223")).unwrap();
+    assert_eq!((c.as_slice(), &tag), (hex("7162015b").as_slice(), &Tag::new([0x4d, 0xac, 0x25, 0x5d])));
+    ccm.open_detached(&mut m, &n, &hex("0001020304050607"), &hex("7162015b"), &tag).unwrap();
+    assert_eq!(m, hex("20212223"));
+    assert_eq!(ccm.open_detached(&mut m, &n, b"", &hex("7162015b"), &tag).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert_eq!(ccm.seal_detached::<7, 16>(&mut c, &n, b"", &p).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    
+    // the `Iv<16>` is converted to the caller specified IV, the `Iv<8>` is rejected by the CBC
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let iv = Iv::<16>::random(&mut rd).unwrap();
+    let cbc = CBC::new(AES::new(vec![0u8; 16]).unwrap(), EmptyPadding::new(), FixedInitialVec::from(iv)).unwrap();
+    assert_eq!(cbc.cur_iv().as_slice(), iv.as_ref());
+    assert!(CBC::new(AES::new(vec![0u8; 16]).unwrap(), EmptyPadding::new(), FixedInitialVec::from(Iv::new([0u8; 8]))).is_err());
+}
+
+#[test]
+fn ctr_drbg_aes() {
+    // CAVS 14.3 CTR_DRBG(no reseed) AES-128 no df and AES-128 use df, COUNT = 0, the second output is returned
+    let hex = |s: &str| Hex::lower().decode(s).unwrap();
+    let mut buf = Vec::new();
+    let mut drbg = CtrDrbg::new_without_df(16, hex("ce50f33da5d4c1d3d4004eb35244b7f2cd7f2e5076fbf6780a7ff634b249a5fc").as_slice(), b"").unwrap();
+    drbg.generate(&mut buf, 64, b"").unwrap();
+    drbg.generate(&mut buf, 64, b"").unwrap();
+    assert_eq!(buf, hex("6545c0529d372443b392ceb3ae3a99a30f963eaf313280f1d1a1e87f9db373d361e75d18018266499cccd64d9bbb8de0185f213383080faddec46bae1f784e5a"));
+    assert_eq!(drbg.reseed_counter(), 2);
+    
+    let mut drbg = CtrDrbg::new(16, hex("890eb067acf7382eff80b0c73bc872c6").as_slice(), hex("aad471ef3ef1d203").as_slice(), b"").unwrap();
+    drbg.generate(&mut buf, 64, b"").unwrap();
+    drbg.generate(&mut buf, 64, b"").unwrap();
+    assert_eq!(buf, hex("a5514ed7095f64f3d0d3a5760394ab42062f373a25072a6ea6bcfd8489e94af6cf18659fea22ed1ca0a9e33f718b115ee536b12809c31b72b08ddd8be1910fa3"));
+    
+    // the additional input and the reseed change the output
+    let (mut d1, mut d2) = (drbg.clone(), drbg.clone());
+    let mut other = Vec::new();
+    d1.generate(&mut buf, 32, b"").unwrap();
+    d2.generate(&mut other, 32, b"additional input").unwrap();
+    assert_ne!(buf, other);
+    d2.reseed(&[7u8; 32], b"").unwrap();
+    assert_eq!(d2.reseed_counter(), 0);
+    d2.generate(&mut other, 32, b"").unwrap();
+    d1.generate(&mut buf, 32, b"").unwrap();
+    assert_ne!(buf, other);
+    
+    assert_eq!(CtrDrbg::new(20, &[0u8; 32], b"", b"").err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(CtrDrbg::new(32, &[0u8; 32], &[0u8; 8], b"").err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(CtrDrbg::new(32, &[0u8; 48], b"", b"").is_ok());
+    assert!(CtrDrbg::new_without_df(16, &[0u8; 31], b"").is_err());
+    assert!(CtrDrbg::new_without_df(16, &[0u8; 32], &[0u8; 33]).is_err());
+    assert_eq!(drbg.generate(&mut buf, 65537, b"").unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    
+    let mut drbg = CtrDrbg::from_seed(32, &DefaultSeed::<u32>::new().unwrap(), b"rcrypto test").unwrap();
+    assert_eq!(drbg.security_strength(), 256);
+    let r = (0..32).map(|_| drbg.gen().unwrap()).collect::<Vec<_>>();
+    assert!(r.windows(2).any(|x| x[0] != x[1]));
+    drbg.reset(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    assert_eq!(drbg.reseed_counter(), 0);
+    
+    let aes = AES::new(vec![0u8; 16]).unwrap();
+    let mut cbc = CBC::new(aes.clone(), EmptyPadding::new(), DrbgInitialVec::from_entropy(&aes, b"cbc").unwrap()).unwrap();
+    let iv = cbc.cur_iv();
+    assert_ne!(cbc.update_iv().unwrap(), &iv);
+}
+
+#[derive(Clone, Default)]
+struct MemoryIvCounterStore(Rc<RefCell<(Option<u128>, usize)>>);
+
+impl IvCounterStore for MemoryIvCounterStore {
+    fn load(&mut self) -> Result<Option<u128>, CryptoError> {
+        Ok(self.0.borrow().0)
+    }
+    
+    fn store(&mut self, val: u128) -> Result<(), CryptoError> {
+        let mut s = self.0.borrow_mut();
+        *s = (Some(val), s.1 + 1);
+        Ok(())
+    }
+}
+
+#[test]
+fn counter_initial_vec() {
+    let aes = AES::new(vec![0u8; 16]).unwrap();
+    let store = MemoryIvCounterStore::default();
+    let mut ivs = Vec::new();
+    let mut civ = CounterInitialVec::new(&aes, store.clone(), 4).unwrap();
+    let mut iv = Vec::new();
+    for _ in 0..6 {
+        InitialVec::<AES>::initial_vec(&mut civ, &mut iv).unwrap();
+        ivs.push(iv.clone());
+    }
+    assert_eq!(&ivs[5][..], &5u128.to_be_bytes()[..]);
+    assert_eq!(*store.0.borrow(), (Some(8), 2));
+    
+    // restart after the crash, the unused reserved values 6 and 7 are skipped
+    let mut civ = CounterInitialVec::new(&aes, store.clone(), 4).unwrap();
+    assert_eq!(civ.next_counter(), 8);
+    InitialVec::<AES>::initial_vec(&mut civ, &mut iv).unwrap();
+    assert_eq!(&iv[..], &8u128.to_be_bytes()[..]);
+    assert!(!ivs.contains(&iv));
+    
+    // the encrypted counter IV of the CBC
+    let store = MemoryIvCounterStore::default();
+    let mut cbc = CBC::new(aes.clone(), EmptyPadding::new(), CounterInitialVec::new_encrypted(aes.clone(), store, 16).unwrap()).unwrap();
+    let mut expect = Vec::new();
+    aes.encrypt(&mut expect, &0u128.to_be_bytes()).unwrap();
+    assert_eq!(cbc.cur_iv(), expect);
+    aes.encrypt(&mut expect, &1u128.to_be_bytes()).unwrap();
+    assert_eq!(cbc.update_iv().unwrap(), &expect);
+    
+    // the message counter is in the high 64 bits of the initial counter block
+    let mut civ = CounterInitialVec::new_ctr(&aes, MemoryIvCounterStore::default(), 1, 64).unwrap();
+    InitialVec::<AES>::initial_vec(&mut civ, &mut iv).unwrap();
+    InitialVec::<AES>::initial_vec(&mut civ, &mut iv).unwrap();
+    assert_eq!(iv, (1u128 << 64).to_be_bytes().to_vec());
+    assert_eq!(civ.remaining_ivs(), (1u128 << 64) - 2);
+    
+    // the 2 bits message counter of the 64 bits block cipher
+    let tdes = TDES::new([1u8; 8], [2u8; 8], [3u8; 8]);
+    let store = MemoryIvCounterStore::default();
+    let mut civ = CounterInitialVec::new_ctr(&tdes, store.clone(), 3, 62).unwrap();
+    for i in 0..4u64 {
+        InitialVec::<TDES>::initial_vec(&mut civ, &mut iv).unwrap();
+        assert_eq!(iv, (i << 62).to_be_bytes().to_vec());
+    }
+    assert_eq!(InitialVec::<TDES>::initial_vec(&mut civ, &mut iv).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert_eq!(*store.0.borrow(), (Some(4), 2));
+    assert!(CounterInitialVec::new_ctr(&tdes, MemoryIvCounterStore::default(), 3, 64).is_err());
+    assert!(CounterInitialVec::new(&tdes, MemoryIvCounterStore::default(), 0).is_err());
+    
+    let path = std::env::temp_dir().join(format!("rcrypto-iv-counter-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut store = FileIvCounterStore::new(path.clone());
+    assert_eq!(store.load().unwrap(), None);
+    let mut civ = CounterInitialVec::new(&aes, store.clone(), 100).unwrap();
+    InitialVec::<AES>::initial_vec(&mut civ, &mut iv).unwrap();
+    assert_eq!(store.load().unwrap(), Some(100));
+    assert_eq!(CounterInitialVec::new(&aes, store.clone(), 100).unwrap().next_counter(), 100);
+    std::fs::write(&path, "x").unwrap();
+    assert!(store.load().is_err());
+    std::fs::remove_file(&path).unwrap();
+    
+    // the relative path is synced in the current directory, the missing directory fails
+    let rel = format!("rcrypto-iv-counter-rel-{}", std::process::id());
+    let mut store = FileIvCounterStore::new(rel.clone());
+    store.store(7).unwrap();
+    assert_eq!(store.load().unwrap(), Some(7));
+    std::fs::remove_file(&rel).unwrap();
+    assert!(FileIvCounterStore::new(path.join("missing")).store(7).is_err());
+}
+
+#[test]
+fn stored_counter_ctr() {
+    let aes = AES::new(vec![7u8; 16]).unwrap();
+    let data = (0..100u8).collect::<Vec<_>>();
+    let expect = |initial: u128| {
+        let mut dst = Vec::new();
+        CTR::new(aes.clone(), DefaultCounter::new(initial.to_be_bytes().to_vec(), 128).unwrap()).unwrap().encrypt(&mut dst, data.as_slice()).unwrap();
+        dst
+    };
+    
+    // the first message starts at the counter block 0, the stream starts the next message at 1 << 64
+    let store = MemoryIvCounterStore::default();
+    let ctr = CTR::new(aes.clone(), StoredCounter::new(&aes, store.clone(), 4, 64).unwrap()).unwrap();
+    let mut dst = Vec::new();
+    ctr.encrypt(&mut dst, &data[..32]).unwrap();
+    let mut tail = Vec::new();
+    ctr.encrypt(&mut tail, &data[32..48]).unwrap();
+    dst.append(&mut tail);
+    assert_eq!(dst.as_slice(), &expect(0)[..48]);
+    
+    let mut stream = ctr.encrypt_stream();
+    let mut buf = Vec::new();
+    stream.write(data.as_slice()).unwrap().draw_off(&mut buf);
+    stream.finish().unwrap().draw_off(&mut buf);
+    assert_eq!(buf, expect(1 << 64));
+    stream.reset();
+    buf.clear();
+    stream.write(data.as_slice()).unwrap().draw_off(&mut buf);
+    stream.finish().unwrap().draw_off(&mut buf);
+    assert_eq!(buf, expect(2 << 64));
+    assert_eq!(*store.0.borrow(), (Some(4), 1));
+    
+    // the restarted counter skips the reserved message counters
+    let counter = StoredCounter::new(&aes, store.clone(), 4, 64).unwrap();
+    assert_eq!(counter.next_counter(), 4);
+    let ctr = CTR::new(aes.clone(), counter).unwrap();
+    ctr.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_eq!(dst, expect(4 << 64));
+    
+    // the message is limited to 2^{block_counter_bits} blocks, and the message counter is limited by the rest bits
+    let tdes = TDES::new([1u8; 8], [2u8; 8], [3u8; 8]);
+    let ctr = CTR::new(tdes.clone(), StoredCounter::new(&tdes, MemoryIvCounterStore::default(), 1, 1).unwrap()).unwrap();
+    assert_eq!(ctr.remaining_bytes(), Some(16));
+    assert_eq!(ctr.encrypt(&mut dst, &data[..17]).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    ctr.encrypt(&mut dst, &data[..16]).unwrap();
+    assert_eq!(ctr.encrypt(&mut dst, &data[..1]).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    let mut ctr = CTR::new(tdes.clone(), StoredCounter::new(&tdes, MemoryIvCounterStore::default(), 1, 63).unwrap()).unwrap().encrypt_stream();
+    let mut buf = Vec::new();
+    ctr.write(&data[..8]).unwrap().draw_off(&mut buf);
+    ctr.reset();
+    ctr.write(&data[..8]).unwrap().draw_off(&mut buf);
+    ctr.reset();
+    assert!(ctr.write(&data[..8]).is_err());
+    assert!(StoredCounter::new(&tdes, MemoryIvCounterStore::default(), 1, 0).is_err());
+}
+
+#[test]
+fn mode_builder_reset() {
+    let aes = AES::new(vec![7u8; 16]).unwrap();
+    let builder = ModeBuilder::new(aes.clone());
+    let (data, mut dst, mut tgt) = (vec![0x5au8; 40], Vec::new(), Vec::new());
+    
+    // the IV modes restore the construction-time IV
+    let iv = DefaultInitialVec::new(&aes, CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap());
+    let mut cbc = builder.build_cbc(PKCS7Padding::new(&aes), iv).unwrap();
+    let init_iv = cbc.cur_iv();
+    cbc.encrypt(&mut tgt, data.as_slice()).unwrap();
+    cbc.update_iv().unwrap();
+    assert_ne!(cbc.cur_iv(), init_iv);
+    cbc.reset();
+    assert_eq!(cbc.cur_iv(), init_iv);
+    cbc.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_eq!(dst, tgt);
+    assert!(cbc.reset_with_iv(init_iv.clone()).is_err());
+    cbc.reset_with_iv(vec![9u8; 16]).unwrap();
+    assert!(cbc.reset_with_iv(vec![9u8; 16]).is_err());
+    cbc.reset();
+    assert_eq!(cbc.cur_iv(), vec![9u8; 16]);
+    
+    let mut cts = builder.clone().ciphertext_stealing(CiphertextStealing::CS3).build_cbc_cts(FixedInitialVec::new(vec![0u8; 16])).unwrap();
+    assert_eq!(cts.ciphertext_stealing(), Some(CiphertextStealing::CS3));
+    cts.set_iv(vec![1u8; 16]).unwrap();
+    cts.reset();
+    assert_eq!(cts.cur_iv(), vec![0u8; 16]);
+    assert_eq!(builder.build_cbc_cts(FixedInitialVec::new(vec![0u8; 16])).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    
+    let mut cfb = builder.build_cfb(EmptyPadding::new(), FixedInitialVec::new(vec![3u8; 16])).unwrap();
+    cfb.encrypt(&mut tgt, data.as_slice()).unwrap();
+    cfb.set_iv(vec![4u8; 16]).unwrap();
+    cfb.reset();
+    cfb.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_eq!(dst, tgt);
+    assert_eq!(cfb.reset_with_iv(vec![3u8; 16]).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(cfb.reset_with_iv(vec![4u8; 8]).is_err());
+    cfb.reset_with_iv(vec![4u8; 16]).unwrap();
+    cfb.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_ne!(dst, tgt);
+    cfb.reset();
+    assert_eq!(cfb.cur_iv(), vec![4u8; 16]);
+    assert!(builder.clone().segment_bits_len(8).build_cfb(EmptyPadding::new(), FixedInitialVec::new(vec![3u8; 16])).is_ok());
+    assert!(builder.clone().segment_bits_len(12).build_cfb(EmptyPadding::new(), FixedInitialVec::new(vec![3u8; 16])).is_err());
+    
+    let mut ofb = builder.build_ofb(FixedInitialVec::new(vec![5u8; 16])).unwrap();
+    ofb.encrypt(&mut tgt, data.as_slice()).unwrap();
+    ofb.set_iv(vec![6u8; 16]).unwrap();
+    ofb.reset();
+    ofb.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_eq!(dst, tgt);
+    ofb.reset_with_iv(vec![6u8; 16]).unwrap();
+    ofb.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_ne!(dst, tgt);
+    assert!(ofb.reset_with_iv(vec![6u8; 16]).is_err());
+    
+    // the CTR key stream continues between the calls until the reset
+    let mut ctr = builder.build_ctr(DefaultCounter::new(vec![0u8; 16], 128).unwrap()).unwrap();
+    ctr.encrypt(&mut tgt, data.as_slice()).unwrap();
+    ctr.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_ne!(dst, tgt);
+    ctr.reset();
+    ctr.encrypt(&mut dst, data.as_slice()).unwrap();
+    assert_eq!(dst, tgt);
+    
+    let (mut gcm, mut ccm) = (builder.build_gcm().unwrap(), builder.clone().tag_len(8).nonce_len(13).build_ccm().unwrap());
+    assert_eq!((gcm.tag_len(), ccm.tag_len(), ccm.nonce_len()), (16, 8, 13));
+    gcm.seal(&mut tgt, &[1u8; 12], b"aad", data.as_slice()).unwrap();
+    gcm.reset();
+    gcm.seal(&mut dst, &[1u8; 12], b"aad", data.as_slice()).unwrap();
+    assert_eq!(dst, tgt);
+    ccm.seal(&mut tgt, &[1u8; 13], b"aad", data.as_slice()).unwrap();
+    ccm.reset();
+    ccm.seal(&mut dst, &[1u8; 13], b"aad", data.as_slice()).unwrap();
+    assert_eq!(dst, tgt);
+    assert!(builder.clone().tag_len(5).build_gcm().is_err());
 }
\ No newline at end of file
@@ -10,6 +10,23 @@ pub trait Counter {
     fn next(&mut self) -> Option<&Vec<u8>>;
     
     fn bits_len(&self) -> usize;
+    
+    /// the counter value of the last block, `None` means the counter is in the initial status.
+    /// It's used to save the stream state, the counter doesn't support it by default.
+    fn cur_val(&self) -> Result<Option<Vec<u8>>, CryptoError> {
+        Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The counter doesn't support to export its value"))
+    }
+    
+    /// restore the counter value which is returned by the `cur_val`
+    fn set_cur_val(&mut self, _val: Option<Vec<u8>>) -> Result<(), CryptoError> {
+        Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The counter doesn't support to import its value"))
+    }
+    
+    /// the number of the remaining counter blocks before the counter wraps to the used value, `None` means
+    /// the counter doesn't know it.
+    fn remaining_blocks(&self) -> Option<u128> {
+        None
+    }
 }
 
 pub struct DefaultCounter {
@@ -74,4 +91,40 @@ impl Counter for DefaultCounter {
     fn bits_len(&self) -> usize {
         self.bits_len
     }
+
+    fn cur_val(&self) -> Result<Option<Vec<u8>>, CryptoError> {
+        Ok(self.cur_val.clone())
+    }
+
+    fn set_cur_val(&mut self, val: Option<Vec<u8>>) -> Result<(), CryptoError> {
+        match val {
+            Some(v) if v.len() != ((self.bits_len + 7) >> 3) => {
+                Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("Wrong counter value length: {}, it must be {} in bytes", v.len(), (self.bits_len + 7) >> 3)))
+            },
+            _ => {
+                self.cur_val = val;
+                Ok(())
+            }
+        }
+    }
+
+    /// the counter value is incremented modulo $2^{8 \cdot len}$, `len` is the bytes length of the counter value
+    fn remaining_blocks(&self) -> Option<u128> {
+        let len = (self.bits_len + 7) >> 3;
+        let cur = match self.cur_val.as_ref() {
+            None => return Some(if len >= 16 { u128::MAX } else { 1u128 << (len << 3) }),
+            Some(v) => v,
+        };
+        
+        // the used blocks: (cur - initial) mod 2^{8len} + 1, the lowest 16 bytes are enough
+        let low16 = |v: &[u8]| v.iter().rev().take(16).rev().fold(0u128, |a, &b| (a << 8) | (b as u128));
+        let init = low16(&self.initial_val[self.initial_val.len().saturating_sub(len)..]);
+        let mut used = low16(cur.as_slice()).wrapping_sub(init);
+        if len < 16 {
+            used &= (1u128 << (len << 3)) - 1;
+        }
+        
+        Some(if len >= 16 { u128::MAX - used } else { (1u128 << (len << 3)) - used - 1 })
+    }
 }
\ No newline at end of file
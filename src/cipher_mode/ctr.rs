@@ -1,7 +1,10 @@
 //! CTR(Counter Mode)
 
 use crate::{Cipher, CryptoError, CryptoErrorKind};
-use crate::cipher_mode::{Counter, EncryptStream, Pond, DecryptStream};
+use crate::cipher_mode::{Counter, EncryptStream, Pond, DecryptStream, StreamState};
+use crate::cipher_mode::stream_state::{encode_state, decode_state, STATE_MODE_CTR};
+use crate::cipher_mode::DataLimit;
+use crate::cipher_mode::limits::limit_err;
 use std::marker::PhantomData;
 use std::cell::Cell;
 
@@ -41,6 +44,18 @@ impl<C, T>  CTR<C, T>
         }
     }
     
+    /// reset the counter by the `Counter::reset`, e.g. the `DefaultCounter` restarts from the initial counter
+    /// block, and the `StoredCounter` starts the next message.
+    ///
+    /// # Security
+    ///
+    /// the `DefaultCounter` generates the same key stream again after the `reset`, it only allows to re-process the
+    /// same message, e.g. decrypt what was just encrypted. Encrypt the next message by the `set_counter` with a fresh
+    /// initial counter block, or by the `StoredCounter`.
+    pub fn reset(&mut self) {
+        self.get_counter().reset();
+    }
+    
     #[inline]
     fn get_buf(&self) -> &mut Vec<u8> {
         unsafe {
@@ -57,6 +72,12 @@ impl<C, T>  CTR<C, T>
     
     fn encrypt_inner(&self, mut data: &[u8], dst: &mut Vec<u8>) -> Result<usize, CryptoError> {
         let block_len = self.cipher.block_size().unwrap_or(1);
+        if let Some(remaining) = self.get_counter().remaining_blocks() {
+            if (data.len().div_ceil(block_len) as u128) > remaining {
+                return limit_err(format!("The data is too long for the remaining {} counter blocks", remaining));
+            }
+        }
+        
         let oj = self.get_buf();
         while !data.is_empty() {
             match self.get_counter().next() {
@@ -122,6 +143,29 @@ impl<C, T> Cipher for CTR<C, T>
     }
 }
 
+impl<C, T> DataLimit for CTR<C, T>
+    where C: Cipher, T: Counter {
+    /// the remaining bytes before the counter block is reused
+    fn remaining_bytes(&self) -> Option<u128> {
+        let block_len = self.cipher.block_size().unwrap_or(1) as u128;
+        self.get_counter().remaining_blocks().map(|x| x.saturating_mul(block_len))
+    }
+}
+
+impl<C, T> DataLimit for CTREncrypt<C, T>
+    where C: Cipher, T: Counter {
+    fn remaining_bytes(&self) -> Option<u128> {
+        self.ctr.remaining_bytes()
+    }
+}
+
+impl<C, T> DataLimit for CTRDecrypt<C, T>
+    where C: Cipher, T: Counter {
+    fn remaining_bytes(&self) -> Option<u128> {
+        self.ctr.remaining_bytes()
+    }
+}
+
 impl<C, T> Clone for CTR<C, T>
     where C: Cipher + Clone, T: Counter + Clone {
     fn clone(&self) -> Self {
@@ -160,6 +204,41 @@ impl<C, T>  CTRDecrypt<C, T>
     }
 }
 
+impl<C, T> StreamState for CTREncrypt<C, T>
+    where C: Cipher, T: Counter {
+    /// the counter must support the `Counter::cur_val`
+    fn save_state(&self) -> Result<Vec<u8>, CryptoError> {
+        let val = self.ctr.get_counter().cur_val()?.unwrap_or_default();
+        encode_state(STATE_MODE_CTR, self.ctr.block_size().unwrap_or(1),
+            &[self.data.as_slice(), self.pond.as_slice(), val.as_slice()])
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError> {
+        let block_len = self.ctr.block_size().unwrap_or(1);
+        let mut fields = decode_state(state, STATE_MODE_CTR, block_len, 3)?;
+        if fields[0].len() >= block_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid stream state: the buffered data too long"));
+        }
+        
+        let val = fields.pop().unwrap();
+        self.ctr.get_counter().set_cur_val(if val.is_empty() {None} else {Some(val)})?;
+        self.pond = fields.pop().unwrap();
+        self.data = fields.pop().unwrap();
+        Ok(())
+    }
+}
+
+impl<C, T> StreamState for CTRDecrypt<C, T>
+    where C: Cipher, T: Counter {
+    fn save_state(&self) -> Result<Vec<u8>, CryptoError> {
+        self.ctr.save_state()
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError> {
+        self.ctr.restore_state(state)
+    }
+}
+
 impl<C, T> Cipher for CTREncrypt<C, T>
     where C: Cipher, T: Counter {
     type Output = usize;
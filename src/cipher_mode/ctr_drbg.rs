@@ -0,0 +1,284 @@
+//! CTR_DRBG: NIST SP 800-90A Rev.1 10.2.1
+//!
+//! The deterministic random bit generator by the AES-128/192/256 in the counter mode, the internal state is the
+//! `(Key, V, reseed_counter)`. The seed material is derived from the `entropy_input || nonce || personalization_string`
+//! by the `Block_Cipher_df`(10.3.2), or it's the `entropy_input xor personalization_string` if the derivation function
+//! isn't used.
+//!
+//! Generate(n): V = V + 1, output = CIPH_{Key}(V) || ..., then (Key, V) = Update(additional_input, Key, V), so the
+//! previous outputs can't be recovered from the compromised state(backtracking resistance).
+
+use rmath::rand::{Source, IterSource, Seed, Iter, RandError, RandErrKind};
+use crate::{AES, Cipher, CryptoError, CryptoErrorKind};
+
+const DRBG_BLOCK_LEN: usize = 16;
+/// the maximum number of the requests between the reseeds(SP 800-90A Table 3)
+const DRBG_RESEED_INTERVAL: u64 = 1 << 48;
+/// the maximum output of one request is $2^{19}$ bits
+const DRBG_MAX_REQUEST_LEN: usize = 1 << 16;
+/// the retries of the failed entropy source before the error is returned
+const DRBG_SEED_RETRIES: usize = 64;
+/// the output buffer of the `Source<u32>`
+const DRBG_POOL_LEN: usize = 64;
+
+/// the AES CTR_DRBG, it's also the `Source<u32>`, so it can be used as the random source of the `DefaultInitialVec`
+/// and the key generations.
+#[derive(Clone)]
+pub struct CtrDrbg {
+    key_len: usize,
+    cipher: AES,
+    v: [u8; DRBG_BLOCK_LEN],
+    reseed_counter: u64,
+    use_df: bool,
+    pool: Vec<u8>,
+}
+
+impl CtrDrbg {
+    fn check_key_len(key_len: usize) -> Result<(), CryptoError> {
+        match key_len {
+            16 | 24 | 32 => Ok(()),
+            _ => Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the CTR_DRBG key length(in bytes) only can be 16/24/32", key_len))),
+        }
+    }
+
+    fn instantiate(key_len: usize, use_df: bool, seed_material: &[u8]) -> Result<Self, CryptoError> {
+        let mut drbg = Self {
+            key_len,
+            cipher: AES::new(vec![0u8; key_len])?,
+            v: [0u8; DRBG_BLOCK_LEN],
+            reseed_counter: 1,
+            use_df,
+            pool: Vec::with_capacity(DRBG_POOL_LEN),
+        };
+        drbg.update(seed_material)?;
+        Ok(drbg)
+    }
+
+    /// the CTR_DRBG with the derivation function, the `key_len` is 16/24/32 bytes for the AES-128/192/256. The
+    /// `entropy_input || nonce` must be at least the 3/2 security strength(e.g. 24 bytes for the AES-128), the nonce can
+    /// be empty if the entropy input is long enough.
+    pub fn new(key_len: usize, entropy_input: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, CryptoError> {
+        Self::check_key_len(key_len)?;
+        if entropy_input.len() < key_len || entropy_input.len() + nonce.len() < ((key_len * 3) >> 1) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The entropy input({} bytes) and the nonce({} bytes) are too short for the {} bits security strength",
+                    entropy_input.len(), nonce.len(), key_len << 3)));
+        }
+
+        let seed_material = Self::block_cipher_df(key_len, &[entropy_input, nonce, personalization], key_len + DRBG_BLOCK_LEN)?;
+        Self::instantiate(key_len, true, seed_material.as_slice())
+    }
+
+    /// the CTR_DRBG without the derivation function, the `entropy_input` must be the full entropy of the
+    /// `key_len + 16` bytes, and the `personalization` is at most `key_len + 16` bytes.
+    pub fn new_without_df(key_len: usize, entropy_input: &[u8], personalization: &[u8]) -> Result<Self, CryptoError> {
+        Self::check_key_len(key_len)?;
+        let seed_material = Self::xor_seed_material(key_len, entropy_input, personalization)?;
+        Self::instantiate(key_len, false, seed_material.as_slice())
+    }
+
+    /// the CTR_DRBG with the derivation function, the entropy input is read from the `seed`(e.g. the `DefaultSeed`)
+    pub fn from_seed<Sd: Seed<u32>>(key_len: usize, seed: &Sd, personalization: &[u8]) -> Result<Self, CryptoError> {
+        Self::check_key_len(key_len)?;
+        let entropy_input = Self::entropy_from_seed(seed, key_len + DRBG_BLOCK_LEN)?;
+        Self::new(key_len, entropy_input.as_slice(), &[], personalization)
+    }
+
+    /// the security strength in bits
+    pub fn security_strength(&self) -> usize {
+        self.key_len << 3
+    }
+
+    /// the number of the generate requests since the last (re)seeding
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter - 1
+    }
+
+    /// reseed by the fresh `entropy_input`, it must be at least the security strength with the derivation function, or
+    /// the `key_len + 16` bytes without the derivation function.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), CryptoError> {
+        let seed_material = if self.use_df {
+            if entropy_input.len() < self.key_len {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("The entropy input({} bytes) is too short for the {} bits security strength",
+                        entropy_input.len(), self.security_strength())));
+            }
+            Self::block_cipher_df(self.key_len, &[entropy_input, additional_input], self.seed_len())?
+        } else {
+            Self::xor_seed_material(self.key_len, entropy_input, additional_input)?
+        };
+
+        self.update(seed_material.as_slice())?;
+        self.reseed_counter = 1;
+        self.pool.clear();
+        Ok(())
+    }
+
+    /// generate the `len` bytes to the `dst`, the `len` is at most 65536 bytes. The `LimitExceeded` error is returned
+    /// if the DRBG must be reseeded.
+    pub fn generate(&mut self, dst: &mut Vec<u8>, len: usize, additional_input: &[u8]) -> Result<(), CryptoError> {
+        if len > DRBG_MAX_REQUEST_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The CTR_DRBG request({} bytes) is longer than {} bytes", len, DRBG_MAX_REQUEST_LEN)));
+        }
+        if self.reseed_counter > DRBG_RESEED_INTERVAL {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded, "The CTR_DRBG must be reseeded"));
+        }
+
+        let additional = if additional_input.is_empty() {
+            vec![0u8; self.seed_len()]
+        } else {
+            let additional = if self.use_df {
+                Self::block_cipher_df(self.key_len, &[additional_input], self.seed_len())?
+            } else {
+                Self::xor_seed_material(self.key_len, &vec![0u8; self.seed_len()], additional_input)?
+            };
+            self.update(additional.as_slice())?;
+            additional
+        };
+
+        dst.clear();
+        let mut block = Vec::with_capacity(DRBG_BLOCK_LEN);
+        while dst.len() < len {
+            Self::increment(&mut self.v);
+            self.cipher.encrypt(&mut block, self.v.as_ref())?;
+            dst.extend_from_slice(block.as_slice());
+        }
+        dst.truncate(len);
+
+        self.update(additional.as_slice())?;
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    fn seed_len(&self) -> usize {
+        self.key_len + DRBG_BLOCK_LEN
+    }
+
+    fn increment(v: &mut [u8; DRBG_BLOCK_LEN]) {
+        *v = u128::from_be_bytes(*v).wrapping_add(1).to_be_bytes();
+    }
+
+    /// CTR_DRBG_Update(10.2.1.2), the `provided_data` is the `seed_len` bytes
+    fn update(&mut self, provided_data: &[u8]) -> Result<(), CryptoError> {
+        let (mut temp, mut block) = (Vec::with_capacity(self.seed_len() + DRBG_BLOCK_LEN), Vec::with_capacity(DRBG_BLOCK_LEN));
+        while temp.len() < self.seed_len() {
+            Self::increment(&mut self.v);
+            self.cipher.encrypt(&mut block, self.v.as_ref())?;
+            temp.extend_from_slice(block.as_slice());
+        }
+        temp.truncate(self.seed_len());
+        temp.iter_mut().zip(provided_data.iter()).for_each(|(a, &b)| *a ^= b);
+
+        self.cipher = AES::new(temp[..self.key_len].to_vec())?;
+        self.v.copy_from_slice(&temp[self.key_len..]);
+        Ok(())
+    }
+
+    /// the `(entropy_input xor additional_input)`, the `additional_input` is padded by the zeros
+    fn xor_seed_material(key_len: usize, entropy_input: &[u8], additional_input: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let seed_len = key_len + DRBG_BLOCK_LEN;
+        if entropy_input.len() != seed_len || additional_input.len() > seed_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The entropy input must be {} bytes and the additional input is at most {} bytes without the derivation function, but got {} and {} bytes",
+                    seed_len, seed_len, entropy_input.len(), additional_input.len())));
+        }
+
+        let mut seed_material = entropy_input.to_vec();
+        seed_material.iter_mut().zip(additional_input.iter()).for_each(|(a, &b)| *a ^= b);
+        Ok(seed_material)
+    }
+
+    /// Block_Cipher_df(10.3.2), the `input` is the concatenation of the input strings
+    fn block_cipher_df(key_len: usize, input: &[&[u8]], out_len: usize) -> Result<Vec<u8>, CryptoError> {
+        let seed_len = key_len + DRBG_BLOCK_LEN;
+        let input_len = input.iter().map(|x| x.len()).sum::<usize>();
+        if input_len > (u32::MAX as usize) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The input of the Block_Cipher_df is too long"));
+        }
+
+        // S = L || N || input_string || 0x80 || 0^*
+        let mut s = Vec::with_capacity(input_len + 8 + DRBG_BLOCK_LEN);
+        s.extend_from_slice(&(input_len as u32).to_be_bytes());
+        s.extend_from_slice(&(out_len as u32).to_be_bytes());
+        input.iter().for_each(|x| s.extend_from_slice(x));
+        s.push(0x80);
+        s.resize(s.len() + (DRBG_BLOCK_LEN - s.len() % DRBG_BLOCK_LEN) % DRBG_BLOCK_LEN, 0);
+
+        let k = AES::new((0..key_len as u8).collect())?;
+        let (mut temp, mut block) = (Vec::with_capacity(seed_len + DRBG_BLOCK_LEN), Vec::with_capacity(DRBG_BLOCK_LEN));
+        let mut i = 0u32;
+        while temp.len() < seed_len {
+            // BCC(K, IV || S), IV = i || 0^{96}
+            let mut chain = [0u8; DRBG_BLOCK_LEN];
+            chain[..4].copy_from_slice(&i.to_be_bytes());
+            k.encrypt(&mut block, chain.as_ref())?;
+            for blk in s.chunks(DRBG_BLOCK_LEN) {
+                chain.iter_mut().zip(block.iter().zip(blk.iter())).for_each(|(c, (&a, &b))| *c = a ^ b);
+                k.encrypt(&mut block, chain.as_ref())?;
+            }
+            temp.extend_from_slice(block.as_slice());
+            i += 1;
+        }
+
+        let k = AES::new(temp[..key_len].to_vec())?;
+        let mut x = temp[key_len..seed_len].to_vec();
+        let mut out = Vec::with_capacity(out_len + DRBG_BLOCK_LEN);
+        while out.len() < out_len {
+            k.encrypt(&mut block, x.as_slice())?;
+            x.clear();
+            x.extend_from_slice(block.as_slice());
+            out.extend_from_slice(block.as_slice());
+        }
+        out.truncate(out_len);
+        Ok(out)
+    }
+
+    fn entropy_from_seed<Sd: Seed<u32>>(seed: &Sd, len: usize) -> Result<Vec<u8>, CryptoError> {
+        let mut entropy = Vec::with_capacity(len + 4);
+        while entropy.len() < len {
+            // the hardware entropy source(e.g. the RDSEED) may be exhausted temporarily
+            let mut s = seed.seed();
+            for _ in 0..DRBG_SEED_RETRIES {
+                if s.is_ok() {
+                    break;
+                }
+                std::thread::yield_now();
+                s = seed.seed();
+            }
+            let s = s.map_err(|e| CryptoError::new(CryptoErrorKind::RandError, e))?;
+            entropy.extend_from_slice(&s.to_be_bytes());
+        }
+        entropy.truncate(len);
+        Ok(entropy)
+    }
+}
+
+impl Source<u32> for CtrDrbg {
+    fn gen(&mut self) -> Result<u32, RandError> {
+        if self.pool.len() < 4 {
+            let mut pool = std::mem::take(&mut self.pool);
+            let r = self.generate(&mut pool, DRBG_POOL_LEN, &[]);
+            self.pool = pool;
+            r.map_err(|e| RandError::new(RandErrKind::NoNewRandNumberGen, e))?;
+        }
+
+        let n = self.pool.len();
+        let r = u32::from_be_bytes([self.pool[n - 4], self.pool[n - 3], self.pool[n - 2], self.pool[n - 1]]);
+        self.pool.truncate(n - 4);
+        Ok(r)
+    }
+
+    /// reseed by the `sd`
+    fn reset<Sd: Seed<u32>>(&mut self, sd: &Sd) -> Result<(), RandError> {
+        let entropy = Self::entropy_from_seed(sd, self.seed_len()).map_err(|e| RandError::new(RandErrKind::NoNewRandSeedGen, e))?;
+        self.reseed(entropy.as_slice(), &[]).map_err(|e| RandError::new(RandErrKind::InnerErr, e))
+    }
+}
+
+impl IterSource<u32> for CtrDrbg {
+    fn iter_mut(&mut self) -> Iter<'_, Self, u32> where Self: std::marker::Sized {
+        Iter::new(self)
+    }
+}
\ No newline at end of file
@@ -189,6 +189,7 @@ pub struct ECBEncrypt<C, P> {
 
 impl_cipher!(ECBEncrypt, ecb);
 impl_fn_reset!(ECBEncrypt);
+impl_stream_state!(ECBEncrypt, ecb, STATE_MODE_ECB_ENCRYPT);
 
 impl<C, P> EncryptStream for ECBEncrypt<C, P> 
     where C: Cipher, P: 'static + Padding {
@@ -270,6 +271,7 @@ pub struct ECBDecrypt<C, P> {
 
 impl_cipher!(ECBDecrypt, ecb);
 impl_fn_reset!(ECBDecrypt);
+impl_stream_state!(ECBDecrypt, ecb, STATE_MODE_ECB_DECRYPT);
 
 impl<C, P> DecryptStream for ECBDecrypt<C, P> 
     where C: Cipher, P: 'static + Padding {
@@ -322,7 +324,7 @@ impl<C, P> DecryptStream for ECBDecrypt<C, P>
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "aes"))]
 mod tests {
     use crate::aes::AES;
     use crate::cipher_mode::{DefaultPadding, ECB, EmptyPadding, Padding, EncryptStream};
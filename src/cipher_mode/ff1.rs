@@ -0,0 +1,256 @@
+//! FF1(the format-preserving encryption)
+//! NIST SP 800-38G
+//!
+//! The plaintext and ciphertext are the numeral strings of the same length and radix, e.g. the 16 decimal digits of the
+//! PAN is encrypted to another 16 decimal digits. The numeral string is split into the two halves $A || B$, and
+//! enciphered by the 10 rounds Feistel network whose round function is the CBC-MAC of the block cipher over the
+//! `[1, 2, 1] || [radix]^3 || [10] || [u mod 256] || [n]^4 || [t]^4` and the `T || [0]^{(-t-b-1) mod 16} || [i] || [NUM_radix(B)]^b`.
+//!
+//! The `NumeralAlphabet` converts the characters to the numerals, e.g. `0123456789` for the decimal digits.
+//!
+//! # Example
+//!
+//! ```Rust
+//! use rcrypto::{AES, cipher_mode::{FF1, NumeralAlphabet}};
+//!
+//! let ff1 = FF1::new(AES::aes_128(key), 10).unwrap();
+//! let digits = NumeralAlphabet::decimal();
+//! let x = digits.encode("0123456789").unwrap();
+//! let mut y = Vec::new();
+//! ff1.encrypt(&mut y, tweak, x.as_slice()).unwrap();
+//! assert_eq!(digits.decode(y.as_slice()).unwrap(), "2433477484");
+//! ```
+
+use rmath::bigint::BigInt;
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+
+const FF1_BLOCK_LEN: usize = 16;
+const FF1_ROUNDS: u8 = 10;
+const FF1_MAX_RADIX: u32 = 1 << 16;
+/// the domain size $radix^{minlen}$ must be not less than one million
+const FF1_MIN_DOMAIN: u64 = 1_000_000;
+
+/// FF1 with the 128-bit block cipher(AES)
+#[derive(Clone)]
+pub struct FF1<C> {
+    cipher: C,
+    radix: u32,
+    min_len: usize,
+}
+
+impl<C: Cipher> FF1<C> {
+    /// `radix`: the base of the numerals, it must be in the range $[2, 2^{16}]$;
+    pub fn new(cipher: C, radix: u32) -> Result<Self, CryptoError> {
+        if cipher.block_size() != Some(FF1_BLOCK_LEN) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The FF1 needs the 128-bit block cipher"));
+        } else if !(2..=FF1_MAX_RADIX).contains(&radix) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong radix: {}, the FF1 radix must be in the range [2, 2^16]", radix)));
+        }
+
+        // radix^minlen >= 10^6 and minlen >= 2
+        let (mut min_len, mut domain) = (0usize, 1u64);
+        while domain < FF1_MIN_DOMAIN {
+            domain *= radix as u64;
+            min_len += 1;
+        }
+
+        Ok(Self { cipher, radix, min_len: std::cmp::max(min_len, 2) })
+    }
+
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// the minimum length of the numeral string
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// encrypt the numeral string `x` with the `tweak`, the ciphertext numeral string will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u16>, tweak: &[u8], x: &[u16]) -> Result<(), CryptoError> {
+        self.check(tweak, x)?;
+        let (u, v) = (x.len() >> 1, x.len() - (x.len() >> 1));
+        let (p, b, d) = self.round_params(tweak, x.len(), u, v);
+        let (ru, rv) = (self.pow(u), self.pow(v));
+
+        let (mut a, mut bb) = (self.num(&x[..u]), self.num(&x[u..]));
+        for i in 0..FF1_ROUNDS {
+            let y = self.round(p.as_slice(), tweak, i, &bb, b, d)?;
+            let m = if i & 1 == 0 { &ru } else { &rv };
+            let c = (a + y).rem_euclid(m.clone());
+            a = bb;
+            bb = c;
+        }
+
+        dst.clear();
+        self.str_m(dst, &a, u);
+        self.str_m(dst, &bb, v);
+        Ok(())
+    }
+
+    /// decrypt the numeral string `x` with the `tweak`, the plaintext numeral string will output to the `dst`.
+    pub fn decrypt(&self, dst: &mut Vec<u16>, tweak: &[u8], x: &[u16]) -> Result<(), CryptoError> {
+        self.check(tweak, x)?;
+        let (u, v) = (x.len() >> 1, x.len() - (x.len() >> 1));
+        let (p, b, d) = self.round_params(tweak, x.len(), u, v);
+        let (ru, rv) = (self.pow(u), self.pow(v));
+
+        let (mut a, mut bb) = (self.num(&x[..u]), self.num(&x[u..]));
+        for i in (0..FF1_ROUNDS).rev() {
+            let y = self.round(p.as_slice(), tweak, i, &a, b, d)?;
+            let m = if i & 1 == 0 { &ru } else { &rv };
+            let c = (bb - y).rem_euclid(m.clone());
+            bb = a;
+            a = c;
+        }
+
+        dst.clear();
+        self.str_m(dst, &a, u);
+        self.str_m(dst, &bb, v);
+        Ok(())
+    }
+
+    fn check(&self, tweak: &[u8], x: &[u16]) -> Result<(), CryptoError> {
+        if x.len() < self.min_len || x.len() > (u32::MAX as usize) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong numeral string length: {}, the FF1 numeral string length must be in the range [{}, 2^32)", x.len(), self.min_len)))
+        } else if tweak.len() > (u32::MAX as usize) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The FF1 tweak is too long"))
+        } else if let Some(&e) = x.iter().find(|&&e| (e as u32) >= self.radix) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong numeral: {}, the numeral must be less than the radix {}", e, self.radix)))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// (P, b, d)
+    fn round_params(&self, tweak: &[u8], n: usize, u: usize, v: usize) -> (Vec<u8>, usize, usize) {
+        // b = ceil(ceil(v * log2(radix)) / 8), the bits length of radix^v - 1 is ceil(v * log2(radix))
+        let b = ((self.pow(v) - BigInt::from(1u32)).bits_len() + 7) >> 3;
+        let d = 4 * b.div_ceil(4) + 4;
+
+        let mut p = vec![1u8, 2, 1];
+        p.extend_from_slice(&self.radix.to_be_bytes()[1..]);
+        p.push(FF1_ROUNDS);
+        p.push((u & 0xff) as u8);
+        p.extend_from_slice((n as u32).to_be_bytes().as_ref());
+        p.extend_from_slice((tweak.len() as u32).to_be_bytes().as_ref());
+        (p, b, d)
+    }
+
+    /// $y = NUM(S)$, $S$ is the first $d$ bytes of $R || CIPH(R \oplus [1]^{16}) || CIPH(R \oplus [2]^{16}) || ...$
+    fn round(&self, p: &[u8], tweak: &[u8], i: u8, x: &BigInt, b: usize, d: usize) -> Result<BigInt, CryptoError> {
+        let mut q = tweak.to_vec();
+        q.resize(tweak.len() + ((FF1_BLOCK_LEN - (tweak.len() + b + 1) % FF1_BLOCK_LEN) % FF1_BLOCK_LEN), 0);
+        q.push(i);
+        let num = x.to_be_bytes();
+        q.resize(q.len() + b - num.len(), 0);
+        q.extend_from_slice(num.as_slice());
+
+        // PRF: the CBC-MAC with the zero IV
+        let (mut r, mut buf) = (vec![0u8; FF1_BLOCK_LEN], Vec::with_capacity(FF1_BLOCK_LEN));
+        for block in p.chunks(FF1_BLOCK_LEN).chain(q.chunks(FF1_BLOCK_LEN)) {
+            r.iter_mut().zip(block.iter()).for_each(|(a, &b)| *a ^= b);
+            self.cipher.encrypt(&mut buf, r.as_slice())?;
+            r.clear();
+            r.extend_from_slice(buf.as_slice());
+        }
+
+        let mut s = r.clone();
+        for j in 1..(d.div_ceil(FF1_BLOCK_LEN) as u64) {
+            let mut blk = r.clone();
+            blk[(FF1_BLOCK_LEN - 8)..].iter_mut().zip(j.to_be_bytes().iter()).for_each(|(a, &b)| *a ^= b);
+            self.cipher.encrypt(&mut buf, blk.as_slice())?;
+            s.extend_from_slice(buf.as_slice());
+        }
+
+        Ok(BigInt::from_be_bytes(&s[..d]))
+    }
+
+    /// $radix^e$
+    fn pow(&self, e: usize) -> BigInt {
+        let (mut r, mut base, mut e) = (BigInt::from(1u32), BigInt::from(self.radix), e);
+        while e > 0 {
+            if e & 1 == 1 {
+                r *= base.clone();
+            }
+            base = base.clone() * base;
+            e >>= 1;
+        }
+        r
+    }
+
+    /// $NUM_{radix}(X)$
+    fn num(&self, x: &[u16]) -> BigInt {
+        let radix = BigInt::from(self.radix);
+        x.iter().fold(BigInt::from(0u32), |a, &e| a * radix.clone() + BigInt::from(e as u32))
+    }
+
+    /// append the $STR^m_{radix}(x)$ to the `dst`
+    fn str_m(&self, dst: &mut Vec<u16>, x: &BigInt, m: usize) {
+        let (start, radix) = (dst.len(), BigInt::from(self.radix));
+        let mut x = x.deep_clone();
+        for _ in 0..m {
+            let digit = x.rem_euclid(radix.clone()).to_be_bytes();
+            dst.push(digit.iter().fold(0u32, |a, &b| (a << 8) | (b as u32)) as u16);
+            x = x.div_euclid(radix.clone());
+        }
+        dst[start..].reverse();
+    }
+}
+
+/// The characters of the numerals, the `i`-th character represents the numeral `i`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumeralAlphabet {
+    chars: Vec<char>,
+}
+
+impl NumeralAlphabet {
+    /// the characters must be distinct, and the number of the characters(the radix) must be in the range $[2, 2^{16}]$
+    pub fn new(alphabet: &str) -> Result<Self, CryptoError> {
+        let chars = alphabet.chars().collect::<Vec<_>>();
+        if chars.len() < 2 || chars.len() > (FF1_MAX_RADIX as usize) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong alphabet length: {}, the radix must be in the range [2, 2^16]", chars.len())));
+        }
+
+        for (i, c) in chars.iter().enumerate() {
+            if chars[..i].contains(c) {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("The character {:?} is repeated in the alphabet", c)));
+            }
+        }
+        Ok(Self { chars })
+    }
+
+    /// `0123456789`
+    pub fn decimal() -> Self {
+        Self { chars: "0123456789".chars().collect() }
+    }
+
+    /// `0123456789abcdefghijklmnopqrstuvwxyz`
+    pub fn base36() -> Self {
+        Self { chars: "0123456789abcdefghijklmnopqrstuvwxyz".chars().collect() }
+    }
+
+    pub fn radix(&self) -> u32 {
+        self.chars.len() as u32
+    }
+
+    /// encode the `s` to the numeral string, the `InvalidParameter` is returned if the character is not in the alphabet
+    pub fn encode(&self, s: &str) -> Result<Vec<u16>, CryptoError> {
+        s.chars().map(|c| {
+            self.chars.iter().position(|&x| x == c).map(|i| i as u16).ok_or_else(||
+                CryptoError::new(CryptoErrorKind::InvalidParameter, format!("The character {:?} is not in the alphabet", c)))
+        }).collect()
+    }
+
+    /// decode the numeral string `x` to the string
+    pub fn decode(&self, x: &[u16]) -> Result<String, CryptoError> {
+        x.iter().map(|&e| {
+            self.chars.get(e as usize).copied().ok_or_else(||
+                CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Wrong numeral: {}, the numeral must be less than the radix {}", e, self.radix())))
+        }).collect()
+    }
+}
@@ -0,0 +1,332 @@
+//! GCM(Galois/Counter Mode) and GMAC
+//! NIST SP 800-38D
+//!
+//! H = CIPH_K(0^128), J_0 = IV || 0^31 || 1 if len(IV) = 96, otherwise J_0 = GHASH_H(IV || 0^{s+64} || [len(IV)]_64);
+//! C = GCTR_K(inc_32(J_0), P), S = GHASH_H(A || 0^v || C || 0^u || [len(A)]_64 || [len(C)]_64),
+//! T = MSB_t(GCTR_K(J_0, S)), the GMAC is the GCM with the empty plaintext.
+//!
+//! The tag can be appended to the ciphertext(`encrypt/decrypt`) or kept detached(`encrypt_detached/decrypt_detached`),
+//! the associated data can be fed partially by the `GCMContext`. The GCM is generic over the 128 bits block ciphers,
+//! the `GCM::new_sm4` is the SM4-GCM of the national standard suite(RFC 8998, the `TLS_SM4_GCM_SM3`).
+
+use crate::{Cipher, CryptoError, CryptoErrorKind, AEAD};
+use crate::cipher_mode::DataLimit;
+use crate::cipher_mode::limits::limit_err;
+use crate::aead::{Nonce, Tag};
+#[cfg(feature = "sm")]
+use crate::SM4;
+
+const GCM_BLOCK_LEN: usize = 16;
+/// the plaintext of one invocation is not more than $2^{39}-256$ bits
+const GCM_MAX_DATA_LEN: u128 = (1u128 << 36) - 32;
+/// the associated data is not more than $2^{64}-1$ bits
+const GCM_MAX_AAD_LEN: u64 = u64::MAX >> 3;
+/// the polynomial x^128 + x^7 + x^2 + x + 1 in the bit-reflected order
+const GCM_R: u128 = 0xe1 << 120;
+
+fn tag_err<T>() -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The GCM tag not match"))
+}
+
+pub struct GCM<C> {
+    cipher: C,
+    // the hash subkey H
+    h: u128,
+    tag_len: usize,
+}
+
+impl<C: Cipher> GCM<C> {
+    /// `cipher`: the block cipher with the 128 bits block size, e.g. AES, SM4;
+    /// `tag_len`: the tag length(in bytes), it must be one of the 4, 8, 12, 13, 14, 15, 16, the 4 and 8 bytes tag
+    /// should only be used by the protocols which limit the data length and the invocations(SP 800-38D Appendix C).
+    pub fn new(cipher: C, tag_len: usize) -> Result<Self, CryptoError> {
+        if cipher.block_size() != Some(GCM_BLOCK_LEN) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The GCM only support the block cipher with the 128 bits block size"));
+        } else if !(tag_len == 4 || tag_len == 8 || (12..=16).contains(&tag_len)) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong tag length: {}, it must be one of the 4, 8, 12, 13, 14, 15, 16", tag_len)));
+        }
+
+        let mut buf = Vec::with_capacity(GCM_BLOCK_LEN);
+        cipher.encrypt(&mut buf, [0u8; GCM_BLOCK_LEN].as_ref())?;
+        let h = Self::to_u128(buf.as_slice());
+        Ok(Self { cipher, h, tag_len })
+    }
+
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+    
+    /// the GCM keeps no state between the messages(the `GCMContext` is consumed by each message), so the reset
+    /// is no-op, it's for the uniform reuse of the modes.
+    pub fn reset(&mut self) {}
+
+    fn to_u128(block: &[u8]) -> u128 {
+        let mut b = [0u8; GCM_BLOCK_LEN];
+        b[..block.len()].copy_from_slice(block);
+        u128::from_be_bytes(b)
+    }
+
+    /// X·Y in the GF(2^128), the bit 0 of the block is the most significant bit of the u128
+    fn gf_mul(x: u128, y: u128) -> u128 {
+        let (mut z, mut v) = (0u128, y);
+        for i in (0..128).rev() {
+            z ^= v & 0u128.wrapping_sub((x >> i) & 1);
+            v = (v >> 1) ^ (GCM_R & 0u128.wrapping_sub(v & 1));
+        }
+        z
+    }
+
+    /// Y = (Y xor X_i)·H for the blocks of the `data`, the last partial block is padded by zeros
+    fn ghash(&self, y: &mut u128, data: &[u8]) {
+        for block in data.chunks(GCM_BLOCK_LEN) {
+            *y = Self::gf_mul(*y ^ Self::to_u128(block), self.h);
+        }
+    }
+
+    fn encrypt_block(&self, block: u128, buf: &mut Vec<u8>) -> Result<u128, CryptoError> {
+        self.cipher.encrypt(buf, block.to_be_bytes().as_ref())?;
+        Ok(Self::to_u128(buf.as_slice()))
+    }
+
+    fn j0(&self, nonce: &[u8]) -> Result<u128, CryptoError> {
+        if nonce.is_empty() {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The GCM nonce must not be empty"))
+        } else if ((nonce.len() as u128) << 3) > (u64::MAX as u128) {
+            limit_err(format!("The nonce length {} is great than the 2^64-1 bits", nonce.len()))
+        } else if nonce.len() == 12 {
+            Ok((Self::to_u128(nonce)) | 1)
+        } else {
+            let mut y = 0u128;
+            self.ghash(&mut y, nonce);
+            self.ghash(&mut y, ((nonce.len() as u128) << 3).to_be_bytes().as_ref());
+            Ok(y)
+        }
+    }
+
+    /// GCTR_K(inc_32(J_0), data), the counter is increased by the low 32 bits
+    fn gctr(&self, j0: u128, data: &[u8], dst: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let (mut cb, mut buf) = (j0, Vec::with_capacity(GCM_BLOCK_LEN));
+        for block in data.chunks(GCM_BLOCK_LEN) {
+            cb = (cb & !(u32::MAX as u128)) | ((cb as u32).wrapping_add(1) as u128);
+            let s = self.encrypt_block(cb, &mut buf)?.to_be_bytes();
+            dst.extend(block.iter().zip(s.iter()).map(|(&a, &b)| a ^ b));
+        }
+        Ok(())
+    }
+
+    fn check_data_len(len: usize) -> Result<(), CryptoError> {
+        if (len as u128) > GCM_MAX_DATA_LEN {
+            limit_err(format!("The data length {} is great than the 2^39-256 bits", len))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// start the invocation with the `nonce`, the associated data can be fed partially by the `GCMContext::update_aad`.
+    pub fn context(&self, nonce: &[u8]) -> Result<GCMContext<'_, C>, CryptoError> {
+        let j0 = self.j0(nonce)?;
+        Ok(GCMContext { gcm: self, j0, y: 0, aad_buf: Vec::with_capacity(GCM_BLOCK_LEN), aad_len: 0 })
+    }
+
+    /// encrypt and authenticate the `plaintext` and `aad`, the ciphertext will output to the `dst`, and return the
+    /// detached tag.
+    pub fn encrypt_detached(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut ctx = self.context(nonce)?;
+        ctx.update_aad(aad)?;
+        ctx.encrypt(dst, plaintext)
+    }
+
+    /// verify the detached `tag` and decrypt the `ciphertext`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt_detached(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<(), CryptoError> {
+        let mut ctx = self.context(nonce)?;
+        ctx.update_aad(aad)?;
+        ctx.decrypt(dst, ciphertext, tag)
+    }
+
+    fn check_tag_len(&self, len: usize) -> Result<(), CryptoError> {
+        if len == self.tag_len {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The GCM tag is {} bytes, but got the Tag<{}>", self.tag_len, len)))
+        }
+    }
+
+    /// encrypt and authenticate the `plaintext` and `aad`, the `ciphertext||tag` will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        let tag = self.encrypt_detached(dst, nonce, aad, plaintext)?;
+        dst.extend_from_slice(tag.as_slice());
+        Ok(())
+    }
+
+    /// verify and decrypt the `ciphertext||tag`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if ciphertext.len() < self.tag_len {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the tag"));
+        }
+        let (ct, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len);
+        self.decrypt_detached(dst, nonce, aad, ct, tag)
+    }
+
+    /// GMAC, authenticate the `aad` only and return the tag
+    pub fn mac(&self, nonce: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut ctx = self.context(nonce)?;
+        ctx.update_aad(aad)?;
+        ctx.finalize_mac()
+    }
+
+    /// verify the GMAC `tag` of the `aad`
+    pub fn verify_mac(&self, nonce: &[u8], aad: &[u8], tag: &[u8]) -> Result<(), CryptoError> {
+        let mut ctx = self.context(nonce)?;
+        ctx.update_aad(aad)?;
+        ctx.verify_mac(tag)
+    }
+}
+
+/// The invocation of the GCM under one nonce, the associated data is fed by the `update_aad` for any times,
+/// then it's finished by the `encrypt`, `decrypt`, `finalize_mac` or `verify_mac`.
+pub struct GCMContext<'a, C> {
+    gcm: &'a GCM<C>,
+    j0: u128,
+    // the GHASH state
+    y: u128,
+    // the partial block of the associated data
+    aad_buf: Vec<u8>,
+    aad_len: u64,
+}
+
+impl<'a, C: Cipher> GCMContext<'a, C> {
+    /// feed the associated data
+    pub fn update_aad(&mut self, aad: &[u8]) -> Result<(), CryptoError> {
+        if (aad.len() as u64) > GCM_MAX_AAD_LEN - self.aad_len {
+            return limit_err("The associated data is great than the 2^64-1 bits".to_string());
+        }
+        self.aad_len += aad.len() as u64;
+
+        let mut aad = aad;
+        if !self.aad_buf.is_empty() {
+            let l = (GCM_BLOCK_LEN - self.aad_buf.len()).min(aad.len());
+            self.aad_buf.extend_from_slice(&aad[..l]);
+            aad = &aad[l..];
+            if self.aad_buf.len() < GCM_BLOCK_LEN {
+                return Ok(());
+            }
+            self.gcm.ghash(&mut self.y, self.aad_buf.as_slice());
+            self.aad_buf.clear();
+        }
+
+        let full_len = aad.len() - (aad.len() % GCM_BLOCK_LEN);
+        self.gcm.ghash(&mut self.y, &aad[..full_len]);
+        self.aad_buf.extend_from_slice(&aad[full_len..]);
+        Ok(())
+    }
+
+    /// the tag of the associated data and the `ciphertext`
+    fn tag(mut self, ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let gcm = self.gcm;
+        gcm.ghash(&mut self.y, self.aad_buf.as_slice());
+        gcm.ghash(&mut self.y, ciphertext);
+        let lens = ((self.aad_len as u128) << 67) | ((ciphertext.len() as u128) << 3);
+        gcm.ghash(&mut self.y, lens.to_be_bytes().as_ref());
+
+        let mut buf = Vec::with_capacity(GCM_BLOCK_LEN);
+        let t = (gcm.encrypt_block(self.j0, &mut buf)? ^ self.y).to_be_bytes();
+        Ok(t[..gcm.tag_len].to_vec())
+    }
+
+    /// encrypt the `plaintext` to the `dst`, return the tag
+    pub fn encrypt(self, dst: &mut Vec<u8>, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        GCM::<C>::check_data_len(plaintext.len())?;
+        dst.clear();
+        self.gcm.gctr(self.j0, plaintext, dst)?;
+        self.tag(dst.as_slice())
+    }
+
+    /// verify the `tag` and decrypt the `ciphertext` to the `dst`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt(self, dst: &mut Vec<u8>, ciphertext: &[u8], tag: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        GCM::<C>::check_data_len(ciphertext.len())?;
+        let (gcm, j0) = (self.gcm, self.j0);
+        let expected = self.tag(ciphertext)?;
+        if tag.len() != expected.len() || expected.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            tag_err()
+        } else {
+            gcm.gctr(j0, ciphertext, dst)
+        }
+    }
+
+    /// GMAC, return the tag of the associated data
+    pub fn finalize_mac(self) -> Result<Vec<u8>, CryptoError> {
+        self.tag(&[])
+    }
+
+    /// verify the GMAC `tag` of the associated data
+    pub fn verify_mac(self, tag: &[u8]) -> Result<(), CryptoError> {
+        let expected = self.tag(&[])?;
+        if tag.len() != expected.len() || expected.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            tag_err()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "sm")]
+impl GCM<SM4> {
+    /// SM4-GCM with the 16 bytes `key`, see the `new` for the `tag_len`
+    pub fn new_sm4(key: [u8; 16], tag_len: usize) -> Result<Self, CryptoError> {
+        Self::new(SM4::new(key), tag_len)
+    }
+}
+
+impl<C: Cipher> AEAD for GCM<C> {
+    /// the recommended nonce length, the other length is also supported by the `encrypt/decrypt`
+    fn nonce_len(&self) -> usize {
+        12
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, nonce, aad, plaintext)
+    }
+
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.decrypt(dst, nonce, aad, ciphertext)
+    }
+
+    /// the `encrypt_detached` with the typed nonce and tag, the nonce of any length is supported, the `T` must be
+    /// the `tag_len`
+    fn seal_detached<const N: usize, const T: usize>(&self, dst: &mut Vec<u8>, nonce: &Nonce<N>, aad: &[u8], plaintext: &[u8]) -> Result<Tag<T>, CryptoError> {
+        self.check_tag_len(T)?;
+        let tag = self.encrypt_detached(dst, nonce.as_ref(), aad, plaintext)?;
+        Tag::from_slice(tag.as_slice())
+    }
+
+    /// the `decrypt_detached` with the typed nonce and tag, see the `seal_detached`
+    fn open_detached<const N: usize, const T: usize>(&self, dst: &mut Vec<u8>, nonce: &Nonce<N>, aad: &[u8], ciphertext: &[u8], tag: &Tag<T>) -> Result<(), CryptoError> {
+        self.check_tag_len(T)?;
+        self.decrypt_detached(dst, nonce.as_ref(), aad, ciphertext, tag.as_ref())
+    }
+}
+
+impl<C: Cipher> DataLimit for GCM<C> {
+    /// the maximum plaintext length of one nonce
+    fn remaining_bytes(&self) -> Option<u128> {
+        Some(GCM_MAX_DATA_LEN)
+    }
+}
+
+impl<C: Cipher + Clone> Clone for GCM<C> {
+    fn clone(&self) -> Self {
+        Self {
+            cipher: self.cipher.clone(),
+            h: self.h,
+            tag_len: self.tag_len,
+        }
+    }
+}
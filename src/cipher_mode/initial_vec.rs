@@ -1,10 +1,18 @@
 use crate::{CryptoError, Cipher, CryptoErrorKind};
+#[cfg(feature = "aes")]
+use crate::cipher_mode::CtrDrbg;
+use crate::cipher_mode::Counter;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
 pub trait InitialVec<C: Cipher> {
     fn initial_vec(&mut self, iv: &mut Vec<u8>) -> std::result::Result<(), CryptoError>;
 }
 
+/// the random IV of the `block_size` bytes which is read from the `R`, the IV is as unpredictable as the `R`, so the `R`
+/// must be the cryptographically secure random source(e.g. the `CtrDrbg`, see the `DrbgInitialVec`). The IVs are
+/// unique with the overwhelming probability only, the collision of the 128 bits random IVs is expected after the
+/// $2^{64}$ IVs, use the `CounterInitialVec` if the IVs must never repeat.
 #[derive(Clone)]
 pub struct DefaultInitialVec<C, R> {
     block_size: usize,
@@ -43,3 +51,286 @@ impl<C: Cipher, R: rmath::rand::Source<u32>> InitialVec<C> for DefaultInitialVec
         Ok(())
     }
 }
+
+
+/// the caller specified initialization vector, it's only used to decrypt or to be compatible with the
+/// existing formats, the IV must be unpredictable and never reused under the same key for the encryption.
+#[derive(Clone)]
+pub struct FixedInitialVec {
+    iv: Vec<u8>,
+}
+
+impl FixedInitialVec {
+    pub fn new(iv: Vec<u8>) -> Self {
+        Self {
+            iv,
+        }
+    }
+}
+
+impl<C: Cipher> InitialVec<C> for FixedInitialVec {
+    fn initial_vec(&mut self, iv: &mut Vec<u8>) -> Result<(), CryptoError> {
+        iv.clear();
+        iv.extend_from_slice(self.iv.as_slice());
+        Ok(())
+    }
+}
+
+impl<const N: usize> From<crate::aead::Iv<N>> for FixedInitialVec {
+    fn from(iv: crate::aead::Iv<N>) -> Self {
+        Self::new(iv.as_ref().to_vec())
+    }
+}
+
+/// the random IV by the AES-256 CTR_DRBG(SP 800-90A) which is seeded by the `DefaultSeed`
+#[cfg(feature = "aes")]
+pub type DrbgInitialVec<C> = DefaultInitialVec<C, CtrDrbg>;
+
+#[cfg(feature = "aes")]
+impl<C: Cipher> DefaultInitialVec<C, CtrDrbg> {
+    /// the `personalization` is the optional identification string of the instance, e.g. the host name and the
+    /// process id.
+    pub fn from_entropy(cipher: &C, personalization: &[u8]) -> Result<Self, CryptoError> {
+        let seed = rmath::rand::DefaultSeed::<u32>::new().map_err(|e| CryptoError::new(CryptoErrorKind::RandError, e))?;
+        Ok(Self::new(cipher, CtrDrbg::from_seed(32, &seed, personalization)?))
+    }
+}
+
+/// The persistent storage hook of the `CounterInitialVec`
+pub trait IvCounterStore {
+    /// the counter value which is persisted by the last `store`, `None` means nothing is persisted
+    fn load(&mut self) -> Result<Option<u128>, CryptoError>;
+    
+    /// persist the `val`, it must be durable before returned(e.g. write the temporary file, sync it and rename it to
+    /// the target file), otherwise the IVs may repeat after the crash.
+    fn store(&mut self, val: u128) -> Result<(), CryptoError>;
+}
+
+/// the counter value in the file as the decimal string, the file is replaced by the synced temporary file `path.tmp`,
+/// and the parent directory is synced after the rename
+#[derive(Clone)]
+pub struct FileIvCounterStore {
+    path: PathBuf,
+}
+
+impl FileIvCounterStore {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+        }
+    }
+
+    /// the rename is durable only after the directory entry is synced
+    #[cfg(unix)]
+    fn sync_parent(path: &std::path::Path) -> Result<(), CryptoError> {
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => std::path::Path::new("."),
+        };
+        std::fs::File::open(parent).and_then(|d| d.sync_all()).map_err(|e| CryptoError::new(CryptoErrorKind::OuterErr, e))
+    }
+
+    /// the directory cannot be opened as the file on the other platforms, the rename is flushed by the file system
+    #[cfg(not(unix))]
+    fn sync_parent(_path: &std::path::Path) -> Result<(), CryptoError> {
+        Ok(())
+    }
+}
+
+impl IvCounterStore for FileIvCounterStore {
+    fn load(&mut self) -> Result<Option<u128>, CryptoError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(s) => s.trim().parse::<u128>().map(Some).map_err(|e| {
+                CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid IV counter file {}: {}", self.path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CryptoError::new(CryptoErrorKind::OuterErr, e)),
+        }
+    }
+
+    fn store(&mut self, val: u128) -> Result<(), CryptoError> {
+        use std::io::Write;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        
+        let mut f = std::fs::File::create(&tmp).map_err(|e| CryptoError::new(CryptoErrorKind::OuterErr, e))?;
+        f.write_all(val.to_string().as_bytes()).and_then(|_| f.sync_all())
+            .and_then(|_| std::fs::rename(&tmp, &self.path)).map_err(|e| CryptoError::new(CryptoErrorKind::OuterErr, e))?;
+        Self::sync_parent(&self.path)
+    }
+}
+
+/// The monotonic counter IV which never repeats under the same `IvCounterStore`, even if the process crashes.
+///
+/// the counter values are reserved by the windows of the `reserve` values: the end of the next window is persisted by
+/// the `IvCounterStore::store` before any value of the window is used, and the counter restarts from the persisted
+/// value, so the unused values of the last window are skipped after the restart. The store must not be shared by the
+/// different instances under the same key.
+pub struct CounterInitialVec<C, S> {
+    block_size: usize,
+    cipher: Option<C>,
+    store: S,
+    shift: usize,
+    next: u128,
+    reserved: u128,
+    reserve: u128,
+    limit: u128,
+}
+
+impl<C: Cipher, S: IvCounterStore> CounterInitialVec<C, S> {
+    fn with_params(block_size: Option<usize>, cipher: Option<C>, mut store: S, reserve: u64, shift: usize) -> Result<Self, CryptoError> {
+        let block_size = block_size.unwrap_or(0);
+        let bits = std::cmp::min(block_size << 3, 128);
+        if bits == 0 || shift >= bits || reserve == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The counter IV needs the block cipher, the non-zero reserve and the block counter bits({}) less than {}", shift, bits)));
+        }
+        
+        let limit = if bits - shift >= 128 { u128::MAX } else { 1u128 << (bits - shift) };
+        let next = store.load()?.unwrap_or(0);
+        Ok(Self {
+            block_size,
+            cipher,
+            store,
+            shift,
+            next,
+            reserved: next,
+            reserve: reserve as u128,
+            limit,
+        })
+    }
+    
+    /// the IV is the big-endian counter, it's unique but predictable, so it's only for the modes which need the unique
+    /// IV(e.g. the OFB), the `reserve` is the number of the counter values which are reserved by one `store`.
+    pub fn new(cipher: &C, store: S, reserve: u64) -> Result<Self, CryptoError> {
+        Self::with_params(cipher.block_size(), None, store, reserve, 0)
+    }
+    
+    /// the initial counter block of the CTR is `counter << block_counter_bits`, the message of one IV must be at
+    /// most $2^{block\_counter\_bits}$ blocks, so the counter blocks of the different messages never overlap.
+    /// The `CTR` takes the `Counter` rather than the IV, see the `StoredCounter`.
+    pub fn new_ctr(cipher: &C, store: S, reserve: u64, block_counter_bits: usize) -> Result<Self, CryptoError> {
+        Self::with_params(cipher.block_size(), None, store, reserve, block_counter_bits)
+    }
+    
+    /// the IV is the `CIPH_K(counter)`(SP 800-38A Appendix C), it's unique and unpredictable for the CBC and CFB, the
+    /// `cipher` must be keyed by the same key as the mode.
+    pub fn new_encrypted(cipher: C, store: S, reserve: u64) -> Result<Self, CryptoError> {
+        Self::with_params(cipher.block_size(), Some(cipher), store, reserve, 0)
+    }
+    
+    /// the counter value of the next IV
+    pub fn next_counter(&self) -> u128 {
+        self.next
+    }
+    
+    /// the number of the remaining IVs before the counter is exhausted
+    pub fn remaining_ivs(&self) -> u128 {
+        self.limit.saturating_sub(self.next)
+    }
+}
+
+impl<C: Cipher, S: IvCounterStore> InitialVec<C> for CounterInitialVec<C, S> {
+    fn initial_vec(&mut self, iv: &mut Vec<u8>) -> Result<(), CryptoError> {
+        if self.next >= self.limit {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded, "The IV counter is exhausted"));
+        }
+        
+        if self.next == self.reserved {
+            let reserved = std::cmp::min(self.next.saturating_add(self.reserve), self.limit);
+            self.store.store(reserved)?;
+            self.reserved = reserved;
+        }
+        
+        let val = (self.next << self.shift).to_be_bytes();
+        let n = std::cmp::min(self.block_size, val.len());
+        let mut block = vec![0u8; self.block_size];
+        block[(self.block_size - n)..].copy_from_slice(&val[(val.len() - n)..]);
+        
+        match self.cipher.as_ref() {
+            Some(c) => {
+                c.encrypt(iv, block.as_slice())?;
+            },
+            None => {
+                iv.clear();
+                iv.extend_from_slice(block.as_slice());
+            }
+        }
+        
+        self.next += 1;
+        Ok(())
+    }
+}
+
+/// The CTR counter which is backed by the `IvCounterStore`, the initial counter block of every message is the next
+/// IV of the `CounterInitialVec::new_ctr`, and the low `block_counter_bits` bits are incremented by the blocks of the
+/// message. The `Counter::reset`(e.g. the `CTR::encrypt_stream`) starts the next message instead of reusing the
+/// initial counter block, so the counter blocks never repeat under the same store.
+pub struct StoredCounter<C, S> {
+    iv: CounterInitialVec<C, S>,
+    cur: Option<Vec<u8>>,
+    used: u128,
+    block_limit: u128,
+}
+
+impl<C: Cipher, S: IvCounterStore> StoredCounter<C, S> {
+    /// the parameters are the same as the `CounterInitialVec::new_ctr`
+    pub fn new(cipher: &C, store: S, reserve: u64, block_counter_bits: usize) -> Result<Self, CryptoError> {
+        if block_counter_bits == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The block counter bits of the CTR must be great than 0"));
+        }
+        let iv = CounterInitialVec::new_ctr(cipher, store, reserve, block_counter_bits)?;
+        let block_limit = if block_counter_bits >= 128 { u128::MAX } else { 1u128 << block_counter_bits };
+        Ok(Self { iv, cur: None, used: 0, block_limit })
+    }
+
+    /// the message counter of the next message
+    pub fn next_counter(&self) -> u128 {
+        self.iv.next_counter()
+    }
+}
+
+impl<C: Cipher, S: IvCounterStore> Counter for StoredCounter<C, S> {
+    /// start the next message, the next `Counter::next` reserves the new initial counter block
+    fn reset(&mut self) {
+        self.cur.take();
+        self.used = 0;
+    }
+
+    fn next(&mut self) -> Option<&Vec<u8>> {
+        match self.cur.as_mut() {
+            None => {
+                let mut block = Vec::with_capacity(self.iv.block_size);
+                InitialVec::<C>::initial_vec(&mut self.iv, &mut block).ok()?;
+                self.cur = Some(block);
+            },
+            Some(_) if self.used >= self.block_limit => return None,
+            Some(v) => {
+                // the carry never reaches the message counter in the high bits, the blocks are limited to 2^{shift}
+                for a in v.iter_mut().rev() {
+                    let (x, carry) = a.overflowing_add(1);
+                    *a = x;
+                    if !carry {
+                        break;
+                    }
+                }
+            },
+        }
+        self.used += 1;
+        self.cur.as_ref()
+    }
+
+    fn bits_len(&self) -> usize {
+        self.iv.block_size << 3
+    }
+
+    /// the remaining blocks of the current message, it's 0 if the message counter is exhausted
+    fn remaining_blocks(&self) -> Option<u128> {
+        if self.cur.is_none() && self.iv.remaining_ivs() == 0 {
+            Some(0)
+        } else {
+            Some(self.block_limit - self.used)
+        }
+    }
+}
\ No newline at end of file
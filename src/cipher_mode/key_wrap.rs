@@ -0,0 +1,179 @@
+//! AES Key Wrap(KW) and AES Key Wrap with Padding(KWP)
+//! NIST SP 800-38F
+//! RFC 3394, RFC 5649
+//!
+//! KW: W(ICV1 || P), ICV1 = 0xA6A6A6A6A6A6A6A6;
+//! KWP: W(ICV2 || [len(P)/8]_32 || P || 0^pad), ICV2 = 0xA65959A6, if the padded plaintext is 8 bytes,
+//! the `W` is replaced by the one time block cipher encryption.
+
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::cipher_mode::limits::limit_err;
+
+const KW_BLOCK_LEN: usize = 16;
+const KW_SEMIBLOCK_LEN: usize = 8;
+const KW_ICV1: [u8; 8] = [0xa6, 0xa6, 0xa6, 0xa6, 0xa6, 0xa6, 0xa6, 0xa6];
+const KWP_ICV2: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+fn integrity_err<T>() -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The key wrap integrity check failed"))
+}
+
+pub struct KeyWrap<C> {
+    cipher: C,
+}
+
+impl<C: Cipher> KeyWrap<C> {
+    /// `cipher`: the block cipher with the KEK, its block size must be 128 bits, e.g. AES
+    pub fn new(cipher: C) -> Result<Self, CryptoError> {
+        if cipher.block_size() != Some(KW_BLOCK_LEN) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The key wrap only support the block cipher with the 128 bits block size"))
+        } else {
+            Ok(Self { cipher })
+        }
+    }
+
+    /// the wrapping function W, the `a` is the initial value, the `r` is the semiblocks R_1..R_n.
+    fn w(&self, a: &mut [u8; KW_SEMIBLOCK_LEN], r: &mut [u8]) -> Result<(), CryptoError> {
+        let n = r.len() / KW_SEMIBLOCK_LEN;
+        let (mut b, mut buf) = ([0u8; KW_BLOCK_LEN], Vec::with_capacity(KW_BLOCK_LEN));
+        for j in 0..6 {
+            for (i, ri) in r.chunks_mut(KW_SEMIBLOCK_LEN).enumerate() {
+                b[..KW_SEMIBLOCK_LEN].copy_from_slice(a.as_ref());
+                b[KW_SEMIBLOCK_LEN..].copy_from_slice(ri);
+                self.cipher.encrypt(&mut buf, b.as_ref())?;
+
+                let t = ((n * j + i + 1) as u64).to_be_bytes();
+                a.iter_mut().zip(buf.iter().zip(t.iter())).for_each(|(x, (&y, &z))| *x = y ^ z);
+                ri.copy_from_slice(&buf[KW_SEMIBLOCK_LEN..]);
+            }
+        }
+        Ok(())
+    }
+
+    /// the unwrapping function W^{-1}
+    fn w_inverse(&self, a: &mut [u8; KW_SEMIBLOCK_LEN], r: &mut [u8]) -> Result<(), CryptoError> {
+        let n = r.len() / KW_SEMIBLOCK_LEN;
+        let (mut b, mut buf) = ([0u8; KW_BLOCK_LEN], Vec::with_capacity(KW_BLOCK_LEN));
+        for j in (0..6).rev() {
+            for (i, ri) in r.chunks_mut(KW_SEMIBLOCK_LEN).enumerate().rev() {
+                let t = ((n * j + i + 1) as u64).to_be_bytes();
+                b[..KW_SEMIBLOCK_LEN].iter_mut().zip(a.iter().zip(t.iter())).for_each(|(x, (&y, &z))| *x = y ^ z);
+                b[KW_SEMIBLOCK_LEN..].copy_from_slice(ri);
+                self.cipher.decrypt(&mut buf, b.as_ref())?;
+
+                a.copy_from_slice(&buf[..KW_SEMIBLOCK_LEN]);
+                ri.copy_from_slice(&buf[KW_SEMIBLOCK_LEN..]);
+            }
+        }
+        Ok(())
+    }
+
+    /// KW-AE, the length of the `key` must be a multiple of 8 bytes and at least 16 bytes, the `dst` is 8 bytes
+    /// longer than the `key`.
+    pub fn wrap(&self, dst: &mut Vec<u8>, key: &[u8]) -> Result<(), CryptoError> {
+        if key.len() < KW_BLOCK_LEN || !key.len().is_multiple_of(KW_SEMIBLOCK_LEN) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the KW key must be a multiple of 8 bytes and at least 16 bytes", key.len())));
+        }
+
+        let mut a = KW_ICV1;
+        dst.clear();
+        dst.extend_from_slice(key);
+        self.w(&mut a, dst.as_mut_slice())?;
+        dst.splice(0..0, a.iter().copied());
+        Ok(())
+    }
+
+    /// KW-AD, the `CryptoErrorKind::VerificationFailed` will be returned if the integrity check failed,
+    /// nothing will output to the `dst` in that case.
+    pub fn unwrap(&self, dst: &mut Vec<u8>, wrapped_key: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if wrapped_key.len() < (KW_BLOCK_LEN + KW_SEMIBLOCK_LEN) || !wrapped_key.len().is_multiple_of(KW_SEMIBLOCK_LEN) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong wrapped key length: {}, it must be a multiple of 8 bytes and at least 24 bytes", wrapped_key.len())));
+        }
+
+        let mut a = [0u8; KW_SEMIBLOCK_LEN];
+        a.copy_from_slice(&wrapped_key[..KW_SEMIBLOCK_LEN]);
+        dst.extend_from_slice(&wrapped_key[KW_SEMIBLOCK_LEN..]);
+        self.w_inverse(&mut a, dst.as_mut_slice())?;
+
+        if a.iter().zip(KW_ICV1.iter()).fold(0u8, |x, (&y, &z)| x | (y ^ z)) != 0 {
+            dst.iter_mut().for_each(|x| *x = 0);
+            dst.clear();
+            integrity_err()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// KWP-AE, the `key` must not be empty, the `dst` is the padded key length plus 8 bytes.
+    pub fn wrap_with_padding(&self, dst: &mut Vec<u8>, key: &[u8]) -> Result<(), CryptoError> {
+        if key.is_empty() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The KWP key must not be empty"));
+        } else if (key.len() as u64) > (u32::MAX as u64) {
+            return limit_err(format!("Wrong key length: {}, the KWP key length must be less than 2^32", key.len()));
+        }
+
+        let mut a = [0u8; KW_SEMIBLOCK_LEN];
+        a[..4].copy_from_slice(KWP_ICV2.as_ref());
+        a[4..].copy_from_slice((key.len() as u32).to_be_bytes().as_ref());
+
+        dst.clear();
+        dst.extend_from_slice(key);
+        dst.resize(key.len().div_ceil(KW_SEMIBLOCK_LEN) * KW_SEMIBLOCK_LEN, 0);
+        if dst.len() == KW_SEMIBLOCK_LEN {
+            let mut b = [0u8; KW_BLOCK_LEN];
+            b[..KW_SEMIBLOCK_LEN].copy_from_slice(a.as_ref());
+            b[KW_SEMIBLOCK_LEN..].copy_from_slice(dst.as_slice());
+            self.cipher.encrypt(dst, b.as_ref())?;
+        } else {
+            self.w(&mut a, dst.as_mut_slice())?;
+            dst.splice(0..0, a.iter().copied());
+        }
+        Ok(())
+    }
+
+    /// KWP-AD, the `CryptoErrorKind::VerificationFailed` will be returned if the integrity check(the ICV2,
+    /// the message length indicator and the padding) failed, nothing will output to the `dst` in that case.
+    pub fn unwrap_with_padding(&self, dst: &mut Vec<u8>, wrapped_key: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if wrapped_key.len() < KW_BLOCK_LEN || !wrapped_key.len().is_multiple_of(KW_SEMIBLOCK_LEN) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong wrapped key length: {}, it must be a multiple of 8 bytes and at least 16 bytes", wrapped_key.len())));
+        }
+
+        let mut a = [0u8; KW_SEMIBLOCK_LEN];
+        if wrapped_key.len() == KW_BLOCK_LEN {
+            self.cipher.decrypt(dst, wrapped_key)?;
+            a.copy_from_slice(&dst[..KW_SEMIBLOCK_LEN]);
+            dst.drain(..KW_SEMIBLOCK_LEN);
+        } else {
+            a.copy_from_slice(&wrapped_key[..KW_SEMIBLOCK_LEN]);
+            dst.extend_from_slice(&wrapped_key[KW_SEMIBLOCK_LEN..]);
+            self.w_inverse(&mut a, dst.as_mut_slice())?;
+        }
+
+        let mli = u32::from_be_bytes([a[4], a[5], a[6], a[7]]) as usize;
+        let padded_len = dst.len();
+        let is_valid = a[..4] == KWP_ICV2 && mli <= padded_len && mli + KW_SEMIBLOCK_LEN > padded_len
+            && dst[mli.min(padded_len)..].iter().fold(0u8, |x, &y| x | y) == 0;
+
+        if !is_valid {
+            dst.iter_mut().for_each(|x| *x = 0);
+            dst.clear();
+            integrity_err()
+        } else {
+            dst.truncate(mli);
+            Ok(())
+        }
+    }
+}
+
+impl<C: Cipher + Clone> Clone for KeyWrap<C> {
+    fn clone(&self) -> Self {
+        Self {
+            cipher: self.cipher.clone(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! The data limits of the cipher modes
+//!
+//! - CTR: the counter blocks must not be reused, so the data under one initial counter is limited by the
+//!   counter space;
+//! - CCM: the payload under one nonce is less than $2^{8q}$ bytes(SP 800-38C A.1);
+//! - GCM: the plaintext under one nonce is not more than $2^{39}-256$ bits(SP 800-38D 5.2.1.1);
+//! - XTS: the data unit is not more than $2^{20}$ blocks(SP 800-38E);
+//! - KWP: the key is not more than $2^{32}-1$ bytes(SP 800-38F);
+//! - the block cipher with the 64 bits block size(e.g. TDES) is not used to process more than $2^{20}$ blocks
+//!   under one key(SP 800-67 Rev.2 3.4), the `KeyUsageLimit` enforces it for any mode(ECB, CBC, CFB, OFB, CTR).
+//!
+//! The limits are enforced by the `CryptoErrorKind::LimitExceeded`.
+
+use std::cell::Cell;
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+
+pub(super) fn limit_err<T>(msg: String) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::LimitExceeded, msg))
+}
+
+/// The data limit of the key or the nonce
+pub trait DataLimit {
+    /// the remaining bytes which can be processed safely under the current key/nonce, `None` means there is no
+    /// known limit. For the stateless modes(e.g. CCM), it's the maximum data length of one nonce.
+    fn remaining_bytes(&self) -> Option<u128>;
+}
+
+/// The block cipher wrapper which counts the processed blocks, the `CryptoErrorKind::LimitExceeded` is returned
+/// if the blocks under the key exceed the limit.
+pub struct KeyUsageLimit<C> {
+    cipher: C,
+    max_blocks: u128,
+    used_blocks: Cell<u128>,
+}
+
+impl<C: Cipher> KeyUsageLimit<C> {
+    /// the default limit: $2^{20}$ blocks for the 64 bits block size(SP 800-67), $2^{64}$ blocks for the
+    /// 128 bits block size, and $2^{8b/2}$ blocks for the other block size `b` bytes.
+    pub fn new(cipher: C) -> Self {
+        let max_blocks = match cipher.block_size() {
+            Some(8) => 1u128 << 20,
+            Some(b) if b < 32 => 1u128 << (b << 2),
+            _ => u128::MAX,
+        };
+        Self::with_max_blocks(cipher, max_blocks)
+    }
+
+    pub fn with_max_blocks(cipher: C, max_blocks: u128) -> Self {
+        Self { cipher, max_blocks, used_blocks: Cell::new(0) }
+    }
+
+    /// the number of the processed blocks under the key
+    pub fn used_blocks(&self) -> u128 {
+        self.used_blocks.get()
+    }
+
+    fn consume(&self, len: usize) -> Result<(), CryptoError> {
+        let blocks = (len.div_ceil(self.cipher.block_size().unwrap_or(1).max(1))) as u128;
+        let used = self.used_blocks.get();
+        if blocks > self.max_blocks - used {
+            limit_err(format!("The key usage limit {} blocks is exceeded", self.max_blocks))
+        } else {
+            self.used_blocks.set(used + blocks);
+            Ok(())
+        }
+    }
+}
+
+impl<C: Cipher> Cipher for KeyUsageLimit<C> {
+    type Output = C::Output;
+
+    fn block_size(&self) -> Option<usize> {
+        self.cipher.block_size()
+    }
+
+    fn encrypt(&self, dst: &mut Vec<u8>, plaintext_block: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.consume(plaintext_block.len())?;
+        self.cipher.encrypt(dst, plaintext_block)
+    }
+
+    fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.consume(cipher_block.len())?;
+        self.cipher.decrypt(dst, cipher_block)
+    }
+}
+
+impl<C: Cipher> DataLimit for KeyUsageLimit<C> {
+    fn remaining_bytes(&self) -> Option<u128> {
+        let block_len = self.cipher.block_size().unwrap_or(1) as u128;
+        Some((self.max_blocks - self.used_blocks.get()).saturating_mul(block_len))
+    }
+}
+
+impl<C: Cipher + Clone> Clone for KeyUsageLimit<C> {
+    /// the clone shares the same key, so the count of the used blocks is cloned
+    fn clone(&self) -> Self {
+        Self {
+            cipher: self.cipher.clone(),
+            max_blocks: self.max_blocks,
+            used_blocks: Cell::new(self.used_blocks.get()),
+        }
+    }
+}
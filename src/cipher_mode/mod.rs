@@ -3,8 +3,11 @@
 mod pond;
 pub use pond::{Pond, DecryptStream, EncryptStream};
 
+mod stream_state;
+pub use stream_state::StreamState;
+
 mod padding;
-pub use padding::{Padding, DefaultPadding, EmptyPadding};
+pub use padding::{Padding, DefaultPadding, EmptyPadding, PKCS7Padding};
 
 #[macro_use]
 mod cipher_mode_macros;
@@ -12,11 +15,18 @@ mod cipher_mode_macros;
 mod ecb;
 pub use ecb::{ECB, ECBDecrypt, ECBEncrypt};
 
+#[cfg(feature = "aes")]
+mod ctr_drbg;
+#[cfg(feature = "aes")]
+pub use ctr_drbg::CtrDrbg;
+
 mod initial_vec;
-pub use initial_vec::{InitialVec, DefaultInitialVec};
+pub use initial_vec::{InitialVec, DefaultInitialVec, FixedInitialVec, IvCounterStore, FileIvCounterStore, CounterInitialVec, StoredCounter};
+#[cfg(feature = "aes")]
+pub use initial_vec::DrbgInitialVec;
 
 mod cbc;
-pub use cbc::{CBC, CBCEncrypt, CBCDecrypt};
+pub use cbc::{CBC, CBCEncrypt, CBCDecrypt, CiphertextStealing};
 
 mod cfb;
 pub use cfb::{CFB, CFBEncrypt, CFBDecrypt};
@@ -24,11 +34,35 @@ pub use cfb::{CFB, CFBEncrypt, CFBDecrypt};
 mod ofb;
 pub use ofb::{OFB, OFBEncrypt, OFBDecrypt};
 
+mod limits;
+pub use limits::{DataLimit, KeyUsageLimit};
+
 mod counter;
 pub use counter::{Counter, DefaultCounter};
 
 mod ctr;
 pub use ctr::{CTR, CTREncrypt, CTRDecrypt};
 
-#[cfg(test)]
+mod ccm;
+pub use ccm::CCM;
+
+mod gcm;
+pub use gcm::{GCM, GCMContext};
+
+mod xts;
+pub use xts::XTS;
+
+mod key_wrap;
+pub use key_wrap::KeyWrap;
+
+mod ff1;
+pub use ff1::{FF1, NumeralAlphabet};
+
+mod siv;
+pub use siv::SIV;
+
+mod builder;
+pub use builder::ModeBuilder;
+
+#[cfg(all(test, feature = "aes", feature = "des"))]
 mod cipher_mode_test;
\ No newline at end of file
@@ -7,6 +7,7 @@ use std::cell::Cell;
 
 pub struct OFB<C, IV> {
     cur_iv: Vec<u8>,
+    init_iv: Vec<u8>,
     buf: Cell<Vec<u8>>,
     cipher: C,
     iv: IV,
@@ -30,6 +31,7 @@ impl<C, IV> OFB<C, IV>
         
         Ok(
             Self {
+                init_iv: cur_iv.clone(),
                 cur_iv,
                 buf: Cell::new(Vec::with_capacity(len)),
                 cipher: c,
@@ -51,6 +53,31 @@ impl<C, IV> OFB<C, IV>
         }
     }
     
+    /// reset to the state right after the construction: the IV is restored to the construction-time IV.
+    ///
+    /// # Security
+    ///
+    /// the construction-time IV is reused after the `reset`, it only allows to re-process the same message, e.g.
+    /// decrypt what was just encrypted. Encrypt the next message by the `update_iv` or the `reset_with_iv`.
+    pub fn reset(&mut self) {
+        self.cur_iv.clear();
+        self.cur_iv.extend_from_slice(self.init_iv.as_slice());
+    }
+    
+    /// reset to the fresh `iv` for the next message, the following `reset` restores this IV. The `iv` equal to the
+    /// construction-time IV or the current IV is rejected.
+    pub fn reset_with_iv(&mut self, iv: Vec<u8>) -> Result<(), CryptoError> {
+        if iv == self.init_iv || iv == self.cur_iv {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                                        "The IV must be fresh, it's the same as the construction-time IV or the current IV"));
+        }
+        
+        self.set_iv(iv)?;
+        self.init_iv.clear();
+        self.init_iv.extend_from_slice(self.cur_iv.as_slice());
+        Ok(())
+    }
+    
     pub fn cur_iv(&self) -> Vec<u8> {
         self.cur_iv.clone()
     }
@@ -124,6 +151,7 @@ impl<C, IV> Clone for OFB<C, IV>
     fn clone(&self) -> Self {
         Self {
             cur_iv: self.cur_iv.clone(),
+            init_iv: self.init_iv.clone(),
             buf: Cell::new(Vec::with_capacity(self.cur_iv.len())),
             cipher: self.cipher.clone(),
             iv: self.iv.clone(),
@@ -185,6 +213,8 @@ impl_cipher_ofb!(OFBEncrypt, ofb);
 impl_fn_reset_ofb!(OFBEncrypt);
 impl_cipher_ofb!(OFBDecrypt, ofb);
 impl_fn_reset_ofb!(OFBDecrypt);
+impl_stream_state_ofb!(OFBEncrypt, ofb, STATE_MODE_OFB_ENCRYPT);
+impl_stream_state_ofb!(OFBDecrypt, ofb, STATE_MODE_OFB_DECRYPT);
 
 impl<C, IV> EncryptStream for OFBEncrypt<C, IV> 
     where C: Cipher, IV: InitialVec<C> {
@@ -54,6 +54,40 @@ impl<C: Cipher> Padding for DefaultPadding<C> {
     }
 }
 
+/// PKCS#7 padding(RFC 5652 6.3): append `n` bytes with the value `n`, where `n = block_size - (len % block_size)`
+#[derive(Clone)]
+pub struct PKCS7Padding<C> {
+    block_size: usize,
+    phd: PhantomData<C>
+}
+
+impl<C: Cipher> PKCS7Padding<C> {
+    pub fn new(cipher: &C) -> Self {
+        PKCS7Padding {
+            block_size: cipher.block_size().unwrap_or(1),
+            phd: PhantomData,
+        }
+    }
+}
+
+impl<C: Cipher> Padding for PKCS7Padding<C> {
+    fn padding(&self, buf: &mut Vec<u8>) {
+        let n = self.block_size - (buf.len() % self.block_size);
+        buf.resize(buf.len() + n, n as u8);
+    }
+
+    fn unpadding(&self, buf: &mut Vec<u8>) -> Result<usize, CryptoError> {
+        let n = buf.last().copied().unwrap_or(0) as usize;
+        if n == 0 || n > self.block_size || n > buf.len() || 
+            buf.iter().rev().take(n).fold(0u8, |a, &e| a | (e ^ (n as u8))) != 0 {
+            return Err(CryptoError::new(CryptoErrorKind::UnpaddingNotMatch, "unpadding error, invalid PKCS#7 padding"));
+        }
+        
+        buf.truncate(buf.len() - n);
+        Ok(buf.len())
+    }
+}
+
 /// padding nothing
 #[derive(Clone)]
 pub struct EmptyPadding;
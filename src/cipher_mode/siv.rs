@@ -0,0 +1,148 @@
+//! SIV(Synthetic Initialization Vector)
+//! RFC 5297
+//!
+//! $$
+//! V = S2V(K_1, AD_1, ..., AD_n, P), C = CTR(K_2, V \& (1^{64} || 0^1 || 1^{31} || 0^1 || 1^{31}), P)
+//! $$
+//!
+//! The SIV is the deterministic authenticated encryption, the same key, associated data and plaintext always give the
+//! same ciphertext, it only leaks whether the two plaintexts are the same. The random or unique nonce can be the last
+//! associated data component to make it the nonce-based AEAD. The output is `V || C`.
+
+use crate::{Cipher, CryptoError, CryptoErrorKind, Digest};
+use crate::cmac::CMAC;
+use crate::aead::Tag;
+
+const SIV_BLOCK_LEN: usize = 16;
+/// the S2V can process at most 126 associated data components before the plaintext
+const SIV_MAX_AD_NUM: usize = 126;
+
+/// SIV with the 128-bit block cipher, e.g. the AES-SIV(the 256/384/512 bits key is split to the `mac_cipher` key and
+/// the `ctr_cipher` key)
+#[derive(Clone)]
+pub struct SIV<C> {
+    mac_cipher: C,
+    ctr_cipher: C,
+}
+
+impl<C: 'static + Cipher + Clone> SIV<C> {
+    /// `mac_cipher`: the $K_1$ of the S2V; `ctr_cipher`: the $K_2$ of the CTR;
+    pub fn new(mac_cipher: C, ctr_cipher: C) -> Result<Self, CryptoError> {
+        if mac_cipher.block_size() != Some(SIV_BLOCK_LEN) || ctr_cipher.block_size() != Some(SIV_BLOCK_LEN) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The SIV needs the 128-bit block cipher"))
+        } else {
+            Ok(Self { mac_cipher, ctr_cipher })
+        }
+    }
+
+    /// doubling in the $GF(2^{128})$
+    fn dbl(d: &mut [u8]) {
+        let msb = d[0] >> 7;
+        for i in 0..(SIV_BLOCK_LEN - 1) {
+            d[i] = (d[i] << 1) | (d[i + 1] >> 7);
+        }
+        d[SIV_BLOCK_LEN - 1] = (d[SIV_BLOCK_LEN - 1] << 1) ^ (0x87 & 0u8.wrapping_sub(msb));
+    }
+
+    fn cmac(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut cmac = CMAC::new(self.mac_cipher.clone())?;
+        let mut mac = Vec::with_capacity(SIV_BLOCK_LEN);
+        cmac.write(data);
+        cmac.checksum(&mut mac);
+        Ok(mac)
+    }
+
+    fn s2v(&self, ad: &[&[u8]], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut d = self.cmac(&[0u8; SIV_BLOCK_LEN])?;
+        for s in ad.iter() {
+            Self::dbl(d.as_mut_slice());
+            d.iter_mut().zip(self.cmac(s)?.iter()).for_each(|(a, &b)| *a ^= b);
+        }
+
+        let t = if plaintext.len() >= SIV_BLOCK_LEN {
+            let mut t = plaintext.to_vec();
+            let n = t.len() - SIV_BLOCK_LEN;
+            t[n..].iter_mut().zip(d.iter()).for_each(|(a, &b)| *a ^= b);
+            t
+        } else {
+            Self::dbl(d.as_mut_slice());
+            let mut t = plaintext.to_vec();
+            t.push(0x80);
+            t.resize(SIV_BLOCK_LEN, 0);
+            t.iter_mut().zip(d.iter()).for_each(|(a, &b)| *a ^= b);
+            t
+        };
+        self.cmac(t.as_slice())
+    }
+
+    fn ctr(&self, dst: &mut Vec<u8>, v: &[u8], data: &[u8]) -> Result<(), CryptoError> {
+        let mut q = [0u8; SIV_BLOCK_LEN];
+        q.copy_from_slice(v);
+        q[8] &= 0x7f;
+        q[12] &= 0x7f;
+        let mut ctr = u128::from_be_bytes(q);
+
+        let mut ks = Vec::with_capacity(SIV_BLOCK_LEN);
+        for block in data.chunks(SIV_BLOCK_LEN) {
+            self.ctr_cipher.encrypt(&mut ks, ctr.to_be_bytes().as_ref())?;
+            dst.extend(block.iter().zip(ks.iter()).map(|(&a, &b)| a ^ b));
+            ctr = ctr.wrapping_add(1);
+        }
+        Ok(())
+    }
+
+    fn check_ad(ad: &[&[u8]]) -> Result<(), CryptoError> {
+        if ad.len() > SIV_MAX_AD_NUM {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong associated data number: {}, the SIV associated data components must not be more than {}", ad.len(), SIV_MAX_AD_NUM)))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// encrypt the `plaintext` with the associated data components `ad`, the `V || C` will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, ad: &[&[u8]], plaintext: &[u8]) -> Result<(), CryptoError> {
+        Self::check_ad(ad)?;
+        let v = self.s2v(ad, plaintext)?;
+        dst.clear();
+        dst.extend_from_slice(v.as_slice());
+        self.ctr(dst, v.as_slice(), plaintext)
+    }
+
+    /// verify and decrypt the `V || C`, nothing will output to the `dst` if the verification failed.
+    pub fn decrypt(&self, dst: &mut Vec<u8>, ad: &[&[u8]], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        Self::check_ad(ad)?;
+        dst.clear();
+        if ciphertext.len() < SIV_BLOCK_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the SIV"));
+        }
+
+        let (v, c) = ciphertext.split_at(SIV_BLOCK_LEN);
+        let mut p = Vec::with_capacity(c.len());
+        self.ctr(&mut p, v, c)?;
+        let t = self.s2v(ad, p.as_slice())?;
+        if t.iter().zip(v.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The SIV not match"))
+        } else {
+            dst.append(&mut p);
+            Ok(())
+        }
+    }
+
+    /// the `encrypt` with the detached synthetic IV `V` as the tag, the `C` will output to the `dst`.
+    pub fn seal_detached(&self, dst: &mut Vec<u8>, ad: &[&[u8]], plaintext: &[u8]) -> Result<Tag<SIV_BLOCK_LEN>, CryptoError> {
+        Self::check_ad(ad)?;
+        let v = self.s2v(ad, plaintext)?;
+        dst.clear();
+        self.ctr(dst, v.as_slice(), plaintext)?;
+        Tag::from_slice(v.as_slice())
+    }
+
+    /// verify the detached synthetic IV `tag` and decrypt the `ciphertext`, see the `seal_detached`
+    pub fn open_detached(&self, dst: &mut Vec<u8>, ad: &[&[u8]], ciphertext: &[u8], tag: &Tag<SIV_BLOCK_LEN>) -> Result<(), CryptoError> {
+        let mut data = Vec::with_capacity(SIV_BLOCK_LEN + ciphertext.len());
+        data.extend_from_slice(tag.as_ref());
+        data.extend_from_slice(ciphertext);
+        self.decrypt(dst, ad, data.as_slice())
+    }
+}
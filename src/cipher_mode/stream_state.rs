@@ -0,0 +1,114 @@
+//! The stream state save/restore(suspend-resume)
+//!
+//! magic("RCMS") || version(1) || mode(1) || block_size(2) || field_num(1) ||
+//! [field_len(4) || field]... || SHA-256(all the previous bytes)
+//!
+//! All the integers are big-endian. The checksum only detects the corrupted or truncated state, it's not
+//! a MAC: the state contains the chaining value and the buffered plaintext, so the caller need to protect
+//! its confidentiality and authenticity, e.g. seal it by the AEAD before storing.
+
+use crate::{CryptoError, CryptoErrorKind, Digest};
+use crate::sha::SHA256;
+
+const STATE_MAGIC: &[u8] = b"RCMS";
+const STATE_VERSION: u8 = 1;
+const STATE_CHECKSUM_LEN: usize = 32;
+
+pub(super) const STATE_MODE_ECB_ENCRYPT: u8 = 1;
+pub(super) const STATE_MODE_ECB_DECRYPT: u8 = 2;
+pub(super) const STATE_MODE_CBC_ENCRYPT: u8 = 3;
+pub(super) const STATE_MODE_CBC_DECRYPT: u8 = 4;
+pub(super) const STATE_MODE_CFB_ENCRYPT: u8 = 5;
+pub(super) const STATE_MODE_CFB_DECRYPT: u8 = 6;
+pub(super) const STATE_MODE_OFB_ENCRYPT: u8 = 7;
+pub(super) const STATE_MODE_OFB_DECRYPT: u8 = 8;
+pub(super) const STATE_MODE_CTR: u8 = 9;
+
+/// Save and restore the internal state of the streaming mode, so that the long-running stream can be
+/// resumed after the process restarted.
+///
+/// The state includes the chaining value(or the counter value), the buffered input which is not enough to
+/// one block, and the output which is not drawn off. The cipher key, the padding and the initial IV are not
+/// included, the state must be restored into the stream which is constructed with the same parameters.
+pub trait StreamState {
+    /// export the internal state to the versioned and checksummed bytes
+    fn save_state(&self) -> Result<Vec<u8>, CryptoError>;
+
+    /// restore the internal state which is exported by the `save_state`, the stream will not change if
+    /// any error returned.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), CryptoError>;
+}
+
+pub(super) fn encode_state(mode: u8, block_size: usize, fields: &[&[u8]]) -> Result<Vec<u8>, CryptoError> {
+    if block_size > (u16::MAX as usize) || fields.len() > (u8::MAX as usize) || 
+        fields.iter().any(|f| f.len() > (u32::MAX as usize)) {
+        return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The stream state is too large to save"));
+    }
+    
+    let len = fields.iter().fold(STATE_MAGIC.len() + 5 + STATE_CHECKSUM_LEN, |a, f| a + 4 + f.len());
+    let mut state = Vec::with_capacity(len);
+    state.extend_from_slice(STATE_MAGIC);
+    state.push(STATE_VERSION);
+    state.push(mode);
+    state.extend_from_slice((block_size as u16).to_be_bytes().as_ref());
+    state.push(fields.len() as u8);
+    for f in fields.iter() {
+        state.extend_from_slice((f.len() as u32).to_be_bytes().as_ref());
+        state.extend_from_slice(f);
+    }
+    
+    let (mut sha, mut checksum) = (SHA256::new(), Vec::with_capacity(STATE_CHECKSUM_LEN));
+    sha.write(state.as_slice());
+    sha.checksum(&mut checksum);
+    state.append(&mut checksum);
+    Ok(state)
+}
+
+fn state_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid stream state: {}", msg)))
+}
+
+/// decode the state and return the `field_num` fields
+pub(super) fn decode_state(state: &[u8], mode: u8, block_size: usize, field_num: usize) -> Result<Vec<Vec<u8>>, CryptoError> {
+    let head_len = STATE_MAGIC.len() + 5;
+    if state.len() < head_len + STATE_CHECKSUM_LEN {
+        return state_err("too short");
+    }
+
+    let (body, checksum) = state.split_at(state.len() - STATE_CHECKSUM_LEN);
+    let (mut sha, mut tgt) = (SHA256::new(), Vec::with_capacity(STATE_CHECKSUM_LEN));
+    sha.write(body);
+    sha.checksum(&mut tgt);
+    if tgt.as_slice() != checksum {
+        return state_err("checksum not match, the state may be corrupted");
+    } else if &body[..STATE_MAGIC.len()] != STATE_MAGIC {
+        return state_err("magic not match");
+    } else if body[4] != STATE_VERSION {
+        return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The stream state version {} is not supported", body[4])));
+    } else if body[5] != mode {
+        return state_err("the state is saved by the other mode");
+    } else if (((body[6] as usize) << 8) | (body[7] as usize)) != block_size {
+        return state_err("block size not match");
+    } else if (body[8] as usize) != field_num {
+        return state_err("field number not match");
+    }
+
+    let (mut data, mut fields) = (&body[head_len..], Vec::with_capacity(field_num));
+    for _ in 0..field_num {
+        if data.len() < 4 {
+            return state_err("unexpected end of the state");
+        }
+        let len = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        data = &data[4..];
+        if data.len() < len {
+            return state_err("unexpected end of the state");
+        }
+        fields.push(data[..len].to_vec());
+        data = &data[len..];
+    }
+
+    if !data.is_empty() {
+        return state_err("trailing bytes");
+    }
+    Ok(fields)
+}
@@ -0,0 +1,157 @@
+//! XTS-AES(XEX-based tweaked-codebook mode with ciphertext stealing)
+//! IEEE P1619
+//! NIST SP 800-38E
+//!
+//! T_0 = CIPH_{K2}(i), T_j = T_0 ⊗ α^j;
+//! C_j = CIPH_{K1}(P_j xor T_j) xor T_j, the last partial block is encrypted by the ciphertext stealing,
+//! where the `i` is the 128 bits tweak(e.g. the little-endian sector number), α is the primitive element of GF(2^128).
+
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::cipher_mode::DataLimit;
+use crate::cipher_mode::limits::limit_err;
+#[cfg(feature = "aes")]
+use crate::AES;
+
+const XTS_BLOCK_LEN: usize = 16;
+/// the maximum length of the data unit is 2^20 blocks(SP 800-38E)
+const XTS_MAX_DATA_UNIT_LEN: usize = XTS_BLOCK_LEN << 20;
+
+pub struct XTS<C> {
+    // K1, encrypt the data unit
+    cipher: C,
+    // K2, encrypt the tweak
+    tweak_cipher: C,
+}
+
+impl<C: Cipher> XTS<C> {
+    /// `cipher`: the block cipher with the `key1` to encrypt the data;
+    /// `tweak_cipher`: the block cipher with the `key2` to encrypt the tweak;
+    /// the block size of the cipher must be 128 bits.
+    pub fn new(cipher: C, tweak_cipher: C) -> Result<Self, CryptoError> {
+        if cipher.block_size() != Some(XTS_BLOCK_LEN) || tweak_cipher.block_size() != Some(XTS_BLOCK_LEN) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The XTS only support the block cipher with the 128 bits block size"))
+        } else {
+            Ok(Self { cipher, tweak_cipher })
+        }
+    }
+
+    /// T = T ⊗ α, the T is little-endian
+    fn mul_alpha(t: &mut [u8; XTS_BLOCK_LEN]) {
+        let mut carry = 0u8;
+        for x in t.iter_mut() {
+            let c = *x >> 7;
+            *x = (*x << 1) | carry;
+            carry = c;
+        }
+        t[0] ^= 0x87 & 0u8.wrapping_sub(carry);
+    }
+
+    fn xor_block(&self, block: &[u8], t: &[u8; XTS_BLOCK_LEN], is_encrypt: bool, buf: &mut Vec<u8>) -> Result<[u8; XTS_BLOCK_LEN], CryptoError> {
+        let mut pp = [0u8; XTS_BLOCK_LEN];
+        pp.iter_mut().zip(block.iter().zip(t.iter())).for_each(|(a, (&b, &c))| *a = b ^ c);
+        if is_encrypt {
+            self.cipher.encrypt(buf, pp.as_ref())?;
+        } else {
+            self.cipher.decrypt(buf, pp.as_ref())?;
+        }
+        pp.iter_mut().zip(buf.iter().zip(t.iter())).for_each(|(a, (&b, &c))| *a = b ^ c);
+        Ok(pp)
+    }
+
+    fn crypt(&self, dst: &mut Vec<u8>, tweak: &[u8; XTS_BLOCK_LEN], data: &[u8], is_encrypt: bool) -> Result<(), CryptoError> {
+        if data.len() < XTS_BLOCK_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The data unit length {} is less than the block length of the XTS", data.len())));
+        } else if data.len() > XTS_MAX_DATA_UNIT_LEN {
+            return limit_err(format!("The data unit length {} is great than the 2^20 blocks", data.len()));
+        }
+
+        dst.clear();
+        let mut buf = Vec::with_capacity(XTS_BLOCK_LEN);
+        self.tweak_cipher.encrypt(&mut buf, tweak.as_ref())?;
+        let mut t = [0u8; XTS_BLOCK_LEN];
+        t.copy_from_slice(buf.as_slice());
+
+        let tail_len = data.len() % XTS_BLOCK_LEN;
+        // the last full block and the partial block are processed by the ciphertext stealing
+        let full_len = if tail_len == 0 { data.len() } else { data.len() - tail_len - XTS_BLOCK_LEN };
+        for block in data[..full_len].chunks(XTS_BLOCK_LEN) {
+            let c = self.xor_block(block, &t, is_encrypt, &mut buf)?;
+            dst.extend_from_slice(c.as_ref());
+            Self::mul_alpha(&mut t);
+        }
+
+        if tail_len != 0 {
+            let (last, tail) = data[full_len..].split_at(XTS_BLOCK_LEN);
+            // the encryption uses the T_{m-1} and then T_m, the decryption uses the reverse order
+            let mut t_next = t;
+            Self::mul_alpha(&mut t_next);
+            let (t1, t2) = if is_encrypt { (&t, &t_next) } else { (&t_next, &t) };
+
+            let cc = self.xor_block(last, t1, is_encrypt, &mut buf)?;
+            let mut pp = cc;
+            pp[..tail_len].copy_from_slice(tail);
+            let c = self.xor_block(pp.as_ref(), t2, is_encrypt, &mut buf)?;
+            dst.extend_from_slice(c.as_ref());
+            dst.extend_from_slice(&cc[..tail_len]);
+        }
+
+        Ok(())
+    }
+
+    /// encrypt the data unit `plaintext` with the 128 bits `tweak`, the length of the `plaintext` must be
+    /// great than or equal to 16 bytes, and the length of the ciphertext is the same as the `plaintext`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, tweak: &[u8; XTS_BLOCK_LEN], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.crypt(dst, tweak, plaintext, true)
+    }
+
+    /// decrypt the data unit `ciphertext` with the 128 bits `tweak`
+    pub fn decrypt(&self, dst: &mut Vec<u8>, tweak: &[u8; XTS_BLOCK_LEN], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.crypt(dst, tweak, ciphertext, false)
+    }
+
+    /// encrypt the sector, the tweak is the little-endian 128 bits sector number(the data unit sequence number).
+    pub fn encrypt_sector(&self, dst: &mut Vec<u8>, sector: u128, plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, &sector.to_le_bytes(), plaintext)
+    }
+
+    /// decrypt the sector, see `encrypt_sector`
+    pub fn decrypt_sector(&self, dst: &mut Vec<u8>, sector: u128, ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.decrypt(dst, &sector.to_le_bytes(), ciphertext)
+    }
+}
+
+impl<C: Cipher> DataLimit for XTS<C> {
+    /// the maximum length of one data unit
+    fn remaining_bytes(&self) -> Option<u128> {
+        Some(XTS_MAX_DATA_UNIT_LEN as u128)
+    }
+}
+
+#[cfg(feature = "aes")]
+impl XTS<AES> {
+    /// XTS-AES-128 or XTS-AES-256, the `key` is the `key1||key2` which is 32 or 64 bytes,
+    /// the `key1` must be different from the `key2`(SP 800-38E, FIPS 140-3 IG C.I).
+    pub fn new_aes(key: Vec<u8>) -> Result<Self, CryptoError> {
+        if key.len() != 32 && key.len() != 64 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong XTS-AES key length: {}, it must be 32 or 64 bytes", key.len())));
+        }
+
+        let (k1, k2) = key.split_at(key.len() >> 1);
+        if k1.iter().zip(k2.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The XTS-AES key1 must be different from the key2"));
+        }
+
+        Self::new(AES::new(k1.to_vec())?, AES::new(k2.to_vec())?)
+    }
+}
+
+impl<C: Cipher + Clone> Clone for XTS<C> {
+    fn clone(&self) -> Self {
+        Self {
+            cipher: self.cipher.clone(),
+            tweak_cipher: self.tweak_cipher.clone(),
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::cmac::const_tables::{RB_128, RB_64, RB_32, RB_48, RB_96, RB_160, RB_1
 
 /// CMAC(Block Cipher-based Message Authentication Code)  
 /// SP 800-38B  
+#[derive(Clone)]
 pub struct CMAC<C> {
     k1: Vec<u8>,
     k2: Vec<u8>,
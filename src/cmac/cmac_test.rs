@@ -116,4 +116,26 @@ fn cmac_aes() {
             assert_eq!(buf.as_slice(), tgt_mac.as_slice(), "case: {}-{}", i, j);
         }
     }
-}
\ No newline at end of file
+}
+#[test]
+fn cmac_vectored() {
+    let key = vec![0x2Bu8, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C];
+    let mut msg = Vec::new();
+    [0x6BC1BEE2u32, 0x2E409F96, 0xE93D7E11, 0x7393172A, 0xAE2D8A57, 0x1E03AC9C, 0x9EB76FAC, 0x45AF8E51, 0x30C81C46, 0xA35CE411, 0xE5FBC119, 0x1A0A52EF, 0xF69F2445, 0xDF4F9B17, 0xAD2B417B, 0xE66C3710,].iter().for_each(|&x| {
+        msg.extend_from_slice(x.to_be_bytes().as_ref());
+    });
+    
+    let mut cmac = CMAC::new(AES::new(key).unwrap()).unwrap();
+    let (mut tgt_mac, mut mac) = (Vec::new(), Vec::new());
+    for &len in [0usize, 5, 16, 20, 64].iter() {
+        cmac.write(&msg[..len]);
+        cmac.checksum(&mut tgt_mac);
+        for &(i, j) in [(0usize, 0usize), (0, len), (1, len), (len / 2, len), (len / 3, 2 * len / 3)].iter() {
+            let (i, j) = (i.min(len), j.min(len));
+            cmac.reset();
+            cmac.write_vectored(&[&msg[..i], &msg[i..j], &[], &msg[j..len]]);
+            cmac.checksum(&mut mac);
+            assert_eq!(mac, tgt_mac, "case: {}-{}-{}", len, i, j);
+        }
+    }
+}
@@ -7,5 +7,5 @@ mod const_tables;
 
 pub use cmac::CMAC;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "aes", feature = "des"))]
 mod cmac_test;
\ No newline at end of file
@@ -10,6 +10,10 @@ pub enum CryptoErrorKind {
     InvalidPublicKey,
     InvalidPrivateKey,
     VerificationFailed,
+    /// the per-key or per-nonce data limit of the algorithm is exceeded
+    LimitExceeded,
+    /// the long-running operation is cancelled by the caller, e.g. by the progress callback of the key generation
+    Cancelled,
     OuterErr,
     InnerErr,
 }
@@ -24,6 +28,8 @@ impl Debug for CryptoErrorKind {
             CryptoErrorKind::InvalidPublicKey => write!(f, "{}", "InvalidPublicKey"),
             CryptoErrorKind::InvalidPrivateKey => write!(f, "{}", "InvalidPrivateKey"),
             CryptoErrorKind::VerificationFailed => write!(f, "{}", "VerificationFailed"),
+            CryptoErrorKind::LimitExceeded => write!(f, "LimitExceeded"),
+            CryptoErrorKind::Cancelled => write!(f, "Cancelled"),
             CryptoErrorKind::OuterErr => write!(f, "{}", "OuterErr: ErrorsCausedByExternalModule"),
             CryptoErrorKind::InnerErr => write!(f, "{}", "InnerError"),
         }
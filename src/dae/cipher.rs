@@ -0,0 +1,94 @@
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind, AES};
+use crate::cipher_mode::{SIV, GCM};
+use crate::kdf::HKDF;
+use crate::sha::SHA256;
+
+/// the HKDF info label of the deterministic key
+pub const DAE_LABEL_DETERMINISTIC: &[u8] = b"rcrypto dae deterministic aes-256-siv";
+/// the HKDF info label of the randomized key
+pub const DAE_LABEL_RANDOMIZED: &[u8] = b"rcrypto dae randomized aes-256-gcm";
+pub const DAE_MIN_MASTER_KEY_LEN: usize = 16;
+
+const DAE_SIV_KEY_LEN: usize = 64;
+const DAE_GCM_KEY_LEN: usize = 32;
+const DAE_GCM_NONCE_LEN: usize = 12;
+const DAE_GCM_TAG_LEN: usize = 16;
+
+/// `info = label || 0x00 || context`
+fn derive_key(master_key: &[u8], label: &[u8], context: &[u8], len: usize) -> Result<Vec<u8>, CryptoError> {
+    if master_key.len() < DAE_MIN_MASTER_KEY_LEN {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("Wrong master key length: {}, the master key must be at least {} bytes", master_key.len(), DAE_MIN_MASTER_KEY_LEN)));
+    }
+
+    let mut info = label.to_vec();
+    info.push(0);
+    info.extend_from_slice(context);
+    let mut key = Vec::with_capacity(len);
+    HKDF::new(SHA256::new())?.derive(&[], master_key, info.as_slice(), len, &mut key)?;
+    Ok(key)
+}
+
+/// The deterministic authenticated encryption(AES-256-SIV), the output is `V || C`
+#[derive(Clone)]
+pub struct DeterministicCipher {
+    siv: SIV<AES>,
+}
+
+impl DeterministicCipher {
+    /// the AES-256-SIV key is derived from the `master_key` by the `DAE_LABEL_DETERMINISTIC` and the `context`
+    pub fn new(master_key: &[u8], context: &[u8]) -> Result<Self, CryptoError> {
+        let key = derive_key(master_key, DAE_LABEL_DETERMINISTIC, context, DAE_SIV_KEY_LEN)?;
+        let (k1, k2) = key.split_at(DAE_SIV_KEY_LEN >> 1);
+        Ok(Self { siv: SIV::new(AES::new(k1.to_vec())?, AES::new(k2.to_vec())?)? })
+    }
+
+    pub fn encrypt(&self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut ct = Vec::with_capacity(plaintext.len() + 16);
+        self.siv.encrypt(&mut ct, &[aad], plaintext)?;
+        Ok(ct)
+    }
+
+    pub fn decrypt(&self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut pt = Vec::with_capacity(ciphertext.len());
+        self.siv.decrypt(&mut pt, &[aad], ciphertext)?;
+        Ok(pt)
+    }
+}
+
+/// The randomized authenticated encryption(AES-256-GCM with the random 96-bit nonce), the output is
+/// `nonce || ciphertext || tag`
+#[derive(Clone)]
+pub struct RandomizedCipher {
+    gcm: GCM<AES>,
+}
+
+impl RandomizedCipher {
+    /// the AES-256-GCM key is derived from the `master_key` by the `DAE_LABEL_RANDOMIZED` and the `context`
+    pub fn new(master_key: &[u8], context: &[u8]) -> Result<Self, CryptoError> {
+        let key = derive_key(master_key, DAE_LABEL_RANDOMIZED, context, DAE_GCM_KEY_LEN)?;
+        Ok(Self { gcm: GCM::new(AES::new(key)?, DAE_GCM_TAG_LEN)? })
+    }
+
+    pub fn encrypt<R: IterSource<u32>>(&self, aad: &[u8], plaintext: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+        let mut blob = Vec::with_capacity(DAE_GCM_NONCE_LEN + plaintext.len() + DAE_GCM_TAG_LEN);
+        rd.iter_mut().take(DAE_GCM_NONCE_LEN >> 2).for_each(|x| blob.extend_from_slice(x.to_be_bytes().as_ref()));
+
+        let mut ct = Vec::with_capacity(plaintext.len() + DAE_GCM_TAG_LEN);
+        self.gcm.encrypt(&mut ct, &blob[..DAE_GCM_NONCE_LEN], aad, plaintext)?;
+        blob.append(&mut ct);
+        Ok(blob)
+    }
+
+    pub fn decrypt(&self, aad: &[u8], blob: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if blob.len() < DAE_GCM_NONCE_LEN + DAE_GCM_TAG_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the nonce and the tag"));
+        }
+
+        let (nonce, ct) = blob.split_at(DAE_GCM_NONCE_LEN);
+        let mut pt = Vec::with_capacity(ct.len());
+        self.gcm.decrypt(&mut pt, nonce, aad, ct)?;
+        Ok(pt)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::dae::{DeterministicCipher, RandomizedCipher};
+use crate::CryptoErrorKind;
+use rmath::rand::{CryptoRand, DefaultSeed};
+
+#[test]
+fn dae_cipher() {
+    let master = [0x5au8; 32];
+    let dae = DeterministicCipher::new(&master, b"users.email").unwrap();
+    let (c0, c1) = (dae.encrypt(b"", b"alice@example.com").unwrap(), dae.encrypt(b"", b"alice@example.com").unwrap());
+    assert_eq!(c0, c1);
+    assert_ne!(c0, dae.encrypt(b"", b"bob@example.com").unwrap());
+    assert_ne!(c0, dae.encrypt(b"tenant 2", b"alice@example.com").unwrap());
+    assert_ne!(c0, DeterministicCipher::new(&master, b"users.name").unwrap().encrypt(b"", b"alice@example.com").unwrap());
+    assert_eq!(dae.decrypt(b"", c0.as_slice()).unwrap(), b"alice@example.com".to_vec());
+    assert_eq!(dae.decrypt(b"tenant 2", c0.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let rae = RandomizedCipher::new(&master, b"users.email").unwrap();
+    let (r0, r1) = (rae.encrypt(b"", b"alice@example.com", &mut rd).unwrap(), rae.encrypt(b"", b"alice@example.com", &mut rd).unwrap());
+    assert_ne!(r0, r1);
+    assert_eq!(rae.decrypt(b"", r0.as_slice()).unwrap(), b"alice@example.com".to_vec());
+    assert_eq!(rae.decrypt(b"", r1.as_slice()).unwrap(), b"alice@example.com".to_vec());
+    assert!(rae.decrypt(b"x", r0.as_slice()).is_err());
+    assert!(rae.decrypt(b"", &r0[..20]).is_err());
+
+    assert!(DeterministicCipher::new(&master[..15], b"").is_err());
+    assert!(RandomizedCipher::new(&master[..15], b"").is_err());
+}
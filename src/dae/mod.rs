@@ -0,0 +1,28 @@
+//! Deterministic authenticated encryption(DAE) for the deduplication and the indexing
+//!
+//! The `DeterministicCipher`(AES-256-SIV) always encrypts the same associated data and plaintext to the same
+//! ciphertext, so the ciphertexts can be compared for the equality(e.g. the deduplication of the stored blobs, the
+//! lookup of the encrypted column in the database), but the equality of the plaintexts is leaked. The
+//! `RandomizedCipher`(AES-256-GCM with the random nonce) should be used for the other data.
+//!
+//! Both ciphers are constructed from the master key, the deterministic and the randomized keys are derived by the
+//! HKDF-SHA256(`kdf::HKDF`) with the different labels, so the same master key can be shared by the two usages
+//! without the key reuse across the algorithms. The `context`(e.g. the table and column name) separates the keys of
+//! the different indexes.
+//!
+//! # Example
+//!
+//! ```Rust
+//! use rcrypto::dae::DeterministicCipher;
+//!
+//! let dae = DeterministicCipher::new(master_key, b"users.email").unwrap();
+//! let c0 = dae.encrypt(b"", b"alice@example.com").unwrap();
+//! let c1 = dae.encrypt(b"", b"alice@example.com").unwrap();
+//! assert_eq!(c0, c1);
+//! ```
+
+mod cipher;
+pub use cipher::{DeterministicCipher, RandomizedCipher, DAE_LABEL_DETERMINISTIC, DAE_LABEL_RANDOMIZED, DAE_MIN_MASTER_KEY_LEN};
+
+#[cfg(test)]
+mod dae_test;
\ No newline at end of file
@@ -19,6 +19,29 @@ impl DES {
         }
     }
 
+    /// `key` must be 8 bytes, the weak keys are not rejected as the `new`
+    pub fn from_slice(key: &[u8]) -> Result<DES, CryptoError> {
+        if key.len() != mct::DES_BLOCK_SIZE {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key len: {}, the DES key must be the {} bytes", key.len(), mct::DES_BLOCK_SIZE)))
+        } else {
+            let mut k = [0u8; 8];
+            k.copy_from_slice(key);
+            Ok(DES::new(k))
+        }
+    }
+
+    /// the weak and the semi-weak keys are rejected
+    pub fn new_checked(key: [u8; 8]) -> Result<DES, CryptoError> {
+        if crate::validation::is_des_weak_key(&key) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The DES key is a weak key"))
+        } else if crate::validation::is_des_semi_weak_key(&key) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The DES key is a semi-weak key"))
+        } else {
+            Ok(DES::new(key))
+        }
+    }
+
     #[inline]
     fn cvt_slice_to_u64(src: &[u8]) -> u64 {
         let v = [src[0], src[1], src[2], src[3], src[4], src[5], src[6], src[7]];
@@ -6,7 +6,7 @@
 //! 
 //! FIPS 46-3
 
-use crate::{CryptoError, DES, Cipher};
+use crate::{CryptoError, CryptoErrorKind, DES, Cipher};
 use crate::des::const_tables::DES_BLOCK_SIZE;
 use std::cell::Cell;
 
@@ -40,6 +40,38 @@ impl TDES {
         }
     }
     
+    /// `key` is the `key1 || key2 || key3` of the 24 bytes, or the `key1 || key2` of the 16 bytes which means the
+    /// `key3 = key1`(the keying option 2).
+    pub fn from_slice(key: &[u8]) -> Result<TDES, CryptoError> {
+        let k = |i: usize| {
+            let mut x = [0u8; 8];
+            x.copy_from_slice(&key[(i * DES_BLOCK_SIZE)..((i + 1) * DES_BLOCK_SIZE)]);
+            x
+        };
+        match key.len() {
+            24 => Ok(TDES::new(k(0), k(1), k(2))),
+            16 => Ok(TDES::new(k(0), k(1), k(0))),
+            _ => Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key len: {}, the TDES key must be the 16 or 24 bytes", key.len()))),
+        }
+    }
+    
+    /// the weak and the semi-weak keys are rejected, and the keying option which degenerates to the single 
+    /// DES(key1 = key2 or key2 = key3, the parity bits are ignored) is rejected.
+    pub fn new_checked(key1: [u8; 8], key2: [u8; 8], key3: [u8; 8]) -> Result<TDES, CryptoError> {
+        let is_eq = |a: &[u8; 8], b: &[u8; 8]| a.iter().zip(b.iter()).all(|(&x, &y)| (x & 0xfe) == (y & 0xfe));
+        if is_eq(&key1, &key2) || is_eq(&key2, &key3) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The TDES keys degenerate to the single DES"));
+        }
+        
+        Ok(TDES {
+            des1: DES::new_checked(key1)?,
+            des2: DES::new_checked(key2)?,
+            des3: DES::new_checked(key3)?,
+            buf: Cell::new(Vec::with_capacity(DES_BLOCK_SIZE)),
+        })
+    }
+    
     #[inline]
     fn get_buf(&self) -> &mut Vec<u8> {
         unsafe {
@@ -0,0 +1,108 @@
+use crate::dh::{validate_ffdh_public_value, validate_ffdh_public_key, validate_ec_public_point, validate_ec_public_key};
+use crate::elliptic::{CurveP224, CurveP256, EllipticCurve, CurveParams, PublicKey};
+use crate::dsa::DomainParameters;
+use crate::CryptoErrorKind;
+use rmath::bigint::{BigInt, Nat};
+
+#[test]
+fn dh_ffdh_public_value() {
+    // p = 2q + 1, the generator 4 has the order q
+    let (p, q) = (BigInt::from(23u32), BigInt::from(11u32));
+
+    for y in [2u32, 3, 4, 13, 18].iter() {
+        assert!(validate_ffdh_public_value(&p, &q, &BigInt::from(*y)).is_ok(), "y = {}", y);
+    }
+
+    for y in [0u32, 1, 22, 23, 24, 5, 7].iter() {
+        let e = validate_ffdh_public_value(&p, &q, &BigInt::from(*y)).unwrap_err();
+        assert_eq!(e.kind(), CryptoErrorKind::InvalidPublicKey, "y = {}", y);
+    }
+
+    assert_eq!(validate_ffdh_public_value(&p, &p, &BigInt::from(2u32)).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+
+    let dp = DomainParameters::new_uncheck(&p, &q, &BigInt::from(4u32)).unwrap();
+    let pk = crate::dsa::PublicKey::new_uncheck(&dp, &BigInt::from(9u32)).unwrap();
+    assert!(validate_ffdh_public_key(&pk).is_ok());
+    let pk = crate::dsa::PublicKey::new_uncheck(&dp, &BigInt::from(1u32)).unwrap();
+    assert!(validate_ffdh_public_key(&pk).is_err());
+}
+
+#[test]
+fn dh_ec_public_point() {
+    let (p224, p256) = (CurveP224::new().unwrap(), CurveP256::new().unwrap());
+    let (p384, p521) = (CurveParams::p384().unwrap(), CurveParams::p521().unwrap());
+
+    let check = |c: &dyn Fn(&BigInt, &BigInt) -> Result<(), crate::CryptoError>, cp: &CurveParams| {
+        let (gx, gy) = cp.base_point();
+        assert!(c(gx, gy).is_ok());
+        let (x, y) = cp.scalar_base_point(&Nat::from(0x1234567u32));
+        assert!(c(&x, &y).is_ok());
+        let neg_y = cp.field_order().clone() - y.clone();
+        assert!(c(&x, &neg_y).is_ok());
+
+        let zero = BigInt::from(0u32);
+        let invalid = [
+            (zero.clone(), zero.clone()),
+            (BigInt::from(1u32), BigInt::from(1u32)),
+            (x.clone(), y.clone() + BigInt::from(1u32)),
+            (x.clone(), y.clone() + cp.field_order().clone()),
+            (x.clone() + cp.field_order().clone(), y.clone()),
+            (zero.clone() - x.clone(), y.clone()),
+        ];
+        for (ix, iy) in invalid.iter() {
+            assert_eq!(c(ix, iy).unwrap_err().kind(), CryptoErrorKind::InvalidPublicKey, "{}: ({:#x}, {:#x})", cp.name(), ix, iy);
+        }
+    };
+
+    check(&|x, y| validate_ec_public_point(&p224, x, y), p224.curve_params());
+    check(&|x, y| validate_ec_public_point(&p256, x, y), p256.curve_params());
+    check(&|x, y| validate_ec_public_point(&p384, x, y), &p384);
+    check(&|x, y| validate_ec_public_point(&p521, x, y), &p521);
+
+    let (gx, gy) = p256.curve_params().base_point();
+    assert!(validate_ec_public_key(&p256, &PublicKey::new_uncheck(gx, gy)).is_ok());
+    assert!(validate_ec_public_key(&p224, &PublicKey::new_uncheck(gx, gy)).is_err());
+}
+
+#[test]
+fn dh_ffdh_agreement() {
+    use crate::dh::{FFDH, validate_ffdh_domain_parameters};
+    use crate::dsa::WellKnownGroup;
+    
+    let seed = rmath::rand::DefaultSeed::<u32>::new().unwrap();
+    let mut rd = rmath::rand::CryptoRand::new(&seed).unwrap();
+    
+    // p = 23, q = 11, g = 4: x = 3, y = 4^3 mod 23 = 18, the peer's y = 9, Z = 9^3 mod 23 = 16
+    let (p, q, g) = (BigInt::from(23u32), BigInt::from(11u32), BigInt::from(4u32));
+    let dp = DomainParameters::new_uncheck(&p, &q, &g).unwrap();
+    validate_ffdh_domain_parameters(&dp, &mut rd).unwrap();
+    let pk = crate::dsa::PublicKey::new_uncheck(&dp, &BigInt::from(18u32)).unwrap();
+    let dh = FFDH::new(crate::dsa::PrivateKey::new_uncheck(&pk, &BigInt::from(3u32)).unwrap()).unwrap();
+    let peer = crate::dsa::PublicKey::new_uncheck(&dp, &BigInt::from(9u32)).unwrap();
+    assert_eq!(dh.shared_secret_len(), 1);
+    assert_eq!(dh.shared_secret(&peer).unwrap(), vec![16u8]);
+    
+    // the peer's public value out of the range or the subgroup
+    for y in [1u32, 5, 22].iter() {
+        let peer = crate::dsa::PublicKey::new_uncheck(&dp, &BigInt::from(*y)).unwrap();
+        assert_eq!(dh.shared_secret(&peer).unwrap_err().kind(), CryptoErrorKind::InvalidPublicKey, "y = {}", y);
+    }
+    
+    // the invalid domain parameters: the composite p, the q doesn't divide p-1, the generator of the order 22
+    for &(p, q, g) in [(25u32, 11u32, 4u32), (23, 7, 4), (23, 11, 5), (23, 11, 22)].iter() {
+        let dp = DomainParameters::new_uncheck(&BigInt::from(p), &BigInt::from(q), &BigInt::from(g)).unwrap();
+        assert_eq!(validate_ffdh_domain_parameters(&dp, &mut rd).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+        assert!(FFDH::generate_key(&dp, &mut rd).is_err());
+    }
+    
+    let dp = DomainParameters::well_known(WellKnownGroup::Ffdhe2048);
+    let (alice, bob) = (FFDH::generate_key(&dp, &mut rd).unwrap(), FFDH::generate_key(&dp, &mut rd).unwrap());
+    let z = alice.shared_secret(bob.public_key()).unwrap();
+    assert_eq!(z.len(), 256);
+    assert_eq!(z, bob.shared_secret(alice.public_key()).unwrap());
+    
+    // the peer's public key in the other group
+    let other = FFDH::generate_key(&DomainParameters::well_known(WellKnownGroup::Modp2048), &mut rd).unwrap();
+    assert_eq!(alice.shared_secret(other.public_key()).unwrap_err().kind(), CryptoErrorKind::InvalidPublicKey);
+    assert!(alice.shared_secret(dh.public_key()).is_err());
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::dsa::{DomainParameters, PrivateKey, PublicKey};
+use crate::dh::validate_ffdh_public_value;
+use crate::prime::is_probably_prime;
+use crate::{CryptoError, CryptoErrorKind};
+
+/// validate the FFC domain parameters `(p, q, g)` of the Diffie-Hellman key agreement:  
+/// - the `p` and `q` are primes and $q \mid (p-1)$;
+/// - the generator $2 \le g \le p-2$ and $g^q \mod p = 1$, i.e. the `g` generates the subgroup of order `q`.
+///
+/// The well-known groups(see `dsa::WellKnownGroup`, e.g. the RFC 7919 FFDHE groups) have been validated, so the
+/// primality tests are skipped for them.
+pub fn validate_ffdh_domain_parameters<R: IterSource<u32>>(dp: &DomainParameters, rd: &mut R) -> Result<(), CryptoError> {
+    let (p, q, g) = dp.unwrap();
+    let one = BigInt::from(1u32);
+    let invalid = |msg: &str| Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid FFC domain parameters: {}", msg)));
+    
+    if p.is_nan() || q.is_nan() || g.is_nan() || p <= &BigInt::from(3u32) || q <= &one || q >= p {
+        return invalid("the p and q are out of range");
+    }
+    
+    if dp.well_known_group().is_none() && !(is_probably_prime(p, 20, rd) && is_probably_prime(q, 20, rd)) {
+        return invalid("the p and q must be primes");
+    }
+    
+    let p_minus_1 = p.clone() - one.clone();
+    if p_minus_1.rem_euclid(q.clone()).signnum() != Some(0) {
+        return invalid("the q doesn't divide the p-1");
+    }
+    
+    if g <= &one || g >= &p_minus_1 || g.exp(q, p) != one {
+        invalid("the generator g must be in [2, p-2] and g^q = 1 mod p")
+    } else {
+        Ok(())
+    }
+}
+
+/// The finite field Diffie-Hellman key agreement
+/// NIST SP 800-56A Rev. 3 5.7.1.1(FFC DH primitive), RFC 7919
+///
+/// the key pair is the `dsa::PrivateKey` over the FFC domain parameters(e.g. the RFC 7919 FFDHE groups,
+/// `DomainParameters::well_known`), the private value `x` is in the range [1, q-1] and the public value is
+/// $y = g^x \mod p$. The shared secret $Z = y_{peer}^x \mod p$ is encoded as the big-endian bytes of the byte length
+/// of the `p`. The peer's public value is validated(see `dh::validate_ffdh_public_value`) before the exponentiation.
+/// The `Z` is not uniformly random, it should be passed to a key derivation function.
+pub struct FFDH {
+    key: PrivateKey,
+}
+
+impl FFDH {
+    /// the private key x must be in the range [1, q-1], the domain parameters are not validated, see the
+    /// `validate_ffdh_domain_parameters`
+    pub fn new(key: PrivateKey) -> Result<Self, CryptoError> {
+        let x = key.private_value();
+        let (_, q, _) = key.domain_parameters().unwrap();
+        if x.is_nan() || x.signnum() != Some(1) || &x >= q {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The FFDH private key must be in the range [1, q-1]"));
+        }
+        
+        Ok(Self { key })
+    }
+    
+    /// generate the ephemeral or static key pair in the group `dp`, the domain parameters are validated at first
+    pub fn generate_key<R: IterSource<u32>>(dp: &DomainParameters, rd: &mut R) -> Result<Self, CryptoError> {
+        validate_ffdh_domain_parameters(dp, rd)?;
+        
+        let (p, q, g) = dp.unwrap();
+        let x = loop {
+            let x = q.random(rd);
+            if x != 0u32 && &x < q {
+                break x;
+            }
+        };
+        
+        let pk = PublicKey::new_uncheck(dp, &g.exp(&x, p))?;
+        let key = PrivateKey::new_uncheck(&pk, &x)?;
+        Ok(Self { key })
+    }
+    
+    pub fn domain_parameters(&self) -> &DomainParameters {
+        self.key.domain_parameters()
+    }
+    
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.key
+    }
+    
+    /// the public key which is sent to the peer
+    pub fn public_key(&self) -> &PublicKey {
+        self.key.public_key()
+    }
+    
+    /// the length of the shared secret, i.e. the byte length of the prime `p`
+    pub fn shared_secret_len(&self) -> usize {
+        (self.domain_parameters().unwrap().0.bits_len() + 7) >> 3
+    }
+    
+    /// compute the shared secret `Z` with the peer's public key, the peer's public key in the other group, the invalid
+    /// public value(out of the range [2, p-2], not in the subgroup of order `q`) and the degenerate shared secret
+    /// `Z` in {0, 1, p-1} return the `InvalidPublicKey` error.
+    pub fn shared_secret(&self, peer: &PublicKey) -> Result<Vec<u8>, CryptoError> {
+        if !self.domain_parameters().ct_eq(peer.domain_parameters()) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The FFDH peer's public key is in the other group"));
+        }
+        
+        let (p, q, _) = self.domain_parameters().unwrap();
+        let y = peer.public_value();
+        validate_ffdh_public_value(p, q, y)?;
+        
+        let z = y.exp(&self.key.private_value(), p);
+        let one = BigInt::from(1u32);
+        if z.is_nan() || z <= one || z >= p.clone() - one {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The FFDH shared secret is degenerate"));
+        }
+        
+        let (z, len) = (z.to_be_bytes(), self.shared_secret_len());
+        let mut buf = vec![0u8; len.saturating_sub(z.len())];
+        buf.extend_from_slice(z.as_slice());
+        Ok(buf)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+//! The public value validation of the Diffie-Hellman key agreement, and the finite field Diffie-Hellman
+//! NIST SP 800-56A Rev. 3 5.6.2.3(FFC and ECC full public key validation), 5.7.1.1(FFC DH primitive)  
+//! RFC 7919 5.1(checking the peer's public key)
+//!
+//! The agreement functions of this crate validate the peer's public value by these functions before
+//! computing the shared secret, so that the small subgroup and invalid curve attacks are rejected centrally.
+//! The `FFDH` agrees the shared secret over the FFC domain parameters of the `dsa` feature, the ECDH is the
+//! `elliptic::ECDH`.
+
+mod validation;
+pub use validation::validate_ffdh_public_value;
+#[cfg(feature = "dsa")]
+pub use validation::validate_ffdh_public_key;
+
+#[cfg(feature = "dsa")]
+mod ffdh;
+#[cfg(feature = "dsa")]
+pub use ffdh::{FFDH, validate_ffdh_domain_parameters};
+#[cfg(feature = "ec")]
+pub use validation::{validate_ec_public_point, validate_ec_public_key};
+
+#[cfg(all(test, feature = "ec"))]
+mod dh_test;
\ No newline at end of file
@@ -0,0 +1,87 @@
+use rmath::bigint::BigInt;
+use crate::{CryptoError, CryptoErrorKind};
+#[cfg(feature = "ec")]
+use crate::elliptic::EllipticCurve;
+use crate::numeric::mod_exp_public;
+
+fn invalid_pk<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, format!("Invalid peer public value: {}", msg)))
+}
+
+/// FFC full public key validation of the peer's public value `y` in the group `(p, q)`:  
+/// - range check: $2 \le y \le p-2$, this rejects the `0, 1, p-1` which lie in the subgroup of order 1 or 2;
+/// - subgroup order check: $y^q \mod p = 1$, this rejects the `y` which lies in the small subgroup.  
+///
+/// `q` is the order of the generator, it's `(p-1)/2` for the safe prime groups(e.g. the RFC 7919 FFDHE groups).
+pub fn validate_ffdh_public_value(p: &BigInt, q: &BigInt, y: &BigInt) -> Result<(), CryptoError> {
+    let one = BigInt::from(1u32);
+    if p.is_nan() || q.is_nan() || p <= &BigInt::from(3u32) || q <= &one || q >= p {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid FFC domain parameters"));
+    }
+
+    if y.is_nan() {
+        return invalid_pk("the y is not a number");
+    }
+
+    let p_minus_1 = p.clone() - one.clone();
+    if y <= &one || y >= &p_minus_1 {
+        return invalid_pk("the y is out of the range [2, p-2]");
+    }
+
+    if mod_exp_public(y, q, p)? != one {
+        invalid_pk("the y is not in the subgroup of order q")
+    } else {
+        Ok(())
+    }
+}
+
+/// validate the peer's public key `y` by its `(p, q)` domain parameters, see `validate_ffdh_public_value`
+#[cfg(feature = "dsa")]
+pub fn validate_ffdh_public_key(pk: &crate::dsa::PublicKey) -> Result<(), CryptoError> {
+    let (p, q, _) = pk.domain_parameters().unwrap();
+    validate_ffdh_public_value(p, q, pk.public_value())
+}
+
+/// ECC full public key validation of the peer's public point `(x, y)`:  
+/// - the point is not the infinite point `(0, 0)`;
+/// - range check: $0 \le x, y < p$;
+/// - the point lies on the `curve`;
+/// - subgroup order check: $n \cdot Q = O$.
+///
+/// The curves of this crate have the cofactor 1, so the last check is implied by the point-on-curve check
+/// in theory, it's still performed in case of the faulty curve arithmetic, and the generic double-and-add of the
+/// `CurveParams` is used because the optimized implementations may reduce the scalar `n` modulo `n`.
+#[cfg(feature = "ec")]
+pub fn validate_ec_public_point<C: EllipticCurve>(curve: &C, x: &BigInt, y: &BigInt) -> Result<(), CryptoError> {
+    if x.is_nan() || y.is_nan() {
+        return invalid_pk("the point is not a number");
+    }
+
+    let zero = BigInt::from(0u32);
+    if x == &zero && y == &zero {
+        return invalid_pk("the point is the infinite point");
+    }
+
+    let cp = curve.curve_params();
+    let p = cp.field_order();
+    if x < &zero || y < &zero || x >= p || y >= p {
+        return invalid_pk("the coordinate is out of the range [0, p)");
+    }
+
+    if !curve.is_on_curve(x, y) {
+        return invalid_pk(format!("the point is not on the {}", cp.name()).as_str());
+    }
+
+    let (nx, ny) = cp.scalar(x, y, cp.base_point_order().as_ref());
+    if nx != zero || ny != zero {
+        invalid_pk("the point is not in the subgroup of order n")
+    } else {
+        Ok(())
+    }
+}
+
+/// validate the peer's public key `Q` on the `curve`, see `validate_ec_public_point`
+#[cfg(feature = "ec")]
+pub fn validate_ec_public_key<C: EllipticCurve>(curve: &C, pk: &crate::elliptic::PublicKey) -> Result<(), CryptoError> {
+    validate_ec_public_point(curve, &pk.qx, &pk.qy)
+}
@@ -3,6 +3,10 @@ use crate::{Digest, CryptoError, CryptoErrorKind, Signature};
 use rmath::rand::IterSource;
 use std::fmt::{Display, Formatter, Debug};
 use crate::dsa::signature::SignatureContent;
+use crate::validation::{ValidationReport, CheckCategory};
+use crate::prime::is_probably_prime;
+use crate::dsa::WellKnownGroup;
+use crate::numeric::{ct_mod_inverse, SecretScalar};
 
 /// FIPS 186-4  
 /// DSA domain parameters p,q,g  
@@ -42,14 +46,14 @@ impl Clone for PublicKey {
 pub struct PrivateKey {
     pk: PublicKey,
     // private key, x belong to [1,q-1]
-    x: BigInt,
+    x: SecretScalar,
 }
 
 impl Clone for PrivateKey {
     fn clone(&self) -> Self {
         Self {
             pk: self.pk.clone(),
-            x: self.x.deep_clone(),
+            x: self.x.clone(),
         }
     }
 }
@@ -141,21 +145,11 @@ impl<H, R> DSA<H, R>
                     dp, 
                     y,
                 },
-                x,
+                x: SecretScalar::new(&x),
             }
         )
     }
 
-    /// fermatInverse calculates the inverse of k in GF(P) using Fermat's method.
-    /// This has better constant-time properties than Euclid's method (implemented
-    /// in math/big.Int.ModInverse) although math/big itself isn't strictly
-    /// constant-time so it's not perfect.
-    fn fermat_inverse(k: &BigInt, p: &BigInt) -> BigInt {
-        let two = BigInt::from(2u32);
-        let pm2 = p.clone() - two;
-        k.exp(&pm2, &p)
-    }
-    
     /// FIPS 186-4, 4.2, L=1024, N=160
     pub fn l1024_n160(rd: &mut R) -> Result<DomainParameters, CryptoError> {
         let (l, n) = (1024, 160);
@@ -260,8 +254,9 @@ impl<H, R> DSA<H, R>
         let dp = pk.domain_parameters();
         let n = dp.q.bits_len();
         
+        let x = pk.x.get();
         if dp.q.signnum() != Some(1) || dp.p.signnum() != Some(1) || dp.g.signnum() != Some(1)
-            || pk.x.signnum() != Some(1) || (n & 7) != 0 {
+            || x.signnum() != Some(1) || (n & 7) != 0 {
             return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "Invalid public key"));
         }
         
@@ -287,10 +282,10 @@ impl<H, R> DSA<H, R>
                 continue;
             }
 
-            let kinv = Self::fermat_inverse(&k, &dp.q);
+            let kinv = ct_mod_inverse(&k, &dp.q)?;
             let tmp = std::cmp::min(h_len, n);
             let z = BigInt::from_be_bytes(&hm.as_slice()[..tmp]);
-            let mut s = pk.x.clone() * r.clone();
+            let mut s = x.clone() * r.clone();
             s += z;
             s.rem_euclid_assign(dp.q.clone());
             s *= kinv;
@@ -362,7 +357,6 @@ impl<H, R> DSA<H, R> {
 }
 
 impl DomainParameters {
-    #[allow(unused)]
     fn check_pq_len(p: usize, q: usize) -> bool {
         if (p == 1024  && q == 160) || (p == 2048 && q == 224) 
             || (p == 2048 && q == 256) || (p == 3072 && q == 256) {
@@ -374,7 +368,7 @@ impl DomainParameters {
     
     /// (p,q,g)
     #[allow(unused)]
-    pub(super) fn unwrap(&self) -> (&BigInt, &BigInt, &BigInt) {
+    pub(crate) fn unwrap(&self) -> (&BigInt, &BigInt, &BigInt) {
         (&self.p, &self.q, &self.g)
     }
     
@@ -392,6 +386,62 @@ impl DomainParameters {
             }
         )
     }
+    
+    /// the domain parameters of the well-known group, see `WellKnownGroup::domain_parameters`
+    pub fn well_known(group: WellKnownGroup) -> Self {
+        group.domain_parameters()
+    }
+    
+    /// the well-known group which the domain parameters belong to
+    pub fn well_known_group(&self) -> Option<WellKnownGroup> {
+        WellKnownGroup::find(self)
+    }
+    
+    /// the canonical serialization: `len(p) || p || len(q) || q || len(g) || g`, the `len(x)` is the 4 bytes
+    /// big-endian length of the `x`, and the `x` is the big-endian bytes of the magnitude without the
+    /// leading zeros. The same parameters always have the same bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(((self.p.bits_len() + 7) >> 3) * 3 + 12);
+        for x in [&self.p, &self.q, &self.g].iter() {
+            let x = x.as_ref().to_be_bytes();
+            let start = x.iter().position(|&e| e != 0).unwrap_or(x.len());
+            buf.extend_from_slice(((x.len() - start) as u32).to_be_bytes().as_ref());
+            buf.extend_from_slice(&x[start..]);
+        }
+        buf
+    }
+    
+    /// parse the canonical serialization of the `to_bytes`, the non-canonical encoding(e.g. the leading zeros,
+    /// the trailing bytes) is rejected.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, CryptoError> {
+        let invalid = || CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid domain parameters encoding");
+        let mut rest = data;
+        let mut v = Vec::with_capacity(3);
+        for _ in 0..3 {
+            if rest.len() < 4 {
+                return Err(invalid());
+            }
+            let (len, tail) = rest.split_at(4);
+            let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+            if len == 0 || tail.len() < len || tail[0] == 0 {
+                return Err(invalid());
+            }
+            let (x, tail) = tail.split_at(len);
+            v.push(BigInt::from_be_bytes(x));
+            rest = tail;
+        }
+        
+        if !rest.is_empty() {
+            return Err(invalid());
+        }
+        Self::new_uncheck(&v[0], &v[1], &v[2])
+    }
+    
+    /// compare the domain parameters in constant time, the time only depends on the lengths of the parameters.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.to_bytes(), other.to_bytes());
+        a.len() == b.len() && a.iter().zip(b.iter()).fold(0u8, |acc, (&x, &y)| acc | (x ^ y)) == 0
+    }
 }
 
 impl Display for DomainParameters {
@@ -450,16 +500,22 @@ impl PrivateKey {
         Ok(
             Self {
                 pk: pk.clone(),
-                x: x.deep_clone(),
+                x: SecretScalar::new(x),
             }
         )
     }
+    
+    /// export the private key x(the minimal big-endian bytes) to the locked memory
+    #[cfg(feature = "mlock")]
+    pub fn to_secret_bytes(&self) -> Result<crate::secret::SecretBytes, CryptoError> {
+        self.x.to_secret_bytes()
+    }
 }
 
 impl Display for PrivateKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let pk = format!("{}", self.pk);
-        let x = format!("{:#x}", self.x);
+        let x = format!("{:#x}", self.x.get());
         write!(f, "{{x: \"{}\", {}}}", x, pk)
     }
 }
@@ -478,44 +534,100 @@ impl PrivateKey {
     pub fn domain_parameters(&self) -> &DomainParameters {
         &self.public_key().domain_parameters()
     }
+
+    /// the private value x
+    pub(crate) fn private_value(&self) -> BigInt {
+        self.x.get()
+    }
 }
 
 impl PublicKey {
     pub fn domain_parameters(&self) -> &DomainParameters {
         &self.dp
     }
+
+    /// the public value y
+    pub(crate) fn public_value(&self) -> &BigInt {
+        &self.y
+    }
+
+    fn validate_inner<R: IterSource<u32>>(&self, report: &mut ValidationReport, rd: &mut R) -> bool {
+        let (p, q, g) = self.dp.unwrap();
+        let bigone = BigInt::from(1u32);
+        
+        // the well-known groups have been validated, so the primality tests are skipped
+        let is_primes = (self.dp.well_known_group().is_some() || (is_probably_prime(p, 20, rd) && is_probably_prime(q, 20, rd)))
+            && (p.clone() - bigone.clone()).rem_euclid(q.clone()).signnum() == Some(0);
+        if !report.check("domain parameters", CheckCategory::Parameter, is_primes, 
+            "the p and q must be primes and q | (p-1)") {
+            return false;
+        }
+        
+        let is_gen = g > &bigone && g < p && g.exp(q, p) == bigone;
+        report.check("generator", CheckCategory::Parameter, is_gen, "the generator g must be in [2, p-1] and g^q = 1 mod p");
+        
+        report.check("public value", CheckCategory::Parameter, crate::dh::validate_ffdh_public_value(p, q, &self.y).is_ok(),
+            "the public value y must be in [2, p-2] and y^q = 1 mod p");
+        
+        report.check("domain parameter sizes", CheckCategory::Fips, DomainParameters::check_pq_len(p.bits_len(), q.bits_len()),
+            format!("the (L, N) = ({}, {}) is not one of the FIPS 186-4 4.2 choices", p.bits_len(), q.bits_len()).as_str());
+        true
+    }
+    
+    /// validate the public key: the primality of p and q, the order of generator g and public value y, 
+    /// and the FIPS 186-4 4.2 (L, N) choices.
+    pub fn validate<R: IterSource<u32>>(&self, rd: &mut R) -> ValidationReport {
+        let mut report = ValidationReport::new("DSA public key", false);
+        self.validate_inner(&mut report, rd);
+        report.skip("key pair consistency", CheckCategory::Pairwise, "the private key is absent");
+        report
+    }
 }
 
-pub struct KeyPair {
-    pub_key: Option<PublicKey>,
-    pri_key: Option<PrivateKey>,
+impl PrivateKey {
+    /// validate the private key: the public key checks, the range of the private key x, and the 
+    /// pairwise consistency check y = g^x mod p.
+    pub fn validate<R: IterSource<u32>>(&self, rd: &mut R) -> ValidationReport {
+        let mut report = ValidationReport::new("DSA private key", true);
+        if !self.pk.validate_inner(&mut report, rd) {
+            return report;
+        }
+        
+        let (p, q, g) = self.pk.dp.unwrap();
+        let x = self.x.get();
+        report.check("private key", CheckCategory::Parameter, x.signnum() == Some(1) && &x < q,
+            "the private key x must be in [1, q-1]");
+        report.check("key pair consistency", CheckCategory::Pairwise, g.exp(&x, p) == self.pk.y,
+            "the public value y doesn't equal to g^x mod p");
+        report
+    }
+}
+
+/// The DSA key material, the verification is always available, the signing needs the `Full` key pair.
+#[derive(Clone)]
+pub enum KeyPair {
+    PublicOnly(PublicKey),
+    Full(PrivateKey),
 }
 
 impl From<PublicKey> for KeyPair {
     fn from(pk: PublicKey) -> Self {
-        Self {
-            pub_key: Some(pk),
-            pri_key: None,
-        }
+        KeyPair::PublicOnly(pk)
     }
 }
 
 impl From<PrivateKey> for KeyPair {
     fn from(pk: PrivateKey) -> Self {
-        Self {
-            pub_key: None,
-            pri_key: Some(pk),
-        }
+        KeyPair::Full(pk)
     }
 }
 
 
 impl KeyPair {
     pub fn public_key(&self) -> &PublicKey {
-        if self.pri_key.is_some() {
-            self.pri_key.as_ref().unwrap().public_key()
-        } else {
-            self.pub_key.as_ref().unwrap()
+        match self {
+            KeyPair::PublicOnly(pk) => pk,
+            KeyPair::Full(sk) => sk.public_key(),
         }
     }
     
@@ -524,7 +636,20 @@ impl KeyPair {
     }
     
     pub fn private_key(&self) -> Option<&PrivateKey> {
-        self.pri_key.as_ref()
+        match self {
+            KeyPair::PublicOnly(_) => None,
+            KeyPair::Full(sk) => Some(sk),
+        }
+    }
+    
+    /// the private key is present, so the key pair can be used by the signing
+    pub fn can_sign(&self) -> bool {
+        self.private_key().is_some()
+    }
+    
+    /// the DSA is only the signature scheme, it always returns `false`
+    pub fn can_decrypt(&self) -> bool {
+        false
     }
 }
 
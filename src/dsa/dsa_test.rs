@@ -1,5 +1,5 @@
 use std::str::FromStr;
-use crate::dsa::{DSA, SignatureContent, DomainParameters, PrivateKey, PublicKey, KeyPair};
+use crate::dsa::{DSA, SignatureContent, DomainParameters, PrivateKey, PublicKey, KeyPair, WellKnownGroup};
 use crate::{sha, Signature};
 use rmath::rand::{DefaultSeed, CryptoRand};
 use rmath::bigint::BigInt;
@@ -78,4 +78,119 @@ fn dsa_sign_verify() {
     let mut sig = SignatureContent::new();
     sig.set(BigInt::from(2u32), BigInt::from(4u32));
     assert!(dsa.verify(&sig, msg.as_bytes()).is_err());
+}
+#[test]
+fn dsa_key_validate() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    
+    // the generator 4 has the order 11 in GF(23), y = 4^3 mod 23
+    let (p, q, g) = (BigInt::from(23u32), BigInt::from(11u32), BigInt::from(4u32));
+    let dp = DomainParameters::new_uncheck(&p, &q, &g).unwrap();
+    let pk = PublicKey::new_uncheck(&dp, &BigInt::from(18u32)).unwrap();
+    let sk = PrivateKey::new_uncheck(&pk, &BigInt::from(3u32)).unwrap();
+    let report = sk.validate(&mut rd);
+    assert!(report.is_valid() && !report.is_fips_compliant(), "{}", report);
+    assert_eq!(report.failures().map(|c| c.name).collect::<Vec<_>>(), vec!["domain parameter sizes"]);
+    
+    let sk = PrivateKey::new_uncheck(&pk, &BigInt::from(4u32)).unwrap();
+    let report = sk.validate(&mut rd);
+    assert_eq!(report.failures().map(|c| c.name).collect::<Vec<_>>(), vec!["domain parameter sizes", "key pair consistency"]);
+    
+    let pk = PublicKey::new_uncheck(&dp, &BigInt::from(22u32)).unwrap();
+    assert!(!pk.validate(&mut rd).is_valid());
+    
+    let dp = DomainParameters::new_uncheck(&p, &q, &BigInt::from(5u32)).unwrap();
+    let pk = PublicKey::new_uncheck(&dp, &BigInt::from(18u32)).unwrap();
+    assert!(pk.validate(&mut rd).failures().any(|c| c.name == "generator"));
+    
+    let dp = DomainParameters::new_uncheck(&BigInt::from(25u32), &q, &g).unwrap();
+    let pk = PublicKey::new_uncheck(&dp, &BigInt::from(18u32)).unwrap();
+    assert!(pk.validate(&mut rd).failures().any(|c| c.name == "domain parameters"));
+}
+
+#[test]
+fn dsa_domain_parameters_serialization() {
+    let dp = DomainParameters::well_known(WellKnownGroup::Ffdhe2048);
+    let bytes = dp.to_bytes();
+    assert_eq!(bytes.len(), 4 + 256 + 4 + 256 + 4 + 1);
+    assert_eq!(&bytes[..6], &[0, 0, 1, 0, 0xff, 0xff]);
+    let dp2 = DomainParameters::from_bytes(bytes.as_slice()).unwrap();
+    assert!(dp.ct_eq(&dp2));
+    assert_eq!(dp2.to_bytes(), bytes);
+    assert_eq!(dp2.well_known_group(), Some(WellKnownGroup::Ffdhe2048));
+    
+    // the leading zeros and the trailing bytes are not canonical
+    let mut b = bytes.clone();
+    b.push(0);
+    assert!(DomainParameters::from_bytes(b.as_slice()).is_err());
+    let mut b = vec![0, 0, 1, 1, 0];
+    b.extend_from_slice(&bytes[4..]);
+    assert!(DomainParameters::from_bytes(b.as_slice()).is_err());
+    assert!(DomainParameters::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    
+    let other = DomainParameters::well_known(WellKnownGroup::Modp2048);
+    assert!(!dp.ct_eq(&other));
+    let small = DomainParameters::new_uncheck(&BigInt::from(23u32), &BigInt::from(11u32), &BigInt::from(2u32)).unwrap();
+    assert_eq!(small.to_bytes(), vec![0, 0, 0, 1, 23, 0, 0, 0, 1, 11, 0, 0, 0, 1, 2]);
+    assert_eq!(small.well_known_group(), None);
+}
+
+#[test]
+fn dsa_well_known_groups() {
+    let one = BigInt::from(1u32);
+    
+    for &group in WellKnownGroup::all() {
+        let dp = DomainParameters::well_known(group);
+        assert_eq!(dp.well_known_group(), Some(group), "{}", group.name());
+        let (p, q, g) = dp.unwrap();
+        assert_eq!(p.bits_len(), group.bits_len(), "{}", group.name());
+        assert_eq!(q.clone() * BigInt::from(2u32) + one.clone(), p.clone(), "{}", group.name());
+        if group.bits_len() <= 2048 {
+            // the Fermat test of the safe prime p = 2q + 1
+            let three = BigInt::from(3u32);
+            assert_eq!(three.exp(&(p.clone() - one.clone()), p), one, "{}", group.name());
+            assert_eq!(three.exp(&(q.clone() - one.clone()), q), one, "{}", group.name());
+            assert_eq!(g.exp(q, p), one, "{}", group.name());
+        }
+    }
+}
+
+#[test]
+fn dsa_key_pair_capability() {
+    let (p, q, g) = (BigInt::from(23u32), BigInt::from(11u32), BigInt::from(4u32));
+    let dp = DomainParameters::new_uncheck(&p, &q, &g).unwrap();
+    let pk = PublicKey::new_uncheck(&dp, &BigInt::from(18u32)).unwrap();
+    let sk = PrivateKey::new_uncheck(&pk, &BigInt::from(3u32)).unwrap();
+    
+    let full = KeyPair::from(sk);
+    assert!(full.can_sign() && !full.can_decrypt());
+    assert_eq!(full.domain_parameters().to_bytes(), dp.to_bytes());
+    
+    let public_only = KeyPair::from(pk);
+    assert!(!public_only.can_sign() && public_only.private_key().is_none());
+    assert!(matches!(public_only, KeyPair::PublicOnly(_)));
+}
+
+#[test]
+fn dsa_signature_der() {
+    // r with the high bit needs the leading 0x00
+    let (r, s) = (BigInt::from(0x80u32), BigInt::from(0x7fu32));
+    let sig = SignatureContent::form_bigint(&r, &s);
+    let der = sig.to_der();
+    assert_eq!(der, vec![0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x7f]);
+    let sig = SignatureContent::from_der(der.as_slice()).unwrap();
+    assert_eq!(sig.to_bigint(), (r.clone(), s.clone()));
+    
+    // the non-minimal integer, the negative integer, the trailing bytes
+    assert!(SignatureContent::from_der(&[0x30, 0x07, 0x02, 0x02, 0x00, 0x7f, 0x02, 0x01, 0x7f]).is_err());
+    assert!(SignatureContent::from_der(&[0x30, 0x06, 0x02, 0x01, 0x80, 0x02, 0x01, 0x7f]).is_err());
+    assert!(SignatureContent::from_der(&[0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x7f, 0x00]).is_err());
+    assert!(SignatureContent::from_der(&[0x30, 0x81, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x7f]).is_err());
+    
+    let fixed = sig.to_fixed_bytes(4).unwrap();
+    assert_eq!(fixed, vec![0, 0, 0, 0x80, 0, 0, 0, 0x7f]);
+    assert_eq!(SignatureContent::from_fixed_bytes(fixed.as_slice()).unwrap().to_bigint(), (r, s));
+    assert!(SignatureContent::form_bigint(&BigInt::from(0x10000u32), &BigInt::from(1u32)).to_fixed_bytes(2).is_err());
+    assert!(SignatureContent::from_fixed_bytes(&[1, 2, 3]).is_err());
 }
\ No newline at end of file
@@ -11,5 +11,8 @@ pub use dsa::{DSA, PrivateKey, PublicKey, KeyPair, DomainParameters};
 mod signature;
 pub use signature::SignatureContent;
 
-#[cfg(test)]
+mod groups;
+pub use groups::WellKnownGroup;
+
+#[cfg(all(test, feature = "sha2"))]
 mod dsa_test;
\ No newline at end of file
@@ -1,4 +1,6 @@
 use rmath::bigint::BigInt;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::encoding::der::{self, DerReader};
 
 /// (r, s)
 pub struct SignatureContent {
@@ -48,6 +50,66 @@ impl SignatureContent {
         self.content.append(&mut s.to_be_bytes());
         self.s_len = self.content.len() - self.r_len;
     }
+    
+    fn r_bytes(&self) -> &[u8] {
+        &self.content[..self.r_len]
+    }
+    
+    fn s_bytes(&self) -> &[u8] {
+        &self.content[self.r_len..(self.r_len + self.s_len)]
+    }
+    
+    /// the DER encoding of the `Dss-Sig-Value`/`Ecdsa-Sig-Value ::= SEQUENCE { r INTEGER, s INTEGER }`(RFC 3279 2.2.2, 2.2.3),
+    /// it's used by the OpenSSL, TLS and X.509.
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut seq = Vec::with_capacity(self.content.len() + 6);
+        der::encode_unsigned_integer(self.r_bytes(), &mut seq);
+        der::encode_unsigned_integer(self.s_bytes(), &mut seq);
+        let mut buf = Vec::with_capacity(seq.len() + 4);
+        der::encode_tlv(der::TAG_SEQUENCE, seq.as_slice(), &mut buf);
+        buf
+    }
+    
+    /// parse the DER encoded `SEQUENCE { r INTEGER, s INTEGER }`, the BER(e.g. the non-minimal length or integer),
+    /// the negative integers and the trailing bytes are rejected.
+    pub fn from_der(der: &[u8]) -> Result<Self, CryptoError> {
+        let mut rd = DerReader::new(der);
+        let mut seq = DerReader::new(rd.read_expect(der::TAG_SEQUENCE)?.value);
+        let (r, s) = (seq.read_unsigned_integer()?, seq.read_unsigned_integer()?);
+        if !rd.is_empty() || !seq.is_empty() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid DER: the trailing bytes of the signature"));
+        }
+        
+        Ok(Self::form_bigint(&BigInt::from_be_bytes(r), &BigInt::from_be_bytes(s)))
+    }
+    
+    /// the fixed-width `r || s` encoding(e.g. the IEEE P1363, JWS ES256, WebCrypto ECDSA), the `r` and `s` are the
+    /// `len` bytes big-endian integers, e.g. the `len` is 32 for the P-256.
+    pub fn to_fixed_bytes(&self, len: usize) -> Result<Vec<u8>, CryptoError> {
+        let mut buf = Vec::with_capacity(len << 1);
+        for x in [self.r_bytes(), self.s_bytes()].iter() {
+            let skip = x.iter().take_while(|&&b| b == 0).count();
+            let x = &x[skip..];
+            if x.len() > len {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("The signature integer is {} bytes, it cannot be encoded in {} bytes", x.len(), len)));
+            }
+            buf.resize(buf.len() + len - x.len(), 0);
+            buf.extend_from_slice(x);
+        }
+        Ok(buf)
+    }
+    
+    /// parse the fixed-width `r || s` encoding, the `bytes` is split into the two halves
+    pub fn from_fixed_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.is_empty() || (bytes.len() & 1) != 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The fixed-width signature must be the non-empty even length, but got {}", bytes.len())));
+        }
+        
+        let (r, s) = bytes.split_at(bytes.len() >> 1);
+        Ok(Self::form_bigint(&BigInt::from_be_bytes(r), &BigInt::from_be_bytes(s)))
+    }
 }
 
 impl AsRef<Vec<u8>> for SignatureContent {
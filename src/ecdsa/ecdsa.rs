@@ -1,10 +1,30 @@
-use crate::elliptic::{EllipticCurve, KeyPair, PublicKey, PrivateKey, CurveParams};
+use crate::elliptic::{EllipticCurve, KeyPair, PublicKey, PrivateKey, CurveParams, ScalarField};
 use crate::{Digest, CryptoError, CryptoErrorKind, Signature};
 use rmath::rand::IterSource;
 use rmath::bigint::BigInt;
 use crate::sha::SHA512;
 use crate::ecdsa::csp_rng::CSPRng;
-use crate::ecdsa::SignatureContent;
+use crate::ecdsa::{SignatureContent, RecoverableSignature};
+use crate::numeric::ct_mod_inverse;
+
+/// the leftmost `min(bits_len(n), bits_len(hash))` bits of the `hash`
+pub(crate) fn hash_to_bigint(n: &BigInt, hash: &[u8]) -> BigInt {
+    let order_bits = n.bits_len();
+    let order_byte = (order_bits + 7) >> 3;
+    let hash = if hash.len() > order_byte {
+        &hash[..order_byte]
+    } else {
+        hash
+    };
+    
+    let mut ret = BigInt::from_be_bytes(hash);
+    let excess = (hash.len() << 3).saturating_sub(order_bits);
+    if excess > 0 {
+        ret >>= excess;
+    }
+    
+    ret
+}
 
 /// Elliptic Curve Digital Signature Algorithms  
 /// FIPS 186-4, chapter 6
@@ -14,8 +34,9 @@ pub struct ECDSA<H, R, C> {
     rd: R,
     kp: KeyPair,
     md: SHA512,
-    d_byes: Option<Vec<u8>>,
     hash_buf: Vec<u8>,
+    // the cached constant-time inversion modulo the order n, `None` if the n is not an odd number
+    scalar_field: Option<ScalarField>,
 }
 
 impl<H, R, C> ECDSA<H, R, C>
@@ -89,7 +110,7 @@ impl<H, R, C> ECDSA<H, R, C>
         Ok(
             Self {
                 hash_buf: Vec::with_capacity((hf.bits_len() + 7) >> 3),
-                d_byes: key_pair.private_key().map(|e| {e.d.to_be_bytes()}),
+                scalar_field: ScalarField::new(curve.curve_params().base_point_order()),
                 curve,
                 hf,
                 rd,
@@ -103,44 +124,20 @@ impl<H, R, C> ECDSA<H, R, C>
     pub fn auto_generate_key(hf: H, mut rd: R, curve: C) -> Result<Self, CryptoError> {
         let k = Self::rand_field_element(&curve, &mut rd)?;
         let (px, py) = curve.scalar_base_point(k.as_ref());
-        let pk = PrivateKey {
-            pk: PublicKey {
-                qx: px,
-                qy: py,
-            },
-            d: k
-        };
+        let pk = PrivateKey::new_uncheck(PublicKey { qx: px, qy: py }, &k);
         Self::new_unchcek(hf, rd, curve, KeyPair::from(pk))
     }
     
     fn hash_to_bigint(&self, hash: &[u8]) -> BigInt {
-        let order_bits = self.curve.curve_params().base_point_order().bits_len();
-        let order_byte = (order_bits + 7) >> 3;
-        let hash = if hash.len() > order_byte {
-            &hash[..order_byte]
-        } else {
-            hash
-        };
-        
-        let mut ret = BigInt::from_be_bytes(hash);
-        let excess = (hash.len() << 3).saturating_sub(order_bits);
-        if excess > 0 {
-            ret >>= excess;
-        }
-        
-        ret
-    }
-    
-    fn fermat_inverse(k: &BigInt, n: &BigInt) -> BigInt {
-        let two = BigInt::from(2u32);
-        let nm2 = n.clone() - two;
-        k.exp(&nm2, n)
+        hash_to_bigint(self.curve.curve_params().base_point_order(), hash)
     }
     
-    fn sign_inner(&mut self) -> Result<(BigInt, BigInt), CryptoError> {
+    /// return `(r, s, v)`, the `v` is the recovery id of the `RecoverableSignature`
+    fn sign_inner(&mut self) -> Result<(BigInt, BigInt, u8), CryptoError> {
         let hash = self.hash_buf.as_slice();
         let pk = self.kp.private_key().ok_or(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "Public key cannot used to sign"))?;
-        let d_bytes = self.d_byes.as_ref().unwrap();
+        let d = pk.d.get();
+        let d_bytes = d.to_be_bytes();
         
         let entropy_len = std::cmp::min(32, (self.curve.curve_params().field_bits_size() + 7) >> 4);
         let mut entropy = Vec::with_capacity(entropy_len);
@@ -171,28 +168,33 @@ impl<H, R, C> ECDSA<H, R, C>
             return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, ""));
         }
         
-        let (r, s) = loop {
-            let (r, kinv) = loop {
+        let (r, s, v) = loop {
+            let (r, kinv, v) = loop {
                 let k = Self::rand_field_element_for_csprng(&self.curve, &mut csprng)?;
-                let kinv = Self::fermat_inverse(&k, n);
-                let (mut r, _) = self.curve.scalar_base_point(k.as_ref());
+                let kinv = match self.scalar_field.as_ref() {
+                    Some(f) => f.invert(&k)?,
+                    None => ct_mod_inverse(&k, n)?,
+                };
+                let (x, y) = self.curve.scalar_base_point(k.as_ref());
+                let v = (y.to_be_bytes().last().copied().unwrap_or(0) & 1) | if &x >= n {2} else {0};
+                let mut r = x;
                 r.rem_euclid_assign(n.clone());
                 if r.signnum() == Some(1) {
-                    break (r, kinv);
+                    break (r, kinv, v);
                 }
             };
             
             let e = self.hash_to_bigint(hash);
-            let mut s = pk.d.clone() * r.clone();
+            let mut s = d.clone() * r.clone();
             s += e;
             s *= kinv;
             s.rem_euclid_assign(n.clone());
             if s.signnum() == Some(1) {
-                break (r, s);
+                break (r, s, v);
             }
         };
         
-        Ok((r, s))
+        Ok((r, s, v))
     }
     
     fn verify_inner(&mut self, r: &BigInt, s: &BigInt) -> Result<(), CryptoError> {
@@ -207,7 +209,10 @@ impl<H, R, C> ECDSA<H, R, C>
         }
 
         let mut e = self.hash_to_bigint(hash);
-        let mut w = s.mod_inverse(n.clone());
+        let mut w = match self.scalar_field.as_ref() {
+            Some(f) => f.invert(s)?,
+            None => s.mod_inverse(n.clone()),
+        };
         e *= w.clone();
         let mut u1 = e;
         u1.rem_euclid_assign(n.clone());
@@ -232,6 +237,31 @@ impl<H, R, C> ECDSA<H, R, C>
     }
 }
 
+impl<H, R, C> ECDSA<H, R, C>
+    where H: Digest, R: IterSource<u32>, C: EllipticCurve {
+    /// sign the `message` with the recovery id, the public key can be recovered by the `recover_public_key` with the
+    /// digest of the `message`.
+    pub fn sign_recoverable(&mut self, message: &[u8]) -> Result<RecoverableSignature, CryptoError> {
+        self.hf.reset();
+        self.hf.write(message);
+        self.hf.checksum(&mut self.hash_buf);
+        let (r, s, v) = self.sign_inner()?;
+        RecoverableSignature::new(SignatureContent::form_bigint(&r, &s), v)
+    }
+}
+
+impl<H, R> ECDSA<H, R, CurveParams>
+    where H: Digest, R: IterSource<u32> {
+    /// the ECDSA over the user-supplied curve(`CurveParams::new_uncheck`), the domain parameters are validated by the
+    /// `CurveParams::validate_domain`(the `InvalidParameter` error wraps the `CurveDefect`), and the public key is
+    /// validated by the `dh::validate_ec_public_key`.
+    pub fn new_custom_curve(hf: H, mut rd: R, curve: CurveParams, key_pair: KeyPair) -> Result<Self, CryptoError> {
+        curve.validate_domain(&mut rd).map_err(|e| CryptoError::new(CryptoErrorKind::InvalidParameter, e))?;
+        crate::dh::validate_ec_public_key(&curve, key_pair.public_key())?;
+        Self::new_unchcek(hf, rd, curve, key_pair)
+    }
+}
+
 impl<H, R, C> Signature<SignatureContent> for ECDSA<H, R, C>
     where H: Digest, R: IterSource<u32>, C: EllipticCurve {
     type Output = ();
@@ -240,7 +270,7 @@ impl<H, R, C> Signature<SignatureContent> for ECDSA<H, R, C>
         self.hf.reset();
         self.hf.write(message);
         self.hf.checksum(&mut self.hash_buf);
-        let (r, s) = self.sign_inner()?;
+        let (r, s, _) = self.sign_inner()?;
         signature.set(r, s);
         Ok(())
     }
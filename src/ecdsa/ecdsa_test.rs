@@ -1,9 +1,9 @@
 use crate::elliptic::{CurveP224, CurveP256, CurveParams, EllipticCurve};
-use crate::ecdsa::ECDSA;
+use crate::ecdsa::{ECDSA, RecoverableSignature, recover_public_key};
 use crate::sha::SHA1;
 use rmath::rand::{DefaultSeed, CryptoRand};
 use crate::dsa::SignatureContent;
-use crate::Signature;
+use crate::{Signature, Digest};
 
 #[test]
 fn ecdsa() {
@@ -40,4 +40,90 @@ fn ecdsa() {
     ecdsa3.sign(&mut sig, s.as_slice()).unwrap();
     ecdsa3.verify(&sig, s.as_slice()).unwrap();
     assert!(ecdsa3.verify(&sig, ss.as_slice()).is_err());
+}
+#[test]
+fn ecdsa_custom_curve() {
+    use crate::elliptic::{CurveDefect, KeyPair};
+    use rmath::bigint::BigInt;
+    
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let p256 = CurveParams::p256().unwrap();
+    let (p, n, b) = (p256.field_order().clone(), p256.base_point_order().clone(), p256.coefficient_b().clone());
+    let (gx, gy) = (p256.base_point().0.clone(), p256.base_point().1.clone());
+    let one = BigInt::from(1u32);
+    
+    let custom = CurveParams::new_uncheck("custom P-256", &p, &n, &one, &b, &gx, &gy);
+    assert_eq!(custom.validate_domain(&mut rd), Ok(()));
+    assert_eq!(p256.validate_domain(&mut rd), Ok(()));
+    for c in [CurveParams::p224().unwrap(), CurveParams::p384().unwrap(), CurveParams::p521().unwrap()].iter() {
+        assert_eq!(c.validate_domain(&mut rd), Ok(()), "{}", c.name());
+    }
+    
+    let key = custom.generate_key(&mut rd).unwrap();
+    let mut ecdsa = ECDSA::new_custom_curve(SHA1::new(), rd.clone(), custom.clone(), KeyPair::from(key)).unwrap();
+    let mut sig = SignatureContent::new();
+    ecdsa.sign(&mut sig, b"testing").unwrap();
+    ecdsa.verify(&sig, b"testing").unwrap();
+    
+    let two = BigInt::from(2u32);
+    let cases = [
+        (CurveParams::new_uncheck("", &(p.clone() + two.clone()), &n, &one, &b, &gx, &gy), CurveDefect::FieldNotPrime),
+        (CurveParams::new_uncheck("", &p, &n, &one, &BigInt::from(0u32), &gx, &gy), CurveDefect::CoefficientZero),
+        (CurveParams::new_uncheck("", &p, &n, &one, &two, &gx, &gy), CurveDefect::SingularCurve),
+        (CurveParams::new_uncheck("", &p, &n, &one, &b, &gx, &(gy.clone() + one.clone())), CurveDefect::BasePointNotOnCurve),
+        (CurveParams::new_uncheck("", &p, &(n.clone() - one.clone()), &one, &b, &gx, &gy), CurveDefect::OrderNotPrime),
+        (CurveParams::new_uncheck("", &p, &p, &one, &b, &gx, &gy), CurveDefect::BasePointOrderMismatch),
+        (CurveParams::new_uncheck("", &p, &n, &two, &b, &gx, &gy), CurveDefect::CofactorOutOfBound),
+        (CurveParams::new_uncheck("", &p, &n, &BigInt::from(0u32), &b, &gx, &gy), CurveDefect::CofactorOutOfBound),
+    ];
+    for (c, defect) in cases.iter() {
+        assert_eq!(c.validate_domain(&mut rd), Err(*defect));
+    }
+    
+    let key = p256.generate_key(&mut rd).unwrap();
+    let e = ECDSA::new_custom_curve(SHA1::new(), rd.clone(), cases[6].0.clone(), KeyPair::from(key)).err().unwrap();
+    assert_eq!(e.kind(), crate::CryptoErrorKind::InvalidParameter);
+}
+
+#[test]
+fn ecdsa_recoverable() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let msg = b"only the signature is transmitted";
+    let mut hash = Vec::new();
+    let mut sha = SHA1::new();
+    sha.write(msg.as_ref());
+    sha.checksum(&mut hash);
+    
+    // the P-224 field order p ≡ 1 (mod 4) needs the Tonelli-Shanks square root
+    let curves = [CurveParams::p224().unwrap(), CurveParams::p256().unwrap(), CurveParams::p384().unwrap(), CurveParams::brainpool_p256r1().unwrap()];
+    for curve in curves.iter() {
+        let mut ecdsa = ECDSA::auto_generate_key(SHA1::new(), rd.clone(), curve.clone()).unwrap();
+        for _ in 0..4 {
+            let sig = ecdsa.sign_recoverable(msg.as_ref()).unwrap();
+            ecdsa.verify(sig.signature(), msg.as_ref()).unwrap();
+            
+            let pk = recover_public_key(curve, hash.as_slice(), &sig).unwrap();
+            assert_eq!((&pk.qx, &pk.qy), (&ecdsa.public_key().qx, &ecdsa.public_key().qy), "curve: {}", curve.name());
+            
+            let len = (curve.base_point_order().bits_len() + 7) >> 3;
+            let bytes = sig.to_bytes(len).unwrap();
+            assert_eq!(bytes.len(), (len << 1) + 1);
+            let sig2 = RecoverableSignature::from_bytes(bytes.as_slice()).unwrap();
+            assert_eq!(sig2.recovery_id(), sig.recovery_id());
+            assert_eq!(sig2.signature().to_bigint(), sig.signature().to_bigint());
+            
+            // the other parity recovers the other key
+            let (rs, v) = sig2.into_inner();
+            let flipped = RecoverableSignature::new(rs, v ^ 1).unwrap();
+            let other = recover_public_key(curve, hash.as_slice(), &flipped).unwrap();
+            assert!(other.qx != ecdsa.public_key().qx || other.qy != ecdsa.public_key().qy);
+        }
+    }
+    
+    assert!(RecoverableSignature::new(SignatureContent::new(), 4).is_err());
+    assert!(RecoverableSignature::from_bytes(&[]).is_err());
+    let zero = RecoverableSignature::from_bytes(&[0, 0, 0]).unwrap();
+    assert!(recover_public_key(&curves[1], hash.as_slice(), &zero).is_err());
 }
\ No newline at end of file
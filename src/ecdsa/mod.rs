@@ -9,7 +9,10 @@ pub use crate::dsa::SignatureContent;
 mod ecdsa;
 pub use ecdsa::{ECDSA};
 
-mod csp_rng;
+mod recoverable;
+pub use recoverable::{RecoverableSignature, recover_public_key};
+
+pub(crate) mod csp_rng;
 
 #[cfg(test)]
 mod ecdsa_test;
\ No newline at end of file
@@ -0,0 +1,95 @@
+use rmath::bigint::BigInt;
+use crate::elliptic::{EllipticCurve, PublicKey};
+use crate::ecdsa::SignatureContent;
+use crate::ecdsa::ecdsa::hash_to_bigint;
+use crate::{CryptoError, CryptoErrorKind};
+
+/// The ECDSA signature `(r, s)` with the recovery id, the public key can be recovered from the signature and the
+/// message hash by the `recover_public_key`(SEC 1 v2 4.1.6), so only the signature needs to be transmitted.
+///
+/// the recovery id `v` is in the range [0, 3]: the bit 0 is the parity of the y-coordinate of the point `R = kG`,
+/// and the bit 1 means the x-coordinate of the `R` is `r + n`(the probability is about $2^{-127}$ for the P-256).
+pub struct RecoverableSignature {
+    sig: SignatureContent,
+    recovery_id: u8,
+}
+
+impl RecoverableSignature {
+    pub fn new(sig: SignatureContent, recovery_id: u8) -> Result<Self, CryptoError> {
+        if recovery_id > 3 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The recovery id must be in the range [0, 3], but got {}", recovery_id)));
+        }
+
+        Ok(Self { sig, recovery_id })
+    }
+
+    pub fn signature(&self) -> &SignatureContent {
+        &self.sig
+    }
+
+    pub fn recovery_id(&self) -> u8 {
+        self.recovery_id
+    }
+
+    pub fn into_inner(self) -> (SignatureContent, u8) {
+        (self.sig, self.recovery_id)
+    }
+
+    /// the `r || s || v` encoding, the `r` and `s` are the `len` bytes big-endian integers
+    pub fn to_bytes(&self, len: usize) -> Result<Vec<u8>, CryptoError> {
+        let mut buf = self.sig.to_fixed_bytes(len)?;
+        buf.push(self.recovery_id);
+        Ok(buf)
+    }
+
+    /// parse the `r || s || v` encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        match bytes.split_last() {
+            Some((&v, rs)) => Self::new(SignatureContent::from_fixed_bytes(rs)?, v),
+            None => Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The recoverable signature is empty")),
+        }
+    }
+}
+
+/// SEC 1 v2 4.1.6 public key recovery operation, the `msg_hash` is the message digest which is signed(e.g. the
+/// SHA-256 of the message). The recovered key `Q` satisfies the ECDSA verification of the `sig`, the `VerificationFailed`
+/// error is returned if the `sig` is invalid or there is no such key.
+pub fn recover_public_key<C: EllipticCurve>(curve: &C, msg_hash: &[u8], sig: &RecoverableSignature) -> Result<PublicKey, CryptoError> {
+    let cp = curve.curve_params();
+    let n = cp.base_point_order();
+    let (r, s) = sig.sig.to_bigint();
+    if r.signnum() != Some(1) || s.signnum() != Some(1) || &r >= n || &s >= n {
+        return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The signature (r, s) is out of the range [1, n-1]"));
+    }
+
+    // R = (x, y), x = r + jn, the parity of the y is the bit 0 of the recovery id
+    let x = if sig.recovery_id & 2 == 2 { r.clone() + n.clone() } else { r.clone() };
+    let y = cp.decompress_y(&x, sig.recovery_id & 1 == 1).ok_or_else(|| {
+        CryptoError::new(CryptoErrorKind::VerificationFailed, "The point R of the signature is not on the curve")
+    })?;
+    if cp.cofactor() != &BigInt::from(1u32) {
+        let (zx, zy) = curve.scalar(&x, &y, n.as_ref());
+        if zx.signnum() == Some(1) || zy.signnum() == Some(1) {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The order of the point R is not n"));
+        }
+    }
+
+    // Q = r^{-1}(sR - eG) = (-e r^{-1})G + (s r^{-1})R, the r is public so the variable-time inversion is used
+    let rinv = r.mod_inverse(n.clone());
+    let mut e = hash_to_bigint(n, msg_hash);
+    e.rem_euclid_assign(n.clone());
+    let mut u1 = (n.clone() - e) * rinv.clone();
+    u1.rem_euclid_assign(n.clone());
+    let mut u2 = s * rinv;
+    u2.rem_euclid_assign(n.clone());
+
+    let (x1, y1) = curve.scalar_base_point(u1.as_ref());
+    let (x2, y2) = curve.scalar(&x, &y, u2.as_ref());
+    let (qx, qy) = curve.add(&x1, &y1, &x2, &y2);
+    if qx.is_nan() || qy.is_nan() || (qx.signnum() != Some(1) && qy.signnum() != Some(1)) {
+        return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The recovered public key is the infinite point"));
+    }
+
+    Ok(PublicKey { qx, qy })
+}
@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter};
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::elliptic::{CurveParams, EllipticCurve};
+use crate::prime::is_probably_prime;
+
+/// the Miller-Rabin rounds of the primality tests of the `p` and `n`
+const DOMAIN_PRIME_TEST_ROUNDS: usize = 20;
+/// SEC 1 v2 3.1.1.2.1, the `n` of the security level 80 has at least 160 bits
+const DOMAIN_MIN_ORDER_BITS: usize = 160;
+/// the MOV threshold of the SEC 1 v2 3.1.1.2.1
+const DOMAIN_MOV_THRESHOLD: usize = 100;
+
+/// The defect of the elliptic curve domain parameters which is found by the `CurveParams::validate_domain`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveDefect {
+    /// the field order `p` is not an odd prime greater than 3
+    FieldNotPrime,
+    /// the coefficient `b` is not in the range [1, p-1]
+    CoefficientZero,
+    /// the coefficient `a` is not in the range [0, p-1], or the discriminant $-16(4a^3 + 27b^2)$ is 0 modulo `p`
+    SingularCurve,
+    /// the base point is not in the range [0, p-1] or not on the curve
+    BasePointNotOnCurve,
+    /// the order `n` is not prime or less than $2^{160}$
+    OrderNotPrime,
+    /// $n \cdot G$ is not the infinite point
+    BasePointOrderMismatch,
+    /// the cofactor `h` is out of the range $[1, 2^{t/8}]$, or $h \cdot n$ is out of the Hasse bound
+    CofactorOutOfBound,
+    /// $n = p$, the discrete logarithm is solved by the Smart's attack
+    AnomalousCurve,
+    /// $p^B \equiv 1 \mod n$ for some $B \le 100$, the discrete logarithm is reduced by the MOV attack
+    SmallEmbeddingDegree,
+}
+
+impl Display for CurveDefect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            CurveDefect::FieldNotPrime => "the field order p is not an odd prime",
+            CurveDefect::CoefficientZero => "the coefficient b is not in the range [1, p-1]",
+            CurveDefect::SingularCurve => "the curve is singular",
+            CurveDefect::BasePointNotOnCurve => "the base point is not on the curve",
+            CurveDefect::OrderNotPrime => "the order n is not a prime of at least 160 bits",
+            CurveDefect::BasePointOrderMismatch => "the order of the base point is not n",
+            CurveDefect::CofactorOutOfBound => "the cofactor h is out of the bound",
+            CurveDefect::AnomalousCurve => "the curve is anomalous(n = p)",
+            CurveDefect::SmallEmbeddingDegree => "the embedding degree is not greater than 100",
+        };
+        write!(f, "Invalid curve domain parameters: {}", msg)
+    }
+}
+
+impl std::error::Error for CurveDefect {}
+
+impl CurveParams {
+    /// SEC 1 v2 3.1.1.2.1 elliptic curve domain parameters validation:
+    /// - `p` is an odd prime, $0 \le a < p$, $1 \le b < p$ and $4a^3 + 27b^2 \not\equiv 0 \mod p$;
+    /// - the base point `G` lies on the curve, `n` is a prime of at least 160 bits and $n \cdot G = O$;
+    /// - $1 \le h \le 2^{t/8}$ where $t = \lceil \log_2 p / 2 \rceil$, and $|p + 1 - h \cdot n| \le 2\sqrt{p}$(Hasse);
+    /// - $n \ne p$ and $p^B \not\equiv 1 \mod n$ for $1 \le B \le 100$.
+    ///
+    /// The built-in curves always pass the validation, the custom curves(`new_uncheck`) should be validated before
+    /// use, e.g. by the `ECDSA::new_custom_curve`.
+    pub fn validate_domain<R: IterSource<u32>>(&self, rd: &mut R) -> Result<(), CurveDefect> {
+        let (p, n, h, b) = (self.field_order(), self.base_point_order(), self.cofactor(), self.coefficient_b());
+        let (gx, gy) = self.base_point();
+        let (zero, one) = (BigInt::from(0u32), BigInt::from(1u32));
+        if [p, n, h, b, gx, gy].iter().any(|x| x.is_nan()) {
+            return Err(CurveDefect::FieldNotPrime);
+        }
+
+        if p <= &BigInt::from(3u32) || !is_probably_prime(p, DOMAIN_PRIME_TEST_ROUNDS, rd) {
+            return Err(CurveDefect::FieldNotPrime);
+        }
+
+        if b <= &zero || b >= p {
+            return Err(CurveDefect::CoefficientZero);
+        }
+
+        // the a = -3 is represented by the p - 3
+        let a = self.coefficient_a();
+        let mut disc = (a.sqr() * a.clone()) * BigInt::from(4u32) + b.sqr() * BigInt::from(27u32);
+        disc.rem_euclid_assign(p.clone());
+        if a < zero || a >= *p || disc == zero {
+            return Err(CurveDefect::SingularCurve);
+        }
+
+        if gx < &zero || gy < &zero || gx >= p || gy >= p || !self.is_on_curve(gx, gy) {
+            return Err(CurveDefect::BasePointNotOnCurve);
+        }
+
+        if n.bits_len() < DOMAIN_MIN_ORDER_BITS || !is_probably_prime(n, DOMAIN_PRIME_TEST_ROUNDS, rd) {
+            return Err(CurveDefect::OrderNotPrime);
+        }
+
+        let (nx, ny) = self.scalar(gx, gy, n.as_ref());
+        if nx != zero || ny != zero {
+            return Err(CurveDefect::BasePointOrderMismatch);
+        }
+
+        let t = (p.bits_len() + 1) >> 1;
+        let trace = p.clone() + one.clone() - h.clone() * n.clone();
+        if h < &one || h > &(one.clone() << (t >> 3)) || trace.sqr() > (p.clone() << 2) {
+            return Err(CurveDefect::CofactorOutOfBound);
+        }
+
+        if n == p {
+            return Err(CurveDefect::AnomalousCurve);
+        }
+
+        let mut q = p.deep_clone();
+        q.rem_euclid_assign(n.clone());
+        let mut qb = q.deep_clone();
+        for _ in 0..DOMAIN_MOV_THRESHOLD {
+            if qb == one {
+                return Err(CurveDefect::SmallEmbeddingDegree);
+            }
+            qb *= q.clone();
+            qb.rem_euclid_assign(n.clone());
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,76 @@
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::elliptic::{EllipticCurve, PrivateKey, PublicKey};
+use crate::dh::validate_ec_public_key;
+use crate::{CryptoError, CryptoErrorKind};
+
+/// The elliptic curve Diffie-Hellman key agreement
+/// NIST SP 800-56A Rev. 3 5.7.1.2(ECC CDH primitive)
+///
+/// the shared secret `Z` is the x-coordinate of the point $P = (h \cdot d \bmod n) \cdot Q$, where the `h` is the
+/// cofactor of the curve and the `Q` is the peer's public key, it's encoded as the big-endian bytes of the field
+/// element length. The peer's public key is validated(see `dh::validate_ec_public_point`) before the multiplication.
+/// The `Z` is not uniformly random, it should be passed to a key derivation function.
+pub struct ECDH<C> {
+    curve: C,
+    key: PrivateKey,
+}
+
+impl<C: EllipticCurve> ECDH<C> {
+    /// the private key `d` must be in the range [1, n-1]
+    pub fn new(curve: C, key: PrivateKey) -> Result<Self, CryptoError> {
+        let n = curve.curve_params().base_point_order();
+        let d = key.d.get();
+        if d.is_nan() || d.signnum() != Some(1) || &d >= n {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey,
+                format!("The ECDH private key must be in the range [1, n-1] of the {}", curve.curve_params().name())));
+        }
+
+        Ok(Self { curve, key })
+    }
+
+    /// generate the ephemeral or static key pair on the `curve`
+    pub fn generate_key<R: IterSource<u32>>(curve: C, rd: &mut R) -> Result<Self, CryptoError> {
+        let key = curve.curve_params().generate_key(rd)?;
+        Ok(Self { curve, key })
+    }
+
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.key
+    }
+
+    /// the public key which is sent to the peer
+    pub fn public_key(&self) -> &PublicKey {
+        self.key.public_key()
+    }
+
+    /// the length of the shared secret, i.e. the byte length of the field order `p`
+    pub fn shared_secret_len(&self) -> usize {
+        (self.curve.curve_params().field_order().bits_len() + 7) >> 3
+    }
+
+    /// compute the shared secret `Z` with the peer's public key, the invalid public key(not on the curve, not in the
+    /// subgroup of order `n`, the infinite point) and the infinite shared point return the `InvalidPublicKey` error.
+    pub fn shared_secret(&self, peer: &PublicKey) -> Result<Vec<u8>, CryptoError> {
+        validate_ec_public_key(&self.curve, peer)?;
+
+        let cp = self.curve.curve_params();
+        let mut k = cp.cofactor().clone() * self.key.d.get();
+        k.rem_euclid_assign(cp.base_point_order().clone());
+
+        let (zx, zy) = self.curve.scalar(&peer.qx, &peer.qy, k.as_ref());
+        let zero = BigInt::from(0u32);
+        if zx.is_nan() || zy.is_nan() || (zx == zero && zy == zero) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The ECDH shared point is the infinite point"));
+        }
+
+        let (z, len) = (zx.to_be_bytes(), self.shared_secret_len());
+        let mut buf = vec![0u8; len.saturating_sub(z.len())];
+        buf.extend_from_slice(z.as_slice());
+        Ok(buf)
+    }
+}
@@ -1,7 +1,9 @@
 use std::str::FromStr;
 use rmath::bigint::{BigInt, Nat};
 use rmath::rand::IterSource;
-use crate::elliptic::key_pair::{PrivateKey, PublicKey};
+use crate::elliptic::key_pair::{PrivateKey, PublicKey, FIPS_CURVES};
+use crate::validation::KeygenPath;
+use crate::numeric::{ct_mod_inverse, SecretScalar};
 use crate::{CryptoError, CryptoErrorKind};
 
 /// CurveParams contains the parameters of an elliptic curve
@@ -10,6 +12,10 @@ pub struct CurveParams {
     p: BigInt,
     // the order of the base point
     n: BigInt,
+    // the cofactor h = #E(GF(p)) / n
+    h: BigInt,
+    // the coefficient a of the curve equation, `None` means a = -3
+    a: Option<BigInt>,
     // the constant coefficient of the curve equation
     b: BigInt,
     // (gx, gy) of the base point
@@ -21,7 +27,19 @@ pub struct CurveParams {
     name: String,
 }
 
-/// A Curve represents a short-form Weierstrass curve with a=-3.  
+/// the domain parameters of the custom curve $y^2 \equiv x^3 + a\cdot x + b \mod p$ with the base point `(gx, gy)` of
+/// the order `n` and the cofactor `h`, see the `CurveParams::new_uncheck_with_a`
+pub struct CurveDomain<'a> {
+    pub p: &'a BigInt,
+    pub n: &'a BigInt,
+    pub h: &'a BigInt,
+    pub a: &'a BigInt,
+    pub b: &'a BigInt,
+    pub gx: &'a BigInt,
+    pub gy: &'a BigInt,
+}
+
+/// A Curve represents a short-form Weierstrass curve $y^2 = x^3 + ax + b$, the a is -3 except the Brainpool curves.  
 /// (0, 0) identifies the infinite point. 
 /// See https://www.hyperelliptic.org/EFD/g1p/auto-shortw.html
 pub trait EllipticCurve {
@@ -52,19 +70,9 @@ impl EllipticCurve for CurveParams {
         if x.is_nan() || y.is_nan() {
             return false;
         }
-        // y² = x³ - 3x + b
-        let (mut x3, mut y2) = (x.sqr(), y.sqr());
+        let mut y2 = y.sqr();
         y2.rem_euclid_assign(self.p.clone());
-        x3 *= x.clone();
-        
-        let mut three_x = x.clone() << 1;
-        three_x += x.clone();
-        
-        x3 -= three_x;
-        x3 += self.b.clone();
-        x3.rem_euclid_assign(self.p.clone());
-        
-        x3 == y2
+        self.curve_rhs(x) == y2
     }
 
     fn add(&self, x1: &BigInt, y1: &BigInt, x2: &BigInt, y2: &BigInt) -> (BigInt, BigInt) {
@@ -118,6 +126,11 @@ impl CurveParams {
         &self.n
     }
     
+    /// the cofactor of the curve, it's 1 for the FIPS 186-4 prime curves
+    pub fn cofactor(&self) -> &BigInt {
+        &self.h
+    }
+    
     pub fn field_bits_size(&self) -> usize {
         self.bit_size
     }
@@ -134,6 +147,45 @@ impl CurveParams {
         &self.b
     }
     
+    /// the coefficient a in the range [0, p-1]
+    pub(crate) fn coefficient_a(&self) -> BigInt {
+        match self.a.as_ref() {
+            Some(a) => a.deep_clone(),
+            None => self.p.clone() - BigInt::from(3u32),
+        }
+    }
+    
+    /// $x^3 + ax + b \mod p$
+    fn curve_rhs(&self, x: &BigInt) -> BigInt {
+        let mut rhs = x.sqr();
+        rhs *= x.clone();
+        match self.a.as_ref() {
+            Some(a) => rhs += a.clone() * x.clone(),
+            None => rhs -= (x.clone() << 1) + x.clone(),
+        }
+        rhs += self.b.clone();
+        rhs.rem_euclid_assign(self.p.clone());
+        rhs
+    }
+    
+    /// the y-coordinate of the point whose x-coordinate is `x` and the parity of the y is `is_odd`, `None` if the `x`
+    /// is not in the range [0, p-1] or there is no such point on the curve.
+    pub(crate) fn decompress_y(&self, x: &BigInt, is_odd: bool) -> Option<BigInt> {
+        if x.is_nan() || x.signnum() == Some(-1) || x >= &self.p {
+            return None;
+        }
+        
+        let y = crate::prime::sqrt_mod_prime(&self.curve_rhs(x), &self.p)?;
+        let y_is_odd = y.to_be_bytes().last().copied().unwrap_or(0) & 1 == 1;
+        if y_is_odd == is_odd {
+            Some(y)
+        } else if y.signnum() != Some(1) {
+            None
+        } else {
+            Some(self.p.clone() - y)
+        }
+    }
+    
     pub fn generate_key<R: IterSource<u32>>(&self, rd: &mut R) -> Result<PrivateKey, CryptoError> {
         const MASK: [u8;8] = [0xff, 0x1, 0x3, 0x7, 0xf, 0x1f, 0x3f, 0x7f];
         let bits_len = self.n.bits_len();
@@ -168,13 +220,55 @@ impl CurveParams {
                                 qx,
                                 qy,
                             },
-                            d: key,
+                            d: SecretScalar::new(&key),
                         }
                     )
                 }
             }
         }
     }
+    
+    /// generate the key pair by the FIPS 186-5 A.2.1(the key pair generation using the extra random bits) when the
+    /// `fips_mode` is true, otherwise by the `generate_key`, and return the key with the `KeygenPath` which the key is
+    /// generated by.
+    /// 
+    /// The FIPS path only accepts the FIPS 186-5 approved curves(the P-224, P-256, P-384, P-521 and the Brainpool
+    /// curves of the SP 800-186), the private key `d = (c mod (n-1)) + 1` where the `c` is the `len(n) + 64` random
+    /// bits, and the key is rejected if the pairwise consistency test fails.  
+    /// The `rd` must be the approved RBG(e.g. the `cipher_mode::CtrDrbg`) which supports the security strength of
+    /// the curve, it cannot be checked by the key generation.
+    pub fn generate_key_with_mode<R: IterSource<u32>>(&self, fips_mode: bool, rd: &mut R) -> Result<(PrivateKey, KeygenPath), CryptoError> {
+        if !fips_mode {
+            return self.generate_key(rd).map(|k| (k, KeygenPath::Default));
+        }
+        
+        if !FIPS_CURVES.contains(&self.name()) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+                format!("The {} is not the FIPS 186-5 approved curve", self.name())));
+        }
+        
+        let bits_len = self.n.bits_len() + 64;
+        let b_len = (bits_len + 7) >> 3;
+        let mut c = Vec::with_capacity(b_len + 3);
+        rd.iter_mut().take((b_len + 3) >> 2).for_each(|x| c.extend_from_slice(&x.to_be_bytes()));
+        c.resize(b_len, 0);
+        // the leading bits which exceed the `len(n) + 64` bits are masked off
+        if bits_len & 7 != 0 {
+            c[0] &= (1u8 << (bits_len & 7)) - 1;
+        }
+        let c = BigInt::from_be_bytes(c.as_slice());
+        let d = c.rem_euclid(self.n.clone() - BigInt::from(1u32)) + BigInt::from(1u32);
+        
+        let (qx, qy) = self.scalar_inner(&self.gx, &self.gy, d.to_be_bytes().as_slice());
+        let key = PrivateKey { pk: PublicKey { qx, qy }, d: SecretScalar::new(&d) };
+        let report = key.validate(self);
+        if report.is_fips_compliant() {
+            Ok((key, KeygenPath::FipsEcExtraRandomBits))
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::InnerErr, format!("The generated FIPS EC key is rejected: {}", report)))
+        }
+    }
+
 
     fn scalar_inner(&self, x: &BigInt, y: &BigInt, k: &[u8]) -> (BigInt, BigInt) {
         let z = BigInt::from(1u32);
@@ -213,18 +307,19 @@ impl CurveParams {
     fn affine_from_jacobian(&self, x: &BigInt, y: &BigInt, z: &BigInt) -> (BigInt, BigInt) {
         debug_assert!(!x.is_nan() && !y.is_nan() && !z.is_nan());
         
-        if z.signnum() == Some(0) {
-            (BigInt::from(0u32), BigInt::from(0u32))
-        } else {
-            let zinv = z.mod_inverse(self.p.clone());
-            let mut zinvsq = zinv.sqr();
-            let mut xout = x.clone() * zinvsq.clone();
-            xout.rem_euclid_assign(self.p.clone());
-            zinvsq *= zinv;
-            let mut yout = y.clone() * zinvsq;
-            yout.rem_euclid_assign(self.p.clone());
-            (xout, yout)
-        }
+        // the z of the point at the infinity is 0 modulo p, the z is secret in the scalar multiplication of the
+        // private key(e.g. the nonce of the signature)
+        let zinv = match ct_mod_inverse(z, &self.p) {
+            Ok(zinv) => zinv,
+            Err(_) => return (BigInt::from(0u32), BigInt::from(0u32)),
+        };
+        let mut zinvsq = zinv.sqr();
+        let mut xout = x.clone() * zinvsq.clone();
+        xout.rem_euclid_assign(self.p.clone());
+        zinvsq *= zinv;
+        let mut yout = y.clone() * zinvsq;
+        yout.rem_euclid_assign(self.p.clone());
+        (xout, yout)
     }
     
     fn add_jacobian(&self, x1: &BigInt, y1: &BigInt, z1: &BigInt, x2: &BigInt, y2: &BigInt, z2: &BigInt) -> (BigInt, BigInt, BigInt) {
@@ -295,6 +390,10 @@ impl CurveParams {
     
     
     fn double_jacobian(&self, x: &BigInt, y: &BigInt, z: &BigInt) -> (BigInt, BigInt, BigInt) {
+        if let Some(a) = self.a.as_ref() {
+            return self.double_jacobian_general_a(a, x, y, z);
+        }
+        
         // See https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-3.html#doubling-dbl-2001-b
         let (mut delta, mut gamma) = (z.sqr(), y.sqr());
         delta.rem_euclid_assign(self.p.clone());
@@ -351,6 +450,48 @@ impl CurveParams {
         (x3, y3, z3)
     }
 
+    fn double_jacobian_general_a(&self, a: &BigInt, x: &BigInt, y: &BigInt, z: &BigInt) -> (BigInt, BigInt, BigInt) {
+        // See https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-2007-bl
+        let p = &self.p;
+        let modp = |mut v: BigInt| { v.rem_euclid_assign(p.clone()); v };
+        let (xx, yy, zz) = (modp(x.sqr()), modp(y.sqr()), modp(z.sqr()));
+        let yyyy = modp(yy.sqr());
+        
+        // S = 2((X1 + YY)^2 - XX - YYYY), M = 3XX + a*ZZ^2, T = M^2 - 2S
+        let s = modp(((x.clone() + yy.clone()).sqr() - xx.clone() - yyyy.clone()) << 1);
+        let m = modp((xx.clone() << 1) + xx.clone() + a.clone() * zz.sqr());
+        let t = modp(m.sqr() - (s.clone() << 1));
+        
+        // Y3 = M(S - T) - 8YYYY, Z3 = (Y1 + Z1)^2 - YY - ZZ
+        let y3 = modp(m * (s - t.clone()) - (yyyy << 3));
+        let z3 = modp((y.clone() + z.clone()).sqr() - yy - zz);
+        (t, y3, z3)
+    }
+
+    /// the custom curve $y^2 \equiv x^3 - 3\cdot x + b \mod p$ with the base point `(gx, gy)` of the order `n` and the
+    /// cofactor `h`, the parameters are not checked, see the `validate_domain` before using them.
+    pub fn new_uncheck(name: &str, p: &BigInt, n: &BigInt, h: &BigInt, b: &BigInt, gx: &BigInt, gy: &BigInt) -> CurveParams {
+        CurveParams {
+            p: p.deep_clone(),
+            n: n.deep_clone(),
+            h: h.deep_clone(),
+            a: None,
+            b: b.deep_clone(),
+            gx: gx.deep_clone(),
+            gy: gy.deep_clone(),
+            bit_size: if p.is_nan() { 0 } else { p.bits_len() },
+            name: name.to_string(),
+        }
+    }
+    
+    /// the custom curve $y^2 \equiv x^3 + a\cdot x + b \mod p$ of the general coefficient `a`, the parameters are not
+    /// checked, see the `validate_domain` before using them.
+    pub fn new_uncheck_with_a(name: &str, domain: &CurveDomain) -> CurveParams {
+        let mut cp = Self::new_uncheck(name, domain.p, domain.n, domain.h, domain.b, domain.gx, domain.gy);
+        cp.a = Some(domain.a.deep_clone());
+        cp
+    }
+
     /// FIPS 186-4, D.1.2.3 P-224 Curve  
     /// GF(p), E: $y^2 \equiv x^3 - 3\cdot x + b \mod p$  
     /// p.bits_len() = 224
@@ -372,6 +513,8 @@ impl CurveParams {
              CurveParams {
                  p,
                  n,
+                 h: BigInt::from(1u32),
+                 a: None,
                  b,
                  gx,
                  gy,
@@ -402,6 +545,8 @@ impl CurveParams {
             CurveParams {
                 p,
                 n,
+                h: BigInt::from(1u32),
+                a: None,
                 b,
                 gx,
                 gy,
@@ -432,6 +577,8 @@ impl CurveParams {
             CurveParams {
                 p,
                 n,
+                h: BigInt::from(1u32),
+                a: None,
                 b,
                 gx,
                 gy,
@@ -441,7 +588,7 @@ impl CurveParams {
         )
     }
 
-    /// FIPS 186-4, D.1.2.5 P-512 Curve  
+    /// FIPS 186-4, D.1.2.5 P-521 Curve  
     /// GF(p), E: $y^2 \equiv x^3 - 3\cdot x + b \mod p$
     /// p.bits_len() = 521
     pub fn p521() -> Result<CurveParams, CryptoError>{
@@ -456,12 +603,179 @@ impl CurveParams {
         let gy = BigInt::from_str("0x11839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650")
             .or_else(|e| {Err(CryptoError::new(CryptoErrorKind::InnerErr, e))})?;
         let bit_size = 512;
-        let name = String::from("P-512");
+        let name = String::from("P-521");
+
+        Ok(
+            CurveParams {
+                p,
+                n,
+                h: BigInt::from(1u32),
+                a: None,
+                b,
+                gx,
+                gy,
+                bit_size,
+                name,
+            }
+        )
+    }
+
+    /// SEC 2 v2, 2.4.1 secp256k1 Curve  
+    /// GF(p), E: $y^2 \equiv x^3 + 7 \mod p$, $a = 0$  
+    /// p.bits_len() = 256
+    pub fn secp256k1() -> Result<CurveParams, CryptoError> {
+        let p = BigInt::from_str("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let b = BigInt::from(7u32);
+        let gx = BigInt::from_str("0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gy = BigInt::from_str("0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let n = BigInt::from_str("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let bit_size = 256;
+        let name = String::from("secp256k1");
+
+        Ok(
+            CurveParams {
+                p,
+                n,
+                h: BigInt::from(1u32),
+                a: Some(BigInt::from(0u32)),
+                b,
+                gx,
+                gy,
+                bit_size,
+                name,
+            }
+        )
+    }
+
+    /// RFC 5639, 3.4 brainpoolP256r1 Curve  
+    /// GF(p), E: $y^2 \equiv x^3 + a\cdot x + b \mod p$  
+    /// p.bits_len() = 256
+    pub fn brainpool_p256r1() -> Result<CurveParams, CryptoError> {
+        let p = BigInt::from_str("0xa9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let a = BigInt::from_str("0x7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let b = BigInt::from_str("0x26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gx = BigInt::from_str("0x8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gy = BigInt::from_str("0x547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let n = BigInt::from_str("0xa9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let bit_size = 256;
+        let name = String::from("brainpoolP256r1");
+
+        Ok(
+            CurveParams {
+                p,
+                n,
+                h: BigInt::from(1u32),
+                a: Some(a),
+                b,
+                gx,
+                gy,
+                bit_size,
+                name,
+            }
+        )
+    }
+
+    /// RFC 5639, 3.6 brainpoolP384r1 Curve  
+    /// GF(p), E: $y^2 \equiv x^3 + a\cdot x + b \mod p$  
+    /// p.bits_len() = 384
+    pub fn brainpool_p384r1() -> Result<CurveParams, CryptoError> {
+        let p = BigInt::from_str("0x8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b412b1da197fb71123acd3a729901d1a71874700133107ec53")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let a = BigInt::from_str("0x7bc382c63d8c150c3c72080ace05afa0c2bea28e4fb22787139165efba91f90f8aa5814a503ad4eb04a8c7dd22ce2826")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let b = BigInt::from_str("0x04a8c7dd22ce28268b39b55416f0447c2fb77de107dcd2a62e880ea53eeb62d57cb4390295dbc9943ab78696fa504c11")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gx = BigInt::from_str("0x1d1c64f068cf45ffa2a63a81b7c13f6b8847a3e77ef14fe3db7fcafe0cbd10e8e826e03436d646aaef87b2e247d4af1e")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gy = BigInt::from_str("0x8abe1d7520f9c2a45cb1eb8e95cfd55262b70b29feec5864e19c054ff99129280e4646217791811142820341263c5315")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let n = BigInt::from_str("0x8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b31f166e6cac0425a7cf3ab6af6b7fc3103b883202e9046565")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let bit_size = 384;
+        let name = String::from("brainpoolP384r1");
+
+        Ok(
+            CurveParams {
+                p,
+                n,
+                h: BigInt::from(1u32),
+                a: Some(a),
+                b,
+                gx,
+                gy,
+                bit_size,
+                name,
+            }
+        )
+    }
+
+    /// RFC 5639, 3.7 brainpoolP512r1 Curve  
+    /// GF(p), E: $y^2 \equiv x^3 + a\cdot x + b \mod p$  
+    /// p.bits_len() = 512
+    pub fn brainpool_p512r1() -> Result<CurveParams, CryptoError> {
+        let p = BigInt::from_str("0xaadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca703308717d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f3")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let a = BigInt::from_str("0x7830a3318b603b89e2327145ac234cc594cbdd8d3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94ca")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let b = BigInt::from_str("0x3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94cadc083e67984050b75ebae5dd2809bd638016f723")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gx = BigInt::from_str("0x81aee4bdd82ed9645a21322e9c4c6a9385ed9f70b5d916c1b43b62eef4d0098eff3b1f78e2d0d48d50d1687b93b97d5f7c6d5047406a5e688b352209bcb9f822")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gy = BigInt::from_str("0x7dde385d566332ecc0eabfa9cf7822fdf209f70024a57b1aa000c55b881f8111b2dcde494a5f485e5bca4bd88a2763aed1ca2b2fa8f0540678cd1e0f3ad80892")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let n = BigInt::from_str("0xaadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330870553e5c414ca92619418661197fac10471db1d381085ddaddb58796829ca90069")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let bit_size = 512;
+        let name = String::from("brainpoolP512r1");
+
+        Ok(
+            CurveParams {
+                p,
+                n,
+                h: BigInt::from(1u32),
+                a: Some(a),
+                b,
+                gx,
+                gy,
+                bit_size,
+                name,
+            }
+        )
+    }
+
+    /// GB/T 32918.5-2017, the SM2 recommended curve sm2p256v1  
+    /// GF(p), E: $y^2 \equiv x^3 + a\cdot x + b \mod p$, $a = p - 3$  
+    /// p.bits_len() = 256
+    pub fn sm2p256v1() -> Result<CurveParams, CryptoError> {
+        let p = BigInt::from_str("0xfffffffeffffffffffffffffffffffffffffffff00000000ffffffffffffffff")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let b = BigInt::from_str("0x28e9fa9e9d9f5e344d5a9e4bcf6509a7f39789f515ab8f92ddbcbd414d940e93")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gx = BigInt::from_str("0x32c4ae2c1f1981195f9904466a39c9948fe30bbff2660be1715a4589334c74c7")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let gy = BigInt::from_str("0xbc3736a2f4f6779c59bdcee36b692153d0a9877cc62a474002df32e52139f0a0")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let n = BigInt::from_str("0xfffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d54123")
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InnerErr, e))?;
+        let bit_size = 256;
+        let name = String::from("sm2p256v1");
 
         Ok(
             CurveParams {
                 p,
                 n,
+                h: BigInt::from(1u32),
+                a: None,
                 b,
                 gx,
                 gy,
@@ -477,6 +791,8 @@ impl Clone for CurveParams {
         Self {
             p: self.p.deep_clone(),
             n: self.n.deep_clone(),
+            h: self.h.deep_clone(),
+            a: self.a.as_ref().map(|a| a.deep_clone()),
             b: self.b.deep_clone(),
             gx: self.gx.deep_clone(),
             gy: self.gy.deep_clone(),
@@ -1,5 +1,5 @@
 use std::str::FromStr;
-use crate::elliptic::{CurveP224, EllipticCurve, CurveP256};
+use crate::elliptic::{CurveP224, EllipticCurve, CurveP256, CurveOracle, CurveParams, CurveDomain, ECDH, PublicKey, PrivateKey};
 use rmath::bigint::{BigInt, Nat};
 
 #[test]
@@ -428,3 +428,223 @@ fn elliptic_combined_mult() {
     assert_eq!(x, bzero, "1×G + (-1)×G = ({}, {}), should be ∞", x, y);
     assert_eq!(y, bzero, "1×G + (-1)×G = ({}, {}), should be ∞", x, y);
 }
+
+#[test]
+fn elliptic_key_validate() {
+    let p256 = CurveP256::new().unwrap();
+    let seed = rmath::rand::DefaultSeed::<u32>::new().unwrap();
+    let mut rd = rmath::rand::CryptoRand::new(&seed).unwrap();
+    let sk = p256.curve_params().generate_key(&mut rd).unwrap();
+    
+    let report = sk.validate(&p256);
+    assert!(report.is_fips_compliant(), "{}", report);
+    assert!(sk.public_key().validate(&p256).is_fips_compliant());
+    
+    let p224 = CurveP224::new().unwrap();
+    let report = sk.validate(&p224);
+    assert!(!report.is_valid());
+    assert!(report.failures().any(|c| c.name == "public point"));
+    
+    let d = sk.d.get() + BigInt::from(1u32);
+    let sk2 = crate::elliptic::PrivateKey::new_uncheck(sk.public_key().clone(), &d);
+    let report = sk2.validate(&p256);
+    assert_eq!(report.failures().map(|c| c.name).collect::<Vec<_>>(), vec!["key pair consistency"]);
+    
+    let n = p256.curve_params().base_point_order().clone();
+    let sk2 = crate::elliptic::PrivateKey::new_uncheck(sk.public_key().clone(), &n);
+    assert!(sk2.validate(&p256).failures().any(|c| c.name == "private key"));
+}
+
+#[test]
+fn elliptic_oracle_edge_cases() {
+    let (p224, p256) = (CurveP224::new().unwrap(), CurveP256::new().unwrap());
+    
+    let mut oracle = CurveOracle::new(&p224);
+    oracle.check_edge_cases().unwrap_or_else(|e| panic!("P-224 diverged: {}", e));
+    assert!(oracle.checks() > 0);
+    
+    let mut oracle = CurveOracle::new(&p256);
+    oracle.check_edge_cases().unwrap_or_else(|e| panic!("P-256 diverged: {}", e));
+}
+
+#[test]
+fn elliptic_oracle_random() {
+    let seed = rmath::rand::DefaultSeed::<u32>::new().unwrap();
+    let mut rd = rmath::rand::CryptoRand::new(&seed).unwrap();
+    let (p224, p256) = (CurveP224::new().unwrap(), CurveP256::new().unwrap());
+    
+    CurveOracle::new(&p224).check_random(&mut rd, 4).unwrap_or_else(|e| panic!("P-224 diverged: {}", e));
+    CurveOracle::new(&p256).check_random(&mut rd, 4).unwrap_or_else(|e| panic!("P-256 diverged: {}", e));
+}
+
+/// the long-running soak test: `RCRYPTO_EC_SOAK_ROUNDS=10000 cargo test --release elliptic_oracle_soak -- --ignored`
+#[test]
+#[ignore]
+fn elliptic_oracle_soak() {
+    let rounds = std::env::var("RCRYPTO_EC_SOAK_ROUNDS").ok().and_then(|x| x.parse().ok()).unwrap_or(1000usize);
+    let seed = rmath::rand::DefaultSeed::<u32>::new().unwrap();
+    let mut rd = rmath::rand::CryptoRand::new(&seed).unwrap();
+    let (p224, p256) = (CurveP224::new().unwrap(), CurveP256::new().unwrap());
+    
+    let mut oracle = CurveOracle::new(&p224);
+    oracle.check_random(&mut rd, rounds).unwrap_or_else(|e| panic!("P-224 diverged: {}", e));
+    let mut oracle = CurveOracle::new(&p256);
+    oracle.check_random(&mut rd, rounds).unwrap_or_else(|e| panic!("P-256 diverged: {}", e));
+}
+
+
+#[test]
+fn ecdh_shared_secret() {
+    // NIST CAVS 14.1 KAS ECC CDH primitive, P-256 COUNT = 0
+    let h = |s: &str| BigInt::from_str(s).unwrap();
+    let peer = PublicKey::new_uncheck(&h("0x700c48f77f56584c5cc632ca65640db91b6bacce3a4df6b42ce7cc838833d287"),
+        &h("0xdb71e509e3fd9b060ddb20ba5c51dcc5948d46fbf640dfe0441782cab85fa4ac"));
+    let pk = PublicKey::new_uncheck(&h("0xead218590119e8876b29146ff89ca61770c4edbbf97d38ce385ed281d8a6b230"),
+        &h("0x28af61281fd35e2fa7002523acc85a429cb06ee6648325389f59edfce1405141"));
+    let sk = PrivateKey::new_uncheck(pk, &h("0x7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534"));
+    let z = h("0x46fc62106420ff012e54a434fbdd2d25ccc5852060561e68040dd7778997bd7b").to_be_bytes();
+    assert_eq!(z, ECDH::new(CurveP256::new().unwrap(), sk.clone()).unwrap().shared_secret(&peer).unwrap());
+    assert_eq!(z, ECDH::new(CurveParams::p256().unwrap(), sk).unwrap().shared_secret(&peer).unwrap());
+
+    let mut rd = rmath::rand::CryptoRand::new(&rmath::rand::DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let curves = [CurveParams::p224().unwrap(), CurveParams::p256().unwrap(), CurveParams::p384().unwrap(), CurveParams::p521().unwrap()];
+    for (curve, len) in curves.iter().zip([28usize, 32, 48, 66].iter()) {
+        let alice = ECDH::generate_key(curve.clone(), &mut rd).unwrap();
+        let bob = ECDH::generate_key(curve.clone(), &mut rd).unwrap();
+        let z = alice.shared_secret(bob.public_key()).unwrap();
+        assert_eq!(z.len(), *len);
+        assert_eq!(z, bob.shared_secret(alice.public_key()).unwrap(), "{}", curve.name());
+
+        let (x, y) = (&bob.public_key().qx, &bob.public_key().qy);
+        for invalid in [PublicKey::new_uncheck(x, &(y.clone() + BigInt::from(1u32))),
+            PublicKey::new_uncheck(&BigInt::from(0u32), &BigInt::from(0u32))].iter() {
+            assert_eq!(alice.shared_secret(invalid).unwrap_err().kind(), crate::CryptoErrorKind::InvalidPublicKey);
+        }
+    }
+
+    let p224 = CurveP224::new().unwrap();
+    let n = p224.curve_params().base_point_order().clone();
+    for d in [BigInt::from(0u32), n].iter() {
+        let sk = PrivateKey::new_uncheck(PublicKey::new_uncheck(&BigInt::from(1u32), &BigInt::from(1u32)), d);
+        assert_eq!(ECDH::new(p224.clone(), sk).err().unwrap().kind(), crate::CryptoErrorKind::InvalidPrivateKey);
+    }
+}
+
+#[test]
+fn scalar_field_inversion() {
+    use crate::elliptic::ScalarField;
+    let mut rd = rmath::rand::CryptoRand::new(&rmath::rand::DefaultSeed::<u32>::new().unwrap()).unwrap();
+    
+    for cp in [CurveParams::p224().unwrap(), CurveParams::p256().unwrap(), CurveParams::p384().unwrap()].iter() {
+        let n = cp.base_point_order();
+        let f = ScalarField::new(n).unwrap();
+        let one = BigInt::from(1u32);
+        let mut ks = vec![one.clone(), BigInt::from(2u32), n.clone() - one.clone(), n.clone() - BigInt::from(2u32)];
+        for _ in 0..32 {
+            ks.push(cp.generate_key(&mut rd).unwrap().d.get());
+        }
+        
+        for k in ks.iter() {
+            let kinv = f.invert(k).unwrap();
+            assert_eq!(kinv, k.mod_inverse(n.deep_clone()), "{}: {:#x}", cp.name(), k);
+            let mut x = kinv * k.clone();
+            x.rem_euclid_assign(n.deep_clone());
+            assert_eq!(x, one);
+        }
+    }
+    
+    let f = ScalarField::new(&BigInt::from(0xfffffffbu32)).unwrap();
+    assert_eq!(f.invert(&BigInt::from(2u32)).unwrap(), BigInt::from(0x7ffffffeu32));
+    assert!(f.invert(&BigInt::from(0u32)).is_err() && f.invert(&BigInt::from(0xfffffffbu32)).is_err());
+    assert!(ScalarField::new(&(BigInt::from(1u32) << 200usize)).is_none());
+    assert!(ScalarField::new(&BigInt::from(1u32)).is_none());
+}
+
+#[test]
+fn elliptic_brainpool() {
+    // RFC 6932 2.1-2.3 the ECDH test vectors of the brainpoolP256r1/P384r1/P512r1
+    let h = |s: &str| BigInt::from_str(s).unwrap();
+    let cases = [
+        (CurveParams::brainpool_p256r1().unwrap(),
+            "0x81db1ee100150ff2ea338d708271be38300cb54241d79950f77b063039804f1d",
+            ("0x44106e913f92bc02a1705d9953a8414db95e1aaa49e81d9e85f929a8e3100be5", "0x8ab4846f11caccb73ce49cbdd120f5a900a69fd32c272223f789ef10eb089bdc"),
+            "0x55e40bc41e37e3e2ad25c3c6654511ffa8474a91a0032087593852d3e7d76bd3",
+            ("0x8d2d688c6cf93e1160ad04cc4429117dc2c41825e1e9fca0addd34e6f1b39f7b", "0x990c57520812be512641e47034832106bc7d3e8dd0e4c7f1136d7006547cec6a"),
+            "0x89afc39d41d3b327814b80940b042590f96556ec91e6ae7939bce31f3a18bf2b"),
+        (CurveParams::brainpool_p384r1().unwrap(),
+            "0x1e20f5e048a5886f1f157c74e91bde2b98c8b52d58e5003d57053fc4b0bd65d6f15eb5d1ee1610df870795143627d042",
+            ("0x68b665dd91c195800650cdd363c625f4e742e8134667b767b1b476793588f885ab698c852d4a6e77a252d6380fcaf068", "0x55bc91a39c9ec01dee36017b7d673a931236d2f1f5c83942d049e3fa20607493e0d038ff2fd30c2ab67d15c85f7faa59"),
+            "0x032640bc6003c59260f7250c3db58ce647f98e1260acce4acda3dd869f74e01f8ba5e0324309db6a9831497abac96670",
+            ("0x4d44326f269a597a5b58bba565da5556ed7fd9a8a9eb76c25f46db69d19dc8ce6ad18e404b15738b2086df37e71d1eb4", "0x62d692136de56cbe93bf5fa3188ef58bc8a3a0ec6c1e151a21038a42e9185329b5b275903d192f8d4e1f32fe9cc78c48"),
+            "0x0bd9d3a7ea0b3d519d09d8e48d0785fb744a6b355e6304bc51c229fbbce239bbadf6403715c35d4fb2a5444f575d4f42"),
+        (CurveParams::brainpool_p512r1().unwrap(),
+            "0x16302ff0dbbb5a8d733dab7141c1b45acbc8715939677f6a56850a38bd87bd59b09e80279609ff333eb9d4c061231fb26f92eeb04982a5f1d1764cad57665422",
+            ("0x0a420517e406aac0acdce90fcd71487718d3b953efd7fbec5f7f27e28c6149999397e91e029e06457db2d3e640668b392c2a7e737a7f0bf04436d11640fd09fd", "0x72e6882e8db28aad36237cd25d580db23783961c8dc52dfa2ec138ad472a0fcef3887cf62b623b2a87de5c588301ea3e5fc269b373b60724f5e82a6ad147fde7"),
+            "0x230e18e1bcc88a362fa54e4ea3902009292f7f8033624fd471b5d8ace49d12cfabbc19963dab8e2f1eba00bffb29e4d72d13f2224562f405cb80503666b25429",
+            ("0x9d45f66de5d67e2e6db6e93a59ce0bb48106097ff78a081de781cdb31fce8ccbaaea8dd4320c4119f1e9cd437a2eab3731fa9668ab268d871deda55a5473199f", "0x2fdc313095bcdd5fb3a91636f07a959c8e86b5636a1e930e8396049cb481961d365cc11453a06c719835475b12cb52fc3c383bce35e27ef194512b71876285fa"),
+            "0xa7927098655f1f9976fa50a9d566865dc530331846381c87256baf3226244b76d36403c024d7bbf0aa0803eaff405d3d24f11a9b5c0bef679fe1454b21c4cd1f"),
+    ];
+    
+    let mut rd = rmath::rand::CryptoRand::new(&rmath::rand::DefaultSeed::<u32>::new().unwrap()).unwrap();
+    for (curve, da, qa, db, qb, z) in cases.iter() {
+        assert_eq!(curve.validate_domain(&mut rd), Ok(()), "{}", curve.name());
+        let (qa, qb) = (PublicKey::new_uncheck(&h(qa.0), &h(qa.1)), PublicKey::new_uncheck(&h(qb.0), &h(qb.1)));
+        assert!(curve.is_on_curve(&qa.qx, &qa.qy) && curve.is_on_curve(&qb.qx, &qb.qy), "{}", curve.name());
+        assert!(!curve.is_on_curve(&qa.qx, &qb.qy), "{}", curve.name());
+        
+        let (x, y) = curve.scalar_base_point(h(da).as_ref());
+        assert_eq!((&x, &y), (&qa.qx, &qa.qy), "{}", curve.name());
+        let (x, y) = curve.scalar_base_point(h(db).as_ref());
+        assert_eq!((&x, &y), (&qb.qx, &qb.qy), "{}", curve.name());
+        
+        let (alice, bob) = (PrivateKey::new_uncheck(qa.clone(), &h(da)), PrivateKey::new_uncheck(qb.clone(), &h(db)));
+        let z = h(z).to_be_bytes();
+        let mut zz = vec![0u8; ((curve.field_bits_size() + 7) >> 3) - z.len()];
+        zz.extend_from_slice(z.as_slice());
+        assert_eq!(ECDH::new(curve.clone(), alice).unwrap().shared_secret(&qb).unwrap(), zz, "{}", curve.name());
+        assert_eq!(ECDH::new(curve.clone(), bob).unwrap().shared_secret(&qa).unwrap(), zz, "{}", curve.name());
+        
+        // the same curve by the general coefficient a
+        let (gx, gy) = curve.base_point();
+        let a = curve.coefficient_a();
+        let custom = CurveParams::new_uncheck_with_a("custom", &CurveDomain {
+            p: curve.field_order(), n: curve.base_point_order(), h: curve.cofactor(), a: &a, b: curve.coefficient_b(), gx, gy,
+        });
+        let (x, y) = custom.scalar_base_point(h(da).as_ref());
+        assert_eq!((&x, &y), (&qa.qx, &qa.qy), "{}", curve.name());
+        assert_eq!(custom.double(&x, &y), curve.add(&x, &y, &x, &y), "{}", curve.name());
+    }
+    
+    let cp = CurveParams::brainpool_p256r1().unwrap();
+    let (gx, gy) = cp.base_point();
+    let (a, b) = (cp.field_order().clone() - BigInt::from(3u32), BigInt::from(2u32));
+    let singular = CurveParams::new_uncheck_with_a("singular", &CurveDomain {
+        p: cp.field_order(), n: cp.base_point_order(), h: cp.cofactor(), a: &a, b: &b, gx, gy,
+    });
+    assert_eq!(singular.validate_domain(&mut rd), Err(crate::elliptic::CurveDefect::SingularCurve));
+}
+#[test]
+fn elliptic_fips_keygen() {
+    use crate::validation::KeygenPath;
+    
+    let seed = rmath::rand::DefaultSeed::<u32>::new().unwrap();
+    let mut rd = rmath::rand::CryptoRand::new(&seed).unwrap();
+    let p256 = CurveP256::new().unwrap();
+    
+    for cp in [CurveParams::p224().unwrap(), CurveParams::p521().unwrap(), CurveParams::brainpool_p256r1().unwrap()].iter() {
+        let (sk, path) = cp.generate_key_with_mode(true, &mut rd).unwrap();
+        assert_eq!(path, KeygenPath::FipsEcExtraRandomBits);
+        let report = sk.validate(cp);
+        assert!(report.is_fips_compliant(), "{}", report);
+    }
+    let (sk, path) = p256.curve_params().generate_key_with_mode(true, &mut rd).unwrap();
+    assert!(path.is_fips() && sk.validate(&p256).is_fips_compliant());
+    
+    // the SM2 curve is not the FIPS 186-5 approved curve
+    let sm2 = CurveParams::sm2p256v1().unwrap();
+    assert!(sm2.generate_key_with_mode(true, &mut rd).is_err());
+    let (sk, path) = sm2.generate_key_with_mode(false, &mut rd).unwrap();
+    assert_eq!(path, KeygenPath::Default);
+    let report = sk.validate(&sm2);
+    assert!(report.is_valid() && !report.is_fips_compliant(), "{}", report);
+}
\ No newline at end of file
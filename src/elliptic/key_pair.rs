@@ -1,5 +1,8 @@
 use rmath::bigint::BigInt;
 use std::fmt::{Display, Formatter, Debug};
+use crate::elliptic::EllipticCurve;
+use crate::validation::{ValidationReport, CheckCategory};
+use crate::numeric::SecretScalar;
 
 pub struct PublicKey {
     // public key Q: (qx,qy)
@@ -9,12 +12,14 @@ pub struct PublicKey {
 
 pub struct PrivateKey {
     pub(crate) pk: PublicKey,
-    pub(crate) d: BigInt,
+    pub(crate) d: SecretScalar,
 }
 
-pub struct KeyPair {
-    pub_key: Option<PublicKey>,
-    pri_key: Option<PrivateKey>,
+/// The elliptic curve key material, the verification is always available, the signing needs the `Full` key pair.
+#[derive(Clone)]
+pub enum KeyPair {
+    PublicOnly(PublicKey),
+    Full(PrivateKey),
 }
 
 impl Clone for PublicKey {
@@ -52,41 +57,100 @@ impl PrivateKey {
     pub fn new_uncheck(pk: PublicKey, d: &BigInt) -> Self {
         Self {
             pk,
-            d: d.deep_clone(),
+            d: SecretScalar::new(d),
         }
     }
+    
+    /// export the private key d(the minimal big-endian bytes) to the locked memory
+    #[cfg(feature = "mlock")]
+    pub fn to_secret_bytes(&self) -> Result<crate::secret::SecretBytes, crate::CryptoError> {
+        self.d.to_secret_bytes()
+    }
+}
+
+/// the FIPS 186-5 approved curves of the SP 800-186 3.2.1 and 3.2.3(the Brainpool curves)
+pub(super) const FIPS_CURVES: [&str; 7] = ["P-224", "P-256", "P-384", "P-521", "brainpoolP256r1", "brainpoolP384r1", "brainpoolP512r1"];
+
+impl PublicKey {
+    fn validate_inner<C: EllipticCurve>(&self, curve: &C, report: &mut ValidationReport) -> bool {
+        let is_valid = match crate::dh::validate_ec_public_point(curve, &self.qx, &self.qy) {
+            Ok(_) => report.check("public point", CheckCategory::Parameter, true, ""),
+            Err(e) => report.check("public point", CheckCategory::Parameter, false, format!("{}", e).as_str()),
+        };
+        
+        let name = curve.curve_params().name();
+        report.check("curve", CheckCategory::Fips, FIPS_CURVES.contains(&name),
+            format!("the {} is not the FIPS 186-5 approved curve", name).as_str());
+        is_valid
+    }
+    
+    /// validate the public key on the `curve`: the ECC full public key validation(see `dh::validate_ec_public_point`) 
+    /// and the FIPS 186-5 approved curves of the SP 800-186.
+    pub fn validate<C: EllipticCurve>(&self, curve: &C) -> ValidationReport {
+        let mut report = ValidationReport::new(format!("EC {} public key", curve.curve_params().name()).as_str(), false);
+        self.validate_inner(curve, &mut report);
+        report.skip("key pair consistency", CheckCategory::Pairwise, "the private key is absent");
+        report
+    }
+}
+
+impl PrivateKey {
+    /// validate the private key on the `curve`: the public key checks, the range of the private key d, and
+    /// the pairwise consistency check Q = d * G.
+    pub fn validate<C: EllipticCurve>(&self, curve: &C) -> ValidationReport {
+        let mut report = ValidationReport::new(format!("EC {} private key", curve.curve_params().name()).as_str(), true);
+        self.pk.validate_inner(curve, &mut report);
+        
+        let n = curve.curve_params().base_point_order();
+        let d = self.d.get();
+        let is_range = !d.is_nan() && d.signnum() == Some(1) && &d < n;
+        report.check("private key", CheckCategory::Parameter, is_range, "the private key d must be in [1, n-1]");
+        
+        let is_consistent = is_range && {
+            let (x, y) = curve.scalar_base_point(d.as_ref());
+            x == self.pk.qx && y == self.pk.qy
+        };
+        report.check("key pair consistency", CheckCategory::Pairwise, is_consistent, "the public point doesn't equal to d * G");
+        report
+    }
 }
 
 impl From<PrivateKey> for KeyPair {
     fn from(pk: PrivateKey) -> Self {
-        Self {
-            pub_key: None,
-            pri_key: Some(pk),
-        }
+        KeyPair::Full(pk)
     }
 }
 
 
 impl From<PublicKey> for KeyPair {
     fn from(pk: PublicKey) -> Self {
-        Self {
-            pub_key: Some(pk),
-            pri_key: None,
-        }
+        KeyPair::PublicOnly(pk)
     }
 }
 
 impl KeyPair {
     pub fn public_key(&self) -> &PublicKey {
-        if self.pri_key.is_some() {
-            self.pri_key.as_ref().unwrap().public_key()
-        } else {
-            self.pub_key.as_ref().unwrap()
+        match self {
+            KeyPair::PublicOnly(pk) => pk,
+            KeyPair::Full(sk) => sk.public_key(),
         }
     }
     
     pub fn private_key(&self) -> Option<&PrivateKey> {
-        self.pri_key.as_ref()
+        match self {
+            KeyPair::PublicOnly(_) => None,
+            KeyPair::Full(sk) => Some(sk),
+        }
+    }
+    
+    /// the private key is present, so the key pair can be used by the signing
+    pub fn can_sign(&self) -> bool {
+        self.private_key().is_some()
+    }
+    
+    /// the elliptic curve key pair is only used by the signature here, it always returns `false`
+    pub fn can_decrypt(&self) -> bool {
+        false
     }
 }
 
@@ -108,7 +172,7 @@ impl Debug for PublicKey {
 impl Display for PrivateKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let pk = format!("{}", self.pk);
-        let d = format!("{:#x}", self.d);
+        let d = format!("{:#x}", self.d.get());
         
         write!(f, "{{d: \"{}\", {}}}", d, pk)
     }
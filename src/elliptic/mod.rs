@@ -2,11 +2,15 @@
 //! [DSS数字签名标准](https://www.cnblogs.com/mengsuenyan/p/13818607.html)
 //!
 //! FIPS 186-4   
-//! FIPS 186-5
+//! FIPS 186-5  
+//! NIST SP 800-56A Rev. 3(ECDH)
 //! 
 
 mod elliptic;
-pub use elliptic::{CurveParams, EllipticCurve};
+pub use elliptic::{CurveParams, CurveDomain, EllipticCurve};
+
+mod domain;
+pub use domain::CurveDefect;
 
 mod key_pair;
 pub use key_pair::{PublicKey, PrivateKey, KeyPair};
@@ -18,5 +22,14 @@ pub use p224::{CurveP224};
 mod p256;
 pub use p256::{CurveP256};
 
+mod scalar;
+pub(crate) use scalar::ScalarField;
+
+mod ecdh;
+pub use ecdh::ECDH;
+
+mod oracle;
+pub use oracle::{CurveOracle, Divergence};
+
 #[cfg(test)]
 mod elliptic_test;
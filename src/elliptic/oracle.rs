@@ -0,0 +1,185 @@
+//! The differential testing of the fast curve implementations(e.g. `CurveP224`, `CurveP256`) against the
+//! generic `CurveParams` implementation.
+//!
+//! The fast curves are the ports of the optimized field arithmetic, the `CurveOracle` runs the same operation
+//! on both the fast curve and the generic `BigInt` path, and reports the first divergence.
+
+use std::fmt::{Display, Formatter};
+use rmath::bigint::{BigInt, Nat};
+use rmath::rand::IterSource;
+use crate::elliptic::{CurveParams, EllipticCurve};
+
+/// The inputs and the different outputs of the diverged operation
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    /// the operation name: `add`, `double`, `scalar`, `scalar_base_point`, `is_on_curve`
+    pub op: &'static str,
+    pub inputs: Vec<BigInt>,
+    /// the output of the fast curve
+    pub fast: (BigInt, BigInt),
+    /// the output of the generic `CurveParams`
+    pub generic: (BigInt, BigInt),
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.op)?;
+        for (i, x) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:#x}", x)?;
+        }
+        write!(f, "): fast=({:#x}, {:#x}), generic=({:#x}, {:#x})", self.fast.0, self.fast.1, self.generic.0, self.generic.1)
+    }
+}
+
+/// The oracle which compares the fast curve `C` with its `CurveParams`
+pub struct CurveOracle<'a, C> {
+    fast: &'a C,
+    // the number of the checked operations
+    checks: usize,
+}
+
+impl<'a, C: EllipticCurve> CurveOracle<'a, C> {
+    pub fn new(fast: &'a C) -> Self {
+        Self { fast, checks: 0 }
+    }
+
+    /// the number of the checked operations
+    pub fn checks(&self) -> usize {
+        self.checks
+    }
+
+    fn generic(&self) -> &'a CurveParams {
+        self.fast.curve_params()
+    }
+
+    fn compare(&mut self, op: &'static str, inputs: &[&BigInt], fast: (BigInt, BigInt), generic: (BigInt, BigInt)) -> Result<(), Divergence> {
+        self.checks += 1;
+        if fast == generic {
+            Ok(())
+        } else {
+            Err(Divergence { op, inputs: inputs.iter().map(|&x| x.deep_clone()).collect(), fast, generic })
+        }
+    }
+
+    pub fn check_is_on_curve(&mut self, x: &BigInt, y: &BigInt) -> Result<(), Divergence> {
+        let as_pair = |b: bool| (BigInt::from(b as u32), BigInt::from(0u32));
+        let (fast, generic) = (self.fast.is_on_curve(x, y), self.generic().is_on_curve(x, y));
+        self.compare("is_on_curve", &[x, y], as_pair(fast), as_pair(generic))
+    }
+
+    pub fn check_add(&mut self, x1: &BigInt, y1: &BigInt, x2: &BigInt, y2: &BigInt) -> Result<(), Divergence> {
+        let (fast, generic) = (self.fast.add(x1, y1, x2, y2), self.generic().add(x1, y1, x2, y2));
+        self.compare("add", &[x1, y1, x2, y2], fast, generic)
+    }
+
+    pub fn check_double(&mut self, x: &BigInt, y: &BigInt) -> Result<(), Divergence> {
+        let (fast, generic) = (self.fast.double(x, y), self.generic().double(x, y));
+        self.compare("double", &[x, y], fast, generic)
+    }
+
+    pub fn check_scalar(&mut self, x: &BigInt, y: &BigInt, k: &Nat) -> Result<(), Divergence> {
+        let (fast, generic) = (self.fast.scalar(x, y, k), self.generic().scalar(x, y, k));
+        self.compare("scalar", &[x, y, &BigInt::from(k.clone())], fast, generic)
+    }
+
+    pub fn check_scalar_base_point(&mut self, k: &Nat) -> Result<(), Divergence> {
+        let (fast, generic) = (self.fast.scalar_base_point(k), self.generic().scalar_base_point(k));
+        self.compare("scalar_base_point", &[&BigInt::from(k.clone())], fast, generic)
+    }
+
+    /// the point `(x, p - y)`
+    fn negate(&self, x: &BigInt, y: &BigInt) -> (BigInt, BigInt) {
+        if x.signnum() == Some(0) && y.signnum() == Some(0) {
+            (x.deep_clone(), y.deep_clone())
+        } else {
+            (x.deep_clone(), self.generic().field_order().clone() - y.clone())
+        }
+    }
+
+    /// check all operations with the point `(x, y)` and the scalar `k`
+    fn check_point(&mut self, x: &BigInt, y: &BigInt, k: &Nat) -> Result<(), Divergence> {
+        let (gx, gy) = self.generic().base_point();
+        let (gx, gy) = (gx.deep_clone(), gy.deep_clone());
+        let (nx, ny) = self.negate(x, y);
+        let (zero, one) = (Nat::from(0u32), Nat::from(1u32));
+        let n = self.generic().base_point_order().as_ref().clone();
+
+        self.check_is_on_curve(x, y)?;
+        self.check_double(x, y)?;
+        self.check_add(x, y, &gx, &gy)?;
+        // P + P
+        self.check_add(x, y, x, y)?;
+        // P + (-P)
+        self.check_add(x, y, &nx, &ny)?;
+        self.check_add(&nx, &ny, x, y)?;
+        self.check_scalar(x, y, k)?;
+        self.check_scalar(x, y, &zero)?;
+        self.check_scalar(x, y, &one)?;
+        // k ≥ n
+        self.check_scalar(x, y, &n)?;
+        self.check_scalar(x, y, &(n.clone() + k.clone()))?;
+        self.check_scalar(x, y, &(n - 1u32))
+    }
+
+    /// check the edge cases: the infinity point `(0, 0)`, `P + (-P)`, `P + P`, the scalar `0`, `n-1`, `n`, `n+1`, `2n`
+    /// and the scalar which is longer than the `n`.
+    pub fn check_edge_cases(&mut self) -> Result<(), Divergence> {
+        let (gx, gy) = self.generic().base_point();
+        let (gx, gy) = (gx.deep_clone(), gy.deep_clone());
+        let inf = BigInt::from(0u32);
+        let n = self.generic().base_point_order().as_ref().clone();
+        let ks = [
+            Nat::from(0u32), Nat::from(1u32), Nat::from(2u32), Nat::from(3u32),
+            n.clone() - 1u32, n.clone(), n.clone() + 1u32, n.clone() << 1,
+            (Nat::from(1u32) << (n.bits_len() + 8)) - 1u32,
+        ];
+
+        self.check_is_on_curve(&inf, &inf)?;
+        self.check_double(&inf, &inf)?;
+        self.check_add(&inf, &inf, &inf, &inf)?;
+        self.check_add(&gx, &gy, &inf, &inf)?;
+        self.check_add(&inf, &inf, &gx, &gy)?;
+        for k in ks.iter() {
+            self.check_scalar_base_point(k)?;
+            self.check_scalar(&gx, &gy, k)?;
+            self.check_scalar(&inf, &inf, k)?;
+        }
+
+        let k = Nat::from(0x5a5au32);
+        self.check_point(&gx, &gy, &k)?;
+        for k in ks.iter().skip(1) {
+            let (x, y) = self.generic().scalar_base_point(k);
+            self.check_point(&x, &y, &k.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// the random scalar in the range $[0, 2^{bits(n) + 8})$, so that the scalar may be great than the `n`
+    fn random_scalar<R: IterSource<u32>>(&self, rd: &mut R) -> Nat {
+        let len = (self.generic().base_point_order().bits_len() + 8 + 7) >> 3;
+        let mut k = Vec::with_capacity(len + 3);
+        rd.iter_mut().take((len + 3) >> 2).for_each(|x| k.extend_from_slice(x.to_be_bytes().as_ref()));
+        k.truncate(len);
+        Nat::from_be_bytes(k.as_slice())
+    }
+
+    /// check the `rounds` random points and scalars, the points are computed by the generic `CurveParams`.
+    pub fn check_random<R: IterSource<u32>>(&mut self, rd: &mut R, rounds: usize) -> Result<(), Divergence> {
+        for _ in 0..rounds {
+            let (s, k, k2) = (self.random_scalar(rd), self.random_scalar(rd), self.random_scalar(rd));
+            self.check_scalar_base_point(&s)?;
+
+            let (x, y) = self.generic().scalar_base_point(&s);
+            self.check_point(&x, &y, &k)?;
+
+            let (x2, y2) = self.generic().scalar_base_point(&k2);
+            self.check_add(&x, &y, &x2, &y2)?;
+        }
+
+        Ok(())
+    }
+}
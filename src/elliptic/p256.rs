@@ -1201,6 +1201,9 @@ impl EllipticCurve for CurveP256 {
         if k.is_nan() || x.is_nan() || y.is_nan() {
             let tmp = Vec::new();
             return (BigInt::from_be_bytes(tmp.as_slice()), BigInt::from_be_bytes(tmp.as_slice()));
+        } else if x.signnum() == Some(0) && y.signnum() == Some(0) {
+            // the infinite point has no affine representation in the field elements
+            return (BigInt::from(0u32), BigInt::from(0u32));
         }
 
         let mut scalar_reversed = [0u8; 32];
@@ -0,0 +1,26 @@
+use rmath::bigint::BigInt;
+use crate::CryptoError;
+use crate::numeric::MontgomeryContext;
+
+/// The scalar field GF(n) of the curve order `n`, it caches the `MontgomeryContext` of the `n`, so the inversions of
+/// the signatures don't compute the Montgomery parameters again.
+///
+/// The inversion is the `MontgomeryContext::ct_inverse_prime`, the Fermat inversion $k^{n-2} \bmod n$ whose sequence
+/// of the multiplications only depends on the public exponent `n-2`, so the time doesn't depend on the secret `k`(e.g.
+/// the ECDSA nonce).
+#[derive(Clone)]
+pub(crate) struct ScalarField {
+    ctx: MontgomeryContext,
+}
+
+impl ScalarField {
+    /// the `n` must be an odd number which is great than 1
+    pub(crate) fn new(n: &BigInt) -> Option<Self> {
+        MontgomeryContext::new(n).ok().map(|ctx| Self { ctx })
+    }
+
+    /// $k^{-1} \bmod n$, the `k` must be in the range [1, n-1]
+    pub(crate) fn invert(&self, k: &BigInt) -> Result<BigInt, CryptoError> {
+        self.ctx.ct_inverse_prime(k)
+    }
+}
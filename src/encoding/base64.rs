@@ -0,0 +1,92 @@
+//! Base64
+//! RFC 4648 chapter 4, 5
+
+use crate::{CryptoError, CryptoErrorKind};
+
+const BASE64_STD_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const BASE64_PAD: u8 = b'=';
+
+/// The strict Base64 codec, the whitespace and the non-zero trailing bits are rejected by the `decode`.
+#[derive(Clone, Copy)]
+pub struct Base64 {
+    table: &'static [u8; 64],
+    is_padding: bool,
+}
+
+impl Base64 {
+    /// the standard alphabet with the padding
+    pub fn standard() -> Self {
+        Self {
+            table: BASE64_STD_TABLE,
+            is_padding: true,
+        }
+    }
+    
+    /// the URL and filename safe alphabet without the padding
+    pub fn url_safe() -> Self {
+        Self {
+            table: BASE64_URL_TABLE,
+            is_padding: false,
+        }
+    }
+    
+    pub fn with_padding(mut self, is_padding: bool) -> Self {
+        self.is_padding = is_padding;
+        self
+    }
+    
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut s = String::with_capacity(data.len().div_ceil(3) << 2);
+        for c in data.chunks(3) {
+            let b = [c[0], c.get(1).copied().unwrap_or(0), c.get(2).copied().unwrap_or(0)];
+            let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | (b[2] as u32);
+            (0..(c.len() + 1)).for_each(|i| s.push(self.table[((n >> (18 - 6 * i)) & 0x3f) as usize] as char));
+            if self.is_padding {
+                (c.len()..3).for_each(|_| s.push(BASE64_PAD as char));
+            }
+        }
+        s
+    }
+    
+    fn decode_char(&self, c: u8) -> Option<u32> {
+        self.table.iter().position(|&x| x == c).map(|x| x as u32)
+    }
+    
+    pub fn decode(&self, s: &str) -> Result<Vec<u8>, CryptoError> {
+        let s = s.as_bytes();
+        let data = if self.is_padding {
+            if (s.len() & 3) != 0 {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid base64 length"));
+            }
+            let pad = s.iter().rev().take(2).take_while(|&&c| c == BASE64_PAD).count();
+            &s[..(s.len() - pad)]
+        } else {
+            s
+        };
+        
+        if (data.len() & 3) == 1 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid base64 length"));
+        }
+        
+        let mut buf = Vec::with_capacity((data.len() * 3) >> 2);
+        for c in data.chunks(4) {
+            let mut n = 0u32;
+            for (i, &x) in c.iter().enumerate() {
+                match self.decode_char(x) {
+                    Some(v) => n |= v << (18 - 6 * i),
+                    None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                        format!("Invalid base64 character: {:?}", x as char))),
+                }
+            }
+            
+            let len = c.len() - 1;
+            if (n & ((1u32 << (24 - 8 * len)) - 1)) != 0 {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Non-zero base64 trailing bits"));
+            }
+            (0..len).for_each(|i| buf.push((n >> (16 - 8 * i)) as u8));
+        }
+        
+        Ok(buf)
+    }
+}
@@ -0,0 +1,157 @@
+//! DER(Distinguished Encoding Rules)
+//! ITU-T X.690 chapter 8, 10
+//!
+//! Only the low tag number form(the tag number less than 31) is supported.
+
+use crate::{CryptoError, CryptoErrorKind};
+
+pub const TAG_BOOLEAN: u8 = 0x01;
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_BIT_STRING: u8 = 0x03;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_NULL: u8 = 0x05;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_UTC_TIME: u8 = 0x17;
+pub const TAG_GENERALIZED_TIME: u8 = 0x18;
+pub const TAG_SEQUENCE: u8 = 0x30;
+pub const TAG_SET: u8 = 0x31;
+/// the constructed context-specific tag `[n]`
+pub const fn tag_context(n: u8) -> u8 {
+    0xa0 | (n & 0x1f)
+}
+
+fn der_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid DER: {}", msg)))
+}
+
+/// The DER element
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerTLV<'a> {
+    pub tag: u8,
+    /// the content octets
+    pub value: &'a [u8],
+    /// the whole encoding of the element, `tag || length || value`
+    pub raw: &'a [u8],
+}
+
+/// The DER elements reader
+#[derive(Clone)]
+pub struct DerReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> DerReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+        }
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+    
+    /// the tag of the next element
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+    
+    /// read the next element
+    pub fn read(&mut self) -> Result<DerTLV<'a>, CryptoError> {
+        let d = &self.data[self.pos..];
+        if d.len() < 2 {
+            return der_err("unexpected end of data");
+        }
+        
+        let tag = d[0];
+        if (tag & 0x1f) == 0x1f {
+            return der_err("the high tag number form is not supported");
+        }
+        
+        let (len, hdr_len) = if d[1] < 0x80 {
+            (d[1] as usize, 2)
+        } else {
+            let n = (d[1] & 0x7f) as usize;
+            if n == 0 || n > 4 || d.len() < 2 + n {
+                return der_err("invalid length");
+            }
+            let len = d[2..(2 + n)].iter().fold(0usize, |a, &x| (a << 8) | (x as usize));
+            // the length must be encoded in the minimum number of octets
+            if len < 0x80 || d[2] == 0 {
+                return der_err("non-minimal length");
+            }
+            (len, 2 + n)
+        };
+        
+        if d.len() - hdr_len < len {
+            return der_err("unexpected end of data");
+        }
+        
+        self.pos += hdr_len + len;
+        Ok(DerTLV {
+            tag,
+            value: &d[hdr_len..(hdr_len + len)],
+            raw: &d[..(hdr_len + len)],
+        })
+    }
+    
+    /// read the next element which must be tagged with the `tag`
+    pub fn read_expect(&mut self, tag: u8) -> Result<DerTLV<'a>, CryptoError> {
+        match self.peek_tag() {
+            Some(t) if t == tag => self.read(),
+            _ => der_err(format!("expected tag 0x{:02x}", tag).as_str()),
+        }
+    }
+    
+    /// read the next element if it's tagged with the `tag`
+    pub fn read_optional(&mut self, tag: u8) -> Result<Option<DerTLV<'a>>, CryptoError> {
+        if self.peek_tag() == Some(tag) {
+            self.read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    
+    /// read the non-negative INTEGER, and return its big-endian bytes without the leading zero
+    pub fn read_unsigned_integer(&mut self) -> Result<&'a [u8], CryptoError> {
+        let v = self.read_expect(TAG_INTEGER)?.value;
+        if v.is_empty() || (v[0] & 0x80) != 0 {
+            der_err("not a non-negative integer")
+        } else if v.len() > 1 && v[0] == 0 && (v[1] & 0x80) == 0 {
+            der_err("non-minimal integer")
+        } else if v.len() > 1 && v[0] == 0 {
+            Ok(&v[1..])
+        } else {
+            Ok(v)
+        }
+    }
+}
+
+/// append the element `tag || length || value` to the `dst`
+pub fn encode_tlv(tag: u8, value: &[u8], dst: &mut Vec<u8>) {
+    dst.push(tag);
+    let len = value.len();
+    if len < 0x80 {
+        dst.push(len as u8);
+    } else {
+        let b = len.to_be_bytes();
+        let skip = b.iter().take_while(|&&x| x == 0).count();
+        dst.push(0x80 | ((b.len() - skip) as u8));
+        dst.extend_from_slice(&b[skip..]);
+    }
+    dst.extend_from_slice(value);
+}
+
+/// append the non-negative INTEGER with the big-endian bytes `value` to the `dst`
+pub fn encode_unsigned_integer(value: &[u8], dst: &mut Vec<u8>) {
+    let skip = value.iter().take_while(|&&x| x == 0).count();
+    let v = &value[skip..];
+    let mut buf = Vec::with_capacity(v.len() + 1);
+    if v.is_empty() || (v[0] & 0x80) != 0 {
+        buf.push(0);
+    }
+    buf.extend_from_slice(v);
+    encode_tlv(TAG_INTEGER, buf.as_slice(), dst);
+}
@@ -0,0 +1,64 @@
+//! Base16(hex)
+//! RFC 4648 chapter 8
+
+use crate::{CryptoError, CryptoErrorKind};
+
+const HEX_LOWER_TABLE: &[u8; 16] = b"0123456789abcdef";
+const HEX_UPPER_TABLE: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The hex codec, the `decode` accepts both the lower and the upper case digits, but rejects the whitespace
+/// and the odd length.
+#[derive(Clone, Copy)]
+pub struct Hex {
+    table: &'static [u8; 16],
+}
+
+impl Hex {
+    /// encode with the lower case digits
+    pub fn lower() -> Self {
+        Self {
+            table: HEX_LOWER_TABLE,
+        }
+    }
+    
+    /// encode with the upper case digits
+    pub fn upper() -> Self {
+        Self {
+            table: HEX_UPPER_TABLE,
+        }
+    }
+    
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut s = String::with_capacity(data.len() << 1);
+        data.iter().for_each(|&b| {
+            s.push(self.table[(b >> 4) as usize] as char);
+            s.push(self.table[(b & 0xf) as usize] as char);
+        });
+        s
+    }
+    
+    fn decode_char(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    
+    pub fn decode(&self, s: &str) -> Result<Vec<u8>, CryptoError> {
+        let s = s.as_bytes();
+        if (s.len() & 1) != 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid hex length"));
+        }
+        
+        let mut data = Vec::with_capacity(s.len() >> 1);
+        for c in s.chunks(2) {
+            match (Self::decode_char(c[0]), Self::decode_char(c[1])) {
+                (Some(h), Some(l)) => data.push((h << 4) | l),
+                _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid hex character")),
+            }
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,15 @@
+//! Binary-to-text and ASN.1 encodings
+
+mod base64;
+pub use base64::Base64;
+
+mod hex;
+pub use hex::Hex;
+
+pub mod der;
+
+mod pem;
+pub use pem::{PemBlock, PemReader, PemMode};
+
+#[cfg(test)]
+mod encoding_test;
@@ -0,0 +1,302 @@
+//! PEM
+//! RFC 7468, RFC 1421 4.6(the encapsulated headers)
+//!
+//! -----BEGIN label-----
+//! [Name: value
+//! ...
+//! (empty line)]
+//! base64 text
+//! -----END label-----
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::encoding::Base64;
+use std::io::{BufRead, BufReader, Read, Write};
+
+const PEM_BEGIN: &str = "-----BEGIN ";
+const PEM_END: &str = "-----END ";
+const PEM_DASHES: &str = "-----";
+const PEM_LINE_LEN: usize = 64;
+
+/// The parsing mode of the PEM
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PemMode {
+    /// the `stricttextualmsg` of RFC 7468: the base64 lines must be 64 characters except the last one,
+    /// no whitespace is allowed in the base64 lines, and no text is allowed outside the blocks.
+    Strict,
+    /// the `laxtextualmsg` of RFC 7468: any line length, the whitespace in the base64 lines are ignored,
+    /// and the explanatory text outside the blocks are skipped.
+    Lenient,
+}
+
+fn pem_err<T>(line: usize, msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid PEM at line {}: {}", line, msg)))
+}
+
+/// The PEM block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PemBlock {
+    label: String,
+    headers: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl PemBlock {
+    /// `label`: e.g. `CERTIFICATE`, `PUBLIC KEY`, `RSA PRIVATE KEY`;
+    pub fn new(label: &str, data: Vec<u8>) -> Result<Self, CryptoError> {
+        if !Self::is_valid_label(label) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid PEM label: {}", label)));
+        }
+
+        Ok(Self {
+            label: label.to_string(),
+            headers: Vec::new(),
+            data,
+        })
+    }
+
+    fn is_valid_label(label: &str) -> bool {
+        label.bytes().all(|c| (0x20..=0x7e).contains(&c)) &&
+            !label.starts_with([' ', '-']) && !label.ends_with([' ', '-'])
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// the encapsulated headers in order, e.g. `("Proc-Type", "4,ENCRYPTED")`, `("DEK-Info", "AES-128-CBC,<IV>")`
+    pub fn headers(&self) -> &[(String, String)] {
+        self.headers.as_slice()
+    }
+
+    /// return the value of the first header named `name`(case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// append the encapsulated header
+    pub fn add_header(&mut self, name: &str, value: &str) -> Result<(), CryptoError> {
+        if name.is_empty() || name.bytes().any(|c| c == b':' || !(0x21..=0x7e).contains(&c)) ||
+            value.bytes().any(|c| c == b'\r' || c == b'\n') {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid PEM header: {}", name)));
+        }
+
+        self.headers.push((name.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    /// encode to the PEM text with the 64 characters per line
+    pub fn encode(&self) -> String {
+        let b64 = Base64::standard().encode(self.data.as_slice());
+        let mut s = String::with_capacity(b64.len() + (b64.len() / PEM_LINE_LEN) + (self.label.len() << 1) + 32);
+        s.push_str(PEM_BEGIN);
+        s.push_str(self.label.as_str());
+        s.push_str(PEM_DASHES);
+        s.push('\n');
+
+        if !self.headers.is_empty() {
+            for (k, v) in self.headers.iter() {
+                s.push_str(k.as_str());
+                s.push_str(": ");
+                s.push_str(v.as_str());
+                s.push('\n');
+            }
+            s.push('\n');
+        }
+
+        for line in b64.as_bytes().chunks(PEM_LINE_LEN) {
+            s.push_str(std::str::from_utf8(line).unwrap());
+            s.push('\n');
+        }
+
+        s.push_str(PEM_END);
+        s.push_str(self.label.as_str());
+        s.push_str(PEM_DASHES);
+        s.push('\n');
+        s
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), CryptoError> {
+        writer.write_all(self.encode().as_bytes()).map_err(|e| {
+            CryptoError::new(CryptoErrorKind::OuterErr, e)
+        })
+    }
+
+    /// parse all the PEM blocks in the `text`
+    pub fn parse_all(text: &str, mode: PemMode) -> Result<Vec<PemBlock>, CryptoError> {
+        PemReader::new(text.as_bytes(), mode).collect()
+    }
+
+    /// parse the `text` which must contain exactly one PEM block
+    pub fn parse(text: &str, mode: PemMode) -> Result<PemBlock, CryptoError> {
+        let mut blocks = Self::parse_all(text, mode)?;
+        if blocks.len() != 1 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Expected one PEM block, but found {}", blocks.len())))
+        } else {
+            Ok(blocks.pop().unwrap())
+        }
+    }
+}
+
+/// The streaming PEM reader which yields the blocks one by one
+pub struct PemReader<R: Read> {
+    reader: BufReader<R>,
+    mode: PemMode,
+    line: String,
+    line_num: usize,
+    is_eof: bool,
+}
+
+impl<R: Read> PemReader<R> {
+    pub fn new(reader: R, mode: PemMode) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            mode,
+            line: String::new(),
+            line_num: 0,
+            is_eof: false,
+        }
+    }
+
+    /// read the next line without the line ending into the `self.line`, return false on EOF
+    fn next_line(&mut self) -> Result<bool, CryptoError> {
+        self.line.clear();
+        let n = self.reader.read_line(&mut self.line).map_err(|e| {
+            CryptoError::new(CryptoErrorKind::OuterErr, e)
+        })?;
+        if n == 0 {
+            return Ok(false);
+        }
+
+        self.line_num += 1;
+        while self.line.ends_with('\n') || self.line.ends_with('\r') {
+            self.line.pop();
+        }
+        if self.mode == PemMode::Lenient {
+            let len = self.line.trim_end().len();
+            self.line.truncate(len);
+        }
+        Ok(true)
+    }
+
+    fn parse_boundary<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+        line.strip_prefix(prefix).and_then(|l| l.strip_suffix(PEM_DASHES))
+    }
+
+    fn read_block(&mut self) -> Result<Option<PemBlock>, CryptoError> {
+        // pre-encapsulation boundary
+        let label = loop {
+            if !self.next_line()? {
+                return Ok(None);
+            }
+
+            let line = if self.mode == PemMode::Lenient { self.line.trim_start() } else { self.line.as_str() };
+            if let Some(label) = Self::parse_boundary(line, PEM_BEGIN) {
+                break label.to_string();
+            } else if self.mode == PemMode::Strict && !self.line.is_empty() {
+                return pem_err(self.line_num, "the text outside the PEM block");
+            }
+        };
+
+        let mut block = match PemBlock::new(label.as_str(), Vec::new()) {
+            Ok(b) => b,
+            Err(_) => return pem_err(self.line_num, "invalid label"),
+        };
+
+        let (mut b64, mut is_header, mut is_short_line) = (String::new(), true, false);
+        loop {
+            if !self.next_line()? {
+                return pem_err(self.line_num, "missing the post-encapsulation boundary");
+            }
+
+            let line = if self.mode == PemMode::Lenient { self.line.trim_start() } else { self.line.as_str() };
+            if let Some(end) = Self::parse_boundary(line, PEM_END) {
+                if end != block.label {
+                    return pem_err(self.line_num, "the END label not match the BEGIN label");
+                }
+                break;
+            }
+
+            if is_header && b64.is_empty() {
+                if self.line.is_empty() {
+                    if !block.headers.is_empty() {
+                        is_header = false;
+                    } else if self.mode == PemMode::Strict {
+                        return pem_err(self.line_num, "unexpected empty line");
+                    }
+                    continue;
+                } else if self.line.starts_with(' ') || self.line.starts_with('\t') {
+                    // the continuation of the previous header
+                    match block.headers.last_mut() {
+                        Some((_, v)) => {
+                            v.push_str(self.line.trim());
+                            continue;
+                        },
+                        None => if self.mode == PemMode::Strict {
+                            return pem_err(self.line_num, "unexpected whitespace");
+                        },
+                    }
+                } else if let Some(idx) = self.line.find(':') {
+                    let (k, v) = (self.line[..idx].trim().to_string(), self.line[(idx + 1)..].trim().to_string());
+                    if block.add_header(k.as_str(), v.as_str()).is_err() {
+                        return pem_err(self.line_num, "invalid header");
+                    }
+                    continue;
+                } else if !block.headers.is_empty() && self.mode == PemMode::Strict {
+                    return pem_err(self.line_num, "missing the empty line after the headers");
+                }
+                is_header = false;
+            }
+
+            match self.mode {
+                PemMode::Strict => {
+                    if is_short_line || self.line.len() > PEM_LINE_LEN || self.line.is_empty() {
+                        return pem_err(self.line_num, "the base64 line must be 64 characters except the last line");
+                    }
+                    is_short_line = self.line.len() < PEM_LINE_LEN;
+                    b64.push_str(self.line.as_str());
+                },
+                PemMode::Lenient => {
+                    b64.extend(self.line.chars().filter(|c| !c.is_ascii_whitespace()));
+                },
+            }
+        }
+
+        block.data = match Base64::standard().decode(b64.as_str()) {
+            Ok(d) => d,
+            Err(e) => return pem_err(self.line_num, format!("{}", e).as_str()),
+        };
+        Ok(Some(block))
+    }
+}
+
+impl<R: Read> Iterator for PemReader<R> {
+    type Item = Result<PemBlock, CryptoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_eof {
+            return None;
+        }
+
+        match self.read_block() {
+            Ok(Some(b)) => Some(Ok(b)),
+            Ok(None) => {
+                self.is_eof = true;
+                None
+            },
+            Err(e) => {
+                // stop at the first error, because the rest of the stream cannot be resynchronized reliably
+                self.is_eof = true;
+                Some(Err(e))
+            },
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::CryptoErrorKind;
+use crate::envelope::{seal, open, seal_with_password, open_with_password, Header, EnvelopeAlgorithm, KdfParams, ENVELOPE_VERSION,
+    ENVELOPE_MAX_ITERATIONS};
+
+#[test]
+fn envelope_header() {
+    let cases = [
+        Header::new(EnvelopeAlgorithm::Aes128Ccm, vec![1u8; 13], None).unwrap(),
+        Header::new(EnvelopeAlgorithm::Aes256Ccm, vec![2u8; 13], Some(KdfParams::Pbkdf2Sha256 { iterations: 1000, salt: vec![3u8; 16] })).unwrap(),
+        Header::new(EnvelopeAlgorithm::Aes256Ccm, vec![4u8; 13], Some(KdfParams::Pbkdf2Sha512 { iterations: 1, salt: vec![] })).unwrap(),
+    ];
+
+    for (i, header) in cases.iter().enumerate() {
+        let mut buf = header.to_bytes();
+        assert_eq!(buf.len(), header.encoded_len(), "case-{}", i);
+        buf.extend_from_slice(b"payload");
+        let (h, len) = Header::parse(buf.as_slice()).unwrap();
+        assert_eq!(&h, header, "case-{}", i);
+        assert_eq!(len, header.encoded_len(), "case-{}", i);
+    }
+
+    let buf = cases[1].to_bytes();
+    assert_eq!(&buf[..8], &[b'R', b'C', b'E', b'N', ENVELOPE_VERSION, 2, 1, 13]);
+    assert!(Header::parse(&buf[..(buf.len() - 1)]).is_err());
+    assert!(Header::new(EnvelopeAlgorithm::Aes128Ccm, vec![0u8; 12], None).is_err());
+
+    let mut bad_magic = buf.clone();
+    bad_magic[0] = b'X';
+    assert_eq!(Header::parse(bad_magic.as_slice()).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    for idx in [4usize, 5, 6] {
+        let mut unknown = buf.clone();
+        unknown[idx] = 0xff;
+        assert_eq!(Header::parse(unknown.as_slice()).unwrap_err().kind(), CryptoErrorKind::NotSupportUsage, "case-{}", idx);
+    }
+}
+
+#[test]
+fn envelope_seal_open() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let msg = b"The quick brown fox jumps over the lazy dog";
+
+    let algs = [
+        (EnvelopeAlgorithm::Aes128Ccm, vec![7u8; 16]), (EnvelopeAlgorithm::Aes256Ccm, vec![8u8; 32]),
+        (EnvelopeAlgorithm::Aes128Gcm, vec![9u8; 16]), (EnvelopeAlgorithm::Aes256Gcm, vec![10u8; 32]),
+        (EnvelopeAlgorithm::ChaCha20Poly1305, vec![11u8; 32]),
+    ];
+    for (alg, key) in algs.iter().cloned() {
+        assert_eq!(EnvelopeAlgorithm::from_id(alg.id()), Some(alg));
+        let blob = seal(alg, key.as_slice(), msg.as_ref(), &mut rd).unwrap();
+        let (header, len) = Header::parse(blob.as_slice()).unwrap();
+        assert_eq!(header.algorithm, alg);
+        assert_eq!(blob.len(), len + msg.len() + alg.tag_len());
+        assert_eq!(open(key.as_slice(), blob.as_slice()).unwrap(), msg.to_vec());
+
+        let blob2 = seal(alg, key.as_slice(), msg.as_ref(), &mut rd).unwrap();
+        assert_ne!(blob, blob2, "the nonce must be random");
+        assert!(seal(alg, &key[1..], msg.as_ref(), &mut rd).is_err());
+
+        // the header is authenticated
+        let mut tampered = blob.clone();
+        tampered[9] ^= 1;
+        assert_eq!(open(key.as_slice(), tampered.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(open(key.as_slice(), tampered.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(open_with_password(b"password", blob.as_slice()).is_err());
+    }
+
+    let blob = seal(EnvelopeAlgorithm::Aes128Ccm, &[0u8; 16], &[], &mut rd).unwrap();
+    assert!(open(&[0u8; 16], blob.as_slice()).unwrap().is_empty());
+
+    // the CCM with the 13 bytes nonce is limited to 65535 bytes, the GCM and the ChaCha20-Poly1305 aren't
+    let large = (0..70000u32).map(|x| x as u8).collect::<Vec<_>>();
+    for (alg, key) in algs.iter() {
+        if alg.max_plaintext_len() < large.len() as u64 {
+            assert_eq!(seal(*alg, key.as_slice(), large.as_slice(), &mut rd).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+            let blob = seal(*alg, key.as_slice(), &large[..0xffff], &mut rd).unwrap();
+            assert_eq!(open(key.as_slice(), blob.as_slice()).unwrap().as_slice(), &large[..0xffff]);
+        } else {
+            let blob = seal(*alg, key.as_slice(), large.as_slice(), &mut rd).unwrap();
+            assert_eq!(open(key.as_slice(), blob.as_slice()).unwrap(), large);
+        }
+    }
+}
+
+#[test]
+fn envelope_password() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let msg = b"The quick brown fox jumps over the lazy dog";
+
+    let blob = seal_with_password(EnvelopeAlgorithm::Aes256Ccm, b"password", 16, msg.as_ref(), &mut rd).unwrap();
+    let (header, _) = Header::parse(blob.as_slice()).unwrap();
+    let kdf = header.kdf.unwrap();
+    assert_eq!(kdf.iterations(), 16);
+    assert_eq!(kdf.salt().len(), 16);
+
+    assert_eq!(open_with_password(b"password", blob.as_slice()).unwrap(), msg.to_vec());
+    assert_eq!(open_with_password(b"passw0rd", blob.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert_eq!(open(&[0u8; 32], blob.as_slice()).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(seal_with_password(EnvelopeAlgorithm::Aes256Ccm, b"password", 0, msg.as_ref(), &mut rd).is_err());
+    assert!(seal_with_password(EnvelopeAlgorithm::Aes256Ccm, b"password", ENVELOPE_MAX_ITERATIONS + 1, msg.as_ref(), &mut rd).is_err());
+
+    // the huge iterations of the untrusted header are rejected before deriving the key
+    let mut huge = blob.clone();
+    let pos = 8 + EnvelopeAlgorithm::Aes256Ccm.nonce_len();
+    huge[pos..(pos + 4)].copy_from_slice(u32::MAX.to_be_bytes().as_ref());
+    assert_eq!(open_with_password(b"password", huge.as_slice()).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+}
+
+#[cfg(all(feature = "rsa", feature = "ec"))]
+#[test]
+fn envelope_recipients() {
+    use crate::envelope::{seal_for_recipients, open_for_recipient, RecipientList, RecipientKind, RecipientPrivateKey};
+    use crate::elliptic::CurveParams;
+
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let msg = b"The quick brown fox jumps over the lazy dog";
+    let p256 = CurveParams::p256().unwrap();
+    let keys = [
+        RecipientPrivateKey::Rsa(crate::rsa::PrivateKey::generate_key(1024, 19, &mut rd).unwrap()),
+        RecipientPrivateKey::EcP256(p256.generate_key(&mut rd).unwrap()),
+        RecipientPrivateKey::EcP256(p256.generate_key(&mut rd).unwrap()),
+    ];
+    let outsider = RecipientPrivateKey::EcP256(p256.generate_key(&mut rd).unwrap());
+
+    let recipients = keys.iter().map(|k| k.public_key()).collect::<Vec<_>>();
+    let blob = seal_for_recipients(EnvelopeAlgorithm::Aes256Ccm, recipients.as_slice(), msg.as_ref(), &mut rd).unwrap();
+    let (list, len) = RecipientList::parse(blob.as_slice()).unwrap();
+    assert_eq!(list.to_bytes().as_slice(), &blob[..len]);
+    assert_eq!(list.recipients.iter().map(|r| r.kind).collect::<Vec<_>>(), vec![RecipientKind::RsaOaep, RecipientKind::EciesP256, RecipientKind::EciesP256]);
+    assert_eq!(list.recipients[1].wrapped_key.len(), 65 + 40);
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(list.find(key.public_key().key_id().as_ref()).count(), 1, "case-{}", i);
+        assert_eq!(open_for_recipient(key, blob.as_slice(), &mut rd).unwrap(), msg.to_vec(), "case-{}", i);
+    }
+    assert_eq!(open_for_recipient(&outsider, blob.as_slice(), &mut rd).unwrap_err().kind(), CryptoErrorKind::InvalidPrivateKey);
+
+    // the recipient list is authenticated
+    let mut tampered = blob.clone();
+    tampered[len - 1] ^= 1;
+    assert!(open_for_recipient(&keys[0], tampered.as_slice(), &mut rd).is_err());
+    let mut tampered = blob.clone();
+    tampered[7] ^= 1;
+    assert_eq!(open_for_recipient(&keys[1], tampered.as_slice(), &mut rd).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(seal_for_recipients(EnvelopeAlgorithm::Aes128Ccm, &[], msg.as_ref(), &mut rd).is_err());
+
+    // the payload larger than 64 KiB
+    let large = vec![0x5au8; 70000];
+    assert!(seal_for_recipients(EnvelopeAlgorithm::Aes256Ccm, recipients.as_slice(), large.as_slice(), &mut rd).is_err());
+    let blob = seal_for_recipients(EnvelopeAlgorithm::Aes256Gcm, recipients.as_slice(), large.as_slice(), &mut rd).unwrap();
+    assert_eq!(open_for_recipient(&keys[2], blob.as_slice(), &mut rd).unwrap(), large);
+}
@@ -0,0 +1,214 @@
+//! The header of the encrypted blob
+
+use crate::{CryptoError, CryptoErrorKind};
+
+pub const ENVELOPE_MAGIC: [u8; 4] = *b"RCEN";
+pub const ENVELOPE_VERSION: u8 = 1;
+/// the max PBKDF2 iterations of the header, the larger iterations are rejected before deriving the key, so the
+/// untrusted blob cannot make the `open_with_password` hang
+pub const ENVELOPE_MAX_ITERATIONS: u32 = 10_000_000;
+
+fn header_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid envelope header: {}", msg)))
+}
+
+/// The AEAD algorithm of the blob, the id is stable and never reused
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvelopeAlgorithm {
+    /// AES-128-CCM, 13 bytes nonce, 16 bytes tag, the plaintext is at most 65535 bytes(the 2 bytes length field)
+    Aes128Ccm,
+    /// AES-256-CCM, 13 bytes nonce, 16 bytes tag, the plaintext is at most 65535 bytes(the 2 bytes length field)
+    Aes256Ccm,
+    /// AES-128-GCM, 12 bytes nonce, 16 bytes tag
+    Aes128Gcm,
+    /// AES-256-GCM, 12 bytes nonce, 16 bytes tag
+    Aes256Gcm,
+    /// ChaCha20-Poly1305(RFC 8439), 12 bytes nonce, 16 bytes tag
+    ChaCha20Poly1305,
+}
+
+impl EnvelopeAlgorithm {
+    pub fn id(&self) -> u8 {
+        match self {
+            EnvelopeAlgorithm::Aes128Ccm => 1,
+            EnvelopeAlgorithm::Aes256Ccm => 2,
+            EnvelopeAlgorithm::Aes128Gcm => 3,
+            EnvelopeAlgorithm::Aes256Gcm => 4,
+            EnvelopeAlgorithm::ChaCha20Poly1305 => 5,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(EnvelopeAlgorithm::Aes128Ccm),
+            2 => Some(EnvelopeAlgorithm::Aes256Ccm),
+            3 => Some(EnvelopeAlgorithm::Aes128Gcm),
+            4 => Some(EnvelopeAlgorithm::Aes256Gcm),
+            5 => Some(EnvelopeAlgorithm::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self {
+            EnvelopeAlgorithm::Aes128Ccm | EnvelopeAlgorithm::Aes128Gcm => 16,
+            EnvelopeAlgorithm::Aes256Ccm | EnvelopeAlgorithm::Aes256Gcm | EnvelopeAlgorithm::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    pub fn nonce_len(&self) -> usize {
+        match self {
+            EnvelopeAlgorithm::Aes128Ccm | EnvelopeAlgorithm::Aes256Ccm => 13,
+            _ => 12,
+        }
+    }
+
+    /// the max plaintext length in bytes
+    pub fn max_plaintext_len(&self) -> u64 {
+        match self {
+            EnvelopeAlgorithm::Aes128Ccm | EnvelopeAlgorithm::Aes256Ccm => 0xffff,
+            // 2^32 - 2 blocks of the GCM, 2^32 - 1 blocks of the ChaCha20 with the initial counter 1
+            EnvelopeAlgorithm::Aes128Gcm | EnvelopeAlgorithm::Aes256Gcm => ((1u64 << 32) - 2) << 4,
+            EnvelopeAlgorithm::ChaCha20Poly1305 => ((1u64 << 32) - 1) << 6,
+        }
+    }
+
+    pub fn tag_len(&self) -> usize {
+        16
+    }
+}
+
+/// The password-based KDF parameters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KdfParams {
+    /// PBKDF2-HMAC-SHA256
+    Pbkdf2Sha256 { iterations: u32, salt: Vec<u8> },
+    /// PBKDF2-HMAC-SHA512
+    Pbkdf2Sha512 { iterations: u32, salt: Vec<u8> },
+}
+
+impl KdfParams {
+    pub fn id(&self) -> u8 {
+        match self {
+            KdfParams::Pbkdf2Sha256 { .. } => 1,
+            KdfParams::Pbkdf2Sha512 { .. } => 2,
+        }
+    }
+
+    pub fn iterations(&self) -> u32 {
+        match self {
+            KdfParams::Pbkdf2Sha256 { iterations, .. } | KdfParams::Pbkdf2Sha512 { iterations, .. } => *iterations,
+        }
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        match self {
+            KdfParams::Pbkdf2Sha256 { salt, .. } | KdfParams::Pbkdf2Sha512 { salt, .. } => salt.as_slice(),
+        }
+    }
+
+    fn from_id(id: u8, iterations: u32, salt: Vec<u8>) -> Option<Self> {
+        match id {
+            1 => Some(KdfParams::Pbkdf2Sha256 { iterations, salt }),
+            2 => Some(KdfParams::Pbkdf2Sha512 { iterations, salt }),
+            _ => None,
+        }
+    }
+}
+
+/// The header of the blob
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub version: u8,
+    pub algorithm: EnvelopeAlgorithm,
+    pub nonce: Vec<u8>,
+    pub kdf: Option<KdfParams>,
+}
+
+impl Header {
+    pub fn new(algorithm: EnvelopeAlgorithm, nonce: Vec<u8>, kdf: Option<KdfParams>) -> Result<Self, CryptoError> {
+        let header = Self { version: ENVELOPE_VERSION, algorithm, nonce, kdf };
+        header.check()?;
+        Ok(header)
+    }
+
+    fn check(&self) -> Result<(), CryptoError> {
+        if self.nonce.len() != self.algorithm.nonce_len() {
+            header_err("the nonce length doesn't match the algorithm")
+        } else if let Some(kdf) = self.kdf.as_ref() {
+            if kdf.iterations() == 0 || kdf.iterations() > ENVELOPE_MAX_ITERATIONS {
+                header_err("the KDF iterations must be in the range [1, ENVELOPE_MAX_ITERATIONS]")
+            } else if kdf.salt().len() > (u8::MAX as usize) {
+                header_err("the KDF salt is too long")
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// the header length in bytes
+    pub fn encoded_len(&self) -> usize {
+        8 + self.nonce.len() + self.kdf.as_ref().map(|k| 5 + k.salt().len()).unwrap_or(0)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        buf.extend_from_slice(ENVELOPE_MAGIC.as_ref());
+        buf.push(self.version);
+        buf.push(self.algorithm.id());
+        buf.push(self.kdf.as_ref().map(|k| k.id()).unwrap_or(0));
+        buf.push(self.nonce.len() as u8);
+        buf.extend_from_slice(self.nonce.as_slice());
+        if let Some(kdf) = self.kdf.as_ref() {
+            buf.extend_from_slice(kdf.iterations().to_be_bytes().as_ref());
+            buf.push(kdf.salt().len() as u8);
+            buf.extend_from_slice(kdf.salt());
+        }
+        buf
+    }
+
+    /// parse the header from the beginning of the `blob`, return the header and the length of the header.
+    /// The unknown version, algorithm id or KDF id is rejected by the `CryptoErrorKind::NotSupportUsage`.
+    pub fn parse(blob: &[u8]) -> Result<(Self, usize), CryptoError> {
+        if blob.len() < 8 || blob[..4] != ENVELOPE_MAGIC {
+            return header_err("the magic doesn't match");
+        }
+
+        let (version, alg_id, kdf_id, nonce_len) = (blob[4], blob[5], blob[6], blob[7] as usize);
+        if version != ENVELOPE_VERSION {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The envelope version {} is not supported", version)));
+        }
+        let algorithm = match EnvelopeAlgorithm::from_id(alg_id) {
+            Some(a) => a,
+            None => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The envelope algorithm id {} is not supported", alg_id))),
+        };
+
+        let mut rest = &blob[8..];
+        if rest.len() < nonce_len {
+            return header_err("the nonce is truncated");
+        }
+        let nonce = rest[..nonce_len].to_vec();
+        rest = &rest[nonce_len..];
+
+        let kdf = if kdf_id == 0 {
+            None
+        } else {
+            if rest.len() < 5 || rest.len() < 5 + (rest[4] as usize) {
+                return header_err("the KDF parameters are truncated");
+            }
+            let iterations = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]);
+            let salt = rest[5..(5 + rest[4] as usize)].to_vec();
+            match KdfParams::from_id(kdf_id, iterations, salt) {
+                Some(k) => Some(k),
+                None => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The envelope KDF id {} is not supported", kdf_id))),
+            }
+        };
+
+        let header = Self { version, algorithm, nonce, kdf };
+        header.check()?;
+        let len = header.encoded_len();
+        Ok((header, len))
+    }
+}
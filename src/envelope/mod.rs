@@ -0,0 +1,28 @@
+//! The self-describing encrypted blob
+//!
+//! The blob is `header || ciphertext || tag`, the header identifies the cipher, the nonce and the optional
+//! password-based KDF parameters, so the stored data can be decrypted after the default algorithm is migrated.
+//! The header is authenticated as the associated data of the AEAD.
+//!
+//! ```text
+//! magic("RCEN", 4) || version(1) || algorithm id(1) || kdf id(1) || nonce_len(1) || nonce
+//!     || [iterations(4, big-endian) || salt_len(1) || salt], the KDF parameters are present if the kdf id isn't 0
+//! ```
+//!
+//! The multi-recipient blob(`seal_for_recipients`) encrypts the payload once under the random CEK, and wraps the CEK
+//! for every recipient by the RSA-OAEP or the ECIES-P256, the recipient entry is picked by the key id.
+
+mod header;
+pub use header::{Header, EnvelopeAlgorithm, KdfParams, ENVELOPE_MAGIC, ENVELOPE_VERSION, ENVELOPE_MAX_ITERATIONS};
+
+mod seal;
+pub use seal::{seal, open, seal_with_password, open_with_password};
+
+#[cfg(all(feature = "rsa", feature = "ec"))]
+mod recipient;
+#[cfg(all(feature = "rsa", feature = "ec"))]
+pub use recipient::{seal_for_recipients, open_for_recipient, RecipientList, RecipientEntry, RecipientKind,
+    RecipientPublicKey, RecipientPrivateKey, RECIPIENT_MAGIC, RECIPIENT_KEY_ID_LEN};
+
+#[cfg(test)]
+mod envelope_test;
@@ -0,0 +1,367 @@
+//! The multi-recipient blob
+//!
+//! The payload is encrypted once under the random content encryption key(CEK), and the CEK is wrapped for every
+//! recipient. The recipient list is authenticated as the associated data of the AEAD.
+//!
+//! ```text
+//! magic("RCMR", 4) || version(1) || algorithm id(1) || nonce_len(1) || nonce || recipient count(2, big-endian)
+//!     || [kind(1) || key id(8) || wrapped_len(2, big-endian) || wrapped CEK] * count
+//! ```
+//!
+//! - RSA-OAEP: the CEK is encrypted by the RSAES-OAEP with the SHA-256 and the empty label;
+//! - ECIES-P256: the ephemeral key $E = kG$, $Z = x(kQ)$, the KEK is
+//!   `SHA-256([1]^4 || Z || "rcrypto envelope ecies-p256" || E || Q)`, the wrapped CEK is `E || KW(KEK, CEK)`,
+//!   the points are uncompressed(`0x04 || x || y`).
+//!
+//! The key id is the first 8 bytes of `SHA-256(kind || public key)`, the public key of the RSA is `n || e`.
+
+use rmath::bigint::BigInt;
+use rmath::rand::{IterSource, Source, Seed, Iter, RandError, RandErrKind};
+use crate::{Cipher, CryptoError, CryptoErrorKind, Digest, AES};
+use crate::cipher_mode::KeyWrap;
+use crate::elliptic::{CurveP256, EllipticCurve};
+use crate::envelope::{EnvelopeAlgorithm, ENVELOPE_VERSION};
+use crate::envelope::seal::{random_bytes, check_key, encrypt_payload, decrypt_payload};
+use crate::rsa::OAEP;
+use crate::sha::SHA256;
+
+pub const RECIPIENT_MAGIC: [u8; 4] = *b"RCMR";
+pub const RECIPIENT_KEY_ID_LEN: usize = 8;
+
+const ECIES_P256_FIELD_LEN: usize = 32;
+const ECIES_P256_POINT_LEN: usize = 1 + (ECIES_P256_FIELD_LEN << 1);
+const ECIES_P256_LABEL: &[u8] = b"rcrypto envelope ecies-p256";
+
+fn recipient_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid recipient list: {}", msg)))
+}
+
+/// The random source which borrows the caller's random source, so the OAEP doesn't need to own it
+struct BorrowedRand<'a, R>(&'a mut R);
+
+impl<'a, R: IterSource<u32>> Source<u32> for BorrowedRand<'a, R> {
+    fn gen(&mut self) -> Result<u32, RandError> {
+        self.0.iter_mut().next().ok_or_else(|| RandError::new(RandErrKind::NoNewRandNumberGen, "The random source is exhausted"))
+    }
+
+    fn reset<Sd: Seed<u32>>(&mut self, sd: &Sd) -> Result<(), RandError> {
+        self.0.reset(sd)
+    }
+}
+
+impl<'a, R: IterSource<u32>> IterSource<u32> for BorrowedRand<'a, R> {
+    fn iter_mut(&mut self) -> Iter<'_, Self, u32> where Self: std::marker::Sized {
+        Iter::new(self)
+    }
+}
+
+/// The key wrapping algorithm of the recipient entry, the id is stable and never reused
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecipientKind {
+    /// RSAES-OAEP with SHA-256
+    RsaOaep,
+    /// ECIES over the P-256 with the AES key wrap
+    EciesP256,
+}
+
+impl RecipientKind {
+    pub fn id(&self) -> u8 {
+        match self {
+            RecipientKind::RsaOaep => 1,
+            RecipientKind::EciesP256 => 2,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(RecipientKind::RsaOaep),
+            2 => Some(RecipientKind::EciesP256),
+            _ => None,
+        }
+    }
+}
+
+/// The public key of the recipient
+#[derive(Clone)]
+pub enum RecipientPublicKey {
+    Rsa(crate::rsa::PublicKey),
+    EcP256(crate::elliptic::PublicKey),
+}
+
+/// The private key of the recipient
+#[derive(Clone)]
+pub enum RecipientPrivateKey {
+    Rsa(crate::rsa::PrivateKey),
+    EcP256(crate::elliptic::PrivateKey),
+}
+
+/// the big-endian bytes which are left padded to the `len`
+fn to_fixed_be_bytes(x: &BigInt, len: usize) -> Vec<u8> {
+    let b = x.to_be_bytes();
+    let mut buf = vec![0u8; len.saturating_sub(b.len())];
+    buf.extend_from_slice(b.as_slice());
+    buf
+}
+
+fn encode_point(x: &BigInt, y: &BigInt) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(ECIES_P256_POINT_LEN);
+    buf.push(0x04);
+    buf.append(&mut to_fixed_be_bytes(x, ECIES_P256_FIELD_LEN));
+    buf.append(&mut to_fixed_be_bytes(y, ECIES_P256_FIELD_LEN));
+    buf
+}
+
+impl RecipientPublicKey {
+    pub fn kind(&self) -> RecipientKind {
+        match self {
+            RecipientPublicKey::Rsa(_) => RecipientKind::RsaOaep,
+            RecipientPublicKey::EcP256(_) => RecipientKind::EciesP256,
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            RecipientPublicKey::Rsa(pk) => {
+                let mut buf = to_fixed_be_bytes(pk.modulus(), pk.modulus_len());
+                buf.append(&mut pk.exponent().to_be_bytes());
+                buf
+            },
+            RecipientPublicKey::EcP256(pk) => encode_point(&pk.qx, &pk.qy),
+        }
+    }
+
+    /// the first 8 bytes of `SHA-256(kind || public key)`
+    pub fn key_id(&self) -> [u8; RECIPIENT_KEY_ID_LEN] {
+        let (mut sha, mut digest) = (SHA256::new(), Vec::with_capacity(32));
+        sha.write(&[self.kind().id()]);
+        sha.write(self.encode().as_slice());
+        sha.checksum(&mut digest);
+
+        let mut id = [0u8; RECIPIENT_KEY_ID_LEN];
+        id.copy_from_slice(&digest[..RECIPIENT_KEY_ID_LEN]);
+        id
+    }
+
+    fn wrap<R: IterSource<u32>>(&self, cek: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+        let mut wrapped = Vec::new();
+        match self {
+            RecipientPublicKey::Rsa(pk) => {
+                let oaep = OAEP::new(SHA256::new(), BorrowedRand(rd), pk.clone().into(), Vec::new(), false)?;
+                oaep.encrypt(&mut wrapped, cek)?;
+            },
+            RecipientPublicKey::EcP256(pk) => {
+                let curve = CurveP256::new()?;
+                let ephemeral = curve.curve_params().generate_key(rd)?;
+                let e = encode_point(&ephemeral.public_key().qx, &ephemeral.public_key().qy);
+                let kek = ecies_kek(&curve, &pk.qx, &pk.qy, &ephemeral.d.get(), e.as_slice(), self.encode().as_slice())?;
+                let mut kw = Vec::with_capacity(cek.len() + 8);
+                KeyWrap::new(AES::new(kek)?)?.wrap(&mut kw, cek)?;
+                wrapped = e;
+                wrapped.append(&mut kw);
+            },
+        }
+        Ok(wrapped)
+    }
+}
+
+impl RecipientPrivateKey {
+    pub fn public_key(&self) -> RecipientPublicKey {
+        match self {
+            RecipientPrivateKey::Rsa(sk) => RecipientPublicKey::Rsa(sk.public_key().clone()),
+            RecipientPrivateKey::EcP256(sk) => RecipientPublicKey::EcP256(sk.public_key().clone()),
+        }
+    }
+
+    fn unwrap<R: IterSource<u32>>(&self, wrapped: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+        let mut cek = Vec::new();
+        match self {
+            RecipientPrivateKey::Rsa(sk) => {
+                let oaep = OAEP::new(SHA256::new(), BorrowedRand(rd), sk.clone().into(), Vec::new(), true)?;
+                oaep.decrypt(&mut cek, wrapped)?;
+            },
+            RecipientPrivateKey::EcP256(sk) => {
+                if wrapped.len() < ECIES_P256_POINT_LEN || wrapped[0] != 0x04 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The ECIES ephemeral key is not the uncompressed point"));
+                }
+
+                let curve = CurveP256::new()?;
+                let (e, wrapped_cek) = wrapped.split_at(ECIES_P256_POINT_LEN);
+                let (ex, ey) = (BigInt::from_be_bytes(&e[1..(1 + ECIES_P256_FIELD_LEN)]), BigInt::from_be_bytes(&e[(1 + ECIES_P256_FIELD_LEN)..]));
+                if !curve.is_on_curve(&ex, &ey) {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The ECIES ephemeral key is not on the P-256"));
+                }
+
+                let kek = ecies_kek(&curve, &ex, &ey, &sk.d.get(), e, self.public_key().encode().as_slice())?;
+                KeyWrap::new(AES::new(kek)?)?.unwrap(&mut cek, wrapped_cek)?;
+            },
+        }
+        Ok(cek)
+    }
+}
+
+/// `SHA-256([1]^4 || x(d(px, py)) || label || E || Q)`
+fn ecies_kek(curve: &CurveP256, px: &BigInt, py: &BigInt, d: &BigInt, e: &[u8], q: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (zx, zy) = curve.scalar(px, py, d.as_ref());
+    if zx.is_nan() || (zx == 0u32 && zy == 0u32) {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The ECIES shared secret is the point at infinity"));
+    }
+
+    let (mut sha, mut kek) = (SHA256::new(), Vec::with_capacity(32));
+    sha.write(1u32.to_be_bytes().as_ref());
+    sha.write(to_fixed_be_bytes(&zx, ECIES_P256_FIELD_LEN).as_slice());
+    sha.write(ECIES_P256_LABEL);
+    sha.write(e);
+    sha.write(q);
+    sha.checksum(&mut kek);
+    Ok(kek)
+}
+
+/// The wrapped CEK of one recipient
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecipientEntry {
+    pub kind: RecipientKind,
+    pub key_id: [u8; RECIPIENT_KEY_ID_LEN],
+    pub wrapped_key: Vec<u8>,
+}
+
+/// The header of the multi-recipient blob
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecipientList {
+    pub version: u8,
+    pub algorithm: EnvelopeAlgorithm,
+    pub nonce: Vec<u8>,
+    pub recipients: Vec<RecipientEntry>,
+}
+
+impl RecipientList {
+    pub fn new(algorithm: EnvelopeAlgorithm, nonce: Vec<u8>, recipients: Vec<RecipientEntry>) -> Result<Self, CryptoError> {
+        let list = Self { version: ENVELOPE_VERSION, algorithm, nonce, recipients };
+        list.check()?;
+        Ok(list)
+    }
+
+    fn check(&self) -> Result<(), CryptoError> {
+        if self.nonce.len() != self.algorithm.nonce_len() {
+            recipient_err("the nonce length doesn't match the algorithm")
+        } else if self.recipients.is_empty() || self.recipients.len() > (u16::MAX as usize) {
+            recipient_err("the number of the recipients must be in the range [1, 65535]")
+        } else if self.recipients.iter().any(|r| r.wrapped_key.len() > (u16::MAX as usize)) {
+            recipient_err("the wrapped key is too long")
+        } else {
+            Ok(())
+        }
+    }
+
+    /// the entries whose key id is the `key_id`
+    pub fn find<'a>(&'a self, key_id: &'a [u8]) -> impl Iterator<Item=&'a RecipientEntry> {
+        self.recipients.iter().filter(move |r| r.key_id.as_ref() == key_id)
+    }
+
+    /// the header length in bytes
+    pub fn encoded_len(&self) -> usize {
+        9 + self.nonce.len() + self.recipients.iter().map(|r| 3 + RECIPIENT_KEY_ID_LEN + r.wrapped_key.len()).sum::<usize>()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        buf.extend_from_slice(RECIPIENT_MAGIC.as_ref());
+        buf.push(self.version);
+        buf.push(self.algorithm.id());
+        buf.push(self.nonce.len() as u8);
+        buf.extend_from_slice(self.nonce.as_slice());
+        buf.extend_from_slice((self.recipients.len() as u16).to_be_bytes().as_ref());
+        for r in self.recipients.iter() {
+            buf.push(r.kind.id());
+            buf.extend_from_slice(r.key_id.as_ref());
+            buf.extend_from_slice((r.wrapped_key.len() as u16).to_be_bytes().as_ref());
+            buf.extend_from_slice(r.wrapped_key.as_slice());
+        }
+        buf
+    }
+
+    /// parse the recipient list from the beginning of the `blob`, return the list and the length of the list.
+    /// The unknown version, algorithm id or recipient kind is rejected by the `CryptoErrorKind::NotSupportUsage`.
+    pub fn parse(blob: &[u8]) -> Result<(Self, usize), CryptoError> {
+        if blob.len() < 7 || blob[..4] != RECIPIENT_MAGIC {
+            return recipient_err("the magic doesn't match");
+        }
+
+        let (version, alg_id, nonce_len) = (blob[4], blob[5], blob[6] as usize);
+        if version != ENVELOPE_VERSION {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The envelope version {} is not supported", version)));
+        }
+        let algorithm = match EnvelopeAlgorithm::from_id(alg_id) {
+            Some(a) => a,
+            None => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The envelope algorithm id {} is not supported", alg_id))),
+        };
+
+        let mut rest = &blob[7..];
+        if rest.len() < nonce_len + 2 {
+            return recipient_err("the nonce is truncated");
+        }
+        let nonce = rest[..nonce_len].to_vec();
+        let count = u16::from_be_bytes([rest[nonce_len], rest[nonce_len + 1]]) as usize;
+        rest = &rest[(nonce_len + 2)..];
+
+        let mut recipients = Vec::with_capacity(count);
+        for _ in 0..count {
+            if rest.len() < 3 + RECIPIENT_KEY_ID_LEN {
+                return recipient_err("the recipient entry is truncated");
+            }
+            let kind = match RecipientKind::from_id(rest[0]) {
+                Some(k) => k,
+                None => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The recipient kind {} is not supported", rest[0]))),
+            };
+            let mut key_id = [0u8; RECIPIENT_KEY_ID_LEN];
+            key_id.copy_from_slice(&rest[1..(1 + RECIPIENT_KEY_ID_LEN)]);
+            let len = u16::from_be_bytes([rest[1 + RECIPIENT_KEY_ID_LEN], rest[2 + RECIPIENT_KEY_ID_LEN]]) as usize;
+            rest = &rest[(3 + RECIPIENT_KEY_ID_LEN)..];
+            if rest.len() < len {
+                return recipient_err("the wrapped key is truncated");
+            }
+            recipients.push(RecipientEntry { kind, key_id, wrapped_key: rest[..len].to_vec() });
+            rest = &rest[len..];
+        }
+
+        let list = Self { version, algorithm, nonce, recipients };
+        list.check()?;
+        let len = list.encoded_len();
+        Ok((list, len))
+    }
+}
+
+/// encrypt the `plaintext` once under the random CEK by the `alg`, and wrap the CEK for every recipient,
+/// return the blob `recipient list || ciphertext || tag`
+pub fn seal_for_recipients<R: IterSource<u32>>(alg: EnvelopeAlgorithm, recipients: &[RecipientPublicKey], plaintext: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+    let cek = random_bytes(rd, alg.key_len());
+    let mut entries = Vec::with_capacity(recipients.len());
+    for r in recipients.iter() {
+        entries.push(RecipientEntry { kind: r.kind(), key_id: r.key_id(), wrapped_key: r.wrap(cek.as_slice(), rd)? });
+    }
+
+    let list = RecipientList::new(alg, random_bytes(rd, alg.nonce_len()), entries)?;
+    let mut blob = list.to_bytes();
+    encrypt_payload(&mut blob, alg, cek.as_slice(), list.nonce.as_slice(), plaintext)?;
+    Ok(blob)
+}
+
+/// decrypt the blob which is sealed by the `seal_for_recipients`, the recipient entry is picked by the key id of
+/// the `key`, the `rd` is used by the RSA blinding.
+pub fn open_for_recipient<R: IterSource<u32>>(key: &RecipientPrivateKey, blob: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+    let (list, len) = RecipientList::parse(blob)?;
+    let (kind, key_id) = {
+        let pk = key.public_key();
+        (pk.kind(), pk.key_id())
+    };
+
+    // the key ids may collide, every matched entry is tried
+    for entry in list.find(key_id.as_ref()).filter(|r| r.kind == kind) {
+        if let Ok(cek) = key.unwrap(entry.wrapped_key.as_slice(), rd) {
+            if check_key(list.algorithm, cek.as_slice()).is_ok() {
+                return decrypt_payload(list.algorithm, cek.as_slice(), list.nonce.as_slice(), blob, len);
+            }
+        }
+    }
+
+    Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "No recipient entry can be unwrapped by the key"))
+}
@@ -0,0 +1,138 @@
+//! The high-level encryption helpers which emit/consume the self-describing blob
+
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind, Digest, AES};
+use crate::cipher_mode::{CCM, GCM};
+use crate::chacha::ChaCha20Poly1305;
+use crate::kdf::PBKDF2;
+use crate::sha::{SHA256, SHA512};
+use crate::envelope::{Header, EnvelopeAlgorithm, KdfParams};
+
+const ENVELOPE_SALT_LEN: usize = 16;
+
+pub(super) fn random_bytes<R: IterSource<u32>>(rd: &mut R, len: usize) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(len + 3);
+    rd.iter_mut().take(len.div_ceil(4)).for_each(|x| buf.extend_from_slice(x.to_be_bytes().as_ref()));
+    buf.truncate(len);
+    buf
+}
+
+fn chacha_key(key: &[u8]) -> ChaCha20Poly1305 {
+    let mut k = [0u8; 32];
+    k.copy_from_slice(key);
+    ChaCha20Poly1305::new(k)
+}
+
+pub(super) fn check_key(alg: EnvelopeAlgorithm, key: &[u8]) -> Result<(), CryptoError> {
+    if key.len() != alg.key_len() {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("Wrong key length: {}, the {:?} key must be {} bytes", key.len(), alg, alg.key_len())))
+    } else {
+        Ok(())
+    }
+}
+
+fn pbkdf2_with<D: Digest>(digest: D, kdf: &KdfParams, password: &[u8], key_len: usize) -> Result<Vec<u8>, CryptoError> {
+    let mut key = Vec::with_capacity(key_len);
+    PBKDF2::new(digest, kdf.iterations() as usize)?.derive(password, kdf.salt(), key_len, &mut key)?;
+    Ok(key)
+}
+
+fn derive_key(kdf: &KdfParams, password: &[u8], key_len: usize) -> Result<Vec<u8>, CryptoError> {
+    match kdf {
+        KdfParams::Pbkdf2Sha256 { .. } => pbkdf2_with(SHA256::new(), kdf, password, key_len),
+        KdfParams::Pbkdf2Sha512 { .. } => pbkdf2_with(SHA512::new(), kdf, password, key_len),
+    }
+}
+
+/// the `aad`(the header) is already in the `blob`, the `ciphertext || tag` is appended to it
+pub(super) fn encrypt_payload(blob: &mut Vec<u8>, alg: EnvelopeAlgorithm, key: &[u8], nonce: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+    if plaintext.len() as u64 > alg.max_plaintext_len() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The plaintext is too long: {}, the {:?} plaintext is at most {} bytes", plaintext.len(), alg, alg.max_plaintext_len())));
+    }
+    let mut ct = Vec::with_capacity(plaintext.len() + alg.tag_len());
+    match alg {
+        EnvelopeAlgorithm::Aes128Ccm | EnvelopeAlgorithm::Aes256Ccm => {
+            CCM::new(AES::new(key.to_vec())?, alg.tag_len(), alg.nonce_len())?
+                .encrypt(&mut ct, nonce, blob.as_slice(), plaintext)?;
+        },
+        EnvelopeAlgorithm::Aes128Gcm | EnvelopeAlgorithm::Aes256Gcm => {
+            GCM::new(AES::new(key.to_vec())?, alg.tag_len())?.encrypt(&mut ct, nonce, blob.as_slice(), plaintext)?;
+        },
+        EnvelopeAlgorithm::ChaCha20Poly1305 => {
+            chacha_key(key).encrypt(&mut ct, nonce, blob.as_slice(), plaintext)?;
+        },
+    }
+    blob.append(&mut ct);
+    Ok(())
+}
+
+/// the first `aad_len` bytes of the `blob` is the associated data
+pub(super) fn decrypt_payload(alg: EnvelopeAlgorithm, key: &[u8], nonce: &[u8], blob: &[u8], aad_len: usize) -> Result<Vec<u8>, CryptoError> {
+    let (aad, ct) = blob.split_at(aad_len);
+    let mut pt = Vec::with_capacity(ct.len());
+    match alg {
+        EnvelopeAlgorithm::Aes128Ccm | EnvelopeAlgorithm::Aes256Ccm => {
+            CCM::new(AES::new(key.to_vec())?, alg.tag_len(), alg.nonce_len())?
+                .decrypt(&mut pt, nonce, aad, ct)?;
+        },
+        EnvelopeAlgorithm::Aes128Gcm | EnvelopeAlgorithm::Aes256Gcm => {
+            GCM::new(AES::new(key.to_vec())?, alg.tag_len())?.decrypt(&mut pt, nonce, aad, ct)?;
+        },
+        EnvelopeAlgorithm::ChaCha20Poly1305 => {
+            chacha_key(key).decrypt(&mut pt, nonce, aad, ct)?;
+        },
+    }
+    Ok(pt)
+}
+
+/// the `header` is authenticated as the associated data
+fn seal_with_header(header: &Header, key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let alg = header.algorithm;
+    check_key(alg, key)?;
+    let mut blob = header.to_bytes();
+    encrypt_payload(&mut blob, alg, key, header.nonce.as_slice(), plaintext)?;
+    Ok(blob)
+}
+
+fn open_with_header(header: &Header, header_len: usize, key: &[u8], blob: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let alg = header.algorithm;
+    check_key(alg, key)?;
+    decrypt_payload(alg, key, header.nonce.as_slice(), blob, header_len)
+}
+
+/// encrypt the `plaintext` by the `alg` with the random nonce, return the blob `header || ciphertext || tag`
+pub fn seal<R: IterSource<u32>>(alg: EnvelopeAlgorithm, key: &[u8], plaintext: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+    let header = Header::new(alg, random_bytes(rd, alg.nonce_len()), None)?;
+    seal_with_header(&header, key, plaintext)
+}
+
+/// decrypt the blob which is sealed by the `seal`, the algorithm is identified by the header
+pub fn open(key: &[u8], blob: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (header, len) = Header::parse(blob)?;
+    if header.kdf.is_some() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The blob is sealed with the password"));
+    }
+    open_with_header(&header, len, key, blob)
+}
+
+/// encrypt the `plaintext` by the key which is derived from the `password` by the PBKDF2-HMAC-SHA256 with the
+/// `iterations` and the random 16 bytes salt, the KDF parameters are stored in the header.
+pub fn seal_with_password<R: IterSource<u32>>(alg: EnvelopeAlgorithm, password: &[u8], iterations: u32, plaintext: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+    let salt = random_bytes(rd, ENVELOPE_SALT_LEN);
+    let header = Header::new(alg, random_bytes(rd, alg.nonce_len()), Some(KdfParams::Pbkdf2Sha256 { iterations, salt }))?;
+    let key = derive_key(header.kdf.as_ref().unwrap(), password, alg.key_len())?;
+    seal_with_header(&header, key.as_slice(), plaintext)
+}
+
+/// decrypt the blob which is sealed by the `seal_with_password`, the KDF parameters are read from the header, the
+/// iterations greater than the `ENVELOPE_MAX_ITERATIONS` are rejected before deriving the key.
+pub fn open_with_password(password: &[u8], blob: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (header, len) = Header::parse(blob)?;
+    let key = match header.kdf.as_ref() {
+        Some(kdf) => derive_key(kdf, password, header.algorithm.key_len())?,
+        None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The blob isn't sealed with the password")),
+    };
+    open_with_header(&header, len, key.as_slice(), blob)
+}
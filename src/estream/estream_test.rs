@@ -0,0 +1,102 @@
+use crate::{HC128, HC256, Rabbit, StreamCipher, CryptoErrorKind};
+use crate::test_util::hex;
+
+fn key_stream<S: StreamCipher>(s: &mut S, len: usize, step: usize) -> Vec<u8> {
+    let (data, mut buf, mut ks) = (vec![0u8; len], Vec::new(), Vec::new());
+    for d in data.chunks(step) {
+        s.xor_key_stream(&mut buf, d).unwrap();
+        ks.extend_from_slice(buf.as_slice());
+    }
+    ks
+}
+
+#[test]
+fn hc128() {
+    // eSTREAM test vectors, the zero key and IV
+    assert_eq!(key_stream(&mut HC128::new([0u8; 16], [0u8; 16]), 32, 32), hex("82001573a003fd3b7fd72ffb0eaf63aac62f12deb629dca72785a66268ec758b"));
+
+    let tgt = hex("2be0721c5dca3bca218905e514b8d989c4d8320df33b2736c0e17a9a0d072e7b3c85fe0432f4b83d4b3b140261f14ce3e395539199783f564b8ab1873a80ddc8516d4146624c645db1c769416515460f5b3826366a6becc876f911c1d9dd3f200ee97054");
+    let (key, iv) = ((0u8..16).collect::<Vec<_>>(), (16u8..32).collect::<Vec<_>>());
+    for &step in [1usize, 7, 64, 100].iter() {
+        let mut hc = HC128::from_slice(key.as_slice(), iv.as_slice()).unwrap();
+        assert_eq!(key_stream(&mut hc, tgt.len(), step), tgt, "step: {}", step);
+    }
+    assert_eq!(HC128::from_slice(key.as_slice(), &iv[1..]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+}
+
+#[test]
+fn hc256() {
+    // the zero key and IV
+    assert_eq!(key_stream(&mut HC256::new([0u8; 32], [0u8; 32]), 32, 32), hex("5b078985d8f6f30d42c5c02fa6b6795153f06534801f89f24e74248b720b4818"));
+
+    let tgt = hex("ebbdcc7f208a649c9a34c52e0b01d22d1e36102c73049d9579ee0ce7708dce6893451d55e837c5131c0ac0dd9a42b81c156f5df6b6469a9f440daf06f651732498dbb0cf5638cb1aedff80dd137c0e880d414a96bc0b154f4b23b132edb9c028712ccf27");
+    let (key, iv) = ((0u8..32).collect::<Vec<_>>(), (32u8..64).collect::<Vec<_>>());
+    for &step in [1usize, 13, 64, 100].iter() {
+        let mut hc = HC256::from_slice(key.as_slice(), iv.as_slice()).unwrap();
+        assert_eq!(key_stream(&mut hc, tgt.len(), step), tgt, "step: {}", step);
+    }
+    assert!(HC256::from_slice(&key[..16], iv.as_slice()).is_err());
+}
+
+#[test]
+fn rabbit() {
+    // RFC 4503 A.1, A.2, the zero key without and with the zero IV, the bytes of every block are reversed in the RFC
+    assert_eq!(key_stream(&mut Rabbit::without_iv([0u8; 16]), 48, 48),
+        hex("02f74a1c26456bf5ecd6a536f05457b1a78ac689476c697b390c9cc515d8e88896d6731688d168da51d40c70c3a116f4"));
+    assert_eq!(key_stream(&mut Rabbit::new([0u8; 16], [0u8; 8]), 48, 48),
+        hex("edb70567375dcd7cd89554f85e27a7c68d4adc7032298f7bd4eff504aca6295f668fbf478adb2be51e6cde292b82de2a"));
+
+    let tgt = hex("f28919dda128f8f90a30346e9794d2b74c69a2d9913727bc5a3018e6332af7f3be3ac3efb368f43a4cb85867b81c91f924290c816b8b578898c57fb4c0ba05bd9293046b1d02108c04d3806210c95d2c148c35e0988ff423a388d7f30668696426e92ac7");
+    let (key, iv) = ((0u8..16).collect::<Vec<_>>(), (0u8..8).collect::<Vec<_>>());
+    for &step in [1usize, 5, 16, 100].iter() {
+        let mut rabbit = Rabbit::from_slice(key.as_slice(), iv.as_slice()).unwrap();
+        assert_eq!(key_stream(&mut rabbit, tgt.len(), step), tgt, "step: {}", step);
+    }
+
+    let mut rabbit = Rabbit::from_slice(key.as_slice(), iv.as_slice()).unwrap();
+    let (mut c, mut m) = (Vec::new(), Vec::new());
+    rabbit.xor_key_stream(&mut c, b"the eSTREAM portfolio").unwrap();
+    Rabbit::from_slice(key.as_slice(), iv.as_slice()).unwrap().xor_key_stream(&mut m, c.as_slice()).unwrap();
+    assert_eq!(m, b"the eSTREAM portfolio");
+    assert!(Rabbit::from_slice(key.as_slice(), &iv[..4]).is_err());
+    assert!(Rabbit::from_slice(&key[..8], &[]).is_err());
+}
+
+fn check_reset<S: StreamCipher + Clone>(mut s: S) {
+    let tgt = key_stream(&mut s.clone(), 300, 300);
+    let _ = key_stream(&mut s, 77, 7);
+    s.reset();
+    assert_eq!(key_stream(&mut s, 300, 13), tgt);
+    s.reset();
+    assert_eq!(key_stream(&mut s, 300, 300), tgt);
+}
+
+#[test]
+fn stream_cipher_reset() {
+    check_reset(HC128::new([3u8; 16], [5u8; 16]));
+    check_reset(HC256::new([3u8; 32], [5u8; 32]));
+    check_reset(Rabbit::new([3u8; 16], [5u8; 8]));
+    check_reset(Rabbit::without_iv([3u8; 16]));
+}
+
+#[test]
+fn stream_cipher_reset_with_iv() {
+    let mut hc = HC128::new([3u8; 16], [5u8; 16]);
+    let _ = key_stream(&mut hc, 77, 7);
+    hc.reset_with_iv([6u8; 16].as_ref()).unwrap();
+    assert_eq!(key_stream(&mut hc, 300, 13), key_stream(&mut HC128::new([3u8; 16], [6u8; 16]), 300, 300));
+    assert_eq!(hc.reset_with_iv([6u8; 16].as_ref()).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(hc.reset_with_iv([7u8; 8].as_ref()).is_err());
+
+    let mut hc = HC256::new([3u8; 32], [5u8; 32]);
+    hc.reset_with_iv([6u8; 32].as_ref()).unwrap();
+    assert_eq!(key_stream(&mut hc, 300, 13), key_stream(&mut HC256::new([3u8; 32], [6u8; 32]), 300, 300));
+    assert!(hc.reset_with_iv([6u8; 32].as_ref()).is_err());
+
+    let mut rabbit = Rabbit::without_iv([3u8; 16]);
+    rabbit.reset_with_iv([6u8; 8].as_ref()).unwrap();
+    assert_eq!(key_stream(&mut rabbit, 300, 13), key_stream(&mut Rabbit::new([3u8; 16], [6u8; 8]), 300, 300));
+    assert!(rabbit.reset_with_iv([6u8; 8].as_ref()).is_err());
+    rabbit.reset_with_iv(&[]).unwrap();
+    assert_eq!(key_stream(&mut rabbit, 300, 300), key_stream(&mut Rabbit::without_iv([3u8; 16]), 300, 300));
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use crate::{StreamCipher, CryptoError, CryptoErrorKind};
+use crate::estream::key_stream::{KeyStreamBuf, wipe};
+
+const HC128_TABLE_LEN: usize = 512;
+// the key stream words generated by one block
+const HC128_BLOCK_WORDS: usize = 16;
+
+#[inline]
+fn f1(x: u32) -> u32 {
+    x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+}
+
+#[inline]
+fn f2(x: u32) -> u32 {
+    x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+}
+
+/// HC-128 stream cipher
+/// Hongjun Wu. The Stream Cipher HC-128, eSTREAM portfolio.
+///
+/// the 128 bits key and the 128 bits IV, the key stream of one IV must not be more than $2^{64}$ bits.
+#[derive(Clone)]
+pub struct HC128 {
+    p: [u32; HC128_TABLE_LEN],
+    q: [u32; HC128_TABLE_LEN],
+    // the step counter modulo 1024
+    i: usize,
+    key_stream: KeyStreamBuf,
+    // for the `reset` and the `reset_with_iv`, the key is wiped on the drop
+    key: [u8; 16],
+    iv: Vec<u8>,
+}
+
+impl HC128 {
+    pub fn new(key: [u8; 16], iv: [u8; 16]) -> Self {
+        Self::from_slice(key.as_ref(), iv.as_ref()).unwrap()
+    }
+
+    pub fn from_slice(key: &[u8], iv: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != 16 || iv.len() != 16 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The HC-128 key and IV must be 16 bytes, but got {} and {}", key.len(), iv.len())));
+        }
+
+        let mut w = vec![0u32; 1280];
+        w[..4].iter_mut().zip(key.chunks(4)).for_each(|(w, k)| *w = u32::from_le_bytes([k[0], k[1], k[2], k[3]]));
+        w[8..12].iter_mut().zip(iv.chunks(4)).for_each(|(w, v)| *w = u32::from_le_bytes([v[0], v[1], v[2], v[3]]));
+        w.copy_within(0..4, 4);
+        w.copy_within(8..12, 12);
+        for i in 16..1280 {
+            w[i] = f2(w[i - 2]).wrapping_add(w[i - 7]).wrapping_add(f1(w[i - 15])).wrapping_add(w[i - 16]).wrapping_add(i as u32);
+        }
+
+        let mut hc = Self {
+            p: [0u32; HC128_TABLE_LEN],
+            q: [0u32; HC128_TABLE_LEN],
+            i: 0,
+            key_stream: KeyStreamBuf::new(HC128_BLOCK_WORDS << 2),
+            key: [0u8; 16],
+            iv: iv.to_vec(),
+        };
+        hc.key.copy_from_slice(key);
+        hc.p.copy_from_slice(&w[256..768]);
+        hc.q.copy_from_slice(&w[768..1280]);
+
+        // run the cipher 1024 steps and use the outputs to replace the table elements
+        for _ in 0..1024 {
+            let (j, is_p) = (hc.i & 511, hc.i < 512);
+            let s = hc.step();
+            if is_p {
+                hc.p[j] = s;
+            } else {
+                hc.q[j] = s;
+            }
+        }
+        Ok(hc)
+    }
+
+    /// reset the key stream to the fresh `iv`(16 bytes) under the same key, it's the way to encrypt the next message.
+    /// The `iv` equal to the current IV is rejected.
+    pub fn reset_with_iv(&mut self, iv: &[u8]) -> Result<(), CryptoError> {
+        if iv == self.iv.as_slice() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The HC-128 IV must be fresh, it's the same as the current IV"));
+        }
+        *self = Self::from_slice(self.key.as_ref(), iv)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn h1(&self, x: u32) -> u32 {
+        self.q[(x & 0xff) as usize].wrapping_add(self.q[256 + ((x >> 16) & 0xff) as usize])
+    }
+
+    #[inline]
+    fn h2(&self, x: u32) -> u32 {
+        self.p[(x & 0xff) as usize].wrapping_add(self.p[256 + ((x >> 16) & 0xff) as usize])
+    }
+
+    fn step(&mut self) -> u32 {
+        let j = self.i & 511;
+        let (j3, j10, j511, j12) = ((j + 509) & 511, (j + 502) & 511, (j + 1) & 511, (j + 500) & 511);
+        let s = if self.i < 512 {
+            let (x, y, z) = (self.p[j3], self.p[j10], self.p[j511]);
+            self.p[j] = self.p[j].wrapping_add((x.rotate_right(10) ^ z.rotate_right(23)).wrapping_add(y.rotate_right(8)));
+            self.h1(self.p[j12]) ^ self.p[j]
+        } else {
+            let (x, y, z) = (self.q[j3], self.q[j10], self.q[j511]);
+            self.q[j] = self.q[j].wrapping_add((x.rotate_left(10) ^ z.rotate_left(23)).wrapping_add(y.rotate_left(8)));
+            self.h2(self.q[j12]) ^ self.q[j]
+        };
+
+        self.i = (self.i + 1) & 1023;
+        s
+    }
+}
+
+impl StreamCipher for HC128 {
+    fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError> {
+        let mut key_stream = std::mem::replace(&mut self.key_stream, KeyStreamBuf::new(0));
+        key_stream.xor(dst, data, |buf| {
+            for _ in 0..HC128_BLOCK_WORDS {
+                buf.extend_from_slice(self.step().to_le_bytes().as_ref());
+            }
+        });
+        self.key_stream = key_stream;
+        Ok(())
+    }
+
+    /// # Security
+    ///
+    /// the key stream of the construction-time IV is generated again, only re-process the same message after the
+    /// `reset`, and encrypt the next message by the `reset_with_iv` with a fresh IV.
+    fn reset(&mut self) {
+        *self = Self::from_slice(self.key.as_ref(), self.iv.as_slice()).unwrap();
+    }
+}
+
+impl Drop for HC128 {
+    fn drop(&mut self) {
+        wipe(self.key.as_mut());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{StreamCipher, CryptoError, CryptoErrorKind};
+use crate::estream::key_stream::{KeyStreamBuf, wipe};
+
+const HC256_TABLE_LEN: usize = 1024;
+// the key stream words generated by one block
+const HC256_BLOCK_WORDS: usize = 16;
+
+#[inline]
+fn f1(x: u32) -> u32 {
+    x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+}
+
+#[inline]
+fn f2(x: u32) -> u32 {
+    x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+}
+
+/// HC-256 stream cipher
+/// Hongjun Wu. A New Stream Cipher HC-256, FSE 2004.
+///
+/// the 256 bits key and the 256 bits IV, the key stream of one IV must not be more than $2^{128}$ bits.
+#[derive(Clone)]
+pub struct HC256 {
+    p: [u32; HC256_TABLE_LEN],
+    q: [u32; HC256_TABLE_LEN],
+    // the step counter modulo 2048
+    i: usize,
+    key_stream: KeyStreamBuf,
+    // for the `reset` and the `reset_with_iv`, the key is wiped on the drop
+    key: [u8; 32],
+    iv: Vec<u8>,
+}
+
+impl HC256 {
+    pub fn new(key: [u8; 32], iv: [u8; 32]) -> Self {
+        Self::from_slice(key.as_ref(), iv.as_ref()).unwrap()
+    }
+
+    pub fn from_slice(key: &[u8], iv: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != 32 || iv.len() != 32 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The HC-256 key and IV must be 32 bytes, but got {} and {}", key.len(), iv.len())));
+        }
+
+        let mut w = vec![0u32; 2560];
+        w[..8].iter_mut().zip(key.chunks(4)).for_each(|(w, k)| *w = u32::from_le_bytes([k[0], k[1], k[2], k[3]]));
+        w[8..16].iter_mut().zip(iv.chunks(4)).for_each(|(w, v)| *w = u32::from_le_bytes([v[0], v[1], v[2], v[3]]));
+        for i in 16..2560 {
+            w[i] = f2(w[i - 2]).wrapping_add(w[i - 7]).wrapping_add(f1(w[i - 15])).wrapping_add(w[i - 16]).wrapping_add(i as u32);
+        }
+
+        let mut hc = Self {
+            p: [0u32; HC256_TABLE_LEN],
+            q: [0u32; HC256_TABLE_LEN],
+            i: 0,
+            key_stream: KeyStreamBuf::new(HC256_BLOCK_WORDS << 2),
+            key: [0u8; 32],
+            iv: iv.to_vec(),
+        };
+        hc.key.copy_from_slice(key);
+        hc.p.copy_from_slice(&w[512..1536]);
+        hc.q.copy_from_slice(&w[1536..2560]);
+
+        // run the cipher 4096 steps without generating the output
+        for _ in 0..4096 {
+            hc.step();
+        }
+        Ok(hc)
+    }
+
+    /// reset the key stream to the fresh `iv`(32 bytes) under the same key, it's the way to encrypt the next message.
+    /// The `iv` equal to the current IV is rejected.
+    pub fn reset_with_iv(&mut self, iv: &[u8]) -> Result<(), CryptoError> {
+        if iv == self.iv.as_slice() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The HC-256 IV must be fresh, it's the same as the current IV"));
+        }
+        *self = Self::from_slice(self.key.as_ref(), iv)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn h(t: &[u32], x: u32) -> u32 {
+        let x = x.to_le_bytes();
+        t[x[0] as usize].wrapping_add(t[256 + x[1] as usize]).wrapping_add(t[512 + x[2] as usize]).wrapping_add(t[768 + x[3] as usize])
+    }
+
+    fn step(&mut self) -> u32 {
+        let j = self.i & 1023;
+        let (j3, j10, j1023, j12) = ((j + 1021) & 1023, (j + 1014) & 1023, (j + 1) & 1023, (j + 1012) & 1023);
+        let (t, u) = if self.i < 1024 { (&mut self.p, &self.q) } else { (&mut self.q, &self.p) };
+        let (x, y) = (t[j3], t[j1023]);
+        let g = (x.rotate_right(10) ^ y.rotate_right(23)).wrapping_add(u[((x ^ y) & 1023) as usize]);
+        t[j] = t[j].wrapping_add(t[j10]).wrapping_add(g);
+        let s = Self::h(u, t[j12]) ^ t[j];
+
+        self.i = (self.i + 1) & 2047;
+        s
+    }
+}
+
+impl StreamCipher for HC256 {
+    fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError> {
+        let mut key_stream = std::mem::replace(&mut self.key_stream, KeyStreamBuf::new(0));
+        key_stream.xor(dst, data, |buf| {
+            for _ in 0..HC256_BLOCK_WORDS {
+                buf.extend_from_slice(self.step().to_le_bytes().as_ref());
+            }
+        });
+        self.key_stream = key_stream;
+        Ok(())
+    }
+
+    /// # Security
+    ///
+    /// the key stream of the construction-time IV is generated again, only re-process the same message after the
+    /// `reset`, and encrypt the next message by the `reset_with_iv` with a fresh IV.
+    fn reset(&mut self) {
+        *self = Self::from_slice(self.key.as_ref(), self.iv.as_slice()).unwrap();
+    }
+}
+
+impl Drop for HC256 {
+    fn drop(&mut self) {
+        wipe(self.key.as_mut());
+    }
+}
@@ -0,0 +1,32 @@
+/// The unused key stream bytes of the last generated block
+#[derive(Clone)]
+pub(super) struct KeyStreamBuf {
+    buf: Vec<u8>,
+}
+
+impl KeyStreamBuf {
+    pub(super) fn new(block_len: usize) -> Self {
+        Self { buf: Vec::with_capacity(block_len) }
+    }
+
+    /// xor the `data` with the buffered key stream first, then with the blocks appended by the `next_block`
+    pub(super) fn xor<F: FnMut(&mut Vec<u8>)>(&mut self, dst: &mut Vec<u8>, data: &[u8], mut next_block: F) {
+        dst.clear();
+        let mut data = data;
+        while !data.is_empty() {
+            if self.buf.is_empty() {
+                next_block(&mut self.buf);
+            }
+
+            let len = self.buf.len().min(data.len());
+            dst.extend(data[..len].iter().zip(self.buf.drain(..len)).map(|(&a, b)| a ^ b));
+            data = &data[len..];
+        }
+    }
+}
+
+/// overwrite the copy of the key by zeros when the cipher is dropped, the volatile writes are not optimized out
+pub(super) fn wipe(buf: &mut [u8]) {
+    buf.iter_mut().for_each(|x| unsafe { std::ptr::write_volatile(x, 0) });
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
@@ -0,0 +1,20 @@
+//! The eSTREAM portfolio software ciphers: HC-128, HC-256 and Rabbit(RFC 4503)
+//!
+//! https://www.ecrypt.eu.org/stream/
+//!
+//! They are provided for the interoperability with the legacy protocols and the research, the key stream of them is
+//! generated by the `StreamCipher::xor_key_stream`.
+
+mod key_stream;
+
+mod hc128;
+pub use hc128::HC128;
+
+mod hc256;
+pub use hc256::HC256;
+
+mod rabbit;
+pub use rabbit::Rabbit;
+
+#[cfg(test)]
+mod estream_test;
\ No newline at end of file
@@ -0,0 +1,141 @@
+use crate::{StreamCipher, CryptoError, CryptoErrorKind};
+use crate::estream::key_stream::{KeyStreamBuf, wipe};
+
+const RABBIT_A: [u32; 8] = [0x4D34D34D, 0xD34D34D3, 0x34D34D34, 0x4D34D34D, 0xD34D34D3, 0x34D34D34, 0x4D34D34D, 0xD34D34D3];
+const RABBIT_BLOCK_LEN: usize = 16;
+
+/// Rabbit stream cipher
+/// RFC 4503
+///
+/// the 128 bits key and the optional 64 bits IV, the key stream of one IV must not be more than $2^{64}$ blocks.
+/// The key stream is the little-endian 16 bytes blocks, note that the test vectors of the RFC 4503 show the bytes of
+/// every block from the most significant byte `S[15..8]` to the least significant byte `S[7..0]`.
+#[derive(Clone)]
+pub struct Rabbit {
+    x: [u32; 8],
+    c: [u32; 8],
+    carry: u32,
+    key_stream: KeyStreamBuf,
+    // for the `reset` and the `reset_with_iv`, the key is wiped on the drop
+    key: [u8; 16],
+    iv: Vec<u8>,
+}
+
+impl Rabbit {
+    pub fn new(key: [u8; 16], iv: [u8; 8]) -> Self {
+        Self::from_slice(key.as_ref(), iv.as_ref()).unwrap()
+    }
+
+    /// the key setup without the IV setup(RFC 4503 2.3)
+    pub fn without_iv(key: [u8; 16]) -> Self {
+        Self::from_slice(key.as_ref(), &[]).unwrap()
+    }
+
+    /// the `iv` must be 8 bytes or empty, the empty `iv` means no IV setup
+    pub fn from_slice(key: &[u8], iv: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != 16 || (!iv.is_empty() && iv.len() != 8) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The Rabbit key must be 16 bytes and the IV must be 8 bytes or empty, but got {} and {}", key.len(), iv.len())));
+        }
+
+        let k = key.chunks(4).map(|k| u32::from_le_bytes([k[0], k[1], k[2], k[3]])).collect::<Vec<_>>();
+        let mut r = Self {
+            x: [
+                k[0], (k[3] << 16) | (k[2] >> 16), k[1], (k[0] << 16) | (k[3] >> 16),
+                k[2], (k[1] << 16) | (k[0] >> 16), k[3], (k[2] << 16) | (k[1] >> 16),
+            ],
+            c: [
+                k[2].rotate_left(16), (k[0] & 0xffff0000) | (k[1] & 0xffff), k[3].rotate_left(16), (k[1] & 0xffff0000) | (k[2] & 0xffff),
+                k[0].rotate_left(16), (k[2] & 0xffff0000) | (k[3] & 0xffff), k[1].rotate_left(16), (k[3] & 0xffff0000) | (k[0] & 0xffff),
+            ],
+            carry: 0,
+            key_stream: KeyStreamBuf::new(RABBIT_BLOCK_LEN),
+            key: [0u8; 16],
+            iv: iv.to_vec(),
+        };
+        r.key.copy_from_slice(key);
+
+        (0..4).for_each(|_| r.next_state());
+        for j in 0..8 {
+            r.c[j] ^= r.x[(j + 4) & 7];
+        }
+
+        if !iv.is_empty() {
+            let (i0, i2) = (u32::from_le_bytes([iv[0], iv[1], iv[2], iv[3]]), u32::from_le_bytes([iv[4], iv[5], iv[6], iv[7]]));
+            let (i1, i3) = ((i0 >> 16) | (i2 & 0xffff0000), (i2 << 16) | (i0 & 0xffff));
+            r.c.iter_mut().zip([i0, i1, i2, i3].iter().cycle()).for_each(|(c, &i)| *c ^= i);
+            (0..4).for_each(|_| r.next_state());
+        }
+
+        Ok(r)
+    }
+
+    /// reset the key stream to the fresh `iv`(8 bytes, or empty for no IV setup) under the same key, it's the way to
+    /// encrypt the next message. The `iv` equal to the current IV is rejected.
+    pub fn reset_with_iv(&mut self, iv: &[u8]) -> Result<(), CryptoError> {
+        if iv == self.iv.as_slice() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The Rabbit IV must be fresh, it's the same as the current IV"));
+        }
+        *self = Self::from_slice(self.key.as_ref(), iv)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn g(u: u32, v: u32) -> u32 {
+        let x = u.wrapping_add(v) as u64;
+        let x = x * x;
+        (x ^ (x >> 32)) as u32
+    }
+
+    fn next_state(&mut self) {
+        for (c, &a) in self.c.iter_mut().zip(RABBIT_A.iter()) {
+            let t = (*c as u64) + (a as u64) + (self.carry as u64);
+            *c = t as u32;
+            self.carry = (t >> 32) as u32;
+        }
+
+        let mut g = [0u32; 8];
+        g.iter_mut().zip(self.x.iter().zip(self.c.iter())).for_each(|(g, (&x, &c))| *g = Self::g(x, c));
+        for j in 0..8 {
+            let (g1, g2) = (g[(j + 7) & 7], g[(j + 6) & 7]);
+            self.x[j] = if j & 1 == 0 {
+                g[j].wrapping_add(g1.rotate_left(16)).wrapping_add(g2.rotate_left(16))
+            } else {
+                g[j].wrapping_add(g1.rotate_left(8)).wrapping_add(g2)
+            };
+        }
+    }
+
+    fn next_block(&mut self, buf: &mut Vec<u8>) {
+        self.next_state();
+        let x = &self.x;
+        let s = [
+            x[0] ^ (x[5] >> 16) ^ (x[3] << 16), x[2] ^ (x[7] >> 16) ^ (x[5] << 16),
+            x[4] ^ (x[1] >> 16) ^ (x[7] << 16), x[6] ^ (x[3] >> 16) ^ (x[1] << 16),
+        ];
+        s.iter().for_each(|s| buf.extend_from_slice(s.to_le_bytes().as_ref()));
+    }
+}
+
+impl StreamCipher for Rabbit {
+    fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError> {
+        let mut key_stream = std::mem::replace(&mut self.key_stream, KeyStreamBuf::new(0));
+        key_stream.xor(dst, data, |buf| self.next_block(buf));
+        self.key_stream = key_stream;
+        Ok(())
+    }
+
+    /// # Security
+    ///
+    /// the key stream of the construction-time IV is generated again, only re-process the same message after the
+    /// `reset`, and encrypt the next message by the `reset_with_iv` with a fresh IV.
+    fn reset(&mut self) {
+        *self = Self::from_slice(self.key.as_ref(), self.iv.as_slice()).unwrap();
+    }
+}
+
+impl Drop for Rabbit {
+    fn drop(&mut self) {
+        wipe(self.key.as_mut());
+    }
+}
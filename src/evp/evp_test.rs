@@ -0,0 +1,79 @@
+use crate::evp::{EvpDigest, EvpCipher, EvpBlockCipher, EvpCipherMode};
+use crate::test_util::hex;
+
+#[test]
+fn evp_digest_lookup() {
+    assert_eq!(EvpDigest::from_nid(672), Some(EvpDigest::SHA256));
+    assert_eq!(EvpDigest::from_oid("2.16.840.1.101.3.4.2.1"), Some(EvpDigest::SHA256));
+    assert_eq!(EvpDigest::from_name("sha256"), Some(EvpDigest::SHA256));
+    assert_eq!(EvpDigest::from_name("SHA3-256"), Some(EvpDigest::SHA3_256));
+    assert_eq!(EvpDigest::from_name("sha-256"), None);
+    assert_eq!(EvpDigest::from_nid(0), None);
+    
+    let mut md = EvpDigest::from_name("SHA256").unwrap().new_digest();
+    let mut d = Vec::new();
+    md.write(b"abc");
+    md.checksum(&mut d);
+    assert_eq!(d, hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+    
+    for x in EvpDigest::all() {
+        assert_eq!(EvpDigest::from_nid(x.nid()), Some(x));
+        assert_eq!(EvpDigest::from_oid(x.oid()), Some(x));
+        assert_eq!(EvpDigest::from_name(x.short_name()), Some(x));
+        assert_eq!(EvpDigest::from_name(x.long_name()), Some(x));
+        let mut md = x.new_digest();
+        md.write(b"abc");
+        md.checksum(&mut d);
+        assert_eq!((d.len(), (md.bits_len() + 7) >> 3), (x.digest_len(), x.digest_len()), "case: {}", x.short_name());
+    }
+}
+
+#[test]
+fn evp_cipher_lookup() {
+    let aes128cbc = EvpCipher { cipher: EvpBlockCipher::AES128, mode: EvpCipherMode::CBC };
+    assert_eq!(EvpCipher::from_nid(419), Some(aes128cbc));
+    assert_eq!(EvpCipher::from_oid("2.16.840.1.101.3.4.1.2"), Some(aes128cbc));
+    assert_eq!(EvpCipher::from_name("AES-128-CBC"), Some(aes128cbc));
+    assert_eq!(EvpCipher::from_name("aes-128-ctr").unwrap().oid(), None);
+    
+    // NIST SP 800-38A F.2.1, F.3.7, F.4.1, F.5.1
+    let (key, data) = (hex("2b7e151628aed2a6abf7158809cf4f3c"), hex("6bc1bee22e409f96e93d7e117393172aae2d"));
+    let (iv, ctr) = (hex("000102030405060708090a0b0c0d0e0f"), hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff"));
+    let cases = [
+        ("aes-128-cbc", iv.as_slice(), "7649abac8119b246cee98e9b12e9197d"),
+        ("aes-128-cfb8", iv.as_slice(), "3b79424c9c0dd436bace9e0ed4586a4f32b9"),
+        ("aes-128-ofb", iv.as_slice(), "3b3fd92eb72dad20333449f8e83cfb4a"),
+        ("aes-128-ctr", ctr.as_slice(), "874d6191b620e3261bef6864990db6ce"),
+    ];
+    let mut dst = Vec::new();
+    for &(name, iv, c) in cases.iter() {
+        let cipher = EvpCipher::from_name(name).unwrap().new_cipher(key.as_slice(), iv).unwrap();
+        cipher.encrypt(&mut dst, data.as_slice()).unwrap();
+        assert_eq!(&dst[..(c.len() >> 1)], hex(c).as_slice(), "case: {}", name);
+    }
+    
+    // GB/T 32907-2016 A.1
+    let k = hex("0123456789abcdeffedcba9876543210");
+    let sm4 = EvpCipher::from_nid(1133).unwrap().new_cipher(k.as_slice(), &[]).unwrap();
+    sm4.encrypt(&mut dst, k.as_slice()).unwrap();
+    assert_eq!((dst.len(), &dst[..16]), (32, hex("681edf34d206965e86b3e94f536e4246").as_slice()));
+    
+    let mut msg = Vec::new();
+    for x in EvpCipher::all() {
+        assert_eq!(EvpCipher::from_nid(x.nid()), Some(x));
+        assert_eq!(EvpCipher::from_name(x.short_name()), Some(x));
+        assert_eq!(EvpCipher::from_name(x.long_name()), Some(x));
+        if let Some(oid) = x.oid() {
+            assert_eq!(EvpCipher::from_oid(oid), Some(x));
+        }
+        
+        let (key, iv) = (vec![0x5au8; x.key_len()], vec![0xa5u8; x.iv_len()]);
+        let cipher = x.new_cipher(key.as_slice(), iv.as_slice()).unwrap();
+        cipher.encrypt(&mut dst, data.as_slice()).unwrap();
+        // the counter of the CTR continues as the EVP context, so the new cipher is used to decrypt
+        let cipher = x.new_cipher(key.as_slice(), iv.as_slice()).unwrap();
+        cipher.decrypt(&mut msg, dst.as_slice()).unwrap();
+        assert_eq!(msg, data, "case: {}", x.short_name());
+        assert!(x.new_cipher(&key[1..], iv.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! OpenSSL EVP interoperability
+//!
+//! The digests and the ciphers can be obtained by the OpenSSL NID, the OID(dotted decimal string) or the OpenSSL
+//! short/long name(e.g. `SHA256`/`sha256`, `AES-128-CBC`/`aes-128-cbc`), which are carried by the wire protocols.
+
+mod objects;
+pub use objects::{EvpDigest, EvpCipher, EvpBlockCipher, EvpCipherMode};
+
+#[cfg(test)]
+mod evp_test;
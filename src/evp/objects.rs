@@ -0,0 +1,292 @@
+//! The OpenSSL object identifiers of the digests and the ciphers
+//! OpenSSL `crypto/objects/objects.txt`, `include/openssl/obj_mac.h`
+
+use crate::{Cipher, Digest, CryptoError, CryptoErrorKind, AES, DES, TDES, MD5, SM3, SM4};
+use crate::oid::Oid;
+use crate::cipher_mode::{ECB, CBC, CFB, OFB, CTR, PKCS7Padding, EmptyPadding, FixedInitialVec, DefaultCounter};
+
+/// The digest algorithm which is identified by the OpenSSL
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvpDigest {
+    MD5,
+    SHA1,
+    SHA224,
+    SHA256,
+    SHA384,
+    SHA512,
+    SHA512T224,
+    SHA512T256,
+    SHA3_224,
+    SHA3_256,
+    SHA3_384,
+    SHA3_512,
+    SM3,
+}
+
+/// (digest, NID, short name, long name, OID)
+const DIGESTS: [(EvpDigest, i32, &str, &str, &str); 13] = [
+    (EvpDigest::MD5, 4, "MD5", "md5", Oid::MD5.dotted()),
+    (EvpDigest::SHA1, 64, "SHA1", "sha1", Oid::SHA1.dotted()),
+    (EvpDigest::SHA224, 675, "SHA224", "sha224", Oid::SHA224.dotted()),
+    (EvpDigest::SHA256, 672, "SHA256", "sha256", Oid::SHA256.dotted()),
+    (EvpDigest::SHA384, 673, "SHA384", "sha384", Oid::SHA384.dotted()),
+    (EvpDigest::SHA512, 674, "SHA512", "sha512", Oid::SHA512.dotted()),
+    (EvpDigest::SHA512T224, 1094, "SHA512-224", "sha512-224", Oid::SHA512_224.dotted()),
+    (EvpDigest::SHA512T256, 1095, "SHA512-256", "sha512-256", Oid::SHA512_256.dotted()),
+    (EvpDigest::SHA3_224, 1096, "SHA3-224", "sha3-224", Oid::SHA3_224.dotted()),
+    (EvpDigest::SHA3_256, 1097, "SHA3-256", "sha3-256", Oid::SHA3_256.dotted()),
+    (EvpDigest::SHA3_384, 1098, "SHA3-384", "sha3-384", Oid::SHA3_384.dotted()),
+    (EvpDigest::SHA3_512, 1099, "SHA3-512", "sha3-512", Oid::SHA3_512.dotted()),
+    (EvpDigest::SM3, 1143, "SM3", "sm3", Oid::SM3.dotted()),
+];
+
+impl EvpDigest {
+    fn entry(&self) -> &'static (EvpDigest, i32, &'static str, &'static str, &'static str) {
+        DIGESTS.iter().find(|x| x.0 == *self).unwrap()
+    }
+
+    /// all the digests in the NID table order, as the `EVP_MD_do_all`
+    pub fn all() -> impl Iterator<Item = EvpDigest> {
+        DIGESTS.iter().map(|x| x.0)
+    }
+
+    pub fn from_nid(nid: i32) -> Option<Self> {
+        DIGESTS.iter().find(|x| x.1 == nid).map(|x| x.0)
+    }
+
+    /// `oid`: the dotted decimal string, e.g. `2.16.840.1.101.3.4.2.1`;
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        DIGESTS.iter().find(|x| x.4 == oid).map(|x| x.0)
+    }
+
+    /// the OpenSSL short name or long name(case-insensitive), as the `EVP_get_digestbyname`
+    pub fn from_name(name: &str) -> Option<Self> {
+        DIGESTS.iter().find(|x| x.2.eq_ignore_ascii_case(name) || x.3.eq_ignore_ascii_case(name)).map(|x| x.0)
+    }
+
+    pub fn nid(&self) -> i32 {
+        self.entry().1
+    }
+
+    /// the OpenSSL short name, e.g. `SHA256`
+    pub fn short_name(&self) -> &'static str {
+        self.entry().2
+    }
+
+    /// the OpenSSL long name, e.g. `sha256`
+    pub fn long_name(&self) -> &'static str {
+        self.entry().3
+    }
+
+    pub fn oid(&self) -> &'static str {
+        self.entry().4
+    }
+
+    /// the digest length in bytes
+    pub fn digest_len(&self) -> usize {
+        match self {
+            EvpDigest::MD5 => 16,
+            EvpDigest::SHA1 => 20,
+            EvpDigest::SHA224 | EvpDigest::SHA512T224 | EvpDigest::SHA3_224 => 28,
+            EvpDigest::SHA256 | EvpDigest::SHA512T256 | EvpDigest::SHA3_256 | EvpDigest::SM3 => 32,
+            EvpDigest::SHA384 | EvpDigest::SHA3_384 => 48,
+            EvpDigest::SHA512 | EvpDigest::SHA3_512 => 64,
+        }
+    }
+
+    /// create the digest, as the `EVP_DigestInit`
+    pub fn new_digest(&self) -> Box<dyn Digest> {
+        match self {
+            EvpDigest::MD5 => Box::new(MD5::new()),
+            EvpDigest::SHA1 => Box::new(crate::sha::SHA1::new()),
+            EvpDigest::SHA224 => Box::new(crate::sha::SHA224::new()),
+            EvpDigest::SHA256 => Box::new(crate::sha::SHA256::new()),
+            EvpDigest::SHA384 => Box::new(crate::sha::SHA384::new()),
+            EvpDigest::SHA512 => Box::new(crate::sha::SHA512::new()),
+            EvpDigest::SHA512T224 => Box::new(crate::sha::SHA512T224::new()),
+            EvpDigest::SHA512T256 => Box::new(crate::sha::SHA512T256::new()),
+            EvpDigest::SHA3_224 => Box::new(crate::sha3::SHA224::new()),
+            EvpDigest::SHA3_256 => Box::new(crate::sha3::SHA256::new()),
+            EvpDigest::SHA3_384 => Box::new(crate::sha3::SHA384::new()),
+            EvpDigest::SHA3_512 => Box::new(crate::sha3::SHA512::new()),
+            EvpDigest::SM3 => Box::new(SM3::new()),
+        }
+    }
+}
+
+/// The block cipher of the `EvpCipher`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvpBlockCipher {
+    AES128,
+    AES192,
+    AES256,
+    DES,
+    /// the three keys TDES
+    DESEDE3,
+    SM4,
+}
+
+/// The cipher mode of the `EvpCipher`, the ECB and CBC use the PKCS#7 padding as the OpenSSL EVP by default
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvpCipherMode {
+    ECB,
+    CBC,
+    /// the 8 bits CFB
+    CFB8,
+    OFB,
+    CTR,
+}
+
+/// The cipher which is identified by the OpenSSL
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvpCipher {
+    pub cipher: EvpBlockCipher,
+    pub mode: EvpCipherMode,
+}
+
+const fn evp_cipher(cipher: EvpBlockCipher, mode: EvpCipherMode) -> EvpCipher {
+    EvpCipher { cipher, mode }
+}
+
+/// (cipher, NID, short name, long name, OID)
+const CIPHERS: [(EvpCipher, i32, &str, &str, Option<&str>); 25] = [
+    (evp_cipher(EvpBlockCipher::AES128, EvpCipherMode::ECB), 418, "AES-128-ECB", "aes-128-ecb", Some(Oid::AES128_ECB.dotted())),
+    (evp_cipher(EvpBlockCipher::AES128, EvpCipherMode::CBC), 419, "AES-128-CBC", "aes-128-cbc", Some(Oid::AES128_CBC.dotted())),
+    (evp_cipher(EvpBlockCipher::AES128, EvpCipherMode::OFB), 420, "AES-128-OFB", "aes-128-ofb", Some(Oid::AES128_OFB.dotted())),
+    (evp_cipher(EvpBlockCipher::AES128, EvpCipherMode::CFB8), 653, "AES-128-CFB8", "aes-128-cfb8", None),
+    (evp_cipher(EvpBlockCipher::AES128, EvpCipherMode::CTR), 904, "AES-128-CTR", "aes-128-ctr", None),
+    (evp_cipher(EvpBlockCipher::AES192, EvpCipherMode::ECB), 422, "AES-192-ECB", "aes-192-ecb", Some(Oid::AES192_ECB.dotted())),
+    (evp_cipher(EvpBlockCipher::AES192, EvpCipherMode::CBC), 423, "AES-192-CBC", "aes-192-cbc", Some(Oid::AES192_CBC.dotted())),
+    (evp_cipher(EvpBlockCipher::AES192, EvpCipherMode::OFB), 424, "AES-192-OFB", "aes-192-ofb", Some(Oid::AES192_OFB.dotted())),
+    (evp_cipher(EvpBlockCipher::AES192, EvpCipherMode::CFB8), 654, "AES-192-CFB8", "aes-192-cfb8", None),
+    (evp_cipher(EvpBlockCipher::AES192, EvpCipherMode::CTR), 905, "AES-192-CTR", "aes-192-ctr", None),
+    (evp_cipher(EvpBlockCipher::AES256, EvpCipherMode::ECB), 426, "AES-256-ECB", "aes-256-ecb", Some(Oid::AES256_ECB.dotted())),
+    (evp_cipher(EvpBlockCipher::AES256, EvpCipherMode::CBC), 427, "AES-256-CBC", "aes-256-cbc", Some(Oid::AES256_CBC.dotted())),
+    (evp_cipher(EvpBlockCipher::AES256, EvpCipherMode::OFB), 428, "AES-256-OFB", "aes-256-ofb", Some(Oid::AES256_OFB.dotted())),
+    (evp_cipher(EvpBlockCipher::AES256, EvpCipherMode::CFB8), 655, "AES-256-CFB8", "aes-256-cfb8", None),
+    (evp_cipher(EvpBlockCipher::AES256, EvpCipherMode::CTR), 906, "AES-256-CTR", "aes-256-ctr", None),
+    (evp_cipher(EvpBlockCipher::DES, EvpCipherMode::ECB), 29, "DES-ECB", "des-ecb", Some(Oid::DES_ECB.dotted())),
+    (evp_cipher(EvpBlockCipher::DES, EvpCipherMode::CBC), 31, "DES-CBC", "des-cbc", Some(Oid::DES_CBC.dotted())),
+    (evp_cipher(EvpBlockCipher::DES, EvpCipherMode::OFB), 45, "DES-OFB", "des-ofb", Some(Oid::DES_OFB.dotted())),
+    (evp_cipher(EvpBlockCipher::DESEDE3, EvpCipherMode::ECB), 33, "DES-EDE3", "des-ede3", None),
+    (evp_cipher(EvpBlockCipher::DESEDE3, EvpCipherMode::CBC), 44, "DES-EDE3-CBC", "des-ede3-cbc", Some(Oid::DES_EDE3_CBC.dotted())),
+    (evp_cipher(EvpBlockCipher::DESEDE3, EvpCipherMode::OFB), 63, "DES-EDE3-OFB", "des-ede3-ofb", None),
+    (evp_cipher(EvpBlockCipher::SM4, EvpCipherMode::ECB), 1133, "SM4-ECB", "sm4-ecb", Some(Oid::SM4_ECB.dotted())),
+    (evp_cipher(EvpBlockCipher::SM4, EvpCipherMode::CBC), 1134, "SM4-CBC", "sm4-cbc", Some(Oid::SM4_CBC.dotted())),
+    (evp_cipher(EvpBlockCipher::SM4, EvpCipherMode::OFB), 1135, "SM4-OFB", "sm4-ofb", Some(Oid::SM4_OFB.dotted())),
+    (evp_cipher(EvpBlockCipher::SM4, EvpCipherMode::CTR), 1139, "SM4-CTR", "sm4-ctr", Some(Oid::SM4_CTR.dotted())),
+];
+
+fn with_mode<C: 'static + Cipher<Output = usize>>(c: C, mode: EvpCipherMode, iv: &[u8]) -> Result<Box<dyn Cipher<Output = usize>>, CryptoError> {
+    let (fixed_iv, iv) = (FixedInitialVec::new(iv.to_vec()), iv.to_vec());
+    Ok(match mode {
+        EvpCipherMode::ECB => {
+            let p = PKCS7Padding::new(&c);
+            Box::new(ECB::new(c, p))
+        },
+        EvpCipherMode::CBC => {
+            let p = PKCS7Padding::new(&c);
+            Box::new(CBC::new(c, p, fixed_iv)?)
+        },
+        EvpCipherMode::CFB8 => Box::new(CFB::new(c, EmptyPadding::new(), fixed_iv, 8)?),
+        EvpCipherMode::OFB => Box::new(OFB::new(c, fixed_iv)?),
+        EvpCipherMode::CTR => {
+            let counter = DefaultCounter::new(iv, c.block_size().unwrap_or(1) << 3)?;
+            Box::new(CTR::new(c, counter)?)
+        },
+    })
+}
+
+impl EvpCipher {
+    fn entry(&self) -> &'static (EvpCipher, i32, &'static str, &'static str, Option<&'static str>) {
+        CIPHERS.iter().find(|x| x.0 == *self).unwrap()
+    }
+
+    /// all the ciphers in the table order, as the `EVP_CIPHER_do_all`
+    pub fn all() -> impl Iterator<Item = EvpCipher> {
+        CIPHERS.iter().map(|x| x.0)
+    }
+
+    pub fn from_nid(nid: i32) -> Option<Self> {
+        CIPHERS.iter().find(|x| x.1 == nid).map(|x| x.0)
+    }
+
+    /// `oid`: the dotted decimal string, e.g. `2.16.840.1.101.3.4.1.2`;
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        CIPHERS.iter().find(|x| x.4 == Some(oid)).map(|x| x.0)
+    }
+
+    /// the OpenSSL short name or long name(case-insensitive), as the `EVP_get_cipherbyname`
+    pub fn from_name(name: &str) -> Option<Self> {
+        CIPHERS.iter().find(|x| x.2.eq_ignore_ascii_case(name) || x.3.eq_ignore_ascii_case(name)).map(|x| x.0)
+    }
+
+    pub fn nid(&self) -> i32 {
+        self.entry().1
+    }
+
+    /// the OpenSSL short name, e.g. `AES-128-CBC`
+    pub fn short_name(&self) -> &'static str {
+        self.entry().2
+    }
+
+    /// the OpenSSL long name, e.g. `aes-128-cbc`
+    pub fn long_name(&self) -> &'static str {
+        self.entry().3
+    }
+
+    /// some ciphers have no OID, e.g. the AES-CTR
+    pub fn oid(&self) -> Option<&'static str> {
+        self.entry().4
+    }
+
+    /// key length in bytes
+    pub fn key_len(&self) -> usize {
+        match self.cipher {
+            EvpBlockCipher::AES128 | EvpBlockCipher::SM4 => 16,
+            EvpBlockCipher::AES192 | EvpBlockCipher::DESEDE3 => 24,
+            EvpBlockCipher::AES256 => 32,
+            EvpBlockCipher::DES => 8,
+        }
+    }
+
+    pub fn block_size(&self) -> usize {
+        match self.cipher {
+            EvpBlockCipher::DES | EvpBlockCipher::DESEDE3 => 8,
+            _ => 16,
+        }
+    }
+
+    /// IV length in bytes, it's 0 for the ECB
+    pub fn iv_len(&self) -> usize {
+        match self.mode {
+            EvpCipherMode::ECB => 0,
+            _ => self.block_size(),
+        }
+    }
+
+    /// create the cipher with the `key` and `iv`, as the `EVP_CipherInit`.
+    /// The counter of the CTR cipher continues between the calls as the EVP context.
+    pub fn new_cipher(&self, key: &[u8], iv: &[u8]) -> Result<Box<dyn Cipher<Output = usize>>, CryptoError> {
+        if key.len() != self.key_len() || iv.len() != self.iv_len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key or IV length for the {}", self.short_name())));
+        }
+
+        let des_key = |i: usize| {
+            let mut k = [0u8; 8];
+            k.copy_from_slice(&key[(i << 3)..((i + 1) << 3)]);
+            k
+        };
+        match self.cipher {
+            EvpBlockCipher::DES => with_mode(DES::new(des_key(0)), self.mode, iv),
+            EvpBlockCipher::DESEDE3 => with_mode(TDES::new(des_key(0), des_key(1), des_key(2)), self.mode, iv),
+            EvpBlockCipher::SM4 => {
+                let mut k = [0u8; 16];
+                k.copy_from_slice(key);
+                with_mode(SM4::new(k), self.mode, iv)
+            },
+            _ => with_mode(AES::new(key.to_vec())?, self.mode, iv),
+        }
+    }
+}
\ No newline at end of file
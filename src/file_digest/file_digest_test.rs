@@ -0,0 +1,22 @@
+use crate::file_digest::{digest_reader, digest_file, DIGEST_CHUNK_LEN};
+use crate::sha::SHA256;
+
+#[test]
+fn file_digest() {
+    let dir = std::env::temp_dir();
+    // the empty file, the small file, and the large file which is mapped with the `mmap` feature
+    for (i, &len) in [0usize, 1, DIGEST_CHUNK_LEN + 1, (3 << 20) + 7].iter().enumerate() {
+        let data = (0..len).map(|x| (x * 31 + (x >> 8)) as u8).collect::<Vec<_>>();
+        let path = dir.join(format!("rcrypto_file_digest_{}_{}", std::process::id(), i));
+        std::fs::write(&path, data.as_slice()).unwrap();
+
+        let tgt = SHA256::digest(data.as_slice());
+        let mut digest = SHA256::new();
+        let checksum = digest_file(&mut digest, &path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(checksum.unwrap(), tgt, "case: {}", len);
+        assert_eq!(digest_reader(&mut SHA256::new(), data.as_slice()).unwrap(), tgt, "case: {}", len);
+    }
+
+    assert!(digest_file(&mut SHA256::new(), dir.join("rcrypto_file_digest_not_exist")).is_err());
+}
@@ -0,0 +1,54 @@
+//! The read-only private file mapping by the POSIX `mmap`
+
+use std::fs::File;
+use std::io;
+use std::os::raw::{c_int, c_void};
+use std::os::unix::io::AsRawFd;
+
+/// the smaller file is hashed by the chunked reading, the mapping costs more than the copies
+pub(super) const MMAP_THRESHOLD: u64 = 1 << 20;
+
+const PROT_READ: c_int = 1;
+const MAP_PRIVATE: c_int = 2;
+const MADV_SEQUENTIAL: c_int = 2;
+
+extern "C" {
+    fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: i64) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    fn madvise(addr: *mut c_void, len: usize, advice: c_int) -> c_int;
+}
+
+pub(super) struct Mmap {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Mmap {
+    /// map the first `len` bytes of the `file`, the `len` must not be 0
+    pub(super) fn map(file: &File, len: usize) -> io::Result<Self> {
+        if len == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "The empty file cannot be mapped"));
+        }
+
+        // the mapping is created by the valid file descriptor and is unmapped by the `Drop`
+        let ptr = unsafe { mmap(std::ptr::null_mut(), len, PROT_READ, MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr as isize == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            // the advice is only the hint of the read-ahead, its failure is ignored
+            unsafe { madvise(ptr, len, MADV_SEQUENTIAL); }
+            Ok(Self { ptr, len })
+        }
+    }
+
+    pub(super) fn as_slice(&self) -> &[u8] {
+        // the `len` bytes from the `ptr` are mapped readable until the `Drop`
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe { munmap(self.ptr, self.len); }
+    }
+}
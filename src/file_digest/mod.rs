@@ -0,0 +1,14 @@
+//! The file hashing utilities
+//!
+//! The file is hashed by the chunked reading by default. With the `mmap` feature, the large file is mapped into the
+//! memory on the 64 bits unix platforms, so the digest reads the page cache directly without the `read` syscalls and
+//! the copies; the file is hashed by the chunked reading on the other platforms or if the mapping failed.
+
+mod reader;
+pub use reader::{digest_reader, digest_file, DIGEST_CHUNK_LEN};
+
+#[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+mod mmap;
+
+#[cfg(all(test, feature = "sha2"))]
+mod file_digest_test;
\ No newline at end of file
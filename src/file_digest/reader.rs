@@ -0,0 +1,48 @@
+use std::io::{self, Read};
+use std::fs::File;
+use std::path::Path;
+use crate::Digest;
+
+/// the buffer length of the chunked reading
+pub const DIGEST_CHUNK_LEN: usize = 64 << 10;
+
+/// write all the data of the `reader` to the `digest` by the chunks, and return the checksum.
+pub fn digest_reader<D: Digest, R: Read>(digest: &mut D, mut reader: R) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0u8; DIGEST_CHUNK_LEN];
+    loop {
+        match reader.read(buf.as_mut_slice()) {
+            Ok(0) => break,
+            Ok(n) => digest.write(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut checksum = Vec::with_capacity((digest.bits_len() + 7) >> 3);
+    digest.checksum(&mut checksum);
+    Ok(checksum)
+}
+
+/// write the content of the file to the `digest`, and return the checksum.
+///
+/// With the `mmap` feature, the file which is not less than 1 MiB is mapped into the memory on the 64 bits unix
+/// platforms. The mapped file must not be truncated by the other processes during the hashing(the access to the
+/// truncated pages raises the `SIGBUS`).
+pub fn digest_file<D: Digest, P: AsRef<Path>>(digest: &mut D, path: P) -> io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+
+    #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+    {
+        let len = file.metadata()?.len();
+        if len >= crate::file_digest::mmap::MMAP_THRESHOLD {
+            if let Ok(m) = crate::file_digest::mmap::Mmap::map(&file, len as usize) {
+                digest.write(m.as_slice());
+                let mut checksum = Vec::with_capacity((digest.bits_len() + 7) >> 3);
+                digest.checksum(&mut checksum);
+                return Ok(checksum);
+            }
+        }
+    }
+
+    digest_reader(digest, file)
+}
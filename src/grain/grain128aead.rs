@@ -0,0 +1,202 @@
+use crate::{CryptoError, CryptoErrorKind, AEAD};
+
+const GRAIN128AEAD_KEY_LEN: usize = 16;
+const GRAIN128AEAD_NONCE_LEN: usize = 12;
+const GRAIN128AEAD_TAG_LEN: usize = 8;
+
+/// the LFSR `s` and the NFSR `b` of the Grain-128a keystream generator, the bit `i` of the `u128` is the `s_i`/`b_i`,
+/// the accumulator `A` and the shift register `R` of the authenticator, the bit `i` of the `u64` is the `A_i`/`R_i`.
+struct GrainState {
+    lfsr: u128,
+    nfsr: u128,
+    acc: u64,
+    reg: u64,
+}
+
+impl GrainState {
+    /// the initialization of the Grain-128AEADv2, the key is re-introduced after the 320 clocks
+    fn new(key: &[u8; GRAIN128AEAD_KEY_LEN], nonce: &[u8]) -> Self {
+        let mut iv = [0xffu8; 16];
+        iv[..GRAIN128AEAD_NONCE_LEN].copy_from_slice(nonce);
+        iv[15] = 0x7f;
+        let k = u128::from_le_bytes(*key);
+        let mut g = Self { lfsr: u128::from_le_bytes(iv), nfsr: k, acc: 0, reg: 0 };
+
+        for _ in 0..320 {
+            let y = g.pre_output();
+            g.clock(y, y);
+        }
+        for t in 0..64 {
+            g.acc |= (g.pre_output() as u64) << t;
+            g.clock(((k >> (64 + t)) & 1) as u8, ((k >> t) & 1) as u8);
+        }
+        for t in 0..64 {
+            g.reg |= (g.pre_output() as u64) << t;
+            g.clock(0, 0);
+        }
+        g
+    }
+
+    #[inline]
+    fn bit(x: u128, i: u32) -> u8 {
+        ((x >> i) & 1) as u8
+    }
+
+    fn pre_output(&self) -> u8 {
+        let (s, b) = (|i| Self::bit(self.lfsr, i), |i| Self::bit(self.nfsr, i));
+        let h = (b(12) & s(8)) ^ (s(13) & s(20)) ^ (b(95) & s(42)) ^ (s(60) & s(79)) ^ (b(12) & b(95) & s(94));
+        h ^ s(93) ^ b(2) ^ b(15) ^ b(36) ^ b(45) ^ b(64) ^ b(73) ^ b(89)
+    }
+
+    /// the `lfsr_in` and `nfsr_in` are added to the feedback of the LFSR and NFSR
+    fn clock(&mut self, lfsr_in: u8, nfsr_in: u8) {
+        let (s, b) = (|i| Self::bit(self.lfsr, i), |i| Self::bit(self.nfsr, i));
+        let f = s(0) ^ s(7) ^ s(38) ^ s(70) ^ s(81) ^ s(96);
+        let g = b(0) ^ b(26) ^ b(56) ^ b(91) ^ b(96) ^ (b(3) & b(67)) ^ (b(11) & b(13)) ^ (b(17) & b(18))
+            ^ (b(27) & b(59)) ^ (b(40) & b(48)) ^ (b(61) & b(65)) ^ (b(68) & b(84)) ^ (b(22) & b(24) & b(25))
+            ^ (b(70) & b(78) & b(82)) ^ (b(88) & b(92) & b(93) & b(95));
+        let s0 = s(0);
+
+        self.lfsr = (self.lfsr >> 1) | (((f ^ lfsr_in) as u128) << 127);
+        self.nfsr = (self.nfsr >> 1) | (((s0 ^ g ^ nfsr_in) as u128) << 127);
+    }
+
+    fn next_bit(&mut self) -> u8 {
+        let y = self.pre_output();
+        self.clock(0, 0);
+        y
+    }
+
+    /// the `R` is accumulated to the `A` if the bit `m` is 1
+    #[inline]
+    fn auth_bit(&mut self, m: u8) {
+        self.acc ^= self.reg & 0u64.wrapping_sub(m as u64);
+    }
+
+    /// authenticate the bits of the `data` from the least significant bit of every byte, the even keystream bits
+    /// are xored with the `data` and output to the `dst` if it's not `None`, the odd keystream bits are shifted into the `R`.
+    /// the `data` is authenticated if `is_decrypt` is false, otherwise the output is authenticated.
+    fn process(&mut self, mut dst: Option<&mut Vec<u8>>, data: &[u8], is_decrypt: bool) {
+        for &x in data.iter() {
+            let mut c = 0u8;
+            for i in 0..8 {
+                let m = (x >> i) & 1;
+                let o = m ^ self.next_bit();
+                self.auth_bit(if is_decrypt { o } else { m });
+                let z = self.next_bit();
+                self.reg = (self.reg >> 1) | ((z as u64) << 63);
+                c |= o << i;
+            }
+            if let Some(d) = dst.as_mut() {
+                d.push(c);
+            }
+        }
+    }
+
+    /// the associated data is prefixed by its DER length encoding
+    fn process_aad(&mut self, aad: &[u8]) {
+        let len = aad.len().to_be_bytes();
+        let l = len.iter().position(|&x| x != 0).map(|i| &len[i..]).unwrap_or(&[]);
+        if aad.len() < 128 {
+            self.process(None, &[aad.len() as u8], false);
+        } else {
+            self.process(None, &[0x80 | (l.len() as u8)], false);
+            self.process(None, l, false);
+        }
+        self.process(None, aad, false);
+    }
+
+    /// the message is padded with the bit 1 for the authentication
+    fn tag(mut self) -> [u8; GRAIN128AEAD_TAG_LEN] {
+        self.auth_bit(1);
+        self.acc.to_le_bytes()
+    }
+}
+
+/// Grain-128AEADv2
+/// Hell, Johansson, Maximov, Meier, Sönnerup, Yoshida. Grain-128AEADv2, NIST LWC finalist.
+///
+/// the 128 bits key, the 96 bits nonce and the 64 bits tag. The bits of the key, nonce, associated data, message and tag
+/// are in the little-endian bit order, i.e., the bit 0 of the Grain is the least significant bit of the first byte.
+/// Every message bit uses two keystream bits, the even bits encrypt the message and the odd bits drive the authenticator.
+#[derive(Clone)]
+pub struct Grain128AEAD {
+    key: [u8; GRAIN128AEAD_KEY_LEN],
+}
+
+impl Grain128AEAD {
+    pub fn new(key: [u8; 16]) -> Self {
+        Self { key }
+    }
+
+    pub fn from_slice(key: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != GRAIN128AEAD_KEY_LEN {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the Grain-128AEAD key must be 16 bytes", key.len())))
+        } else {
+            let mut k = [0u8; GRAIN128AEAD_KEY_LEN];
+            k.copy_from_slice(key);
+            Ok(Self { key: k })
+        }
+    }
+
+    fn state(&self, nonce: &[u8], aad: &[u8]) -> Result<GrainState, CryptoError> {
+        if nonce.len() != GRAIN128AEAD_NONCE_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong nonce length: {}, the Grain-128AEAD nonce must be 12 bytes", nonce.len())));
+        }
+
+        let mut g = GrainState::new(&self.key, nonce);
+        g.process_aad(aad);
+        Ok(g)
+    }
+
+    /// encrypt and authenticate the `plaintext` and `aad`, the `ciphertext||tag` will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        let mut g = self.state(nonce, aad)?;
+        dst.clear();
+        g.process(Some(dst), plaintext, false);
+        dst.extend_from_slice(g.tag().as_ref());
+        Ok(())
+    }
+
+    /// verify and decrypt the `ciphertext||tag`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if ciphertext.len() < GRAIN128AEAD_TAG_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the tag"));
+        }
+        let (ct, tag) = ciphertext.split_at(ciphertext.len() - GRAIN128AEAD_TAG_LEN);
+        let mut g = self.state(nonce, aad)?;
+
+        // the tag depends on the plaintext, so the plaintext is released after the verification
+        let mut pt = Vec::with_capacity(ct.len());
+        g.process(Some(&mut pt), ct, true);
+        let expected = g.tag();
+        if expected.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            pt.iter_mut().for_each(|x| *x = 0);
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The Grain-128AEAD tag not match"))
+        } else {
+            dst.append(&mut pt);
+            Ok(())
+        }
+    }
+}
+
+impl AEAD for Grain128AEAD {
+    fn nonce_len(&self) -> usize {
+        GRAIN128AEAD_NONCE_LEN
+    }
+
+    fn tag_len(&self) -> usize {
+        GRAIN128AEAD_TAG_LEN
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, nonce, aad, plaintext)
+    }
+
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.decrypt(dst, nonce, aad, ciphertext)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{Grain128AEAD, AEAD, CryptoErrorKind};
+use crate::test_util::hex;
+
+/// check the entries of the LWC `genkat_aead` file(`Count`, `Key`, `Nonce`, `PT`, `AD`, `CT`), return the number of the entries
+fn check_lwc_kat(kat: &str) -> usize {
+    let mut entries = 0;
+    for entry in kat.split("Count = ").skip(1) {
+        let field = |name: &str| -> Vec<u8> {
+            let line = entry.lines().find(|l| l.starts_with(name)).unwrap_or_else(|| panic!("no {} in the entry: {}", name, entry));
+            hex(line[name.len()..].trim().to_lowercase().as_str())
+        };
+        let (key, nonce, pt, ad, ct) = (field("Key = "), field("Nonce = "), field("PT = "), field("AD = "), field("CT = "));
+        let count = entry.lines().next().unwrap().trim();
+
+        let (g, mut dst, mut msg) = (Grain128AEAD::from_slice(key.as_slice()).unwrap(), Vec::new(), Vec::new());
+        g.seal(&mut dst, nonce.as_slice(), ad.as_slice(), pt.as_slice()).unwrap();
+        assert_eq!(dst, ct, "Count = {}", count);
+        g.open(&mut msg, nonce.as_slice(), ad.as_slice(), ct.as_slice()).unwrap();
+        assert_eq!(msg, pt, "Count = {}", count);
+        entries += 1;
+    }
+    entries
+}
+
+/// the entries of the `LWC_AEAD_KAT_128_96.txt` layout: the key is 00..0f, the nonce is 00..0b, the PT and AD are the
+/// incremental bytes, and the Count is `33 * len(PT) + len(AD) + 1`.
+///
+/// The official KAT file of the Grain-128AEADv2 submission isn't shipped in the tree, the entries below are the
+/// regression values of the bit-level model of the specification, set the `RCRYPTO_GRAIN128AEAD_KAT` to the path of the
+/// official file to check all the 1089 entries.
+const GRAIN128AEAD_KAT: &str = "
+Count = 1
+Key = 000102030405060708090A0B0C0D0E0F
+Nonce = 000102030405060708090A0B
+PT = 
+AD = 
+CT = BFC36FE95D9CD448
+
+Count = 2
+Key = 000102030405060708090A0B0C0D0E0F
+Nonce = 000102030405060708090A0B
+PT = 
+AD = 00
+CT = 1EBA892F7C40141B
+
+Count = 34
+Key = 000102030405060708090A0B0C0D0E0F
+Nonce = 000102030405060708090A0B
+PT = 00
+AD = 
+CT = 1CD653093F0AE0E390
+
+Count = 273
+Key = 000102030405060708090A0B0C0D0E0F
+Nonce = 000102030405060708090A0B
+PT = 0001020304050607
+AD = 0001020304050607
+CT = 218F298BD851E90D9F1F8244962CE28D
+
+Count = 544
+Key = 000102030405060708090A0B0C0D0E0F
+Nonce = 000102030405060708090A0B
+PT = 000102030405060708090A0B0C0D0E0F
+AD = 000102030405060708090A0B0C0D0E
+CT = 0A095C3A5F1268BBB4FA78AF0080A8B5FE7EA2CF56C0488E
+
+Count = 1089
+Key = 000102030405060708090A0B0C0D0E0F
+Nonce = 000102030405060708090A0B
+PT = 000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F
+AD = 000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F
+CT = 93F410390FBEA83CE875F7A422E6697F5A59DED16C9A2B882FB6014BD814EDE393605A6C4A682CB9
+";
+
+#[test]
+fn grain128aead_kat() {
+    assert_eq!(check_lwc_kat(GRAIN128AEAD_KAT), 6);
+    if let Ok(path) = std::env::var("RCRYPTO_GRAIN128AEAD_KAT") {
+        let kat = std::fs::read_to_string(path.as_str()).unwrap_or_else(|e| panic!("read the {}: {}", path, e));
+        assert_eq!(check_lwc_kat(kat.as_str()), 1089);
+    }
+}
+
+#[test]
+fn grain128aead() {
+    let (key, nonce) = ((0u8..16).collect::<Vec<_>>(), (0u8..12).collect::<Vec<_>>());
+    let cases = [
+        (vec![], vec![], "bfc36fe95d9cd448"),
+        // the 200 bytes associated data has the 2 bytes DER length encoding
+        ((0u8..200).collect::<Vec<_>>(), (0u8..33).collect::<Vec<_>>(), "62b70c864ed442625196826079a4968231c750745b0772c6d87b1df04a00ac339e7b6e3e42bc672060"),
+    ];
+
+    let (g, mut ct, mut pt) = (Grain128AEAD::from_slice(key.as_slice()).unwrap(), Vec::new(), Vec::new());
+    for (aad, msg, tgt) in cases.iter() {
+        g.seal(&mut ct, nonce.as_slice(), aad, msg).unwrap();
+        assert_eq!(ct, hex(tgt));
+        g.open(&mut pt, nonce.as_slice(), aad, ct.as_slice()).unwrap();
+        assert_eq!(&pt, msg);
+
+        let mut tampered = ct.clone();
+        tampered[0] ^= 0x80;
+        assert_eq!(g.open(&mut pt, nonce.as_slice(), aad, tampered.as_slice()).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(pt.is_empty());
+    }
+
+    assert_eq!(g.seal(&mut ct, &nonce[1..], &[], &[]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(g.open(&mut pt, nonce.as_slice(), &[], &[0u8; 7]).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+    assert_eq!(Grain128AEAD::from_slice(&key[1..]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+}
@@ -0,0 +1,11 @@
+//! Grain-128AEADv2
+//!
+//! The Grain-128a keystream generator with the bit-oriented authenticator, every bit of the associated
+//! data and the message is authenticated by the accumulator, it's designed for the constrained hardware
+//! (e.g. the RFID tags and the IEEE 802.15.4 sensors).
+
+mod grain128aead;
+pub use grain128aead::Grain128AEAD;
+
+#[cfg(test)]
+mod grain_test;
\ No newline at end of file
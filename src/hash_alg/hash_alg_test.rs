@@ -0,0 +1,87 @@
+use crate::hash_alg::{HashAlg, DynDigest};
+use crate::{sha, sha3, Digest, HMAC, MD5, SM3, Signature};
+use crate::kdf::PBKDF2;
+use crate::rsa::{PSS, KeyPair, SignatureContent};
+use rmath::rand::{CryptoRand, DefaultSeed};
+
+fn concrete_digest(alg: HashAlg) -> Box<dyn DynDigest> {
+    match alg {
+        HashAlg::Md5 => Box::new(MD5::new()),
+        HashAlg::Sha1 => Box::new(sha::SHA1::new()),
+        HashAlg::Sha224 => Box::new(sha::SHA224::new()),
+        HashAlg::Sha256 => Box::new(sha::SHA256::new()),
+        HashAlg::Sha384 => Box::new(sha::SHA384::new()),
+        HashAlg::Sha512 => Box::new(sha::SHA512::new()),
+        HashAlg::Sha512_224 => Box::new(sha::SHA512T224::new()),
+        HashAlg::Sha512_256 => Box::new(sha::SHA512T256::new()),
+        HashAlg::Sha3_224 => Box::new(sha3::SHA224::new()),
+        HashAlg::Sha3_256 => Box::new(sha3::SHA256::new()),
+        HashAlg::Sha3_384 => Box::new(sha3::SHA384::new()),
+        HashAlg::Sha3_512 => Box::new(sha3::SHA512::new()),
+        HashAlg::Sm3 => Box::new(SM3::new()),
+    }
+}
+
+#[test]
+fn hash_alg_registry() {
+    let msg = b"The quick brown fox jumps over the lazy dog";
+    assert_eq!(HashAlg::all().count(), 13);
+    for alg in HashAlg::all() {
+        let mut h = alg.new_digest();
+        assert_eq!((h.bits_len() >> 3, h.block_size()), (alg.digest_len(), Some(alg.block_size())), "{}", alg);
+        assert_eq!(HashAlg::from_name(alg.name()), Some(alg));
+        assert_eq!(alg.name().to_lowercase().replace('-', "").parse::<HashAlg>().unwrap(), alg);
+
+        let (mut d1, mut d2) = (Vec::new(), Vec::new());
+        h.write(&msg[..10]);
+        // the clone keeps the state
+        let mut h2 = h.clone();
+        h.write(&msg[10..]);
+        h.checksum(&mut d1);
+        h2.write(&msg[10..]);
+        h2.checksum(&mut d2);
+        assert_eq!(d1, d2, "{}", alg);
+        assert_eq!(d1, alg.digest(msg), "{}", alg);
+        let mut c = concrete_digest(alg);
+        c.write(msg);
+        c.checksum(&mut d2);
+        assert_eq!(d1, d2, "{}", alg);
+    }
+
+    assert_eq!(HashAlg::from_name("sha512/256"), Some(HashAlg::Sha512_256));
+    assert_eq!(HashAlg::from_name("SHA3_384"), Some(HashAlg::Sha3_384));
+    assert_eq!(HashAlg::from_name("sha"), None);
+    assert!("SHAKE128".parse::<HashAlg>().is_err());
+}
+
+#[test]
+fn hash_alg_generic_constructs() {
+    let msg = b"The quick brown fox jumps over the lazy dog";
+    let (mut t1, mut t2) = (Vec::new(), Vec::new());
+    for alg in [HashAlg::Sha1, HashAlg::Sha256, HashAlg::Sha3_256, HashAlg::Sm3] {
+        let mut hmac = HMAC::new(b"key".to_vec(), alg.new_digest()).unwrap();
+        hmac.write(msg);
+        hmac.checksum(&mut t1);
+        let mut hmac = HMAC::new(b"key".to_vec(), concrete_digest(alg)).unwrap();
+        hmac.write(msg);
+        hmac.checksum(&mut t2);
+        assert_eq!(t1, t2, "{}", alg);
+
+        PBKDF2::new(alg.new_digest(), 2).unwrap().derive(b"password", b"salt", 40, &mut t1).unwrap();
+        PBKDF2::new(concrete_digest(alg), 2).unwrap().derive(b"password", b"salt", 40, &mut t2).unwrap();
+        assert_eq!(t1, t2, "{}", alg);
+    }
+
+    // HMAC-SHA256("key", msg)
+    let mut hmac = HMAC::new(b"key".to_vec(), HashAlg::Sha256.new_digest()).unwrap();
+    hmac.write(msg);
+    hmac.checksum(&mut t1);
+    assert_eq!(crate::encoding::Hex::lower().encode(t1.as_slice()), "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+
+    let rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let mut pss = PSS::auto_generate_key(1024, 10, HashAlg::Sha256.new_digest(), rd.clone(), None, true).unwrap();
+    let mut sig = SignatureContent::new();
+    pss.sign(&mut sig, msg.as_ref()).unwrap();
+    let mut verifier = PSS::new(sha::SHA256::new(), rd, KeyPair::from(pss.public_key().clone()), None, false).unwrap();
+    verifier.verify(&sig, msg.as_ref()).unwrap();
+}
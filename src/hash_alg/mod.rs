@@ -0,0 +1,23 @@
+//! The runtime-dispatch hash algorithm registry
+//!
+//! The `HashAlg` identifies the digest by the runtime data(e.g. the negotiated algorithm name or the config file),
+//! and the `HashAlg::new_digest` creates the boxed digest `Box<dyn DynDigest>` which implements the `Digest` and
+//! the `Clone`, so it can be used by the HMAC, PBKDF2, RSA PSS/OAEP and the other generic constructs directly:
+//!
+//! ```rust
+//! use rcrypto::{Digest, HMAC};
+//! use rcrypto::hash_alg::HashAlg;
+//!
+//! let alg = HashAlg::from_name("SHA-256").unwrap();
+//! let mut hmac = HMAC::new(b"key".to_vec(), alg.new_digest()).unwrap();
+//! hmac.write(b"message");
+//! let mut tag = Vec::new();
+//! hmac.checksum(&mut tag);
+//! assert_eq!(tag.len(), alg.digest_len());
+//! ```
+
+mod registry;
+pub use registry::{HashAlg, DynDigest};
+
+#[cfg(all(test, feature = "kdf", feature = "rsa"))]
+mod hash_alg_test;
\ No newline at end of file
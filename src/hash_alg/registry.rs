@@ -0,0 +1,157 @@
+use crate::{Digest, MD5, SM3};
+use crate::sha::{SHA1, SHA224, SHA256, SHA384, SHA512, SHA512T224, SHA512T256};
+
+/// The digest which can be boxed and cloned, it's implemented for all the `Digest + Clone`
+pub trait DynDigest: Digest {
+    fn clone_box(&self) -> Box<dyn DynDigest>;
+}
+
+impl<D: Digest + Clone + 'static> DynDigest for D {
+    fn clone_box(&self) -> Box<dyn DynDigest> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynDigest> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl Digest for Box<dyn DynDigest> {
+    fn block_size(&self) -> Option<usize> {
+        self.as_ref().block_size()
+    }
+
+    fn bits_len(&self) -> usize {
+        self.as_ref().bits_len()
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        self.as_mut().write(data)
+    }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) {
+        self.as_mut().write_vectored(bufs)
+    }
+
+    fn checksum(&mut self, digest: &mut Vec<u8>) {
+        self.as_mut().checksum(digest)
+    }
+
+    fn reset(&mut self) {
+        self.as_mut().reset()
+    }
+}
+
+/// The hash algorithm
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashAlg {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha512_224,
+    Sha512_256,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Sm3,
+}
+
+/// (algorithm, name, digest length, block size)
+const HASH_ALGS: [(HashAlg, &str, usize, usize); 13] = [
+    (HashAlg::Md5, "MD5", 16, 64),
+    (HashAlg::Sha1, "SHA-1", 20, 64),
+    (HashAlg::Sha224, "SHA-224", 28, 64),
+    (HashAlg::Sha256, "SHA-256", 32, 64),
+    (HashAlg::Sha384, "SHA-384", 48, 128),
+    (HashAlg::Sha512, "SHA-512", 64, 128),
+    (HashAlg::Sha512_224, "SHA-512/224", 28, 128),
+    (HashAlg::Sha512_256, "SHA-512/256", 32, 128),
+    (HashAlg::Sha3_224, "SHA3-224", 28, 144),
+    (HashAlg::Sha3_256, "SHA3-256", 32, 136),
+    (HashAlg::Sha3_384, "SHA3-384", 48, 104),
+    (HashAlg::Sha3_512, "SHA3-512", 64, 72),
+    (HashAlg::Sm3, "SM3", 32, 64),
+];
+
+impl HashAlg {
+    fn entry(&self) -> &'static (HashAlg, &'static str, usize, usize) {
+        HASH_ALGS.iter().find(|x| x.0 == *self).unwrap()
+    }
+
+    /// all the hash algorithms
+    pub fn all() -> impl Iterator<Item = HashAlg> {
+        HASH_ALGS.iter().map(|x| x.0)
+    }
+
+    /// the name is case-insensitive and the separators `-`, `_`, `/` are ignored, e.g. `SHA-256`, `sha256`,
+    /// `SHA512/256`, `sha3_256`, `SM3`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalize = |s: &str| s.chars().filter(|c| !matches!(c, '-' | '_' | '/'))
+            .map(|c| c.to_ascii_lowercase()).collect::<String>();
+        let name = normalize(name);
+        HASH_ALGS.iter().find(|x| normalize(x.1) == name).map(|x| x.0)
+    }
+
+    /// the canonical name, e.g. `SHA-256`, `SHA-512/256`, `SHA3-256`
+    pub fn name(&self) -> &'static str {
+        self.entry().1
+    }
+
+    /// the digest length in bytes
+    pub fn digest_len(&self) -> usize {
+        self.entry().2
+    }
+
+    /// the block size(the rate of the SHA-3) in bytes
+    pub fn block_size(&self) -> usize {
+        self.entry().3
+    }
+
+    /// create the boxed digest
+    pub fn new_digest(&self) -> Box<dyn DynDigest> {
+        match self {
+            HashAlg::Md5 => Box::new(MD5::new()),
+            HashAlg::Sha1 => Box::new(SHA1::new()),
+            HashAlg::Sha224 => Box::new(SHA224::new()),
+            HashAlg::Sha256 => Box::new(SHA256::new()),
+            HashAlg::Sha384 => Box::new(SHA384::new()),
+            HashAlg::Sha512 => Box::new(SHA512::new()),
+            HashAlg::Sha512_224 => Box::new(SHA512T224::new()),
+            HashAlg::Sha512_256 => Box::new(SHA512T256::new()),
+            HashAlg::Sha3_224 => Box::new(crate::sha3::SHA224::new()),
+            HashAlg::Sha3_256 => Box::new(crate::sha3::SHA256::new()),
+            HashAlg::Sha3_384 => Box::new(crate::sha3::SHA384::new()),
+            HashAlg::Sha3_512 => Box::new(crate::sha3::SHA512::new()),
+            HashAlg::Sm3 => Box::new(SM3::new()),
+        }
+    }
+
+    /// the one-shot digest of the `data`
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (self.new_digest(), Vec::with_capacity(self.digest_len()));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
+}
+
+impl std::fmt::Display for HashAlg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for HashAlg {
+    type Err = crate::CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| crate::CryptoError::new(crate::CryptoErrorKind::NotSupportUsage,
+            format!("The hash algorithm {} is not supported", s)))
+    }
+}
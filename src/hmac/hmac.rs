@@ -17,6 +17,8 @@ pub struct HMAC<D: Digest> {
     k0_o: Vec<u8>,
     buf: Vec<u8>,
     is_checked: bool,
+    /// the `K0 ^ ipad` has been written to the digest
+    is_started: bool,
 }
 
 impl<D: Digest> HMAC<D> {
@@ -56,6 +58,7 @@ impl<D: Digest> HMAC<D> {
                             k0_o,
                             buf: Vec::with_capacity(b),
                             is_checked: false,
+                            is_started: false,
                         }
                     )
                 }
@@ -73,6 +76,17 @@ impl<D: Digest> HMAC<D> {
         self.reset();
     }
     
+    /// the message is hashed incrementally: `H((K0 ^ opad) || H((K0 ^ ipad) || text))`, the new message
+    /// will be started if writing after the checksum.
+    fn start_inner(&mut self) {
+        if self.is_checked || !self.is_started {
+            self.df.reset();
+            self.df.write(self.k0_i.as_slice());
+            self.is_checked = false;
+            self.is_started = true;
+        }
+    }
+    
     // pub fn mac(&mut self, text: &[u8], results: &mut Vec<u8>) {
     //     self.k0_o.truncate(self.df.block_size().unwrap());
     //     self.k0_i.truncate(self.df.block_size().unwrap());
@@ -105,32 +119,26 @@ impl<D: Digest>  Digest for HMAC<D> {
     }
 
     fn write(&mut self, data: &[u8]) {
-        if self.is_checked {
-            self.k0_i.truncate(self.df.block_size().unwrap());
-            self.k0_o.truncate(self.df.block_size().unwrap());
-        }
-        
-        data.iter().for_each(|&e| {
-            self.k0_i.push(e)
-        });
-        
-        self.is_checked = false;
+        self.start_inner();
+        self.df.write(data);
+    }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) {
+        self.start_inner();
+        self.df.write_vectored(bufs);
     }
 
     fn checksum(&mut self, digest: &mut Vec<u8>) {
         if !self.is_checked {
-            self.df.reset();
-            self.df.write(self.k0_i.as_slice());
+            self.start_inner();
             self.df.checksum(&mut self.buf);
 
-            for &e in self.buf.iter() {
-                self.k0_o.push(e);
-            }
-
             self.df.reset();
             self.df.write(self.k0_o.as_slice());
+            self.df.write(self.buf.as_slice());
             self.df.checksum(digest);
             self.is_checked = true;
+            self.is_started = false;
         } else {
             self.df.checksum(digest);
         }
@@ -138,12 +146,11 @@ impl<D: Digest>  Digest for HMAC<D> {
 
     fn reset(&mut self) {
         self.is_checked = false;
-        self.k0_i.truncate(self.df.block_size().unwrap());
-        self.k0_o.truncate(self.df.block_size().unwrap());
+        self.is_started = false;
         self.buf.clear();
         self.df.reset();
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sha2", feature = "md5"))]
 mod tests;
\ No newline at end of file
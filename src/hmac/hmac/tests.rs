@@ -386,4 +386,26 @@ fn hmac_md5() {
         hmac.checksum(&mut mac);
         assert_eq!(e.0, cvt_bytes_to_str(mac.as_slice()), "case: {:?}", e.1);
     });
-}
\ No newline at end of file
+}
+#[test]
+fn hmac_vectored() {
+    // RFC 4231 test case 2
+    let (key, msg) = (b"Jefe".to_vec(), b"what do ya want for nothing?");
+    let tag = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+    
+    let mut hmac = HMAC::new(key, SHA::sha256()).unwrap();
+    let mut mac = Vec::new();
+    for &(i, j) in [(0usize, 0usize), (0, 28), (4, 7), (13, 27), (28, 28)].iter() {
+        hmac.write_vectored(&[&msg[..i], &msg[i..j], &msg[j..]]);
+        hmac.checksum(&mut mac);
+        assert_eq!(tag, cvt_bytes_to_str(mac.as_slice()), "case: {}-{}", i, j);
+    }
+    
+    // mixed with the write
+    hmac.reset();
+    hmac.write(&msg[..5]);
+    hmac.write_vectored(&[&msg[5..9], &msg[9..20]]);
+    hmac.write(&msg[20..]);
+    hmac.checksum(&mut mac);
+    assert_eq!(tag, cvt_bytes_to_str(mac.as_slice()));
+}
@@ -0,0 +1,385 @@
+//! JSON Canonicalization Scheme(JCS)
+//! RFC 8785
+//!
+//! - the object members are sorted by the UTF-16 code units of their names;
+//! - the strings are serialized with the minimal escaping;
+//! - the numbers are serialized as the ECMAScript `Number.prototype.toString()`;
+//! - no whitespace between the tokens;
+//!
+//! The input must be I-JSON(RFC 7493), so the duplicate member names, the lone surrogates and
+//! the numbers which cannot be represented as the IEEE 754 double are rejected.
+
+use crate::{Signature, CryptoError, CryptoErrorKind};
+
+/// The JSON value
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// the members are stored in the input order
+    Object(Vec<(String, JsonValue)>),
+}
+
+fn parse_err<T>(pos: usize, msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid JSON at {}: {}", pos, msg)))
+}
+
+struct JsonParser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn skip_ws(&mut self) {
+        while self.pos < self.s.len() && matches!(self.s[self.pos], b' ' | b'\t' | b'\n' | b'\r') {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, lit: &[u8]) -> Result<(), CryptoError> {
+        if self.s[self.pos..].starts_with(lit) {
+            self.pos += lit.len();
+            Ok(())
+        } else {
+            parse_err(self.pos, "unexpected token")
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, CryptoError> {
+        if depth > JsonValue::MAX_DEPTH {
+            return parse_err(self.pos, "nesting too deep");
+        }
+
+        self.skip_ws();
+        match self.peek() {
+            Some(b'n') => self.expect(b"null").map(|_| JsonValue::Null),
+            Some(b't') => self.expect(b"true").map(|_| JsonValue::Bool(true)),
+            Some(b'f') => self.expect(b"false").map(|_| JsonValue::Bool(false)),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut arr = Vec::new();
+                self.skip_ws();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(arr));
+                }
+                loop {
+                    arr.push(self.parse_value(depth + 1)?);
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(JsonValue::Array(arr));
+                        },
+                        _ => return parse_err(self.pos, "expected ',' or ']'"),
+                    }
+                }
+            },
+            Some(b'{') => {
+                self.pos += 1;
+                let mut obj: Vec<(String, JsonValue)> = Vec::new();
+                self.skip_ws();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(obj));
+                }
+                loop {
+                    self.skip_ws();
+                    if self.peek() != Some(b'"') {
+                        return parse_err(self.pos, "expected member name");
+                    }
+                    let name_pos = self.pos;
+                    let name = self.parse_string()?;
+                    if obj.iter().any(|(k, _)| k == &name) {
+                        return parse_err(name_pos, "duplicate member name");
+                    }
+                    self.skip_ws();
+                    if self.peek() != Some(b':') {
+                        return parse_err(self.pos, "expected ':'");
+                    }
+                    self.pos += 1;
+                    let v = self.parse_value(depth + 1)?;
+                    obj.push((name, v));
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(JsonValue::Object(obj));
+                        },
+                        _ => return parse_err(self.pos, "expected ',' or '}'"),
+                    }
+                }
+            },
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_number(),
+            _ => parse_err(self.pos, "unexpected token"),
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u16, CryptoError> {
+        if self.pos + 4 > self.s.len() {
+            return parse_err(self.pos, "invalid unicode escape");
+        }
+        let h = std::str::from_utf8(&self.s[self.pos..(self.pos + 4)]).ok()
+            .filter(|h| h.bytes().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|h| u16::from_str_radix(h, 16).ok());
+        match h {
+            Some(h) => {
+                self.pos += 4;
+                Ok(h)
+            },
+            None => parse_err(self.pos, "invalid unicode escape"),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, CryptoError> {
+        // skip the '"'
+        self.pos += 1;
+        let mut buf = Vec::new();
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return parse_err(self.pos, "unterminated string"),
+            };
+            self.pos += 1;
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let e = match self.peek() {
+                        Some(e) => e,
+                        None => return parse_err(self.pos, "unterminated string"),
+                    };
+                    self.pos += 1;
+                    match e {
+                        b'"' | b'\\' | b'/' => buf.push(e),
+                        b'b' => buf.push(0x08),
+                        b'f' => buf.push(0x0c),
+                        b'n' => buf.push(b'\n'),
+                        b'r' => buf.push(b'\r'),
+                        b't' => buf.push(b'\t'),
+                        b'u' => {
+                            let hi = self.parse_hex4()?;
+                            let cp = if (0xd800..0xdc00).contains(&hi) {
+                                if !self.s[self.pos..].starts_with(b"\\u") {
+                                    return parse_err(self.pos, "lone surrogate");
+                                }
+                                self.pos += 2;
+                                let lo = self.parse_hex4()?;
+                                if !(0xdc00..0xe000).contains(&lo) {
+                                    return parse_err(self.pos, "lone surrogate");
+                                }
+                                0x10000 + ((((hi as u32) - 0xd800) << 10) | ((lo as u32) - 0xdc00))
+                            } else if (0xdc00..0xe000).contains(&hi) {
+                                return parse_err(self.pos, "lone surrogate");
+                            } else {
+                                hi as u32
+                            };
+                            let mut tmp = [0u8; 4];
+                            buf.extend_from_slice(std::char::from_u32(cp).unwrap().encode_utf8(&mut tmp).as_bytes());
+                        },
+                        _ => return parse_err(self.pos - 1, "invalid escape"),
+                    }
+                },
+                0x00..=0x1f => return parse_err(self.pos - 1, "unescaped control character"),
+                _ => buf.push(c),
+            }
+        }
+
+        String::from_utf8(buf).or_else(|_| parse_err(self.pos, "invalid UTF-8"))
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, CryptoError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let s = p.pos;
+            while p.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                p.pos += 1;
+            }
+            p.pos - s
+        };
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => { digits(self); },
+            _ => return parse_err(self.pos, "invalid number"),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if digits(self) == 0 {
+                return parse_err(self.pos, "invalid number");
+            }
+        }
+        if matches!(self.peek(), Some(b'e') | Some(b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+') | Some(b'-')) {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                return parse_err(self.pos, "invalid number");
+            }
+        }
+
+        let n = std::str::from_utf8(&self.s[start..self.pos]).unwrap().parse::<f64>();
+        match n {
+            Ok(n) if n.is_finite() => Ok(JsonValue::Number(n)),
+            _ => parse_err(start, "number out of the IEEE 754 double range"),
+        }
+    }
+}
+
+/// serialize the number as the ECMAScript `Number.prototype.toString()`
+fn write_number(n: f64, dst: &mut String) -> Result<(), CryptoError> {
+    if !n.is_finite() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "NaN and Infinity are not allowed in JSON"));
+    }
+    if n == 0.0 {
+        dst.push('0');
+        return Ok(());
+    }
+
+    // the `{:e}` gives the shortest round-trip digits, e.g. `-1.2345e-7`, but it rounds half up when
+    // two candidates are equally close, so the number is formatted again with the same precision which
+    // rounds the exact value half to even as the ECMAScript required.
+    let s = format!("{:e}", n);
+    let k = s[..s.find('e').unwrap()].bytes().filter(|c| c.is_ascii_digit()).count();
+    let s = format!("{:.*e}", k.saturating_sub(1), n);
+    let (mantissa, exp) = s.split_at(s.find('e').unwrap());
+    let exp = exp[1..].parse::<i32>().unwrap();
+    let (sign, mantissa) = if let Some(m) = mantissa.strip_prefix('-') { ("-", m) } else { ("", mantissa) };
+    let digits = mantissa.replace('.', "");
+    let (k, n) = (digits.len() as i32, exp + 1);
+
+    dst.push_str(sign);
+    if k <= n && n <= 21 {
+        dst.push_str(digits.as_str());
+        (0..(n - k)).for_each(|_| dst.push('0'));
+    } else if 0 < n && n <= 21 {
+        dst.push_str(&digits[..(n as usize)]);
+        dst.push('.');
+        dst.push_str(&digits[(n as usize)..]);
+    } else if -6 < n && n <= 0 {
+        dst.push_str("0.");
+        (0..(-n)).for_each(|_| dst.push('0'));
+        dst.push_str(digits.as_str());
+    } else {
+        dst.push_str(&digits[..1]);
+        if k > 1 {
+            dst.push('.');
+            dst.push_str(&digits[1..]);
+        }
+        dst.push('e');
+        dst.push(if n - 1 < 0 { '-' } else { '+' });
+        dst.push_str((n - 1).abs().to_string().as_str());
+    }
+
+    Ok(())
+}
+
+fn write_string(s: &str, dst: &mut String) {
+    dst.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => dst.push_str("\\\""),
+            '\\' => dst.push_str("\\\\"),
+            '\u{08}' => dst.push_str("\\b"),
+            '\u{0c}' => dst.push_str("\\f"),
+            '\n' => dst.push_str("\\n"),
+            '\r' => dst.push_str("\\r"),
+            '\t' => dst.push_str("\\t"),
+            c if (c as u32) < 0x20 => dst.push_str(format!("\\u{:04x}", c as u32).as_str()),
+            c => dst.push(c),
+        }
+    }
+    dst.push('"');
+}
+
+impl JsonValue {
+    /// the maximum nesting depth of the arrays and objects
+    pub const MAX_DEPTH: usize = 128;
+
+    /// parse the JSON text
+    pub fn parse(json: &str) -> Result<Self, CryptoError> {
+        let mut p = JsonParser { s: json.as_bytes(), pos: 0 };
+        let v = p.parse_value(0)?;
+        p.skip_ws();
+        if p.pos != p.s.len() {
+            parse_err(p.pos, "trailing characters")
+        } else {
+            Ok(v)
+        }
+    }
+
+    /// serialize the value in the canonical form to the `dst`
+    pub fn canonicalize(&self, dst: &mut String) -> Result<(), CryptoError> {
+        match self {
+            JsonValue::Null => dst.push_str("null"),
+            JsonValue::Bool(b) => dst.push_str(if *b { "true" } else { "false" }),
+            JsonValue::Number(n) => write_number(*n, dst)?,
+            JsonValue::String(s) => write_string(s.as_str(), dst),
+            JsonValue::Array(arr) => {
+                dst.push('[');
+                for (i, v) in arr.iter().enumerate() {
+                    if i > 0 {
+                        dst.push(',');
+                    }
+                    v.canonicalize(dst)?;
+                }
+                dst.push(']');
+            },
+            JsonValue::Object(obj) => {
+                let mut members = obj.iter().map(|(k, v)| {
+                    (k.encode_utf16().collect::<Vec<u16>>(), k, v)
+                }).collect::<Vec<_>>();
+                members.sort_by(|a, b| a.0.cmp(&b.0));
+                if members.windows(2).any(|w| w[0].0 == w[1].0) {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "duplicate member name"));
+                }
+
+                dst.push('{');
+                for (i, (_, k, v)) in members.into_iter().enumerate() {
+                    if i > 0 {
+                        dst.push(',');
+                    }
+                    write_string(k.as_str(), dst);
+                    dst.push(':');
+                    v.canonicalize(dst)?;
+                }
+                dst.push('}');
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// canonicalize the JSON text
+pub fn canonicalize(json: &str) -> Result<String, CryptoError> {
+    let mut dst = String::with_capacity(json.len());
+    JsonValue::parse(json)?.canonicalize(&mut dst)?;
+    Ok(dst)
+}
+
+/// sign the canonical form of the JSON text, so the signature doesn't depend on the
+/// member order, the whitespace and the number/string representation of the `json`.
+pub fn sign_json<T, S: Signature<T>>(signer: &mut S, signature: &mut T, json: &str) -> Result<S::Output, CryptoError> {
+    let c = canonicalize(json)?;
+    signer.sign(signature, c.as_bytes())
+}
+
+/// verify the `signature` over the canonical form of the JSON text
+pub fn verify_json<T, S: Signature<T>>(verifier: &mut S, signature: &T, json: &str) -> Result<S::Output, CryptoError> {
+    let c = canonicalize(json)?;
+    verifier.verify(signature, c.as_bytes())
+}
@@ -0,0 +1,121 @@
+use crate::jcs::{JsonValue, canonicalize, sign_json, verify_json};
+use crate::{Signature, CryptoError, CryptoErrorKind, HMAC, Digest};
+use crate::sha::SHA256;
+
+#[test]
+fn jcs_number() {
+    // RFC 8785 Appendix B
+    let cases = [
+        (0x0000000000000000u64, "0"),
+        (0x8000000000000000, "0"),
+        (0x0000000000000001, "5e-324"),
+        (0x8000000000000001, "-5e-324"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0xffefffffffffffff, "-1.7976931348623157e+308"),
+        (0x4340000000000000, "9007199254740992"),
+        (0xc340000000000000, "-9007199254740992"),
+        (0x4430000000000000, "295147905179352830000"),
+        (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+        (0x44b52d02c7e14af6, "1e+23"),
+        (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+        (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x41b3de4355555553, "333333333.3333332"),
+        (0x41b3de4355555554, "333333333.33333325"),
+        (0x41b3de4355555555, "333333333.3333333"),
+        (0x41b3de4355555556, "333333333.3333334"),
+        (0x41b3de4355555557, "333333333.33333343"),
+        (0xbecbf647612f3696, "-0.0000033333333333333333"),
+        (0x43143ff3c1cb0959, "1424953923781206.2"),
+    ];
+    
+    for &(bits, tgt) in cases.iter() {
+        let mut s = String::new();
+        JsonValue::Number(f64::from_bits(bits)).canonicalize(&mut s).unwrap();
+        assert_eq!(s, tgt, "case: {:016x}", bits);
+    }
+    
+    let mut s = String::new();
+    assert!(JsonValue::Number(f64::NAN).canonicalize(&mut s).is_err());
+    assert!(JsonValue::Number(f64::INFINITY).canonicalize(&mut s).is_err());
+}
+
+#[test]
+fn jcs_canonicalize() {
+    // RFC 8785 3.2.2
+    let json = r#"{
+        "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+        "literals": [null, true, false]
+    }"#;
+    assert_eq!(canonicalize(json).unwrap(),
+        "{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}");
+    
+    // RFC 8785 3.2.3, sorted by the UTF-16 code units
+    let json = r#"{
+        "\u20ac": "Euro Sign",
+        "\r": "Carriage Return",
+        "\ufb33": "Hebrew Letter Dalet With Dagesh",
+        "1": "One",
+        "\ud83d\ude00": "Emoji: Grinning Face",
+        "\u0080": "Control",
+        "\u00f6": "Latin Small Letter O With Diaeresis"
+    }"#;
+    let c = canonicalize(json).unwrap();
+    let keys = match JsonValue::parse(c.as_str()).unwrap() {
+        JsonValue::Object(obj) => obj.into_iter().map(|(k, _)| k).collect::<Vec<_>>(),
+        _ => panic!("not an object"),
+    };
+    assert_eq!(keys, vec!["\r", "1", "\u{80}", "\u{f6}", "\u{20ac}", "\u{1f600}", "\u{fb33}"]);
+    
+    assert_eq!(canonicalize(" [ {\"b\" : [], \"a\": {}} , -0.0, \"\\u001f\"] ").unwrap(), "[{\"a\":{},\"b\":[]},0,\"\\u001f\"]");
+    
+    let invalid = ["", "{\"a\":1,\"a\":2}", "[1,]", "01", "1.", "-", "1e400", "\"\\ud800\"", "\"\\udc00\"",
+        "\"\t\"", "[1] 2", "{a:1}", "tru", "\"\\x\""];
+    for &s in invalid.iter() {
+        assert!(canonicalize(s).is_err(), "case: {}", s);
+    }
+    
+    let deep = format!("{}{}", "[".repeat(JsonValue::MAX_DEPTH + 2), "]".repeat(JsonValue::MAX_DEPTH + 2));
+    assert!(canonicalize(deep.as_str()).is_err());
+}
+
+struct TestSigner {
+    hmac: HMAC<SHA256>,
+}
+
+impl Signature<Vec<u8>> for TestSigner {
+    type Output = ();
+    
+    fn sign(&mut self, signature: &mut Vec<u8>, message: &[u8]) -> Result<(), CryptoError> {
+        self.hmac.reset();
+        self.hmac.write(message);
+        self.hmac.checksum(signature);
+        Ok(())
+    }
+    
+    fn verify(&mut self, signature: &Vec<u8>, message: &[u8]) -> Result<(), CryptoError> {
+        let mut s = Vec::new();
+        self.sign(&mut s, message)?;
+        if &s == signature {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "not match"))
+        }
+    }
+}
+
+#[test]
+fn jcs_sign_json() {
+    let mut signer = TestSigner { hmac: HMAC::new(b"webhook secret".to_vec(), SHA256::new()).unwrap() };
+    let mut sig = Vec::new();
+    sign_json(&mut signer, &mut sig, r#"{"event": "push", "id": 1.0, "tags": ["a", "b"]}"#).unwrap();
+    
+    // the same document with the different member order, whitespace and number representation
+    assert!(verify_json(&mut signer, &sig, r#"{"tags":["a","b"],"id":1,"event":"\u0070ush"}"#).is_ok());
+    assert!(verify_json(&mut signer, &sig, r#"{"tags":["b","a"],"id":1,"event":"push"}"#).is_err());
+    assert!(verify_json(&mut signer, &sig, r#"{"tags":["a","b"],"id":1,"event":"push""#).is_err());
+}
@@ -0,0 +1,9 @@
+//! JSON Canonicalization Scheme
+//!
+//! RFC 8785
+
+mod jcs;
+pub use jcs::{JsonValue, canonicalize, sign_json, verify_json};
+
+#[cfg(all(test, feature = "sha2"))]
+mod jcs_test;
@@ -0,0 +1,499 @@
+//! Argon2
+//! RFC 9106
+//!
+//! The memory of `m` KiB is split to `p` lanes of 1KiB blocks, every lane is split to 4 segments(slices), the segments
+//! of the same slice are filled independently, so the lanes can be filled by the worker threads. The block is
+//! $B[i][j] = G(B[i][j-1], B[l][z])$, the reference block $B[l][z]$ is selected by the previous block(Argon2d), or
+//! by the pseudo-random address block(Argon2i), Argon2id uses the Argon2i in the first half of the first pass and
+//! the Argon2d in the rest.
+//!
+//! The password hash is the PHC string format, e.g. `$argon2id$v=19$m=65536,t=3,p=4$<salt>$<hash>`, the salt and
+//! the hash are the Base64 without the padding.
+
+use std::fmt::{Display, Formatter};
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::encoding::Base64;
+use crate::kdf::blake2b::{Blake2b, BLAKE2B_MAX_LEN};
+
+const ARGON2_VERSION: u32 = 0x13;
+const ARGON2_QWORDS: usize = 128;
+const ARGON2_BLOCK_LEN: usize = ARGON2_QWORDS << 3;
+const ARGON2_SYNC_POINTS: usize = 4;
+const ARGON2_MAX_LANES: u32 = (1 << 24) - 1;
+const ARGON2_MIN_SALT_LEN: usize = 8;
+const ARGON2_MIN_HASH_LEN: usize = 4;
+/// the length of the salt and the hash of the `hash_password`
+pub const ARGON2_DEFAULT_SALT_LEN: usize = 16;
+pub const ARGON2_DEFAULT_HASH_LEN: usize = 32;
+/// the default limits of the costs, the memory is 1GiB(in KiB), the passes and the lanes
+pub const ARGON2_DEFAULT_MAX_MEMORY: u32 = 1 << 20;
+pub const ARGON2_DEFAULT_MAX_PASSES: u32 = 64;
+pub const ARGON2_DEFAULT_MAX_LANES: u32 = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argon2Variant {
+    /// the data-dependent memory access, it's used when the side-channel attack is not a concern
+    Argon2d,
+    /// the data-independent memory access
+    Argon2i,
+    /// the recommended hybrid variant
+    Argon2id,
+}
+
+impl Argon2Variant {
+    pub fn id(&self) -> u32 {
+        match self {
+            Argon2Variant::Argon2d => 0,
+            Argon2Variant::Argon2i => 1,
+            Argon2Variant::Argon2id => 2,
+        }
+    }
+
+    /// the identifier of the PHC string
+    pub fn name(&self) -> &'static str {
+        match self {
+            Argon2Variant::Argon2d => "argon2d",
+            Argon2Variant::Argon2i => "argon2i",
+            Argon2Variant::Argon2id => "argon2id",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "argon2d" => Some(Argon2Variant::Argon2d),
+            "argon2i" => Some(Argon2Variant::Argon2i),
+            "argon2id" => Some(Argon2Variant::Argon2id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Block([u64; ARGON2_QWORDS]);
+
+impl Block {
+    fn zero() -> Self {
+        Block([0u64; ARGON2_QWORDS])
+    }
+
+    fn from_le_bytes(b: &[u8]) -> Self {
+        let mut block = Self::zero();
+        block.0.iter_mut().zip(b.chunks_exact(8)).for_each(|(a, c)| {
+            *a = u64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]);
+        });
+        block
+    }
+
+    fn xor(&mut self, other: &Block) {
+        self.0.iter_mut().zip(other.0.iter()).for_each(|(a, &b)| *a ^= b);
+    }
+
+    /// the BLAKE2b round with the multiplication(BlaMka) of the 16 words at the `idx`
+    fn round(&mut self, idx: [usize; 16]) {
+        let v = &mut self.0;
+        macro_rules! gb {
+            ($a: expr, $b: expr, $c: expr, $d: expr) => {
+                let (a, b, c, d) = (idx[$a], idx[$b], idx[$c], idx[$d]);
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(2u64.wrapping_mul(v[a] & 0xffffffff).wrapping_mul(v[b] & 0xffffffff));
+                v[d] = (v[d] ^ v[a]).rotate_right(32);
+                v[c] = v[c].wrapping_add(v[d]).wrapping_add(2u64.wrapping_mul(v[c] & 0xffffffff).wrapping_mul(v[d] & 0xffffffff));
+                v[b] = (v[b] ^ v[c]).rotate_right(24);
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(2u64.wrapping_mul(v[a] & 0xffffffff).wrapping_mul(v[b] & 0xffffffff));
+                v[d] = (v[d] ^ v[a]).rotate_right(16);
+                v[c] = v[c].wrapping_add(v[d]).wrapping_add(2u64.wrapping_mul(v[c] & 0xffffffff).wrapping_mul(v[d] & 0xffffffff));
+                v[b] = (v[b] ^ v[c]).rotate_right(63);
+            };
+        }
+
+        gb!(0, 4, 8, 12);
+        gb!(1, 5, 9, 13);
+        gb!(2, 6, 10, 14);
+        gb!(3, 7, 11, 15);
+        gb!(0, 5, 10, 15);
+        gb!(1, 6, 11, 12);
+        gb!(2, 7, 8, 13);
+        gb!(3, 4, 9, 14);
+    }
+
+    /// the compression function $G(X, Y) = P(X \oplus Y) \oplus X \oplus Y$, the `old` block is also xored in the
+    /// passes after the first pass.
+    fn compress(x: &Block, y: &Block, old: Option<&Block>) -> Block {
+        let mut r = *x;
+        r.xor(y);
+        let mut z = r;
+        if let Some(old) = old {
+            z.xor(old);
+        }
+
+        for i in 0..8 {
+            let mut idx = [0usize; 16];
+            idx.iter_mut().enumerate().for_each(|(j, e)| *e = (i << 4) + j);
+            r.round(idx);
+        }
+        for i in 0..8 {
+            let mut idx = [0usize; 16];
+            idx.iter_mut().enumerate().for_each(|(j, e)| *e = (i << 1) + ((j >> 1) << 4) + (j & 1));
+            r.round(idx);
+        }
+
+        z.xor(&r);
+        z
+    }
+}
+
+/// the variable-length hash function $H'$
+fn hash_prime(out_len: usize, data: &[&[u8]]) -> Vec<u8> {
+    let len = (out_len as u32).to_le_bytes();
+    if out_len <= BLAKE2B_MAX_LEN {
+        let mut b = Blake2b::new(out_len);
+        b.write(len.as_ref());
+        data.iter().for_each(|d| b.write(d));
+        return b.finalize();
+    }
+
+    let mut b = Blake2b::new(BLAKE2B_MAX_LEN);
+    b.write(len.as_ref());
+    data.iter().for_each(|d| b.write(d));
+    let mut v = b.finalize();
+
+    let r = out_len.div_ceil(32) - 2;
+    let mut out = Vec::with_capacity(out_len);
+    out.extend_from_slice(&v[..32]);
+    for _ in 1..r {
+        v = Blake2b::digest(BLAKE2B_MAX_LEN, &[v.as_slice()]);
+        out.extend_from_slice(&v[..32]);
+    }
+    out.append(&mut Blake2b::digest(out_len - 32 * r, &[v.as_slice()]));
+    out
+}
+
+fn param_len(len: usize, name: &str) -> Result<[u8; 4], CryptoError> {
+    if len > (u32::MAX as usize) {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("The Argon2 {} is too long", name)))
+    } else {
+        Ok((len as u32).to_le_bytes())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Argon2 {
+    variant: Argon2Variant,
+    m_cost: u32,
+    t_cost: u32,
+    lanes: u32,
+    secret: Vec<u8>,
+    ad: Vec<u8>,
+    max_memory: u32,
+    max_passes: u32,
+    max_lanes: u32,
+}
+
+impl Argon2 {
+    /// `m_cost`: the memory size in KiB, it must be at least `8 * lanes`;
+    /// `t_cost`: the number of the passes, it must be great than 0;
+    /// `lanes`: the degree of the parallelism, it must be in the range $[1, 2^{24})$;
+    /// the costs are limited by the `ARGON2_DEFAULT_MAX_MEMORY`, `ARGON2_DEFAULT_MAX_PASSES` and `ARGON2_DEFAULT_MAX_LANES`.
+    pub fn new(variant: Argon2Variant, m_cost: u32, t_cost: u32, lanes: u32) -> Result<Self, CryptoError> {
+        Self::with_max_cost(variant, m_cost, t_cost, lanes, ARGON2_DEFAULT_MAX_MEMORY, ARGON2_DEFAULT_MAX_PASSES, ARGON2_DEFAULT_MAX_LANES)
+    }
+
+    /// the `LimitExceeded` is returned if the `m_cost` is more than the `max_memory` KiB, the `t_cost` is more than
+    /// the `max_passes` or the `lanes` is more than the `max_lanes`, the `verify_password` checks the costs of the
+    /// PHC string by the same limits before hashing.
+    pub fn with_max_cost(variant: Argon2Variant, m_cost: u32, t_cost: u32, lanes: u32, max_memory: u32, max_passes: u32, max_lanes: u32) -> Result<Self, CryptoError> {
+        if lanes == 0 || lanes > ARGON2_MAX_LANES {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong lanes: {}, the Argon2 lanes must be in the range [1, 2^24)", lanes)));
+        } else if t_cost == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The Argon2 passes must be great than 0"));
+        } else if (m_cost as u64) < 8 * (lanes as u64) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong memory size: {}KiB, the Argon2 memory must be at least 8 * lanes KiB", m_cost)));
+        } else if (m_cost as u64) * (ARGON2_BLOCK_LEN as u64) > (usize::MAX as u64) {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded,
+                format!("The Argon2 memory {}KiB cannot be addressed on the platform", m_cost)));
+        } else if m_cost > max_memory || t_cost > max_passes || lanes > max_lanes {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded,
+                format!("The Argon2 costs m={}, t={}, p={} exceed the limits m={}, t={}, p={}", m_cost, t_cost, lanes, max_memory, max_passes, max_lanes)));
+        }
+
+        Ok(Self { variant, m_cost, t_cost, lanes, secret: Vec::new(), ad: Vec::new(), max_memory, max_passes, max_lanes })
+    }
+
+    pub fn variant(&self) -> Argon2Variant {
+        self.variant
+    }
+
+    pub fn m_cost(&self) -> u32 {
+        self.m_cost
+    }
+
+    pub fn t_cost(&self) -> u32 {
+        self.t_cost
+    }
+
+    pub fn lanes(&self) -> u32 {
+        self.lanes
+    }
+
+    /// the limits of the memory(in KiB), the passes and the lanes
+    pub fn max_cost(&self) -> (u32, u32, u32) {
+        (self.max_memory, self.max_passes, self.max_lanes)
+    }
+
+    /// set the secret value `K`(e.g. the pepper which is stored apart from the password hashes)
+    pub fn set_secret(&mut self, secret: Vec<u8>) {
+        self.secret = secret;
+    }
+
+    /// set the associated data `X`
+    pub fn set_associated_data(&mut self, ad: Vec<u8>) {
+        self.ad = ad;
+    }
+
+    /// derive the `key_len` bytes key from the `password` and `salt`, the salt must be at least 8 bytes,
+    /// the `key_len` must be in the range $[4, 2^{32})$.
+    pub fn derive(&self, password: &[u8], salt: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        self.derive_with(password, salt, key_len, key, 1)
+    }
+
+    /// `derive` with the lanes filled by the `threads` worker threads, the key doesn't depend on the `threads`.
+    #[cfg(feature = "parallel")]
+    pub fn derive_parallel(&self, password: &[u8], salt: &[u8], key_len: usize, threads: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        if threads == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The number of threads must be great than 0"));
+        }
+        self.derive_with(password, salt, key_len, key, threads)
+    }
+
+    fn derive_with(&self, password: &[u8], salt: &[u8], key_len: usize, key: &mut Vec<u8>, threads: usize) -> Result<(), CryptoError> {
+        if salt.len() < ARGON2_MIN_SALT_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong salt length: {}, the Argon2 salt must be at least {} bytes", salt.len(), ARGON2_MIN_SALT_LEN)));
+        } else if key_len < ARGON2_MIN_HASH_LEN || key_len > (u32::MAX as usize) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the Argon2 tag length must be in the range [4, 2^32)", key_len)));
+        }
+
+        let h0 = Blake2b::digest(BLAKE2B_MAX_LEN, &[
+            self.lanes.to_le_bytes().as_ref(), (key_len as u32).to_le_bytes().as_ref(), self.m_cost.to_le_bytes().as_ref(),
+            self.t_cost.to_le_bytes().as_ref(), ARGON2_VERSION.to_le_bytes().as_ref(), self.variant.id().to_le_bytes().as_ref(),
+            param_len(password.len(), "password")?.as_ref(), password, param_len(salt.len(), "salt")?.as_ref(), salt,
+            param_len(self.secret.len(), "secret")?.as_ref(), self.secret.as_slice(),
+            param_len(self.ad.len(), "associated data")?.as_ref(), self.ad.as_slice(),
+        ]);
+
+        let lanes = self.lanes as usize;
+        let lane_len = (self.m_cost as usize / (ARGON2_SYNC_POINTS * lanes)) * ARGON2_SYNC_POINTS;
+        let mut memory = vec![Block::zero(); lane_len * lanes];
+        for l in 0..lanes {
+            for j in 0..2u32 {
+                let b = hash_prime(ARGON2_BLOCK_LEN, &[h0.as_slice(), j.to_le_bytes().as_ref(), (l as u32).to_le_bytes().as_ref()]);
+                memory[l * lane_len + (j as usize)] = Block::from_le_bytes(b.as_slice());
+            }
+        }
+
+        let seg_len = lane_len / ARGON2_SYNC_POINTS;
+        for pass in 0..(self.t_cost as usize) {
+            for slice in 0..ARGON2_SYNC_POINTS {
+                let segments = self.fill_slice(memory.as_slice(), lane_len, pass, slice, threads);
+                for (l, seg) in segments {
+                    let start = l * lane_len + slice * seg_len;
+                    memory[start..(start + seg_len)].copy_from_slice(seg.as_slice());
+                }
+            }
+        }
+
+        let mut c = memory[lane_len - 1];
+        (1..lanes).for_each(|l| c.xor(&memory[l * lane_len + lane_len - 1]));
+        let c = c.0.iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<_>>();
+
+        key.clear();
+        key.append(&mut hash_prime(key_len, &[c.as_slice()]));
+        Ok(())
+    }
+
+    /// the segments of the `slice` of all the lanes, the segments only reference the blocks of the other slices and
+    /// the own segment, so they are filled independently.
+    #[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+    fn fill_slice(&self, memory: &[Block], lane_len: usize, pass: usize, slice: usize, threads: usize) -> Vec<(usize, Vec<Block>)> {
+        let lanes = self.lanes as usize;
+        #[cfg(feature = "parallel")]
+        if threads > 1 && lanes > 1 {
+            return std::thread::scope(|s| {
+                let workers = (0..std::cmp::min(threads, lanes)).map(|w| {
+                    s.spawn(move || {
+                        (w..lanes).step_by(threads).map(|l| (l, self.fill_segment(memory, lane_len, pass, slice, l))).collect::<Vec<_>>()
+                    })
+                }).collect::<Vec<_>>();
+                workers.into_iter().flat_map(|w| w.join().unwrap()).collect()
+            });
+        }
+
+        (0..lanes).map(|l| (l, self.fill_segment(memory, lane_len, pass, slice, l))).collect()
+    }
+
+    fn fill_segment(&self, memory: &[Block], lane_len: usize, pass: usize, slice: usize, lane: usize) -> Vec<Block> {
+        let seg_len = lane_len / ARGON2_SYNC_POINTS;
+        let (lane_start, seg_start) = (lane * lane_len, slice * seg_len);
+        let mut seg = memory[(lane_start + seg_start)..(lane_start + seg_start + seg_len)].to_vec();
+
+        let is_data_independent = match self.variant {
+            Argon2Variant::Argon2d => false,
+            Argon2Variant::Argon2i => true,
+            Argon2Variant::Argon2id => pass == 0 && slice < (ARGON2_SYNC_POINTS >> 1),
+        };
+        let (mut input, mut address) = (Block::zero(), Block::zero());
+        if is_data_independent {
+            input.0[..6].copy_from_slice(&[pass as u64, lane as u64, slice as u64, memory.len() as u64,
+                self.t_cost as u64, self.variant.id() as u64]);
+        }
+        let next_addresses = |input: &mut Block, address: &mut Block| {
+            input.0[6] += 1;
+            *address = Block::compress(&Block::zero(), &Block::compress(&Block::zero(), input, None), None);
+        };
+
+        let start = if pass == 0 && slice == 0 {
+            if is_data_independent {
+                next_addresses(&mut input, &mut address);
+            }
+            2
+        } else {
+            0
+        };
+
+        for i in start..seg_len {
+            if is_data_independent && i % ARGON2_QWORDS == 0 {
+                next_addresses(&mut input, &mut address);
+            }
+
+            let prev = if i > 0 {
+                &seg[i - 1]
+            } else if slice == 0 {
+                &memory[lane_start + lane_len - 1]
+            } else {
+                &memory[lane_start + seg_start - 1]
+            };
+            let rand = if is_data_independent { address.0[i % ARGON2_QWORDS] } else { prev.0[0] };
+
+            let ref_lane = if pass == 0 && slice == 0 { lane } else { ((rand >> 32) % (self.lanes as u64)) as usize };
+            let ref_index = Self::reference_index(lane_len, pass, slice, i, rand & 0xffffffff, ref_lane == lane);
+            let reference = if ref_lane == lane && ref_index >= seg_start && ref_index < seg_start + i {
+                &seg[ref_index - seg_start]
+            } else {
+                &memory[ref_lane * lane_len + ref_index]
+            };
+
+            seg[i] = Block::compress(prev, reference, if pass > 0 { Some(&seg[i]) } else { None });
+        }
+
+        seg
+    }
+
+    /// the index of the reference block in the reference lane
+    fn reference_index(lane_len: usize, pass: usize, slice: usize, i: usize, j1: u64, is_same_lane: bool) -> usize {
+        let seg_len = lane_len / ARGON2_SYNC_POINTS;
+        let area = if pass == 0 {
+            if slice == 0 || is_same_lane {
+                slice * seg_len + i - 1
+            } else {
+                slice * seg_len - ((i == 0) as usize)
+            }
+        } else if is_same_lane {
+            lane_len - seg_len + i - 1
+        } else {
+            lane_len - seg_len - ((i == 0) as usize)
+        };
+
+        let x = (j1 * j1) >> 32;
+        let relative = area - 1 - (((area as u64) * x) >> 32) as usize;
+        let start = if pass == 0 || slice == ARGON2_SYNC_POINTS - 1 { 0 } else { (slice + 1) * seg_len };
+        (start + relative) % lane_len
+    }
+
+    /// hash the `password` with the random 16 bytes salt to the PHC string
+    pub fn hash_password<R: IterSource<u32>>(&self, password: &[u8], rd: &mut R) -> Result<String, CryptoError> {
+        let mut salt = Vec::with_capacity(ARGON2_DEFAULT_SALT_LEN);
+        rd.iter_mut().take(ARGON2_DEFAULT_SALT_LEN >> 2).for_each(|x| salt.extend_from_slice(x.to_be_bytes().as_ref()));
+
+        let mut hash = Vec::with_capacity(ARGON2_DEFAULT_HASH_LEN);
+        self.derive(password, salt.as_slice(), ARGON2_DEFAULT_HASH_LEN, &mut hash)?;
+        Ok(Argon2Hash { variant: self.variant, m_cost: self.m_cost, t_cost: self.t_cost, lanes: self.lanes, salt, hash }.to_string())
+    }
+
+    /// verify the `password` against the PHC string `hash`, the variant and the costs are read from the `hash`,
+    /// the secret, the associated data and the limits of the costs are the `self`'s.
+    pub fn verify_password(&self, hash: &str, password: &[u8]) -> Result<(), CryptoError> {
+        let h = Argon2Hash::parse(hash)?;
+        let mut argon2 = Self::with_max_cost(h.variant, h.m_cost, h.t_cost, h.lanes, self.max_memory, self.max_passes, self.max_lanes)?;
+        argon2.secret.clone_from(&self.secret);
+        argon2.ad.clone_from(&self.ad);
+
+        let mut tag = Vec::with_capacity(h.hash.len());
+        argon2.derive(password, h.salt.as_slice(), h.hash.len(), &mut tag)?;
+        if tag.iter().zip(h.hash.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The Argon2 password hash not match"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The PHC string of the Argon2 password hash
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Argon2Hash {
+    pub variant: Argon2Variant,
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub lanes: u32,
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+impl Argon2Hash {
+    /// parse the `$<variant>$v=19$m=<m_cost>,t=<t_cost>,p=<lanes>$<salt>$<hash>`
+    pub fn parse(s: &str) -> Result<Self, CryptoError> {
+        let phc_err = |msg: &str| CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid Argon2 hash: {}", msg));
+        let fields = s.split('$').collect::<Vec<_>>();
+        if fields.len() != 6 || !fields[0].is_empty() {
+            return Err(phc_err("the number of the fields is wrong"));
+        }
+
+        let variant = Argon2Variant::from_name(fields[1]).ok_or_else(||
+            CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The Argon2 variant {} is not supported", fields[1])))?;
+        if fields[2] != format!("v={}", ARGON2_VERSION) {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The Argon2 version {} is not supported", fields[2])));
+        }
+
+        let (mut m_cost, mut t_cost, mut lanes) = (None, None, None);
+        for param in fields[3].split(',') {
+            let (name, value) = param.split_once('=').ok_or_else(|| phc_err("the parameter is not the name=value"))?;
+            let value = value.parse::<u32>().map_err(|_| phc_err("the parameter is not the decimal u32"))?;
+            match name {
+                "m" if m_cost.is_none() => m_cost = Some(value),
+                "t" if t_cost.is_none() => t_cost = Some(value),
+                "p" if lanes.is_none() => lanes = Some(value),
+                _ => return Err(phc_err("the parameter is unknown or repeated")),
+            }
+        }
+
+        let b64 = Base64::standard().with_padding(false);
+        Ok(Self {
+            variant,
+            m_cost: m_cost.ok_or_else(|| phc_err("the m is missing"))?,
+            t_cost: t_cost.ok_or_else(|| phc_err("the t is missing"))?,
+            lanes: lanes.ok_or_else(|| phc_err("the p is missing"))?,
+            salt: b64.decode(fields[4])?,
+            hash: b64.decode(fields[5])?,
+        })
+    }
+}
+
+impl Display for Argon2Hash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let b64 = Base64::standard().with_padding(false);
+        write!(f, "${}$v={}$m={},t={},p={}${}${}", self.variant.name(), ARGON2_VERSION, self.m_cost, self.t_cost,
+            self.lanes, b64.encode(self.salt.as_slice()), b64.encode(self.hash.as_slice()))
+    }
+}
@@ -0,0 +1,118 @@
+//! BLAKE2b(the unkeyed hash of the Argon2)
+//! RFC 7693
+
+const BLAKE2B_BLOCK_LEN: usize = 128;
+pub(super) const BLAKE2B_MAX_LEN: usize = 64;
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+pub(super) struct Blake2b {
+    h: [u64; 8],
+    buf: Vec<u8>,
+    len: u128,
+    out_len: usize,
+}
+
+impl Blake2b {
+    /// `out_len` must be in the range [1, 64]
+    pub(super) fn new(out_len: usize) -> Self {
+        debug_assert!(out_len > 0 && out_len <= BLAKE2B_MAX_LEN);
+        let mut h = BLAKE2B_IV;
+        h[0] ^= 0x01010000 ^ (out_len as u64);
+        Self { h, buf: Vec::with_capacity(BLAKE2B_BLOCK_LEN), len: 0, out_len }
+    }
+
+    pub(super) fn write(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // the last block is compressed in the `finalize`
+            if self.buf.len() == BLAKE2B_BLOCK_LEN {
+                self.len += BLAKE2B_BLOCK_LEN as u128;
+                let block = std::mem::take(&mut self.buf);
+                self.compress(block.as_slice(), false);
+                self.buf = block;
+                self.buf.clear();
+            }
+
+            let n = std::cmp::min(BLAKE2B_BLOCK_LEN - self.buf.len(), data.len());
+            self.buf.extend_from_slice(&data[..n]);
+            data = &data[n..];
+        }
+    }
+
+    pub(super) fn finalize(mut self) -> Vec<u8> {
+        self.len += self.buf.len() as u128;
+        let mut block = std::mem::take(&mut self.buf);
+        block.resize(BLAKE2B_BLOCK_LEN, 0);
+        self.compress(block.as_slice(), true);
+
+        let mut out = self.h.iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<_>>();
+        out.truncate(self.out_len);
+        out
+    }
+
+    /// hash the `data` with the `out_len` bytes output
+    pub(super) fn digest(out_len: usize, data: &[&[u8]]) -> Vec<u8> {
+        let mut b = Self::new(out_len);
+        data.iter().for_each(|d| b.write(d));
+        b.finalize()
+    }
+
+    fn compress(&mut self, block: &[u8], is_last: bool) {
+        let mut m = [0u64; 16];
+        m.iter_mut().zip(block.chunks_exact(8)).for_each(|(a, c)| {
+            *a = u64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]);
+        });
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(self.h.as_ref());
+        v[8..].copy_from_slice(BLAKE2B_IV.as_ref());
+        v[12] ^= self.len as u64;
+        v[13] ^= (self.len >> 64) as u64;
+        if is_last {
+            v[14] = !v[14];
+        }
+
+        macro_rules! g {
+            ($a: expr, $b: expr, $c: expr, $d: expr, $x: expr, $y: expr) => {
+                v[$a] = v[$a].wrapping_add(v[$b]).wrapping_add($x);
+                v[$d] = (v[$d] ^ v[$a]).rotate_right(32);
+                v[$c] = v[$c].wrapping_add(v[$d]);
+                v[$b] = (v[$b] ^ v[$c]).rotate_right(24);
+                v[$a] = v[$a].wrapping_add(v[$b]).wrapping_add($y);
+                v[$d] = (v[$d] ^ v[$a]).rotate_right(16);
+                v[$c] = v[$c].wrapping_add(v[$d]);
+                v[$b] = (v[$b] ^ v[$c]).rotate_right(63);
+            };
+        }
+
+        for i in 0..12 {
+            let s = &BLAKE2B_SIGMA[i % 10];
+            g!(0, 4, 8, 12, m[s[0]], m[s[1]]);
+            g!(1, 5, 9, 13, m[s[2]], m[s[3]]);
+            g!(2, 6, 10, 14, m[s[4]], m[s[5]]);
+            g!(3, 7, 11, 15, m[s[6]], m[s[7]]);
+            g!(0, 5, 10, 15, m[s[8]], m[s[9]]);
+            g!(1, 6, 11, 12, m[s[10]], m[s[11]]);
+            g!(2, 7, 8, 13, m[s[12]], m[s[13]]);
+            g!(3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        self.h.iter_mut().enumerate().for_each(|(i, h)| *h ^= v[i] ^ v[i + 8]);
+    }
+}
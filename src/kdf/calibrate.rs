@@ -0,0 +1,126 @@
+//! KDF work-factor calibration
+//!
+//! Measure the cost of the KDF on the host machine, and recommend the work factor which makes
+//! one derivation take about the target duration. The work factor is extrapolated linearly from
+//! a sample run which takes at least the `min_sample` duration, so the recommendation is only an
+//! estimate and the application should pick the parameters at install time rather than at every run.
+//!
+//! The memory-hard KDFs are calibrated under the caller-chosen memory parameters: the scrypt `N` is rounded down to
+//! the power of 2 and limited by the `SCRYPT_DEFAULT_MAX_MEMORY`, the Argon2 passes are calibrated at the given memory
+//! size and lanes(RFC 9106 4: choose the memory first, then the passes) and limited by the `ARGON2_DEFAULT_MAX_PASSES`.
+
+use crate::{Digest, CryptoError, CryptoErrorKind};
+use crate::kdf::{PBKDF2, Scrypt, Argon2, Argon2Variant, ARGON2_DEFAULT_MAX_PASSES};
+use std::time::{Duration, Instant};
+
+const CALIBRATE_PASSWORD: &[u8] = b"rcrypto calibration password";
+const CALIBRATE_SALT: &[u8] = b"rcrypto calibration salt";
+const CALIBRATE_KEY_LEN: usize = 32;
+const CALIBRATE_PBKDF2_START_ITERATIONS: usize = 1000;
+const CALIBRATE_SCRYPT_START_N: usize = 1 << 10;
+
+pub struct KDFCalibrator {
+    target: Duration,
+    min_sample: Duration,
+}
+
+impl KDFCalibrator {
+    /// `target`: the expected duration of one derivation, e.g. 250ms, it must be great than 0;
+    pub fn new(target: Duration) -> Result<Self, CryptoError> {
+        if target.as_nanos() == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The target duration must be great than 0"));
+        }
+
+        Ok(Self {
+            target,
+            min_sample: std::cmp::min(target, Duration::from_millis(50)),
+        })
+    }
+
+    pub fn target(&self) -> Duration {
+        self.target
+    }
+
+    /// set the minimum duration of the sample run, the longer sample gives the more accurate recommendation.
+    pub fn set_min_sample(&mut self, min_sample: Duration) {
+        self.min_sample = std::cmp::min(self.target, min_sample);
+    }
+
+    /// scale the sample `work` which takes the `elapsed` duration to the target duration
+    fn extrapolate(&self, work: usize, elapsed: Duration) -> usize {
+        let elapsed = std::cmp::max(elapsed.as_nanos(), 1);
+        let w = (work as u128) * self.target.as_nanos() / elapsed;
+        std::cmp::max(std::cmp::min(w, usize::MAX as u128) as usize, 1)
+    }
+
+    /// recommend the PBKDF2 iteration count with the `digest` as the PRF
+    pub fn pbkdf2_iterations<D: Digest>(&self, digest: D) -> Result<usize, CryptoError> {
+        let key_len = (digest.bits_len() + 7) >> 3;
+        let mut pbkdf2 = PBKDF2::new(digest, CALIBRATE_PBKDF2_START_ITERATIONS)?;
+        let mut key = Vec::with_capacity(key_len);
+
+        let mut iterations = CALIBRATE_PBKDF2_START_ITERATIONS;
+        loop {
+            pbkdf2.set_iterations(iterations)?;
+            let now = Instant::now();
+            pbkdf2.derive(CALIBRATE_PASSWORD, CALIBRATE_SALT, key_len, &mut key)?;
+            let elapsed = now.elapsed();
+
+            if elapsed >= self.min_sample || iterations > (usize::MAX >> 1) {
+                return Ok(self.extrapolate(iterations, elapsed));
+            }
+            iterations <<= 1;
+        }
+    }
+
+    /// the largest power of 2 `N` which is accepted by the `Scrypt::new` with the `r` and `p`
+    fn scrypt_max_n(r: usize, p: usize) -> usize {
+        let mut n = 1usize << (usize::BITS - 1);
+        while n > 2 && Scrypt::new(n, r, p).is_err() {
+            n >>= 1;
+        }
+        n
+    }
+
+    /// recommend the scrypt cost parameter `N` with the block size `r` and the parallelization `p`
+    pub fn scrypt_n(&self, r: usize, p: usize) -> Result<usize, CryptoError> {
+        Scrypt::new(2, r, p)?;
+        let max_n = Self::scrypt_max_n(r, p);
+        let mut key = Vec::with_capacity(CALIBRATE_KEY_LEN);
+
+        let mut n = std::cmp::min(CALIBRATE_SCRYPT_START_N, max_n);
+        loop {
+            let scrypt = Scrypt::new(n, r, p)?;
+            let now = Instant::now();
+            scrypt.derive(CALIBRATE_PASSWORD, CALIBRATE_SALT, CALIBRATE_KEY_LEN, &mut key)?;
+            let elapsed = now.elapsed();
+
+            if elapsed >= self.min_sample || n >= max_n {
+                let w = self.extrapolate(n, elapsed);
+                let w = 1usize << (usize::BITS - 1 - w.leading_zeros());
+                return Ok(std::cmp::max(std::cmp::min(w, max_n), 2));
+            }
+            n <<= 1;
+        }
+    }
+
+    /// recommend the Argon2 passes `t_cost` with the memory size `m_cost`(in KiB) and the `lanes`, the
+    /// recommendation is for the single-threaded `Argon2::derive`.
+    pub fn argon2_passes(&self, variant: Argon2Variant, m_cost: u32, lanes: u32) -> Result<u32, CryptoError> {
+        let mut key = Vec::with_capacity(CALIBRATE_KEY_LEN);
+
+        let mut t_cost = 1u32;
+        loop {
+            let argon2 = Argon2::new(variant, m_cost, t_cost, lanes)?;
+            let now = Instant::now();
+            argon2.derive(CALIBRATE_PASSWORD, CALIBRATE_SALT, CALIBRATE_KEY_LEN, &mut key)?;
+            let elapsed = now.elapsed();
+
+            if elapsed >= self.min_sample || t_cost >= ARGON2_DEFAULT_MAX_PASSES {
+                let w = self.extrapolate(t_cost as usize, elapsed);
+                return Ok(std::cmp::min(w, ARGON2_DEFAULT_MAX_PASSES as usize) as u32);
+            }
+            t_cost = std::cmp::min(t_cost << 1, ARGON2_DEFAULT_MAX_PASSES);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! HKDF(HMAC-based Extract-and-Expand Key Derivation Function)
+//! RFC 5869
+//!
+//! PRK = HMAC(salt, IKM), OKM = T(1) || T(2) || ... || T(N), T(i) = HMAC(PRK, T(i-1) || info || i), T(0) = empty,
+//! the length of the OKM must be less than or equal to `255 * HashLen`.
+
+use crate::{HMAC, Digest, CryptoError, CryptoErrorKind};
+
+#[derive(Clone)]
+pub struct HKDF<D: Digest> {
+    hmac: HMAC<D>,
+}
+
+impl<D: Digest> HKDF<D> {
+    pub fn new(digest: D) -> Result<Self, CryptoError> {
+        Ok(Self {
+            hmac: HMAC::new(Vec::new(), digest)?,
+        })
+    }
+
+    /// the length of the PRK in bytes(HashLen)
+    pub fn hash_len(&self) -> usize {
+        (self.hmac.bits_len() + 7) >> 3
+    }
+
+    /// the maximum length of the OKM in bytes, `255 * HashLen`
+    pub fn max_key_len(&self) -> usize {
+        255 * self.hash_len()
+    }
+
+    /// extract the pseudorandom key `prk` from the input keying material `ikm`, the empty `salt` is the same as the
+    /// `HashLen` zero bytes.
+    pub fn extract(&mut self, salt: &[u8], ikm: &[u8], prk: &mut Vec<u8>) {
+        self.hmac.set_key(salt.to_vec());
+        self.hmac.write(ikm);
+        self.hmac.checksum(prk);
+    }
+
+    /// expand the `prk` to the `key_len` bytes output keying material, the `key_len` must be less than or equal to
+    /// `255 * HashLen`, and the `prk` must be at least `HashLen` bytes.
+    pub fn expand(&mut self, prk: &[u8], info: &[u8], key_len: usize, okm: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let h_len = self.hash_len();
+        if key_len > self.max_key_len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the HKDF output must not be more than {} bytes", key_len, self.max_key_len())));
+        } else if prk.len() < h_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong PRK length: {}, the HKDF PRK must be at least {} bytes", prk.len(), h_len)));
+        }
+
+        self.hmac.set_key(prk.to_vec());
+        okm.clear();
+        let mut t = Vec::with_capacity(h_len);
+        for i in 1..=(key_len.div_ceil(h_len) as u8) {
+            self.hmac.reset();
+            self.hmac.write(t.as_slice());
+            self.hmac.write(info);
+            self.hmac.write(&[i]);
+            self.hmac.checksum(&mut t);
+            okm.extend_from_slice(t.as_slice());
+        }
+
+        okm.truncate(key_len);
+        Ok(())
+    }
+
+    /// the one-shot `extract` and `expand`
+    pub fn derive(&mut self, salt: &[u8], ikm: &[u8], info: &[u8], key_len: usize, okm: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let mut prk = Vec::with_capacity(self.hash_len());
+        self.extract(salt, ikm, &mut prk);
+        self.expand(prk.as_slice(), info, key_len, okm)
+    }
+}
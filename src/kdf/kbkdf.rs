@@ -0,0 +1,101 @@
+//! KBKDF(Key-based Key Derivation Function) in the counter mode
+//! NIST SP 800-108r1 4.1
+//!
+//! K(i) = PRF(K_I, [i]_r || FixedInput), KO = K(1) || K(2) || ... || K(n), n = ceil(L / h),
+//! FixedInput = Label || 0x00 || Context || [L]_32, where the PRF is the HMAC or the CMAC keyed by the K_I,
+//! `[i]_r` is the `r` bits big-endian counter which can also be placed after the fixed input.
+
+use crate::{Digest, CryptoError, CryptoErrorKind};
+
+/// the position of the counter in the PRF input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KBKDFCounterLocation {
+    BeforeFixed,
+    AfterFixed,
+}
+
+#[derive(Clone)]
+pub struct KBKDF<P: Digest> {
+    prf: P,
+    counter_bits: usize,
+    location: KBKDFCounterLocation,
+}
+
+impl<P: Digest> KBKDF<P> {
+    /// `prf`: the PRF keyed by the key derivation key K_I, e.g. `HMAC::new(ki, SHA256::new())` or
+    /// `CMAC::new(AES::new(ki))`, the 32 bits counter is placed before the fixed input.
+    pub fn new(prf: P) -> Self {
+        Self { prf, counter_bits: 32, location: KBKDFCounterLocation::BeforeFixed }
+    }
+
+    /// `counter_bits`: the length `r` of the counter, it must be 8, 16, 24 or 32.
+    pub fn with_counter(prf: P, counter_bits: usize, location: KBKDFCounterLocation) -> Result<Self, CryptoError> {
+        if counter_bits == 0 || counter_bits > 32 || counter_bits & 7 != 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong counter length: {}, it must be 8, 16, 24 or 32 bits", counter_bits)));
+        }
+
+        Ok(Self { prf, counter_bits, location })
+    }
+
+    pub fn counter_bits(&self) -> usize {
+        self.counter_bits
+    }
+
+    pub fn location(&self) -> KBKDFCounterLocation {
+        self.location
+    }
+
+    /// the length of the PRF output in bytes(h)
+    pub fn prf_len(&self) -> usize {
+        (self.prf.bits_len() + 7) >> 3
+    }
+
+    /// the maximum length of the KO in bytes, `(2^r - 1) * h`
+    pub fn max_key_len(&self) -> usize {
+        let n = (1u64 << self.counter_bits) - 1;
+        let max = n.saturating_mul(self.prf_len() as u64);
+        // the L is encoded as the 32 bits length in bits
+        std::cmp::min(max, (u32::MAX >> 3) as u64) as usize
+    }
+
+    /// derive the `key_len` bytes key with the fixed input `label || 0x00 || context || [L]_32`
+    pub fn derive(&mut self, label: &[u8], context: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let mut fixed = Vec::with_capacity(label.len() + context.len() + 5);
+        fixed.extend_from_slice(label);
+        fixed.push(0);
+        fixed.extend_from_slice(context);
+        fixed.extend_from_slice(((key_len as u32) << 3).to_be_bytes().as_ref());
+        self.derive_with_fixed_input(fixed.as_slice(), key_len, key)
+    }
+
+    /// derive the `key_len` bytes key with the encoded `fixed_input`, e.g. the test vectors of the NIST CAVP
+    /// or the protocol specific encoding of the label and context.
+    pub fn derive_with_fixed_input(&mut self, fixed_input: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        if key_len > self.max_key_len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the KBKDF output must not be more than {} bytes", key_len, self.max_key_len())));
+        }
+
+        let h_len = self.prf_len();
+        let c_len = self.counter_bits >> 3;
+        key.clear();
+        let mut k = Vec::with_capacity(h_len);
+        for i in 1..=(key_len.div_ceil(h_len) as u32) {
+            let counter = i.to_be_bytes();
+            self.prf.reset();
+            if self.location == KBKDFCounterLocation::BeforeFixed {
+                self.prf.write(&counter[(4 - c_len)..]);
+                self.prf.write(fixed_input);
+            } else {
+                self.prf.write(fixed_input);
+                self.prf.write(&counter[(4 - c_len)..]);
+            }
+            self.prf.checksum(&mut k);
+            key.extend_from_slice(k.as_slice());
+        }
+
+        key.truncate(key_len);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! 3GPP KDF  
+//! 3GPP TS 33.220 Annex B, 3GPP TS 33.401 Annex A  
+//! 
+//! KDF(Key, S) = HMAC-SHA-256(Key, S), S = FC || P0 || L0 || P1 || L1 || ... || Pn || Ln,
+//! where Li is the length in bytes of Pi which is encoded as a 16-bit big-endian integer.
+
+use crate::{HMAC, Digest, CryptoError, CryptoErrorKind};
+use crate::sha::SHA256;
+
+/// The key derivation function used in the LTE/5G key hierarchy
+#[derive(Clone)]
+pub struct KDF3GPP {
+    hmac: HMAC<SHA256>,
+}
+
+impl KDF3GPP {
+    /// FC for the K_ASME derivation
+    pub const FC_K_ASME: u8 = 0x10;
+    /// FC for the K_eNB derivation
+    pub const FC_K_ENB: u8 = 0x11;
+    /// FC for the NH derivation
+    pub const FC_NH: u8 = 0x12;
+    /// FC for the K_eNB* derivation
+    pub const FC_K_ENB_STAR: u8 = 0x13;
+    /// FC for the NAS/RRC/UP algorithm key derivation
+    pub const FC_ALG_KEY: u8 = 0x15;
+    
+    /// algorithm type distinguisher
+    pub const NAS_ENC_ALG: u8 = 0x01;
+    pub const NAS_INT_ALG: u8 = 0x02;
+    pub const RRC_ENC_ALG: u8 = 0x03;
+    pub const RRC_INT_ALG: u8 = 0x04;
+    pub const UP_ENC_ALG: u8 = 0x05;
+    pub const UP_INT_ALG: u8 = 0x06;
+    
+    /// algorithm identity, EEA3/EIA3 is the ZUC based algorithm
+    pub const EEA0: u8 = 0x00;
+    pub const EEA1: u8 = 0x01;
+    pub const EEA2: u8 = 0x02;
+    pub const EEA3: u8 = 0x03;
+    pub const EIA1: u8 = 0x01;
+    pub const EIA2: u8 = 0x02;
+    pub const EIA3: u8 = 0x03;
+    
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            hmac: HMAC::new(key, SHA256::new()).unwrap(),
+        }
+    }
+    
+    /// set new `key`
+    pub fn set_key(&mut self, key: Vec<u8>) {
+        self.hmac.set_key(key);
+    }
+    
+    /// derive the 256-bits key by the `fc` and the parameters `P0, P1, ..., Pn`, 
+    /// the length in bytes of each parameter must be less than 65536.
+    pub fn derive(&mut self, fc: u8, params: &[&[u8]], key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        if params.iter().any(|p| p.len() > (u16::MAX as usize)) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The length of parameter must be less than {} in bytes", (u16::MAX as usize) + 1)));
+        }
+        
+        self.hmac.reset();
+        self.hmac.write(&[fc]);
+        for p in params.iter() {
+            self.hmac.write(p);
+            self.hmac.write((p.len() as u16).to_be_bytes().as_ref());
+        }
+        self.hmac.checksum(key);
+        
+        Ok(())
+    }
+    
+    /// K_ASME = KDF(CK || IK, 0x10, SN id, SQN ⊕ AK)  
+    /// `sn_id`: 3 bytes PLMN identity, `sqn_xor_ak`: 6 bytes
+    pub fn k_asme(ck: &[u8], ik: &[u8], sn_id: &[u8], sqn_xor_ak: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if ck.len() != 16 || ik.len() != 16 || sn_id.len() != 3 || sqn_xor_ak.len() != 6 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                "The length of CK/IK/SN id/SQN⊕AK must be 16/16/3/6 in bytes"));
+        }
+        
+        let mut key = Vec::with_capacity(32);
+        key.extend_from_slice(ck);
+        key.extend_from_slice(ik);
+        let mut kdf = Self::new(key);
+        let mut k_asme = Vec::with_capacity(32);
+        kdf.derive(Self::FC_K_ASME, &[sn_id, sqn_xor_ak], &mut k_asme)?;
+        Ok(k_asme)
+    }
+    
+    /// K_eNB = KDF(K_ASME, 0x11, uplink NAS COUNT)
+    pub fn k_enb(k_asme: &[u8], ul_nas_count: u32) -> Result<Vec<u8>, CryptoError> {
+        let mut kdf = Self::new(k_asme.to_vec());
+        let mut k_enb = Vec::with_capacity(32);
+        kdf.derive(Self::FC_K_ENB, &[ul_nas_count.to_be_bytes().as_ref()], &mut k_enb)?;
+        Ok(k_enb)
+    }
+    
+    /// NH = KDF(K_ASME, 0x12, SYNC-input), the `sync_input` is the K_eNB or the previous NH
+    pub fn nh(k_asme: &[u8], sync_input: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut kdf = Self::new(k_asme.to_vec());
+        let mut nh = Vec::with_capacity(32);
+        kdf.derive(Self::FC_NH, &[sync_input], &mut nh)?;
+        Ok(nh)
+    }
+    
+    /// K_alg = KDF(key, 0x15, algorithm type distinguisher, algorithm identity), and
+    /// truncated to the 128 least significant bits.  
+    /// 
+    /// `key`: K_ASME for NAS keys, K_eNB for RRC/UP keys
+    pub fn algorithm_key(key: &[u8], distinguisher: u8, alg_id: u8) -> Result<Vec<u8>, CryptoError> {
+        let mut kdf = Self::new(key.to_vec());
+        let mut k = Vec::with_capacity(32);
+        kdf.derive(Self::FC_ALG_KEY, &[&[distinguisher], &[alg_id]], &mut k)?;
+        Ok(k.split_off(16))
+    }
+}
@@ -0,0 +1,300 @@
+use crate::kdf::{KDF3GPP, PBKDF2, HKDF, KBKDF, KBKDFCounterLocation, X963KDF, KeyHierarchy, Scrypt, KDFCalibrator, Argon2, Argon2Variant, Argon2Hash,
+    ARGON2_DEFAULT_MAX_MEMORY, ARGON2_DEFAULT_MAX_PASSES, ARGON2_DEFAULT_MAX_LANES};
+use crate::sha::{SHA1, SHA256, SHA512};
+use crate::{SM3, HMAC, CryptoErrorKind};
+use std::time::Duration;
+use rmath::rand::{CryptoRand, DefaultSeed};
+
+fn cvt_bytes_to_str(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{:02x}", x)).collect::<String>()
+}
+
+#[test]
+fn kdf_3gpp() {
+    let ck = (0..16u8).collect::<Vec<_>>();
+    let ik = (16..32u8).collect::<Vec<_>>();
+    let (sn_id, sqn_xor_ak) = ([0x02u8, 0xf8, 0x39], [0xfdu8, 0x8e, 0xef, 0x40, 0xdf, 0x7d]);
+    
+    let k_asme = KDF3GPP::k_asme(ck.as_slice(), ik.as_slice(), sn_id.as_ref(), sqn_xor_ak.as_ref()).unwrap();
+    assert_eq!(cvt_bytes_to_str(k_asme.as_slice()), "25306101437038f1647db37c8a7b70802ddf495ceae7902cc7ad9cd7252d6055");
+    
+    let k_enb = KDF3GPP::k_enb(k_asme.as_slice(), 0x1234).unwrap();
+    assert_eq!(cvt_bytes_to_str(k_enb.as_slice()), "94148cc2a68db1062de8c0a1b052118d051f9ea0c6ab48e63267758c6b42d6ed");
+    
+    let nh = KDF3GPP::nh(k_asme.as_slice(), k_enb.as_slice()).unwrap();
+    assert_eq!(cvt_bytes_to_str(nh.as_slice()), "346de2d8d9a1eecce223bdf9377a180a6fea0fd0f5de883c8110fd0efb60dbf0");
+    
+    let k = KDF3GPP::algorithm_key(k_asme.as_slice(), KDF3GPP::NAS_ENC_ALG, KDF3GPP::EEA3).unwrap();
+    assert_eq!(cvt_bytes_to_str(k.as_slice()), "df472ba0f5372641dd5d93ce8388a036");
+    
+    let k = KDF3GPP::algorithm_key(k_enb.as_slice(), KDF3GPP::RRC_INT_ALG, KDF3GPP::EIA2).unwrap();
+    assert_eq!(cvt_bytes_to_str(k.as_slice()), "e02b430795fd6e78240e707e4a52e930");
+    
+    assert!(KDF3GPP::k_asme(ck.as_slice(), ik.as_slice(), &sn_id[..2], sqn_xor_ak.as_ref()).is_err());
+    let mut kdf = KDF3GPP::new(k_asme);
+    let mut buf = Vec::new();
+    assert!(kdf.derive(KDF3GPP::FC_K_ENB, &[vec![0u8; 65536].as_slice()], &mut buf).is_err());
+}
+
+
+#[test]
+fn pbkdf2() {
+    let mut key = Vec::new();
+    // RFC 6070
+    let cases = [
+        (1usize, "0c60c80f961f0e71f3a9b524af6012062fe037a6"),
+        (2, "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"),
+        (4096, "4b007901b765489abead49d926f721d065a429c1"),
+    ];
+    for &(c, tgt) in cases.iter() {
+        let mut kdf = PBKDF2::new(SHA1::new(), c).unwrap();
+        kdf.derive(b"password", b"salt", 20, &mut key).unwrap();
+        assert_eq!(cvt_bytes_to_str(key.as_slice()), tgt, "case: {}", c);
+    }
+    
+    let mut kdf = PBKDF2::new(SHA256::new(), 4096).unwrap();
+    kdf.derive(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 40, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9");
+    
+    let mut kdf = PBKDF2::new(SHA512::new(), 1000).unwrap();
+    kdf.derive(b"pass\0word", b"sa\0lt", 16, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "c50f36d34df3c2310621ce3b3815bb63");
+    
+    let mut kdf = PBKDF2::new(SM3::new(), 2).unwrap();
+    kdf.derive(b"password", b"salt", 32, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "fee723a2bc966e11dffb66133f4e8df577383c78ade30e3298edbd3e54ed85b7");
+    
+    assert!(PBKDF2::new(SHA256::new(), 0).is_err());
+    assert!(kdf.set_iterations(0).is_err());
+}
+
+#[test]
+fn hkdf() {
+    let (mut prk, mut okm) = (Vec::new(), Vec::new());
+    let ikm = [0x0bu8; 22];
+    // RFC 5869 A.1
+    let mut kdf = HKDF::new(SHA256::new()).unwrap();
+    kdf.extract(&(0..13u8).collect::<Vec<_>>(), ikm.as_ref(), &mut prk);
+    assert_eq!(cvt_bytes_to_str(prk.as_slice()), "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5");
+    kdf.expand(prk.as_slice(), &(0xf0..0xfau8).collect::<Vec<_>>(), 42, &mut okm).unwrap();
+    assert_eq!(cvt_bytes_to_str(okm.as_slice()), "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865");
+    
+    // RFC 5869 A.3, A.7, the zero-length salt
+    kdf.derive(&[], ikm.as_ref(), &[], 42, &mut okm).unwrap();
+    assert_eq!(cvt_bytes_to_str(okm.as_slice()), "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8");
+    HKDF::new(SHA1::new()).unwrap().derive(&[], ikm.as_ref(), &[], 42, &mut okm).unwrap();
+    assert_eq!(cvt_bytes_to_str(okm.as_slice()), "0ac1af7002b3d761d1e55298da9d0506b9ae52057220a306e07b6b87e8df21d0ea00033de03984d34918");
+    
+    let mut kdf = HKDF::new(SM3::new()).unwrap();
+    kdf.derive(b"salt", b"input key material", b"info", 50, &mut okm).unwrap();
+    assert_eq!(cvt_bytes_to_str(okm.as_slice()), "33b89ed43886eb84b602ec655189b46b823223e7cd1f9d78c1fa59972677645fa3c2babd7cb9b3b6c7cee0c0991f6afd5066");
+    
+    assert_eq!(kdf.max_key_len(), 255 * 32);
+    kdf.derive(b"salt", b"ikm", b"", 255 * 32, &mut okm).unwrap();
+    assert_eq!(okm.len(), 255 * 32);
+    assert!(kdf.derive(b"salt", b"ikm", b"", 255 * 32 + 1, &mut okm).is_err());
+    assert!(kdf.expand(&[0u8; 31], b"", 32, &mut okm).is_err());
+}
+
+#[test]
+fn kbkdf() {
+    let mut key = Vec::new();
+    let ki = (0..32u8).collect::<Vec<_>>();
+    let mut kdf = KBKDF::new(HMAC::new(ki.clone(), SHA256::new()).unwrap());
+    assert_eq!((kdf.counter_bits(), kdf.location(), kdf.prf_len()), (32, KBKDFCounterLocation::BeforeFixed, 32));
+    kdf.derive(b"label", b"context", 42, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "b9cd5f6323f01f4680650855f1ebea9b4c54c08131b506fc28c856364a38a2f4fb680c12ea51696887d9");
+    
+    let mut kdf = KBKDF::with_counter(HMAC::new(ki.clone(), SHA256::new()).unwrap(), 8, KBKDFCounterLocation::AfterFixed).unwrap();
+    kdf.derive(b"label", b"context", 42, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "e5a7d0f366e5921f6a408b40553ead19a40acd696ff0fb8fc3b8bafc22ce381459fd0f546cc315095251");
+    assert_eq!(kdf.max_key_len(), 255 * 32);
+    assert!(kdf.derive(b"label", b"context", 255 * 32 + 1, &mut key).is_err());
+    
+    let mut kdf = KBKDF::with_counter(HMAC::new(ki.clone(), SHA512::new()).unwrap(), 16, KBKDFCounterLocation::BeforeFixed).unwrap();
+    kdf.derive(&[], &[], 100, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "08eeb6c7ff0785c6c07f263ada3a4734f0b8d854ee22d3faec024a8bbffed4c21ae68c24b41eef5b08736951a8c993bd0feb493b004b6e46dd904c5a0bf6aee093f1c593fec00946be4ce71a2e8f57a6a926831d356d95ff9937823d7913aba5057ec8bb");
+    
+    #[cfg(feature = "aes")]
+    {
+        use crate::{AES, CMAC};
+        let mut kdf = KBKDF::new(CMAC::new(AES::new(ki[..16].to_vec()).unwrap()).unwrap());
+        kdf.derive(b"session", b"alice-bob", 40, &mut key).unwrap();
+        assert_eq!(cvt_bytes_to_str(key.as_slice()), "d8201c39c75027d8e3f3674c9993361d1d33802971db7f6fd44822cf0f357cd540748f932dff9de8");
+        
+        let mut kdf = KBKDF::with_counter(CMAC::new(AES::new(ki.clone()).unwrap()).unwrap(), 24, KBKDFCounterLocation::AfterFixed).unwrap();
+        kdf.derive_with_fixed_input(&(0..60u8).collect::<Vec<_>>(), 16, &mut key).unwrap();
+        assert_eq!(cvt_bytes_to_str(key.as_slice()), "b3330467f8bf8d8d456e050efb8efd01");
+    }
+    
+    for bits in [0, 12, 40] {
+        let e = KBKDF::with_counter(HMAC::new(ki.clone(), SHA256::new()).unwrap(), bits, KBKDFCounterLocation::BeforeFixed).err().unwrap();
+        assert_eq!(e.kind(), CryptoErrorKind::InvalidParameter);
+    }
+}
+
+#[test]
+fn x963_kdf() {
+    let mut key = Vec::new();
+    let hex = |s: &str| crate::encoding::Hex::lower().decode(s).unwrap();
+    // NIST CAVP SP 800-135 ANSI X9.63
+    let mut kdf = X963KDF::new(SHA256::new());
+    kdf.derive(&hex("96c05619d56c328ab95fe84b18264b08725b85e33fd34f08"), &[], 16, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "443024c3dae66b95e6f5670601558f71");
+    kdf.derive(&hex("22518b10e70f2a3f243810ae3254139efbee04aa57c7af7d"), &hex("75eef81aa3041e33b80971203d2c0c52"), 128, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "c498af77161cc59f2962b9a713e2b215152d139766ce34a776df11866a69bf2e52a13d9c7c6fc878c50c5ea0bc7b00e0da2447cfd874f6cf92f30d0097111485500c90c3af8b487872d04685d14c8d1dc8d7fa08beb0ce0ababc11f0bd496269142d43525a78e5bc79a17f59676a5706dc54d54d4d1f0bd7e386128ec26afc21");
+    
+    let mut kdf = X963KDF::new(SHA512::new());
+    kdf.derive(&(0..66u8).collect::<Vec<_>>(), b"shared info", 70, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "158fbf2656ccaabe0805f8bfe47235016e9b1604f396265966f3d66fd9c161bda409b0437ea053641f94998dd5df857a1d65e08ddf90912529c7f26266c9006e72825caff374");
+    X963KDF::new(SHA1::new()).derive(&[], &[], 20, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "479e04f3d12d112b5c04c9ee67e4b1e6e201ea4e");
+    assert_eq!(kdf.hash_len(), 64);
+}
+
+#[test]
+fn key_hierarchy() {
+    let mut key = Vec::new();
+    let mut kh = KeyHierarchy::new(SHA256::new(), &(0..32u8).collect::<Vec<_>>(), b"salt").unwrap();
+    kh.derive(b"file-encryption", 32, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "82d94c3940e96099fd0e996a24886bd14972c3d48738336865bb8827b81b0707");
+    
+    // the length-prefixed label and context
+    kh.derive_with_context(b"ab", b"c", 16, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "1a32dd4fdd578723721d2fd83769d13b");
+    kh.derive_with_context(b"a", b"bc", 16, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "49bc5f042cacd9a5fbf31dbd71124959");
+    
+    let mut child = kh.child(b"tenant").unwrap();
+    child.derive(b"file-encryption", 32, &mut key).unwrap();
+    assert_eq!(cvt_bytes_to_str(key.as_slice()), "25c9ff4618ba66a69eca337edb49ce8a1bb05f2f1bbaeb7eacc4a573d0fde657");
+    
+    assert_eq!(kh.derive(b"", 32, &mut key).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(kh.child(b"").err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(kh.derive(b"file-encryption", 255 * 32 + 1, &mut key).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+}
+
+#[test]
+fn scrypt() {
+    let mut key = Vec::new();
+    // RFC 7914 chapter 12
+    let cases = [
+        ("", "", 16usize, 1usize, 1usize, "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"),
+        ("password", "NaCl", 1024, 8, 16, "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"),
+        ("pleaseletmein", "SodiumChloride", 16384, 8, 1, "7023bdcb3afd7348461c06cd81fd38ebfda8fbba904f8e3ea9b543f6545da1f2d5432955613f0fcf62d49705242a9af9e61e85dc0d651e40dfcf017b45575887"),
+    ];
+    for &(password, salt, n, r, p, tgt) in cases.iter() {
+        let kdf = Scrypt::new(n, r, p).unwrap();
+        kdf.derive(password.as_bytes(), salt.as_bytes(), 64, &mut key).unwrap();
+        assert_eq!(cvt_bytes_to_str(key.as_slice()), tgt, "case: {}", n);
+    }
+    
+    assert_eq!(Scrypt::new(16384, 8, 1).unwrap().memory_len(), 128 * 8 * 16385);
+    assert!(Scrypt::new(1000, 8, 1).is_err());
+    assert!(Scrypt::new(1, 8, 1).is_err());
+    assert!(Scrypt::new(1 << 16, 1, 1).is_err());
+    assert!(Scrypt::new(16, 0, 1).is_err());
+    assert!(Scrypt::new(16, 1 << 15, 1 << 15).is_err());
+    assert_eq!(Scrypt::new(1 << 20, 8, 1).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert_eq!(Scrypt::with_max_memory(1024, 8, 1, 1 << 20).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert!(Scrypt::with_max_memory(1024, 8, 1, 2 << 20).is_ok());
+}
+
+#[test]
+fn argon2() {
+    // RFC 9106 5.1-5.3
+    let cases = [
+        (Argon2Variant::Argon2d, "512b391b6f1162975371d30919734294f868e3be3984f3c1a13a4db9fabe4acb"),
+        (Argon2Variant::Argon2i, "c814d9d1dc7f37aa13f0d77f2494bda1c8de6b016dd388d29952a4c4672b6ce8"),
+        (Argon2Variant::Argon2id, "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"),
+    ];
+    let (password, salt) = ([0x01u8; 32], [0x02u8; 16]);
+    let mut key = Vec::new();
+    for (variant, tgt) in cases.iter() {
+        let mut kdf = Argon2::new(*variant, 32, 3, 4).unwrap();
+        kdf.set_secret(vec![0x03; 8]);
+        kdf.set_associated_data(vec![0x04; 12]);
+        kdf.derive(password.as_ref(), salt.as_ref(), 32, &mut key).unwrap();
+        assert_eq!(cvt_bytes_to_str(key.as_slice()), *tgt, "case: {}", variant.name());
+        
+        #[cfg(feature = "parallel")]
+        {
+            let mut pkey = Vec::new();
+            kdf.derive_parallel(password.as_ref(), salt.as_ref(), 32, 3, &mut pkey).unwrap();
+            assert_eq!(pkey, key, "case: {}", variant.name());
+        }
+    }
+    
+    // the tag more than 64 bytes, and the multiple address blocks
+    let cases = [
+        ("password", "somesalt", 256, 2, 2, 64, "98aedd1f6bb1c26f26d23ea717bd61c50fc688ac43d3bfab170bca475e696671edb5df08e0c80f278c875291cd18eee9dcfdfad8114333658bb0d7f753108db9"),
+        ("pw", "saltsaltsalt", 8, 1, 1, 100, "c819782e8690b851d3212b883943a33de443be5228432a2b6812473e123cd8b61acb4ed714ea8713b07209b53a783faff2343e9031043d6f300132f2f465b75caec6152a9b250891f6fc9a9861e6077b50fca24f4be74c010884ac176b954f54c7a36d42"),
+    ];
+    for (password, salt, m, t, p, len, tgt) in cases.iter() {
+        let kdf = Argon2::new(Argon2Variant::Argon2id, *m, *t, *p).unwrap();
+        kdf.derive(password.as_bytes(), salt.as_bytes(), *len, &mut key).unwrap();
+        assert_eq!(cvt_bytes_to_str(key.as_slice()), *tgt, "case: {}", password);
+    }
+    
+    assert!(Argon2::new(Argon2Variant::Argon2id, 31, 1, 4).is_err());
+    assert!(Argon2::new(Argon2Variant::Argon2id, 32, 0, 4).is_err());
+    assert!(Argon2::new(Argon2Variant::Argon2id, 32, 1, 0).is_err());
+    let kdf = Argon2::new(Argon2Variant::Argon2id, 64, 1, 1).unwrap();
+    assert!(kdf.derive(b"pw", b"short", 32, &mut key).is_err());
+    assert!(kdf.derive(b"pw", b"somesalt", 3, &mut key).is_err());
+    
+    // the PHC string
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let mut kdf = Argon2::new(Argon2Variant::Argon2id, 64, 2, 2).unwrap();
+    kdf.set_secret(b"pepper".to_vec());
+    let hash = kdf.hash_password(b"correct horse", &mut rd).unwrap();
+    assert!(hash.starts_with("$argon2id$v=19$m=64,t=2,p=2$"), "{}", hash);
+    assert!(kdf.verify_password(hash.as_str(), b"correct horse").is_ok());
+    assert_eq!(kdf.verify_password(hash.as_str(), b"wrong horse").unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    let other = Argon2::new(Argon2Variant::Argon2id, 64, 2, 2).unwrap();
+    assert_eq!(other.verify_password(hash.as_str(), b"correct horse").unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    
+    let phc = "$argon2id$v=19$m=256,t=2,p=2$c29tZXNhbHQ$mK7dH2uxwm8m0j6nF71hxQ/GiKxD07+rFwvKR15pZnHttd8I4MgPJ4yHUpHNGO7p3P362BFDM2WLsNf3UxCNuQ";
+    let h = Argon2Hash::parse(phc).unwrap();
+    assert_eq!((h.variant, h.m_cost, h.t_cost, h.lanes, h.salt.as_slice()), (Argon2Variant::Argon2id, 256, 2, 2, b"somesalt".as_ref()));
+    assert_eq!(h.to_string(), phc);
+    assert!(Argon2::new(Argon2Variant::Argon2i, 8, 1, 1).unwrap().verify_password(phc, b"password").is_ok());
+    assert!(Argon2Hash::parse("$argon2id$v=16$m=256,t=2,p=2$c29tZXNhbHQ$mK7d").is_err());
+    assert!(Argon2Hash::parse("$argon2x$v=19$m=256,t=2,p=2$c29tZXNhbHQ$mK7d").is_err());
+    assert!(Argon2Hash::parse("$argon2id$v=19$m=256,t=2$c29tZXNhbHQ$mK7d").is_err());
+
+    // the costs of the untrusted PHC string are limited before hashing
+    let kdf = Argon2::new(Argon2Variant::Argon2id, 64, 1, 1).unwrap();
+    assert_eq!(kdf.max_cost(), (ARGON2_DEFAULT_MAX_MEMORY, ARGON2_DEFAULT_MAX_PASSES, ARGON2_DEFAULT_MAX_LANES));
+    for huge in ["m=4294967295,t=2,p=2", "m=256,t=4294967295,p=2", "m=65536,t=2,p=4096"].iter() {
+        let phc = format!("$argon2id$v=19${}$c29tZXNhbHQ$mK7dH2uxwm8m0j6nF71hxQ", huge);
+        assert_eq!(kdf.verify_password(phc.as_str(), b"password").unwrap_err().kind(), CryptoErrorKind::LimitExceeded, "{}", huge);
+    }
+    let strict = Argon2::with_max_cost(Argon2Variant::Argon2id, 64, 1, 1, 128, 1, 1).unwrap();
+    assert_eq!(strict.verify_password(phc, b"password").unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert!(Argon2::with_max_cost(Argon2Variant::Argon2id, 256, 2, 2, 256, 2, 2).unwrap().verify_password(phc, b"password").is_ok());
+    assert_eq!(Argon2::new(Argon2Variant::Argon2id, ARGON2_DEFAULT_MAX_MEMORY + 1, 1, 1).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+}
+
+#[test]
+fn kdf_calibrate() {
+    assert!(KDFCalibrator::new(Duration::from_millis(0)).is_err());
+    
+    // the recommendations depend on the host speed, so only the parameter constraints are checked
+    let mut cal = KDFCalibrator::new(Duration::from_millis(20)).unwrap();
+    cal.set_min_sample(Duration::from_millis(5));
+    let iterations = cal.pbkdf2_iterations(SHA256::new()).unwrap();
+    assert!(iterations >= 1 && PBKDF2::new(SHA256::new(), iterations).is_ok());
+    
+    let n = cal.scrypt_n(8, 1).unwrap();
+    assert!(n >= 2 && n.is_power_of_two() && Scrypt::new(n, 8, 1).is_ok(), "N: {}", n);
+    // the N is limited by the 2^{16r} and the default memory limit
+    assert!(cal.scrypt_n(1, 1).unwrap() < (1 << 16));
+    assert_eq!(cal.scrypt_n(1 << 22, 1).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert_eq!(cal.scrypt_n(0, 1).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    
+    let t_cost = cal.argon2_passes(Argon2Variant::Argon2id, 64, 1).unwrap();
+    assert!(t_cost >= 1 && Argon2::new(Argon2Variant::Argon2id, 64, t_cost, 1).is_ok(), "t: {}", t_cost);
+    assert_eq!(cal.argon2_passes(Argon2Variant::Argon2id, 4, 1).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(cal.argon2_passes(Argon2Variant::Argon2id, ARGON2_DEFAULT_MAX_MEMORY + 1, 1).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+}
@@ -0,0 +1,69 @@
+//! The purpose-labeled key hierarchy on the HKDF(RFC 5869)
+//!
+//! PRK = HKDF-Extract(salt, master_key), the subkey is `HKDF-Expand(PRK, 0x00 || enc(label) || enc(context), L)`
+//! and the child hierarchy's PRK is `HKDF-Expand(PRK, 0x01 || enc(label), HashLen)`, where `enc(x) = [len(x)]_32 || x`.
+//! The length prefixes make the encoding of the `(label, context)` injective, e.g. the `("ab", "c")` and the `("a", "bc")`
+//! derive the different keys, unlike the ad-hoc `label || context` concatenation.
+
+use crate::kdf::HKDF;
+use crate::{Digest, CryptoError, CryptoErrorKind};
+
+const KEY_HIERARCHY_SUBKEY: u8 = 0x00;
+const KEY_HIERARCHY_CHILD: u8 = 0x01;
+
+#[derive(Clone)]
+pub struct KeyHierarchy<D: Digest> {
+    hkdf: HKDF<D>,
+    prk: Vec<u8>,
+}
+
+impl<D: Digest + Clone> KeyHierarchy<D> {
+    /// the `salt` is optional(empty), the `master_key` should have at least `HashLen` bytes entropy
+    pub fn new(digest: D, master_key: &[u8], salt: &[u8]) -> Result<Self, CryptoError> {
+        let mut hkdf = HKDF::new(digest)?;
+        let mut prk = Vec::with_capacity(hkdf.hash_len());
+        hkdf.extract(salt, master_key, &mut prk);
+        Ok(Self { hkdf, prk })
+    }
+
+    pub fn hash_len(&self) -> usize {
+        self.hkdf.hash_len()
+    }
+
+    fn info(kind: u8, label: &[u8], context: Option<&[u8]>) -> Result<Vec<u8>, CryptoError> {
+        if label.is_empty() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The key hierarchy label must not be empty"));
+        }
+
+        let mut info = Vec::with_capacity(9 + label.len() + context.map(|c| c.len()).unwrap_or(0));
+        info.push(kind);
+        for x in std::iter::once(label).chain(context) {
+            if x.len() > (u32::MAX as usize) {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("The key hierarchy label or context is too long: {}", x.len())));
+            }
+            info.extend_from_slice((x.len() as u32).to_be_bytes().as_ref());
+            info.extend_from_slice(x);
+        }
+        Ok(info)
+    }
+
+    /// derive the `key_len` bytes subkey for the purpose `label`(e.g. `b"file-encryption"`)
+    pub fn derive(&mut self, label: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        self.derive_with_context(label, &[], key_len, key)
+    }
+
+    /// derive the `key_len` bytes subkey for the purpose `label` and the `context`(e.g. the user ID or the file ID)
+    pub fn derive_with_context(&mut self, label: &[u8], context: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let info = Self::info(KEY_HIERARCHY_SUBKEY, label, Some(context))?;
+        self.hkdf.expand(self.prk.as_slice(), info.as_slice(), key_len, key)
+    }
+
+    /// the child hierarchy for the `label`, its subkeys are independent of the subkeys of this hierarchy
+    pub fn child(&mut self, label: &[u8]) -> Result<Self, CryptoError> {
+        let info = Self::info(KEY_HIERARCHY_CHILD, label, None)?;
+        let mut prk = Vec::with_capacity(self.hash_len());
+        self.hkdf.expand(self.prk.as_slice(), info.as_slice(), self.hash_len(), &mut prk)?;
+        Ok(Self { hkdf: self.hkdf.clone(), prk })
+    }
+}
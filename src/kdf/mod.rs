@@ -1,3 +1,35 @@
+//! Key derivation functions
 
+mod kdf;
 
-mod kdf;
\ No newline at end of file
+mod kdf_3gpp;
+pub use kdf_3gpp::KDF3GPP;
+
+mod pbkdf2;
+pub use pbkdf2::PBKDF2;
+
+mod hkdf;
+pub use hkdf::HKDF;
+
+mod scrypt;
+pub use scrypt::{Scrypt, SCRYPT_DEFAULT_MAX_MEMORY};
+
+mod kbkdf;
+pub use kbkdf::{KBKDF, KBKDFCounterLocation};
+
+mod x963;
+pub use x963::X963KDF;
+
+mod key_hierarchy;
+pub use key_hierarchy::KeyHierarchy;
+
+mod blake2b;
+mod argon2;
+pub use argon2::{Argon2, Argon2Variant, Argon2Hash, ARGON2_DEFAULT_SALT_LEN, ARGON2_DEFAULT_HASH_LEN, ARGON2_DEFAULT_MAX_MEMORY,
+    ARGON2_DEFAULT_MAX_PASSES, ARGON2_DEFAULT_MAX_LANES};
+
+mod calibrate;
+pub use calibrate::KDFCalibrator;
+
+#[cfg(all(test, feature = "sm"))]
+mod kdf_test;
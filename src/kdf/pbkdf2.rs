@@ -0,0 +1,75 @@
+//! PBKDF2
+//! RFC 8018 5.2, NIST SP 800-132
+//!
+//! DK = T_1 || T_2 || ... || T_l, T_i = U_1 ⊕ U_2 ⊕ ... ⊕ U_c,
+//! U_1 = PRF(P, S || INT(i)), U_j = PRF(P, U_{j-1}), where PRF is the HMAC.
+
+use crate::{HMAC, Digest, CryptoError, CryptoErrorKind};
+
+#[derive(Clone)]
+pub struct PBKDF2<D: Digest> {
+    hmac: HMAC<D>,
+    iterations: usize,
+}
+
+impl<D: Digest> PBKDF2<D> {
+    /// `iterations`: the iteration count `c`, it must be great than 0;
+    pub fn new(digest: D, iterations: usize) -> Result<Self, CryptoError> {
+        if iterations == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The iteration count must be great than 0"));
+        }
+
+        Ok(Self {
+            hmac: HMAC::new(Vec::new(), digest)?,
+            iterations,
+        })
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// set new iteration count
+    pub fn set_iterations(&mut self, iterations: usize) -> Result<(), CryptoError> {
+        if iterations == 0 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The iteration count must be great than 0"))
+        } else {
+            self.iterations = iterations;
+            Ok(())
+        }
+    }
+
+    /// derive the `key_len` bytes key from the `password` and `salt`,
+    /// the `key_len` must be less than or equal to `(2^32 - 1) * hLen`.
+    pub fn derive(&mut self, password: &[u8], salt: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let h_len = (self.hmac.bits_len() + 7) >> 3;
+        let l = key_len.div_ceil(h_len);
+        if l > (u32::MAX as usize) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "derived key too long"));
+        }
+
+        self.hmac.set_key(password.to_vec());
+        key.clear();
+        let (mut u, mut t) = (Vec::with_capacity(h_len), Vec::with_capacity(h_len));
+        for i in 1..=(l as u32) {
+            self.hmac.reset();
+            self.hmac.write(salt);
+            self.hmac.write(i.to_be_bytes().as_ref());
+            self.hmac.checksum(&mut u);
+            t.clear();
+            t.extend_from_slice(u.as_slice());
+
+            for _ in 1..self.iterations {
+                self.hmac.reset();
+                self.hmac.write(u.as_slice());
+                self.hmac.checksum(&mut u);
+                t.iter_mut().zip(u.iter()).for_each(|(a, &b)| *a ^= b);
+            }
+
+            key.extend_from_slice(t.as_slice());
+        }
+
+        key.truncate(key_len);
+        Ok(())
+    }
+}
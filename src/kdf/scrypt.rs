@@ -0,0 +1,157 @@
+//! scrypt
+//! RFC 7914
+//!
+//! B = PBKDF2-HMAC-SHA256(P, S, 1, p * 128 * r), B_i = ROMix(r, B_i, N), DK = PBKDF2-HMAC-SHA256(P, B, 1, dkLen),
+//! the ROMix fills the $V$ of `128 * r * N` bytes by the BlockMix(Salsa20/8), and reads it back in the
+//! data-dependent order, so the derivation needs `128 * r * (N + p)` bytes memory.
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::kdf::PBKDF2;
+use crate::sha::SHA256;
+
+/// the default memory limit of the derivation, 1GiB
+pub const SCRYPT_DEFAULT_MAX_MEMORY: usize = 1 << 30;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scrypt {
+    n: usize,
+    r: usize,
+    p: usize,
+    max_memory: usize,
+}
+
+impl Scrypt {
+    /// `n`: the CPU/memory cost, it must be the power of 2 and great than 1, and less than $2^{16r}$;
+    /// `r`: the block size; `p`: the parallelization, `r * p` must be less than $2^{30}$;
+    /// the memory is limited by the `SCRYPT_DEFAULT_MAX_MEMORY`.
+    pub fn new(n: usize, r: usize, p: usize) -> Result<Self, CryptoError> {
+        Self::with_max_memory(n, r, p, SCRYPT_DEFAULT_MAX_MEMORY)
+    }
+
+    /// the `LimitExceeded` is returned if the memory `128 * r * (n + p)` is more than the `max_memory` bytes
+    pub fn with_max_memory(n: usize, r: usize, p: usize, max_memory: usize) -> Result<Self, CryptoError> {
+        if n < 2 || !n.is_power_of_two() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong cost parameter N: {}, it must be the power of 2 and great than 1", n)));
+        } else if r == 0 || p == 0 || (r as u64) * (p as u64) >= (1u64 << 30) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong parameters r: {}, p: {}, they must be great than 0 and r * p < 2^30", r, p)));
+        } else if r < 4 && (n.trailing_zeros() as usize) >= (r << 4) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong cost parameter N: {}, it must be less than 2^(128 * r / 8)", n)));
+        }
+
+        let memory = (n as u128 + p as u128) * (r as u128) * 128;
+        if memory > (max_memory as u128) {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded,
+                format!("The scrypt needs {} bytes memory, it exceeds the limit {} bytes", memory, max_memory)));
+        }
+
+        Ok(Self { n, r, p, max_memory })
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn r(&self) -> usize {
+        self.r
+    }
+
+    pub fn p(&self) -> usize {
+        self.p
+    }
+
+    pub fn max_memory(&self) -> usize {
+        self.max_memory
+    }
+
+    /// the memory in bytes which the derivation needs, `128 * r * (N + p)`
+    pub fn memory_len(&self) -> usize {
+        128 * self.r * (self.n + self.p)
+    }
+
+    /// derive the `key_len` bytes key from the `password` and `salt`,
+    /// the `key_len` must be less than or equal to `(2^32 - 1) * 32`.
+    pub fn derive(&self, password: &[u8], salt: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        let block_len = 128 * self.r;
+        let mut pbkdf2 = PBKDF2::new(SHA256::new(), 1)?;
+        let mut b = Vec::with_capacity(block_len * self.p);
+        pbkdf2.derive(password, salt, block_len * self.p, &mut b)?;
+
+        let (mut x, mut v) = (vec![0u32; block_len >> 2], vec![0u32; (block_len >> 2) * self.n]);
+        for chunk in b.chunks_exact_mut(block_len) {
+            x.iter_mut().zip(chunk.chunks_exact(4)).for_each(|(a, c)| *a = u32::from_le_bytes([c[0], c[1], c[2], c[3]]));
+            self.romix(x.as_mut_slice(), v.as_mut_slice());
+            chunk.chunks_exact_mut(4).zip(x.iter()).for_each(|(c, a)| c.copy_from_slice(a.to_le_bytes().as_ref()));
+        }
+
+        pbkdf2.derive(password, b.as_slice(), key_len, key)
+    }
+
+    /// $X = B$, $V_i = X, X = BlockMix(X)$ for $i$ in $[0, N)$, $X = BlockMix(X \oplus V_j)$ for $j = Integerify(X) \mod N$
+    fn romix(&self, x: &mut [u32], v: &mut [u32]) {
+        let words = x.len();
+        let mut y = vec![0u32; words];
+        for vi in v.chunks_exact_mut(words) {
+            vi.copy_from_slice(x);
+            Self::block_mix(x, y.as_mut_slice());
+            x.copy_from_slice(y.as_slice());
+        }
+
+        for _ in 0..self.n {
+            let j = Self::integerify(x) & ((self.n - 1) as u64);
+            let vj = &v[((j as usize) * words)..((j as usize + 1) * words)];
+            x.iter_mut().zip(vj.iter()).for_each(|(a, &b)| *a ^= b);
+            Self::block_mix(x, y.as_mut_slice());
+            x.copy_from_slice(y.as_slice());
+        }
+    }
+
+    /// the first 64 bits of the last 64 bytes block as the little-endian integer
+    fn integerify(x: &[u32]) -> u64 {
+        let k = x.len() - 16;
+        (x[k] as u64) | ((x[k + 1] as u64) << 32)
+    }
+
+    /// $X = B_{2r-1}$, $Y_i = Salsa20/8(X \oplus B_i)$, $B' = (Y_0, Y_2, ..., Y_{2r-2}, Y_1, Y_3, ..., Y_{2r-1})$
+    fn block_mix(b: &[u32], out: &mut [u32]) {
+        let blocks = b.len() >> 4;
+        let mut x = [0u32; 16];
+        x.copy_from_slice(&b[((blocks - 1) << 4)..]);
+        for (i, bi) in b.chunks_exact(16).enumerate() {
+            x.iter_mut().zip(bi.iter()).for_each(|(a, &b)| *a ^= b);
+            Self::salsa20_8(&mut x);
+            let pos = if i & 1 == 0 { i >> 1 } else { (blocks >> 1) + (i >> 1) };
+            out[(pos << 4)..((pos + 1) << 4)].copy_from_slice(x.as_ref());
+        }
+    }
+
+    /// the Salsa20/8 core
+    fn salsa20_8(b: &mut [u32; 16]) {
+        let mut x = *b;
+        macro_rules! quarter {
+            ($a: expr, $b: expr, $c: expr, $d: expr) => {
+                x[$b] ^= x[$a].wrapping_add(x[$d]).rotate_left(7);
+                x[$c] ^= x[$b].wrapping_add(x[$a]).rotate_left(9);
+                x[$d] ^= x[$c].wrapping_add(x[$b]).rotate_left(13);
+                x[$a] ^= x[$d].wrapping_add(x[$c]).rotate_left(18);
+            };
+        }
+
+        for _ in 0..4 {
+            // columns
+            quarter!(0, 4, 8, 12);
+            quarter!(5, 9, 13, 1);
+            quarter!(10, 14, 2, 6);
+            quarter!(15, 3, 7, 11);
+            // rows
+            quarter!(0, 1, 2, 3);
+            quarter!(5, 6, 7, 4);
+            quarter!(10, 11, 8, 9);
+            quarter!(15, 12, 13, 14);
+        }
+
+        b.iter_mut().zip(x.iter()).for_each(|(a, &b)| *a = a.wrapping_add(b));
+    }
+}
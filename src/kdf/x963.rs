@@ -0,0 +1,52 @@
+//! ANSI X9.63 KDF
+//! SEC 1 v2 3.6.1, NIST SP 800-56A 5.8.1
+//!
+//! K = K_1 || K_2 || ... || K_n, K_i = Hash(Z || [i]_32 || SharedInfo), the counter `i` starts from 1,
+//! where the `Z` is the shared secret(e.g. the x coordinate of the ECDH shared point in the field element bytes),
+//! the length of the K must be less than `(2^32 - 1) * HashLen`.
+
+use crate::{Digest, CryptoError, CryptoErrorKind};
+
+#[derive(Clone)]
+pub struct X963KDF<D: Digest> {
+    digest: D,
+}
+
+impl<D: Digest> X963KDF<D> {
+    pub fn new(digest: D) -> Self {
+        Self { digest }
+    }
+
+    /// the length of the hash output in bytes(HashLen)
+    pub fn hash_len(&self) -> usize {
+        (self.digest.bits_len() + 7) >> 3
+    }
+
+    /// the maximum length of the K in bytes, `(2^32 - 1) * HashLen - 1`
+    pub fn max_key_len(&self) -> usize {
+        (u32::MAX as u64).saturating_mul(self.hash_len() as u64).saturating_sub(1).min(usize::MAX as u64) as usize
+    }
+
+    /// derive the `key_len` bytes key from the shared secret `z` and the `shared_info`
+    pub fn derive(&mut self, z: &[u8], shared_info: &[u8], key_len: usize, key: &mut Vec<u8>) -> Result<(), CryptoError> {
+        if key_len > self.max_key_len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the X9.63 KDF output must be less than {} bytes", key_len, self.max_key_len() + 1)));
+        }
+
+        let h_len = self.hash_len();
+        key.clear();
+        let mut k = Vec::with_capacity(h_len);
+        for i in 1..=(key_len.div_ceil(h_len) as u32) {
+            self.digest.reset();
+            self.digest.write(z);
+            self.digest.write(i.to_be_bytes().as_ref());
+            self.digest.write(shared_info);
+            self.digest.checksum(&mut k);
+            key.extend_from_slice(k.as_slice());
+        }
+
+        key.truncate(key_len);
+        Ok(())
+    }
+}
@@ -382,22 +382,43 @@ impl Keccak {
 }
 
 
+/// Sponge[Keccak-p[b,nr], pad10*1, rate], the incremental absorbing and squeezing, it's also the duplex
+/// construction for the frameworks built on the Keccak-p(e.g. the Strobe, the duplex AEAD).
+///
+/// the invariants:  
+/// - the pending bits of the rate buffer are always less than the `rate` bits, the full block is XORed into the
+///   state and permuted as soon as it's absorbed;  
+/// - the `pad` applies the pad10*1 once and switches the sponge to the squeezing phase, the `squeeze` pads the
+///   pending bits implicitly if the sponge is still absorbing;  
+/// - the `absorb` after the `squeeze` starts the next duplex block: the bits are XORed to the current state which
+///   the output has been taken from, use the `reset` to start a new message;  
+/// - the state can only be changed by the `absorb`, `pad`, `permute` and `reset`, the step mappings of the
+///   Keccak-p are not exposed.
+///
+/// the bits are processed from left to right in the writing order, the bit `i` of the byte is the `i`-th bit.
 #[derive(Clone)]
 pub struct KeccakSponge {
     rate: usize,
     keccak: Keccak,
     buf: Vec<u8>,
+    // the bits have been squeezed from the current state
+    squeezed: usize,
+    is_squeezing: bool,
 }
 
 impl KeccakSponge {
     /// Sponge[Keccak-p[b,nr], pad10*1, rate]  
     /// `rate` must be a positive integer and strictly less than the width `self.widths()`.
-    pub fn new(keccak: Keccak, rate: usize) -> Result<KeccakSponge, CryptoError> {
-        if rate > 0 || rate < keccak.widths() {
+    pub fn new(mut keccak: Keccak, rate: usize) -> Result<KeccakSponge, CryptoError> {
+        if rate > 0 && rate < keccak.widths() {
+            keccak.buf0.reset();
+            keccak.buf1.reset();
             Ok(KeccakSponge {
                 rate,
                 keccak,
                 buf: Vec::with_capacity(rate),
+                squeezed: 0,
+                is_squeezing: false,
             })
         } else {
             Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
@@ -408,86 +429,145 @@ impl KeccakSponge {
     /// 1 || 0^j || 1   
     /// return j
     #[inline]
-    fn pad(x: usize, m: usize) -> usize {
+    fn pad_len(x: usize, m: usize) -> usize {
         Keccak::minus_rem_euclid(0, m + 2, x)
     }
     
-    pub(crate) fn clear_buf(&mut self) {
+    /// the rate in bits
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+    
+    /// the capacity in bits, `self.rate() + self.capacity() == keccak.widths()`
+    pub fn capacity(&self) -> usize {
+        self.keccak.widths() - self.rate
+    }
+    
+    /// whether the sponge is in the squeezing phase
+    pub fn is_squeezing(&self) -> bool {
+        self.is_squeezing
+    }
+    
+    /// reset the state to the all zero bits and discard the pending bits
+    pub fn reset(&mut self) {
+        self.keccak.buf0.reset();
+        self.keccak.buf1.reset();
+        self.buf.clear();
+        self.squeezed = 0;
+        self.is_squeezing = false;
+    }
+    
+    fn current_state(&self) -> &KeccakStateArr {
+        // see the `KeccakBufGuard`, the input is the current state
+        if self.keccak.state_flag {
+            &self.keccak.buf1
+        } else {
+            &self.keccak.buf0
+        }
+    }
+    
+    /// S = S ^ (block || 0^{b-len(block)}), the `block.len()` must be less than or equal to the rate
+    fn xor_block(&mut self, block: &[u8]) {
+        let w = self.keccak.w;
+        let state = KeccakBufGuard::new(&mut self.keccak);
+        state.output.state.iter_mut().zip(state.input.state.iter()).enumerate().for_each(|(x, (sheeto, sheeti))| {
+            sheeto.iter_mut().zip(sheeti.iter()).enumerate().for_each(|(y, (laneo, lanei))| {
+                laneo.iter_mut().take(w).zip(lanei.iter().take(w)).enumerate().for_each(|(z, (eo, &ei))| {
+                    let idx = (w * ((KECCAK_Y_SIZE * y) + x)) + z;
+                    *eo = if idx < block.len() { ei ^ block[idx] } else { ei };
+                });
+            });
+        });
+    }
+    
+    fn absorb_buf(&mut self) {
+        let buf = std::mem::take(&mut self.buf);
+        self.xor_block(buf.as_slice());
+        self.keccak.permutation_inner();
+        self.buf = buf;
         self.buf.clear();
     }
 
+    /// absorb the `bits_len` bits of the `byte_data`, the extra bits of the `byte_data` are discarded.  
     /// b0b1b2b3b4b5b6b7   
     /// hex = b7*2^7 + b6*2^6 + ... + b0*2^0   
-    pub(crate) fn write_to_buf(&mut self, byte_data: &[u8], bits_len: usize) {
-        let old_len = self.buf.len();
-        byte_data.iter().for_each(|&e| {
-            (0..8).for_each(|i| {
-                self.buf.push((e >> i) & 1);
-            });
-        });
-        if (self.buf.len()-old_len) > bits_len {self.buf.truncate(old_len + bits_len);}
+    pub fn absorb(&mut self, byte_data: &[u8], bits_len: usize) {
+        if self.is_squeezing {
+            self.is_squeezing = false;
+            self.squeezed = 0;
+        }
+        
+        let bits_len = bits_len.min(byte_data.len() << 3);
+        for i in 0..bits_len {
+            self.buf.push((byte_data[i >> 3] >> (i & 7)) & 1);
+            if self.buf.len() == self.rate {
+                self.absorb_buf();
+            }
+        }
     }
     
-    /// `clear_buf`   
-    /// `write_bot_buf()` many times  
-    /// `sponge_buf`
-    pub(crate) fn sponge_buf(&mut self, want_bits_len: usize, results: &mut Vec<u8>) {
-        // byte_data || 1 || 0^j || 1
+    /// pending bits || 1 || 0^j || 1, absorb the last block and switch to the squeezing phase.  
+    /// the domain separation suffix(e.g. the `01` of the SHA-3) should be absorbed before the padding.
+    pub fn pad(&mut self) {
+        if self.is_squeezing {
+            self.is_squeezing = false;
+        }
+        
         let bits_len = self.buf.len();
-        let pad_j = Self::pad(self.rate, bits_len);
+        let pad_j = Self::pad_len(self.rate, bits_len);
         self.buf.push(1);
         self.buf.resize(bits_len + 1 + pad_j, 0);
         self.buf.push(1);
-
-        let n = self.buf.len() / self.rate;
-        // let c =  self.keccak.widths() - self.rate;
-
-        {
-            let state = KeccakBufGuard::new(&mut self.keccak);
-            state.output.reset();
+        
+        // the pad10*1 is 2 bits at least, so it may be the two blocks
+        if self.buf.len() > self.rate {
+            let tail = self.buf.split_off(self.rate);
+            self.absorb_buf();
+            self.buf = tail;
         }
-
-        let (buf, rate, w) = (&mut self.buf, self.rate, self.keccak.w);
-        for i in 0..n {
-            {
-                let state = KeccakBufGuard::new(&mut self.keccak);
-                let start = i * self.rate;
-                state.output.state.iter_mut().zip(state.input.state.iter()).enumerate().for_each(|(x, (sheeto, sheeti))| {
-                    sheeto.iter_mut().zip(sheeti.iter()).enumerate().for_each(|(y, (laneo, lanei))| {
-                        laneo.iter_mut().take(w).zip(lanei.iter().take(w)).enumerate().for_each(|(z, (eo, &ei))| {
-                            let idx = (w * ((KECCAK_Y_SIZE * y) + x)) + z;
-                            if idx >= rate {
-                                *eo = ei ^ 0;
-                            } else {
-                                *eo = ei ^ buf[start + idx];
-                            }
-                        });
-                    });
-                });
-            }
-
-            self.keccak.permutation_inner();
+        self.absorb_buf();
+        
+        self.squeezed = 0;
+        self.is_squeezing = true;
+    }
+    
+    /// XOR the pending bits without the padding into the state and apply the Keccak-p permutation, the sponge
+    /// is in the squeezing phase after it. It's for the frameworks which apply the padding themselves.
+    pub fn permute(&mut self) {
+        self.absorb_buf();
+        self.squeezed = 0;
+        self.is_squeezing = true;
+    }
+    
+    /// the rate bits of the current state which are packed to the bytes, the pending bits are not included
+    pub fn rate_state(&self, results: &mut Vec<u8>) {
+        results.clear();
+        self.current_state().finish(results, self.keccak.w);
+        results.truncate(self.rate);
+        KeccakStateArr::cvt_to_slice(results);
+    }
+    
+    /// squeeze the `want_bits_len` bits to the `results`, the output of the successive calls is the continuous
+    /// output stream.
+    pub fn squeeze(&mut self, want_bits_len: usize, results: &mut Vec<u8>) {
+        if !self.is_squeezing {
+            self.pad();
         }
-
+        
         results.clear();
-        loop {
-            let old_len = results.len();
-            {
-                let state = KeccakBufGuard::new(&mut self.keccak);
-                state.input.finish(results, w);
-            }
-
-            // Z = Z || Trunc_r(S)
-            results.truncate(old_len+self.rate);
-
-            if want_bits_len <= results.len() {
-                results.truncate(want_bits_len);
-                break;
-            } else {
-                let state = KeccakBufGuard::new(&mut self.keccak);
-                std::mem::drop(state);
+        let mut block = Vec::with_capacity(self.keccak.widths());
+        while results.len() < want_bits_len {
+            if self.squeezed == self.rate {
                 self.keccak.permutation_inner();
+                self.squeezed = 0;
             }
+            
+            // Z = Z || Trunc_r(S)
+            block.clear();
+            self.current_state().finish(&mut block, self.keccak.w);
+            let n = (self.rate - self.squeezed).min(want_bits_len - results.len());
+            results.extend_from_slice(&block[self.squeezed..(self.squeezed + n)]);
+            self.squeezed += n;
         }
 
         KeccakStateArr::cvt_to_slice(results);
@@ -502,17 +582,16 @@ impl KeccakSponge {
     pub fn sponge(&mut self, byte_data: &[u8], bits_len: usize, want_bits_len: usize, results: &mut Vec<u8>) {
         assert!(bits_len <= (byte_data.len() << 3), "bits_len must be less than or equal to the byte_data.len() * 8");
         
-        self.clear_buf();
-        self.write_to_buf(byte_data, bits_len);
-        
-        self.sponge_buf(want_bits_len, results);
+        self.reset();
+        self.absorb(byte_data, bits_len);
+        self.squeeze(want_bits_len, results);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::keccak::keccak::KECCAK_PERMUTATION_WIDTHS;
-    use crate::Keccak;
+    use crate::{Keccak, SHA3, Digest};
 
     #[test]
     fn keccak() {
@@ -526,4 +605,68 @@ mod tests {
             assert_eq!(1024/8, sp.len());
         }
     }
+
+    #[test]
+    fn keccak_sponge_incremental() {
+        let msg = (0..1000u32).map(|x| (x * 7 + 3) as u8).collect::<Vec<_>>();
+        let (mut z1, mut z2, mut tmp) = (Vec::new(), Vec::new(), Vec::new());
+        
+        // SHA3-256 = Keccak[512](M || 01, 256)
+        let mut sha3 = SHA3::sha256();
+        sha3.write(msg.as_slice());
+        sha3.checksum(&mut z1);
+        let mut sponge = Keccak::new(1600, 24).unwrap().sponge(1088).unwrap();
+        assert_eq!((sponge.rate(), sponge.capacity()), (1088, 512));
+        for chunk in msg.chunks(77) {
+            sponge.absorb(chunk, chunk.len() << 3);
+        }
+        sponge.absorb(&[0b10], 2);
+        assert!(!sponge.is_squeezing());
+        sponge.pad();
+        assert!(sponge.is_squeezing());
+        sponge.squeeze(256, &mut z2);
+        assert_eq!(z1, z2);
+        
+        // the successive squeezing is the continuous output stream
+        sponge.sponge(msg.as_slice(), 1000 << 3, 3000, &mut z1);
+        sponge.reset();
+        sponge.absorb(msg.as_slice(), 1000 << 3);
+        z2.clear();
+        for &l in [8usize, 1080, 16, 1600, 296].iter() {
+            sponge.squeeze(l, &mut tmp);
+            z2.extend_from_slice(tmp.as_slice());
+        }
+        assert_eq!(z1, z2);
+        
+        // duplexing(σ1) after duplexing(σ0) = sponge(pad(σ0) || σ1)
+        sponge.reset();
+        sponge.absorb(&msg[..8], 64);
+        sponge.pad();
+        sponge.squeeze(128, &mut tmp);
+        sponge.rate_state(&mut z1);
+        assert_eq!(&z1[..16], tmp.as_slice());
+        sponge.absorb(&msg[8..20], 96);
+        sponge.squeeze(256, &mut z1);
+        
+        let mut padded = msg[..8].to_vec();
+        padded.push(1);
+        padded.resize(135, 0);
+        padded.push(0x80);
+        padded.extend_from_slice(&msg[8..20]);
+        sponge.sponge(padded.as_slice(), padded.len() << 3, 256, &mut z2);
+        assert_eq!(z1, z2);
+        
+        // permute(P) = absorb(P || 0^{r-len(P)})
+        sponge.reset();
+        sponge.absorb(&padded[..135], 1080);
+        sponge.absorb(&[0], 8);
+        sponge.absorb(&msg[8..20], 96);
+        sponge.squeeze(256, &mut z2);
+        sponge.reset();
+        sponge.absorb(&padded[..135], 1080);
+        sponge.permute();
+        sponge.absorb(&msg[8..20], 96);
+        sponge.squeeze(256, &mut z1);
+        assert_eq!(z1, z2);
+    }
 }
\ No newline at end of file
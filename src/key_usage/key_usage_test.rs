@@ -0,0 +1,84 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use rmath::rand::{DefaultSeed, CryptoRand};
+use crate::elliptic::CurveP256;
+use crate::ecdsa::{ECDSA, SignatureContent};
+use crate::key_usage::{UsageLimitedSigner, ConstantLatency};
+use crate::sha::SHA256;
+use crate::{CryptoErrorKind, Signature};
+
+#[test]
+fn usage_limited_signer() {
+    let rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let ecdsa = || ECDSA::auto_generate_key(SHA256::new(), rd.clone(), CurveP256::new().unwrap()).unwrap();
+    assert!(UsageLimitedSigner::new(ecdsa(), 0).is_err());
+
+    let (mut sig, msg) = (SignatureContent::new(), b"usage limited".as_ref());
+    let mut signer = UsageLimitedSigner::new(ecdsa(), 2).unwrap();
+    signer.sign(&mut sig, msg).unwrap();
+    signer.sign(&mut sig, msg).unwrap();
+    assert_eq!(signer.remaining(), 0);
+    signer.verify(&sig, msg).unwrap();
+    assert_eq!(signer.sign(&mut sig, msg).unwrap_err().kind(), CryptoErrorKind::LimitExceeded);
+    assert_eq!(signer.total_used(), 2);
+    signer.reauthorize();
+    signer.sign(&mut sig, msg).unwrap();
+    assert_eq!((signer.used(), signer.total_used()), (1, 3));
+
+    // grant the re-authorization once
+    let calls = Rc::new(Cell::new(0u32));
+    let c = calls.clone();
+    let mut signer = UsageLimitedSigner::with_reauthorization(ecdsa(), 1, move |total| {
+        c.set(c.get() + 1);
+        total < 2
+    }).unwrap();
+    signer.sign(&mut sig, msg).unwrap();
+    signer.sign(&mut sig, msg).unwrap();
+    assert!(signer.sign(&mut sig, msg).is_err());
+    assert_eq!((calls.get(), signer.total_used()), (2, 2));
+    signer.verify(&sig, msg).unwrap();
+}
+
+#[test]
+fn constant_latency() {
+    let rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let ecdsa = ECDSA::auto_generate_key(SHA256::new(), rd.clone(), CurveP256::new().unwrap()).unwrap();
+    assert!(ConstantLatency::new(ECDSA::auto_generate_key(SHA256::new(), rd.clone(), CurveP256::new().unwrap()).unwrap(), Duration::from_secs(0)).is_err());
+
+    let ceiling = Duration::from_millis(60);
+    let mut signer = ConstantLatency::new(UsageLimitedSigner::new(ecdsa, 1).unwrap(), ceiling).unwrap();
+    let (mut sig, msg) = (SignatureContent::new(), b"constant latency".as_ref());
+    for i in 0..2 {
+        // the failed signing is padded too
+        let start = Instant::now();
+        assert_eq!(signer.sign(&mut sig, msg).is_ok(), i == 0);
+        assert!(start.elapsed() >= ceiling);
+    }
+    signer.verify(&sig, msg).unwrap();
+    assert_eq!((signer.ceiling(), signer.overruns()), (ceiling, 0));
+
+    // the operation which exceeds the ceiling isn't padded
+    let mut signer = ConstantLatency::new(ECDSA::auto_generate_key(SHA256::new(), rd.clone(), CurveP256::new().unwrap()).unwrap(), Duration::from_nanos(1)).unwrap();
+    signer.sign(&mut sig, msg).unwrap();
+    assert_eq!(signer.overruns(), 1);
+
+    #[cfg(feature = "rsa")]
+    {
+        use crate::rsa::PKCS1;
+        use crate::Cipher;
+
+        let rsa = ConstantLatency::new(PKCS1::auto_generate_key(1024, 19, SHA256::new(), rd, true).unwrap(), ceiling).unwrap();
+        let (mut c, mut m) = (Vec::new(), Vec::new());
+        rsa.encrypt(&mut c, msg).unwrap();
+        let start = Instant::now();
+        rsa.decrypt(&mut m, c.as_slice()).unwrap();
+        assert!(start.elapsed() >= ceiling);
+        assert_eq!(m.as_slice(), msg);
+
+        c[0] ^= 0x80;
+        let start = Instant::now();
+        assert!(rsa.decrypt(&mut m, c.as_slice()).is_err());
+        assert!(start.elapsed() >= ceiling);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use crate::{Cipher, CryptoError, CryptoErrorKind, Signature};
+
+/// The private key handle which pads the latency of the private key operations to the `ceiling`
+///
+/// the signing(`sign`, `sign_digest`) and the decryption(`decrypt`) return after at least the `ceiling` since they
+/// are called, no matter whether they succeed or fail, so the remote peer can't measure how long the private key
+/// operation takes. The remaining time is waited by the `std::thread::sleep` instead of the busy loop. The operation
+/// which takes longer than the `ceiling` isn't padded, it's counted by the `overruns`, the `ceiling` should be
+/// configured above the worst latency(e.g. the measured maximum with a margin).
+///
+/// It's the mitigation of the remote timing analysis, not the constant-time implementation: the local attacker can
+/// still observe the CPU usage, the cache and the power consumption. The public key operations(`verify`, `encrypt`)
+/// are not padded.
+pub struct ConstantLatency<S> {
+    scheme: S,
+    ceiling: Duration,
+    overruns: AtomicU64,
+}
+
+impl<S> ConstantLatency<S> {
+    /// the `ceiling` must be greater than 0
+    pub fn new(scheme: S, ceiling: Duration) -> Result<Self, CryptoError> {
+        if ceiling == Duration::from_secs(0) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The latency ceiling must be greater than 0"));
+        }
+
+        Ok(Self { scheme, ceiling, overruns: AtomicU64::new(0) })
+    }
+
+    pub fn ceiling(&self) -> Duration {
+        self.ceiling
+    }
+
+    /// the number of the operations which took longer than the `ceiling`
+    pub fn overruns(&self) -> u64 {
+        self.overruns.load(Ordering::Relaxed)
+    }
+
+    /// wait until the `ceiling` since the `start`, and return the result of the operation
+    fn pad<T>(&self, start: Instant, result: T) -> T {
+        match self.ceiling.checked_sub(start.elapsed()) {
+            Some(d) if d > Duration::from_secs(0) => std::thread::sleep(d),
+            _ => {
+                self.overruns.fetch_add(1, Ordering::Relaxed);
+            },
+        }
+        result
+    }
+}
+
+impl<S, T> Signature<T> for ConstantLatency<S>
+    where S: Signature<T> {
+    type Output = S::Output;
+
+    fn sign(&mut self, signature: &mut T, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        let start = Instant::now();
+        let r = self.scheme.sign(signature, message);
+        self.pad(start, r)
+    }
+
+    fn verify(&mut self, signature: &T, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.scheme.verify(signature, message)
+    }
+}
+
+impl<C: Cipher> Cipher for ConstantLatency<C> {
+    type Output = C::Output;
+
+    fn block_size(&self) -> Option<usize> {
+        self.scheme.block_size()
+    }
+
+    fn encrypt(&self, dst: &mut Vec<u8>, plaintext_block: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.scheme.encrypt(dst, plaintext_block)
+    }
+
+    /// the padding check failures(e.g. the RSAES-PKCS1-v1_5, OAEP) take the same time as the successful decryption
+    fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<Self::Output, CryptoError> {
+        let start = Instant::now();
+        let r = self.scheme.decrypt(dst, cipher_block);
+        self.pad(start, r)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl<S, H> crate::rsa::DigestSignature<H> for ConstantLatency<S>
+    where S: crate::rsa::DigestSignature<H>, H: crate::Digest {
+    fn sign_digest(&mut self, signature: &mut crate::rsa::SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        let start = Instant::now();
+        let r = self.scheme.sign_digest(signature, m_hash);
+        self.pad(start, r)
+    }
+
+    fn verify_digest(&mut self, signature: &crate::rsa::SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.scheme.verify_digest(signature, m_hash)
+    }
+}
\ No newline at end of file
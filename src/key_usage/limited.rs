@@ -0,0 +1,102 @@
+use crate::{CryptoError, CryptoErrorKind, Signature};
+
+/// The re-authorization callback, the argument is the total number of the signing operations of the key, the
+/// count of the current authorization is reset if it returns `true`.
+pub type ReauthorizeFn = Box<dyn FnMut(u64) -> bool>;
+
+/// The private key handle which limits the number of the signing operations
+pub struct UsageLimitedSigner<S> {
+    scheme: S,
+    max_uses: u64,
+    used: u64,
+    total_used: u64,
+    reauthorize: Option<ReauthorizeFn>,
+}
+
+impl<S> UsageLimitedSigner<S> {
+    /// refuse to sign after `max_uses` signing operations, `max_uses` must be greater than 0.
+    pub fn new(scheme: S, max_uses: u64) -> Result<Self, CryptoError> {
+        if max_uses == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The max uses of the key must be greater than 0"));
+        }
+
+        Ok(Self { scheme, max_uses, used: 0, total_used: 0, reauthorize: None })
+    }
+
+    /// the `reauthorize` is called when the `max_uses` is reached, the count is reset if it returns `true`,
+    /// otherwise the `LimitExceeded` is returned.
+    pub fn with_reauthorization<F>(scheme: S, max_uses: u64, reauthorize: F) -> Result<Self, CryptoError>
+        where F: 'static + FnMut(u64) -> bool {
+        let mut signer = Self::new(scheme, max_uses)?;
+        signer.reauthorize = Some(Box::new(reauthorize));
+        Ok(signer)
+    }
+
+    pub fn max_uses(&self) -> u64 {
+        self.max_uses
+    }
+
+    /// the number of the signing operations since the last authorization
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// the number of the signing operations since the handle is created
+    pub fn total_used(&self) -> u64 {
+        self.total_used
+    }
+
+    /// the remaining signing operations before the re-authorization
+    pub fn remaining(&self) -> u64 {
+        self.max_uses - self.used
+    }
+
+    /// reset the count of the current authorization, e.g. the operator re-authorizes the key out of band
+    pub fn reauthorize(&mut self) {
+        self.used = 0;
+    }
+
+    /// the signing operation is counted even if the inner scheme failed, the key material has been used.
+    fn consume(&mut self) -> Result<(), CryptoError> {
+        if self.used >= self.max_uses {
+            let total = self.total_used;
+            if self.reauthorize.as_mut().map(|f| f(total)).unwrap_or(false) {
+                self.used = 0;
+            } else {
+                return Err(CryptoError::new(CryptoErrorKind::LimitExceeded,
+                    format!("The key usage limit {} signatures is exceeded, the key needs to be re-authorized", self.max_uses)));
+            }
+        }
+
+        self.used += 1;
+        self.total_used = self.total_used.saturating_add(1);
+        Ok(())
+    }
+}
+
+impl<S, T> Signature<T> for UsageLimitedSigner<S>
+    where S: Signature<T> {
+    type Output = S::Output;
+
+    fn sign(&mut self, signature: &mut T, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.consume()?;
+        self.scheme.sign(signature, message)
+    }
+
+    fn verify(&mut self, signature: &T, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.scheme.verify(signature, message)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl<S, H> crate::rsa::DigestSignature<H> for UsageLimitedSigner<S>
+    where S: crate::rsa::DigestSignature<H>, H: crate::Digest {
+    fn sign_digest(&mut self, signature: &mut crate::rsa::SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.consume()?;
+        self.scheme.sign_digest(signature, m_hash)
+    }
+
+    fn verify_digest(&mut self, signature: &crate::rsa::SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.scheme.verify_digest(signature, m_hash)
+    }
+}
@@ -0,0 +1,22 @@
+//! The usage policy of the private key handles
+//!
+//! The `UsageLimitedSigner` wraps the signature scheme(e.g. the `ECDSA`, `PSS`, `DSA`) which holds the private key,
+//! it counts the signing operations and refuses to sign after `max_uses` operations, the `LimitExceeded` is returned.
+//! The optional re-authorization callback is called when the limit is reached, the count is reset if the callback
+//! grants it, so the long-running signing service can enforce the key-hygiene policy(e.g. ask the operator or the
+//! HSM policy to re-authorize after every N signatures).
+//!
+//! The wrapper doesn't give back the inner scheme, so the private key can't be exported or cloned through the
+//! handle. The verification is not counted.
+//!
+//! The `ConstantLatency` pads the latency of the signing and the decryption to the configured ceiling, the remote peer
+//! can't distinguish the fast and slow private key operations(e.g. the padding check failures of the RSA decryption).
+
+mod limited;
+pub use limited::{UsageLimitedSigner, ReauthorizeFn};
+
+mod latency;
+pub use latency::ConstantLatency;
+
+#[cfg(all(test, feature = "ec"))]
+mod key_usage_test;
\ No newline at end of file
@@ -0,0 +1,81 @@
+//! The encrypted traditional PEM
+//! RFC 1421 4.6.1.1(Proc-Type), 4.6.1.3(DEK-Info), OpenSSL `crypto/pem/pem_lib.c`
+//!
+//! Proc-Type: 4,ENCRYPTED
+//! DEK-Info: <cipher>,<IV in hex>
+//!
+//! The key is derived by the `EVP_BytesToKey(MD5, password, IV[..8], 1)`.
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::MD5;
+use crate::encoding::PemBlock;
+use crate::legacy::{LegacyCipher, insecure_evp_bytes_to_key};
+
+const PROC_TYPE: &str = "Proc-Type";
+const PROC_TYPE_ENCRYPTED: &str = "4,ENCRYPTED";
+const DEK_INFO: &str = "DEK-Info";
+
+fn pem_key(cipher: LegacyCipher, password: &[u8], iv: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    insecure_evp_bytes_to_key(MD5::new(), password, &iv[..8], 1, cipher.key_len(), 0).map(|(k, _)| k)
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..(i + 2)], 16).ok()).collect()
+}
+
+/// whether the `block` has the `Proc-Type: 4,ENCRYPTED` header
+pub fn is_encrypted_pem_block(block: &PemBlock) -> bool {
+    block.header(PROC_TYPE).map(|v| v.replace(' ', "").eq_ignore_ascii_case(PROC_TYPE_ENCRYPTED)).unwrap_or(false)
+}
+
+/// decrypt the traditional PEM block which is encrypted by the `DEK-Info` cipher, e.g. the
+/// `BEGIN RSA PRIVATE KEY` generated by the `openssl genrsa -aes128 -traditional`, and return the
+/// block without the encapsulated headers.
+/// INSECURE: it's only used to read the legacy files.
+pub fn insecure_decrypt_pem_block(block: &PemBlock, password: &[u8]) -> Result<PemBlock, CryptoError> {
+    if !is_encrypted_pem_block(block) {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The PEM block is not encrypted"));
+    }
+
+    let dek = block.header(DEK_INFO).ok_or_else(|| {
+        CryptoError::new(CryptoErrorKind::InvalidParameter, "The DEK-Info header is not found in the PEM block")
+    })?;
+    let (name, iv) = match dek.find(',') {
+        Some(idx) => (dek[..idx].trim(), dek[(idx + 1)..].trim()),
+        None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid DEK-Info: {}", dek))),
+    };
+    let cipher = LegacyCipher::from_name(name).ok_or_else(|| {
+        CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The DEK-Info cipher {} is not supported", name))
+    })?;
+    let iv = match parse_hex(iv) {
+        Some(iv) if iv.len() == cipher.iv_len() => iv,
+        _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid DEK-Info IV: {}", iv))),
+    };
+
+    let key = pem_key(cipher, password, iv.as_slice())?;
+    let data = cipher.cbc_crypt(key.as_slice(), iv.as_slice(), block.data(), false)?;
+    PemBlock::new(block.label(), data)
+}
+
+/// encrypt the PEM block data as the OpenSSL traditional format, the encapsulated headers of the `block`
+/// are not kept.
+/// INSECURE: it's only used to be compatible with the legacy tools.
+/// `iv`: the random IV of the `cipher.iv_len()` bytes, it's also used as the salt of the key derivation;
+pub fn insecure_encrypt_pem_block(block: &PemBlock, cipher: LegacyCipher, password: &[u8], iv: &[u8]) -> Result<PemBlock, CryptoError> {
+    if iv.len() != cipher.iv_len() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("Wrong IV length: {}, the IV length of the {} must be {}", iv.len(), cipher.name(), cipher.iv_len())));
+    }
+
+    let key = pem_key(cipher, password, iv)?;
+    let data = cipher.cbc_crypt(key.as_slice(), iv, block.data(), true)?;
+    let mut dst = PemBlock::new(block.label(), data)?;
+    let iv_hex: String = iv.iter().map(|x| format!("{:02X}", x)).collect();
+    dst.add_header(PROC_TYPE, PROC_TYPE_ENCRYPTED)?;
+    dst.add_header(DEK_INFO, format!("{},{}", cipher.name(), iv_hex).as_str())?;
+    Ok(dst)
+}
@@ -0,0 +1,121 @@
+//! EVP_BytesToKey
+//! OpenSSL `crypto/evp/evp_key.c`
+//!
+//! D_1 = H^count(password || salt), D_i = H^count(D_{i-1} || password || salt),
+//! key || iv = D_1 || D_2 || ..., where the `salt` is 8 bytes or empty.
+
+use crate::{Digest, Cipher, CryptoError, CryptoErrorKind, AES, DES, TDES};
+use crate::cipher_mode::{CBC, PKCS7Padding, FixedInitialVec};
+
+/// derive the `key_len` bytes key and the `iv_len` bytes IV by the OpenSSL `EVP_BytesToKey`.
+/// INSECURE: it's only used to be compatible with the legacy files.
+/// `salt`: the 8 bytes salt or empty;
+/// `count`: the iteration count, it's 1 in the `openssl enc` and the encrypted PEM;
+pub fn insecure_evp_bytes_to_key<D: Digest>(digest: D, password: &[u8], salt: &[u8], count: usize,
+                                            key_len: usize, iv_len: usize) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    if !salt.is_empty() && salt.len() != 8 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The salt of the EVP_BytesToKey must be 8 bytes or empty"));
+    } else if count == 0 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The iteration count must be great than 0"));
+    }
+
+    let mut digest = digest;
+    let (mut dk, mut d) = (Vec::with_capacity(key_len + iv_len), Vec::new());
+    while dk.len() < key_len + iv_len {
+        digest.reset();
+        digest.write(d.as_slice());
+        digest.write(password);
+        digest.write(salt);
+        digest.checksum(&mut d);
+        for _ in 1..count {
+            digest.reset();
+            digest.write(d.as_slice());
+            digest.checksum(&mut d);
+        }
+        dk.extend_from_slice(d.as_slice());
+    }
+
+    dk.truncate(key_len + iv_len);
+    let iv = dk.split_off(key_len);
+    Ok((dk, iv))
+}
+
+/// The legacy CBC ciphers with the PKCS#7 padding, which are used in the `openssl enc` and the `DEK-Info`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegacyCipher {
+    AES128CBC,
+    AES192CBC,
+    AES256CBC,
+    DESCBC,
+    DESEDE3CBC,
+}
+
+impl LegacyCipher {
+    /// the OpenSSL cipher name, e.g. `AES-128-CBC`
+    pub fn name(&self) -> &'static str {
+        match self {
+            LegacyCipher::AES128CBC => "AES-128-CBC",
+            LegacyCipher::AES192CBC => "AES-192-CBC",
+            LegacyCipher::AES256CBC => "AES-256-CBC",
+            LegacyCipher::DESCBC => "DES-CBC",
+            LegacyCipher::DESEDE3CBC => "DES-EDE3-CBC",
+        }
+    }
+
+    /// parse the OpenSSL cipher name(case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        [LegacyCipher::AES128CBC, LegacyCipher::AES192CBC, LegacyCipher::AES256CBC,
+            LegacyCipher::DESCBC, LegacyCipher::DESEDE3CBC].iter().find(|x| {
+            x.name().eq_ignore_ascii_case(name)
+        }).copied()
+    }
+
+    /// key length in bytes
+    pub fn key_len(&self) -> usize {
+        match self {
+            LegacyCipher::AES128CBC => 16,
+            LegacyCipher::AES192CBC => 24,
+            LegacyCipher::AES256CBC => 32,
+            LegacyCipher::DESCBC => 8,
+            LegacyCipher::DESEDE3CBC => 24,
+        }
+    }
+
+    /// IV length in bytes
+    pub fn iv_len(&self) -> usize {
+        match self {
+            LegacyCipher::DESCBC | LegacyCipher::DESEDE3CBC => 8,
+            _ => 16,
+        }
+    }
+
+    fn crypt<C: 'static + Cipher>(c: C, iv: &[u8], data: &[u8], is_encrypt: bool) -> Result<Vec<u8>, CryptoError> {
+        let padding = PKCS7Padding::new(&c);
+        let cbc = CBC::new(c, padding, FixedInitialVec::new(iv.to_vec()))?;
+        let mut dst = Vec::with_capacity(data.len() + iv.len());
+        if is_encrypt {
+            cbc.encrypt(&mut dst, data)?;
+        } else {
+            cbc.decrypt(&mut dst, data)?;
+        }
+        Ok(dst)
+    }
+
+    pub(super) fn cbc_crypt(&self, key: &[u8], iv: &[u8], data: &[u8], is_encrypt: bool) -> Result<Vec<u8>, CryptoError> {
+        if key.len() != self.key_len() || iv.len() != self.iv_len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key or IV length for the {}", self.name())));
+        }
+
+        let des_key = |i: usize| {
+            let mut k = [0u8; 8];
+            k.copy_from_slice(&key[(i << 3)..((i + 1) << 3)]);
+            k
+        };
+        match self {
+            LegacyCipher::DESCBC => Self::crypt(DES::new(des_key(0)), iv, data, is_encrypt),
+            LegacyCipher::DESEDE3CBC => Self::crypt(TDES::new(des_key(0), des_key(1), des_key(2)), iv, data, is_encrypt),
+            _ => Self::crypt(AES::new(key.to_vec())?, iv, data, is_encrypt),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Legacy OpenSSL compatibility
+//!
+//! The OpenSSL `EVP_BytesToKey` key derivation, the salted `openssl enc` format and the encrypted
+//! traditional PEM(`Proc-Type: 4,ENCRYPTED`, `DEK-Info`) are provided to read the existing files only.
+//! They are INSECURE: the key derivation uses the fast hash with one iteration by default, which is
+//! easy to brute force, and the ciphertext is unauthenticated, so the padding oracle is possible.
+//! All the functions are prefixed by `insecure_`, use the PBKDF2 and the AEAD for the new data.
+
+mod evp;
+pub use evp::{insecure_evp_bytes_to_key, LegacyCipher};
+
+mod openssl_enc;
+pub use openssl_enc::{insecure_openssl_enc_encrypt, insecure_openssl_enc_decrypt};
+
+mod encrypted_pem;
+pub use encrypted_pem::{insecure_decrypt_pem_block, insecure_encrypt_pem_block, is_encrypted_pem_block};
+
+#[cfg(test)]
+mod legacy_test;
@@ -0,0 +1,46 @@
+//! The `openssl enc` format
+//!
+//! salted: "Salted__" || salt(8) || ciphertext;
+//! no salt(`-nosalt`): ciphertext.
+//! The key and IV are derived by the `EVP_BytesToKey` with the iteration count 1, the default digest is MD5
+//! before the OpenSSL 1.1.0, and SHA-256 after that(`-md`).
+
+use crate::{Digest, CryptoError, CryptoErrorKind};
+use crate::legacy::{LegacyCipher, insecure_evp_bytes_to_key};
+
+const SALTED_MAGIC: &[u8] = b"Salted__";
+
+/// encrypt the `data` as the `openssl enc -<cipher> -md <digest> [-nosalt]` does.
+/// INSECURE: it's only used to be compatible with the legacy tools.
+/// `salt`: the 8 bytes random salt, `None` means the `-nosalt`;
+pub fn insecure_openssl_enc_encrypt<D: Digest>(digest: D, cipher: LegacyCipher, password: &[u8],
+                                               salt: Option<[u8; 8]>, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let salt = salt.as_ref().map(|s| s.as_ref()).unwrap_or(&[]);
+    let (key, iv) = insecure_evp_bytes_to_key(digest, password, salt, 1, cipher.key_len(), cipher.iv_len())?;
+    let ct = cipher.cbc_crypt(key.as_slice(), iv.as_slice(), data, true)?;
+
+    let mut dst = Vec::with_capacity(ct.len() + 16);
+    if !salt.is_empty() {
+        dst.extend_from_slice(SALTED_MAGIC);
+        dst.extend_from_slice(salt);
+    }
+    dst.extend(ct);
+    Ok(dst)
+}
+
+/// decrypt the `openssl enc` output, the data without the `Salted__` header is treated as the `-nosalt` output.
+/// INSECURE: it's only used to be compatible with the legacy tools.
+pub fn insecure_openssl_enc_decrypt<D: Digest>(digest: D, cipher: LegacyCipher, password: &[u8],
+                                               data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (salt, ct) = if data.starts_with(SALTED_MAGIC) {
+        if data.len() < 16 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The salted header is too short"));
+        }
+        (&data[8..16], &data[16..])
+    } else {
+        (&data[..0], data)
+    };
+
+    let (key, iv) = insecure_evp_bytes_to_key(digest, password, salt, 1, cipher.key_len(), cipher.iv_len())?;
+    cipher.cbc_crypt(key.as_slice(), iv.as_slice(), ct, false)
+}
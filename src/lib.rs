@@ -1,53 +1,230 @@
 
+//! The algorithms are selected by the cargo features, all the algorithms are enabled by default.
+//! The generic constructions(e.g. the HMAC, CMAC, AEAD STREAM, PBKDF2) and the key validation are always compiled,
+//! they are instantiated by the primitives of the enabled features. The `capability` report of the detected CPU
+//! features, the compiled backends and the enabled features, the `key_usage` limits of the private key handles and
+//! the `batch` hashing and the `oid` constants are also always compiled.
+//!
+//! - `aes`: AES;
+//! - `des`: DES, TDES;
+//! - `md5`: MD5;
+//! - `sha2`: SHA-1, SHA-2;
+//! - `sha3`: Keccak, SHA-3, SHAKE, cSHAKE, KMAC, ParallelHash, KangarooTwelve;
+//! - `sm`: SM3, SM4, ZUC;
+//! - `chacha`: ChaCha20/ChaCha12/ChaCha8, Poly1305, ChaCha20-Poly1305, the non-cryptographic `sim::SimRand` for the simulations;
+//! - `modes`: the block cipher modes, the AES CTR_DRBG(with the `aes`);
+//! - `kdf`: the key derivation functions;
+//! - `rsa`, `dsa`(DSA and FFDH), `ec`(elliptic curve, ECDH and ECDSA): the public key algorithms;
+//! - `webauthn`, `pin`: the WebAuthn relying party, the SPKI pinning;
+//! - `legacy`: the insecure OpenSSL legacy key derivation and encryption, which can be excluded by the auditor;
+//! - `webcrypto`: the WebCrypto algorithm identifiers and the `SubtleCrypto` operations;
+//! - `evp`: the digests and the ciphers by the OpenSSL NID/OID/name;
+//! - `envelope`: the self-describing encrypted blob;
+//! - `hash`: the runtime-dispatch hash algorithm registry;
+//! - `sse`: the searchable symmetric encryption building block(keyword tags, encrypted index entries);
+//! - `dae`: the deterministic authenticated encryption for the deduplication and the indexing;
+//! - `sharing`: the Shamir's secret sharing and the Feldman verifiable secret sharing, the SLIP-39 with the `kdf` feature;
+//! - `timelock`: the RSW time-lock puzzles;
+//! - `oprf`: the OPRF/VOPRF/POPRF(RFC 9497) and the hash-to-curve(RFC 9380) of the P-256, the OPRF-based PSI;
+//! - `estream`: the eSTREAM portfolio stream ciphers HC-128, HC-256 and Rabbit;
+//! - `xoodyak`: the Xoodyak hash, the Xoodyak AEAD and the Cyclist duplex object;
+//! - `grain`: the Grain-128AEADv2 AEAD for the constrained hardware;
+//! - `x25519`: the X25519 Diffie-Hellman key agreement on the Curve25519, the X3DH key agreement with the `kdf` feature;
+//! - `sm2`: the SM2 digital signature on the sm2p256v1 curve;
+//! - `schnorr`: the BIP-340 Schnorr signature and the BIP-327 MuSig2 on the secp256k1 curve;
+//! - `merkle`: the Merkle hash tree with the incremental update;
+//! - `research`: the lightweight block ciphers Simon and Speck for the research comparisons, it's not enabled by default;
+//! - `keyschedule`: the SM4/AES round keys export and import, the round-reduced SM4 for the research and the custom modes,
+//!   it's not enabled by default;
+//! - `testutil`: the `test_util` weak seeded RNG, the seed capture/replay and the block cipher conformance suite for the
+//!   downstream tests, it's not enabled by default;
+//! - `parallel`, `cache`, `mmap`, `mlock`: the parallel RSA key generation, Argon2, ParallelHash and batch hashing, the signature verification cache, the
+//!   memory-mapped file hashing, the secret key material in the locked memory, they are not enabled by default;
+
 extern crate rmath;
 
 mod cipher;
-pub use cipher::{Cipher, Digest, DigestXOF, Signature};
+pub use cipher::{Cipher, StreamCipher, Digest, DigestXOF, DigestMidState, Signature, AEAD};
 
 mod crypto_err;
 pub use crypto_err::{CryptoErrorKind, CryptoError};
 
+#[cfg(any(feature = "rsa", feature = "sm2"))]
+mod rand;
+
+#[cfg(feature = "aes")]
 mod aes;
+#[cfg(feature = "aes")]
 pub use aes::AES;
 
+#[cfg(feature = "des")]
 mod des;
+#[cfg(feature = "des")]
 pub use des::{DES, TDES};
 
+#[cfg(feature = "md5")]
 mod md5;
+#[cfg(feature = "md5")]
 pub use md5::MD5;
 
+#[cfg(feature = "sha2")]
 pub mod sha;
+#[cfg(feature = "sha2")]
 pub use sha::SHA;
 
+#[cfg(feature = "sm")]
 mod sm3;
+#[cfg(feature = "sm")]
 pub use sm3::SM3;
 
+#[cfg(feature = "sha3")]
 mod keccak;
+#[cfg(feature = "sha3")]
 pub use keccak::{Keccak, KeccakSponge};
 
+#[cfg(feature = "sha3")]
 pub mod sha3;
+#[cfg(feature = "sha3")]
 pub use sha3::SHA3;
 
 mod hmac;
 pub use hmac::HMAC;
 
+#[cfg(feature = "modes")]
 pub mod cipher_mode;
 
+#[cfg(feature = "sm")]
 mod zuc;
+#[cfg(feature = "sm")]
 pub use zuc::{ZUC, ZUCCipher, ZUCMac};
 
+#[cfg(feature = "sm")]
 mod sm4;
+#[cfg(feature = "sm")]
 pub use sm4::SM4;
 
+#[cfg(feature = "chacha")]
+mod chacha;
+#[cfg(feature = "chacha")]
+pub use chacha::{ChaCha20, ChaCha12, ChaCha8, Poly1305, ChaCha20Poly1305};
+#[cfg(feature = "chacha")]
+pub mod sim;
+
+#[cfg(feature = "estream")]
+mod estream;
+#[cfg(feature = "estream")]
+pub use estream::{HC128, HC256, Rabbit};
+
+#[cfg(feature = "xoodyak")]
+mod xoodyak;
+#[cfg(feature = "xoodyak")]
+pub use xoodyak::{Cyclist, Xoodyak, XoodyakHash};
+
+#[cfg(feature = "grain")]
+mod grain;
+#[cfg(feature = "grain")]
+pub use grain::Grain128AEAD;
+
+#[cfg(feature = "research")]
+mod simon_speck;
+#[cfg(feature = "research")]
+pub use simon_speck::{Simon, Speck};
+
 mod cmac;
 pub use cmac::CMAC;
 
-mod kdf;
+#[cfg(feature = "kdf")]
+pub mod kdf;
 
+#[cfg(feature = "dsa")]
 pub mod dsa;
 
+#[cfg(feature = "rsa")]
 pub mod rsa;
 
+#[cfg(feature = "ec")]
 pub mod elliptic;
 
-pub mod ecdsa;
\ No newline at end of file
+#[cfg(feature = "ec")]
+pub mod ecdsa;
+
+pub mod aead;
+
+pub mod numeric;
+
+pub mod prime;
+
+pub mod jcs;
+
+#[cfg(feature = "webauthn")]
+pub mod webauthn;
+
+pub mod encoding;
+
+pub mod oid;
+
+pub mod file_digest;
+
+pub mod batch;
+
+#[cfg(feature = "pin")]
+pub mod pin;
+
+#[cfg(feature = "legacy")]
+pub mod legacy;
+
+#[cfg(feature = "webcrypto")]
+pub mod webcrypto;
+
+#[cfg(feature = "evp")]
+pub mod evp;
+
+#[cfg(feature = "envelope")]
+pub mod envelope;
+
+#[cfg(feature = "hash")]
+pub mod hash_alg;
+
+#[cfg(feature = "sse")]
+pub mod sse;
+
+#[cfg(feature = "dae")]
+pub mod dae;
+
+#[cfg(feature = "sharing")]
+pub mod sharing;
+
+#[cfg(feature = "timelock")]
+pub mod timelock;
+
+#[cfg(feature = "oprf")]
+pub mod oprf;
+
+pub mod dh;
+
+#[cfg(feature = "x25519")]
+pub mod x25519;
+
+#[cfg(feature = "sm2")]
+pub mod sm2;
+
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
+
+#[cfg(feature = "merkle")]
+pub mod merkle;
+
+pub mod validation;
+
+pub mod capability;
+
+pub mod key_usage;
+
+#[cfg(feature = "cache")]
+pub mod verify_cache;
+
+#[cfg(any(test, feature = "testutil"))]
+pub mod test_util;
+
+#[cfg(feature = "mlock")]
+pub mod secret;
\ No newline at end of file
@@ -2,7 +2,7 @@
 //! RFC-1321  
 //! https://www.cnblogs.com/mengsuenyan/p/12697709.html  
 
-use crate::Digest;
+use crate::{Digest, DigestMidState, CryptoError, CryptoErrorKind};
 
 pub(super) const MD5_BLOCK_SIZE: usize = 64;
 pub(super) const MD5_DIGEST_BITS_LEN: usize = 16 << 3;
@@ -27,6 +27,20 @@ impl MD5 {
             is_checked: false,
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(16));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
+}
+
+impl Default for MD5 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Digest for MD5 {
@@ -99,6 +113,41 @@ impl Digest for MD5 {
     }
 }
 
+impl DigestMidState for MD5 {
+    fn export_mid_state(&self) -> Result<(Vec<u8>, u64), CryptoError> {
+        let mut h = self.clone();
+        if h.idx == MD5_BLOCK_SIZE {
+            h.update(None);
+            h.idx = 0;
+        }
+        
+        if h.is_checked {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The digest has been finalized"))
+        } else if h.idx != 0 {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, 
+                format!("The processed message length {} is not a multiple of the block size", h.len)))
+        } else {
+            let mut state = Vec::with_capacity(4 << 2);
+            h.digest.iter().for_each(|&e| state.extend_from_slice(e.to_le_bytes().as_ref()));
+            Ok((state, h.len as u64))
+        }
+    }
+
+    fn from_mid_state(state: &[u8], len: u64) -> Result<Self, CryptoError> {
+        if state.len() != (4 << 2) || !len.is_multiple_of(MD5_BLOCK_SIZE as u64) || len > (usize::MAX as u64) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+                format!("Invalid mid-state: the state must be {} bytes, and the length must be a multiple of {}", 4 << 2, MD5_BLOCK_SIZE)));
+        }
+        
+        let mut h = MD5::new();
+        h.digest.iter_mut().zip(state.chunks_exact(4)).for_each(|(a, b)| {
+            *a = u32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+        });
+        h.len = len as usize;
+        Ok(h)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Digest, MD5};
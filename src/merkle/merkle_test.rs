@@ -0,0 +1,76 @@
+use crate::merkle::{MerkleTree, verify_inclusion};
+use crate::sha::SHA256;
+use crate::CryptoErrorKind;
+use crate::test_util::hex;
+
+#[test]
+fn merkle_tree_root() {
+    // the test vectors of the certificate transparency(RFC 6962)
+    let leaves = ["", "00", "10", "2021", "3031", "40414243", "5051525354555657", "606162636465666768696a6b6c6d6e6f"];
+    let roots = [
+        "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+        "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+        "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+        "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+        "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+        "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+        "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+        "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+    ];
+
+    let mut tree = MerkleTree::new(SHA256::new());
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855").as_slice());
+    for (i, (leaf, root)) in leaves.iter().zip(roots.iter()).enumerate() {
+        assert_eq!(tree.append(hex(leaf).as_slice()), i);
+        assert_eq!(tree.root(), hex(root).as_slice(), "case: {}", i);
+
+        let rebuilt = MerkleTree::from_leaf_hashes(SHA256::new(), tree.leaf_hashes().to_vec()).unwrap();
+        assert_eq!(rebuilt.root(), tree.root());
+    }
+}
+
+#[test]
+fn merkle_tree_incremental() {
+    let mut tree = MerkleTree::new(SHA256::new());
+    for i in 0..37u32 {
+        tree.append(format!("file-{}", i).as_bytes());
+    }
+
+    // the updated tree is the same as the tree which is built by the updated leaves
+    for &i in [0usize, 7, 32, 36].iter() {
+        tree.update(i, format!("file-{}-v2", i).as_bytes()).unwrap();
+        let rebuilt = MerkleTree::from_leaf_hashes(SHA256::new(), tree.leaf_hashes().to_vec()).unwrap();
+        assert_eq!(rebuilt.root(), tree.root());
+        assert_eq!(tree.leaf_hash(i).unwrap(), tree.clone().hash_leaf(format!("file-{}-v2", i).as_bytes()).as_slice());
+    }
+    assert_eq!(tree.update(37, b"").err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(tree.update_leaf_hash(0, &[0u8; 31]).is_err());
+    assert!(tree.append_leaf_hash(&[0u8; 33]).is_err());
+
+    // the inclusion proofs of all the tree sizes
+    let mut md = SHA256::new();
+    for n in 1..=tree.len() {
+        let sub = MerkleTree::from_leaf_hashes(SHA256::new(), tree.leaf_hashes()[..n].to_vec()).unwrap();
+        for i in 0..n {
+            let proof = sub.inclusion_proof(i).unwrap();
+            verify_inclusion(&mut md, i, n, sub.leaf_hash(i).unwrap(), proof.as_slice(), sub.root()).unwrap();
+            if n > 1 {
+                assert!(verify_inclusion(&mut md, i ^ 1, n.max((i ^ 1) + 1), sub.leaf_hash(i).unwrap(), proof.as_slice(), sub.root()).is_err());
+                assert!(verify_inclusion(&mut md, i, n, sub.leaf_hash(i).unwrap(), &proof[1..], sub.root()).is_err());
+            }
+        }
+    }
+    assert!(tree.inclusion_proof(tree.len()).is_err());
+
+    // the serialization
+    let bytes = tree.to_bytes();
+    assert_eq!(bytes.len(), 12 + 32 * tree.len());
+    let mut restored = MerkleTree::from_bytes(SHA256::new(), bytes.as_slice()).unwrap();
+    assert_eq!(restored.root(), tree.root());
+    restored.append(b"file-37");
+    tree.append(b"file-37");
+    assert_eq!(restored.root(), tree.root());
+    assert!(MerkleTree::from_bytes(SHA256::new(), &bytes[..(bytes.len() - 1)]).is_err());
+    assert!(MerkleTree::from_bytes(SHA256::new(), &bytes[..11]).is_err());
+}
\ No newline at end of file
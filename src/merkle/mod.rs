@@ -0,0 +1,12 @@
+//! Merkle hash tree
+//! RFC 6962 2.1, RFC 9162 2.1
+//!
+//! The `MerkleTree` keeps all the nodes of the tree, so the appending and the updating of a leaf only recompute the
+//! hashes on the path from the leaf to the root, e.g. the snapshot of a directory is updated by the changed files
+//! instead of being rebuilt. The leaf is the hash of the leaf data, the tree is serialized by the leaf hashes.
+
+mod tree;
+pub use tree::{MerkleTree, verify_inclusion};
+
+#[cfg(all(test, feature = "sha2"))]
+mod merkle_test;
\ No newline at end of file
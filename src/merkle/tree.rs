@@ -0,0 +1,255 @@
+use crate::{Digest, CryptoError, CryptoErrorKind};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// H(0x00 || data)
+fn leaf_hash<D: Digest>(md: &mut D, data: &[u8]) -> Vec<u8> {
+    let mut h = Vec::new();
+    md.reset();
+    md.write(&[LEAF_PREFIX]);
+    md.write(data);
+    md.checksum(&mut h);
+    h
+}
+
+/// H(0x01 || left || right)
+fn node_hash<D: Digest>(md: &mut D, left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut h = Vec::new();
+    md.reset();
+    md.write(&[NODE_PREFIX]);
+    md.write(left);
+    md.write(right);
+    md.checksum(&mut h);
+    h
+}
+
+/// The Merkle tree with the incremental update
+///
+/// the `levels[0]` are the leaf hashes, and the `levels[i+1][j] = H(0x01 || levels[i][2j] || levels[i][2j+1])`, the
+/// last node of the odd length level is promoted to the next level unchanged, so the root is the same as the
+/// `MTH` of the RFC 6962. The root of the empty tree is the `H("")`.
+#[derive(Clone)]
+pub struct MerkleTree<D> {
+    md: D,
+    levels: Vec<Vec<Vec<u8>>>,
+    empty_root: Vec<u8>,
+}
+
+impl<D: Digest> MerkleTree<D> {
+    pub fn new(mut md: D) -> Self {
+        let mut empty_root = Vec::new();
+        md.reset();
+        md.checksum(&mut empty_root);
+
+        Self {
+            md,
+            levels: vec![Vec::new()],
+            empty_root,
+        }
+    }
+
+    /// construct the tree by the leaf hashes, the length of the leaf hash must be the digest length.
+    pub fn from_leaf_hashes(md: D, leaf_hashes: Vec<Vec<u8>>) -> Result<Self, CryptoError> {
+        let mut tree = Self::new(md);
+        tree.check_hash_len(leaf_hashes.iter().map(|h| h.as_slice()))?;
+        tree.levels[0] = leaf_hashes;
+        tree.rebuild();
+        Ok(tree)
+    }
+
+    fn hash_len(&self) -> usize {
+        self.empty_root.len()
+    }
+
+    fn check_hash_len<'a, I: Iterator<Item=&'a [u8]>>(&self, mut hashes: I) -> Result<(), CryptoError> {
+        match hashes.find(|h| h.len() != self.hash_len()) {
+            Some(h) => Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The leaf hash must be {} bytes, but got {} bytes", self.hash_len(), h.len()))),
+            None => Ok(()),
+        }
+    }
+
+    /// recompute all the levels from the leaf hashes
+    fn rebuild(&mut self) {
+        let (md, levels) = (&mut self.md, &mut self.levels);
+        levels.truncate(1);
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1].chunks(2).map(|c| {
+                if c.len() == 2 { node_hash(md, c[0].as_slice(), c[1].as_slice()) } else { c[0].clone() }
+            }).collect::<Vec<_>>();
+            levels.push(next);
+        }
+    }
+
+    /// recompute the hashes on the path from the leaf `idx` to the root
+    fn rehash_path(&mut self, mut idx: usize) {
+        let (md, levels) = (&mut self.md, &mut self.levels);
+        let mut i = 0;
+        while levels[i].len() > 1 {
+            let (p, l) = (idx >> 1, idx & !1);
+            let node = match levels[i].get(l + 1) {
+                Some(r) => node_hash(md, levels[i][l].as_slice(), r.as_slice()),
+                None => levels[i][l].clone(),
+            };
+
+            if levels.len() == i + 1 {
+                levels.push(Vec::new());
+            }
+            let up = &mut levels[i + 1];
+            if p < up.len() {
+                up[p] = node;
+            } else {
+                up.push(node);
+            }
+            idx = p;
+            i += 1;
+        }
+    }
+
+    /// the number of the leaves
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    pub fn root(&self) -> &[u8] {
+        self.levels.last().and_then(|l| l.first()).unwrap_or(&self.empty_root).as_slice()
+    }
+
+    /// the hash of the leaf `index`
+    pub fn leaf_hash(&self, index: usize) -> Option<&[u8]> {
+        self.levels[0].get(index).map(|h| h.as_slice())
+    }
+
+    pub fn leaf_hashes(&self) -> &[Vec<u8>] {
+        self.levels[0].as_slice()
+    }
+
+    /// the leaf hash `H(0x00 || data)` of the `data`
+    pub fn hash_leaf(&mut self, data: &[u8]) -> Vec<u8> {
+        leaf_hash(&mut self.md, data)
+    }
+
+    /// append the leaf data, and return the index of the leaf
+    pub fn append(&mut self, data: &[u8]) -> usize {
+        let h = self.hash_leaf(data);
+        self.levels[0].push(h);
+        self.rehash_path(self.len() - 1);
+        self.len() - 1
+    }
+
+    /// append the leaf hash, and return the index of the leaf
+    pub fn append_leaf_hash(&mut self, leaf_hash: &[u8]) -> Result<usize, CryptoError> {
+        self.check_hash_len(std::iter::once(leaf_hash))?;
+        self.levels[0].push(leaf_hash.to_vec());
+        self.rehash_path(self.len() - 1);
+        Ok(self.len() - 1)
+    }
+
+    /// replace the data of the leaf `index`
+    pub fn update(&mut self, index: usize, data: &[u8]) -> Result<(), CryptoError> {
+        let h = self.hash_leaf(data);
+        self.update_leaf_hash(index, h.as_slice())
+    }
+
+    /// replace the hash of the leaf `index`
+    pub fn update_leaf_hash(&mut self, index: usize, leaf_hash: &[u8]) -> Result<(), CryptoError> {
+        if index >= self.len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The leaf index {} is out of the tree size {}", index, self.len())));
+        }
+        self.check_hash_len(std::iter::once(leaf_hash))?;
+
+        if self.levels[0][index].as_slice() != leaf_hash {
+            self.levels[0][index] = leaf_hash.to_vec();
+            self.rehash_path(index);
+        }
+        Ok(())
+    }
+
+    /// the audit path of the leaf `index` from the bottom to the top(RFC 9162 2.1.3.1)
+    pub fn inclusion_proof(&self, index: usize) -> Result<Vec<Vec<u8>>, CryptoError> {
+        if index >= self.len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The leaf index {} is out of the tree size {}", index, self.len())));
+        }
+
+        let (mut proof, mut idx) = (Vec::new(), index);
+        for level in self.levels.iter().take_while(|l| l.len() > 1) {
+            if let Some(s) = level.get(idx ^ 1) {
+                proof.push(s.clone());
+            }
+            idx >>= 1;
+        }
+        Ok(proof)
+    }
+
+    /// the serialization: `len(H) || n || H(leaf_0) || ... || H(leaf_{n-1})`, the `len(H)` is the 4 bytes big-endian
+    /// length of the leaf hash, and the `n` is the 8 bytes big-endian number of the leaves.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(12 + self.len() * self.hash_len());
+        buf.extend_from_slice((self.hash_len() as u32).to_be_bytes().as_ref());
+        buf.extend_from_slice((self.len() as u64).to_be_bytes().as_ref());
+        self.levels[0].iter().for_each(|h| buf.extend_from_slice(h.as_slice()));
+        buf
+    }
+
+    /// parse the serialization of the `to_bytes`, the inner nodes are recomputed from the leaf hashes, so the root
+    /// should be compared with the trusted root.
+    pub fn from_bytes(md: D, data: &[u8]) -> Result<Self, CryptoError> {
+        let invalid = || CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid Merkle tree encoding");
+        if data.len() < 12 {
+            return Err(invalid());
+        }
+
+        let mut tree = Self::new(md);
+        let hl = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        let mut n = [0u8; 8];
+        n.copy_from_slice(&data[4..12]);
+        let n = u64::from_be_bytes(n);
+        if hl != tree.hash_len() || (data.len() - 12) as u64 != n.saturating_mul(hl as u64) {
+            return Err(invalid());
+        }
+
+        tree.levels[0] = data[12..].chunks(hl).map(|h| h.to_vec()).collect();
+        tree.rebuild();
+        Ok(tree)
+    }
+}
+
+/// verify the audit path of the leaf `index` in the tree of the `tree_size` leaves(RFC 9162 2.1.3.2)
+pub fn verify_inclusion<D: Digest>(md: &mut D, index: usize, tree_size: usize, leaf_hash: &[u8], proof: &[Vec<u8>], root: &[u8]) -> Result<(), CryptoError> {
+    if index >= tree_size {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The leaf index {} is out of the tree size {}", index, tree_size)));
+    }
+
+    let (mut fi, mut si, mut r) = (index, tree_size - 1, leaf_hash.to_vec());
+    for p in proof.iter() {
+        if si == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The audit path is too long"));
+        }
+
+        if fi & 1 == 1 || fi == si {
+            r = node_hash(md, p.as_slice(), r.as_slice());
+            while fi & 1 == 0 && fi != 0 {
+                fi >>= 1;
+                si >>= 1;
+            }
+        } else {
+            r = node_hash(md, r.as_slice(), p.as_slice());
+        }
+        fi >>= 1;
+        si >>= 1;
+    }
+
+    if si == 0 && r.as_slice() == root {
+        Ok(())
+    } else {
+        Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The audit path doesn't match the root"))
+    }
+}
\ No newline at end of file
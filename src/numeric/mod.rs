@@ -0,0 +1,18 @@
+//! Numeric utilities over the `rmath::bigint::BigInt` for the protocol implementations
+//! 
+//! The protocol code should use these wrappers rather than the rmath methods directly, the parameters are 
+//! checked and the results are well-defined(no `nan` returned).
+
+mod modular;
+pub use modular::{mod_exp_public, mod_inverse, mod_reduce, ct_mod_reduce, ct_mod_inverse};
+
+mod montgomery;
+pub use montgomery::MontgomeryContext;
+
+#[cfg(any(feature = "rsa", feature = "dsa", feature = "ec"))]
+mod secret_scalar;
+#[cfg(any(feature = "rsa", feature = "dsa", feature = "ec"))]
+pub(crate) use secret_scalar::SecretScalar;
+
+#[cfg(test)]
+mod numeric_test;
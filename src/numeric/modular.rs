@@ -0,0 +1,141 @@
+//! Modular arithmetic
+
+use rmath::bigint::BigInt;
+use crate::{CryptoError, CryptoErrorKind};
+use super::montgomery::MontgomeryContext;
+
+fn check_modulus(modulus: &BigInt) -> Result<(), CryptoError> {
+    if modulus.is_nan() || modulus <= &BigInt::from(1u32) {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The modulus must be great than 1"))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_operand(a: &BigInt, name: &str) -> Result<(), CryptoError> {
+    if a.is_nan() {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("The {} is nan", name)))
+    } else {
+        Ok(())
+    }
+}
+
+/// $base^{exponent} \mod modulus$, the `exponent` must be a non-negative public value(e.g. RSA public exponent,
+/// group order), because the time of computation depends on the `exponent`.
+pub fn mod_exp_public(base: &BigInt, exponent: &BigInt, modulus: &BigInt) -> Result<BigInt, CryptoError> {
+    check_modulus(modulus)?;
+    check_operand(base, "base")?;
+    check_operand(exponent, "exponent")?;
+    if exponent.is_negative() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The exponent must be non-negative"));
+    }
+    
+    let b = base.rem_euclid(modulus.clone());
+    Ok(b.exp(exponent, modulus))
+}
+
+/// $a^{-1} \mod modulus$, an error will be returned if the `a` is not invertible modulo the `modulus`.
+pub fn mod_inverse(a: &BigInt, modulus: &BigInt) -> Result<BigInt, CryptoError> {
+    check_modulus(modulus)?;
+    check_operand(a, "operand")?;
+    
+    let a = a.rem_euclid(modulus.clone());
+    let x = a.mod_inverse(modulus.clone());
+    if x.is_nan() || (a * x.clone()).rem_euclid(modulus.clone()) != 1u32 {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The operand is not invertible modulo the modulus"))
+    } else {
+        Ok(x)
+    }
+}
+
+/// $a \mod modulus$, the result belong to the range `[0, modulus)`
+pub fn mod_reduce(a: &BigInt, modulus: &BigInt) -> Result<BigInt, CryptoError> {
+    check_modulus(modulus)?;
+    check_operand(a, "operand")?;
+    
+    Ok(a.rem_euclid(modulus.clone()))
+}
+
+/// convert to the little-endian u32 limbs with the fixed length `len`
+pub(super) fn to_limbs(a: &BigInt, len: usize) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(len);
+    let bytes = a.to_le_bytes();
+    for chunk in bytes.chunks(4) {
+        let mut w = [0u8; 4];
+        w[..chunk.len()].copy_from_slice(chunk);
+        limbs.push(u32::from_le_bytes(w));
+    }
+    limbs.resize(len, 0);
+    limbs
+}
+
+/// convert from the little-endian u32 limbs
+pub(super) fn from_limbs(limbs: &[u32]) -> BigInt {
+    let mut bytes = Vec::with_capacity(limbs.len() << 2);
+    limbs.iter().for_each(|w| bytes.extend_from_slice(w.to_le_bytes().as_ref()));
+    BigInt::from_le_bytes(bytes.as_slice())
+}
+
+/// $a \mod modulus$ for the non-negative `a` with `a.bits_len() <= a_bits_bound`.
+/// 
+/// The reduction is the bit-serial shift-and-subtract over the fixed-width limbs, the sequence of the 
+/// operations only depends on the `a_bits_bound` and the bits length of the `modulus`, not the value of the `a`. 
+/// So it's suitable for reducing the secret value(e.g. the hash value of the nonce derivation in the signature 
+/// scheme) by the public modulus. Note that the conversions between the `BigInt` and the limbs are not 
+/// constant-time in the rmath.
+pub fn ct_mod_reduce(a: &BigInt, a_bits_bound: usize, modulus: &BigInt) -> Result<BigInt, CryptoError> {
+    check_modulus(modulus)?;
+    check_operand(a, "operand")?;
+    if a.is_negative() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The operand must be non-negative"));
+    }
+    if a.bits_len() > a_bits_bound {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+            format!("The bits length of operand great than the bound {}", a_bits_bound)));
+    }
+    
+    // one more limb to hold the `2r+1` before the subtraction
+    let len = ((modulus.bits_len() + 31) >> 5) + 1;
+    let (m, x) = (to_limbs(modulus, len), to_limbs(a, (a_bits_bound + 31) >> 5));
+    let (mut r, mut t) = (vec![0u32; len], vec![0u32; len]);
+    
+    for i in (0..a_bits_bound).rev() {
+        // r = 2r + bit_i(a)
+        let mut carry = (x[i >> 5] >> (i & 31)) & 1;
+        for w in r.iter_mut() {
+            let nc = *w >> 31;
+            *w = (*w << 1) | carry;
+            carry = nc;
+        }
+        
+        // t = r - m
+        let mut borrow = 0u64;
+        for ((tw, &rw), &mw) in t.iter_mut().zip(r.iter()).zip(m.iter()) {
+            let d = (rw as u64).wrapping_sub(mw as u64).wrapping_sub(borrow);
+            *tw = d as u32;
+            borrow = (d >> 63) & 1;
+        }
+        
+        // r = if borrow == 0 {t} else {r}
+        let mask = (borrow as u32).wrapping_sub(1);
+        for (rw, &tw) in r.iter_mut().zip(t.iter()) {
+            *rw = (tw & mask) | (*rw & !mask);
+        }
+    }
+    
+    Ok(from_limbs(r.as_slice()))
+}
+
+
+/// $a^{-1} \mod p$ for the odd prime modulus `p` and the `a` in the range `[1, p)`, see the
+/// `MontgomeryContext::ct_inverse_prime`.
+///
+/// The rmath `mod_inverse` is the extended Euclidean algorithm, the number of the iterations depends on the
+/// operand, so the secret values(e.g. the nonce of the ECDSA/DSA/SM2, the CRT coefficients of the RSA, the Z
+/// coordinate of the Jacobian point) should be inverted by this function. Note that the conversions between the
+/// `BigInt` and the limbs are not constant-time in the rmath.
+pub fn ct_mod_inverse(a: &BigInt, p: &BigInt) -> Result<BigInt, CryptoError> {
+    check_modulus(p)?;
+    check_operand(a, "operand")?;
+    MontgomeryContext::new(p)?.ct_inverse_prime(a)
+}
@@ -0,0 +1,154 @@
+//! Montgomery multiplication with the precomputed context
+//!
+//! The rmath `exp` computes the Montgomery parameters($-m^{-1} \mod 2^{32}$, $R^2 \mod m$) for every call,
+//! the `MontgomeryContext` computes them once for the fixed modulus, and can be reused by the repeatedly
+//! exponentiations(e.g. RSA public key operations of the high-rate verification service).
+
+use rmath::bigint::BigInt;
+use crate::{CryptoError, CryptoErrorKind};
+use super::modular::{to_limbs, from_limbs};
+
+/// The precomputed Montgomery context for the odd modulus `m`, $R = 2^{32 \cdot l}$, `l` is the number of limbs.
+#[derive(Clone)]
+pub struct MontgomeryContext {
+    // the little-endian u32 limbs of the modulus
+    m: Vec<u32>,
+    // $-m^{-1} \mod 2^{32}$
+    k0: u32,
+    // $R^2 \mod m$
+    rr: Vec<u32>,
+    // $R \mod m$, i.e. the Montgomery form of 1
+    one: Vec<u32>,
+}
+
+impl MontgomeryContext {
+    /// the `modulus` must be an odd number which is great than 1
+    pub fn new(modulus: &BigInt) -> Result<Self, CryptoError> {
+        if modulus.is_nan() || modulus <= &BigInt::from(1u32) || !modulus.is_set_bit(0).unwrap_or(false) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The modulus must be an odd number which is great than 1"));
+        }
+
+        let len = (modulus.bits_len() + 31) >> 5;
+        let m = to_limbs(modulus, len);
+
+        // Newton's iteration for the inverse modulo the power of 2: $x_{i+1} = x_i (2 - m x_i)$,
+        // the initial value `m` is correct for the lowest 3 bits.
+        let mut inv = m[0];
+        for _ in 0..4 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(m[0].wrapping_mul(inv)));
+        }
+
+        let r = BigInt::from(1u32) << (len << 5);
+        let rr = (r.clone() * r.clone()).rem_euclid(modulus.clone());
+        let one = r.rem_euclid(modulus.clone());
+
+        Ok(Self {
+            k0: inv.wrapping_neg(),
+            rr: to_limbs(&rr, len),
+            one: to_limbs(&one, len),
+            m,
+        })
+    }
+
+    pub fn modulus(&self) -> BigInt {
+        from_limbs(self.m.as_slice())
+    }
+
+    /// $x \cdot y \cdot R^{-1} \mod m$ for $x, y < m$, CIOS method of the Koc, "Analyzing and Comparing Montgomery
+    /// Multiplication Algorithms".
+    fn mul(&self, x: &[u32], y: &[u32]) -> Vec<u32> {
+        let (n, m) = (self.m.len(), self.m.as_slice());
+        let mut t = vec![0u32; n + 2];
+
+        for &yi in y.iter() {
+            let mut c = 0u64;
+            for (tj, &xj) in t.iter_mut().zip(x.iter()) {
+                let s = (*tj as u64) + (xj as u64) * (yi as u64) + c;
+                *tj = s as u32;
+                c = s >> 32;
+            }
+            let s = (t[n] as u64) + c;
+            t[n] = s as u32;
+            t[n + 1] = (s >> 32) as u32;
+
+            let u = t[0].wrapping_mul(self.k0);
+            let mut c = ((t[0] as u64) + (u as u64) * (m[0] as u64)) >> 32;
+            for j in 1..n {
+                let s = (t[j] as u64) + (u as u64) * (m[j] as u64) + c;
+                t[j - 1] = s as u32;
+                c = s >> 32;
+            }
+            let s = (t[n] as u64) + c;
+            t[n - 1] = s as u32;
+            t[n] = t[n + 1] + ((s >> 32) as u32);
+        }
+
+        // t < 2m
+        let mut d = vec![0u32; n];
+        let mut borrow = 0u64;
+        for ((dj, &tj), &mj) in d.iter_mut().zip(t.iter()).zip(m.iter()) {
+            let s = (tj as u64).wrapping_sub(mj as u64).wrapping_sub(borrow);
+            *dj = s as u32;
+            borrow = (s >> 63) & 1;
+        }
+
+        // t = if t[n] == 0 && borrow != 0 {t} else {t - m}, selected by the mask rather than the branch, so the
+        // time doesn't depend on the operands
+        let mask = 0u32.wrapping_sub(((t[n] == 0) as u32) & (borrow as u32));
+        for (dj, &tj) in d.iter_mut().zip(t.iter()) {
+            *dj = (tj & mask) | (*dj & !mask);
+        }
+        d
+    }
+
+    /// $x^{exponent} \cdot R \mod m$ for the Montgomery form `x`, the sequence of the multiplications only depends
+    /// on the `exponent`.
+    fn exp_limbs(&self, x: &[u32], exponent: &BigInt) -> Vec<u32> {
+        let mut z = self.one.clone();
+        for i in (0..exponent.bits_len()).rev() {
+            z = self.mul(z.as_slice(), z.as_slice());
+            if exponent.is_set_bit(i) == Some(true) {
+                z = self.mul(z.as_slice(), x);
+            }
+        }
+        z
+    }
+
+    /// convert from the Montgomery form
+    fn reduce(&self, z: &[u32]) -> BigInt {
+        let mut one = vec![0u32; self.m.len()];
+        one[0] = 1;
+        from_limbs(self.mul(z, one.as_slice()).as_slice())
+    }
+
+    /// $base^{exponent} \mod m$, the `exponent` must be a non-negative public value, because the
+    /// time of computation depends on the `exponent`.
+    pub fn exp_public(&self, base: &BigInt, exponent: &BigInt) -> Result<BigInt, CryptoError> {
+        if base.is_nan() || exponent.is_nan() || exponent.is_negative() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The base must be a number, and the exponent must be non-negative"));
+        }
+
+        let x = to_limbs(&base.rem_euclid(self.modulus()), self.m.len());
+        let x = self.mul(x.as_slice(), self.rr.as_slice());
+        Ok(self.reduce(self.exp_limbs(x.as_slice(), exponent).as_slice()))
+    }
+
+    /// $a^{-1} \mod m$ for the prime modulus `m` by the Fermat's little theorem $a^{m-2} \mod m$, the `a` must belong
+    /// to the range `[1, m)`.
+    ///
+    /// The sequence of the Montgomery multiplications only depends on the public exponent $m-2$, and the
+    /// multiplication has no branch on the operands, so the time doesn't depend on the secret `a`(e.g. the nonce of
+    /// the signature, the prime factor of the RSA key). The primality of the `m` isn't checked, the result is wrong
+    /// for the composite modulus.
+    pub fn ct_inverse_prime(&self, a: &BigInt) -> Result<BigInt, CryptoError> {
+        let m = self.modulus();
+        if a.is_nan() || a.signnum() != Some(1) || a >= &m {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The operand must belong to the range [1, modulus)"));
+        }
+
+        let x = to_limbs(a, self.m.len());
+        let x = self.mul(x.as_slice(), self.rr.as_slice());
+        let e = m - BigInt::from(2u32);
+        Ok(self.reduce(self.exp_limbs(x.as_slice(), &e).as_slice()))
+    }
+}
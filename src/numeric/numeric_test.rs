@@ -0,0 +1,113 @@
+use crate::numeric::{mod_exp_public, mod_inverse, mod_reduce, ct_mod_reduce, ct_mod_inverse, MontgomeryContext};
+use rmath::bigint::BigInt;
+use rmath::rand::{DefaultSeed, CryptoRand};
+use std::str::FromStr;
+
+#[test]
+fn modular_arithmetic() {
+    let p = BigInt::from_str("57896044618658097711785492504343953926634992332820282019728792003956564819949").unwrap();
+    let a = BigInt::from_str("1234567891011121314151617181920").unwrap();
+    
+    // computed by the Python pow
+    let inv = mod_inverse(&a, &p).unwrap();
+    assert_eq!(inv, BigInt::from_str("57661594741343551081634055577461507217556615538486942397979846692474169491667").unwrap());
+    let e = mod_exp_public(&a, &BigInt::from(65537u32), &p).unwrap();
+    assert_eq!(e, BigInt::from_str("7038930709727423187263581847317414873806525574958042603283898172223052687343").unwrap());
+    
+    // negative operand is reduced first
+    let neg = BigInt::from(0u32) - a.clone();
+    assert_eq!(mod_reduce(&neg, &p).unwrap(), p.clone() - a.clone());
+    assert_eq!((mod_inverse(&neg, &p).unwrap() + inv.clone()).rem_euclid(p.clone()), 0u32);
+    
+    assert!(mod_inverse(&BigInt::from(6u32), &BigInt::from(9u32)).is_err());
+    assert!(mod_inverse(&BigInt::from(0u32), &p).is_err());
+    assert!(mod_exp_public(&a, &neg, &p).is_err());
+    assert!(mod_reduce(&a, &BigInt::from(1u32)).is_err());
+}
+
+#[test]
+fn ct_modular_reduction() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let p = BigInt::from_str("0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551").unwrap();
+    let bound = BigInt::from(1u32) << 512usize;
+    
+    for i in 0..64 {
+        let a = bound.random(&mut rd);
+        assert_eq!(ct_mod_reduce(&a, 512, &p).unwrap(), a.rem_euclid(p.clone()), "case: {}", i);
+    }
+    
+    let m = BigInt::from(0x10001u32);
+    for &x in [0u32, 1, 0x10000, 0x10001, 0x10002, u32::MAX].iter() {
+        let a = BigInt::from(x);
+        assert_eq!(ct_mod_reduce(&a, 32, &m).unwrap(), BigInt::from(x % 0x10001), "case: {}", x);
+    }
+    
+    assert!(ct_mod_reduce(&bound, 512, &p).is_err());
+}
+
+#[test]
+fn montgomery_context() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let p = BigInt::from_str("57896044618658097711785492504343953926634992332820282019728792003956564819949").unwrap();
+    let a = BigInt::from_str("1234567891011121314151617181920").unwrap();
+    
+    let ctx = MontgomeryContext::new(&p).unwrap();
+    assert_eq!(ctx.modulus(), p);
+    assert_eq!(ctx.exp_public(&a, &BigInt::from(65537u32)).unwrap(), 
+        BigInt::from_str("7038930709727423187263581847317414873806525574958042603283898172223052687343").unwrap());
+    
+    for &bits in [31usize, 32, 33, 64, 521, 1024, 2048].iter() {
+        let m = (BigInt::from(1u32) << bits).random(&mut rd) | BigInt::from(1u32);
+        let m = if m == 1u32 { BigInt::from(3u32) } else { m };
+        let ctx = MontgomeryContext::new(&m).unwrap();
+        for &e in [0u32, 1, 2, 3, 65537, u32::MAX].iter() {
+            let (x, e) = (m.random(&mut rd), BigInt::from(e));
+            assert_eq!(ctx.exp_public(&x, &e).unwrap(), x.exp(&e, &m).rem_euclid(m.clone()), "case: {}, {}", bits, e);
+        }
+        // the base is reduced first
+        let x = m.clone() * BigInt::from(5u32) + BigInt::from(7u32);
+        assert_eq!(ctx.exp_public(&x, &BigInt::from(3u32)).unwrap(), BigInt::from(343u32).rem_euclid(m.clone()));
+    }
+    
+    assert!(MontgomeryContext::new(&BigInt::from(1u32)).is_err());
+    assert!(MontgomeryContext::new(&BigInt::from(0x10000u32)).is_err());
+    assert!(ctx.exp_public(&a, &(BigInt::from(0u32) - a.clone())).is_err());
+}
+
+
+#[test]
+fn ct_modular_inverse() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let primes = [
+        "3", "65537", "4294967291",
+        "57896044618658097711785492504343953926634992332820282019728792003956564819949",
+        "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+    ];
+    // the Mersenne prime $2^{521} - 1$
+    let p521 = (BigInt::from(1u32) << 521usize) - BigInt::from(1u32);
+    
+    for p in primes.iter().map(|p| BigInt::from_str(p).unwrap()).chain(std::iter::once(p521)) {
+        let ctx = MontgomeryContext::new(&p).unwrap();
+        for i in 0..16 {
+            let a = p.random(&mut rd);
+            let a = if a == 0u32 { BigInt::from(1u32) } else { a };
+            let inv = ct_mod_inverse(&a, &p).unwrap();
+            assert_eq!(inv, mod_inverse(&a, &p).unwrap(), "case: {}, {}", p, i);
+            assert_eq!(ctx.ct_inverse_prime(&a).unwrap(), inv);
+        }
+        let pm1 = p.clone() - BigInt::from(1u32);
+        assert_eq!(ct_mod_inverse(&pm1, &p).unwrap(), pm1);
+        assert_eq!(ct_mod_inverse(&BigInt::from(1u32), &p).unwrap(), 1u32);
+        
+        // the operand isn't reduced
+        assert!(ct_mod_inverse(&BigInt::from(0u32), &p).is_err());
+        assert!(ct_mod_inverse(&p, &p).is_err());
+        assert!(ct_mod_inverse(&(BigInt::from(0u32) - pm1), &p).is_err());
+    }
+    
+    assert!(ct_mod_inverse(&BigInt::from(1u32), &BigInt::from(2u32)).is_err());
+    assert!(ct_mod_inverse(&BigInt::from(1u32), &BigInt::from(1u32)).is_err());
+}
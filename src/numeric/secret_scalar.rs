@@ -0,0 +1,70 @@
+use rmath::bigint::BigInt;
+
+/// The secret big integer of the private keys(e.g. the private exponent, the prime factors), it's stored in the
+/// locked memory(`secret::SecretBytes`) with the `mlock` feature. The `get` returns the copy for the arithmetic,
+/// the copy is allocated by the `rmath` and isn't locked.
+pub(crate) struct SecretScalar {
+    // the big-endian bytes, `None` for the nan, boxed to keep the key types small
+    #[cfg(feature = "mlock")]
+    val: Option<Box<crate::secret::SecretBytes>>,
+    #[cfg(not(feature = "mlock"))]
+    val: BigInt,
+}
+
+impl SecretScalar {
+    #[cfg(feature = "mlock")]
+    pub(crate) fn new(x: &BigInt) -> Self {
+        if x.is_nan() {
+            return Self { val: None };
+        }
+
+        let mut b = x.to_be_bytes();
+        if b.is_empty() {
+            b.push(0);
+        }
+        Self { val: Some(Box::new(crate::secret::SecretBytes::from_vec_or_abort(b))) }
+    }
+
+    #[cfg(not(feature = "mlock"))]
+    pub(crate) fn new(x: &BigInt) -> Self {
+        Self { val: x.deep_clone() }
+    }
+
+    #[cfg(feature = "mlock")]
+    pub(crate) fn get(&self) -> BigInt {
+        match self.val.as_ref() {
+            Some(b) => BigInt::from_be_bytes(b.as_slice()),
+            None => BigInt::from_be_bytes(&[]),
+        }
+    }
+
+    #[cfg(not(feature = "mlock"))]
+    pub(crate) fn get(&self) -> BigInt {
+        self.val.deep_clone()
+    }
+
+    #[cfg(all(feature = "rsa", feature = "mlock"))]
+    pub(crate) fn is_nan(&self) -> bool {
+        self.val.is_none()
+    }
+
+    #[cfg(all(feature = "rsa", not(feature = "mlock")))]
+    pub(crate) fn is_nan(&self) -> bool {
+        self.val.is_nan()
+    }
+
+    /// the big-endian bytes in the new locked memory
+    #[cfg(feature = "mlock")]
+    pub(crate) fn to_secret_bytes(&self) -> Result<crate::secret::SecretBytes, crate::CryptoError> {
+        match self.val.as_ref() {
+            Some(b) => b.try_clone(),
+            None => crate::secret::SecretBytes::new(0),
+        }
+    }
+}
+
+impl Clone for SecretScalar {
+    fn clone(&self) -> Self {
+        Self::new(&self.get())
+    }
+}
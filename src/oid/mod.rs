@@ -0,0 +1,15 @@
+//! The object identifiers of the algorithms
+//! ITU-T X.660, X.690 8.19(the encoding of the object identifier value)
+//!
+//! The `Oid` constants of the digests, MACs, public key algorithms, signature schemes, named curves, ciphers and key
+//! derivation functions of this crate, each constant contains the name, the dotted decimal string and the DER content
+//! octets. The DER, certificate, SPKI and EVP layers of the crate use these constants, so the
+//! external identifiers(e.g. the `AlgorithmIdentifier` of the X.509 certificate, the OID of the OpenSSL/PKCS#11 objects)
+//! can be matched by `Oid::from_der`/`Oid::from_dotted`. The `oid_to_dotted` and `oid_from_dotted` convert any object
+//! identifier.
+
+mod oid;
+pub use oid::{Oid, oid_to_dotted, oid_from_dotted};
+
+#[cfg(test)]
+mod oid_test;
\ No newline at end of file
@@ -0,0 +1,336 @@
+use std::fmt::{Display, Formatter};
+use crate::{CryptoError, CryptoErrorKind};
+
+/// The object identifier of the algorithm, it contains the name(the ASN.1 value name or the OpenSSL long name),
+/// the dotted decimal string and the DER encoded content octets(without the tag and the length).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Oid {
+    name: &'static str,
+    dotted: &'static str,
+    der: &'static [u8],
+}
+
+impl Oid {
+    // the digests(RFC 8017 A.2.4, NIST CSOR, GM/T 0006)
+    pub const MD5: Oid = Oid::new("md5", "1.2.840.113549.2.5", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x05]);
+    pub const SHA1: Oid = Oid::new("sha1", "1.3.14.3.2.26", &[0x2b, 0x0e, 0x03, 0x02, 0x1a]);
+    pub const SHA224: Oid = Oid::new("sha224", "2.16.840.1.101.3.4.2.4", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04]);
+    pub const SHA256: Oid = Oid::new("sha256", "2.16.840.1.101.3.4.2.1", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01]);
+    pub const SHA384: Oid = Oid::new("sha384", "2.16.840.1.101.3.4.2.2", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02]);
+    pub const SHA512: Oid = Oid::new("sha512", "2.16.840.1.101.3.4.2.3", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03]);
+    pub const SHA512_224: Oid = Oid::new("sha512-224", "2.16.840.1.101.3.4.2.5", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x05]);
+    pub const SHA512_256: Oid = Oid::new("sha512-256", "2.16.840.1.101.3.4.2.6", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x06]);
+    pub const SHA3_224: Oid = Oid::new("sha3-224", "2.16.840.1.101.3.4.2.7", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x07]);
+    pub const SHA3_256: Oid = Oid::new("sha3-256", "2.16.840.1.101.3.4.2.8", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x08]);
+    pub const SHA3_384: Oid = Oid::new("sha3-384", "2.16.840.1.101.3.4.2.9", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x09]);
+    pub const SHA3_512: Oid = Oid::new("sha3-512", "2.16.840.1.101.3.4.2.10", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0a]);
+    pub const SHAKE128: Oid = Oid::new("shake128", "2.16.840.1.101.3.4.2.11", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0b]);
+    pub const SHAKE256: Oid = Oid::new("shake256", "2.16.840.1.101.3.4.2.12", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0c]);
+    pub const SM3: Oid = Oid::new("sm3", "1.2.156.10197.1.401", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x83, 0x11]);
+
+    // the MACs(RFC 8018 B.1, RFC 8702)
+    pub const HMAC_SHA1: Oid = Oid::new("hmacWithSHA1", "1.2.840.113549.2.7", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x07]);
+    pub const HMAC_SHA224: Oid = Oid::new("hmacWithSHA224", "1.2.840.113549.2.8", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x08]);
+    pub const HMAC_SHA256: Oid = Oid::new("hmacWithSHA256", "1.2.840.113549.2.9", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x09]);
+    pub const HMAC_SHA384: Oid = Oid::new("hmacWithSHA384", "1.2.840.113549.2.10", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x0a]);
+    pub const HMAC_SHA512: Oid = Oid::new("hmacWithSHA512", "1.2.840.113549.2.11", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x0b]);
+    pub const KMAC128: Oid = Oid::new("id-KMACWithSHAKE128", "2.16.840.1.101.3.4.2.19", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x13]);
+    pub const KMAC256: Oid = Oid::new("id-KMACWithSHAKE256", "2.16.840.1.101.3.4.2.20", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x14]);
+
+    // the public key algorithms and the signature schemes(RFC 8017 A.1/A.2, RFC 3279, RFC 5480, RFC 5758, RFC 8410, GM/T 0006)
+    pub const RSA_ENCRYPTION: Oid = Oid::new("rsaEncryption", "1.2.840.113549.1.1.1", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01]);
+    pub const RSAES_OAEP: Oid = Oid::new("id-RSAES-OAEP", "1.2.840.113549.1.1.7", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x07]);
+    pub const MGF1: Oid = Oid::new("id-mgf1", "1.2.840.113549.1.1.8", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x08]);
+    pub const RSASSA_PSS: Oid = Oid::new("id-RSASSA-PSS", "1.2.840.113549.1.1.10", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0a]);
+    pub const MD5_WITH_RSA: Oid = Oid::new("md5WithRSAEncryption", "1.2.840.113549.1.1.4", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x04]);
+    pub const SHA1_WITH_RSA: Oid = Oid::new("sha1WithRSAEncryption", "1.2.840.113549.1.1.5", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x05]);
+    pub const SHA224_WITH_RSA: Oid = Oid::new("sha224WithRSAEncryption", "1.2.840.113549.1.1.14", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0e]);
+    pub const SHA256_WITH_RSA: Oid = Oid::new("sha256WithRSAEncryption", "1.2.840.113549.1.1.11", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b]);
+    pub const SHA384_WITH_RSA: Oid = Oid::new("sha384WithRSAEncryption", "1.2.840.113549.1.1.12", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c]);
+    pub const SHA512_WITH_RSA: Oid = Oid::new("sha512WithRSAEncryption", "1.2.840.113549.1.1.13", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d]);
+    pub const SHA3_256_WITH_RSA: Oid = Oid::new("id-rsassa-pkcs1-v1_5-with-sha3-256", "2.16.840.1.101.3.4.3.14", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x0e]);
+    pub const SHA3_384_WITH_RSA: Oid = Oid::new("id-rsassa-pkcs1-v1_5-with-sha3-384", "2.16.840.1.101.3.4.3.15", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x0f]);
+    pub const SHA3_512_WITH_RSA: Oid = Oid::new("id-rsassa-pkcs1-v1_5-with-sha3-512", "2.16.840.1.101.3.4.3.16", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x10]);
+    pub const DSA: Oid = Oid::new("id-dsa", "1.2.840.10040.4.1", &[0x2a, 0x86, 0x48, 0xce, 0x38, 0x04, 0x01]);
+    pub const DSA_WITH_SHA1: Oid = Oid::new("id-dsa-with-sha1", "1.2.840.10040.4.3", &[0x2a, 0x86, 0x48, 0xce, 0x38, 0x04, 0x03]);
+    pub const DSA_WITH_SHA224: Oid = Oid::new("id-dsa-with-sha224", "2.16.840.1.101.3.4.3.1", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x01]);
+    pub const DSA_WITH_SHA256: Oid = Oid::new("id-dsa-with-sha256", "2.16.840.1.101.3.4.3.2", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x02]);
+    pub const DH_PUBLIC_NUMBER: Oid = Oid::new("dhpublicnumber", "1.2.840.10046.2.1", &[0x2a, 0x86, 0x48, 0xce, 0x3e, 0x02, 0x01]);
+    pub const EC_PUBLIC_KEY: Oid = Oid::new("id-ecPublicKey", "1.2.840.10045.2.1", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01]);
+    pub const ECDSA_WITH_SHA1: Oid = Oid::new("ecdsa-with-SHA1", "1.2.840.10045.4.1", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x01]);
+    pub const ECDSA_WITH_SHA224: Oid = Oid::new("ecdsa-with-SHA224", "1.2.840.10045.4.3.1", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x01]);
+    pub const ECDSA_WITH_SHA256: Oid = Oid::new("ecdsa-with-SHA256", "1.2.840.10045.4.3.2", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02]);
+    pub const ECDSA_WITH_SHA384: Oid = Oid::new("ecdsa-with-SHA384", "1.2.840.10045.4.3.3", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03]);
+    pub const ECDSA_WITH_SHA512: Oid = Oid::new("ecdsa-with-SHA512", "1.2.840.10045.4.3.4", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04]);
+    pub const ECDSA_WITH_SHA3_256: Oid = Oid::new("id-ecdsa-with-sha3-256", "2.16.840.1.101.3.4.3.10", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x0a]);
+    pub const ECDSA_WITH_SHA3_384: Oid = Oid::new("id-ecdsa-with-sha3-384", "2.16.840.1.101.3.4.3.11", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x0b]);
+    pub const ECDSA_WITH_SHA3_512: Oid = Oid::new("id-ecdsa-with-sha3-512", "2.16.840.1.101.3.4.3.12", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x0c]);
+    pub const SM2_WITH_SM3: Oid = Oid::new("SM2-with-SM3", "1.2.156.10197.1.501", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x83, 0x75]);
+    pub const X25519: Oid = Oid::new("id-X25519", "1.3.101.110", &[0x2b, 0x65, 0x6e]);
+
+    // the named curves(RFC 5480 2.1.1.1, SEC 2 A.2.1, RFC 5639 4.1, GM/T 0006)
+    pub const P224: Oid = Oid::new("secp224r1", "1.3.132.0.33", &[0x2b, 0x81, 0x04, 0x00, 0x21]);
+    pub const P256: Oid = Oid::new("prime256v1", "1.2.840.10045.3.1.7", &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07]);
+    pub const P384: Oid = Oid::new("secp384r1", "1.3.132.0.34", &[0x2b, 0x81, 0x04, 0x00, 0x22]);
+    pub const P521: Oid = Oid::new("secp521r1", "1.3.132.0.35", &[0x2b, 0x81, 0x04, 0x00, 0x23]);
+    pub const SECP256K1: Oid = Oid::new("secp256k1", "1.3.132.0.10", &[0x2b, 0x81, 0x04, 0x00, 0x0a]);
+    pub const BRAINPOOL_P256R1: Oid = Oid::new("brainpoolP256r1", "1.3.36.3.3.2.8.1.1.7", &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x07]);
+    pub const BRAINPOOL_P384R1: Oid = Oid::new("brainpoolP384r1", "1.3.36.3.3.2.8.1.1.11", &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0b]);
+    pub const BRAINPOOL_P512R1: Oid = Oid::new("brainpoolP512r1", "1.3.36.3.3.2.8.1.1.13", &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0d]);
+    pub const SM2P256V1: Oid = Oid::new("sm2p256v1", "1.2.156.10197.1.301", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x82, 0x2d]);
+
+    // the ciphers and the AEAD(NIST CSOR, RFC 3565, RFC 5084, RFC 8103, GM/T 0006)
+    pub const AES128_ECB: Oid = Oid::new("aes-128-ecb", "2.16.840.1.101.3.4.1.1", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x01]);
+    pub const AES128_CBC: Oid = Oid::new("aes-128-cbc", "2.16.840.1.101.3.4.1.2", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x02]);
+    pub const AES128_OFB: Oid = Oid::new("aes-128-ofb", "2.16.840.1.101.3.4.1.3", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x03]);
+    pub const AES128_WRAP: Oid = Oid::new("id-aes128-wrap", "2.16.840.1.101.3.4.1.5", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x05]);
+    pub const AES128_GCM: Oid = Oid::new("id-aes128-GCM", "2.16.840.1.101.3.4.1.6", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x06]);
+    pub const AES128_CCM: Oid = Oid::new("id-aes128-CCM", "2.16.840.1.101.3.4.1.7", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x07]);
+    pub const AES192_ECB: Oid = Oid::new("aes-192-ecb", "2.16.840.1.101.3.4.1.21", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x15]);
+    pub const AES192_CBC: Oid = Oid::new("aes-192-cbc", "2.16.840.1.101.3.4.1.22", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x16]);
+    pub const AES192_OFB: Oid = Oid::new("aes-192-ofb", "2.16.840.1.101.3.4.1.23", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x17]);
+    pub const AES192_WRAP: Oid = Oid::new("id-aes192-wrap", "2.16.840.1.101.3.4.1.25", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x19]);
+    pub const AES192_GCM: Oid = Oid::new("id-aes192-GCM", "2.16.840.1.101.3.4.1.26", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x1a]);
+    pub const AES192_CCM: Oid = Oid::new("id-aes192-CCM", "2.16.840.1.101.3.4.1.27", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x1b]);
+    pub const AES256_ECB: Oid = Oid::new("aes-256-ecb", "2.16.840.1.101.3.4.1.41", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x29]);
+    pub const AES256_CBC: Oid = Oid::new("aes-256-cbc", "2.16.840.1.101.3.4.1.42", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2a]);
+    pub const AES256_OFB: Oid = Oid::new("aes-256-ofb", "2.16.840.1.101.3.4.1.43", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2b]);
+    pub const AES256_WRAP: Oid = Oid::new("id-aes256-wrap", "2.16.840.1.101.3.4.1.45", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2d]);
+    pub const AES256_GCM: Oid = Oid::new("id-aes256-GCM", "2.16.840.1.101.3.4.1.46", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2e]);
+    pub const AES256_CCM: Oid = Oid::new("id-aes256-CCM", "2.16.840.1.101.3.4.1.47", &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2f]);
+    pub const DES_ECB: Oid = Oid::new("des-ecb", "1.3.14.3.2.6", &[0x2b, 0x0e, 0x03, 0x02, 0x06]);
+    pub const DES_CBC: Oid = Oid::new("des-cbc", "1.3.14.3.2.7", &[0x2b, 0x0e, 0x03, 0x02, 0x07]);
+    pub const DES_OFB: Oid = Oid::new("des-ofb", "1.3.14.3.2.8", &[0x2b, 0x0e, 0x03, 0x02, 0x08]);
+    pub const DES_EDE3_CBC: Oid = Oid::new("des-ede3-cbc", "1.2.840.113549.3.7", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x03, 0x07]);
+    pub const SM4_ECB: Oid = Oid::new("sm4-ecb", "1.2.156.10197.1.104.1", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x68, 0x01]);
+    pub const SM4_CBC: Oid = Oid::new("sm4-cbc", "1.2.156.10197.1.104.2", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x68, 0x02]);
+    pub const SM4_OFB: Oid = Oid::new("sm4-ofb", "1.2.156.10197.1.104.3", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x68, 0x03]);
+    pub const SM4_CTR: Oid = Oid::new("sm4-ctr", "1.2.156.10197.1.104.7", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x68, 0x07]);
+    pub const SM4_GCM: Oid = Oid::new("sm4-gcm", "1.2.156.10197.1.104.8", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x68, 0x08]);
+    pub const SM4_CCM: Oid = Oid::new("sm4-ccm", "1.2.156.10197.1.104.9", &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x68, 0x09]);
+    pub const CHACHA20_POLY1305: Oid = Oid::new("id-alg-AEADChaCha20Poly1305", "1.2.840.113549.1.9.16.3.18", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x03, 0x12]);
+
+    // the key derivation functions(RFC 8018 A.2/A.4, RFC 7914 7, RFC 8619)
+    pub const PBKDF2: Oid = Oid::new("id-PBKDF2", "1.2.840.113549.1.5.12", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0c]);
+    pub const PBES2: Oid = Oid::new("id-PBES2", "1.2.840.113549.1.5.13", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0d]);
+    pub const SCRYPT: Oid = Oid::new("id-scrypt", "1.3.6.1.4.1.11591.4.11", &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xda, 0x47, 0x04, 0x0b]);
+    pub const HKDF_SHA256: Oid = Oid::new("id-alg-hkdf-with-sha256", "1.2.840.113549.1.9.16.3.28", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x03, 0x1c]);
+    pub const HKDF_SHA384: Oid = Oid::new("id-alg-hkdf-with-sha384", "1.2.840.113549.1.9.16.3.29", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x03, 0x1d]);
+    pub const HKDF_SHA512: Oid = Oid::new("id-alg-hkdf-with-sha512", "1.2.840.113549.1.9.16.3.30", &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x03, 0x1e]);
+
+    const fn new(name: &'static str, dotted: &'static str, der: &'static [u8]) -> Self {
+        Self { name, dotted, der }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// the dotted decimal string, e.g. `2.16.840.1.101.3.4.2.1`
+    pub const fn dotted(&self) -> &'static str {
+        self.dotted
+    }
+
+    /// the DER content octets of the `OBJECT IDENTIFIER`, e.g. `60 86 48 01 65 03 04 02 01`
+    pub const fn as_der(&self) -> &'static [u8] {
+        self.der
+    }
+
+    /// all the object identifiers of the crate
+    pub fn all() -> impl Iterator<Item = Oid> {
+        ALL_OIDS.iter().copied()
+    }
+
+    /// find the object identifier by the DER content octets
+    pub fn from_der(der: &[u8]) -> Option<Oid> {
+        ALL_OIDS.iter().find(|x| x.der == der).copied()
+    }
+
+    /// find the object identifier by the dotted decimal string
+    pub fn from_dotted(dotted: &str) -> Option<Oid> {
+        ALL_OIDS.iter().find(|x| x.dotted == dotted).copied()
+    }
+
+    /// find the object identifier by the name, the name is case sensitive, e.g. `sha256WithRSAEncryption`
+    pub fn from_name(name: &str) -> Option<Oid> {
+        ALL_OIDS.iter().find(|x| x.name == name).copied()
+    }
+}
+
+impl Display for Oid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", self.name, self.dotted)
+    }
+}
+
+const ALL_OIDS: [Oid; 95] = [
+    Oid::MD5,
+    Oid::SHA1,
+    Oid::SHA224,
+    Oid::SHA256,
+    Oid::SHA384,
+    Oid::SHA512,
+    Oid::SHA512_224,
+    Oid::SHA512_256,
+    Oid::SHA3_224,
+    Oid::SHA3_256,
+    Oid::SHA3_384,
+    Oid::SHA3_512,
+    Oid::SHAKE128,
+    Oid::SHAKE256,
+    Oid::SM3,
+    Oid::HMAC_SHA1,
+    Oid::HMAC_SHA224,
+    Oid::HMAC_SHA256,
+    Oid::HMAC_SHA384,
+    Oid::HMAC_SHA512,
+    Oid::KMAC128,
+    Oid::KMAC256,
+    Oid::RSA_ENCRYPTION,
+    Oid::RSAES_OAEP,
+    Oid::MGF1,
+    Oid::RSASSA_PSS,
+    Oid::MD5_WITH_RSA,
+    Oid::SHA1_WITH_RSA,
+    Oid::SHA224_WITH_RSA,
+    Oid::SHA256_WITH_RSA,
+    Oid::SHA384_WITH_RSA,
+    Oid::SHA512_WITH_RSA,
+    Oid::SHA3_256_WITH_RSA,
+    Oid::SHA3_384_WITH_RSA,
+    Oid::SHA3_512_WITH_RSA,
+    Oid::DSA,
+    Oid::DSA_WITH_SHA1,
+    Oid::DSA_WITH_SHA224,
+    Oid::DSA_WITH_SHA256,
+    Oid::DH_PUBLIC_NUMBER,
+    Oid::EC_PUBLIC_KEY,
+    Oid::ECDSA_WITH_SHA1,
+    Oid::ECDSA_WITH_SHA224,
+    Oid::ECDSA_WITH_SHA256,
+    Oid::ECDSA_WITH_SHA384,
+    Oid::ECDSA_WITH_SHA512,
+    Oid::ECDSA_WITH_SHA3_256,
+    Oid::ECDSA_WITH_SHA3_384,
+    Oid::ECDSA_WITH_SHA3_512,
+    Oid::SM2_WITH_SM3,
+    Oid::X25519,
+    Oid::P224,
+    Oid::P256,
+    Oid::P384,
+    Oid::P521,
+    Oid::SECP256K1,
+    Oid::BRAINPOOL_P256R1,
+    Oid::BRAINPOOL_P384R1,
+    Oid::BRAINPOOL_P512R1,
+    Oid::SM2P256V1,
+    Oid::AES128_ECB,
+    Oid::AES128_CBC,
+    Oid::AES128_OFB,
+    Oid::AES128_WRAP,
+    Oid::AES128_GCM,
+    Oid::AES128_CCM,
+    Oid::AES192_ECB,
+    Oid::AES192_CBC,
+    Oid::AES192_OFB,
+    Oid::AES192_WRAP,
+    Oid::AES192_GCM,
+    Oid::AES192_CCM,
+    Oid::AES256_ECB,
+    Oid::AES256_CBC,
+    Oid::AES256_OFB,
+    Oid::AES256_WRAP,
+    Oid::AES256_GCM,
+    Oid::AES256_CCM,
+    Oid::DES_ECB,
+    Oid::DES_CBC,
+    Oid::DES_OFB,
+    Oid::DES_EDE3_CBC,
+    Oid::SM4_ECB,
+    Oid::SM4_CBC,
+    Oid::SM4_OFB,
+    Oid::SM4_CTR,
+    Oid::SM4_GCM,
+    Oid::SM4_CCM,
+    Oid::CHACHA20_POLY1305,
+    Oid::PBKDF2,
+    Oid::PBES2,
+    Oid::SCRYPT,
+    Oid::HKDF_SHA256,
+    Oid::HKDF_SHA384,
+    Oid::HKDF_SHA512,
+];
+
+fn invalid_oid<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid object identifier: {}", msg)))
+}
+
+/// convert the DER content octets of any `OBJECT IDENTIFIER` to the dotted decimal string, the arcs must be the minimal
+/// base-128 encoding and less than $2^{64}$.
+pub fn oid_to_dotted(der: &[u8]) -> Result<String, CryptoError> {
+    if der.is_empty() || der[der.len() - 1] & 0x80 != 0 {
+        return invalid_oid("the last arc is truncated");
+    }
+
+    let mut arcs = Vec::new();
+    let mut arc = 0u64;
+    for (i, &b) in der.iter().enumerate() {
+        let is_first = i == 0 || der[i - 1] & 0x80 == 0;
+        if is_first && b == 0x80 {
+            return invalid_oid("the arc is not the minimal encoding");
+        }
+        if arc >> 57 != 0 {
+            return invalid_oid("the arc is too large");
+        }
+
+        arc = (arc << 7) | ((b & 0x7f) as u64);
+        if b & 0x80 == 0 {
+            arcs.push(arc);
+            arc = 0;
+        }
+    }
+
+    // the first subidentifier is `40 * arc0 + arc1`, the arc1 of the arc0 2 is unbounded
+    let (first, second) = match arcs[0] {
+        x if x < 40 => (0, x),
+        x if x < 80 => (1, x - 40),
+        x => (2, x - 80),
+    };
+    let mut dotted = format!("{}.{}", first, second);
+    arcs.iter().skip(1).for_each(|x| dotted.push_str(format!(".{}", x).as_str()));
+    Ok(dotted)
+}
+
+/// convert the dotted decimal string of any `OBJECT IDENTIFIER` to the DER content octets, the OID has at least two
+/// arcs, the first arc is 0, 1 or 2, and the second arc is less than 40 if the first arc is 0 or 1.
+pub fn oid_from_dotted(dotted: &str) -> Result<Vec<u8>, CryptoError> {
+    let mut arcs = Vec::new();
+    for s in dotted.split('.') {
+        // the `parse` accepts the leading `+`
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0')) {
+            return invalid_oid(format!("the arc `{}` is not the decimal number", s).as_str());
+        }
+        match s.parse::<u64>() {
+            Ok(x) => arcs.push(x),
+            Err(_) => return invalid_oid("the arc is too large"),
+        }
+    }
+
+    if arcs.len() < 2 || arcs[0] > 2 || (arcs[0] < 2 && arcs[1] >= 40) {
+        return invalid_oid("the first two arcs are out of range");
+    }
+    let first = match (arcs[0] * 40).checked_add(arcs[1]) {
+        Some(x) => x,
+        None => return invalid_oid("the arc is too large"),
+    };
+
+    let mut der = Vec::with_capacity(arcs.len() + 4);
+    for &arc in [first].iter().chain(arcs.iter().skip(2)) {
+        let n = (64 - arc.leading_zeros() as usize).div_ceil(7).max(1);
+        for i in (0..n).rev() {
+            let b = ((arc >> (7 * i)) & 0x7f) as u8;
+            der.push(if i == 0 { b } else { b | 0x80 });
+        }
+    }
+    Ok(der)
+}
\ No newline at end of file
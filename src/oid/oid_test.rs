@@ -0,0 +1,46 @@
+use crate::oid::{Oid, oid_to_dotted, oid_from_dotted};
+use crate::CryptoErrorKind;
+
+#[test]
+fn oid_constants() {
+    let all = Oid::all().collect::<Vec<_>>();
+    for (i, oid) in all.iter().enumerate() {
+        // the DER content octets and the dotted string describe the same object identifier
+        assert_eq!(oid_from_dotted(oid.dotted()).unwrap().as_slice(), oid.as_der(), "{}", oid);
+        assert_eq!(oid_to_dotted(oid.as_der()).unwrap(), oid.dotted(), "{}", oid);
+        assert_eq!(Oid::from_der(oid.as_der()), Some(*oid));
+        assert_eq!(Oid::from_dotted(oid.dotted()), Some(*oid));
+        assert_eq!(Oid::from_name(oid.name()), Some(*oid));
+        assert!(all.iter().skip(i + 1).all(|x| x.as_der() != oid.as_der() && x.name() != oid.name()), "{}", oid);
+    }
+
+    assert_eq!(Oid::P256.as_der(), [0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07].as_ref());
+    assert_eq!(Oid::SHA256.as_der(), [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01].as_ref());
+    assert_eq!(Oid::from_dotted("1.2.840.113549.1.1.11"), Some(Oid::SHA256_WITH_RSA));
+    assert_eq!(format!("{}", Oid::SM2_WITH_SM3), "SM2-with-SM3(1.2.156.10197.1.501)");
+    assert_eq!(Oid::from_dotted("1.2.3.4"), None);
+}
+
+#[test]
+fn oid_dotted_conversion() {
+    // the X.690 8.19.5 example and the large arcs
+    let cases: [(&str, &[u8]); 5] = [
+        ("2.999.3", &[0x88, 0x37, 0x03]),
+        ("0.0", &[0x00]),
+        ("1.39.127.128", &[0x4f, 0x7f, 0x81, 0x00]),
+        ("2.25.18446744073709551615", &[0x69, 0x81, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]),
+        ("1.3.6.1.4.1.11591.4.11", &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xda, 0x47, 0x04, 0x0b]),
+    ];
+    for (dotted, der) in cases.iter() {
+        assert_eq!(oid_from_dotted(dotted).unwrap().as_slice(), *der, "{}", dotted);
+        assert_eq!(oid_to_dotted(der).unwrap().as_str(), *dotted);
+    }
+
+    for dotted in ["", "1", "3.1", "1.40", "0.39.", "1.2.+3", "1.02", "1.2.a", "2.18446744073709551615", "1.2.18446744073709551616"].iter() {
+        assert_eq!(oid_from_dotted(dotted).unwrap_err().kind(), CryptoErrorKind::InvalidParameter, "{}", dotted);
+    }
+    let ders: [&[u8]; 4] = [&[], &[0x2a, 0x86], &[0x2a, 0x80, 0x01], &[0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x01]];
+    for der in ders.iter() {
+        assert_eq!(oid_to_dotted(der).unwrap_err().kind(), CryptoErrorKind::InvalidParameter, "{:?}", der);
+    }
+}
\ No newline at end of file
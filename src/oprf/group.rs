@@ -0,0 +1,221 @@
+//! The prime-order group of the OPRF ciphersuite
+//! RFC 9497 2.1
+//!
+//! The protocols only use the group by the `OprfGroup` interface(the hash-to-group, the canonical
+//! encoding of the elements and the scalars), so the groups other than the curve points(e.g. ristretto255)
+//! can be plugged in without changing the protocols.
+
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::{Digest, CryptoError, CryptoErrorKind};
+use crate::elliptic::{CurveP256, EllipticCurve};
+use crate::numeric::{mod_exp_public, mod_reduce};
+use crate::oprf::hash_to_curve::{hash_to_curve_p256, hash_to_field, P256_HASH_TO_FIELD_LEN};
+use crate::sha::SHA256;
+
+/// The prime-order group and the hash function of the OPRF ciphersuite, the scalars are the integers in the
+/// range `[0, order)`
+pub trait OprfGroup {
+    type Element: Clone;
+
+    /// the ciphersuite identifier, e.g. `P256-SHA256`
+    fn identifier(&self) -> &'static str;
+
+    /// the prime order of the group
+    fn order(&self) -> &BigInt;
+
+    /// the length of the serialized element(Ne)
+    fn element_len(&self) -> usize;
+
+    /// the length of the serialized scalar(Ns)
+    fn scalar_len(&self) -> usize;
+
+    /// the length of the hash output(Nh)
+    fn hash_len(&self) -> usize;
+
+    fn identity(&self) -> Self::Element;
+
+    fn is_identity(&self, e: &Self::Element) -> bool;
+
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// $k e$
+    fn scalar_mul(&self, e: &Self::Element, k: &BigInt) -> Self::Element;
+
+    /// $k G$
+    fn scalar_mul_gen(&self, k: &BigInt) -> Self::Element;
+
+    fn hash_to_group(&self, msg: &[u8], dst: &[u8]) -> Result<Self::Element, CryptoError>;
+
+    fn hash_to_scalar(&self, msg: &[u8], dst: &[u8]) -> Result<BigInt, CryptoError>;
+
+    /// the identity element cannot be serialized
+    fn serialize_element(&self, e: &Self::Element) -> Result<Vec<u8>, CryptoError>;
+
+    /// the identity element and the elements not in the group are rejected
+    fn deserialize_element(&self, buf: &[u8]) -> Result<Self::Element, CryptoError>;
+
+    fn serialize_scalar(&self, k: &BigInt) -> Vec<u8>;
+
+    /// the scalars not less than the order are rejected
+    fn deserialize_scalar(&self, buf: &[u8]) -> Result<BigInt, CryptoError>;
+
+    fn hash(&self, data: &[&[u8]]) -> Vec<u8>;
+
+    /// the uniform random scalar in the range `[1, order)`
+    fn random_scalar<R: IterSource<u32>>(&self, rd: &mut R) -> BigInt {
+        let n = self.order();
+        let bits_len = n.bits_len();
+        let b_len = (bits_len + 7) >> 3;
+        let mut buf = Vec::with_capacity(b_len + 3);
+        loop {
+            buf.clear();
+            rd.iter_mut().take(b_len.div_ceil(4)).for_each(|x| buf.extend_from_slice(x.to_be_bytes().as_ref()));
+            buf.truncate(b_len);
+            if bits_len & 7 != 0 {
+                buf[0] &= (1u8 << (bits_len & 7)) - 1;
+            }
+
+            let k = BigInt::from_be_bytes(buf.as_slice());
+            if k.signnum() != Some(0) && &k < n {
+                return k;
+            }
+        }
+    }
+}
+
+/// The ciphersuite `P256-SHA256`: the P-256 points in the SEC1 compressed form, the big-endian scalars,
+/// the `P256_XMD:SHA-256_SSWU_RO_` hash-to-curve, the `hash_to_field` of the order of the base point.
+#[derive(Clone)]
+pub struct P256Sha256 {
+    curve: CurveP256,
+}
+
+impl P256Sha256 {
+    const FIELD_LEN: usize = 32;
+
+    pub fn new() -> Result<Self, CryptoError> {
+        Ok(Self { curve: CurveP256::new()? })
+    }
+
+    pub fn curve(&self) -> &CurveP256 {
+        &self.curve
+    }
+}
+
+impl OprfGroup for P256Sha256 {
+    type Element = (BigInt, BigInt);
+
+    fn identifier(&self) -> &'static str {
+        "P256-SHA256"
+    }
+
+    fn order(&self) -> &BigInt {
+        self.curve.curve_params().base_point_order()
+    }
+
+    fn element_len(&self) -> usize {
+        Self::FIELD_LEN + 1
+    }
+
+    fn scalar_len(&self) -> usize {
+        Self::FIELD_LEN
+    }
+
+    fn hash_len(&self) -> usize {
+        32
+    }
+
+    fn identity(&self) -> Self::Element {
+        (BigInt::from(0u32), BigInt::from(0u32))
+    }
+
+    fn is_identity(&self, e: &Self::Element) -> bool {
+        e.0.signnum() == Some(0) && e.1.signnum() == Some(0)
+    }
+
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.curve.add(&a.0, &a.1, &b.0, &b.1)
+    }
+
+    fn scalar_mul(&self, e: &Self::Element, k: &BigInt) -> Self::Element {
+        if k.signnum() == Some(0) || self.is_identity(e) {
+            self.identity()
+        } else {
+            self.curve.scalar(&e.0, &e.1, k.as_ref())
+        }
+    }
+
+    fn scalar_mul_gen(&self, k: &BigInt) -> Self::Element {
+        if k.signnum() == Some(0) {
+            self.identity()
+        } else {
+            self.curve.scalar_base_point(k.as_ref())
+        }
+    }
+
+    fn hash_to_group(&self, msg: &[u8], dst: &[u8]) -> Result<Self::Element, CryptoError> {
+        hash_to_curve_p256(&self.curve, msg, dst)
+    }
+
+    fn hash_to_scalar(&self, msg: &[u8], dst: &[u8]) -> Result<BigInt, CryptoError> {
+        hash_to_field(msg, dst, 1, self.order(), P256_HASH_TO_FIELD_LEN).map(|mut x| x.remove(0))
+    }
+
+    fn serialize_element(&self, e: &Self::Element) -> Result<Vec<u8>, CryptoError> {
+        if self.is_identity(e) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The identity element cannot be serialized"));
+        }
+
+        let mut buf = vec![0u8; self.element_len()];
+        buf[0] = if e.1.is_set_bit(0).unwrap_or(false) { 0x03 } else { 0x02 };
+        let x = e.0.to_be_bytes();
+        buf[(1 + Self::FIELD_LEN - x.len())..].copy_from_slice(x.as_slice());
+        Ok(buf)
+    }
+
+    fn deserialize_element(&self, buf: &[u8]) -> Result<Self::Element, CryptoError> {
+        let p = self.curve.curve_params().field_order();
+        if buf.len() != self.element_len() || (buf[0] != 0x02 && buf[0] != 0x03) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The element is not the compressed P-256 point"));
+        }
+
+        let x = BigInt::from_be_bytes(&buf[1..]);
+        if &x >= p {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The x coordinate is not less than the field order"));
+        }
+        let one = BigInt::from(1u32);
+        let gx = mod_reduce(&(x.sqr() * x.clone() - x.clone() * BigInt::from(3u32) + self.curve.curve_params().coefficient_b().clone()), p)?;
+        let y = mod_exp_public(&gx, &((p.clone() + one) >> 2), p)?;
+        let y = if y.is_set_bit(0).unwrap_or(false) != (buf[0] == 0x03) { mod_reduce(&(p.clone() - y), p)? } else { y };
+        if !self.curve.is_on_curve(&x, &y) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The element is not on the P-256"));
+        }
+
+        Ok((x, y))
+    }
+
+    fn serialize_scalar(&self, k: &BigInt) -> Vec<u8> {
+        let x = k.to_be_bytes();
+        let mut buf = vec![0u8; Self::FIELD_LEN.saturating_sub(x.len())];
+        buf.extend_from_slice(x.as_slice());
+        buf
+    }
+
+    fn deserialize_scalar(&self, buf: &[u8]) -> Result<BigInt, CryptoError> {
+        let k = BigInt::from_be_bytes(buf);
+        if buf.len() != self.scalar_len() || &k >= self.order() {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The scalar is not the 32 bytes integer less than the order"))
+        } else {
+            Ok(k)
+        }
+    }
+
+    fn hash(&self, data: &[&[u8]]) -> Vec<u8> {
+        let mut sha = SHA256::new();
+        data.iter().for_each(|d| sha.write(d));
+        let mut h = Vec::with_capacity(self.hash_len());
+        sha.checksum(&mut h);
+        h
+    }
+}
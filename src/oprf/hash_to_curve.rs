@@ -0,0 +1,123 @@
+//! Hashing to elliptic curves
+//! RFC 9380
+//!
+//! `expand_message_xmd` expands the message to the uniform bytes by the Merkle–Damgård hash function,
+//! `hash_to_curve_p256` is the suite `P256_XMD:SHA-256_SSWU_RO_`: $u_0, u_1 = hash\_to\_field(msg, 2)$,
+//! $P = map\_to\_curve(u_0) + map\_to\_curve(u_1)$, where the map is the simplified SWU with $Z = -10$.
+//!
+//! The map is not constant-time, the message(e.g. the password) may leak from the timing.
+
+use rmath::bigint::BigInt;
+use crate::{Digest, CryptoError, CryptoErrorKind};
+use crate::elliptic::{CurveP256, EllipticCurve};
+use crate::numeric::{mod_exp_public, mod_inverse, mod_reduce};
+use crate::sha::SHA256;
+
+/// the length of the uniform bytes of one P-256 field element, $L = \lceil (256 + 128) / 8 \rceil$
+pub(super) const P256_HASH_TO_FIELD_LEN: usize = 48;
+
+/// expand the `msg` to the `len` uniform bytes with the domain separation tag `dst`, the `len` must be less than
+/// or equal to `255 * HashLen` and 65535, the `dst` longer than 255 bytes is hashed to `H("H2C-OVERSIZE-DST-" || dst)`.
+pub fn expand_message_xmd<D: Digest>(digest: &mut D, msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>, CryptoError> {
+    let b_len = (digest.bits_len() + 7) >> 3;
+    let s_len = digest.block_size().ok_or_else(|| CryptoError::new(CryptoErrorKind::NotSupportUsage,
+        "The expand_message_xmd needs the Merkle–Damgård hash function"))?;
+    let ell = len.div_ceil(b_len);
+    if ell > 255 || len > 65535 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("Wrong length: {}, the expand_message_xmd output must not be more than {} bytes", len, std::cmp::min(255 * b_len, 65535))));
+    }
+
+    let mut dst_prime = Vec::with_capacity(std::cmp::max(dst.len(), b_len) + 1);
+    if dst.len() > 255 {
+        digest.reset();
+        digest.write(b"H2C-OVERSIZE-DST-");
+        digest.write(dst);
+        digest.checksum(&mut dst_prime);
+    } else {
+        dst_prime.extend_from_slice(dst);
+    }
+    dst_prime.push(dst_prime.len() as u8);
+
+    let (mut b0, mut bi) = (Vec::with_capacity(b_len), Vec::with_capacity(b_len));
+    digest.reset();
+    digest.write(vec![0u8; s_len].as_slice());
+    digest.write(msg);
+    digest.write((len as u16).to_be_bytes().as_ref());
+    digest.write(&[0]);
+    digest.write(dst_prime.as_slice());
+    digest.checksum(&mut b0);
+
+    let mut out = Vec::with_capacity(ell * b_len);
+    for i in 1..=(ell as u8) {
+        let mut x = b0.clone();
+        x.iter_mut().zip(bi.iter()).for_each(|(a, &b)| *a ^= b);
+        digest.reset();
+        digest.write(x.as_slice());
+        digest.write(&[i]);
+        digest.write(dst_prime.as_slice());
+        digest.checksum(&mut bi);
+        out.extend_from_slice(bi.as_slice());
+    }
+
+    out.truncate(len);
+    Ok(out)
+}
+
+/// hash the `msg` to the `count` elements of the integers modulo the `modulus`, every element is reduced from the
+/// `elem_len` uniform bytes.
+pub(super) fn hash_to_field(msg: &[u8], dst: &[u8], count: usize, modulus: &BigInt, elem_len: usize) -> Result<Vec<BigInt>, CryptoError> {
+    let uniform = expand_message_xmd(&mut SHA256::new(), msg, dst, count * elem_len)?;
+    uniform.chunks_exact(elem_len).map(|e| mod_reduce(&BigInt::from_be_bytes(e), modulus)).collect()
+}
+
+fn is_odd(x: &BigInt) -> bool {
+    x.is_set_bit(0).unwrap_or(false)
+}
+
+/// the simplified SWU map of the P-256(RFC 9380 6.6.2), $A = -3$, $Z = -10$
+fn map_to_curve_p256(curve: &CurveP256, u: &BigInt) -> Result<(BigInt, BigInt), CryptoError> {
+    let cp = curve.curve_params();
+    let (p, b) = (cp.field_order(), cp.coefficient_b());
+    let one = BigInt::from(1u32);
+    let z = p.clone() - BigInt::from(10u32);
+    let g = |x: &BigInt| -> Result<BigInt, CryptoError> {
+        let x3 = x.sqr() * x.clone();
+        mod_reduce(&(x3 + p.clone() * BigInt::from(3u32) - x.clone() * BigInt::from(3u32) + b.clone()), p)
+    };
+
+    let z_u2 = mod_reduce(&(z * u.sqr()), p)?;
+    let tv1 = mod_reduce(&(z_u2.sqr() + z_u2.clone()), p)?;
+    // inv0(0) = 0
+    let tv1 = mod_exp_public(&tv1, &(p.clone() - BigInt::from(2u32)), p)?;
+    let x1 = if tv1.signnum() == Some(0) {
+        // B / (Z * A) = B / 30
+        mod_reduce(&(b.clone() * mod_inverse(&BigInt::from(30u32), p)?), p)?
+    } else {
+        // -B / A * (1 + tv1) = B / 3 * (1 + tv1)
+        mod_reduce(&(b.clone() * mod_inverse(&BigInt::from(3u32), p)? * (one.clone() + tv1)), p)?
+    };
+
+    let gx1 = g(&x1)?;
+    let (half, quarter) = ((p.clone() - one.clone()) >> 1, (p.clone() + one.clone()) >> 2);
+    let (x, gx) = if mod_exp_public(&gx1, &half, p)? <= one {
+        (x1, gx1)
+    } else {
+        let x2 = mod_reduce(&(z_u2 * x1), p)?;
+        let gx2 = g(&x2)?;
+        (x2, gx2)
+    };
+
+    let y = mod_exp_public(&gx, &quarter, p)?;
+    let y = if is_odd(u) != is_odd(&y) { mod_reduce(&(p.clone() - y), p)? } else { y };
+    Ok((x, y))
+}
+
+/// hash the `msg` to the point of the P-256 by the suite `P256_XMD:SHA-256_SSWU_RO_`, the result may be the
+/// infinite point(0, 0) with the negligible probability.
+pub fn hash_to_curve_p256(curve: &CurveP256, msg: &[u8], dst: &[u8]) -> Result<(BigInt, BigInt), CryptoError> {
+    let u = hash_to_field(msg, dst, 2, curve.curve_params().field_order(), P256_HASH_TO_FIELD_LEN)?;
+    let (x0, y0) = map_to_curve_p256(curve, &u[0])?;
+    let (x1, y1) = map_to_curve_p256(curve, &u[1])?;
+    Ok(curve.add(&x0, &y0, &x1, &y1))
+}
@@ -0,0 +1,20 @@
+//! Oblivious pseudorandom functions(RFC 9497), the OPRF/VOPRF/POPRF protocols
+//!
+//! The ciphersuite `P256-SHA256` is implemented by the `P256Sha256`, the other prime-order groups can be used by
+//! implementing the `OprfGroup`. The hash-to-curve(RFC 9380) of the P-256 is also exported.
+//! The private set intersection is built on the OPRF mode.
+
+mod hash_to_curve;
+pub use hash_to_curve::{expand_message_xmd, hash_to_curve_p256};
+
+mod group;
+pub use group::{OprfGroup, P256Sha256};
+
+mod oprf;
+pub use oprf::{OprfMode, OprfProof, OprfBlind, OprfServer, OprfClient};
+
+mod psi;
+pub use psi::{PsiServerSet, PsiServer, PsiClient, PSI_MIN_TAG_LEN};
+
+#[cfg(test)]
+mod oprf_test;
\ No newline at end of file
@@ -0,0 +1,443 @@
+//! Oblivious pseudorandom functions
+//! RFC 9497
+//!
+//! The client blinds the input $r H(x)$, the server evaluates the blinded element by the private key $k$, and the
+//! client unblinds the result to $F(k, x) = Hash(x || k H(x))$, so the server learns nothing about the input and
+//! the client learns nothing about the key.
+//!
+//! - OPRF: the base mode;
+//! - VOPRF: the server proves that the evaluations are computed by the key of the public key $pkS = k G$,
+//!   by the DLEQ proof over the batch of the evaluations;
+//! - POPRF: the VOPRF with the public input `info`, the evaluation key is $t = k + H(info)$;
+
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::numeric::{ct_mod_inverse, mod_reduce};
+use crate::oprf::OprfGroup;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OprfMode {
+    Oprf,
+    Voprf,
+    Poprf,
+}
+
+impl OprfMode {
+    pub fn id(&self) -> u8 {
+        match self {
+            OprfMode::Oprf => 0,
+            OprfMode::Voprf => 1,
+            OprfMode::Poprf => 2,
+        }
+    }
+}
+
+fn length_prefix(data: &[u8], name: &str) -> Result<[u8; 2], CryptoError> {
+    if data.len() > (u16::MAX as usize) {
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("The {} must not be more than 65535 bytes", name)))
+    } else {
+        Ok((data.len() as u16).to_be_bytes())
+    }
+}
+
+/// The DLEQ proof $(c, s)$
+#[derive(Clone, Debug, PartialEq)]
+pub struct OprfProof {
+    c: BigInt,
+    s: BigInt,
+}
+
+impl OprfProof {
+    /// the serialized scalars `c || s`
+    pub fn to_bytes<G: OprfGroup>(&self, group: &G) -> Vec<u8> {
+        let mut buf = group.serialize_scalar(&self.c);
+        buf.append(&mut group.serialize_scalar(&self.s));
+        buf
+    }
+
+    pub fn from_bytes<G: OprfGroup>(group: &G, buf: &[u8]) -> Result<Self, CryptoError> {
+        if buf.len() != group.scalar_len() << 1 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong proof length: {}, it must be {} bytes", buf.len(), group.scalar_len() << 1)));
+        }
+
+        let (c, s) = buf.split_at(group.scalar_len());
+        Ok(Self { c: group.deserialize_scalar(c)?, s: group.deserialize_scalar(s)? })
+    }
+}
+
+/// The blinding state of the client for one input
+#[derive(Clone)]
+pub struct OprfBlind {
+    blind: BigInt,
+    blinded: Vec<u8>,
+}
+
+impl OprfBlind {
+    /// the serialized blinded element which is sent to the server
+    pub fn blinded_element(&self) -> &[u8] {
+        self.blinded.as_slice()
+    }
+}
+
+/// the group with the context string `"OPRFV1-" || I2OSP(mode, 1) || "-" || identifier`
+#[derive(Clone)]
+struct Suite<G> {
+    group: G,
+    mode: OprfMode,
+    context: Vec<u8>,
+}
+
+impl<G: OprfGroup> Suite<G> {
+    fn new(group: G, mode: OprfMode) -> Self {
+        let mut context = b"OPRFV1-".to_vec();
+        context.push(mode.id());
+        context.push(b'-');
+        context.extend_from_slice(group.identifier().as_bytes());
+        Self { group, mode, context }
+    }
+
+    fn dst(&self, prefix: &str) -> Vec<u8> {
+        let mut dst = prefix.as_bytes().to_vec();
+        dst.extend_from_slice(self.context.as_slice());
+        dst
+    }
+
+    fn check_info(&self, info: &[u8]) -> Result<(), CryptoError> {
+        if self.mode != OprfMode::Poprf && !info.is_empty() {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The public input info is only supported by the POPRF"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn hash_to_group(&self, input: &[u8]) -> Result<G::Element, CryptoError> {
+        length_prefix(input, "input")?;
+        let e = self.group.hash_to_group(input, self.dst("HashToGroup-").as_slice())?;
+        if self.group.is_identity(&e) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The input is hashed to the identity element"))
+        } else {
+            Ok(e)
+        }
+    }
+
+    fn hash_to_scalar(&self, msg: &[u8]) -> Result<BigInt, CryptoError> {
+        self.group.hash_to_scalar(msg, self.dst("HashToScalar-").as_slice())
+    }
+
+    /// $m = HashToScalar("Info" || I2OSP(len(info), 2) || info)$
+    fn info_scalar(&self, info: &[u8]) -> Result<BigInt, CryptoError> {
+        let mut framed = b"Info".to_vec();
+        framed.extend_from_slice(length_prefix(info, "info")?.as_ref());
+        framed.extend_from_slice(info);
+        self.hash_to_scalar(framed.as_slice())
+    }
+
+    fn finalize_hash(&self, input: &[u8], info: &[u8], unblinded: &G::Element) -> Result<Vec<u8>, CryptoError> {
+        let unblinded = self.group.serialize_element(unblinded)?;
+        let (input_len, unblinded_len) = (length_prefix(input, "input")?, length_prefix(unblinded.as_slice(), "element")?);
+        let info_len = length_prefix(info, "info")?;
+        Ok(if self.mode == OprfMode::Poprf {
+            self.group.hash(&[input_len.as_ref(), input, info_len.as_ref(), info, unblinded_len.as_ref(), unblinded.as_slice(), b"Finalize"])
+        } else {
+            self.group.hash(&[input_len.as_ref(), input, unblinded_len.as_ref(), unblinded.as_slice(), b"Finalize"])
+        })
+    }
+
+    /// $M = \sum d_i C_i$, $Z = \sum d_i D_i$ or $Z = k M$ if the `k` is known
+    fn composites(&self, k: Option<&BigInt>, b: &G::Element, c: &[G::Element], d: &[G::Element]) -> Result<(G::Element, G::Element), CryptoError> {
+        if c.len() != d.len() || c.is_empty() || c.len() > (u16::MAX as usize) + 1 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The batch must have 1 to 65536 pairs of the elements"));
+        }
+
+        let g = &self.group;
+        let bm = g.serialize_element(b)?;
+        let seed_dst = self.dst("Seed-");
+        let seed = g.hash(&[length_prefix(bm.as_slice(), "element")?.as_ref(), bm.as_slice(),
+            length_prefix(seed_dst.as_slice(), "DST")?.as_ref(), seed_dst.as_slice()]);
+        let seed_len = length_prefix(seed.as_slice(), "seed")?;
+
+        let (mut m, mut z) = (g.identity(), g.identity());
+        let mut transcript = Vec::new();
+        for (i, (ci, di)) in c.iter().zip(d.iter()).enumerate() {
+            let (cs, ds) = (g.serialize_element(ci)?, g.serialize_element(di)?);
+            transcript.clear();
+            transcript.extend_from_slice(seed_len.as_ref());
+            transcript.extend_from_slice(seed.as_slice());
+            transcript.extend_from_slice((i as u16).to_be_bytes().as_ref());
+            transcript.extend_from_slice(length_prefix(cs.as_slice(), "element")?.as_ref());
+            transcript.extend_from_slice(cs.as_slice());
+            transcript.extend_from_slice(length_prefix(ds.as_slice(), "element")?.as_ref());
+            transcript.extend_from_slice(ds.as_slice());
+            transcript.extend_from_slice(b"Composite");
+
+            let s = self.hash_to_scalar(transcript.as_slice())?;
+            m = g.add(&g.scalar_mul(ci, &s), &m);
+            if k.is_none() {
+                z = g.add(&g.scalar_mul(di, &s), &z);
+            }
+        }
+
+        if let Some(k) = k {
+            z = g.scalar_mul(&m, k);
+        }
+        Ok((m, z))
+    }
+
+    fn challenge(&self, b: &G::Element, m: &G::Element, z: &G::Element, t2: &G::Element, t3: &G::Element) -> Result<BigInt, CryptoError> {
+        let mut transcript = Vec::new();
+        for e in [b, m, z, t2, t3] {
+            let e = self.group.serialize_element(e)?;
+            transcript.extend_from_slice(length_prefix(e.as_slice(), "element")?.as_ref());
+            transcript.extend_from_slice(e.as_slice());
+        }
+        transcript.extend_from_slice(b"Challenge");
+        self.hash_to_scalar(transcript.as_slice())
+    }
+
+    /// prove $\log_A B = \log_{C_i} D_i = k$ by the random scalar `r`
+    fn generate_proof(&self, k: &BigInt, a: &G::Element, b: &G::Element, c: &[G::Element], d: &[G::Element], r: &BigInt) -> Result<OprfProof, CryptoError> {
+        let g = &self.group;
+        let (m, z) = self.composites(Some(k), b, c, d)?;
+        let (t2, t3) = (g.scalar_mul(a, r), g.scalar_mul(&m, r));
+        let c = self.challenge(b, &m, &z, &t2, &t3)?;
+        let s = mod_reduce(&(r.clone() - c.clone() * k.clone()), g.order())?;
+        Ok(OprfProof { c, s })
+    }
+
+    fn verify_proof(&self, a: &G::Element, b: &G::Element, c: &[G::Element], d: &[G::Element], proof: &OprfProof) -> Result<(), CryptoError> {
+        let g = &self.group;
+        let (m, z) = self.composites(None, b, c, d)?;
+        let t2 = g.add(&g.scalar_mul(a, &proof.s), &g.scalar_mul(b, &proof.c));
+        let t3 = g.add(&g.scalar_mul(&m, &proof.s), &g.scalar_mul(&z, &proof.c));
+        if self.challenge(b, &m, &z, &t2, &t3)? == proof.c {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The OPRF proof is not valid"))
+        }
+    }
+}
+
+/// The server holds the private key $k$
+pub struct OprfServer<G: OprfGroup> {
+    suite: Suite<G>,
+    sk: BigInt,
+    pk: G::Element,
+}
+
+impl<G: OprfGroup> OprfServer<G> {
+    /// the serialized private key must be in the range `[1, order)`
+    pub fn new(group: G, mode: OprfMode, private_key: &[u8]) -> Result<Self, CryptoError> {
+        let sk = group.deserialize_scalar(private_key)
+            .map_err(|e| CryptoError::new(CryptoErrorKind::InvalidPrivateKey, e))?;
+        Self::from_scalar(group, mode, sk)
+    }
+
+    pub fn generate<R: IterSource<u32>>(group: G, mode: OprfMode, rd: &mut R) -> Result<Self, CryptoError> {
+        let sk = group.random_scalar(rd);
+        Self::from_scalar(group, mode, sk)
+    }
+
+    /// derive the key pair from the `seed` of the scalar length and the public `info` deterministically
+    pub fn derive_key_pair(group: G, mode: OprfMode, seed: &[u8], info: &[u8]) -> Result<Self, CryptoError> {
+        if seed.len() != group.scalar_len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong seed length: {}, it must be {} bytes", seed.len(), group.scalar_len())));
+        }
+
+        let suite = Suite::new(group, mode);
+        let mut derive_input = seed.to_vec();
+        derive_input.extend_from_slice(length_prefix(info, "info")?.as_ref());
+        derive_input.extend_from_slice(info);
+        derive_input.push(0);
+        let dst = suite.dst("DeriveKeyPair");
+        for counter in 0..=255u8 {
+            *derive_input.last_mut().unwrap() = counter;
+            let sk = suite.group.hash_to_scalar(derive_input.as_slice(), dst.as_slice())?;
+            if sk.signnum() != Some(0) {
+                return Self::from_scalar(suite.group, mode, sk);
+            }
+        }
+
+        Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Cannot derive the OPRF key pair from the seed"))
+    }
+
+    fn from_scalar(group: G, mode: OprfMode, sk: BigInt) -> Result<Self, CryptoError> {
+        if sk.signnum() == Some(0) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The OPRF private key must not be zero"));
+        }
+
+        let pk = group.scalar_mul_gen(&sk);
+        Ok(Self { suite: Suite::new(group, mode), sk, pk })
+    }
+
+    pub fn mode(&self) -> OprfMode {
+        self.suite.mode
+    }
+
+    pub fn group(&self) -> &G {
+        &self.suite.group
+    }
+
+    pub fn private_key(&self) -> Vec<u8> {
+        self.suite.group.serialize_scalar(&self.sk)
+    }
+
+    /// the serialized public key $pkS = k G$
+    pub fn public_key(&self) -> Result<Vec<u8>, CryptoError> {
+        self.suite.group.serialize_element(&self.pk)
+    }
+
+    /// evaluate the blinded element in the OPRF mode
+    pub fn blind_evaluate(&self, blinded: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if self.suite.mode != OprfMode::Oprf {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage,
+                "The verifiable modes need the proof, use the `blind_evaluate_batch`"));
+        }
+
+        let g = &self.suite.group;
+        let e = g.deserialize_element(blinded)?;
+        g.serialize_element(&g.scalar_mul(&e, &self.sk))
+    }
+
+    /// evaluate the batch of the blinded elements and prove them in the VOPRF or POPRF mode, the `info` is the
+    /// public input of the POPRF and must be empty in the VOPRF.
+    pub fn blind_evaluate_batch<R: IterSource<u32>>(&self, blinded: &[&[u8]], info: &[u8], rd: &mut R) -> Result<(Vec<Vec<u8>>, OprfProof), CryptoError> {
+        let r = self.suite.group.random_scalar(rd);
+        self.blind_evaluate_batch_with(blinded, info, &r)
+    }
+
+    pub(super) fn blind_evaluate_batch_with(&self, blinded: &[&[u8]], info: &[u8], r: &BigInt) -> Result<(Vec<Vec<u8>>, OprfProof), CryptoError> {
+        let (suite, g) = (&self.suite, &self.suite.group);
+        suite.check_info(info)?;
+        let blinded = blinded.iter().map(|b| g.deserialize_element(b)).collect::<Result<Vec<_>, _>>()?;
+        let gen = g.scalar_mul_gen(&BigInt::from(1u32));
+
+        let (evaluated, proof) = match suite.mode {
+            OprfMode::Oprf => {
+                return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The OPRF mode has no proof, use the `blind_evaluate`"));
+            },
+            OprfMode::Voprf => {
+                let evaluated = blinded.iter().map(|b| g.scalar_mul(b, &self.sk)).collect::<Vec<_>>();
+                let proof = suite.generate_proof(&self.sk, &gen, &self.pk, blinded.as_slice(), evaluated.as_slice(), r)?;
+                (evaluated, proof)
+            },
+            OprfMode::Poprf => {
+                let t = self.tweaked_scalar(info)?;
+                let t_inv = ct_mod_inverse(&t, g.order())?;
+                let evaluated = blinded.iter().map(|b| g.scalar_mul(b, &t_inv)).collect::<Vec<_>>();
+                let tweaked_key = g.scalar_mul_gen(&t);
+                let proof = suite.generate_proof(&t, &gen, &tweaked_key, evaluated.as_slice(), blinded.as_slice(), r)?;
+                (evaluated, proof)
+            },
+        };
+
+        Ok((evaluated.iter().map(|e| g.serialize_element(e)).collect::<Result<Vec<_>, _>>()?, proof))
+    }
+
+    /// $t = k + m$
+    fn tweaked_scalar(&self, info: &[u8]) -> Result<BigInt, CryptoError> {
+        let m = self.suite.info_scalar(info)?;
+        let t = mod_reduce(&(self.sk.clone() + m), self.suite.group.order())?;
+        if t.signnum() == Some(0) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The tweaked private key is zero"))
+        } else {
+            Ok(t)
+        }
+    }
+
+    /// compute the PRF output of the `input` directly, the `info` must be empty except for the POPRF
+    pub fn evaluate(&self, input: &[u8], info: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let (suite, g) = (&self.suite, &self.suite.group);
+        suite.check_info(info)?;
+        let e = suite.hash_to_group(input)?;
+        let k = if suite.mode == OprfMode::Poprf {
+            ct_mod_inverse(&self.tweaked_scalar(info)?, g.order())?
+        } else {
+            self.sk.clone()
+        };
+
+        suite.finalize_hash(input, info, &g.scalar_mul(&e, &k))
+    }
+}
+
+/// The client blinds the inputs and finalizes the evaluations of the server
+pub struct OprfClient<G: OprfGroup> {
+    suite: Suite<G>,
+    pk: Option<G::Element>,
+}
+
+impl<G: OprfGroup> OprfClient<G> {
+    /// the serialized public key of the server is needed by the VOPRF and POPRF
+    pub fn new(group: G, mode: OprfMode, server_public_key: Option<&[u8]>) -> Result<Self, CryptoError> {
+        let pk = match server_public_key {
+            Some(pk) => Some(group.deserialize_element(pk).map_err(|e| CryptoError::new(CryptoErrorKind::InvalidPublicKey, e))?),
+            None if mode != OprfMode::Oprf => {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The verifiable modes need the public key of the server"));
+            },
+            None => None,
+        };
+
+        Ok(Self { suite: Suite::new(group, mode), pk })
+    }
+
+    pub fn mode(&self) -> OprfMode {
+        self.suite.mode
+    }
+
+    pub fn group(&self) -> &G {
+        &self.suite.group
+    }
+
+    /// blind the `input` by the random scalar
+    pub fn blind<R: IterSource<u32>>(&self, input: &[u8], rd: &mut R) -> Result<OprfBlind, CryptoError> {
+        let blind = self.suite.group.random_scalar(rd);
+        self.blind_with(input, &blind)
+    }
+
+    pub(super) fn blind_with(&self, input: &[u8], blind: &BigInt) -> Result<OprfBlind, CryptoError> {
+        let g = &self.suite.group;
+        let e = self.suite.hash_to_group(input)?;
+        let blinded = g.serialize_element(&g.scalar_mul(&e, blind))?;
+        Ok(OprfBlind { blind: blind.deep_clone(), blinded })
+    }
+
+    /// unblind the `evaluated` element to the PRF output, the `proof` is needed by the VOPRF and POPRF,
+    /// the `info` must be empty except for the POPRF.
+    pub fn finalize(&self, input: &[u8], blind: &OprfBlind, evaluated: &[u8], proof: Option<&OprfProof>, info: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.finalize_batch(&[input], std::slice::from_ref(blind), &[evaluated], proof, info).map(|mut x| x.remove(0))
+    }
+
+    /// `finalize` the batch of the evaluations which are proved by one proof
+    pub fn finalize_batch(&self, inputs: &[&[u8]], blinds: &[OprfBlind], evaluated: &[&[u8]], proof: Option<&OprfProof>, info: &[u8]) -> Result<Vec<Vec<u8>>, CryptoError> {
+        let (suite, g) = (&self.suite, &self.suite.group);
+        suite.check_info(info)?;
+        if inputs.len() != blinds.len() || inputs.len() != evaluated.len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The numbers of the inputs, blinds and evaluations are different"));
+        } else if suite.mode == OprfMode::Oprf && inputs.len() != 1 {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The OPRF mode has no batch"));
+        }
+        let evaluated = evaluated.iter().map(|e| g.deserialize_element(e)).collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(pk) = self.pk.as_ref().filter(|_| suite.mode != OprfMode::Oprf) {
+            let proof = proof.ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidParameter, "The verifiable modes need the proof"))?;
+            let blinded = blinds.iter().map(|b| g.deserialize_element(b.blinded_element())).collect::<Result<Vec<_>, _>>()?;
+            let gen = g.scalar_mul_gen(&BigInt::from(1u32));
+            if suite.mode == OprfMode::Voprf {
+                suite.verify_proof(&gen, pk, blinded.as_slice(), evaluated.as_slice(), proof)?;
+            } else {
+                let tweaked_key = g.add(&g.scalar_mul_gen(&suite.info_scalar(info)?), pk);
+                if g.is_identity(&tweaked_key) {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The tweaked public key is the identity element"));
+                }
+                suite.verify_proof(&gen, &tweaked_key, evaluated.as_slice(), blinded.as_slice(), proof)?;
+            }
+        }
+
+        inputs.iter().zip(blinds.iter()).zip(evaluated.iter()).map(|((input, blind), e)| {
+            let unblinded = g.scalar_mul(e, &ct_mod_inverse(&blind.blind, g.order())?);
+            suite.finalize_hash(input, info, &unblinded)
+        }).collect()
+    }
+}
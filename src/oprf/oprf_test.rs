@@ -0,0 +1,196 @@
+use rmath::bigint::BigInt;
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::CryptoErrorKind;
+use crate::elliptic::CurveP256;
+use crate::encoding::Hex;
+use crate::oprf::{expand_message_xmd, hash_to_curve_p256, OprfGroup, P256Sha256, OprfMode, OprfServer, OprfClient, OprfProof,
+    PsiServer, PsiClient, PsiServerSet, PSI_MIN_TAG_LEN};
+use crate::sha::SHA256;
+
+fn cvt_bytes_to_str(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{:02x}", x)).collect::<String>()
+}
+
+fn scalar(s: &str) -> BigInt {
+    BigInt::from_be_bytes(Hex::lower().decode(s).unwrap().as_slice())
+}
+
+#[test]
+fn oprf_hash_to_curve() {
+    // RFC 9380 K.1
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let cases = [
+        ("", 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+        ("abc", 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+        ("", 0x80, "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"),
+    ];
+    for (msg, len, tgt) in cases.iter() {
+        let out = expand_message_xmd(&mut SHA256::new(), msg.as_bytes(), dst.as_ref(), *len).unwrap();
+        assert_eq!(cvt_bytes_to_str(out.as_slice()), *tgt, "case: {}", msg);
+    }
+    assert!(expand_message_xmd(&mut SHA256::new(), b"", dst.as_ref(), 255 * 32 + 1).is_err());
+
+    // RFC 9380 J.1.1
+    let curve = CurveP256::new().unwrap();
+    let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+    let cases = [
+        ("", "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4", "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415"),
+        ("abc", "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f", "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e"),
+    ];
+    for (msg, x, y) in cases.iter() {
+        let p = hash_to_curve_p256(&curve, msg.as_bytes(), dst.as_ref()).unwrap();
+        assert_eq!((p.0, p.1), (scalar(x), scalar(y)), "case: {}", msg);
+    }
+}
+
+#[test]
+fn oprf_p256() {
+    // RFC 9497 A.3
+    let (seed, key_info) = ([0xa3u8; 32], b"test key");
+    let blind = scalar("3338fa65ec36e0290022b48eb562889d89dbfa691d1cde91517fa222ed7ad364");
+    let r = scalar("f9db001266677f62c095021db018cd8cbb55941d4073698ce45c405d1348b7b1");
+    let input = [0u8];
+
+    let server = OprfServer::derive_key_pair(P256Sha256::new().unwrap(), OprfMode::Oprf, seed.as_ref(), key_info.as_ref()).unwrap();
+    assert_eq!(cvt_bytes_to_str(server.private_key().as_slice()), "159749d750713afe245d2d39ccfaae8381c53ce92d098a9375ee70739c7ac0bf");
+    let client = OprfClient::new(P256Sha256::new().unwrap(), OprfMode::Oprf, None).unwrap();
+    let b = client.blind_with(input.as_ref(), &blind).unwrap();
+    assert_eq!(cvt_bytes_to_str(b.blinded_element()), "03723a1e5c09b8b9c18d1dcbca29e8007e95f14f4732d9346d490ffc195110368d");
+    let evaluated = server.blind_evaluate(b.blinded_element()).unwrap();
+    assert_eq!(cvt_bytes_to_str(evaluated.as_slice()), "030de02ffec47a1fd53efcdd1c6faf5bdc270912b8749e783c7ca75bb412958832");
+    let out = client.finalize(input.as_ref(), &b, evaluated.as_slice(), None, &[]).unwrap();
+    assert_eq!(cvt_bytes_to_str(out.as_slice()), "a0b34de5fa4c5b6da07e72af73cc507cceeb48981b97b7285fc375345fe495dd");
+    assert_eq!(server.evaluate(input.as_ref(), &[]).unwrap(), out);
+
+    let server = OprfServer::derive_key_pair(P256Sha256::new().unwrap(), OprfMode::Voprf, seed.as_ref(), key_info.as_ref()).unwrap();
+    assert_eq!(cvt_bytes_to_str(server.private_key().as_slice()), "ca5d94c8807817669a51b196c34c1b7f8442fde4334a7121ae4736364312fca6");
+    let pk = server.public_key().unwrap();
+    assert_eq!(cvt_bytes_to_str(pk.as_slice()), "03e17e70604bcabe198882c0a1f27a92441e774224ed9c702e51dd17038b102462");
+    let client = OprfClient::new(P256Sha256::new().unwrap(), OprfMode::Voprf, Some(pk.as_slice())).unwrap();
+    let b = client.blind_with(input.as_ref(), &blind).unwrap();
+    assert_eq!(cvt_bytes_to_str(b.blinded_element()), "02dd05901038bb31a6fae01828fd8d0e49e35a486b5c5d4b4994013648c01277da");
+    let (evaluated, proof) = server.blind_evaluate_batch_with(&[b.blinded_element()], &[], &r).unwrap();
+    assert_eq!(cvt_bytes_to_str(evaluated[0].as_slice()), "0209f33cab60cf8fe69239b0afbcfcd261af4c1c5632624f2e9ba29b90ae83e4a2");
+    assert_eq!(cvt_bytes_to_str(proof.to_bytes(server.group()).as_slice()), "e7c2b3c5c954c035949f1f74e6bce2ed539a3be267d1481e9ddb178533df4c2664f69d065c604a4fd953e100b856ad83804eb3845189babfa5a702090d6fc5fa");
+    let out = client.finalize(input.as_ref(), &b, evaluated[0].as_slice(), Some(&proof), &[]).unwrap();
+    assert_eq!(cvt_bytes_to_str(out.as_slice()), "0412e8f78b02c415ab3a288e228978376f99927767ff37c5718d420010a645a1");
+    assert_eq!(server.evaluate(input.as_ref(), &[]).unwrap(), out);
+
+    // the batch
+    let inputs = [[0u8].to_vec(), vec![0x5a; 17]];
+    let blinds = [blind.deep_clone(), r.deep_clone()];
+    let bs = inputs.iter().zip(blinds.iter()).map(|(x, bl)| client.blind_with(x.as_slice(), bl).unwrap()).collect::<Vec<_>>();
+    let (evaluated, proof) = server.blind_evaluate_batch_with(&[bs[0].blinded_element(), bs[1].blinded_element()], &[],
+        &scalar("350e8040f828bf6ceca27405420cdf3d63cb3aef005f40ba51943c8026877963")).unwrap();
+    assert_eq!(cvt_bytes_to_str(evaluated[1].as_slice()), "02bb24f4d838414aef052a8f044a6771230ca69c0a5677540fff738dd31bb69771");
+    assert_eq!(cvt_bytes_to_str(proof.to_bytes(server.group()).as_slice()), "bdcc351707d02a72ce49511c7db990566d29d6153ad6f8982fad2b435d6ce4d60da1e6b3fa740811bde34dd4fe0aa1b5fe6600d0440c9ddee95ea7fad7a60cf2");
+    let outs = client.finalize_batch(&[inputs[0].as_slice(), inputs[1].as_slice()], bs.as_slice(),
+        &[evaluated[0].as_slice(), evaluated[1].as_slice()], Some(&proof), &[]).unwrap();
+    assert_eq!(cvt_bytes_to_str(outs[1].as_slice()), "771e10dcd6bcd3664e23b8f2a710cfaaa8357747c4a8cbba03133967b5c24f18");
+    let err = client.finalize_batch(&[inputs[0].as_slice(), inputs[1].as_slice()], bs.as_slice(),
+        &[evaluated[1].as_slice(), evaluated[0].as_slice()], Some(&proof), &[]).unwrap_err();
+    assert_eq!(err.kind(), CryptoErrorKind::VerificationFailed);
+
+    let (seed, info) = ([0xa3u8; 32], b"test info");
+    let server = OprfServer::derive_key_pair(P256Sha256::new().unwrap(), OprfMode::Poprf, seed.as_ref(), key_info.as_ref()).unwrap();
+    assert_eq!(cvt_bytes_to_str(server.private_key().as_slice()), "6ad2173efa689ef2c27772566ad7ff6e2d59b3b196f00219451fb2c89ee4dae2");
+    let pk = server.public_key().unwrap();
+    assert_eq!(cvt_bytes_to_str(pk.as_slice()), "030d7ff077fddeec965db14b794f0cc1ba9019b04a2f4fcc1fa525dedf72e2a3e3");
+    let client = OprfClient::new(P256Sha256::new().unwrap(), OprfMode::Poprf, Some(pk.as_slice())).unwrap();
+    let b = client.blind_with(input.as_ref(), &blind).unwrap();
+    assert_eq!(cvt_bytes_to_str(b.blinded_element()), "031563e127099a8f61ed51eeede05d747a8da2be329b40ba1f0db0b2bd9dd4e2c0");
+    let (evaluated, proof) = server.blind_evaluate_batch_with(&[b.blinded_element()], info.as_ref(), &r).unwrap();
+    assert_eq!(cvt_bytes_to_str(evaluated[0].as_slice()), "02c5e5300c2d9e6ba7f3f4ad60500ad93a0157e6288eb04b67e125db024a2c74d2");
+    assert_eq!(cvt_bytes_to_str(proof.to_bytes(server.group()).as_slice()), "f8a33690b87736c854eadfcaab58a59b8d9c03b569110b6f31f8bf7577f3fbb85a8a0c38468ccde1ba942be501654adb106167c8eb178703ccb42bccffb9231a");
+    let out = client.finalize(input.as_ref(), &b, evaluated[0].as_slice(), Some(&proof), info.as_ref()).unwrap();
+    assert_eq!(cvt_bytes_to_str(out.as_slice()), "193a92520bd8fd1f37accb918040a57108daa110dc4f659abe212636d245c592");
+    assert_eq!(server.evaluate(input.as_ref(), info.as_ref()).unwrap(), out);
+    let err = client.finalize(input.as_ref(), &b, evaluated[0].as_slice(), Some(&proof), b"other info").unwrap_err();
+    assert_eq!(err.kind(), CryptoErrorKind::VerificationFailed);
+}
+
+#[test]
+fn oprf_random() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    for mode in [OprfMode::Oprf, OprfMode::Voprf, OprfMode::Poprf] {
+        let group = P256Sha256::new().unwrap();
+        let server = OprfServer::generate(group.clone(), mode, &mut rd).unwrap();
+        let pk = server.public_key().unwrap();
+        let client = OprfClient::new(group.clone(), mode, Some(pk.as_slice())).unwrap();
+        let info: &[u8] = if mode == OprfMode::Poprf { b"context" } else { &[] };
+
+        let b = client.blind(b"password", &mut rd).unwrap();
+        let out = if mode == OprfMode::Oprf {
+            let evaluated = server.blind_evaluate(b.blinded_element()).unwrap();
+            client.finalize(b"password", &b, evaluated.as_slice(), None, info).unwrap()
+        } else {
+            let (evaluated, proof) = server.blind_evaluate_batch(&[b.blinded_element()], info, &mut rd).unwrap();
+            let proof = OprfProof::from_bytes(&group, proof.to_bytes(&group).as_slice()).unwrap();
+            let other = OprfServer::generate(group.clone(), mode, &mut rd).unwrap();
+            let (forged, forged_proof) = other.blind_evaluate_batch(&[b.blinded_element()], info, &mut rd).unwrap();
+            assert!(client.finalize(b"password", &b, forged[0].as_slice(), Some(&forged_proof), info).is_err(), "case: {:?}", mode);
+            assert!(client.finalize(b"password", &b, evaluated[0].as_slice(), None, info).is_err(), "case: {:?}", mode);
+            assert_eq!(server.blind_evaluate(b.blinded_element()).unwrap_err().kind(), CryptoErrorKind::NotSupportUsage);
+            client.finalize(b"password", &b, evaluated[0].as_slice(), Some(&proof), info).unwrap()
+        };
+
+        assert_eq!(out.len(), group.hash_len());
+        assert_eq!(server.evaluate(b"password", info).unwrap(), out, "case: {:?}", mode);
+        assert_ne!(server.evaluate(b"passw0rd", info).unwrap(), out, "case: {:?}", mode);
+        let restored = OprfServer::new(group.clone(), mode, server.private_key().as_slice()).unwrap();
+        assert_eq!(restored.evaluate(b"password", info).unwrap(), out, "case: {:?}", mode);
+    }
+
+    let group = P256Sha256::new().unwrap();
+    assert!(OprfClient::new(group.clone(), OprfMode::Voprf, None).is_err());
+    assert!(OprfServer::new(group.clone(), OprfMode::Oprf, &[0u8; 32]).is_err());
+    assert!(OprfServer::new(group.clone(), OprfMode::Oprf, &[0xffu8; 32]).is_err());
+    assert!(OprfServer::derive_key_pair(group.clone(), OprfMode::Oprf, &[0u8; 16], b"").is_err());
+    let server = OprfServer::generate(group.clone(), OprfMode::Oprf, &mut rd).unwrap();
+    assert!(server.evaluate(b"x", b"info").is_err());
+    // the x = 0x0202...02 is on the P-256, the x = 1 isn't
+    assert!(server.blind_evaluate(&[0x02; 33]).is_ok());
+    let mut not_on_curve = [0u8; 33];
+    not_on_curve[0] = 0x02;
+    not_on_curve[32] = 0x01;
+    assert_eq!(server.blind_evaluate(&not_on_curve).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(server.blind_evaluate(&[0x04; 33]).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+}
+
+#[test]
+fn oprf_psi() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let group = P256Sha256::new().unwrap();
+    let server = PsiServer::generate(group.clone(), &mut rd).unwrap();
+    let client = PsiClient::new(group.clone()).unwrap();
+    let server_items = ["alice", "bob", "carol", "dave", "bob"].iter().map(|x| x.as_bytes()).collect::<Vec<_>>();
+    let client_items = ["erin", "carol", "alice", "frank"].iter().map(|x| x.as_bytes()).collect::<Vec<_>>();
+
+    let set = server.encode_set(server_items.as_slice(), 16).unwrap();
+    assert_eq!((set.len(), set.tag_len()), (4, 16));
+    let set = PsiServerSet::from_bytes(set.to_bytes().as_slice()).unwrap();
+
+    let blinds = client.blind_batch(client_items.as_slice(), &mut rd).unwrap();
+    let blinded = blinds.iter().map(|b| b.blinded_element()).collect::<Vec<_>>();
+    let evaluated = server.blind_evaluate_batch(blinded.as_slice()).unwrap();
+    let evaluated = evaluated.iter().map(|e| e.as_slice()).collect::<Vec<_>>();
+    let outputs = client.finalize_batch(client_items.as_slice(), blinds.as_slice(), evaluated.as_slice()).unwrap();
+    assert_eq!(client.intersect(outputs.as_slice(), &set), vec![1, 2]);
+
+    // the set of the other key doesn't intersect
+    let other = PsiServer::generate(group.clone(), &mut rd).unwrap();
+    assert_eq!(PsiServer::new(group.clone(), server.private_key().as_slice()).unwrap().encode_set(server_items.as_slice(), 16).unwrap(), set);
+    let other_set = other.encode_set(server_items.as_slice(), 32).unwrap();
+    assert!(client.intersect(outputs.as_slice(), &other_set).is_empty());
+    assert!(client.finalize_batch(&client_items[1..], blinds.as_slice(), evaluated.as_slice()).is_err());
+
+    assert_eq!(server.encode_set(&[], PSI_MIN_TAG_LEN - 1).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(server.encode_set(&[], 33).is_err());
+    assert!(server.encode_set(&[], 8).unwrap().is_empty());
+    let mut buf = set.to_bytes();
+    // the first two tags are swapped
+    buf[1..33].rotate_left(16);
+    assert_eq!(PsiServerSet::from_bytes(buf.as_slice()).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(PsiServerSet::from_bytes(&buf[..20]).is_err());
+    assert!(PsiServerSet::from_bytes(&[]).is_err());
+}
\ No newline at end of file
@@ -0,0 +1,156 @@
+//! Private set intersection based on the OPRF
+//!
+//! The server publishes the tags $Trunc(F(k, y))$ of its set $Y$, the client obtains the $F(k, x)$ of its set $X$
+//! by the OPRF mode without revealing the $x$, and the elements whose tags are in the published tags are the
+//! intersection. The protocol is secure against the semi-honest parties only: the server learns the size of $X$,
+//! the client learns the size of $Y$ and the $X \cap Y$, the malicious server can evaluate by the different keys.
+//!
+//! The tags are truncated to `tag_len` bytes, the probability of the false positive is about
+//! $|X| |Y| / 2^{8 tag\_len}$. The large sets should be processed by the chunks of the `blind_batch`,
+//! `blind_evaluate_batch` and `finalize_batch`, e.g. 4096 elements per message.
+
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::oprf::{OprfGroup, OprfMode, OprfBlind, OprfServer, OprfClient};
+
+/// the minimum length of the tags in bytes
+pub const PSI_MIN_TAG_LEN: usize = 8;
+
+/// The sorted and deduplicated tags of the server set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PsiServerSet {
+    tag_len: usize,
+    tags: Vec<u8>,
+}
+
+impl PsiServerSet {
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /// the number of the tags
+    pub fn len(&self) -> usize {
+        self.tags.len() / self.tag_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    fn tag(&self, i: usize) -> &[u8] {
+        &self.tags[(i * self.tag_len)..((i + 1) * self.tag_len)]
+    }
+
+    /// whether the tag of the PRF output `output` is in the set
+    pub fn contains(&self, output: &[u8]) -> bool {
+        if output.len() < self.tag_len {
+            return false;
+        }
+
+        let tag = &output[..self.tag_len];
+        let (mut lo, mut hi) = (0, self.len());
+        while lo < hi {
+            let mid = (lo + hi) >> 1;
+            match self.tag(mid).cmp(tag) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return true,
+            }
+        }
+        false
+    }
+
+    /// `[tag_len]_8 || tag_1 || tag_2 || ...`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.tags.len() + 1);
+        buf.push(self.tag_len as u8);
+        buf.extend_from_slice(self.tags.as_slice());
+        buf
+    }
+
+    /// the tags must be sorted and distinct
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, CryptoError> {
+        let tag_len = buf.first().copied().unwrap_or(0) as usize;
+        if tag_len < PSI_MIN_TAG_LEN || !(buf.len() - 1).is_multiple_of(tag_len) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The buffer is not the encoded PSI server set"));
+        }
+
+        let set = Self { tag_len, tags: buf[1..].to_vec() };
+        if (1..set.len()).any(|i| set.tag(i - 1) >= set.tag(i)) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The tags of the PSI server set are not sorted and distinct"));
+        }
+
+        Ok(set)
+    }
+}
+
+/// The server evaluates its set and the blinded elements of the client by the OPRF mode
+pub struct PsiServer<G: OprfGroup> {
+    oprf: OprfServer<G>,
+}
+
+impl<G: OprfGroup> PsiServer<G> {
+    pub fn new(group: G, private_key: &[u8]) -> Result<Self, CryptoError> {
+        Ok(Self { oprf: OprfServer::new(group, OprfMode::Oprf, private_key)? })
+    }
+
+    pub fn generate<R: IterSource<u32>>(group: G, rd: &mut R) -> Result<Self, CryptoError> {
+        Ok(Self { oprf: OprfServer::generate(group, OprfMode::Oprf, rd)? })
+    }
+
+    pub fn private_key(&self) -> Vec<u8> {
+        self.oprf.private_key()
+    }
+
+    /// the tags of the `set`, the `tag_len` must be in the range `[PSI_MIN_TAG_LEN, Nh]`
+    pub fn encode_set(&self, set: &[&[u8]], tag_len: usize) -> Result<PsiServerSet, CryptoError> {
+        let h_len = self.oprf.group().hash_len();
+        if tag_len < PSI_MIN_TAG_LEN || tag_len > std::cmp::min(h_len, u8::MAX as usize) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong tag length: {}, it must be in the range [{}, {}]", tag_len, PSI_MIN_TAG_LEN, h_len)));
+        }
+
+        let mut tags = set.iter().map(|y| self.oprf.evaluate(y, &[]).map(|mut t| {t.truncate(tag_len); t}))
+            .collect::<Result<Vec<_>, _>>()?;
+        tags.sort_unstable();
+        tags.dedup();
+
+        Ok(PsiServerSet { tag_len, tags: tags.concat() })
+    }
+
+    /// evaluate the batch of the blinded elements of the client
+    pub fn blind_evaluate_batch(&self, blinded: &[&[u8]]) -> Result<Vec<Vec<u8>>, CryptoError> {
+        blinded.iter().map(|b| self.oprf.blind_evaluate(b)).collect()
+    }
+}
+
+/// The client blinds its set and computes the intersection by the published tags of the server
+pub struct PsiClient<G: OprfGroup> {
+    oprf: OprfClient<G>,
+}
+
+impl<G: OprfGroup> PsiClient<G> {
+    pub fn new(group: G) -> Result<Self, CryptoError> {
+        Ok(Self { oprf: OprfClient::new(group, OprfMode::Oprf, None)? })
+    }
+
+    /// blind the batch of the `inputs`, the blinded elements are sent to the server
+    pub fn blind_batch<R: IterSource<u32>>(&self, inputs: &[&[u8]], rd: &mut R) -> Result<Vec<OprfBlind>, CryptoError> {
+        inputs.iter().map(|x| self.oprf.blind(x, rd)).collect()
+    }
+
+    /// unblind the `evaluated` elements of the server to the PRF outputs of the `inputs`
+    pub fn finalize_batch(&self, inputs: &[&[u8]], blinds: &[OprfBlind], evaluated: &[&[u8]]) -> Result<Vec<Vec<u8>>, CryptoError> {
+        if inputs.len() != blinds.len() || inputs.len() != evaluated.len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The numbers of the inputs, blinds and evaluations are different"));
+        }
+
+        inputs.iter().zip(blinds.iter()).zip(evaluated.iter())
+            .map(|((x, b), e)| self.oprf.finalize(x, b, e, None, &[])).collect()
+    }
+
+    /// the indices of the `outputs` whose tags are in the server set
+    pub fn intersect(&self, outputs: &[Vec<u8>], server_set: &PsiServerSet) -> Vec<usize> {
+        outputs.iter().enumerate().filter(|(_, o)| server_set.contains(o)).map(|(i, _)| i).collect()
+    }
+}
@@ -0,0 +1,7 @@
+//! Certificate pinning
+
+mod spki_pin;
+pub use spki_pin::{SpkiPin, PinSet, rsa_spki_der, ec_spki_der, spki_from_certificate_der};
+
+#[cfg(test)]
+mod pin_test;
@@ -0,0 +1,80 @@
+use crate::pin::{SpkiPin, PinSet, rsa_spki_der, spki_from_certificate_der};
+use crate::elliptic::{CurveP256, CurveParams, PublicKey};
+use rmath::bigint::BigInt;
+use std::str::FromStr;
+use crate::test_util::hex;
+
+fn ec_cert() -> Vec<u8> {
+    hex(concat!(
+        "308201173081bea00302010202021234300a06082a8648ce3d04030230153113301106035504030c0a65632e6578616d706c65301e170d32",
+        "34303130313030303030305a170d3333313232393030303030305a30153113301106035504030c0a65632e6578616d706c65305930130607",
+        "2a8648ce3d020106082a8648ce3d03010703420004471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714cdd520f",
+        "7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858300a06082a8648ce3d040302034800304502203517d4ec12dbce65",
+        "70d71dcfd4da6f7db2a7c764ec20ec92b5a99da213673e37022100bf76f66a984fa9698a9365dc241284bbcd1ffe85a5dfdc56c43a110e9f",
+        "c98f4a",
+    ))
+}
+
+fn rsa_cert() -> Vec<u8> {
+    hex(concat!(
+        "308202a63082018ea00302010202021234300d06092a864886f70d01010b050030163114301206035504030c0b7273612e6578616d706c65",
+        "301e170d3234303130313030303030305a170d3333313232393030303030305a30163114301206035504030c0b7273612e6578616d706c65",
+        "30820122300d06092a864886f70d01010105000382010f003082010a0282010100b21f78c81316db861fbd227c8d8ad9c9681469bfca1c91",
+        "71de88634f40fd286f945fb7abede0e75ae5dfc8d1a2b4b37e48b9b05e4e46c1213a317d8ce2a1c45cafdb5f95c4fa4bdb359bdcfec74654",
+        "b0be1ff8f9fa4ca67639ba7e12ffc5bb51bb73161e9ec6a37969ce997c40281588b9aec612e85ae024d1a4a53549dc400c54592fa3ebf16a",
+        "2e546ea3c59dfdb625bce9718f83095b49975904984f1dfc094cb9fe607e07f55052c8a1d4ada608c627b82d4f14a8a3b45d2eb8f262f96d",
+        "00a66411b19f9a002a822ebcf7d24999c3a72ea7dce314cf6e7cce8755ff3338b05be95de30bb0e82873e6faabf2db9b5c4be02214aab3fd",
+        "7629020912c7722de90203010001300d06092a864886f70d01010b0500038201010065e804613ec44acb051e9d4901f9e7c7201d98322478",
+        "13d283b80be1ec903ce8342723073b11630717f2f829041f0c2fa48b0eac798969be5d660b11537020b7d3e04b080ae4fbc737436f126afe",
+        "157508cfc8ad2bf725a92eb5c438bbd57123d56208f9ccc6d3bb6085b0bcd9116425906610449959a0b161077324ec123ea756f394cbcbb1",
+        "a4336272502ae85f3981152d5ed99b24395f7c9eb389f504e2b5c975bdcccdf5ae6fef7ae47db6e9136d9557b80e3aad74a03248e40002de",
+        "5727472d41e204d89cb719c9b756f8a4cff833dd6f211d4507da2dc5c12521efa5c1e422e4def805f1f7b2af8ea0822ff5718b3e7cc64ffe",
+        "afb8b690b28e2a1c4169",
+    ))
+}
+
+#[test]
+fn spki_pin() {
+    let (ec_cert, rsa_cert) = (ec_cert(), rsa_cert());
+    let ec_pin = SpkiPin::from_certificate_der(ec_cert.as_slice()).unwrap();
+    assert_eq!(ec_pin.to_base64(), "xNkRQyMNMweAkO2JwgceuKe+Klo/I4y9qi+LJ59KlHY=");
+    assert_eq!(ec_pin.to_string(), "sha256/xNkRQyMNMweAkO2JwgceuKe+Klo/I4y9qi+LJ59KlHY=");
+    let rsa_pin = SpkiPin::from_certificate_der(rsa_cert.as_slice()).unwrap();
+    assert_eq!(rsa_pin.to_base64(), "udfiOuIbq3d0ZG4cXtyLL8vOm/br2YPQSCiLfMPboTg=");
+    
+    // from the raw public keys
+    let n = BigInt::from_str("22485937608379408602785224294387499228773708418664672368877198736883266813600218936647084612994286885856771610397731793135777520438399990306197711569611564618294978984488478428858494409529380841143585632351398889250993973178926809698790598260963006611561904304985435568878202690593652035235447909005416974189858790400851939523080582888871115621097020645846621634036478998513647154398732472616898123627143158722279775130130307877143321227768469616437509945081289674581519704848889038008639722794372954392534156310188659505687917078613178039068167147288685826720304716036879587454033257245391010450308259725005361917417").unwrap();
+    let pk = crate::rsa::PublicKey::from_bigint(&n, &BigInt::from(65537u32)).unwrap();
+    assert_eq!(SpkiPin::from_rsa_public_key(&pk), rsa_pin);
+    let spki = rsa_spki_der(&pk);
+    assert_eq!(spki.as_slice(), spki_from_certificate_der(rsa_cert.as_slice()).unwrap());
+    
+    let pk = PublicKey::new_uncheck(&BigInt::from_be_bytes(hex("471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714c").as_slice()),
+        &BigInt::from_be_bytes(hex("dd520f7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858").as_slice()));
+    assert_eq!(SpkiPin::from_ec_public_key(&CurveP256::new().unwrap(), &pk).unwrap(), ec_pin);
+    // not on the P-384
+    assert!(SpkiPin::from_ec_public_key(&CurveParams::p384().unwrap(), &pk).is_err());
+    
+    let pk = PublicKey::new_uncheck(&BigInt::from_be_bytes(hex("fbf96095915eb587390f850ec95b35f618ae121c72a70808ad8f9f4c261b602b63541743e395f7c778e52bb28c18dc83").as_slice()),
+        &BigInt::from_be_bytes(hex("016a6ff3f72c9368326ad50a3bd148a53cd2213bb480e4730ad019db87ae100995ec65df011e19811a15dc9ddfc9ab3c").as_slice()));
+    let ec3_pin = SpkiPin::from_ec_public_key(&CurveParams::p384().unwrap(), &pk).unwrap();
+    assert_eq!(ec3_pin.to_base64(), "7LtWJkQnjh0J8r+VEfbpzeisRdc8cYRJXEUV/P1DGM8=");
+    
+    assert_eq!(SpkiPin::from_base64("sha256/xNkRQyMNMweAkO2JwgceuKe+Klo/I4y9qi+LJ59KlHY=").unwrap(), ec_pin);
+    assert!(SpkiPin::from_base64("AAAA").is_err());
+    assert!(SpkiPin::from_certificate_der(&ec_cert[..(ec_cert.len() - 1)]).is_err());
+}
+
+#[test]
+fn pin_set() {
+    let (ec_cert, rsa_cert) = (ec_cert(), rsa_cert());
+    let set = PinSet::from_base64(&["7LtWJkQnjh0J8r+VEfbpzeisRdc8cYRJXEUV/P1DGM8=", "sha256/xNkRQyMNMweAkO2JwgceuKe+Klo/I4y9qi+LJ59KlHY="]).unwrap();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&SpkiPin::from_base64("xNkRQyMNMweAkO2JwgceuKe+Klo/I4y9qi+LJ59KlHY=").unwrap()));
+    assert!(!set.contains(&SpkiPin::from_base64("udfiOuIbq3d0ZG4cXtyLL8vOm/br2YPQSCiLfMPboTg=").unwrap()));
+    
+    assert!(set.matches_certificate_chain(&[rsa_cert.as_slice(), ec_cert.as_slice()]).unwrap());
+    assert!(!set.matches_certificate_chain(&[rsa_cert.as_slice()]).unwrap());
+    assert!(!PinSet::new().matches_certificate_chain(&[ec_cert.as_slice()]).unwrap());
+    assert!(PinSet::from_base64(&["not a pin"]).is_err());
+}
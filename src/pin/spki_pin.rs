@@ -0,0 +1,217 @@
+//! SPKI pinning  
+//! RFC 7469 2.4
+//! 
+//! pin = base64(SHA-256(DER(SubjectPublicKeyInfo))), which is the same as the HPKP `pin-sha256`, the Android
+//! network security config `<pin digest="SHA-256">` and the OkHttp `sha256/` pins.
+
+use crate::{CryptoError, CryptoErrorKind, Digest};
+use crate::sha::SHA256;
+use crate::encoding::Base64;
+use crate::encoding::der::{self, DerReader};
+use crate::elliptic::EllipticCurve;
+use crate::oid::Oid;
+
+
+const SPKI_PIN_PREFIX: &str = "sha256/";
+
+/// DER encode the `SubjectPublicKeyInfo` of the `rsaEncryption` public key(RFC 8017 Appendix A.1)
+pub fn rsa_spki_der(key: &crate::rsa::PublicKey) -> Vec<u8> {
+    let mut rsa_pk = Vec::new();
+    der::encode_unsigned_integer(key.modulus().to_be_bytes().as_slice(), &mut rsa_pk);
+    der::encode_unsigned_integer(key.exponent().to_be_bytes().as_slice(), &mut rsa_pk);
+    let (mut seq, mut bits) = (Vec::new(), vec![0u8]);
+    der::encode_tlv(der::TAG_SEQUENCE, rsa_pk.as_slice(), &mut seq);
+    bits.append(&mut seq);
+    
+    let mut alg = Vec::new();
+    der::encode_tlv(der::TAG_OID, Oid::RSA_ENCRYPTION.as_der(), &mut alg);
+    der::encode_tlv(der::TAG_NULL, &[], &mut alg);
+    
+    let mut spki = Vec::new();
+    der::encode_tlv(der::TAG_SEQUENCE, alg.as_slice(), &mut spki);
+    der::encode_tlv(der::TAG_BIT_STRING, bits.as_slice(), &mut spki);
+    let mut dst = Vec::with_capacity(spki.len() + 4);
+    der::encode_tlv(der::TAG_SEQUENCE, spki.as_slice(), &mut dst);
+    dst
+}
+
+/// DER encode the `SubjectPublicKeyInfo` of the `id-ecPublicKey` public key with the named curve(RFC 5480),
+/// the public key is encoded as the uncompressed point.
+pub fn ec_spki_der<C: EllipticCurve>(curve: &C, key: &crate::elliptic::PublicKey) -> Result<Vec<u8>, CryptoError> {
+    let params = curve.curve_params();
+    let oid = match params.name() {
+        "P-224" => Oid::P224,
+        "P-256" => Oid::P256,
+        "P-384" => Oid::P384,
+        "P-521" => Oid::P521,
+        "secp256k1" => Oid::SECP256K1,
+        "brainpoolP256r1" => Oid::BRAINPOOL_P256R1,
+        "brainpoolP384r1" => Oid::BRAINPOOL_P384R1,
+        "brainpoolP512r1" => Oid::BRAINPOOL_P512R1,
+        "sm2p256v1" => Oid::SM2P256V1,
+        name => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage,
+            format!("The curve {} has no named curve OID", name))),
+    };
+    
+    let len = (params.field_bits_size() + 7) >> 3;
+    let (x, y) = (key.qx.to_be_bytes(), key.qy.to_be_bytes());
+    if x.len() > len || y.len() > len || !curve.is_on_curve(&key.qx, &key.qy) {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The public key is not on the curve"));
+    }
+    let mut bits = vec![0u8; 2 + (len << 1)];
+    bits[1] = 0x04;
+    bits[(2 + len - x.len())..(2 + len)].copy_from_slice(x.as_slice());
+    bits[(2 + (len << 1) - y.len())..].copy_from_slice(y.as_slice());
+    
+    let mut alg = Vec::new();
+    der::encode_tlv(der::TAG_OID, Oid::EC_PUBLIC_KEY.as_der(), &mut alg);
+    der::encode_tlv(der::TAG_OID, oid.as_der(), &mut alg);
+    
+    let mut spki = Vec::new();
+    der::encode_tlv(der::TAG_SEQUENCE, alg.as_slice(), &mut spki);
+    der::encode_tlv(der::TAG_BIT_STRING, bits.as_slice(), &mut spki);
+    let mut dst = Vec::with_capacity(spki.len() + 4);
+    der::encode_tlv(der::TAG_SEQUENCE, spki.as_slice(), &mut dst);
+    Ok(dst)
+}
+
+/// extract the DER encoded `SubjectPublicKeyInfo` from the DER encoded X.509 certificate(RFC 5280 4.1)
+pub fn spki_from_certificate_der(cert: &[u8]) -> Result<&[u8], CryptoError> {
+    let mut r = DerReader::new(cert);
+    let c = r.read_expect(der::TAG_SEQUENCE)?;
+    if !r.is_empty() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Trailing bytes after the certificate"));
+    }
+    
+    let mut c = DerReader::new(c.value);
+    let mut tbs = DerReader::new(c.read_expect(der::TAG_SEQUENCE)?.value);
+    // version, serialNumber, signature, issuer, validity, subject
+    tbs.read_optional(der::tag_context(0))?;
+    tbs.read_expect(der::TAG_INTEGER)?;
+    for _ in 0..4 {
+        tbs.read_expect(der::TAG_SEQUENCE)?;
+    }
+    let spki = tbs.read_expect(der::TAG_SEQUENCE)?;
+    Ok(spki.raw)
+}
+
+/// The SHA-256 pin of the SubjectPublicKeyInfo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SpkiPin {
+    hash: [u8; 32],
+}
+
+impl SpkiPin {
+    /// `spki`: the DER encoded `SubjectPublicKeyInfo`
+    pub fn from_spki_der(spki: &[u8]) -> Result<Self, CryptoError> {
+        let mut r = DerReader::new(spki);
+        let s = r.read_expect(der::TAG_SEQUENCE)?;
+        let mut s = DerReader::new(s.value);
+        s.read_expect(der::TAG_SEQUENCE)?;
+        s.read_expect(der::TAG_BIT_STRING)?;
+        if !r.is_empty() || !s.is_empty() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid SubjectPublicKeyInfo"));
+        }
+        
+        let (mut sha, mut h) = (SHA256::new(), Vec::with_capacity(32));
+        sha.write(spki);
+        sha.checksum(&mut h);
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(h.as_slice());
+        Ok(Self { hash })
+    }
+    
+    /// `cert`: the DER encoded X.509 certificate
+    pub fn from_certificate_der(cert: &[u8]) -> Result<Self, CryptoError> {
+        Self::from_spki_der(spki_from_certificate_der(cert)?)
+    }
+    
+    pub fn from_rsa_public_key(key: &crate::rsa::PublicKey) -> Self {
+        Self::from_spki_der(rsa_spki_der(key).as_slice()).unwrap()
+    }
+    
+    pub fn from_ec_public_key<C: EllipticCurve>(curve: &C, key: &crate::elliptic::PublicKey) -> Result<Self, CryptoError> {
+        Self::from_spki_der(ec_spki_der(curve, key)?.as_slice())
+    }
+    
+    /// parse the base64 encoded pin, the `sha256/` prefix is optional
+    pub fn from_base64(pin: &str) -> Result<Self, CryptoError> {
+        let pin = pin.strip_prefix(SPKI_PIN_PREFIX).unwrap_or(pin);
+        let h = Base64::standard().decode(pin)?;
+        if h.len() != 32 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The pin must be a SHA-256 hash"));
+        }
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(h.as_slice());
+        Ok(Self { hash })
+    }
+    
+    pub fn to_base64(&self) -> String {
+        Base64::standard().encode(self.hash.as_ref())
+    }
+    
+    pub fn as_bytes(&self) -> &[u8] {
+        self.hash.as_ref()
+    }
+    
+    /// compare the pins in constant time
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.hash.iter().zip(other.hash.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) == 0
+    }
+}
+
+impl std::fmt::Display for SpkiPin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", SPKI_PIN_PREFIX, self.to_base64())
+    }
+}
+
+/// The set of the trusted pins, the match compares the candidate with every pin in the set, so the time doesn't
+/// depend on which pin is matched.
+#[derive(Clone, Default)]
+pub struct PinSet {
+    pins: Vec<SpkiPin>,
+}
+
+impl PinSet {
+    pub fn new() -> Self {
+        Self {
+            pins: Vec::new(),
+        }
+    }
+    
+    /// `pins`: the base64 encoded pins
+    pub fn from_base64(pins: &[&str]) -> Result<Self, CryptoError> {
+        let mut set = Self::new();
+        for p in pins.iter() {
+            set.add(SpkiPin::from_base64(p)?);
+        }
+        Ok(set)
+    }
+    
+    pub fn add(&mut self, pin: SpkiPin) {
+        self.pins.push(pin);
+    }
+    
+    pub fn len(&self) -> usize {
+        self.pins.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+    
+    pub fn contains(&self, pin: &SpkiPin) -> bool {
+        self.pins.iter().fold(false, |a, p| a | p.ct_eq(pin))
+    }
+    
+    /// verify that any certificate(DER encoded) in the chain matches the pin set,
+    /// the chain should have been validated before the pin check.
+    pub fn matches_certificate_chain(&self, chain: &[&[u8]]) -> Result<bool, CryptoError> {
+        let mut is_match = false;
+        for cert in chain.iter() {
+            is_match |= self.contains(&SpkiPin::from_certificate_der(cert)?);
+        }
+        Ok(is_match)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+//! Prime numbers generation
+//! 
+//! - random prime with the specified bits length;
+//! - safe prime `p = 2q + 1` and Sophie Germain prime `q`;
+//! - prime congruent to the specified residue `p ≡ r (mod m)`;
+
+mod prime;
+pub use prime::{PrimeGenerator, is_probably_prime};
+#[cfg(feature = "ec")]
+pub(crate) use prime::sqrt_mod_prime;
+
+#[cfg(test)]
+mod prime_test;
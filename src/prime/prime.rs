@@ -0,0 +1,377 @@
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const SMALL_PRIMES: [u32; 53] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
+];
+
+/// the number of the candidates which are drawn from the random source at once by the `generate_parallel`
+#[cfg(feature = "parallel")]
+const PARALLEL_BATCH_LEN: usize = 256;
+
+/// probability prime test by the Miller-Rabin algorithm with `max(test_round_num, 1)` random bases,
+/// the probability of error is at most $4^{-test_round_num}$(FIPS 186-4 C.3.1).
+/// The `n` less than $2^{32}$ is tested by the trial division. The prime test of the `rmath` is not used, because
+/// it rejects some small primes(e.g. 227, 251) and the primes of the special form(e.g. the field orders of the
+/// P-224 and P-256).
+pub fn is_probably_prime<R: IterSource<u32>>(n: &BigInt, test_round_num: usize, rd: &mut R) -> bool {
+    if n.is_nan() || !n.is_positive() {
+        false
+    } else if n.bits_len() <= 32 {
+        let n = n.to_be_bytes().iter().fold(0u64, |x, &b| (x << 8) | (b as u64));
+        n > 1 && (2..).take_while(|&d| d * d <= n).all(|d| n % d != 0)
+    } else if n.to_be_bytes().last().copied().unwrap_or(0) & 1 == 0 {
+        false
+    } else {
+        miller_rabin(n, test_round_num.max(1), rd)
+    }
+}
+
+/// `x = 2^a * m` where the `m` is odd, the `x` must be positive. The trailing zero bytes are dropped rather than
+/// shifted, because the `>>` of the `rmath` is wrong for some large shifts(e.g. the $p - 1$ of the P-224 field order).
+fn split_two_adic(x: &BigInt) -> (usize, BigInt) {
+    let bytes = x.to_be_bytes();
+    let zero_bytes = bytes.iter().rev().take_while(|&&b| b == 0).count();
+    let bits = bytes[bytes.len() - 1 - zero_bytes].trailing_zeros() as usize;
+    ((zero_bytes << 3) + bits, BigInt::from_be_bytes(&bytes[..(bytes.len() - zero_bytes)]) >> bits)
+}
+
+/// the square root `r` of `a` modulo the odd prime `p`($r^2 \equiv a \mod p$) by the Tonelli-Shanks algorithm,
+/// `None` if the `a` is a quadratic non-residue. It's not constant-time, so the `a` must be public(e.g. the
+/// x-coordinate of the point decompression).
+#[cfg(feature = "ec")]
+pub(crate) fn sqrt_mod_prime(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let (one, two) = (BigInt::from(1u32), BigInt::from(2u32));
+    let mul_mod = |x: &BigInt, y: &BigInt| {
+        let mut z = x.clone() * y.clone();
+        z.rem_euclid_assign(p.clone());
+        z
+    };
+    let mut a = a.deep_clone();
+    a.rem_euclid_assign(p.clone());
+    if a.signnum() != Some(1) {
+        return Some(a);
+    }
+    
+    // Euler's criterion
+    let p_m1 = p.clone() - one.clone();
+    if a.exp(&(p_m1.clone() >> 1usize), p) != one {
+        return None;
+    }
+    
+    // p ≡ 3 (mod 4): r = a^{(p+1)/4}
+    if p.to_be_bytes().last().copied().unwrap_or(0) & 3 == 3 {
+        return Some(a.exp(&((p.clone() + one) >> 2usize), p));
+    }
+    
+    let (mut m, q) = split_two_adic(&p_m1);
+    let mut z = two;
+    while z.exp(&(p_m1.clone() >> 1usize), p) == one {
+        z += BigInt::from(1u32);
+    }
+    
+    let (mut c, mut t, mut r) = (z.exp(&q, p), a.exp(&q, p), a.exp(&((q.clone() + one.clone()) >> 1usize), p));
+    while t != one {
+        // the least i that t^{2^i} = 1, 0 < i < m
+        let (mut i, mut t2) = (0usize, t.deep_clone());
+        while t2 != one {
+            t2 = mul_mod(&t2, &t2);
+            i += 1;
+            if i >= m {
+                return None;
+            }
+        }
+        
+        let mut b = c;
+        for _ in 0..(m - i - 1) {
+            b = mul_mod(&b, &b);
+        }
+        r = mul_mod(&r, &b);
+        c = mul_mod(&b, &b);
+        t = mul_mod(&t, &c);
+        m = i;
+    }
+    
+    Some(r)
+}
+
+/// FIPS 186-4 C.3.1, the `n` is an odd number greater than $2^{32}$
+fn miller_rabin<R: IterSource<u32>>(n: &BigInt, rounds: usize, rd: &mut R) -> bool {
+    let (one, two) = (BigInt::from(1u32), BigInt::from(2u32));
+    let n_m1 = n.clone() - one.clone();
+    
+    // n - 1 = 2^a * m, the m is odd
+    let (a, m) = split_two_adic(&n_m1);
+    
+    // the base b is drawn from [2, n-2] by the extra 64 bits to reduce the bias
+    let (words, range) = ((n.bits_len() + 95) >> 5, n.clone() - BigInt::from(3u32));
+    let mut buf = Vec::with_capacity(words);
+    for _ in 0..rounds {
+        buf.clear();
+        rd.iter_mut().take(words).for_each(|x| buf.push(x));
+        buf.resize(words, 0);
+        let mut b = BigInt::from(buf.clone());
+        b.rem_euclid_assign(range.clone());
+        b += two.clone();
+        
+        let mut z = b.exp(&m, n);
+        if z == one || z == n_m1 {
+            continue;
+        }
+        
+        let mut is_composite = true;
+        for _ in 1..a {
+            z = z.sqr();
+            z.rem_euclid_assign(n.clone());
+            if z == n_m1 {
+                is_composite = false;
+                break;
+            }
+            if z == one {
+                break;
+            }
+        }
+        
+        if is_composite {
+            return false;
+        }
+    }
+    
+    true
+}
+
+/// The prime numbers generator, the `progress` callback will be called with the number of
+/// candidates which has tested when a candidate is rejected.
+pub struct PrimeGenerator<'a, R> {
+    rd: R,
+    test_round_num: usize,
+    // return `false` to cancel the generation
+    progress: Option<Box<dyn FnMut(usize) -> bool + 'a>>,
+    candidates: usize,
+}
+
+impl<'a, R: IterSource<u32>> PrimeGenerator<'a, R> {
+    /// `test_round_num`: the number of the Miller-Rabin test rounds for each candidate
+    pub fn new(rd: R, test_round_num: usize) -> Self {
+        Self {
+            rd,
+            test_round_num,
+            progress: None,
+            candidates: 0,
+        }
+    }
+    
+    pub fn set_progress<F: FnMut(usize) + 'a>(&mut self, mut progress: F) {
+        self.progress = Some(Box::new(move |c| {
+            progress(c);
+            true
+        }));
+    }
+    
+    /// the generation is cancelled with the `Cancelled` error when the `progress` returns `false`, e.g. the deadline
+    /// is reached or the user aborts the operation. The state of the random source is undefined after cancelled.
+    pub fn set_cancellable_progress<F: FnMut(usize) -> bool + 'a>(&mut self, progress: F) {
+        self.progress = Some(Box::new(progress));
+    }
+    
+    pub fn clear_progress(&mut self) {
+        self.progress = None;
+    }
+    
+    /// the number of candidates have been tested since the generator created
+    pub fn candidates(&self) -> usize {
+        self.candidates
+    }
+    
+    pub fn into_inner(self) -> R {
+        self.rd
+    }
+    
+    fn reject(&mut self) -> Result<(), CryptoError> {
+        self.candidates += 1;
+        let c = self.candidates;
+        let is_continued = match self.progress.as_mut() {
+            Some(f) => f(c),
+            None => true,
+        };
+        
+        if is_continued {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::Cancelled, format!("The prime generation is cancelled after {} candidates", c)))
+        }
+    }
+    
+    /// random odd number in the range $[2^{bits_len-1}, 2^{bits_len})$ with the most significant two bits set
+    fn random_odd(&mut self, bits_len: usize) -> BigInt {
+        BigInt::from(self.random_odd_words(bits_len))
+    }
+    
+    /// the little-endian 32-bit words of the `random_odd`
+    fn random_odd_words(&mut self, bits_len: usize) -> Vec<u32> {
+        let len = (bits_len + 31) >> 5;
+        let mut words = Vec::with_capacity(len);
+        self.rd.iter_mut().take(len).for_each(|x| words.push(x));
+        words.resize(len, 0);
+        
+        let b = bits_len - ((len - 1) << 5);
+        if b < 32 {
+            words[len - 1] &= (1u32 << b) - 1;
+        }
+        if bits_len >= 2 {
+            let i = bits_len - 2;
+            words[i >> 5] |= 1 << (i & 31);
+        }
+        let i = bits_len - 1;
+        words[i >> 5] |= 1 << (i & 31);
+        words[0] |= 1;
+        
+        words
+    }
+    
+    fn is_small_prime_divisible(n: &BigInt) -> bool {
+        SMALL_PRIMES.iter().any(|&p| {
+            let p = BigInt::from(p);
+            n > &p && n.rem_euclid(p) == 0u32
+        })
+    }
+    
+    /// generate a random prime with `bits_len` bits, the most significant two bits of the prime are set, 
+    /// so the product of two such primes has `2*bits_len` bits.
+    pub fn generate(&mut self, bits_len: usize) -> Result<BigInt, CryptoError> {
+        if bits_len < 3 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "prime size must at least 3-bits"));
+        }
+        
+        loop {
+            let p = self.random_odd(bits_len);
+            if !Self::is_small_prime_divisible(&p) && is_probably_prime(&p, self.test_round_num, &mut self.rd) {
+                return Ok(p);
+            }
+            self.reject()?;
+        }
+    }
+    
+    /// generate a random safe prime `p = 2q + 1` with `bits_len` bits, where the `q` is also a prime.  
+    /// return `(p, q)`
+    pub fn generate_safe_prime(&mut self, bits_len: usize) -> Result<(BigInt, BigInt), CryptoError> {
+        if bits_len < 4 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "safe prime size must at least 4-bits"));
+        }
+        
+        loop {
+            let q = self.random_odd(bits_len - 1);
+            let p = (q.clone() << 1usize) + BigInt::from(1u32);
+            
+            // cheap tests at first, the `p` and `q` both need to be coprime to the small primes 
+            if Self::is_small_prime_divisible(&q) || Self::is_small_prime_divisible(&p) ||
+                !is_probably_prime(&q, 1, &mut self.rd) ||
+                !is_probably_prime(&p, 1, &mut self.rd) {
+                self.reject()?;
+                continue;
+            }
+            
+            if is_probably_prime(&q, self.test_round_num, &mut self.rd) && is_probably_prime(&p, self.test_round_num, &mut self.rd) {
+                return Ok((p, q));
+            }
+            self.reject()?;
+        }
+    }
+    
+    /// generate a random Sophie Germain prime `q` with `bits_len` bits, where the `2q + 1` is also a prime.  
+    pub fn generate_sophie_germain_prime(&mut self, bits_len: usize) -> Result<BigInt, CryptoError> {
+        self.generate_safe_prime(bits_len + 1).map(|(_, q)| q)
+    }
+    
+    /// generate a random prime `p ≡ residue (mod modulus)` with `bits_len` bits,
+    /// the `residue` and `modulus` need to be coprime and `modulus.bits_len() < bits_len`.
+    pub fn generate_congruent(&mut self, bits_len: usize, residue: &BigInt, modulus: &BigInt) -> Result<BigInt, CryptoError> {
+        if residue.is_nan() || modulus.is_nan() || !modulus.is_positive() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The modulus must be positive"));
+        }
+        
+        if bits_len < 3 || modulus.bits_len() >= bits_len {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+                format!("The bits length of modulus must be less than the prime size {}", bits_len)));
+        }
+        
+        let r = residue.rem_euclid(modulus.clone());
+        let (g, _, _) = r.gcd(modulus.clone());
+        if g != 1u32 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The residue and modulus must be coprime"));
+        }
+        
+        // p = r + k*m, 2^{bits_len-1} <= p < 2^{bits_len}
+        let lower = BigInt::from(1u32) << (bits_len - 1);
+        let k_min = (lower.clone() - r.clone() + modulus.clone() - BigInt::from(1u32)).div_euclid(modulus.clone());
+        let k_max = ((lower << 1usize) - BigInt::from(1u32) - r.clone()).div_euclid(modulus.clone());
+        if k_max < k_min {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "There is no candidate in the range"));
+        }
+        let k_range = k_max.clone() - k_min.clone() + BigInt::from(1u32);
+        
+        loop {
+            let k = k_range.random(&mut self.rd) + k_min.clone();
+            let p = k * modulus.clone() + r.clone();
+            if !Self::is_small_prime_divisible(&p) && is_probably_prime(&p, self.test_round_num, &mut self.rd) {
+                return Ok(p);
+            }
+            self.reject()?;
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<'a, R: IterSource<u32> + Clone + Send> PrimeGenerator<'a, R> {
+    /// generate a random prime with `bits_len` bits like the `generate`, the candidates are tested by the `threads`
+    /// worker threads concurrently.  
+    /// The candidates are drawn from the random source in batches of `PARALLEL_BATCH_LEN`, and the first prime in the
+    /// drawing order is returned, so the prime only depends on the random source, not on the number of threads
+    /// or the thread scheduling. The workers use the clones of the random source in the Miller-Rabin test.
+    pub fn generate_parallel(&mut self, bits_len: usize, threads: usize) -> Result<BigInt, CryptoError> {
+        if bits_len < 3 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "prime size must at least 3-bits"));
+        } else if threads == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The number of threads must be great than 0"));
+        }
+        
+        loop {
+            // the `BigInt` cannot be sent to the other threads, so the candidates are shared as the words
+            let batch = (0..PARALLEL_BATCH_LEN).map(|_| self.random_odd_words(bits_len)).collect::<Vec<_>>();
+            let first = AtomicUsize::new(batch.len());
+            
+            std::thread::scope(|s| {
+                for w in 0..threads {
+                    let (batch, first, mut rd, round_num) = (&batch, &first, self.rd.clone(), self.test_round_num);
+                    s.spawn(move || {
+                        for i in (w..batch.len()).step_by(threads) {
+                            // the candidates after the found prime are not needed
+                            if i >= first.load(Ordering::Acquire) {
+                                break;
+                            }
+                            
+                            let p = BigInt::from(batch[i].clone());
+                            if !Self::is_small_prime_divisible(&p) && is_probably_prime(&p, round_num, &mut rd) {
+                                first.fetch_min(i, Ordering::AcqRel);
+                                break;
+                            }
+                        }
+                    });
+                }
+            });
+            
+            let first = first.into_inner();
+            for _ in 0..first {
+                self.reject()?;
+            }
+            if first < batch.len() {
+                return Ok(BigInt::from(batch[first].clone()));
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::prime::{PrimeGenerator, is_probably_prime};
+use rmath::bigint::BigInt;
+use rmath::rand::{DefaultSeed, CryptoRand};
+use std::cell::Cell;
+
+#[test]
+fn prime_generation() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let called = Cell::new(0usize);
+    let mut pg = PrimeGenerator::new(rd.clone(), 20);
+    pg.set_progress(|x| called.set(x));
+    
+    for &bits in [3usize, 17, 32, 33, 127, 256].iter() {
+        let p = pg.generate(bits).unwrap();
+        assert_eq!(p.bits_len(), bits, "case: {}", bits);
+        assert!(is_probably_prime(&p, 20, &mut rd), "case: {}", bits);
+    }
+    assert_eq!(pg.candidates(), called.get());
+    assert!(called.get() > 0);
+    assert!(pg.generate(2).is_err());
+    assert!(!is_probably_prime(&BigInt::from(561u32), 20, &mut rd));
+    assert!((0..64).all(|_| is_probably_prime(&BigInt::from(227u32), 20, &mut rd)));
+}
+
+#[test]
+fn safe_prime_generation() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let mut pg = PrimeGenerator::new(rd.clone(), 20);
+    
+    for &bits in [8usize, 64, 128].iter() {
+        let (p, q) = pg.generate_safe_prime(bits).unwrap();
+        assert_eq!(p.bits_len(), bits, "case: {}", bits);
+        assert_eq!(p, (q.clone() << 1usize) + BigInt::from(1u32), "case: {}", bits);
+        assert!(is_probably_prime(&p, 20, &mut rd) && is_probably_prime(&q, 20, &mut rd), "case: {}", bits);
+    }
+    
+    let q = pg.generate_sophie_germain_prime(63).unwrap();
+    assert_eq!(q.bits_len(), 63);
+    assert!(is_probably_prime(&((q.clone() << 1usize) + BigInt::from(1u32)), 20, &mut rd));
+}
+
+#[test]
+fn congruent_prime_generation() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let mut pg = PrimeGenerator::new(rd.clone(), 20);
+    
+    let cases = [(3u32, 4u32), (1, 65537), (5, 12), (65536, 65537)];
+    for &(r, m) in cases.iter() {
+        let (r, m) = (BigInt::from(r), BigInt::from(m));
+        let p = pg.generate_congruent(96, &r, &m).unwrap();
+        assert_eq!(p.bits_len(), 96);
+        assert_eq!(p.rem_euclid(m.clone()), r);
+        assert!(is_probably_prime(&p, 20, &mut rd));
+    }
+    
+    assert!(pg.generate_congruent(96, &BigInt::from(2u32), &BigInt::from(4u32)).is_err());
+    assert!(pg.generate_congruent(16, &BigInt::from(1u32), &BigInt::from(65537u32)).is_err());
+}
+
+
+#[test]
+fn prime_special_form() {
+    use std::str::FromStr;
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    
+    // the field orders of the P-224(n - 1 = 2^96 * m), P-256 and P-521, the Mersenne prime 2^127 - 1
+    let primes = [
+        "26959946667150639794667015087019630673557916260026308143510066298881",
+        "115792089210356248762697446949407573530086143415290314195533631308867097853951",
+        "6864797660130609714981900799081393217269435300143305409394463459185543183397656052122559640661454554977296311391480858037121987999716643812574028291115057151",
+        "170141183460469231731687303715884105727",
+    ].iter().map(|s| BigInt::from_str(s).unwrap()).collect::<Vec<_>>();
+    for p in primes.iter() {
+        assert!(is_probably_prime(p, 20, &mut rd), "{}", p);
+    }
+    
+    assert!(!is_probably_prime(&(primes[0].clone() * primes[3].clone()), 20, &mut rd));
+    assert!(!is_probably_prime(&(primes[1].clone() + BigInt::from(2u32)), 20, &mut rd));
+    assert!(!is_probably_prime(&(primes[3].clone() + BigInt::from(1u32)), 20, &mut rd));
+    // the strong pseudoprime to the bases 2, 3, 5, 7, 11, 13, 17, 19, 23
+    assert!(!is_probably_prime(&BigInt::from_str("3825123056546413051").unwrap(), 20, &mut rd));
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+//! The crate internal random sources
+
+use rmath::rand::{IterSource, Source, Seed, Iter, RandError, RandErrKind};
+
+/// The random source which is only used to construct the verifier, the signature verification doesn't
+/// need any random number.
+pub(crate) struct VerifyOnlyRand;
+
+impl Source<u32> for VerifyOnlyRand {
+    fn gen(&mut self) -> Result<u32, RandError> {
+        Err(RandError::new(RandErrKind::NoNewRandNumberGen, "The random source cannot be used to sign"))
+    }
+
+    fn reset<Sd: Seed<u32>>(&mut self, _sd: &Sd) -> Result<(), RandError> {
+        Ok(())
+    }
+}
+
+impl IterSource<u32> for VerifyOnlyRand {
+    fn iter_mut(&mut self) -> Iter<'_, Self, u32> where Self: std::marker::Sized {
+        Iter::new(self)
+    }
+}
@@ -0,0 +1,60 @@
+//! The byte-oriented encryption and signature of the RSA keys by the OAEP and the PSS schemes
+
+use rmath::rand::{IterSource, Source, Seed, Iter, RandError, RandErrKind};
+use crate::{CryptoError, Digest, Cipher, Signature};
+use crate::rsa::{PublicKey, PrivateKey, KeyPair, OAEP, PSS, SignatureContent};
+use crate::rand::VerifyOnlyRand;
+
+/// The random source which borrows the caller's random source, so the schemes don't need to own it
+struct BorrowedRand<'a, R>(&'a mut R);
+
+impl<'a, R: IterSource<u32>> Source<u32> for BorrowedRand<'a, R> {
+    fn gen(&mut self) -> Result<u32, RandError> {
+        self.0.iter_mut().next().ok_or_else(|| RandError::new(RandErrKind::NoNewRandNumberGen, "The random source is exhausted"))
+    }
+
+    fn reset<Sd: Seed<u32>>(&mut self, sd: &Sd) -> Result<(), RandError> {
+        self.0.reset(sd)
+    }
+}
+
+impl<'a, R: IterSource<u32>> IterSource<u32> for BorrowedRand<'a, R> {
+    fn iter_mut(&mut self) -> Iter<'_, Self, u32> where Self: std::marker::Sized {
+        Iter::new(self)
+    }
+}
+
+impl PublicKey {
+    /// RSAES-OAEP encryption, the MGF1 uses the same `digest`, the length of `msg` must be less than or equal to
+    /// `self.modulus_len() - 2 * digest_len - 2`.
+    pub fn encrypt_oaep<H: Digest, R: IterSource<u32>>(&self, digest: H, label: &[u8], msg: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+        let oaep = OAEP::new(digest, BorrowedRand(rd), KeyPair::from(self.clone()), label.to_vec(), false)?;
+        let mut cipher_text = Vec::with_capacity(self.modulus_len());
+        oaep.encrypt(&mut cipher_text, msg)?;
+        Ok(cipher_text)
+    }
+
+    /// RSASSA-PSS verification, the salt length is equal to the digest length as the `sign_pss`
+    pub fn verify_pss<H: Digest>(&self, digest: H, msg: &[u8], signature: &[u8]) -> Result<(), CryptoError> {
+        let mut pss = PSS::new(digest, VerifyOnlyRand, KeyPair::from(self.clone()), None, false)?;
+        pss.verify(&SignatureContent::from(signature), msg)
+    }
+}
+
+impl PrivateKey {
+    /// RSAES-OAEP decryption with the RSA blinding by the `rd`
+    pub fn decrypt_oaep<H: Digest, R: IterSource<u32>>(&self, digest: H, label: &[u8], cipher_text: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+        let oaep = OAEP::new(digest, BorrowedRand(rd), KeyPair::from(self.clone()), label.to_vec(), true)?;
+        let mut msg = Vec::with_capacity(self.modulus_len());
+        oaep.decrypt(&mut msg, cipher_text)?;
+        Ok(msg)
+    }
+
+    /// RSASSA-PSS signature with the RSA blinding, the salt of the digest length is generated by the `rd`
+    pub fn sign_pss<H: Digest, R: IterSource<u32>>(&self, digest: H, msg: &[u8], rd: &mut R) -> Result<Vec<u8>, CryptoError> {
+        let mut pss = PSS::new(digest, BorrowedRand(rd), KeyPair::from(self.clone()), None, true)?;
+        let mut signature = SignatureContent::with_capacity(self.modulus_len());
+        pss.sign(&mut signature, msg)?;
+        Ok(signature.as_slice().to_vec())
+    }
+}
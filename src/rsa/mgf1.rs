@@ -0,0 +1,60 @@
+//! [MGF1](https://www.cnblogs.com/mengsuenyan/p/13796306.html#mgf1)
+
+use crate::{Digest, CryptoError, CryptoErrorKind};
+
+/// Mask generation function based on a hash function, any `Digest` with the fixed output 
+/// length can be used, e.g. SHA-1/SHA-2/SHA-3/SM3.
+#[derive(Clone)]
+pub struct MGF1<H> {
+    hf: H,
+}
+
+impl<H: Digest> MGF1<H> {
+    pub fn new(digest: H) -> Self {
+        Self {
+            hf: digest,
+        }
+    }
+    
+    /// digest message length in bytes
+    pub fn digest_len(&self) -> usize {
+        (self.hf.bits_len() + 7) >> 3
+    }
+    
+    /// generate the mask with `mask_len` bytes from the `seed`, the `mask_len` need to less than or equal to 
+    /// the `2^32 * self.digest_len()`.
+    pub fn generate(&mut self, mask: &mut Vec<u8>, seed: &[u8], mask_len: usize) -> Result<(), CryptoError> {
+        if (mask_len as u128) > ((self.digest_len() as u128) << 32) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The mask length is too long"));
+        }
+        
+        mask.clear();
+        mask.resize(mask_len, 0);
+        Self::mgf1_xor(mask.as_mut_slice(), seed, &mut self.hf);
+        Ok(())
+    }
+    
+    /// `out = out ⊕ MGF1(seed, out.len())`
+    pub fn mask_xor(&mut self, out: &mut [u8], seed: &[u8]) {
+        Self::mgf1_xor(out, seed, &mut self.hf)
+    }
+    
+    pub(super) fn mgf1_xor(out: &mut [u8], seed: &[u8], hf: &mut H) {
+        let (mut done, mut count) = (0, 0u32);
+        let mut digest = Vec::with_capacity((hf.bits_len() + 7) >> 3);
+        
+        while done < out.len() {
+            hf.reset();
+            hf.write(seed);
+            hf.write(count.to_be_bytes().as_ref());
+            hf.checksum(&mut digest);
+            
+            out.iter_mut().skip(done).zip(digest.iter()).for_each(|(a, &b)| {
+                *a ^= b;
+                done += 1;
+            });
+            
+            count = count.wrapping_add(1);
+        }
+    }
+}
@@ -5,7 +5,14 @@
 
 mod rsa;
 
-pub use rsa::{PublicKey, PrivateKey, KeyPair};
+pub use rsa::{PublicKey, PrivateKey, KeyPair, KeygenProgress};
+
+mod key_ops;
+
+pub mod raw;
+
+mod mgf1;
+pub use mgf1::MGF1;
 
 mod oaep;
 pub use oaep::{OAEP};
@@ -19,14 +26,17 @@ pub use pss::{PSS};
 mod signature;
 pub use signature::SignatureContent;
 
+mod sign_session;
+pub use sign_session::{SignSession, DigestSignature};
+
 #[cfg(test)]
 mod rsa_test;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sha2", feature = "sha3", feature = "sm"))]
 mod oaep_test;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sha2", feature = "sha3", feature = "sm"))]
 mod pkcs1_test;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sha2", feature = "sha3", feature = "sm"))]
 mod pss_test;
\ No newline at end of file
@@ -7,7 +7,7 @@ use crate::rsa::rsa::KeyPair;
 use rmath::bigint::BigInt;
 use std::cell::Cell;
 use rmath::rand::IterSource;
-use crate::rsa::{PublicKey, PrivateKey};
+use crate::rsa::{PublicKey, PrivateKey, MGF1};
 
 struct OAEPInner<H, R> {
     kp: KeyPair,
@@ -77,10 +77,12 @@ impl<H, R> OAEPInner<H, R>
             return Err(CryptoError::new(CryptoErrorKind::InnerErr, "The encoding message not equal to modulus length"));
         }
         
-        Self::mgf1_xor(em.as_mut_slice(), db_bound, seed_bound, h_len, &mut self.hf);
-        Self::mgf1_xor(em.as_mut_slice(), seed_bound, db_bound, h_len, &mut self.hf);
+        let (seed, db) = em.split_at_mut(db_bound.0);
+        let seed = &mut seed[(seed_bound.0)..(seed_bound.1)];
+        MGF1::mgf1_xor(db, seed, &mut self.hf);
+        MGF1::mgf1_xor(seed, db, &mut self.hf);
         let m = BigInt::from_be_bytes(em.as_slice());
-        let c = self.kp.public_key().encrypt(&m);
+        let c = self.kp.public_key().rsaep(&m);
        
         let mut out = c.to_be_bytes();
         cipher_txt.clear();
@@ -110,9 +112,9 @@ impl<H, R> OAEPInner<H, R>
         let c = BigInt::from_be_bytes(cipher_text);
         
         let m = if self.is_blinding {
-            kp.decrypt::<R>(&c, Some(&mut self.rd))
+            kp.rsadp::<R>(&c, Some(&mut self.rd))
         } else {
-            kp.decrypt::<R>(&c, None)
+            kp.rsadp::<R>(&c, None)
         }?;
 
         let mut lhash = Vec::with_capacity(h_len);
@@ -134,8 +136,12 @@ impl<H, R> OAEPInner<H, R>
         }
         
         let (seed_bound, db_bound) = ((1, h_len+1), (h_len + 1, em.len()));
-        Self::mgf1_xor(em.as_mut_slice(), seed_bound, db_bound, h_len, &mut self.hf);
-        Self::mgf1_xor(em.as_mut_slice(), db_bound, seed_bound, h_len, &mut self.hf);
+        {
+            let (seed, db) = em.split_at_mut(db_bound.0);
+            let seed = &mut seed[(seed_bound.0)..(seed_bound.1)];
+            MGF1::mgf1_xor(seed, db, &mut self.hf);
+            MGF1::mgf1_xor(db, seed, &mut self.hf);
+        }
         
         let lhash2_bound = (db_bound.0, db_bound.0 + h_len);
         if lhash.as_slice() != &em.as_slice()[(lhash2_bound.0)..(lhash2_bound.1)] {
@@ -157,26 +163,6 @@ impl<H, R> OAEPInner<H, R>
         msg.extend((&em[idx..]).iter());
         Ok(())
     }
-    
-    fn mgf1_xor(em: &mut [u8], obound: (usize, usize), sbound: (usize, usize), h_len: usize, hf: &mut H) {
-        let (mut done, mut count) = (0, 0u32);
-        let mut digest = Vec::with_capacity(h_len);
-        
-        while done < (obound.1 - obound.0) {
-            let seed = &em[(sbound.0)..(sbound.1)];
-            hf.reset();
-            hf.write(seed);
-            hf.write(count.to_be_bytes().as_ref());
-            hf.checksum(&mut digest);
-            
-            (&mut em[(obound.0)..(obound.1)]).iter_mut().skip(done).zip(digest.iter()).for_each(|(a, &b)| {
-                *a ^= b;
-                done += 1;
-            });
-            
-            count += 1;
-        }
-    }
 }
 
 impl<H, R> OAEP<H, R> 
@@ -232,10 +218,9 @@ impl<H, R> OAEP<H, R>
     }
     
     pub fn new(digest: H, rd: R, key_pair: KeyPair, label: Vec<u8>, is_enable_bliding: bool) -> Result<Self, CryptoError> {
-        if key_pair.private_key().is_some() {
-            key_pair.private_key().unwrap().is_valid()?;
-        } else {
-            key_pair.public_key().is_valid()?;
+        match &key_pair {
+            KeyPair::Full(sk) => sk.is_valid()?,
+            KeyPair::PublicOnly(pk) => pk.is_valid()?,
         }
         
         Self::new_uncheck(digest, rd, key_pair, label, is_enable_bliding)
@@ -1,8 +1,9 @@
 use std::str::FromStr;
-use crate::{sha, Cipher};
+use crate::{sha, sha3, Cipher, SM3};
 use rmath::bigint::BigInt;
-use crate::rsa::{PublicKey, OAEP, KeyPair, PrivateKey};
+use crate::rsa::{PublicKey, OAEP, KeyPair, PrivateKey, MGF1};
 use rmath::rand::{DefaultSeed, CryptoRand, Source, RandError, RandErrKind, Seed, IterSource, Iter};
+use crate::test_util::hex;
 
 #[derive(Clone)]
 pub(super) struct TestRand {
@@ -263,4 +264,72 @@ fn oaep_decrypt() {
 			assert_eq!(buf.as_slice(), msg.in_msg.as_slice(), "case(with blinding): {}-{}", i, j);
 		}
 	}
-}
\ No newline at end of file
+}
+
+/// 2048-bits key which is used to test the SHA-3/SM3 schemes, generated by the OpenSSL
+pub(super) fn rsa2048_get_private_key() -> PrivateKey {
+    let n = BigInt::from_str("22485937608379408602785224294387499228773708418664672368877198736883266813600218936647084612994286885856771610397731793135777520438399990306197711569611564618294978984488478428858494409529380841143585632351398889250993973178926809698790598260963006611561904304985435568878202690593652035235447909005416974189858790400851939523080582888871115621097020645846621634036478998513647154398732472616898123627143158722279775130130307877143321227768469616437509945081289674581519704848889038008639722794372954392534156310188659505687917078613178039068167147288685826720304716036879587454033257245391010450308259725005361917417").unwrap();
+    let e = BigInt::from(65537u32);
+    let d = BigInt::from_str("4975593110949785444946223147399696613224119597484938470259012722318643125473045378404898901177806427620633195005650350658509874681745686549932140069802012713480312060184411737731337584927486742039672138546956663292424155065802552185016655604176576302382132928800868826174214197603132480918803984229050850747721575423669376374070106395347263117973048410505721860565105611901358935301680698053852214688846504845795586262874155749273112722471261366401120531276256069745373208756678234002902821640101772751856156834503561212928153102277309630008849673271263621400511533643358657645044117643310570877655301608281873250637").unwrap();
+    let primes = vec![
+        BigInt::from_str("158484449823834330741789780720589480748449559822428494985617704994393479271938820136547294058443085613124101036941080682179314498732593426206218336829421289037052358032468303176172964348011976199815405554392138992738607223590146766456430103461591951160211143163400070690435063624085543792994852805167593807813").unwrap(),
+        BigInt::from_str("141881033964997675836814875739018359384227218607261142853483288300617266219851531125665181694275718249990032918159762106949664426031131509773399542932028087449235807016988154499179932937208642727109638030583364086195823561027614083521456144130243222657835267470007773968193753606159371626789814835987948106709").unwrap(),
+    ];
+
+    PrivateKey::from_bigint_uncheck(&n, &e, &d, &primes).unwrap()
+}
+
+#[test]
+fn oaep_sha3_sm3_openssl() {
+    // openssl pkeyutl -encrypt -pkeyopt rsa_padding_mode:oaep -pkeyopt rsa_oaep_md:$h -pkeyopt rsa_mgf1_md:$h
+    let msg = b"rcrypto rsa with sha3 and sm3";
+    let cases = [
+        "16775b8861452d9faf3cbb200fc169defc6f52a2a4be642e1b5ce8246ea72588f657ae31b897f804bdde683cb5d6159c31a03fa1b8fa2ff6f433479fe371be1049db0e0a79c491699e1d941ea1c442ca34098abecebe5bf48f5c61cc2bc3c81d856b493b6b53aacdc6f542f67ea9388ae863d89797bd07b6600ca903efbb24552206d7b22eebc0d4eba0969c5ba0edd16e55a90c81a969bf3e8485dbc7f6bbbf70b73470dba8bb2e3ea09d6677d61ab14c6204be03e96fef176506c281b757e2f96ca0d466a39ddd0158d91a5fb93b893d4fb5334dc6f2abc547679cc5f05aaaddd2254d701a5a6657b123777fe5e84e2fa6ac9d80e3d1e8ad3f6a393a62c53b",
+        "4bf433883080c58465620823d688856eacc317bfaf9b4893efa3c66e4309088d0f4449e2c2577ad602a380590f9388d0a14e5b71df6cf32938df5c335898868e5b8da3d6e2f29a6dc763cb4d5b53bbe08c94f8b26858f9dbc3a4d9783d62ee0b6a85684f5675b7ad13200d1c45c4b37855ade50631d44841c252bdffc4cdd4073f45491b2841463cf622754bbcbaf948a15d27e9d5ef5984a42f4132ce67aa3e274ab410c5b4589d9dab98f0b16ab1b0f2e78c8385dd68029cd45754a11b3759004661f71770f978df3776baa9f14c4bda60e6f36fd01e1438040143ad51e26f8c224ac8eabe400720fa594e89ce01a3b5d793802e1f550715daa9667a9e5136",
+        "8602ee59a88b870b91b692e78d822664312b97b795bb58fbbf11b60b366fe539807b36e0c0f6325a740b06c0528ce2451465b0ca08f89f7755c62abbb6ceae27533a2d67ddb26ed087ab644431446b72db4c128521738d9ce3cdaadcb54721d93ea2d7bbcff95b3384161ee3e765f46420032e469f9518117d5b6417c9abda6333c2c46fdfe79e97f4fdbc14ac65255bfe15edb3367390820900e21ac8dcc71ac563dd321cb9e79d132c43f12fee90410c9b37abe2067b814663d8f6dfe6599a2eb7a89ceffb3212b17a689f656c7b29ecd29a59500ecc89cafab677d054a8022e25013373b6d04873e679554eb927f5692225b355d5e5da125b36b0ed1c79a3",
+        // label: "label"
+        "737531ab4bc1febbc2efca3f3c65a2c84fee926d800ac3c1dcc05cc36e56844b639710a50069b0da052250065977f4d3675aaceb07199f8b9f95430a7aa4af343d7ac6f50a8f55d3afe38eda0924bf1a78968eb14df86a1533e2a136a98b3872acac4faafe771b9ca3205d065425a8dfe048bae178e667409bb8d066ef0eeb3a40c129ad5f9b4facdd9019b68308b1e09b6fa3f9f88fa257837796e3130d6090aa21c2582d60dd0c92251612ac105cce97d2bd417a1bb56eda5928984916c41192cd830164570c1df90bda651f8df3f6e94d1c16c50087c318b404ecd4edb8513997c8b84bd368f3eeebb0512f1b288655052183c2f8880da6115f3b0f4e19b8",
+    ];
+    
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let (mut buf, mut tmp) = (Vec::new(), Vec::new());
+    
+    let oaep = OAEP::new_uncheck(sha3::SHA256::new(), rd.clone(), KeyPair::from(rsa2048_get_private_key()), Vec::new(), true).unwrap();
+    oaep.decrypt(&mut buf, hex(cases[0]).as_slice()).unwrap();
+    assert_eq!(buf.as_slice(), msg.as_ref(), "case: 0");
+    oaep.encrypt(&mut tmp, msg.as_ref()).unwrap();
+    oaep.decrypt(&mut buf, tmp.as_slice()).unwrap();
+    assert_eq!(buf.as_slice(), msg.as_ref(), "case: 0");
+    
+    let oaep = OAEP::new_uncheck(sha3::SHA512::new(), rd.clone(), KeyPair::from(rsa2048_get_private_key()), Vec::new(), true).unwrap();
+    oaep.decrypt(&mut buf, hex(cases[1]).as_slice()).unwrap();
+    assert_eq!(buf.as_slice(), msg.as_ref(), "case: 1");
+    
+    let mut oaep = OAEP::new_uncheck(SM3::new(), rd.clone(), KeyPair::from(rsa2048_get_private_key()), Vec::new(), true).unwrap();
+    oaep.decrypt(&mut buf, hex(cases[2]).as_slice()).unwrap();
+    assert_eq!(buf.as_slice(), msg.as_ref(), "case: 2");
+    assert!(oaep.decrypt(&mut buf, hex(cases[3]).as_slice()).is_err(), "case: 3");
+    oaep.set_label(b"label".to_vec());
+    oaep.decrypt(&mut buf, hex(cases[3]).as_slice()).unwrap();
+    assert_eq!(buf.as_slice(), msg.as_ref(), "case: 3");
+    oaep.encrypt(&mut tmp, msg.as_ref()).unwrap();
+    oaep.decrypt(&mut buf, tmp.as_slice()).unwrap();
+    assert_eq!(buf.as_slice(), msg.as_ref(), "case: 3");
+}
+
+#[test]
+fn mgf1_sha3_sm3() {
+    // MGF1(seed, 48) which is computed by the Python hashlib
+    let seed = b"rcrypto mgf1";
+    let cases = [
+        "8b9daa0db97111d57516af99a4dfde7fdf8cd837c264c456ebc2403654b42217e788b2e58e261fe53526ed1d07eeefe5",
+        "01413b55c4e1ac9999bf5920218b8637ba7080bf22cbb60f89602701fe2c98a76b57d4aaeab12c33d1a3aae946bfc0ae",
+    ];
+    let mut mask = Vec::new();
+    
+    MGF1::new(sha3::SHA256::new()).generate(&mut mask, seed.as_ref(), 48).unwrap();
+    assert_eq!(mask, hex(cases[0]), "case: 0");
+    MGF1::new(SM3::new()).generate(&mut mask, seed.as_ref(), 48).unwrap();
+    assert_eq!(mask, hex(cases[1]), "case: 1");
+}
@@ -1,14 +1,21 @@
 //! [PKCS #1 v2.2](https://www.cnblogs.com/mengsuenyan/p/13796306.html#rsassa-pss)
 //! 
 
-use crate::{Digest, CryptoErrorKind, CryptoError, MD5, Cipher, Signature};
+use crate::{Digest, CryptoErrorKind, CryptoError, Cipher, Signature};
+#[cfg(feature = "md5")]
+use crate::MD5;
 use crate::rsa::rsa::KeyPair;
 use rmath::bigint::BigInt;
 use std::any::{TypeId, Any};
-use crate::sha::{SHA1, SHA224, SHA384, SHA256, SHA512};
+#[cfg(feature = "sha2")]
+use crate::sha::{SHA1, SHA224, SHA384, SHA256, SHA512, SHA512T224, SHA512T256};
+#[cfg(feature = "sha3")]
+use crate::sha3;
+#[cfg(feature = "sm")]
+use crate::SM3;
 use std::cell::Cell;
 use rmath::rand::IterSource;
-use crate::rsa::{PublicKey, PrivateKey, SignatureContent};
+use crate::rsa::{PublicKey, PrivateKey, SignatureContent, SignSession, DigestSignature};
 
 struct PKCS1Inner<H, R> {
     kp: KeyPair,
@@ -71,7 +78,7 @@ impl<H, R> PKCS1Inner<H, R>
         em.extend(msg.iter());
         
         let m = BigInt::from_be_bytes(em.as_slice());
-        let c = self.kp.public_key().encrypt(&m);
+        let c = self.kp.public_key().rsaep(&m);
         
         let cc = c.to_be_bytes();
         em.clear();
@@ -96,9 +103,9 @@ impl<H, R> PKCS1Inner<H, R>
         
         let c = BigInt::from_be_bytes(cipher_txt);
         let m = if self.is_blinding {
-            kp.decrypt::<R>(&c, Some(&mut self.rd))
+            kp.rsadp::<R>(&c, Some(&mut self.rd))
         } else {
-            kp.decrypt::<R>(&c, None)
+            kp.rsadp::<R>(&c, None)
         }?;
         let mut em = m.to_be_bytes();
         let old_len = em.len();
@@ -136,9 +143,26 @@ impl<H, R> PKCS1Inner<H, R>
 
 impl<H, R> PKCS1Inner<H, R> 
     where H: Digest + Any, R: IterSource<u32> {
-    fn sign(&mut self, sign: &mut Vec<u8>, m_hash: &[u8]) -> Result<(), CryptoError> {
+    fn hash_message(&mut self, message: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::with_capacity((self.hf.bits_len() + 7) >> 3);
+        self.hf.reset();
+        self.hf.write(message);
+        self.hf.checksum(&mut buf);
+        buf
+    }
+    
+    fn sign(&mut self, sign: &mut Vec<u8>, message: &[u8]) -> Result<(), CryptoError> {
+        let m_hash = self.hash_message(message);
+        self.sign_digest(sign, m_hash.as_slice())
+    }
+    
+    /// `m_hash = Hash(Message)`
+    fn sign_digest(&mut self, sign: &mut Vec<u8>, m_hash: &[u8]) -> Result<(), CryptoError> {
         let h_len = (self.hf.bits_len() + 7) >> 3;
         let mut prefix = self.pkcs1_hash_info()?;
+        if h_len != m_hash.len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid hash length"));
+        }
         
         let kp = self.kp.private_key().ok_or(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "RSASSA-PKCS1: public key cannot be used for signing"))?;
         let (t_len, k) = (prefix.len() + h_len, kp.modulus_len());
@@ -153,16 +177,13 @@ impl<H, R> PKCS1Inner<H, R>
         sign.extend(std::iter::repeat(0xff).take(k - t_len - 3));
         sign.push(0x00);
         sign.append(&mut prefix);
-        self.hf.reset();
-        self.hf.write(m_hash);
-        self.hf.checksum(&mut prefix);
-        sign.append(&mut prefix);
+        sign.extend_from_slice(m_hash);
         
         let m = BigInt::from_be_bytes(sign.as_slice());
         let c = if self.is_blinding {
-            kp.decrypt_and_check::<R>(&m, Some(&mut self.rd))
+            kp.rsadp_and_check::<R>(&m, Some(&mut self.rd))
         } else {
-            kp.decrypt_and_check::<R>(&m, None)
+            kp.rsadp_and_check::<R>(&m, None)
         }?;
         
         let mut c = c.to_be_bytes();
@@ -173,9 +194,18 @@ impl<H, R> PKCS1Inner<H, R>
         Ok(())
     }
     
-    fn verify(&mut self, sign: &[u8], m_hash: &[u8]) -> Result<(), CryptoError> {
+    fn verify(&mut self, sign: &[u8], message: &[u8]) -> Result<(), CryptoError> {
+        let m_hash = self.hash_message(message);
+        self.verify_digest(sign, m_hash.as_slice())
+    }
+    
+    /// `m_hash = Hash(Message)`
+    fn verify_digest(&mut self, sign: &[u8], m_hash: &[u8]) -> Result<(), CryptoError> {
         let h_len = (self.hf.bits_len() + 7) >> 3;
-        let mut prefix = self.pkcs1_hash_info()?;
+        let prefix = self.pkcs1_hash_info()?;
+        if h_len != m_hash.len() {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid hash length"));
+        }
         
         let (t_len, k) = (prefix.len() + h_len, self.kp.public_key().modulus_len());
         if k < (t_len + 11) {
@@ -183,7 +213,7 @@ impl<H, R> PKCS1Inner<H, R>
         }
         
         let c = BigInt::from_be_bytes(sign);
-        let m = self.kp.public_key().encrypt(&c);
+        let m = self.kp.public_key().rsaep(&c);
         let mut em = m.to_be_bytes();
         let old_len = em.len();
         if k >  old_len {
@@ -197,11 +227,7 @@ impl<H, R> PKCS1Inner<H, R>
             return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid message encoding format"));
         }
         
-        self.hf.reset();
-        self.hf.write(m_hash);
-        let m_hash = &mut prefix;
-        self.hf.checksum(m_hash);
-        if &em[(k-h_len)..] != m_hash.as_slice() {
+        if &em[(k-h_len)..] != m_hash {
             return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid message encoding format"));
         }
 
@@ -223,20 +249,38 @@ impl<H, R> PKCS1Inner<H, R>
     /// precompute a prefix of the digest value that makes a valid ASN1 DER string
     /// with the correct contents.
     fn pkcs1_hash_prefix() -> Result<Vec<u8>, CryptoError> {
-        if TypeId::of::<H>() == TypeId::of::<MD5>() {
-            Ok(vec![0x30, 0x20, 0x30, 0x0c, 0x06, 0x08, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x05, 0x05, 0x00, 0x04, 0x10])
-        } else if TypeId::of::<H>() == TypeId::of::<SHA1>() {
-            Ok(vec![0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14])
-        } else if TypeId::of::<H>() == TypeId::of::<SHA224>() {
-            Ok(vec![0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04, 0x05, 0x00, 0x04, 0x1c])
-        } else if TypeId::of::<H>() == TypeId::of::<SHA256>() {
-            Ok(vec![0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20])
-        } else if TypeId::of::<H>() == TypeId::of::<SHA384>() {
-            Ok(vec![0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04, 0x30])
-        } else if TypeId::of::<H>() == TypeId::of::<SHA512>() {
-            Ok(vec![0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04, 0x40])
-        } else {
-            unreachable!(format!("not suuport {}", std::any::type_name::<H>()));
+        let prefixes: &[(TypeId, &[u8])] = &[
+            #[cfg(feature = "md5")]
+            (TypeId::of::<MD5>(), &[0x30, 0x20, 0x30, 0x0c, 0x06, 0x08, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x05, 0x05, 0x00, 0x04, 0x10]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA1>(), &[0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA224>(), &[0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04, 0x05, 0x00, 0x04, 0x1c]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA256>(), &[0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA384>(), &[0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04, 0x30]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA512>(), &[0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04, 0x40]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA512T224>(), &[0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x05, 0x05, 0x00, 0x04, 0x1c]),
+            #[cfg(feature = "sha2")]
+            (TypeId::of::<SHA512T256>(), &[0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x06, 0x05, 0x00, 0x04, 0x20]),
+            #[cfg(feature = "sha3")]
+            (TypeId::of::<sha3::SHA224>(), &[0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x07, 0x05, 0x00, 0x04, 0x1c]),
+            #[cfg(feature = "sha3")]
+            (TypeId::of::<sha3::SHA256>(), &[0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x08, 0x05, 0x00, 0x04, 0x20]),
+            #[cfg(feature = "sha3")]
+            (TypeId::of::<sha3::SHA384>(), &[0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x09, 0x05, 0x00, 0x04, 0x30]),
+            #[cfg(feature = "sha3")]
+            (TypeId::of::<sha3::SHA512>(), &[0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0a, 0x05, 0x00, 0x04, 0x40]),
+            #[cfg(feature = "sm")]
+            (TypeId::of::<SM3>(), &[0x30, 0x30, 0x30, 0x0c, 0x06, 0x08, 0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x83, 0x11, 0x05, 0x00, 0x04, 0x20]),
+        ];
+        
+        match prefixes.iter().find(|(t, _)| *t == TypeId::of::<H>()) {
+            Some((_, prefix)) => Ok(prefix.to_vec()),
+            None => Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("not support {} in the RSASSA-PKCS1", std::any::type_name::<H>()))),
         }
     }
     
@@ -258,6 +302,25 @@ impl<H, R> PKCS1<H, R>
     }
 }
 
+impl<H, R> PKCS1<H, R>
+    where H: Digest + Clone + Any, R: IterSource<u32> {
+
+    /// start the streaming session with a new digest
+    pub fn start_session(&mut self) -> SignSession<'_, Self, H> {
+        let digest = self.digest_func();
+        SignSession::new(self, digest)
+    }
+}
+
+impl<H, R> PKCS1<H, R>
+    where H: Digest + Any, R: IterSource<u32> {
+
+    /// start the streaming session with the `digest` which may have been written with the head of the message
+    pub fn start_session_with(&mut self, digest: H) -> SignSession<'_, Self, H> {
+        SignSession::new(self, digest)
+    }
+}
+
 impl<H, R> PKCS1<H, R>
     where H: Digest, R: IterSource<u32> + Clone {
 
@@ -302,24 +365,6 @@ impl<H, R> PKCS1<H, R>
         self.modulus_len().saturating_sub(11)
     }
     
-    fn check_hash_is_support() -> Result<(), CryptoError> {
-        if TypeId::of::<H>() != TypeId::of::<MD5>() {
-            Ok(())
-        } else if TypeId::of::<H>() != TypeId::of::<SHA1>() {
-            Ok(())
-        } else if TypeId::of::<H>() != TypeId::of::<SHA224>() {
-            Ok(())
-        } else if TypeId::of::<H>() != TypeId::of::<SHA256>() {
-            Ok(())
-        } else if TypeId::of::<H>() != TypeId::of::<SHA384>() {
-            Ok(())
-        } else if TypeId::of::<H>() != TypeId::of::<SHA512>() {
-            Ok(())
-        } else {
-            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("{} is not currently supported", std::any::type_name::<H>())))
-        }
-    }
-
     /// maximum message length in byte  allowed to be signing
     pub fn sign_max_message_len(&self) -> usize {
         #[cfg(target_pointer_width = "32")]
@@ -334,8 +379,6 @@ impl<H, R> PKCS1<H, R>
     }
     
     pub fn new_uncheck(digest: H, rd: R, key_pair: KeyPair, is_enable_blinding: bool) -> Result<Self, CryptoError> {
-        Self::check_hash_is_support()?;
-        
         if key_pair.modulus_len() <= 11 {
             return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The modulus length is too small"));
         }
@@ -350,20 +393,15 @@ impl<H, R> PKCS1<H, R>
     }
     
     pub fn new(digest: H, rd: R, key_pair: KeyPair, is_enable_blinding: bool) -> Result<Self, CryptoError> {
-        Self::check_hash_is_support()?;
-        
-        if key_pair.private_key().is_some() {
-            key_pair.private_key().unwrap().is_valid()?;
-        } else {
-            key_pair.public_key().is_valid()?;
+        match &key_pair {
+            KeyPair::Full(sk) => sk.is_valid()?,
+            KeyPair::PublicOnly(pk) => pk.is_valid()?,
         }
         
         Self::new_uncheck(digest, rd, key_pair, is_enable_blinding)
     }
     
     pub fn auto_generate_key(bits_len: usize, test_round_times: usize, digest: H, mut rd: R, is_enable_blinding: bool) -> Result<Self, CryptoError> {
-        Self::check_hash_is_support()?;
-        
         let h_len = (digest.bits_len() + 7) >> 3;
         
         if bits_len <= (11 + h_len) {
@@ -410,4 +448,15 @@ impl<H, R> Signature<SignatureContent> for PKCS1<H, R>
     fn verify(&mut self, signature: &SignatureContent, message: &[u8]) -> Result<Self::Output, CryptoError> {
         self.inner.get_mut().verify(signature.as_ref(), message)
     }
+}
+
+impl<H, R> DigestSignature<H> for PKCS1<H, R>
+    where H: Digest + Any, R: IterSource<u32> {
+    fn sign_digest(&mut self, signature: &mut SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.inner.get_mut().sign_digest(signature.as_mut(), m_hash)
+    }
+
+    fn verify_digest(&mut self, signature: &SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.inner.get_mut().verify_digest(signature.as_ref(), m_hash)
+    }
 }
\ No newline at end of file
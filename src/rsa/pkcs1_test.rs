@@ -2,7 +2,8 @@ use std::str::FromStr;
 use rmath::bigint::BigInt;
 use crate::rsa::{PrivateKey, PKCS1, KeyPair, SignatureContent};
 use rmath::rand::{DefaultSeed, CryptoRand};
-use crate::{sha, Cipher, Signature};
+use crate::{sha, sha3, Cipher, Signature, Digest, SM3};
+use crate::rsa::oaep_test::rsa2048_get_private_key;
 
 fn pkcs1_get_private_key() -> PrivateKey {
     let n = BigInt::from_str("9353930466774385905609975137998169297361893554149986716853295022578535724979677252958524466350471210367835187480748268864277464700638583474144061408845077").unwrap();
@@ -60,4 +61,64 @@ fn pkcs1_sign() {
         
         assert!(pkcs1.verify(&buf, ele.0.as_bytes()).is_ok(), "case-verify: {}", i)
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn pkcs1_sign_sha3_sm3() {
+    let msg = b"rcrypto rsa with sha3 and sm3";
+    // signed by the OpenSSL
+    let (sha3_256_sig, sha512_256_sig) = (
+        "96167e5012f0f941dd1f5d565ee80269fffb74900bc882286115e1a2722a89d8b17a349361f19393b22ae11f32169ffbdc1a2ce97372c5b44d5e30a44e9f6530fe67fb379a9606d5659927b486a75cb4c587464cd01ebce09951e93b923e0db1a685978e13bf57661b70cf098961fc3e7b5249a3e3e72e23a51f153cb24e20fa45b72fdd9beae8a7a55398353292d6fc76c0664e4c2cafcb1d11bfc85fcb0e79b9adf33616c44f202b40269bb86ce86cf560d5bd64ddd98239c37a6405dd8118a95d102b9d9c22df12faf9f638c2f26a455de9965c87e83032eabc365bd7da927b93a19bce7f2c50576a03422124735e2ced5951ad8f97e19bd5c902a579380b",
+        "881d27929a065d50eebb09805059fd300705b75522e97b7961f2bfa581287e44673c12f9edfed0edb39bc30af8e591c5f91b329aad0a4cb153733552e5cff09d5b973f39861e0cf2e8389d05a5b5cd7302031746e90a9a60350b9c9b39be207c0a04084ef8a5aedbe696228dcf73693daa9f6cdc30a1e51f80a41fcaa8557652a544b8ad3e0b507cf165e2b7922883961efaaac7d50584cc788c58e86120b4d5a768620732a224171c1b55c5f30f6c77a36df79f3b054b766b6135cff7aff080614412f54fe1dd99b4bb4ebfaa40a43f3f9d57efb8e684002ae890793a409db9890ed50465100dd05daefe63557ae238b0b164f6b2210b8dec98a5f45212ad6c",
+    );
+    let cvt = |s: &str| (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..(i+2)], 16).unwrap()).collect::<Vec<_>>();
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let mut buf = SignatureContent::new();
+    
+    let mut pkcs1 = PKCS1::new(sha3::SHA256::new(), rd.clone(), KeyPair::from(rsa2048_get_private_key()), false).unwrap();
+    pkcs1.sign(&mut buf, msg.as_ref()).unwrap();
+    assert_eq!(buf.as_slice(), cvt(sha3_256_sig).as_slice());
+    pkcs1.verify(&buf, msg.as_ref()).unwrap();
+    
+    let mut pkcs1 = PKCS1::new(sha::SHA512T256::new(), rd.clone(), KeyPair::from(rsa2048_get_private_key()), false).unwrap();
+    pkcs1.sign(&mut buf, msg.as_ref()).unwrap();
+    assert_eq!(buf.as_slice(), cvt(sha512_256_sig).as_slice());
+    
+    let mut pkcs1 = PKCS1::new(SM3::new(), rd.clone(), KeyPair::from(rsa2048_get_private_key()), false).unwrap();
+    pkcs1.sign(&mut buf, msg.as_ref()).unwrap();
+    pkcs1.verify(&buf, msg.as_ref()).unwrap();
+    
+    let mut pkcs1 = PKCS1::new(sha3::Shake128::new(256), rd, KeyPair::from(rsa2048_get_private_key()), false).unwrap();
+    assert!(pkcs1.sign(&mut buf, msg.as_ref()).is_err());
+}
+
+#[test]
+fn pkcs1_sign_session() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let mut pkcs1 = PKCS1::new(sha::SHA256::new(), rd, KeyPair::from(rsa2048_get_private_key()), false).unwrap();
+    let msg = "The quick brown fox jumps over the lazy dog".repeat(100);
+    
+    let (mut sig, mut sig_stream) = (SignatureContent::new(), SignatureContent::new());
+    pkcs1.sign(&mut sig, msg.as_bytes()).unwrap();
+    let mut session = pkcs1.start_session();
+    msg.as_bytes().chunks(7).for_each(|x| session.write(x));
+    session.sign(&mut sig_stream).unwrap();
+    assert_eq!(sig.as_slice(), sig_stream.as_slice());
+    
+    let mut session = pkcs1.start_session();
+    std::io::copy(&mut msg.as_bytes(), &mut session).unwrap();
+    session.verify(&sig).unwrap();
+    
+    // the digest which has been written with the head of the message
+    let mut digest = sha::SHA256::new();
+    digest.write(&msg.as_bytes()[..100]);
+    let mut session = pkcs1.start_session_with(digest);
+    session.write(&msg.as_bytes()[100..]);
+    session.verify(&sig).unwrap();
+    
+    let mut session = pkcs1.start_session();
+    session.write(&msg.as_bytes()[1..]);
+    assert!(session.verify(&sig).is_err());
+}
@@ -2,7 +2,7 @@
 //! 
 
 use crate::{Digest, CryptoError, CryptoErrorKind, Signature};
-use crate::rsa::{PublicKey, PrivateKey, SignatureContent};
+use crate::rsa::{PublicKey, PrivateKey, SignatureContent, MGF1, SignSession, DigestSignature};
 use rmath::bigint::BigInt;
 use crate::rsa::rsa::KeyPair;
 use rmath::rand::IterSource;
@@ -29,6 +29,25 @@ impl<H, R> PSS<H, R>
     }
 }
 
+impl<H, R> PSS<H, R> 
+    where H: Digest + Clone, R: IterSource<u32> {
+    
+    /// start the streaming session with a new digest
+    pub fn start_session(&mut self) -> SignSession<'_, Self, H> {
+        let digest = self.digest_func();
+        SignSession::new(self, digest)
+    }
+}
+
+impl<H, R> PSS<H, R> 
+    where H: Digest, R: IterSource<u32> {
+    
+    /// start the streaming session with the `digest` which may have been written with the head of the message
+    pub fn start_session_with(&mut self, digest: H) -> SignSession<'_, Self, H> {
+        SignSession::new(self, digest)
+    }
+}
+
 impl<H, R> PSS<H, R>
     where H: Digest, R: IterSource<u32> + Clone {
     pub fn rand_source(&self) -> R {
@@ -95,10 +114,9 @@ impl<H, R> PSS<H, R>
     }
     
     pub fn new(digest: H, rd: R, key_pair: KeyPair, salt_len: Option<usize>, is_enable_blind: bool) -> Result<Self, CryptoError> {
-        if key_pair.private_key().is_some() {
-            key_pair.private_key().unwrap().is_valid()?;
-        } else {
-            key_pair.public_key().is_valid()?;
+        match &key_pair {
+            KeyPair::Full(sk) => sk.is_valid()?,
+            KeyPair::PublicOnly(pk) => pk.is_valid()?,
         }
         
         Self::new_uncheck(digest, rd, key_pair, salt_len, is_enable_blind)
@@ -134,48 +152,41 @@ impl<H, R> PSS<H, R>
         self.slen
     }
     
-    fn mgf1_xor(out: &mut [u8], seed: &[u8], hf: &mut H) {
-        let (mut done, mut count) = (0, 0u32);
-        let mut digest = Vec::with_capacity((hf.bits_len() + 7) >> 3);
-        
-        while done < out.len() {
-            hf.reset();
-            hf.write(seed);
-            hf.write(count.to_be_bytes().as_ref());
-            hf.checksum(&mut digest);
-            
-            out.iter_mut().skip(done).zip(digest.iter()).for_each(|(a, &b)| {
-                *a ^= b;
-                done += 1;
-            });
-            
-            count += 1;
-        }
+    /// `m_hash = Hash(Message)`
+    fn hash_message(&mut self, message: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::with_capacity((self.hf.bits_len() + 7) >> 3);
+        self.hf.reset();
+        self.hf.write(message);
+        self.hf.checksum(&mut buf);
+        buf
+    }
+    
+    #[cfg(test)]
+    pub(super) fn emsa_pss_encode(&mut self, em: &mut Vec<u8>, message: &[u8], em_bits: usize, salt: &[u8]) -> Result<(), CryptoError> {
+        let m_hash = self.hash_message(message);
+        self.emsa_pss_encode_digest(em, m_hash.as_slice(), em_bits, salt)
     }
     
     /// `m_hash = Hash(Message)`
-    pub(super) fn emsa_pss_encode(&mut self, em: &mut Vec<u8>, m_hash: &[u8], em_bits: usize, salt: &[u8]) -> Result<(), CryptoError> {
+    fn emsa_pss_encode_digest(&mut self, em: &mut Vec<u8>, m_hash: &[u8], em_bits: usize, salt: &[u8]) -> Result<(), CryptoError> {
         let (h_len, s_len, em_len) = ((self.hf.bits_len() + 7) >> 3, salt.len(), (em_bits + 7) >> 3);
         
         if em_len < (h_len + s_len + 2) {
             return Err(CryptoError::new(CryptoErrorKind::InnerErr, format!("key size too small: {} < {}", em_len, h_len + s_len + 2)));
         }
         
+        if h_len != m_hash.len() {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid hash length"));
+        }
+        
         em.clear();
         em.resize(em_len, 0);
         
         let mut buf = Vec::with_capacity(h_len);
-        self.hf.reset();
-        self.hf.write(m_hash);
-        self.hf.checksum(&mut buf);
-        if h_len != buf.len() {
-            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid hash length"));
-        }
-        
         const PREFIX: [u8;8] = [0u8;8];
         self.hf.reset();
         self.hf.write(PREFIX.as_ref());
-        self.hf.write(buf.as_slice());
+        self.hf.write(m_hash);
         self.hf.write(salt);
         self.hf.checksum(&mut buf);
         
@@ -186,7 +197,7 @@ impl<H, R> PSS<H, R>
         em[em_len - s_len - h_len - 2] = 0x01;
         (&mut em.as_mut_slice()[(db_end - s_len)..db_end]).copy_from_slice(salt);
         
-        Self::mgf1_xor(&mut em.as_mut_slice()[db_start..db_end], buf.as_slice(), &mut self.hf);
+        MGF1::mgf1_xor(&mut em.as_mut_slice()[db_start..db_end], buf.as_slice(), &mut self.hf);
         
         em[0] &= 0xffu8 >> ((em_len << 3) - em_bits);
         
@@ -194,16 +205,16 @@ impl<H, R> PSS<H, R>
         Ok(())
     }
     
-    /// The method will auto find salt length when the `s_len` is `None`;
-    pub(super) fn emsa_pss_verify(&mut self, em: &[u8], m_hash: &[u8], em_bits: usize) -> Result<(), CryptoError> {
+    #[cfg(test)]
+    pub(super) fn emsa_pss_verify(&mut self, em: &[u8], message: &[u8], em_bits: usize) -> Result<(), CryptoError> {
+        let m_hash = self.hash_message(message);
+        self.emsa_pss_verify_digest(em, m_hash.as_slice(), em_bits)
+    }
+    
+    /// `m_hash = Hash(Message)`
+    fn emsa_pss_verify_digest(&mut self, em: &[u8], m_hash: &[u8], em_bits: usize) -> Result<(), CryptoError> {
         let h_len = (self.hf.bits_len() + 7) >> 3;
         
-        let mut buf = Vec::with_capacity(h_len);
-        self.hf.reset();
-        self.hf.write(m_hash);
-        self.hf.checksum(&mut buf);
-        
-        let m_hash = buf.as_slice();
         if h_len != m_hash.len() {
             return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid hash length"));
         }
@@ -226,9 +237,9 @@ impl<H, R> PSS<H, R>
         
         let mut db = em[db_start..db_end].to_vec();
         let h = &em[h_start..h_end];
-        Self::mgf1_xor(db.as_mut_slice(), h, &mut self.hf);
+        MGF1::mgf1_xor(db.as_mut_slice(), h, &mut self.hf);
         
-        db[0] &= 0xff >> ((em_len >> 3) - em_bits);
+        db[0] &= 0xff >> ((em_len << 3) - em_bits);
 
         for &e in db.iter().take(em_len - h_len - self.salt_len() - 2) {
             if e != 0x00 {
@@ -262,14 +273,14 @@ impl<H, R> PSS<H, R>
     /// later used to verify the signature.
     fn sign_with_salt(&mut self, sign: &mut Vec<u8>, m_hash: &[u8], salt: &[u8]) -> Result<(), CryptoError> {
         let n_bits = self.kp.public_key().modulus().bits_len();
-        self.emsa_pss_encode(sign, m_hash, n_bits - 1, salt)?;
+        self.emsa_pss_encode_digest(sign, m_hash, n_bits - 1, salt)?;
         let m = BigInt::from_be_bytes(sign);
         
         let kp = self.kp.private_key().ok_or(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "RSASSA-PSS: public key cannot be used for signing"))?;
         let c = if self.is_blinding {
-            kp.decrypt_and_check::<R>(&m, Some(&mut self.rd))
+            kp.rsadp_and_check::<R>(&m, Some(&mut self.rd))
         } else {
-            kp.decrypt_and_check::<R>(&m, None)
+            kp.rsadp_and_check::<R>(&m, None)
         }?;
         let mut s = c.to_be_bytes();
         let (new_len, old_len) = ((n_bits + 7) >> 3, s.len());
@@ -280,7 +291,12 @@ impl<H, R> PSS<H, R>
         Ok(())
     }
     
-    fn sign_inner(&mut self, sign: &mut Vec<u8>, m_hash: &[u8]) -> Result<(), CryptoError> {
+    fn sign_inner(&mut self, sign: &mut Vec<u8>, message: &[u8]) -> Result<(), CryptoError> {
+        let m_hash = self.hash_message(message);
+        self.sign_digest_inner(sign, m_hash.as_slice())
+    }
+    
+    fn sign_digest_inner(&mut self, sign: &mut Vec<u8>, m_hash: &[u8]) -> Result<(), CryptoError> {
         let salt_len = self.salt_len();
         let mut salt = Vec::with_capacity(salt_len);
         self.rd.iter_mut().take((salt_len + 3) >> 2).for_each(|x| {
@@ -291,7 +307,12 @@ impl<H, R> PSS<H, R>
         self.sign_with_salt(sign, m_hash, salt.as_slice())
     }
     
-    fn verify_inner(&mut self, sign: &[u8], m_hash: &[u8]) -> Result<(), CryptoError> {
+    fn verify_inner(&mut self, sign: &[u8], message: &[u8]) -> Result<(), CryptoError> {
+        let m_hash = self.hash_message(message);
+        self.verify_digest_inner(sign, m_hash.as_slice())
+    }
+    
+    fn verify_digest_inner(&mut self, sign: &[u8], m_hash: &[u8]) -> Result<(), CryptoError> {
         let n_bits = self.kp.public_key().modulus().bits_len();
         
         if sign.len() != ((n_bits + 7) >> 3) {
@@ -299,7 +320,7 @@ impl<H, R> PSS<H, R>
         }
         
         let s = BigInt::from_be_bytes(sign);
-        let m = self.kp.public_key().encrypt(&s);
+        let m = self.kp.public_key().rsaep(&s);
         
         let em_bits = n_bits - 1;
         let em_len = (em_bits + 7) >> 3;
@@ -312,7 +333,7 @@ impl<H, R> PSS<H, R>
         em.resize(em_len, 0);
         em.rotate_right(em_len - old_len);
         
-        self.emsa_pss_verify(em.as_slice(), m_hash, em_bits)
+        self.emsa_pss_verify_digest(em.as_slice(), m_hash, em_bits)
     }
 }
 
@@ -329,4 +350,15 @@ impl<H, R> Signature<SignatureContent> for PSS<H, R>
     fn verify(&mut self, signature: &SignatureContent, message: &[u8]) -> Result<Self::Output, CryptoError> {
         self.verify_inner(signature.as_ref(), message)
     }
+}
+
+impl<H, R> DigestSignature<H> for PSS<H, R>
+    where H: Digest, R: IterSource<u32> {
+    fn sign_digest(&mut self, signature: &mut SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.sign_digest_inner(signature.as_mut(), m_hash)
+    }
+
+    fn verify_digest(&mut self, signature: &SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError> {
+        self.verify_digest_inner(signature.as_ref(), m_hash)
+    }
 }
\ No newline at end of file
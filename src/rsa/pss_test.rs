@@ -1,6 +1,7 @@
 use std::str::FromStr;
 use crate::rsa::{PrivateKey, PSS, KeyPair, SignatureContent};
-use crate::{sha, Signature};
+use crate::{sha, sha3, Signature, Digest, SM3};
+use crate::rsa::oaep_test::rsa2048_get_private_key;
 use rmath::bigint::BigInt;
 use rmath::rand::{DefaultSeed, CryptoRand};
 
@@ -87,4 +88,54 @@ fn emsa_pss_openssl() {
     emsa.verify(&SignatureContent::from(sig), msg.as_bytes()).unwrap();
     emsa.sign(&mut sign, msg.as_bytes()).unwrap();
     emsa.verify(&sign, msg.as_bytes()).unwrap();
-}
\ No newline at end of file
+}
+
+fn pss_verify_and_sign<H: Digest>(digest: H, sig: &str, case: &str) {
+    let msg = b"rcrypto rsa with sha3 and sm3";
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let mut pss = PSS::new_uncheck(digest, rd, KeyPair::from(rsa2048_get_private_key()), None, true).unwrap();
+    let sig = (0..sig.len()).step_by(2).map(|i| u8::from_str_radix(&sig[i..(i+2)], 16).unwrap()).collect::<Vec<_>>();
+    let mut sign = SignatureContent::from(sig);
+    
+    pss.verify(&sign, msg.as_ref()).unwrap();
+    assert!(pss.verify(&sign, b"rcrypto rsa with sha3 and sm2".as_ref()).is_err(), "case: {}", case);
+    pss.sign(&mut sign, msg.as_ref()).unwrap();
+    pss.verify(&sign, msg.as_ref()).unwrap();
+}
+
+#[test]
+fn pss_sha3_sm3_openssl() {
+    // PSS(mgf=MGF1(H), salt_length=H.digest_size), signed by the OpenSSL
+    pss_verify_and_sign(sha3::SHA224::new(), "74052cdc20b4e0a21204d27b8c305a0aefd1e0006a106fff140855ae8ce5744fd883474b85bf6131c6ed14cb276a688e1d34dcb2523c1d16471e8c10a0a5eedac06cbbaf682c27ea4339f51dab2df315a0c4c62c97fbd6e5b005146a884d1c60c2a6a6941f21027bd7b14f98e802275f6e8ad274543ba8db796b5859da2e69c84e54f3c62842b6f02fd1c30faa2355dbead1523cb61f71c1773ec3232d5f6b0021da68ac15bbc8ab66b961c0b8c2a73c24c19c4a9739dad1b82155ed3c507efda7faedb87ed78076472e873477a7ec0903aacd60c7bce5cdd832ea0425b75c80c43131355bc152e3152c5e15f7c6d4dc7932734dfb6647ec991f50dbbc002083", "SHA3-224");
+    pss_verify_and_sign(sha3::SHA256::new(), "4caa93abfdc409d7576e81021134655ffd52b5f11c646116d2d9eb92b4f3db1de1ad26f0fd38d8dfcc658068d7b706b54e87e0f47dd19e9e3fd50f566f59f9f1e575592a4cb515787de27ce9398feeb0c217e22ba1bfae3c76e6fa0d4ed0ccdc15c63a88c1f8cce2f1b3e84a2a2b619b1a99eec2adcab90c2732b3e9f8c3ab1f9ded31ad647d15220a76f07ddc866c43c5af15638ef6bd1727bd80add2a523f8b807091caa25229bdf6e41422a716ce836227e7d17df89e8324a783988f265b8d9ae3bcac49613dfdfe9b4f7ffb0f2f70fc2ade3a75ad234b82c6c05d62ce1de2f8e1aa7be525de6d5aaadbf0c77a7ca6fe9c2191b8a0ec603bb1c59e7a0e11f", "SHA3-256");
+    pss_verify_and_sign(sha3::SHA384::new(), "84d5b1d8b3a57a87d46b284102d40abff7fba4b4717dd7db166ee1052ab9f3d2692b062d7c5c84aabb05ce50372f5d814141dbb086e6617d8a363d84cd97a3d33fbb4511d64bad3fe1aa88adf3a90b7d7dd0f19ac0abc6c135947ab1df3262d6c7c3d0e266c13ed5c74931815985f8f78201e9f5b35620bddc1cb4a7efa227703e2ca744bf392ae81a75cd5d89fd62a764177ea8d9ff46c884e7f0180b9b192bc5326137f201062da7d19c7aab2988014299548f2b1a776396496d0ff78389d494db25f03d37e4f009342125910c7aa39eba7d69e3a18d1c63ca4b3c0d827162e94f5c82ffe299fe8aa4b2aba1a7d5eaac14e0a4238cde246712b4a398fb5da8", "SHA3-384");
+    pss_verify_and_sign(sha3::SHA512::new(), "5eef252569152b64f865bb8afc37d4c86c4926e7eac2e5360dc48ee3327a7b64a087dcdfecf00351bf67fc3353c28b01a2943b7e3dcf9cf290b4b8d4011ecebcfa2d9e126899510d91ffe426a8a8c327470218dbde719df0e0e60f631bfb0f63ff05a63248dba251343362409d083cc6405867de870abef35beeb2f13b6dee605a326f116313219992bad93d8517af788c4725974dea6418715a880995b33d70b145b2d573b721306f5ddbac103819060f974008a1675623e0ac708d2323943026c9aeb4e063d5de5be5a1720f36a41b9b2dedc1a430bd9ad2db30a2be62e923067ba6fd02cf2a8d30a328d329dbb15f6472fe18a11b40ce50fb77e34414349b", "SHA3-512");
+    pss_verify_and_sign(SM3::new(), "772752a2ec74c3582987324db6dd58cddfa5dbf345e9d74c8eed1f4adfc70221a70c1183ec9cab8eadfb630dcd1d159e1c9182321beaf717d12a36d2ce4583be8f6af4cc19d71a0283e577b48e9fbb2a1f9562ef69d3fc9b1035fd7fcf0a2748b6603e13b0a5dbeb1eb9d68a56cecd200b42fb18ce8a8f89853d669290e4e623a437548b83f084cf795da3faccb280c28be90e0d09a6a9ee342346f32464989c14b9e3a4fb90c6ea4d14afd20973de916b972f1ae425c4df9f2c609054cad3d56debe1bc9253a24af4b36ebb898aca2a6015251357609c69f674e256daaa5bda7759a71466b472553930663c84044af6f9bc0366ef88c7b56ef5e9777e264edb", "SM3");
+}
+
+#[test]
+fn pss_sign_session() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let mut pss = PSS::new(sha::SHA256::new(), rd, KeyPair::from(rsa2048_get_private_key()), None, true).unwrap();
+    let msg = "The quick brown fox jumps over the lazy dog".repeat(100);
+    
+    let mut sig = SignatureContent::new();
+    let mut session = pss.start_session();
+    session.write_vectored(&[&msg.as_bytes()[..10], &msg.as_bytes()[10..]]);
+    session.sign(&mut sig).unwrap();
+    pss.verify(&sig, msg.as_bytes()).unwrap();
+    
+    pss.sign(&mut sig, msg.as_bytes()).unwrap();
+    let mut digest = sha::SHA256::new();
+    digest.write(&msg.as_bytes()[..1000]);
+    let mut session = pss.start_session_with(digest);
+    session.write(&msg.as_bytes()[1000..]);
+    session.verify(&sig).unwrap();
+    
+    let mut session = pss.start_session();
+    session.write(msg.as_bytes());
+    session.write(b"!");
+    assert!(session.verify(&sig).is_err());
+}
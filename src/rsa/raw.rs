@@ -0,0 +1,26 @@
+//! The low-level RSA primitives on the integers(PKCS#1 v2.2 5.1 and 5.2)
+//!
+//! They're the textbook RSA without any padding, the textbook RSA is malleable and deterministic, so it's insecure
+//! to encrypt or sign the message directly by them. Use the `OAEP`/`PSS`/`PKCS1` schemes or the `encrypt_oaep`,
+//! `decrypt_oaep`, `sign_pss`, `verify_pss` of the keys instead, these primitives are only for the new padding
+//! schemes and the interoperability tests.
+
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::CryptoError;
+use crate::rsa::{PublicKey, PrivateKey};
+
+/// RSAEP: $m^e \mod n$, it's also the signature verification primitive RSAVP1
+pub fn rsaep(key: &PublicKey, m: &BigInt) -> BigInt {
+    key.rsaep(m)
+}
+
+/// RSADP: $c^d \mod n$, it's also the signature primitive RSASP1, the RSA blinding is enabled if `rd` is some.
+pub fn rsadp<R: IterSource<u32>>(key: &PrivateKey, c: &BigInt, rd: Option<&mut R>) -> Result<BigInt, CryptoError> {
+    key.rsadp(c, rd)
+}
+
+/// the `rsadp` whose result is checked by the `rsaep`, it protects against the fault attack on the CRT
+pub fn rsadp_and_check<R: IterSource<u32>>(key: &PrivateKey, c: &BigInt, rd: Option<&mut R>) -> Result<BigInt, CryptoError> {
+    key.rsadp_and_check(c, rd)
+}
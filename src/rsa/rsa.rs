@@ -8,12 +8,31 @@ use rmath::bigint::{BigInt, Nat};
 use crate::{CryptoError, CryptoErrorKind};
 use rmath::rand::IterSource;
 use std::fmt::{Display, Formatter, Debug};
+use crate::validation::{self, ValidationReport, CheckCategory, KeygenPath};
+use crate::prime::is_probably_prime;
+use crate::numeric::{MontgomeryContext, ct_mod_inverse, ct_mod_reduce, SecretScalar};
+use crate::prime::PrimeGenerator;
+use std::cell::{Cell, RefCell};
+
+/// The progress of the RSA key generation, see `PrivateKey::generate_multi_prime_key_with_progress`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeygenProgress {
+    /// the number of the primes which are found in the current set of primes
+    pub primes: usize,
+    /// the number of the primes of the key
+    pub n_primes: usize,
+    /// the number of the rejected prime candidates since the key generation started
+    pub candidates: usize,
+}
 
 pub struct PublicKey {
     // modulus, $n = p \cdot q$
     n: BigInt,
     // public exponent
     e: BigInt,
+    // the Montgomery context of the modulus which is reused by the public key operations,
+    // `None` for the even modulus(invalid RSA key)
+    mont: Option<MontgomeryContext>,
 }
 
 impl Clone for PublicKey {
@@ -21,37 +40,30 @@ impl Clone for PublicKey {
         Self {
             n: self.n.deep_clone(),
             e: self.e.deep_clone(),
+            mont: self.mont.clone(),
         }
     }
 }
 
 // chinese remainder theorem
+#[derive(Clone)]
 struct CRTValue {
     // the exponent of the prime factor r: $d \mod (prime-1)$
-    exp: BigInt,
+    exp: SecretScalar,
     // CRT coefficients: $r \cdot coeff \equiv 1 \mod prime$
-    coeff: BigInt,
+    coeff: SecretScalar,
     //prime factor: $r = p \cdot q$
-    r: BigInt,
-}
-
-impl Clone for CRTValue {
-    fn clone(&self) -> Self {
-        Self {
-            exp: self.exp.deep_clone(),
-            coeff: self.coeff.deep_clone(),
-            r: self.r.deep_clone(),
-        }
-    }
+    r: SecretScalar,
 }
 
+#[derive(Clone)]
 struct PrecomputedValues {
     // $e \cdot d_p \equiv 1 \mod (p-1)$
-    d_p: BigInt,
+    d_p: SecretScalar,
     // $e \cdot d_q \equiv 1 \mod (q-1)$
-    d_q: BigInt,
+    d_q: SecretScalar,
     // $q \cdot q_inv \equiv 1 \mod p$
-    q_inv: BigInt,
+    q_inv: SecretScalar,
 
     // CRTValues is used for the 3rd and subsequent primes. Due to a
     // historical accident, the CRT for the first two primes is handled
@@ -60,44 +72,26 @@ struct PrecomputedValues {
     crt_values: Vec<CRTValue>,
 }
 
-impl Clone for PrecomputedValues {
-    fn clone(&self) -> Self {
-        Self {
-            d_p: self.d_p.deep_clone(),
-            d_q: self.d_q.deep_clone(),
-            q_inv: self.q_inv.deep_clone(),
-            crt_values: self.crt_values.clone(),
-        }
-    }
-}
-
+/// The private key material is stored in the locked memory with the `mlock` feature
+#[derive(Clone)]
 pub struct PrivateKey {
     pk: PublicKey,
     // private exponent
-    d: BigInt,
+    d: SecretScalar,
     // prime factors of n, has >= 2 elements
-    primes: Vec<BigInt>,
+    primes: Vec<SecretScalar>,
 
     // Precomputed contains precomputed values that speed up private
     // operations, if available.
     precomputed: PrecomputedValues,
 }
 
-impl Clone for PrivateKey {
-    fn clone(&self) -> Self {
-        let mut primes = Vec::with_capacity(self.primes.len());
-        self.primes.iter().for_each(|e| {primes.push(e.deep_clone());});
-        
-        Self {
-            pk: self.pk.clone(),
-            d: self.d.clone(),
-            primes,
-            precomputed: self.precomputed.clone(),
-        }
-    }
-}
-
 impl PublicKey {
+    fn new_uncheck(n: BigInt, e: BigInt) -> Self {
+        let mont = MontgomeryContext::new(&n).ok();
+        Self { n, e, mont }
+    }
+    
     pub fn from_bigint(modulus: &BigInt, exponent: &BigInt) -> Result<Self, CryptoError> {
         if modulus.signnum() != Some(1) || exponent.signnum() != Some(1) {
             Err(CryptoError::new(CryptoErrorKind::InvalidParameter, ""))
@@ -110,12 +104,7 @@ impl PublicKey {
         if modulus <= exponent || exponent < &3u32 {
             Err(CryptoError::new(CryptoErrorKind::InvalidParameter, ""))
         } else {
-            Ok(
-                Self {
-                    n: BigInt::from(modulus.clone()),
-                    e: BigInt::from(exponent.clone()),
-                }
-            )
+            Ok(Self::new_uncheck(BigInt::from(modulus.clone()), BigInt::from(exponent.clone())))
         }
     }
     
@@ -136,12 +125,12 @@ impl PublicKey {
         (self.n.bits_len() + 7) >> 3
     }
     
-    pub(super) fn modulus(&self) -> &BigInt {
+    pub(crate) fn modulus(&self) -> &BigInt {
         &self.n
     }
     
     /// public key exponent
-    pub(super) fn exponent(&self) -> &BigInt {
+    pub(crate) fn exponent(&self) -> &BigInt {
         &self.e
     }
     
@@ -159,53 +148,100 @@ impl PublicKey {
     
     /// RSAEP: RSA encrypt primitive  
     /// $m^e \mod n$
-    pub fn encrypt(&self, m: &BigInt) -> BigInt {
-        m.exp(&self.e, &self.n)
+    pub(crate) fn rsaep(&self, m: &BigInt) -> BigInt {
+        match self.mont.as_ref() {
+            Some(ctx) if !m.is_nan() && !m.is_negative() => {
+                ctx.exp_public(m, &self.e).unwrap_or_else(|_| m.exp(&self.e, &self.n))
+            },
+            _ => m.exp(&self.e, &self.n),
+        }
+    }
+
+    fn validate_inner(&self, report: &mut ValidationReport) {
+        let (n_len, bigone, e_min) = (self.n.bits_len(), BigInt::from(1u32), BigInt::from(1u32 << 16));
+        let is_odd = |x: &BigInt| !x.is_nan() && x.rem_euclid(BigInt::from(2u32)) == bigone;
+        
+        report.check("modulus", CheckCategory::Parameter, is_odd(&self.n) && self.n > self.e,
+            "the modulus must be an odd number which is greater than the public exponent");
+        report.check("public exponent", CheckCategory::Parameter, self.is_valid().is_ok() && is_odd(&self.e),
+            "the public exponent must be an odd number in the range [3, 2^31-1]");
+        
+        let small_factor = validation::rsa_small_factor(&self.n, 1 << 16);
+        report.check("small factors", CheckCategory::Parameter, small_factor.is_none(),
+            format!("the modulus has the small factor {}", small_factor.unwrap_or(0)).as_str());
+        report.check("close primes", CheckCategory::Parameter, validation::rsa_fermat_factor(&self.n, 256).is_none(),
+            "the modulus can be factored by the Fermat's factorization, the prime factors are too close");
+        report.check("ROCA fingerprint", CheckCategory::Parameter, !validation::is_roca_fingerprint(&self.n),
+            "the modulus has the fingerprint of the ROCA(CVE-2017-15361) vulnerable keys");
+        
+        report.check("modulus size", CheckCategory::Fips, n_len >= 2048,
+            format!("the modulus size {} is less than 2048 bits", n_len).as_str());
+        report.check("public exponent range", CheckCategory::Fips, self.e > e_min,
+            "the public exponent must be in the range (2^16, 2^256)");
+    }
+    
+    /// validate the public key: public exponent and modulus sanity, the weak modulus(small factors, close primes
+    /// and ROCA fingerprint), FIPS 186-4 B.3.1 bounds.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::new("RSA public key", false);
+        self.validate_inner(&mut report);
+        report.skip("encrypt/decrypt", CheckCategory::Pairwise, "the private key is absent");
+        report.skip("sign/verify", CheckCategory::Pairwise, "the private key is absent");
+        report
     }
 }
 
-pub struct KeyPair {
-    pub_key: Option<PublicKey>,
-    pri_key: Option<PrivateKey>,
+/// The RSA key material, the public operations(encryption, verification) are always available, the private
+/// operations(decryption, signing) need the `Full` key pair.
+#[derive(Clone)]
+pub enum KeyPair {
+    PublicOnly(PublicKey),
+    Full(PrivateKey),
 }
 
 impl KeyPair {
     #[inline]
-    pub(super) fn private_key(&self) -> Option<&PrivateKey> {
-        self.pri_key.as_ref()
+    pub fn private_key(&self) -> Option<&PrivateKey> {
+        match self {
+            KeyPair::PublicOnly(_) => None,
+            KeyPair::Full(sk) => Some(sk),
+        }
     }
     
     #[inline]
-    pub(super) fn public_key(&self) -> &PublicKey {
-        if self.pri_key.is_some() {
-            self.pri_key.as_ref().unwrap().public_key()
-        } else {
-            self.pub_key.as_ref().unwrap()
+    pub fn public_key(&self) -> &PublicKey {
+        match self {
+            KeyPair::PublicOnly(pk) => pk,
+            KeyPair::Full(sk) => sk.public_key(),
         }
     }
     
     #[inline]
-    pub(super) fn modulus_len(&self) -> usize {
+    pub fn modulus_len(&self) -> usize {
         self.public_key().modulus_len()
     }
+    
+    /// the private key is present, so the key pair can be used by the signing
+    pub fn can_sign(&self) -> bool {
+        self.private_key().is_some()
+    }
+    
+    /// the private key is present, so the key pair can be used by the decryption
+    pub fn can_decrypt(&self) -> bool {
+        self.private_key().is_some()
+    }
 }
 
 impl From<PublicKey> for KeyPair {
     /// used to verify signature
     fn from(key_: PublicKey) -> Self {
-        Self {
-            pub_key: Some(key_),
-            pri_key: None,
-        }
+        KeyPair::PublicOnly(key_)
     }
 }
 
 impl From<PrivateKey> for KeyPair {
     fn from(key_: PrivateKey) -> Self {
-        Self {
-            pub_key: None,
-            pri_key: Some(key_),
-        }
+        KeyPair::Full(key_)
     }
 }
 
@@ -222,22 +258,31 @@ impl PrivateKey {
         &self.pk
     }
     
+    /// export the private exponent d(the minimal big-endian bytes) to the locked memory
+    #[cfg(feature = "mlock")]
+    pub fn to_secret_bytes(&self) -> Result<crate::secret::SecretBytes, CryptoError> {
+        self.d.to_secret_bytes()
+    }
+    
     /// private key exponent
-    pub(super) fn exponent(&self) -> &BigInt {
-        &self.d
+    pub(super) fn exponent(&self) -> BigInt {
+        self.d.get()
+    }
+    
+    /// the prime factors of the modulus
+    fn prime_factors(&self) -> Vec<BigInt> {
+        self.primes.iter().map(|p| p.get()).collect()
     }
     
     /// only used for test
-    #[allow(unused)]
+    #[cfg(test)]
     pub(super) fn from_bigint_uncheck(n: &BigInt, e: &BigInt, d: &BigInt, primes: &Vec<BigInt>) -> Result<Self, CryptoError> {
         let pk = PublicKey::from_bigint(n, e)?;
-        let mut p = Vec::with_capacity(primes.len());
-        primes.iter().for_each(|e| {p.push(e.deep_clone());});
         Ok(
             Self {
                 pk,
-                d: d.deep_clone(),
-                primes: p,
+                d: SecretScalar::new(d),
+                primes: primes.iter().map(SecretScalar::new).collect(),
                 precomputed: PrecomputedValues::nan(),
             }
         )
@@ -245,7 +290,7 @@ impl PrivateKey {
     
     /// RSADP: RSA decrypt primitive  
     /// if `rd` is some, then enabled RSA blinding
-    pub fn decrypt<R: IterSource<u32>>(&self, c: &BigInt, rd: Option<&mut R>) -> Result<BigInt, CryptoError> {
+    pub(crate) fn rsadp<R: IterSource<u32>>(&self, c: &BigInt, rd: Option<&mut R>) -> Result<BigInt, CryptoError> {
         if c > &self.pk.n {
             return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The cipher text integer is too big"));
         }
@@ -273,9 +318,12 @@ impl PrivateKey {
                         r
                     };
                     
-                    let mi = r.mod_inverse(self.pk.n.clone());
+                    // the blinded inversion $r^{-1} = (r u)^{-1} u$, the $r u$ is independent of the `r`, so the
+                    // variable-time Euclid doesn't leak the blinding factor
+                    let u = self.pk.n.random(rnd);
+                    let mi = (r.clone() * u.clone()).rem_euclid(self.pk.n.clone()).mod_inverse(self.pk.n.clone());
                     if !mi.is_nan() {
-                        break (r, Some(mi));
+                        break (r, Some((mi * u).rem_euclid(self.pk.n.clone())));
                     }
                 };
                 
@@ -291,33 +339,34 @@ impl PrivateKey {
         
         let mut m = if self.precomputed.d_p.is_nan() {
             // first private key representation
-            c.exp(&self.d, &self.pk.n)
+            c.exp(&self.d.get(), &self.pk.n)
         } else {
             // second private key representation
+            let primes = self.prime_factors();
             let (mut m1, m2) = (
-                c.exp(&self.precomputed.d_p, &self.primes[0]),
-                c.exp(&self.precomputed.d_q, &self.primes[1]),
+                c.exp(&self.precomputed.d_p.get(), &primes[0]),
+                c.exp(&self.precomputed.d_q.get(), &primes[1]),
             );
             m1 -= m2.clone();
             if m1.signnum().unwrap() < 0 {
-                m1 += self.primes[0].clone();
+                m1 += primes[0].clone();
             }
-            m1 *= self.precomputed.q_inv.clone();
-            m1.rem_euclid_assign(self.primes.first().unwrap().clone());
-            m1 *= self.primes[1].clone();
+            m1 *= self.precomputed.q_inv.get();
+            m1.rem_euclid_assign(primes[0].clone());
+            m1 *= primes[1].clone();
             m1 += m2.clone();
             
             // m1 as m
-            for (values, prime) in self.precomputed.crt_values.iter().zip(self.primes.iter().skip(2)) {
-                let mut m_i = c.exp(&values.exp, prime);
+            for (values, prime) in self.precomputed.crt_values.iter().zip(primes.iter().skip(2)) {
+                let mut m_i = c.exp(&values.exp.get(), prime);
                 m_i -= m1.clone();
-                m_i *= values.coeff.clone();
+                m_i *= values.coeff.get();
                 m_i.rem_euclid_assign(prime.clone());
                 if m_i.signnum().unwrap() < 0 {
                     m_i += prime.clone();
                 }
                 
-                m_i *= values.r.clone();
+                m_i *= values.r.get();
                 m1 += m_i;
             }
             
@@ -338,10 +387,10 @@ impl PrivateKey {
     
     
     /// decrypt the cipher integer `c` and check its validation using the public key
-    pub fn decrypt_and_check<R: IterSource<u32>>(&self, c: &BigInt, rd: Option<&mut R>) -> Result<BigInt, CryptoError> {
-        let m = self.decrypt(c, rd)?;
+    pub(crate) fn rsadp_and_check<R: IterSource<u32>>(&self, c: &BigInt, rd: Option<&mut R>) -> Result<BigInt, CryptoError> {
+        let m = self.rsadp(c, rd)?;
         
-        let check = self.pk.encrypt(&m);
+        let check = self.pk.rsaep(&m);
         
         if c != &check {
             Err(CryptoError::new(CryptoErrorKind::InnerErr, "Internal error"))
@@ -355,8 +404,8 @@ impl PrivateKey {
         self.public_key().is_valid()?;
         
         let bigone = BigInt::from(1u32);
-        let mut modulus = BigInt::from(1u32);
-        for prime in self.primes.iter() {
+        let (mut modulus, primes) = (BigInt::from(1u32), self.prime_factors());
+        for prime in primes.iter() {
             if prime <= &bigone {
                 return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "Invalid prime value"));
             }
@@ -372,9 +421,9 @@ impl PrivateKey {
         // inverse. Therefore e is coprime to lcm(p-1,q-1,r-1,...) =
         // exponent(ℤ/nℤ). It also implies that a^de ≡ a mod p as a^(p-1) ≡ 1
         // mod p. Thus a^de ≡ a mod n for all a coprime to n, as required.
-        let de = self.exponent().clone() * self.public_key().exponent().clone();
+        let de = self.exponent() * self.public_key().exponent().clone();
         
-        for prime in self.primes.iter() {
+        for prime in primes.iter() {
             let pminus1 = prime.clone() - bigone.clone();
             let congruence = de.rem_euclid(pminus1);
             if congruence != bigone {
@@ -385,6 +434,75 @@ impl PrivateKey {
         Ok(())
     }
 
+    /// validate the private key: the public key checks, the primality of the prime factors, the consistency of the 
+    /// private exponent and the CRT values, the pairwise encrypt/decrypt and sign/verify tests, and the 
+    /// FIPS 186-4 B.3.1/B.3.3 bounds of the primes and the private exponent.
+    pub fn validate<R: IterSource<u32>>(&self, rd: &mut R) -> ValidationReport {
+        let mut report = ValidationReport::new("RSA private key", true);
+        self.pk.validate_inner(&mut report);
+        
+        let (bigone, n_len) = (BigInt::from(1u32), self.pk.n.bits_len());
+        let (d, primes) = (self.d.get(), self.prime_factors());
+        let is_primes = primes.len() >= 2 && primes.iter().all(|p| {
+            p > &bigone && is_probably_prime(p, 20, rd)
+        });
+        report.check("prime factors", CheckCategory::Parameter, is_primes, "the prime factor is not a prime");
+        
+        let modulus = primes.iter().fold(BigInt::from(1u32), |mut m, p| {m *= p.clone(); m});
+        if !report.check("modulus product", CheckCategory::Parameter, modulus == self.pk.n,
+            "the product of the prime factors doesn't equal to the modulus") {
+            // the left checks are meaningless for the wrong factors
+            return report;
+        }
+        
+        let de = d.clone() * self.pk.e.clone();
+        let is_congruent = !d.is_nan() && d > bigone && d < self.pk.n && primes.iter().all(|p| {
+            de.rem_euclid(p.clone() - bigone.clone()) == bigone
+        });
+        report.check("private exponent", CheckCategory::Parameter, is_congruent, 
+            "the private exponent doesn't satisfy d * e = 1 mod (p-1)");
+        
+        if self.precomputed.d_p.is_nan() {
+            report.skip("CRT values", CheckCategory::Parameter, "the CRT values are not precomputed");
+        } else {
+            let (p, q) = (&primes[0], &primes[1]);
+            let mut is_crt = self.precomputed.d_p.get() == d.rem_euclid(p.clone() - bigone.clone()) 
+                && self.precomputed.d_q.get() == d.rem_euclid(q.clone() - bigone.clone())
+                && (self.precomputed.q_inv.get() * q.clone()).rem_euclid(p.clone()) == bigone;
+            let mut r = p.clone() * q.clone();
+            for (values, prime) in self.precomputed.crt_values.iter().zip(primes.iter().skip(2)) {
+                is_crt &= values.exp.get() == d.rem_euclid(prime.clone() - bigone.clone()) && values.r.get() == r
+                    && (values.coeff.get() * r.clone()).rem_euclid(prime.clone()) == bigone;
+                r *= prime.clone();
+            }
+            report.check("CRT values", CheckCategory::Parameter, is_crt, "the CRT values are inconsistent with the private exponent");
+        }
+        
+        let m = BigInt::from(0x5a5a5a5au32).rem_euclid(self.pk.n.clone());
+        let is_enc = match self.rsadp::<R>(&self.pk.rsaep(&m), None) {
+            Ok(x) => x == m,
+            Err(_) => false,
+        };
+        report.check("encrypt/decrypt", CheckCategory::Pairwise, is_enc, "the decrypted message doesn't match");
+        
+        let m = BigInt::from(0x3c3c3c3cu32).rem_euclid(self.pk.n.clone());
+        let is_sig = match self.rsadp(&m, Some(rd)) {
+            Ok(s) => self.pk.rsaep(&s) == m,
+            Err(_) => false,
+        };
+        report.check("sign/verify", CheckCategory::Pairwise, is_sig, "the signature cannot be verified by the public key");
+        
+        report.check("prime count", CheckCategory::Fips, primes.len() == 2, "the FIPS 186-4 only allows two prime factors");
+        let (p, q) = (&primes[0], &primes[1]);
+        let diff = if p > q { p.clone() - q.clone() } else { q.clone() - p.clone() };
+        report.check("prime distance", CheckCategory::Fips, n_len > 200 && diff > (BigInt::from(1u32) << ((n_len >> 1) - 100)),
+            "the |p-q| must be greater than 2^(nlen/2-100)");
+        report.check("private exponent size", CheckCategory::Fips, d > (BigInt::from(1u32) << (n_len >> 1)),
+            "the private exponent must be greater than 2^(nlen/2)");
+        
+        report
+    }
+
     /// `generate_key` generates an RSA keypair of the given bit size using the
     /// random source random (for example, crypto/rand.Reader).
     /// 
@@ -410,6 +528,65 @@ impl PrivateKey {
     /// `prime_test_round_num`(n) means the number of test rounds, for any odd number that great than 2 and positive integer n, the probability of error 
     /// in MillerRabinPrimeTest is at most $2^{-n}$.
     pub fn generate_multi_prime_key<R: IterSource<u32>>(n_primes: usize, bits_len: usize, prime_test_round_num: usize, rd: &mut R) -> Result<PrivateKey, CryptoError> {
+        Self::generate_multi_prime_key_with(n_primes, bits_len, |prime_bits_len| {
+            Nat::generate_prime(prime_bits_len, prime_test_round_num, rd).map(BigInt::from)
+                .map_err(|e| CryptoError::new(CryptoErrorKind::OuterErr, e))
+        })
+    }
+    
+    /// `generate_multi_prime_key` with the progress report, the `progress` is called when a prime candidate is rejected
+    /// and when a prime is found, the key generation is cancelled with the `Cancelled` error if the `progress` returns
+    /// `false`(e.g. the deadline is reached or the user aborts). The primes are generated by the `PrimeGenerator`, so
+    /// the key is not the same as the key generated by the `generate_multi_prime_key` from the same random source.
+    pub fn generate_multi_prime_key_with_progress<R, F>(n_primes: usize, bits_len: usize, prime_test_round_num: usize, rd: &mut R, progress: F) -> Result<PrivateKey, CryptoError>
+        where R: IterSource<u32> + Clone, F: FnMut(&KeygenProgress) -> bool {
+        let (progress, primes) = (RefCell::new(progress), Cell::new(0usize));
+        let report = |candidates: usize| {
+            let p = KeygenProgress { primes: primes.get(), n_primes, candidates };
+            (progress.borrow_mut())(&p)
+        };
+        
+        let mut pg = PrimeGenerator::new(rd.clone(), prime_test_round_num);
+        pg.set_cancellable_progress(report);
+        let key = Self::generate_multi_prime_key_with(n_primes, bits_len, |prime_bits_len| {
+            let p = pg.generate(prime_bits_len)?;
+            // the primes are regenerated if they cannot form the key
+            primes.set((primes.get() % n_primes) + 1);
+            if report(pg.candidates()) {
+                Ok(p)
+            } else {
+                Err(CryptoError::new(CryptoErrorKind::Cancelled, "The RSA key generation is cancelled"))
+            }
+        });
+        *rd = pg.into_inner();
+        key
+    }
+    
+    /// `generate_key` with the primes generated by the `threads` worker threads, see `generate_multi_prime_key_parallel`
+    #[cfg(feature = "parallel")]
+    pub fn generate_key_parallel<R>(bits_len: usize, prime_test_round_num: usize, threads: usize, rd: &mut R) -> Result<PrivateKey, CryptoError>
+        where R: IterSource<u32> + Clone + Send {
+        Self::generate_multi_prime_key_parallel(2, bits_len, prime_test_round_num, threads, rd)
+    }
+    
+    /// `generate_multi_prime_key` with the prime candidates tested by the `threads` worker threads concurrently.  
+    /// The key only depends on the random source `rd`, so the same key is generated from the same seeded random source
+    /// no matter how many threads are used, but it's not the same as the key generated by the `generate_multi_prime_key`.
+    /// See `PrimeGenerator::generate_parallel`.
+    #[cfg(feature = "parallel")]
+    pub fn generate_multi_prime_key_parallel<R>(n_primes: usize, bits_len: usize, prime_test_round_num: usize, threads: usize, rd: &mut R) -> Result<PrivateKey, CryptoError>
+        where R: IterSource<u32> + Clone + Send {
+        let mut pg = PrimeGenerator::new(rd.clone(), prime_test_round_num);
+        let key = Self::generate_multi_prime_key_with(n_primes, bits_len, |prime_bits_len| {
+            pg.generate_parallel(prime_bits_len, threads)
+        });
+        *rd = pg.into_inner();
+        key
+    }
+    
+    /// `gen_prime(bits_len)` generates a random prime with `bits_len` bits and the most significant two bits set
+    fn generate_multi_prime_key_with<F>(n_primes: usize, bits_len: usize, mut gen_prime: F) -> Result<PrivateKey, CryptoError>
+        where F: FnMut(usize) -> Result<BigInt, CryptoError> {
         if n_primes < 2 {
             return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "n_primes must be great or equal to 2"));
         }
@@ -452,14 +629,7 @@ impl PrivateKey {
             }
             
             for i in 0..n_primes {
-                let prime = match Nat::generate_prime(cbits / (n_primes - i), prime_test_round_num, rd) {
-                    Ok(nat) => {
-                        BigInt::from(nat)
-                    },
-                    Err(e) => {
-                        return Err(CryptoError::new(CryptoErrorKind::OuterErr, e));
-                    }
-                };
+                let prime = gen_prime(cbits / (n_primes - i))?;
 
                 cbits -= prime.bits_len();
                 primes.push(prime);
@@ -485,9 +655,7 @@ impl PrivateKey {
                 continue 'next_set_of_primes;
             }
 
-            let pri_exp = pub_exp.mod_inverse(totient);
-
-            if !pri_exp.is_nan() {
+            if let Some(pri_exp) = Self::private_exponent(&pub_exp, &totient) {
                 break (pri_exp, n);
             }
         };
@@ -498,16 +666,156 @@ impl PrivateKey {
         
         Ok(
             PrivateKey {
-                pk: PublicKey {
-                    n: modulus,
-                    e: pub_exp,
-                },
-                d: pri_exp,
-                primes,
+                pk: PublicKey::new_uncheck(modulus, pub_exp),
+                d: SecretScalar::new(&pri_exp),
+                primes: primes.iter().map(SecretScalar::new).collect(),
                 precomputed,
             }
         )
     }
+    
+    /// generate the 2-prime RSA key by the FIPS 186-5 A.1.5 and SP 800-56B Rev. 2 6.3.1.1 when the `fips_mode` is true,
+    /// otherwise by the `generate_key`, and return the key with the `KeygenPath` which the key is generated by.
+    /// 
+    /// The FIPS path only accepts the 2048, 3072 and 4096 bits modulus with the public exponent 65537. The primes
+    /// $p$, $q$ are in the range $[\sqrt{2} \cdot 2^{nlen/2-1}, 2^{nlen/2})$ with $gcd(p-1, e) = 1$, $p-1$ and $p+1$
+    /// have the auxiliary prime factors of the FIPS 186-5 Table A.1 minimum length, $|p-q| > 2^{nlen/2-100}$, and
+    /// the private exponent $d = e^{-1} \mod lcm(p-1, q-1)$ is greater than $2^{nlen/2}$. The primes are tested by at
+    /// least 5(the 1024-bit prime) or 4 rounds of the Miller-Rabin test, and the auxiliary primes by 50 rounds. The key
+    /// is rejected if it isn't FIPS compliant by the `validate`(including the pairwise consistency tests).  
+    /// The `rd` must be the approved RBG(e.g. the `cipher_mode::CtrDrbg`) which supports the security strength of
+    /// the modulus, it cannot be checked by the key generation.
+    pub fn generate_key_with_mode<R: IterSource<u32>>(bits_len: usize, prime_test_round_num: usize, fips_mode: bool, rd: &mut R) -> Result<(PrivateKey, KeygenPath), CryptoError> {
+        if !fips_mode {
+            return Self::generate_key(bits_len, prime_test_round_num, rd).map(|k| (k, KeygenPath::Default));
+        }
+        
+        // FIPS 186-5 Table A.1, the auxiliary primes of the probable primes are longer than 140, 170, 200 bits
+        let aux_len = match bits_len {
+            2048 => 141,
+            3072 => 171,
+            4096 => 201,
+            _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+                format!("The FIPS RSA modulus size must be 2048, 3072 or 4096 bits, not {} bits", bits_len))),
+        };
+        
+        let half = bits_len >> 1;
+        let rounds = prime_test_round_num.max(if half <= 1024 {5} else {4});
+        let (bigone, pub_exp) = (BigInt::from(1u32), BigInt::from(65537u32));
+        let (min_diff, min_d) = (bigone.clone() << (half - 100), bigone.clone() << half);
+        let key = loop {
+            let p = Self::generate_fips_prime(half, aux_len, rounds, &pub_exp, rd)?;
+            let q = loop {
+                let q = Self::generate_fips_prime(half, aux_len, rounds, &pub_exp, rd)?;
+                let diff = if p > q { p.clone() - q.clone() } else { q.clone() - p.clone() };
+                if diff > min_diff {
+                    break q;
+                }
+            };
+            
+            let (pm1, qm1) = (p.clone() - bigone.clone(), q.clone() - bigone.clone());
+            let (g, _, _) = pm1.gcd(qm1.clone());
+            let lambda = (pm1 * qm1) / g;
+            // d <= 2^(nlen/2) happens with the negligible probability, the primes are regenerated
+            let d = match Self::private_exponent(&pub_exp, &lambda) {
+                Some(d) if d > min_d => d,
+                _ => continue,
+            };
+            
+            let precomputed = PrecomputedValues::new(p.clone(), q.clone(), d.clone(), &[]);
+            break PrivateKey {
+                pk: PublicKey::new_uncheck(p.clone() * q.clone(), pub_exp.clone()),
+                d: SecretScalar::new(&d),
+                primes: vec![SecretScalar::new(&p), SecretScalar::new(&q)],
+                precomputed,
+            };
+        };
+        
+        let report = key.validate(rd);
+        if report.is_fips_compliant() {
+            Ok((key, KeygenPath::FipsRsaAuxiliaryPrimes))
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::InnerErr, format!("The generated FIPS RSA key is rejected: {}", report)))
+        }
+    }
+    
+    /// FIPS 186-5 A.1.5 and C.9, the probable prime `p` with `bits_len` bits, `p-1` and `p+1` have the auxiliary 
+    /// prime factors `r1` and `r2` respectively.
+    fn generate_fips_prime<R: IterSource<u32>>(bits_len: usize, aux_len: usize, rounds: usize, pub_exp: &BigInt, rd: &mut R) -> Result<BigInt, CryptoError> {
+        let bigone = BigInt::from(1u32);
+        let r1 = Self::generate_fips_aux_prime(aux_len, rd);
+        let r2 = loop {
+            let r2 = Self::generate_fips_aux_prime(aux_len, rd);
+            if r2 != r1 {
+                break r2;
+            }
+        };
+        
+        // R ≡ 1 (mod 2r1) and R ≡ -1 (mod r2), the gcd(2r1, r2) = 1 for the distinct odd primes. R = 1 + 2r1(r2 - r1^{-1} mod r2)
+        // is the same residue as the C.9 formula, the r1 and r2 are the secret factors of the p-1 and p+1, so only one inverse
+        // by the odd prime r2 is needed, and it's constant-time.
+        let r1_inv = ct_mod_reduce(&r1, r1.bits_len(), &r2).and_then(|t| ct_mod_inverse(&t, &r2))?;
+        let r1x2 = r1 << 1usize;
+        let m = r1x2.clone() * r2.clone();
+        let crt = bigone.clone() + r1x2 * (r2 - r1_inv);
+        let (limit, min_sq) = (bigone.clone() << bits_len, bigone.clone() << ((bits_len << 1) - 1));
+        
+        'next_x: loop {
+            // X in the range [sqrt(2) * 2^(bits_len-1), 2^bits_len)
+            let x = Self::fips_random_bits(bits_len, rd);
+            if x.clone() * x.clone() < min_sq {
+                continue;
+            }
+            
+            let mut y = x.clone() + (crt.clone() - x).rem_euclid(m.clone());
+            for _ in 0..(5 * bits_len) {
+                if y >= limit {
+                    continue 'next_x;
+                }
+                
+                let (g, _, _) = (y.clone() - bigone.clone()).gcd(pub_exp.clone());
+                if g == bigone && is_probably_prime(&y, rounds, rd) {
+                    return Ok(y);
+                }
+                y += m.clone();
+            }
+            
+            return Err(CryptoError::new(CryptoErrorKind::InnerErr, "The FIPS 186-5 C.9 prime generation failed"));
+        }
+    }
+    
+    fn generate_fips_aux_prime<R: IterSource<u32>>(aux_len: usize, rd: &mut R) -> BigInt {
+        loop {
+            let r = Self::fips_random_bits(aux_len, rd) | BigInt::from(1u32);
+            // the error probability of 50 rounds is at most 2^{-100} for the worst case
+            if is_probably_prime(&r, 50, rd) {
+                return r;
+            }
+        }
+    }
+    
+    /// random number in the range $[2^{bits_len-1}, 2^{bits_len})$
+    fn fips_random_bits<R: IterSource<u32>>(bits_len: usize, rd: &mut R) -> BigInt {
+        let len = (bits_len + 31) >> 5;
+        let mut words = Vec::with_capacity(len);
+        rd.iter_mut().take(len).for_each(|x| words.push(x));
+        words.resize(len, 0);
+        
+        let b = bits_len - ((len - 1) << 5);
+        if b < 32 {
+            words[len - 1] &= (1u32 << b) - 1;
+        }
+        words[len - 1] |= 1u32 << (b - 1);
+        BigInt::from(words)
+    }
+    
+    /// $e^{-1} \mod m$ for the prime public exponent `e` and the secret modulus `m`(φ(n) or λ(n)), `None` if the `e` isn't
+    /// invertible. Let $u = (m \mod e)^{-1} \mod e$, then $1 + m (e - u) \equiv 0 \mod e$ and $d = (1 + m (e - u)) / e$, 
+    /// the secret `m` is only reduced and inverted modulo the public `e` in constant time.
+    fn private_exponent(e: &BigInt, m: &BigInt) -> Option<BigInt> {
+        let u = ct_mod_reduce(m, m.bits_len(), e).and_then(|t| ct_mod_inverse(&t, e)).ok()?;
+        Some((BigInt::from(1u32) + m.clone() * (e.clone() - u)) / e.clone())
+    }
 }
 
 impl PrecomputedValues {
@@ -515,35 +823,40 @@ impl PrecomputedValues {
         let bigone = BigInt::from(1u32);
         let d_p = d.rem_euclid(p.clone() - bigone.clone());
         let d_q = d.rem_euclid(q.clone() - bigone.clone());
-        let q_inv = q.mod_inverse(p.clone());
+        let q_inv = Self::ct_inverse(&q, &p);
         let mut r = p.clone() * q.clone();
         let mut crt_values = Vec::with_capacity(primes.len());
         
         for prime in primes.iter() {
             let exp = d.rem_euclid(prime.clone() - bigone.clone());
             let rd = r.deep_clone();
-            let coeff = r.mod_inverse(prime.clone());
+            let coeff = Self::ct_inverse(&r, prime);
             
             r *= prime.clone();
             crt_values.push(CRTValue::new(exp, coeff, rd));
         }
         
         Self {
-            d_p,
-            d_q,
-            q_inv,
+            d_p: SecretScalar::new(&d_p),
+            d_q: SecretScalar::new(&d_q),
+            q_inv: SecretScalar::new(&q_inv),
             crt_values,
         }
     }
     
+    /// $a^{-1} \mod prime$ for the secret `a` and `prime` in constant time, nan if the `a` isn't invertible
+    fn ct_inverse(a: &BigInt, prime: &BigInt) -> BigInt {
+        ct_mod_reduce(a, a.bits_len(), prime).and_then(|a| ct_mod_inverse(&a, prime)).unwrap_or_else(|_| BigInt::from_be_bytes(&[]))
+    }
+    
     /// only used for test
-    #[allow(unused)]
+    #[cfg(test)]
     fn nan() -> Self {
         let n = Vec::new();
         Self {
-            d_p: BigInt::from_be_bytes(n.as_slice()),
-            d_q: BigInt::from_le_bytes(n.as_slice()),
-            q_inv: BigInt::from_le_bytes(n.as_slice()),
+            d_p: SecretScalar::new(&BigInt::from_be_bytes(n.as_slice())),
+            d_q: SecretScalar::new(&BigInt::from_le_bytes(n.as_slice())),
+            q_inv: SecretScalar::new(&BigInt::from_le_bytes(n.as_slice())),
             crt_values: Vec::new(),
         }
     }
@@ -553,9 +866,9 @@ impl PrecomputedValues {
 impl CRTValue {
     fn new(exp: BigInt, coeff: BigInt, r: BigInt) -> Self {
         Self {
-            exp,
-            coeff,
-            r
+            exp: SecretScalar::new(&exp),
+            coeff: SecretScalar::new(&coeff),
+            r: SecretScalar::new(&r),
         }
     }
 }
@@ -575,7 +888,7 @@ impl Debug for PublicKey {
 impl Display for PrivateKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let pk = format!("{}", self.pk);
-        let d = format!("{:#x}", self.d);
+        let d = format!("{:#x}", self.d.get());
         write!(f, "{{d: \"{}\", {}}}", d, pk)
     }
 }
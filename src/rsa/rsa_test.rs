@@ -1,4 +1,6 @@
-use crate::rsa::PrivateKey;
+use crate::rsa::{PrivateKey, KeyPair};
+use crate::rsa::raw;
+use crate::CryptoErrorKind;
 use rmath::bigint::BigInt;
 use rmath::rand::{CryptoRand, DefaultSeed};
 use std::str::FromStr;
@@ -6,16 +8,16 @@ use std::str::FromStr;
 fn rsa_key_basics(pk: &PrivateKey, rd: &mut CryptoRand<u32>) {
     pk.is_valid().unwrap();
     
-    assert!(pk.exponent() <= pk.modulus(), "private exponent too large");
+    assert!(&pk.exponent() <= pk.modulus(), "private exponent too large");
     
     let m = BigInt::from(42u32);
-    let c = pk.public_key().encrypt(&m);
+    let c = raw::rsaep(pk.public_key(), &m);
     
-    let m2 = pk.decrypt::<CryptoRand<u32>>(&c, None).unwrap();
+    let m2 = raw::rsadp::<CryptoRand<u32>>(pk, &c, None).unwrap();
     
     assert_eq!(m, m2, "encrypt message({}) does not equal to decrypt message({})", m, m2);
     
-    let m3 = pk.decrypt(&c, Some(rd)).unwrap();
+    let m3 = raw::rsadp(pk, &c, Some(rd)).unwrap();
     
     assert_eq!(m, m3, "encrypt message({}) does not equal to decrypt message({}) with blinding", m, m2);
 }
@@ -93,3 +95,164 @@ fn rsa_keygen_2048() {
     let mut rd = CryptoRand::new(&seed).unwrap();
     rsa_key_basics(&pk, &mut rd);
 }
+
+#[cfg(feature = "sha2")]
+#[test]
+fn rsa_oaep_pss_bytes() {
+    use crate::sha::SHA256;
+    
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let sk = PrivateKey::generate_key(1024, 19, &mut rd).unwrap();
+    let pk = sk.public_key();
+    let msg = b"the byte-oriented RSA";
+    
+    let c = pk.encrypt_oaep(SHA256::new(), b"label", msg, &mut rd).unwrap();
+    assert_eq!(c.len(), pk.modulus_len());
+    assert_eq!(sk.decrypt_oaep(SHA256::new(), b"label", c.as_slice(), &mut rd).unwrap(), msg.to_vec());
+    assert!(sk.decrypt_oaep(SHA256::new(), b"other label", c.as_slice(), &mut rd).is_err());
+    // the maximum message length is 128 - 2 * 32 - 2
+    assert!(pk.encrypt_oaep(SHA256::new(), &[], &[0u8; 62], &mut rd).is_ok());
+    assert!(pk.encrypt_oaep(SHA256::new(), &[], &[0u8; 63], &mut rd).is_err());
+    
+    let sig = sk.sign_pss(SHA256::new(), msg, &mut rd).unwrap();
+    assert_eq!(sig.len(), pk.modulus_len());
+    pk.verify_pss(SHA256::new(), msg, sig.as_slice()).unwrap();
+    assert!(pk.verify_pss(SHA256::new(), b"the other message", sig.as_slice()).is_err());
+    assert!(pk.verify_pss(SHA256::new(), msg, &sig[1..]).is_err());
+    
+    // the raw primitives are the inverse of each other
+    let m = BigInt::from_be_bytes(&sig[1..]);
+    assert_eq!(raw::rsadp_and_check(&sk, &raw::rsaep(pk, &m), Some(&mut rd)).unwrap(), m);
+}
+
+#[test]
+fn rsa_key_validate() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    
+    let pk = PrivateKey::generate_key(2048, 19, &mut rd).unwrap();
+    let report = pk.validate(&mut rd);
+    assert!(report.is_fips_compliant(), "{}", report);
+    assert!(pk.public_key().validate().is_fips_compliant());
+    
+    let n = BigInt::from_str("290684273230919398108010081414538931343").unwrap();
+    let e = BigInt::from(65537u32); 
+    let d = BigInt::from_str("31877380284581499213530787347443987241").unwrap();
+    let primes = vec![BigInt::from_str("16775196964030542637").unwrap(), BigInt::from_str("17328218193455850539").unwrap()];
+    let pk = PrivateKey::from_bigint_uncheck(&n, &e, &d, &primes).unwrap();
+    let report = pk.validate(&mut rd);
+    assert!(report.is_valid() && !report.is_fips_compliant(), "{}", report);
+    assert!(report.to_result(false).is_ok());
+    assert!(report.to_result(true).is_err());
+    let failures = report.failures().map(|c| c.name).collect::<Vec<_>>();
+    assert_eq!(failures, vec!["modulus size", "prime distance"]);
+    
+    let d2 = d.clone() + BigInt::from(2u32);
+    let report = PrivateKey::from_bigint_uncheck(&n, &e, &d2, &primes).unwrap().validate(&mut rd);
+    assert!(!report.is_valid(), "{}", report);
+    assert!(report.failures().any(|c| c.name == "private exponent"));
+    assert!(report.failures().any(|c| c.name == "encrypt/decrypt"));
+    
+    let primes2 = vec![primes[0].clone() + BigInt::from(2u32), primes[1].clone()];
+    let report = PrivateKey::from_bigint_uncheck(&n, &e, &d, &primes2).unwrap().validate(&mut rd);
+    assert!(report.failures().any(|c| c.name == "modulus product"), "{}", report);
+    
+    let n2 = n.clone() + BigInt::from(1u32);
+    let report = crate::rsa::PublicKey::from_bigint(&n2, &e).unwrap().validate();
+    assert!(!report.is_valid(), "{}", report);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn rsa_keygen_parallel() {
+    use rmath::rand::{LinearCongruentialRand, Seed, RandError};
+    
+    struct FixedSeed(u32);
+    impl Seed<u32> for FixedSeed {
+        fn seed(&self) -> Result<u32, RandError> {
+            Ok(self.0)
+        }
+    }
+    
+    let seeded_rd = || LinearCongruentialRand::new(&FixedSeed(0x2545f491), 1664525u32, 1013904223, u32::MAX).unwrap();
+    let (mut rd1, mut rd4) = (seeded_rd(), seeded_rd());
+    let pk1 = PrivateKey::generate_multi_prime_key_parallel(3, 1024, 19, 1, &mut rd1).unwrap();
+    let pk4 = PrivateKey::generate_multi_prime_key_parallel(3, 1024, 19, 4, &mut rd4).unwrap();
+    
+    // the same key is generated from the same seed no matter how many threads are used
+    assert_eq!(pk1.modulus(), pk4.modulus());
+    assert_eq!(pk1.modulus().bits_len(), 1024);
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    rsa_key_basics(&pk4, &mut rd);
+    
+    let pk = PrivateKey::generate_key_parallel(2048, 19, 4, &mut rd).unwrap();
+    assert_eq!(pk.modulus().bits_len(), 2048);
+    rsa_key_basics(&pk, &mut rd);
+    assert!(PrivateKey::generate_key_parallel(2048, 19, 0, &mut rd).is_err());
+}
+
+#[test]
+fn rsa_key_pair_capability() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    let sk = PrivateKey::generate_key(1024, 19, &mut rd).unwrap();
+    let pk = sk.public_key().clone();
+    
+    let full = KeyPair::from(sk);
+    assert!(full.can_sign() && full.can_decrypt());
+    assert!(full.private_key().is_some());
+    assert_eq!(full.modulus_len(), 128);
+    
+    let public_only = KeyPair::from(pk);
+    assert!(!public_only.can_sign() && !public_only.can_decrypt());
+    assert!(public_only.private_key().is_none());
+    assert_eq!(public_only.public_key().modulus_len(), full.public_key().modulus_len());
+    match public_only {
+        KeyPair::PublicOnly(_) => {},
+        KeyPair::Full(_) => panic!("the public key must be the PublicOnly key pair"),
+    }
+}
+
+
+#[test]
+fn rsa_keygen_progress() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let mut reports = Vec::new();
+    let pk = PrivateKey::generate_multi_prime_key_with_progress(3, 1024, 19, &mut rd, |p| {
+        reports.push(*p);
+        true
+    }).unwrap();
+    assert_eq!(pk.modulus().bits_len(), 1024);
+    rsa_key_basics(&pk, &mut rd);
+    assert!(reports.iter().all(|p| p.n_primes == 3 && p.primes <= 3));
+    assert_eq!(reports.last().map(|p| p.primes), Some(3));
+    assert!(reports.windows(2).all(|w| w[0].candidates <= w[1].candidates));
+    
+    // cancelled after 8 candidates
+    let e = PrivateKey::generate_multi_prime_key_with_progress(2, 2048, 19, &mut rd, |p| p.candidates < 8);
+    assert_eq!(e.err().map(|e| e.kind()), Some(CryptoErrorKind::Cancelled));
+}
+#[test]
+fn rsa_fips_keygen() {
+    use crate::validation::KeygenPath;
+    
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let mut rd = CryptoRand::new(&seed).unwrap();
+    
+    let (sk, path) = PrivateKey::generate_key_with_mode(2048, 0, true, &mut rd).unwrap();
+    assert_eq!(path, KeygenPath::FipsRsaAuxiliaryPrimes);
+    assert!(path.is_fips());
+    assert_eq!(sk.modulus().bits_len(), 2048);
+    assert_eq!(sk.public_key().exponent(), &BigInt::from(65537u32));
+    let report = sk.validate(&mut rd);
+    assert!(report.is_fips_compliant(), "{}", report);
+    rsa_key_basics(&sk, &mut rd);
+    
+    // the FIPS path only accepts the modulus sizes of the FIPS 186-5 Table A.1
+    for &bits_len in [1024usize, 2047, 2560].iter() {
+        assert_eq!(PrivateKey::generate_key_with_mode(bits_len, 19, true, &mut rd).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    }
+    let (sk, path) = PrivateKey::generate_key_with_mode(1024, 19, false, &mut rd).unwrap();
+    assert_eq!(path, KeygenPath::Default);
+    assert!(!path.is_fips() && !sk.validate(&mut rd).is_fips_compliant());
+}
\ No newline at end of file
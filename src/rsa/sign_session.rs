@@ -0,0 +1,65 @@
+//! The streaming signature session over the `Digest` writer
+//!
+//! The message is written to the digest of the session piece by piece, and the signature is computed from the
+//! message digest when the session finished. The type of the digest is the same as the signature scheme's, so the
+//! digest always matches the hash function of the scheme's encoding(e.g. the `DigestInfo` of the RSASSA-PKCS1).
+
+use crate::{Digest, CryptoError};
+use crate::rsa::SignatureContent;
+
+/// The signature scheme which can sign the message digest `m_hash = Hash(Message)` computed by the `H`
+pub trait DigestSignature<H: Digest> {
+    fn sign_digest(&mut self, signature: &mut SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError>;
+
+    fn verify_digest(&mut self, signature: &SignatureContent, m_hash: &[u8]) -> Result<(), CryptoError>;
+}
+
+pub struct SignSession<'a, S, H> {
+    scheme: &'a mut S,
+    digest: H,
+}
+
+impl<'a, S, H> SignSession<'a, S, H>
+    where S: DigestSignature<H>, H: Digest {
+    pub(super) fn new(scheme: &'a mut S, digest: H) -> Self {
+        Self {
+            scheme,
+            digest,
+        }
+    }
+
+    /// write the next piece of the message
+    pub fn write(&mut self, data: &[u8]) {
+        self.digest.write(data);
+    }
+
+    pub fn write_vectored(&mut self, bufs: &[&[u8]]) {
+        self.digest.write_vectored(bufs);
+    }
+
+    /// finish the session and sign the written message
+    pub fn sign(mut self, signature: &mut SignatureContent) -> Result<(), CryptoError> {
+        let mut m_hash = Vec::with_capacity((self.digest.bits_len() + 7) >> 3);
+        self.digest.checksum(&mut m_hash);
+        self.scheme.sign_digest(signature, m_hash.as_slice())
+    }
+
+    /// finish the session and verify the `signature` of the written message
+    pub fn verify(mut self, signature: &SignatureContent) -> Result<(), CryptoError> {
+        let mut m_hash = Vec::with_capacity((self.digest.bits_len() + 7) >> 3);
+        self.digest.checksum(&mut m_hash);
+        self.scheme.verify_digest(signature, m_hash.as_slice())
+    }
+}
+
+impl<'a, S, H> std::io::Write for SignSession<'a, S, H>
+    where S: DigestSignature<H>, H: Digest {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.digest.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
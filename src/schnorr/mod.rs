@@ -0,0 +1,16 @@
+//! BIP-340 Schnorr signatures over the secp256k1
+//!
+//! BIP-340(the x-only public keys, the tagged hashes, the auxiliary randomness nonce derivation and the batch
+//! verification)  
+//! BIP-327(the MuSig2 multi-signatures, the key aggregation, the two rounds nonces and the partial signatures)  
+//! SEC 2 v2(the secp256k1 curve)
+
+mod schnorr;
+pub use schnorr::{Schnorr, SchnorrSignature, tagged_hash, schnorr_batch_verify, SCHNORR_PUBLIC_KEY_LEN, SCHNORR_SIGNATURE_LEN};
+
+mod musig2;
+pub use musig2::{MuSig2KeyAggContext, MuSig2SecretNonce, MuSig2Session, musig2_key_sort, musig2_nonce_gen, musig2_nonce_gen_with_rand,
+    musig2_nonce_agg, MUSIG2_PUBLIC_KEY_LEN, MUSIG2_PUBLIC_NONCE_LEN, MUSIG2_PARTIAL_SIGNATURE_LEN};
+
+#[cfg(test)]
+mod schnorr_test;
\ No newline at end of file
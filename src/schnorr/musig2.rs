@@ -0,0 +1,455 @@
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::elliptic::{CurveParams, EllipticCurve};
+use crate::schnorr::schnorr::{to_bytes32, has_even_y, is_infinity, challenge};
+use crate::schnorr::{tagged_hash, SchnorrSignature};
+use crate::{CryptoError, CryptoErrorKind};
+
+/// the length(in bytes) of the compressed(plain) public key of the signer
+pub const MUSIG2_PUBLIC_KEY_LEN: usize = 33;
+
+/// the length(in bytes) of the public nonce `cbytes(R1) || cbytes(R2)`, it's also the length of the aggregate nonce
+pub const MUSIG2_PUBLIC_NONCE_LEN: usize = 66;
+
+/// the length(in bytes) of the partial signature
+pub const MUSIG2_PARTIAL_SIGNATURE_LEN: usize = 32;
+
+const SECRET_NONCE_LEN: usize = 64 + MUSIG2_PUBLIC_KEY_LEN;
+
+fn param_err<T>(msg: String) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, msg))
+}
+
+fn infinity() -> (BigInt, BigInt) {
+    (BigInt::from(0u32), BigInt::from(0u32))
+}
+
+fn neg(curve: &CurveParams, x: &BigInt, y: &BigInt) -> (BigInt, BigInt) {
+    if is_infinity(x, y) {
+        infinity()
+    } else {
+        (x.deep_clone(), curve.field_order().clone() - y.clone())
+    }
+}
+
+/// cbytes(P), the 33 bytes compressed encoding
+fn cbytes(x: &BigInt, y: &BigInt) -> Vec<u8> {
+    let mut buf = vec![if has_even_y(y) { 2u8 } else { 3u8 }];
+    buf.extend_from_slice(to_bytes32(x).as_slice());
+    buf
+}
+
+/// cbytes_ext(P), the infinite point is encoded as the 33 zero bytes
+fn cbytes_ext(x: &BigInt, y: &BigInt) -> Vec<u8> {
+    if is_infinity(x, y) { vec![0u8; MUSIG2_PUBLIC_KEY_LEN] } else { cbytes(x, y) }
+}
+
+/// cpoint(x), `None` if the `x` is not the compressed encoding of the point on the curve
+fn cpoint(curve: &CurveParams, x: &[u8]) -> Option<(BigInt, BigInt)> {
+    if x.len() != MUSIG2_PUBLIC_KEY_LEN || (x[0] != 2 && x[0] != 3) {
+        return None;
+    }
+    let px = BigInt::from_be_bytes(&x[1..]);
+    curve.decompress_y(&px, x[0] == 3).map(|y| (px, y))
+}
+
+fn cpoint_ext(curve: &CurveParams, x: &[u8]) -> Option<(BigInt, BigInt)> {
+    if x.len() == MUSIG2_PUBLIC_KEY_LEN && x.iter().all(|&b| b == 0) { Some(infinity()) } else { cpoint(curve, x) }
+}
+
+/// the scalar in the range [0, n-1], `None` if the `x` isn't 32 bytes or isn't less than n
+fn scalar32(curve: &CurveParams, x: &[u8]) -> Option<BigInt> {
+    let k = BigInt::from_be_bytes(x);
+    if x.len() == 32 && &k < curve.base_point_order() { Some(k) } else { None }
+}
+
+fn hash_mod_n(curve: &CurveParams, tag: &str, data: &[&[u8]]) -> BigInt {
+    BigInt::from_be_bytes(tagged_hash(tag, data).as_slice()).rem_euclid(curve.base_point_order().clone())
+}
+
+/// g = 1 if the y-coordinate of the Q is even, otherwise n - 1
+fn parity_factor(curve: &CurveParams, qy: &BigInt) -> BigInt {
+    if has_even_y(qy) { BigInt::from(1u32) } else { curve.base_point_order().clone() - BigInt::from(1u32) }
+}
+
+/// BIP-327 `KeySort`, sort the 33 bytes public keys in the lexicographical order
+pub fn musig2_key_sort(public_keys: &mut [&[u8]]) {
+    public_keys.sort_unstable();
+}
+
+/// BIP-327 `NonceAgg`, the `public_nonces` are the 66 bytes public nonces of all signers. The `InvalidParameter`
+/// with the index of the signer is returned if any public nonce is invalid.
+pub fn musig2_nonce_agg(public_nonces: &[&[u8]]) -> Result<Vec<u8>, CryptoError> {
+    let curve = CurveParams::secp256k1()?;
+    let mut aggnonce = Vec::with_capacity(MUSIG2_PUBLIC_NONCE_LEN);
+    for j in 0..2 {
+        let mut r = infinity();
+        for (i, nonce) in public_nonces.iter().enumerate() {
+            let p = if nonce.len() == MUSIG2_PUBLIC_NONCE_LEN { cpoint(&curve, &nonce[(j * 33)..((j + 1) * 33)]) } else { None };
+            let p = match p {
+                Some(p) => p,
+                None => return param_err(format!("The public nonce of the signer {} is invalid", i)),
+            };
+            r = curve.add(&r.0, &r.1, &p.0, &p.1);
+        }
+        aggnonce.extend_from_slice(cbytes_ext(&r.0, &r.1).as_slice());
+    }
+    Ok(aggnonce)
+}
+
+/// The BIP-327 secret nonce `bytes(k1) || bytes(k2) || pk`, it's consumed by the `MuSig2Session::sign` and zeroed
+/// on drop, so the same nonce can't be used to sign twice.
+pub struct MuSig2SecretNonce {
+    bytes: [u8; SECRET_NONCE_LEN],
+}
+
+impl MuSig2SecretNonce {
+    /// the secret nonce of the BIP-327 test vectors
+    #[cfg(test)]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Self {
+        let mut nonce = Self { bytes: [0u8; SECRET_NONCE_LEN] };
+        nonce.bytes.copy_from_slice(bytes);
+        nonce
+    }
+
+    /// the 33 bytes public key of the signer which the nonce is generated for
+    pub fn public_key(&self) -> &[u8] {
+        &self.bytes[64..]
+    }
+}
+
+impl Drop for MuSig2SecretNonce {
+    fn drop(&mut self) {
+        self.bytes.iter_mut().for_each(|x| unsafe { std::ptr::write_volatile(x, 0) });
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// BIP-327 `NonceGen` with the 32 bytes random `rand`, return the secret nonce and the 66 bytes public nonce.
+///
+/// The `secret_key`(32 bytes), the `aggregate_public_key`(the 32 bytes x-only aggregate public key), the `message`
+/// and the `extra_in` are optional, they're mixed into the nonce as the defense-in-depth. The `rand` must be the
+/// fresh random data, the `musig2_nonce_gen` reads it from the random source.
+pub fn musig2_nonce_gen_with_rand(rand: &[u8], secret_key: Option<&[u8]>, public_key: &[u8], aggregate_public_key: Option<&[u8]>,
+    message: Option<&[u8]>, extra_in: Option<&[u8]>) -> Result<(MuSig2SecretNonce, Vec<u8>), CryptoError> {
+    let curve = CurveParams::secp256k1()?;
+    if rand.len() != 32 {
+        return param_err(format!("The random data must be 32 bytes, but got {} bytes", rand.len()));
+    } else if secret_key.map(|x| x.len() != 32).unwrap_or(false) {
+        return param_err("The secret key must be 32 bytes".to_string());
+    } else if public_key.len() != MUSIG2_PUBLIC_KEY_LEN {
+        return param_err(format!("The public key must be {} bytes, but got {} bytes", MUSIG2_PUBLIC_KEY_LEN, public_key.len()));
+    } else if aggregate_public_key.map(|x| x.len() != 32).unwrap_or(false) {
+        return param_err("The aggregate public key must be 32 bytes".to_string());
+    } else if extra_in.map(|x| x.len() > (u32::MAX as usize)).unwrap_or(false) {
+        return param_err("The extra input is longer than the 2^32-1 bytes".to_string());
+    }
+
+    // rand = rand' xor hash_{MuSig/aux}(sk)
+    let mut rand = rand.to_vec();
+    if let Some(sk) = secret_key {
+        rand.iter_mut().zip(tagged_hash("MuSig/aux", &[sk]).iter()).for_each(|(x, &y)| *x ^= y);
+    }
+
+    let aggpk = aggregate_public_key.unwrap_or(&[]);
+    let msg_prefixed = match message {
+        None => vec![0u8],
+        Some(m) => {
+            let mut buf = vec![1u8];
+            buf.extend_from_slice((m.len() as u64).to_be_bytes().as_ref());
+            buf.extend_from_slice(m);
+            buf
+        },
+    };
+    let extra_in = extra_in.unwrap_or(&[]);
+    let extra_len = (extra_in.len() as u32).to_be_bytes();
+
+    let mut nonce = MuSig2SecretNonce { bytes: [0u8; SECRET_NONCE_LEN] };
+    let mut public_nonce = Vec::with_capacity(MUSIG2_PUBLIC_NONCE_LEN);
+    for i in 0..2u8 {
+        let k = hash_mod_n(&curve, "MuSig/nonce", &[rand.as_slice(), &[public_key.len() as u8], public_key,
+            &[aggpk.len() as u8], aggpk, msg_prefixed.as_slice(), extra_len.as_ref(), extra_in, &[i]]);
+        if k.signnum() != Some(1) {
+            return Err(CryptoError::new(CryptoErrorKind::InnerErr, "The MuSig2 nonce is 0"));
+        }
+        let (rx, ry) = curve.scalar_base_point(k.as_ref());
+        let idx = (i as usize) << 5;
+        nonce.bytes[idx..(idx + 32)].copy_from_slice(to_bytes32(&k).as_slice());
+        public_nonce.extend_from_slice(cbytes(&rx, &ry).as_slice());
+    }
+    nonce.bytes[64..].copy_from_slice(public_key);
+
+    Ok((nonce, public_nonce))
+}
+
+/// BIP-327 `NonceGen`, the 32 bytes random data are read from the `rd`, see the `musig2_nonce_gen_with_rand`.
+pub fn musig2_nonce_gen<R: IterSource<u32>>(rd: &mut R, secret_key: Option<&[u8]>, public_key: &[u8], aggregate_public_key: Option<&[u8]>,
+    message: Option<&[u8]>, extra_in: Option<&[u8]>) -> Result<(MuSig2SecretNonce, Vec<u8>), CryptoError> {
+    let mut rand = Vec::with_capacity(36);
+    for e in rd.iter_mut() {
+        rand.extend_from_slice(e.to_be_bytes().as_ref());
+        if rand.len() >= 32 {
+            break;
+        }
+    }
+    if rand.len() < 32 {
+        return Err(CryptoError::new(CryptoErrorKind::RandError, "The random source is exhausted"));
+    }
+    rand.truncate(32);
+
+    musig2_nonce_gen_with_rand(rand.as_slice(), secret_key, public_key, aggregate_public_key, message, extra_in)
+}
+
+/// The BIP-327 `KeyAgg Context`, the aggregate public key `Q` of the signers' 33 bytes public keys, the accumulated
+/// sign `gacc` and the accumulated tweak `tacc`.
+///
+/// The order of the public keys matters, the `musig2_key_sort` sorts them if the signers don't agree on the order.
+/// The aggregate public key can be tweaked by the `apply_tweak`(e.g. BIP-32 or BIP-341 Taproot tweaks).
+#[derive(Clone)]
+pub struct MuSig2KeyAggContext {
+    curve: CurveParams,
+    public_keys: Vec<Vec<u8>>,
+    // hash_{KeyAgg list}(pk_1 || ... || pk_u)
+    list_hash: Vec<u8>,
+    // the first public key which isn't equal to the pk_1
+    second_key: Option<Vec<u8>>,
+    q: (BigInt, BigInt),
+    gacc: BigInt,
+    tacc: BigInt,
+}
+
+impl MuSig2KeyAggContext {
+    /// BIP-327 `KeyAgg`, the `InvalidPublicKey` with the index of the signer is returned if any public key is invalid.
+    pub fn new(public_keys: &[&[u8]]) -> Result<Self, CryptoError> {
+        let curve = CurveParams::secp256k1()?;
+        if public_keys.is_empty() {
+            return param_err("The public keys must not be empty".to_string());
+        }
+
+        let list_hash = tagged_hash("KeyAgg list", public_keys);
+        let second_key = public_keys.iter().skip(1).find(|&&x| x != public_keys[0]).map(|x| x.to_vec());
+        let mut ctx = Self {
+            curve,
+            public_keys: public_keys.iter().map(|x| x.to_vec()).collect(),
+            list_hash,
+            second_key,
+            q: infinity(),
+            gacc: BigInt::from(1u32),
+            tacc: BigInt::from(0u32),
+        };
+
+        let mut q = infinity();
+        for (i, pk) in public_keys.iter().enumerate() {
+            let p = cpoint(&ctx.curve, pk).ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidPublicKey,
+                format!("The public key of the signer {} is invalid", i)))?;
+            let a = ctx.coefficient(pk);
+            let (x, y) = ctx.curve.scalar(&p.0, &p.1, a.as_ref());
+            q = ctx.curve.add(&q.0, &q.1, &x, &y);
+        }
+        if is_infinity(&q.0, &q.1) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The aggregate public key is the infinite point"));
+        }
+        ctx.q = q;
+
+        Ok(ctx)
+    }
+
+    /// BIP-327 `KeyAggCoeff`, 1 for the second key, otherwise $int(hash_{KeyAgg coefficient}(L || pk)) \mod n$
+    fn coefficient(&self, public_key: &[u8]) -> BigInt {
+        if self.second_key.as_deref() == Some(public_key) {
+            BigInt::from(1u32)
+        } else {
+            hash_mod_n(&self.curve, "KeyAgg coefficient", &[self.list_hash.as_slice(), public_key])
+        }
+    }
+
+    /// BIP-327 `ApplyTweak`, the `tweak` is the 32 bytes scalar which is less than n. The x-only tweak(`is_xonly`)
+    /// is added to the point with the even y-coordinate of the `Q`, the plain tweak is added to the `Q` itself.
+    pub fn apply_tweak(&mut self, tweak: &[u8], is_xonly: bool) -> Result<(), CryptoError> {
+        let c = &self.curve;
+        let n = c.base_point_order();
+        let t = scalar32(c, tweak).ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidParameter,
+            "The tweak must be the 32 bytes scalar which is less than n"))?;
+        let g = if is_xonly { parity_factor(c, &self.q.1) } else { BigInt::from(1u32) };
+
+        // Q' = gQ + tG
+        let (x1, y1) = c.scalar(&self.q.0, &self.q.1, g.as_ref());
+        let (x2, y2) = c.scalar_base_point(t.as_ref());
+        let q = c.add(&x1, &y1, &x2, &y2);
+        if is_infinity(&q.0, &q.1) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The tweaked public key is the infinite point"));
+        }
+
+        self.q = q;
+        self.gacc = (g.clone() * self.gacc.clone()).rem_euclid(n.clone());
+        self.tacc = (t + g * self.tacc.clone()).rem_euclid(n.clone());
+        Ok(())
+    }
+
+    /// `GetXonlyPk`, the 32 bytes x-only aggregate public key, the BIP-340 public key of the aggregate signature
+    pub fn x_only_public_key(&self) -> Vec<u8> {
+        to_bytes32(&self.q.0)
+    }
+
+    /// `GetPlainPk`, the 33 bytes compressed aggregate public key
+    pub fn plain_public_key(&self) -> Vec<u8> {
+        cbytes(&self.q.0, &self.q.1)
+    }
+
+    /// the public keys in the aggregation order
+    pub fn public_keys(&self) -> &[Vec<u8>] {
+        self.public_keys.as_slice()
+    }
+}
+
+/// The BIP-327 `Session Context` of signing the `message` with the aggregate nonce, it's the second round of the MuSig2.
+///
+/// 1. every signer generates the nonce by the `musig2_nonce_gen` and sends the public nonce to the others;
+/// 2. the public nonces are aggregated by the `musig2_nonce_agg`, then every signer creates the session and signs by
+///    the `sign` with its secret nonce, and sends the partial signature;
+/// 3. the partial signatures are checked by the `partial_sig_verify` and aggregated by the `partial_sig_agg` to the
+///    BIP-340 signature of the x-only aggregate public key.
+pub struct MuSig2Session {
+    key_agg: MuSig2KeyAggContext,
+    b: BigInt,
+    r: (BigInt, BigInt),
+    e: BigInt,
+}
+
+impl MuSig2Session {
+    /// BIP-327 `GetSessionValues`, the `aggregate_nonce` is the output of the `musig2_nonce_agg`, the `key_agg` has
+    /// applied all tweaks.
+    pub fn new(key_agg: &MuSig2KeyAggContext, aggregate_nonce: &[u8], message: &[u8]) -> Result<Self, CryptoError> {
+        let c = &key_agg.curve;
+        let (r1, r2) = if aggregate_nonce.len() == MUSIG2_PUBLIC_NONCE_LEN {
+            (cpoint_ext(c, &aggregate_nonce[..33]), cpoint_ext(c, &aggregate_nonce[33..]))
+        } else {
+            (None, None)
+        };
+        let (r1, r2) = match (r1, r2) {
+            (Some(r1), Some(r2)) => (r1, r2),
+            _ => return param_err("The aggregate nonce is invalid".to_string()),
+        };
+
+        // b = int(hash_{MuSig/noncecoef}(aggnonce || xbytes(Q) || m)) mod n, R = R1 + bR2, R = G if R is infinite
+        let qx = key_agg.x_only_public_key();
+        let b = hash_mod_n(c, "MuSig/noncecoef", &[aggregate_nonce, qx.as_slice(), message]);
+        let (x, y) = c.scalar(&r2.0, &r2.1, b.as_ref());
+        let r = c.add(&r1.0, &r1.1, &x, &y);
+        let r = if is_infinity(&r.0, &r.1) {
+            let (gx, gy) = c.base_point();
+            (gx.deep_clone(), gy.deep_clone())
+        } else {
+            r
+        };
+        let e = challenge(c, to_bytes32(&r.0).as_slice(), qx.as_slice(), message);
+
+        Ok(Self { key_agg: key_agg.clone(), b, r, e })
+    }
+
+    /// the coefficient `a` of the signer's public key, `None` if the signer isn't in the session
+    fn signer_coefficient(&self, public_key: &[u8]) -> Option<BigInt> {
+        if self.key_agg.public_keys.iter().any(|x| x.as_slice() == public_key) {
+            Some(self.key_agg.coefficient(public_key))
+        } else {
+            None
+        }
+    }
+
+    /// BIP-327 `Sign` with the 32 bytes `secret_key`, the `secret_nonce` is consumed. The partial signature is verified
+    /// before it's returned, against the fault injection.
+    pub fn sign(&self, secret_nonce: MuSig2SecretNonce, secret_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let ctx = &self.key_agg;
+        let (c, n) = (&ctx.curve, ctx.curve.base_point_order());
+        let (k1, k2) = match (scalar32(c, &secret_nonce.bytes[..32]), scalar32(c, &secret_nonce.bytes[32..64])) {
+            (Some(k1), Some(k2)) if k1.signnum() == Some(1) && k2.signnum() == Some(1) => (k1, k2),
+            _ => return param_err("The secret nonce is invalid".to_string()),
+        };
+        let d = match scalar32(c, secret_key) {
+            Some(d) if d.signnum() == Some(1) => d,
+            _ => return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The secret key must be in the range [1, n-1]")),
+        };
+
+        let (px, py) = c.scalar_base_point(d.as_ref());
+        let pk = cbytes(&px, &py);
+        if pk.as_slice() != secret_nonce.public_key() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The secret key doesn't match the public key of the secret nonce"));
+        }
+        let a = self.signer_coefficient(pk.as_slice()).ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidPrivateKey,
+            "The signer isn't in the key aggregation"))?;
+
+        // d = g * gacc * d' mod n, k_i = n - k_i' if the y of the R is odd
+        let mut d = parity_factor(c, &ctx.q.1) * ctx.gacc.clone() * d;
+        d.rem_euclid_assign(n.clone());
+        let (k1, k2) = if has_even_y(&self.r.1) { (k1, k2) } else { (n.clone() - k1, n.clone() - k2) };
+        let mut s = k1 + self.b.clone() * k2 + self.e.clone() * a * d;
+        s.rem_euclid_assign(n.clone());
+        let psig = to_bytes32(&s);
+
+        let mut public_nonce = Vec::with_capacity(MUSIG2_PUBLIC_NONCE_LEN);
+        for i in 0..2 {
+            let k = BigInt::from_be_bytes(&secret_nonce.bytes[(i << 5)..((i + 1) << 5)]);
+            let (x, y) = c.scalar_base_point(k.as_ref());
+            public_nonce.extend_from_slice(cbytes(&x, &y).as_slice());
+        }
+        self.partial_sig_verify(psig.as_slice(), public_nonce.as_slice(), pk.as_slice())
+            .map_err(|_| CryptoError::new(CryptoErrorKind::InnerErr, "The partial signature isn't verified"))?;
+        Ok(psig)
+    }
+
+    /// BIP-327 `PartialSigVerifyInternal`, verify the partial signature of the signer with the 66 bytes
+    /// `public_nonce` and the 33 bytes `public_key`: $sG = R_{s1} + bR_{s2} + e a g' P$.
+    pub fn partial_sig_verify(&self, partial_signature: &[u8], public_nonce: &[u8], public_key: &[u8]) -> Result<(), CryptoError> {
+        let ctx = &self.key_agg;
+        let (c, n) = (&ctx.curve, ctx.curve.base_point_order());
+        let s = scalar32(c, partial_signature).ok_or_else(|| CryptoError::new(CryptoErrorKind::VerificationFailed,
+            "The partial signature isn't less than n"))?;
+        let (r1, r2) = if public_nonce.len() == MUSIG2_PUBLIC_NONCE_LEN {
+            (cpoint(c, &public_nonce[..33]), cpoint(c, &public_nonce[33..]))
+        } else {
+            (None, None)
+        };
+        let (r1, r2) = match (r1, r2) {
+            (Some(r1), Some(r2)) => (r1, r2),
+            _ => return param_err("The public nonce is invalid".to_string()),
+        };
+        let p = cpoint(c, public_key).ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The public key is invalid"))?;
+        let a = self.signer_coefficient(public_key).ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidPublicKey,
+            "The signer isn't in the key aggregation"))?;
+
+        // Re = R_{s1} + bR_{s2}, negated if the y of the R is odd
+        let (x, y) = c.scalar(&r2.0, &r2.1, self.b.as_ref());
+        let re = c.add(&r1.0, &r1.1, &x, &y);
+        let re = if has_even_y(&self.r.1) { re } else { neg(c, &re.0, &re.1) };
+
+        let mut eag = parity_factor(c, &ctx.q.1) * ctx.gacc.clone() * self.e.clone() * a;
+        eag.rem_euclid_assign(n.clone());
+        let (x, y) = c.scalar(&p.0, &p.1, eag.as_ref());
+        let rhs = c.add(&re.0, &re.1, &x, &y);
+        if c.scalar_base_point(s.as_ref()) == rhs {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The partial signature isn't valid"))
+        }
+    }
+
+    /// BIP-327 `PartialSigAgg`, aggregate the partial signatures of all signers to the BIP-340 signature, the
+    /// `VerificationFailed` with the index of the signer is returned if any partial signature isn't less than n.
+    pub fn partial_sig_agg(&self, partial_signatures: &[&[u8]]) -> Result<SchnorrSignature, CryptoError> {
+        let ctx = &self.key_agg;
+        let (c, n) = (&ctx.curve, ctx.curve.base_point_order());
+        let mut s = BigInt::from(0u32);
+        for (i, psig) in partial_signatures.iter().enumerate() {
+            s += scalar32(c, psig).ok_or_else(|| CryptoError::new(CryptoErrorKind::VerificationFailed,
+                format!("The partial signature of the signer {} isn't less than n", i)))?;
+        }
+
+        // s = s_1 + ... + s_u + e * g * tacc mod n
+        s += self.e.clone() * parity_factor(c, &ctx.q.1) * ctx.tacc.clone();
+        s.rem_euclid_assign(n.clone());
+
+        let mut sig = to_bytes32(&self.r.0);
+        sig.extend_from_slice(to_bytes32(&s).as_slice());
+        SchnorrSignature::from_bytes(sig.as_slice())
+    }
+}
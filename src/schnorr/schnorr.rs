@@ -0,0 +1,306 @@
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::elliptic::{CurveParams, EllipticCurve, KeyPair, PublicKey};
+use crate::numeric::{ct_mod_reduce, SecretScalar};
+use crate::sha::SHA256;
+use crate::{Digest, Signature, CryptoError, CryptoErrorKind};
+
+/// the length(in bytes) of the x-only public key
+pub const SCHNORR_PUBLIC_KEY_LEN: usize = 32;
+
+/// the length(in bytes) of the signature `bytes(R) || bytes(s)`
+pub const SCHNORR_SIGNATURE_LEN: usize = 64;
+
+/// $hash_{tag}(x) = SHA256(SHA256(tag) || SHA256(tag) || x)$(BIP-340 "Design", tagged hashes), the `x` is the
+/// concatenation of the `data`.
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> Vec<u8> {
+    let (mut sha, mut tag_hash, mut h) = (SHA256::new(), Vec::with_capacity(32), Vec::with_capacity(32));
+    sha.write(tag.as_bytes());
+    sha.checksum(&mut tag_hash);
+
+    sha.reset();
+    sha.write(tag_hash.as_slice());
+    sha.write(tag_hash.as_slice());
+    data.iter().for_each(|x| sha.write(x));
+    sha.checksum(&mut h);
+    h
+}
+
+/// the 32 bytes big-endian encoding
+pub(super) fn to_bytes32(x: &BigInt) -> Vec<u8> {
+    let b = x.to_be_bytes();
+    let b = if b.len() > 32 { &b[(b.len() - 32)..] } else { b.as_slice() };
+    let mut buf = vec![0u8; 32 - b.len()];
+    buf.extend_from_slice(b);
+    buf
+}
+
+pub(super) fn has_even_y(y: &BigInt) -> bool {
+    y.to_be_bytes().last().copied().unwrap_or(0) & 1 == 0
+}
+
+pub(super) fn is_infinity(x: &BigInt, y: &BigInt) -> bool {
+    x.signnum() != Some(1) && y.signnum() != Some(1)
+}
+
+/// the point with the x-coordinate `x` and the even y-coordinate(BIP-340 `lift_x`)
+fn lift_x(curve: &CurveParams, x: &[u8]) -> Option<(BigInt, BigInt)> {
+    let x = BigInt::from_be_bytes(x);
+    curve.decompress_y(&x, false).map(|y| (x, y))
+}
+
+/// $e = int(hash_{BIP0340/challenge}(bytes(R) || bytes(P) || m)) \mod n$
+pub(super) fn challenge(curve: &CurveParams, r: &[u8], px: &[u8], message: &[u8]) -> BigInt {
+    let e = BigInt::from_be_bytes(tagged_hash("BIP0340/challenge", &[r, px, message]).as_slice());
+    e.rem_euclid(curve.base_point_order().clone())
+}
+
+/// The BIP-340 signature `bytes(R) || bytes(s)`, the `R` is the x-coordinate of the nonce point, the `s` is the
+/// 32 bytes big-endian scalar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchnorrSignature {
+    bytes: [u8; SCHNORR_SIGNATURE_LEN],
+}
+
+impl SchnorrSignature {
+    /// the `bytes` must be 64 bytes, the ranges of the `R` and `s` are checked by the verification
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.len() != SCHNORR_SIGNATURE_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The Schnorr signature must be {} bytes, but got {} bytes", SCHNORR_SIGNATURE_LEN, bytes.len())));
+        }
+
+        let mut sig = Self::default();
+        sig.bytes.copy_from_slice(bytes);
+        Ok(sig)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    /// the x-coordinate of the nonce point `R`
+    pub fn r(&self) -> &[u8] {
+        &self.bytes[..32]
+    }
+
+    pub fn s(&self) -> &[u8] {
+        &self.bytes[32..]
+    }
+}
+
+impl Default for SchnorrSignature {
+    /// the all-zero signature, it's the placeholder which is overwritten by the `sign`
+    fn default() -> Self {
+        Self { bytes: [0u8; SCHNORR_SIGNATURE_LEN] }
+    }
+}
+
+/// BIP-340 Schnorr signature over the secp256k1
+///
+/// the public key is the 32 bytes x-coordinate of the point `P`, the point with the even y-coordinate is implied.
+/// The message is signed directly(not hashed by the caller) and can be any length. The nonce is derived from the
+/// private key, the public key, the message and the 32 bytes auxiliary random data which is read from the `rd`
+/// (BIP-340 "Default Signing"), the `sign_with_aux` is the deterministic signing with the given auxiliary data.
+pub struct Schnorr<R> {
+    curve: CurveParams,
+    rd: R,
+    kp: KeyPair,
+    // bytes(P)
+    px: Vec<u8>,
+    // the private key d of the point dG with the even y-coordinate
+    d: Option<SecretScalar>,
+}
+
+impl<R> Schnorr<R>
+    where R: IterSource<u32> {
+    /// the public key is validated by the `dh::validate_ec_public_key` on the secp256k1, and the private key must be
+    /// in the range [1, n-1]. If the y-coordinate of the public key is odd, the private key is negated, so the `d`
+    /// and the `n - d` sign as the same x-only public key.
+    pub fn new(rd: R, key_pair: KeyPair) -> Result<Self, CryptoError> {
+        let curve = CurveParams::secp256k1()?;
+        crate::dh::validate_ec_public_key(&curve, key_pair.public_key())?;
+        let (n, pk) = (curve.base_point_order(), key_pair.public_key());
+
+        let d = match key_pair.private_key() {
+            Some(k) => {
+                let d = k.d.get();
+                if d.signnum() != Some(1) || &d >= n {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The Schnorr private key must be in the range [1, n-1]"));
+                }
+                let (qx, qy) = curve.scalar_base_point(d.as_ref());
+                if qx != pk.qx || qy != pk.qy {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The private key doesn't match the public key"));
+                }
+                Some(SecretScalar::new(&if has_even_y(&qy) { d } else { n.clone() - d }))
+            },
+            None => None,
+        };
+
+        Ok(
+            Self {
+                px: to_bytes32(&pk.qx),
+                curve,
+                rd,
+                kp: key_pair,
+                d,
+            }
+        )
+    }
+
+    /// the 32 bytes x-only `public_key`, only the verification is available
+    pub fn from_x_only_public_key(rd: R, public_key: &[u8]) -> Result<Self, CryptoError> {
+        let curve = CurveParams::secp256k1()?;
+        let (qx, qy) = if public_key.len() == SCHNORR_PUBLIC_KEY_LEN { lift_x(&curve, public_key) } else { None }
+            .ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The x-only public key is not on the secp256k1"))?;
+        Self::new(rd, KeyPair::from(PublicKey { qx, qy }))
+    }
+
+    pub fn auto_generate_key(mut rd: R) -> Result<Self, CryptoError> {
+        let pk = CurveParams::secp256k1()?.generate_key(&mut rd)?;
+        Self::new(rd, KeyPair::from(pk))
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        self.kp.public_key()
+    }
+
+    /// bytes(P), the 32 bytes x-coordinate of the public key
+    pub fn x_only_public_key(&self) -> &[u8] {
+        self.px.as_slice()
+    }
+
+    /// BIP-340 "Default Signing" with the 32 bytes auxiliary random data `aux`, the signature is deterministic for the
+    /// same `aux`. The signature is verified before it's returned, against the fault injection.
+    pub fn sign_with_aux(&self, message: &[u8], aux: &[u8]) -> Result<SchnorrSignature, CryptoError> {
+        let d = self.d.as_ref().ok_or(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "Public key cannot used to sign"))?.get();
+        if aux.len() != 32 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The auxiliary random data must be 32 bytes, but got {} bytes", aux.len())));
+        }
+
+        // t = bytes(d) xor hash_{BIP0340/aux}(a), k' = int(hash_{BIP0340/nonce}(t || bytes(P) || m)) mod n
+        let n = self.curve.base_point_order();
+        let mut t = to_bytes32(&d);
+        t.iter_mut().zip(tagged_hash("BIP0340/aux", &[aux]).iter()).for_each(|(x, &y)| *x ^= y);
+        let rand = tagged_hash("BIP0340/nonce", &[t.as_slice(), self.px.as_slice(), message]);
+        let k = ct_mod_reduce(&BigInt::from_be_bytes(rand.as_slice()), 256, n)?;
+        if k.signnum() != Some(1) {
+            return Err(CryptoError::new(CryptoErrorKind::InnerErr, "The Schnorr nonce is 0"));
+        }
+
+        // R = k'G, k = k' if the y of the R is even, otherwise n - k'
+        let (rx, ry) = self.curve.scalar_base_point(k.as_ref());
+        let k = if has_even_y(&ry) { k } else { n.clone() - k };
+        let rx = to_bytes32(&rx);
+        let e = challenge(&self.curve, rx.as_slice(), self.px.as_slice(), message);
+        let mut s = e * d.clone() + k;
+        s.rem_euclid_assign(n.clone());
+
+        let mut sig = SchnorrSignature::default();
+        sig.bytes[..32].copy_from_slice(rx.as_slice());
+        sig.bytes[32..].copy_from_slice(to_bytes32(&s).as_slice());
+        self.verify_inner(message, &sig)
+            .map_err(|_| CryptoError::new(CryptoErrorKind::InnerErr, "The Schnorr signature isn't verified by the public key"))?;
+        Ok(sig)
+    }
+
+    /// BIP-340 "Verification": R = sG - eP, the R must not be the infinite point, and the y-coordinate of the R must be
+    /// even, and the x-coordinate of the R must be equal to the `r` of the signature.
+    fn verify_inner(&self, message: &[u8], sig: &SchnorrSignature) -> Result<(), CryptoError> {
+        let (c, pk) = (&self.curve, self.kp.public_key());
+        let n = c.base_point_order();
+        let (r, s) = (BigInt::from_be_bytes(sig.r()), BigInt::from_be_bytes(sig.s()));
+        if &r >= c.field_order() || &s >= n {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The signature r isn't less than p or the s isn't less than n"));
+        }
+
+        // -eP = (n - e)P, the x-only public key implies the even y-coordinate
+        let e = n.clone() - challenge(c, sig.r(), self.px.as_slice(), message);
+        let py = if has_even_y(&pk.qy) { pk.qy.clone() } else { c.field_order().clone() - pk.qy.clone() };
+        let (x1, y1) = c.scalar_base_point(s.as_ref());
+        let (x2, y2) = c.scalar(&pk.qx, &py, e.as_ref());
+        let (x, y) = c.add(&x1, &y1, &x2, &y2);
+        if is_infinity(&x, &y) || !has_even_y(&y) || x != r {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, ""))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<R> Signature<SchnorrSignature> for Schnorr<R>
+    where R: IterSource<u32> {
+    type Output = ();
+
+    fn sign(&mut self, signature: &mut SchnorrSignature, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        let mut aux = Vec::with_capacity(36);
+        for e in self.rd.iter_mut() {
+            aux.extend_from_slice(e.to_be_bytes().as_ref());
+            if aux.len() >= 32 {
+                break;
+            }
+        }
+        aux.truncate(32);
+
+        *signature = self.sign_with_aux(message, aux.as_slice())?;
+        Ok(())
+    }
+
+    fn verify(&mut self, signature: &SchnorrSignature, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.verify_inner(message, signature)
+    }
+}
+
+/// BIP-340 "Batch Verification" of the `(x-only public key, message, signature)` items, the random coefficients
+/// $a_2, \cdots, a_u$ in the range [1, n-1] are read from the `rd`.
+///
+/// It checks $(s_1 + a_2 s_2 + \cdots + a_u s_u)G = R_1 + a_2 R_2 + \cdots + a_u R_u + e_1 P_1 + (a_2 e_2)P_2 +
+/// \cdots + (a_u e_u)P_u$ at once, the `VerificationFailed` is returned if any signature is invalid, but which one isn't
+/// reported(the items should be verified one by one to find it). The empty batch is valid.
+pub fn schnorr_batch_verify<R: IterSource<u32>>(items: &[(&[u8], &[u8], &SchnorrSignature)], rd: &mut R) -> Result<(), CryptoError> {
+    let c = CurveParams::secp256k1()?;
+    let (n, nm1) = (c.base_point_order(), c.base_point_order().clone() - BigInt::from(1u32));
+    let (mut sum_s, mut rhs) = (BigInt::from(0u32), (BigInt::from(0u32), BigInt::from(0u32)));
+
+    for (i, &(public_key, message, sig)) in items.iter().enumerate() {
+        let p = if public_key.len() == SCHNORR_PUBLIC_KEY_LEN { lift_x(&c, public_key) } else { None }
+            .ok_or_else(|| CryptoError::new(CryptoErrorKind::VerificationFailed,
+                format!("The public key of the item {} is not on the secp256k1", i)))?;
+        let (r, s) = (BigInt::from_be_bytes(sig.r()), BigInt::from_be_bytes(sig.s()));
+        let rp = if &r < c.field_order() && &s < n { lift_x(&c, sig.r()) } else { None }
+            .ok_or_else(|| CryptoError::new(CryptoErrorKind::VerificationFailed,
+                format!("The signature of the item {} is out of the range", i)))?;
+        let e = challenge(&c, sig.r(), public_key, message);
+
+        // a_1 = 1
+        let a = if i == 0 {
+            BigInt::from(1u32)
+        } else {
+            let mut buf = Vec::with_capacity(40);
+            for w in rd.iter_mut() {
+                buf.extend_from_slice(w.to_be_bytes().as_ref());
+                if buf.len() >= 40 {
+                    break;
+                }
+            }
+            BigInt::from_be_bytes(buf.as_slice()).rem_euclid(nm1.clone()) + BigInt::from(1u32)
+        };
+
+        sum_s += a.clone() * s;
+        sum_s.rem_euclid_assign(n.clone());
+        let mut ae = a.clone() * e;
+        ae.rem_euclid_assign(n.clone());
+        let (x1, y1) = c.scalar(&rp.0, &rp.1, a.as_ref());
+        let (x2, y2) = c.scalar(&p.0, &p.1, ae.as_ref());
+        let (x, y) = c.add(&x1, &y1, &x2, &y2);
+        rhs = c.add(&rhs.0, &rhs.1, &x, &y);
+    }
+
+    let lhs = c.scalar_base_point(sum_s.as_ref());
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The batch contains the invalid signature"))
+    }
+}
@@ -0,0 +1,271 @@
+use std::str::FromStr;
+use rmath::bigint::BigInt;
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::elliptic::{CurveParams, EllipticCurve, KeyPair, PrivateKey, PublicKey};
+use crate::schnorr::{Schnorr, SchnorrSignature, tagged_hash, schnorr_batch_verify, MuSig2KeyAggContext, MuSig2SecretNonce,
+    MuSig2Session, musig2_key_sort, musig2_nonce_gen, musig2_nonce_gen_with_rand, musig2_nonce_agg};
+use crate::{Signature, CryptoErrorKind};
+use crate::test_util::hex;
+
+fn key_pair(d: &str) -> KeyPair {
+    let d = BigInt::from_str(format!("0x{}", d).as_str()).unwrap();
+    let (qx, qy) = CurveParams::secp256k1().unwrap().scalar_base_point(d.as_ref());
+    KeyPair::from(PrivateKey::new_uncheck(PublicKey::new_uncheck(&qx, &qy), &d))
+}
+
+fn rand() -> CryptoRand<u32> {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    CryptoRand::new(&seed).unwrap()
+}
+
+#[test]
+fn schnorr_bip340_vectors() {
+    // BIP-340 test-vectors.csv, index 0, 1 and 15(the empty message), the last case of the 100 bytes message is
+    // computed by the Python reference code of the BIP-340
+    let cases = [
+        ("0000000000000000000000000000000000000000000000000000000000000003",
+         "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+         "0000000000000000000000000000000000000000000000000000000000000000",
+         "0000000000000000000000000000000000000000000000000000000000000000",
+         "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0"),
+        ("B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+         "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+         "0000000000000000000000000000000000000000000000000000000000000001",
+         "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+         "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A"),
+        ("0340034003400340034003400340034003400340034003400340034003400340",
+         "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+         "0000000000000000000000000000000000000000000000000000000000000000",
+         "",
+         "71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63"),
+        ("0340034003400340034003400340034003400340034003400340034003400340",
+         "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+         "9999999999999999999999999999999999999999999999999999999999999999",
+         "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F202122232425262728292A2B2C2D2E2F303132333435363738393A3B3C3D3E3F404142434445464748494A4B4C4D4E4F505152535455565758595A5B5C5D5E5F60616263",
+         "983D9C65AE5AAD53EDA9F342B5F39BF0CD54F395B1424FD5D1C2CCD0080CCD3FB538B26399C9DF1171185E68990AA9890E64BFCED7AE70BC8F924F9CD250DF54"),
+    ];
+
+    for (i, &(d, px, aux, msg, sig)) in cases.iter().enumerate() {
+        let (px, aux, msg, sig) = (hex(px), hex(aux), hex(msg), hex(sig));
+        let schnorr = Schnorr::new(rand(), key_pair(d)).unwrap();
+        assert_eq!(schnorr.x_only_public_key(), px.as_slice(), "case: {}", i);
+        let s = schnorr.sign_with_aux(msg.as_slice(), aux.as_slice()).unwrap();
+        assert_eq!(s.as_bytes(), sig.as_slice(), "case: {}", i);
+
+        let mut verifier = Schnorr::from_x_only_public_key(rand(), px.as_slice()).unwrap();
+        assert!(verifier.verify(&SchnorrSignature::from_bytes(sig.as_slice()).unwrap(), msg.as_slice()).is_ok(), "case: {}", i);
+    }
+
+    // BIP-340 test-vectors.csv, the failed verification of the index 6 to 13 and the index 14(the public key isn't
+    // less than p)
+    let (px, msg) = (hex("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+        hex("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89"));
+    let cases = [
+        // the R has the odd y
+        "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2",
+        // the negated message
+        "1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD",
+        // the negated s
+        "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6",
+        // the sG - eP is the infinity, the r is 0 and 1
+        "0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051",
+        "00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197",
+        // the r isn't the x coordinate of a point on the curve
+        "4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        // the r is p, the s is n
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+    ];
+    let mut verifier = Schnorr::from_x_only_public_key(rand(), px.as_slice()).unwrap();
+    for (i, sig) in cases.iter().enumerate() {
+        let sig = SchnorrSignature::from_bytes(hex(sig).as_slice()).unwrap();
+        assert_eq!(verifier.verify(&sig, msg.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed, "case: {}", i + 6);
+        assert!(schnorr_batch_verify(&[(px.as_slice(), msg.as_slice(), &sig)], &mut rand()).is_err(), "case: {}", i + 6);
+    }
+    let px = hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30");
+    assert_eq!(Schnorr::from_x_only_public_key(rand(), px.as_slice()).err().unwrap().kind(), CryptoErrorKind::InvalidPublicKey);
+
+    // the private key n - d signs as the same x-only public key
+    let n_minus_3 = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD036413E";
+    let (a, b) = (Schnorr::new(rand(), key_pair("03")).unwrap(), Schnorr::new(rand(), key_pair(n_minus_3)).unwrap());
+    assert_eq!(a.x_only_public_key(), b.x_only_public_key());
+    assert_eq!(a.sign_with_aux(b"msg", &[0u8; 32]).unwrap(), b.sign_with_aux(b"msg", &[0u8; 32]).unwrap());
+
+    // hash_{BIP0340/challenge}, the tag hash is prefixed twice
+    assert_eq!(tagged_hash("BIP0340/aux", &[b"ab", b"c"]), tagged_hash("BIP0340/aux", &[b"abc"]));
+    assert_ne!(tagged_hash("BIP0340/aux", &[b"abc"]), tagged_hash("BIP0340/nonce", &[b"abc"]));
+}
+
+#[test]
+fn schnorr_sign_verify() {
+    let mut schnorr = Schnorr::auto_generate_key(rand()).unwrap();
+    let mut sig = SchnorrSignature::default();
+    schnorr.sign(&mut sig, b"schnorr message").unwrap();
+    assert!(schnorr.verify(&sig, b"schnorr message").is_ok());
+    assert_eq!(schnorr.verify(&sig, b"schnorr messagf").unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+
+    let mut verifier = Schnorr::from_x_only_public_key(rand(), schnorr.x_only_public_key()).unwrap();
+    assert!(verifier.verify(&sig, b"schnorr message").is_ok());
+    assert!(verifier.sign(&mut SchnorrSignature::default(), b"schnorr message").is_err());
+
+    // flipped bits, the r not less than p and the s not less than n
+    let mut bytes = sig.as_bytes().to_vec();
+    bytes[63] ^= 1;
+    assert!(verifier.verify(&SchnorrSignature::from_bytes(bytes.as_slice()).unwrap(), b"schnorr message").is_err());
+    let mut bytes = sig.as_bytes().to_vec();
+    bytes[..32].iter_mut().for_each(|x| *x = 0xff);
+    assert!(verifier.verify(&SchnorrSignature::from_bytes(bytes.as_slice()).unwrap(), b"schnorr message").is_err());
+    let mut bytes = sig.as_bytes().to_vec();
+    bytes[32..].copy_from_slice(hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141").as_slice());
+    assert!(verifier.verify(&SchnorrSignature::from_bytes(bytes.as_slice()).unwrap(), b"schnorr message").is_err());
+
+    // BIP-340 test-vectors.csv index 5: the public key is not on the curve
+    let px = hex("EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34");
+    assert_eq!(Schnorr::from_x_only_public_key(rand(), px.as_slice()).err().unwrap().kind(), CryptoErrorKind::InvalidPublicKey);
+    assert!(Schnorr::from_x_only_public_key(rand(), &px[1..]).is_err());
+    assert!(SchnorrSignature::from_bytes(&[0u8; 63]).is_err());
+    assert!(schnorr.sign_with_aux(b"msg", &[0u8; 31]).is_err());
+
+    // the private key doesn't match the public key
+    let (kp, other) = (key_pair("03"), key_pair("05"));
+    let mismatch = KeyPair::from(PrivateKey::new_uncheck(other.public_key().clone(), &kp.private_key().unwrap().d.get()));
+    assert_eq!(Schnorr::new(rand(), mismatch).err().unwrap().kind(), CryptoErrorKind::InvalidPrivateKey);
+}
+
+#[test]
+fn schnorr_batch_verification() {
+    let mut rd = rand();
+    let signers = (0..4).map(|_| Schnorr::auto_generate_key(rand()).unwrap()).collect::<Vec<_>>();
+    let msgs = (0..8).map(|i| vec![i as u8; i * 13]).collect::<Vec<_>>();
+    let sigs = msgs.iter().enumerate().map(|(i, m)| signers[i % 4].sign_with_aux(m, &[i as u8; 32]).unwrap()).collect::<Vec<_>>();
+    let items = msgs.iter().zip(sigs.iter()).enumerate()
+        .map(|(i, (m, s))| (signers[i % 4].x_only_public_key(), m.as_slice(), s)).collect::<Vec<_>>();
+
+    assert!(schnorr_batch_verify(items.as_slice(), &mut rd).is_ok());
+    assert!(schnorr_batch_verify(&items[..1], &mut rd).is_ok());
+    assert!(schnorr_batch_verify(&[], &mut rd).is_ok());
+
+    // the signature of the other message, the swapped public keys
+    let mut bad = items.clone();
+    bad[5].1 = msgs[4].as_slice();
+    assert_eq!(schnorr_batch_verify(bad.as_slice(), &mut rd).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    let mut bad = items.clone();
+    bad[2].0 = signers[3].x_only_public_key();
+    assert!(schnorr_batch_verify(bad.as_slice(), &mut rd).is_err());
+    let mut bad = items.clone();
+    bad[7].0 = &[0u8; 31];
+    assert!(schnorr_batch_verify(bad.as_slice(), &mut rd).is_err());
+}
+
+#[test]
+fn musig2_bip327_vectors() {
+    // BIP-327 key_agg_vectors.json, the valid cases
+    let pks = ["02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"].iter().map(|x| hex(x)).collect::<Vec<_>>();
+    let cases = [
+        (vec![0, 1, 2], "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C"),
+        (vec![2, 1, 0], "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B"),
+        (vec![0, 0, 0], "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935"),
+        (vec![0, 0, 1, 1], "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E"),
+    ];
+    for (i, (idx, q)) in cases.iter().enumerate() {
+        let keys = idx.iter().map(|&j| pks[j].as_slice()).collect::<Vec<_>>();
+        assert_eq!(MuSig2KeyAggContext::new(keys.as_slice()).unwrap().x_only_public_key(), hex(q), "case: {}", i);
+    }
+    let mut keys = vec![pks[1].as_slice(), pks[2].as_slice(), pks[0].as_slice()];
+    musig2_key_sort(keys.as_mut_slice());
+    assert_eq!(keys, vec![pks[2].as_slice(), pks[0].as_slice(), pks[1].as_slice()]);
+    let bad = hex("020000000000000000000000000000000000000000000000000000000000000005");
+    assert_eq!(MuSig2KeyAggContext::new(&[pks[0].as_slice(), bad.as_slice()]).err().unwrap().kind(), CryptoErrorKind::InvalidPublicKey);
+
+    // BIP-327 nonce_agg_vectors.json and sign_verify_vectors.json, the valid cases 0 and 1
+    let pnonces = ["0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046"]
+        .iter().map(|x| hex(x)).collect::<Vec<_>>();
+    let aggnonce = musig2_nonce_agg(pnonces.iter().map(|x| x.as_slice()).collect::<Vec<_>>().as_slice()).unwrap();
+    assert_eq!(aggnonce, hex("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9"));
+    let mut bad = pnonces[1].clone();
+    bad[0] = 4;
+    assert_eq!(musig2_nonce_agg(&[pnonces[0].as_slice(), bad.as_slice()]).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+
+    let sk = hex("7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671");
+    let secnonce = hex("508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F703935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9");
+    let msg = hex("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+    let pks = ["03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661"].iter().map(|x| hex(x)).collect::<Vec<_>>();
+    let cases = [
+        (vec![0, 1, 2], "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB"),
+        (vec![1, 0, 2], "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52"),
+    ];
+    for (i, (idx, psig)) in cases.iter().enumerate() {
+        let keys = idx.iter().map(|&j| pks[j].as_slice()).collect::<Vec<_>>();
+        let session = MuSig2Session::new(&MuSig2KeyAggContext::new(keys.as_slice()).unwrap(), aggnonce.as_slice(), msg.as_slice()).unwrap();
+        let s = session.sign(MuSig2SecretNonce::from_bytes(secnonce.as_slice()), sk.as_slice()).unwrap();
+        assert_eq!(s, hex(psig), "case: {}", i);
+        assert!(session.partial_sig_verify(s.as_slice(), pnonces[0].as_slice(), pks[0].as_slice()).is_ok(), "case: {}", i);
+        assert_eq!(session.partial_sig_verify(s.as_slice(), pnonces[1].as_slice(), pks[0].as_slice()).unwrap_err().kind(),
+            CryptoErrorKind::VerificationFailed, "case: {}", i);
+    }
+
+    // the secret key doesn't match the secret nonce, the signer isn't in the key aggregation
+    let session = MuSig2Session::new(&MuSig2KeyAggContext::new(&[pks[0].as_slice(), pks[1].as_slice()]).unwrap(), aggnonce.as_slice(), msg.as_slice()).unwrap();
+    assert_eq!(session.sign(MuSig2SecretNonce::from_bytes(secnonce.as_slice()), &[1u8; 32]).unwrap_err().kind(), CryptoErrorKind::InvalidPrivateKey);
+    let session = MuSig2Session::new(&MuSig2KeyAggContext::new(&[pks[1].as_slice(), pks[2].as_slice()]).unwrap(), aggnonce.as_slice(), msg.as_slice()).unwrap();
+    assert_eq!(session.sign(MuSig2SecretNonce::from_bytes(secnonce.as_slice()), sk.as_slice()).unwrap_err().kind(), CryptoErrorKind::InvalidPrivateKey);
+    assert!(MuSig2Session::new(&MuSig2KeyAggContext::new(&[pks[0].as_slice()]).unwrap(), &aggnonce[1..], msg.as_slice()).is_err());
+}
+
+#[test]
+fn musig2_sign_aggregate() {
+    // 3 signers with the plain and the x-only tweaks, the values are computed by the Python code following the BIP-327
+    // reference implementation
+    let sks = (1..=3u8).map(|i| vec![i; 32]).collect::<Vec<_>>();
+    let pks = sks.iter().map(|sk| {
+        let (x, y) = CurveParams::secp256k1().unwrap().scalar_base_point(BigInt::from_be_bytes(sk.as_slice()).as_ref());
+        let mut pk = vec![if y.to_be_bytes().last().copied().unwrap_or(0) & 1 == 0 { 2u8 } else { 3u8 }];
+        pk.extend_from_slice(Schnorr::new(rand(), KeyPair::from(PublicKey::new_uncheck(&x, &y))).unwrap().x_only_public_key());
+        pk
+    }).collect::<Vec<_>>();
+    let keys = pks.iter().map(|x| x.as_slice()).collect::<Vec<_>>();
+    let mut ctx = MuSig2KeyAggContext::new(keys.as_slice()).unwrap();
+    ctx.apply_tweak(&[0x11u8; 32], false).unwrap();
+    ctx.apply_tweak(&[0x22u8; 32], true).unwrap();
+    assert!(ctx.clone().apply_tweak(&[0xffu8; 32], true).is_err());
+    let aggpk = ctx.x_only_public_key();
+    assert_eq!(aggpk, hex("554de313771dfc1506e2d3c4cc6874f9eaee95c923cb681a3711cc9e422a200f"));
+
+    let msg = b"musig2 message";
+    let nonces = (0..3).map(|i| musig2_nonce_gen_with_rand(&[0x10 + i as u8; 32], Some(sks[i].as_slice()), keys[i],
+        Some(aggpk.as_slice()), Some(msg.as_ref()), None).unwrap()).collect::<Vec<_>>();
+    assert_eq!(nonces[0].1, hex("03a0dd8f29278ea06e3d5e3af7d461e4c0b744cbe476cd00e4a98a5a670e739fca03536d97e8eff90dcc5a51f2a749fb35a87138451f1f5f2547c8a98e7790eff3e5"));
+    assert_eq!(nonces[0].0.public_key(), keys[0]);
+    let pnonces = nonces.iter().map(|x| x.1.clone()).collect::<Vec<_>>();
+    let aggnonce = musig2_nonce_agg(pnonces.iter().map(|x| x.as_slice()).collect::<Vec<_>>().as_slice()).unwrap();
+
+    let session = MuSig2Session::new(&ctx, aggnonce.as_slice(), msg.as_ref()).unwrap();
+    let psigs = nonces.into_iter().enumerate().map(|(i, (sec, _))| session.sign(sec, sks[i].as_slice()).unwrap()).collect::<Vec<_>>();
+    assert_eq!(psigs[1], hex("e32a905e2a91386d39046f11b72ae4a798bd40962aeb0898f3bd749e9cb64a7a"));
+    for i in 0..3 {
+        assert!(session.partial_sig_verify(psigs[i].as_slice(), pnonces[i].as_slice(), keys[i]).is_ok(), "signer: {}", i);
+    }
+    let sig = session.partial_sig_agg(psigs.iter().map(|x| x.as_slice()).collect::<Vec<_>>().as_slice()).unwrap();
+    assert_eq!(sig.as_bytes(), hex("2be373291228e02063ce7863d963bd33d29be1483a7d4297073d29dca2394d68b181c9eaef170d070a8ee1f8f19718b3c18b1502e947f3348d31ecdfafbcfd33").as_slice());
+    let mut verifier = Schnorr::from_x_only_public_key(rand(), aggpk.as_slice()).unwrap();
+    assert!(verifier.verify(&sig, msg.as_ref()).is_ok());
+
+    // the randomly generated nonces, the missing partial signature
+    let mut rd = rand();
+    let nonces = (0..3).map(|i| musig2_nonce_gen(&mut rd, Some(sks[i].as_slice()), keys[i], None, None, None).unwrap()).collect::<Vec<_>>();
+    let aggnonce = musig2_nonce_agg(nonces.iter().map(|x| x.1.as_slice()).collect::<Vec<_>>().as_slice()).unwrap();
+    let session = MuSig2Session::new(&ctx, aggnonce.as_slice(), b"other message").unwrap();
+    let psigs = nonces.into_iter().enumerate().map(|(i, (sec, _))| session.sign(sec, sks[i].as_slice()).unwrap()).collect::<Vec<_>>();
+    let sig = session.partial_sig_agg(psigs.iter().map(|x| x.as_slice()).collect::<Vec<_>>().as_slice()).unwrap();
+    assert!(verifier.verify(&sig, b"other message").is_ok());
+    let sig = session.partial_sig_agg(&[psigs[0].as_slice(), psigs[1].as_slice()]).unwrap();
+    assert_eq!(verifier.verify(&sig, b"other message").unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert_eq!(session.partial_sig_agg(&[&[0xffu8; 32]]).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(musig2_nonce_gen_with_rand(&[0u8; 31], None, keys[0], None, None, None).is_err());
+}
\ No newline at end of file
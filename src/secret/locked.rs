@@ -0,0 +1,175 @@
+//! The page-aligned memory region which is locked into the RAM(`mlock` on the unix, `VirtualLock` on the windows)
+
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+use crate::{CryptoError, CryptoErrorKind};
+
+/// the page length if the system page size isn't available
+const DEFAULT_PAGE_LEN: usize = 4096;
+
+#[cfg(unix)]
+mod sys {
+    use std::os::raw::{c_int, c_long, c_void};
+
+    extern "C" {
+        fn mlock(addr: *const c_void, len: usize) -> c_int;
+        fn munlock(addr: *const c_void, len: usize) -> c_int;
+        fn sysconf(name: c_int) -> c_long;
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const SC_PAGESIZE: c_int = 30;
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    const SC_PAGESIZE: c_int = 29;
+    #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+    const SC_PAGESIZE: c_int = 47;
+    #[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+    const SC_PAGESIZE: c_int = 28;
+
+    /// `sysconf(_SC_PAGESIZE)`, 0 if it's unknown
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd",
+        target_os = "dragonfly", target_os = "openbsd", target_os = "netbsd"))]
+    pub fn page_len() -> usize {
+        let len = unsafe { sysconf(SC_PAGESIZE) };
+        if len > 0 { len as usize } else { 0 }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd",
+        target_os = "dragonfly", target_os = "openbsd", target_os = "netbsd")))]
+    pub fn page_len() -> usize {
+        0
+    }
+
+    pub fn lock(ptr: *mut u8, len: usize) -> bool {
+        unsafe { mlock(ptr as *const c_void, len) == 0 }
+    }
+
+    pub fn unlock(ptr: *mut u8, len: usize) {
+        unsafe { munlock(ptr as *const c_void, len); }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::os::raw::{c_int, c_void};
+
+    #[repr(C)]
+    struct SystemInfo {
+        oem_id: u32,
+        page_size: u32,
+        min_app_addr: *mut c_void,
+        max_app_addr: *mut c_void,
+        active_processor_mask: usize,
+        number_of_processors: u32,
+        processor_type: u32,
+        allocation_granularity: u32,
+        processor_level: u16,
+        processor_revision: u16,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn VirtualLock(addr: *mut c_void, len: usize) -> c_int;
+        fn VirtualUnlock(addr: *mut c_void, len: usize) -> c_int;
+        fn GetSystemInfo(info: *mut SystemInfo);
+    }
+
+    /// the `dwPageSize` of the `GetSystemInfo`
+    pub fn page_len() -> usize {
+        let mut info = std::mem::MaybeUninit::<SystemInfo>::zeroed();
+        unsafe {
+            GetSystemInfo(info.as_mut_ptr());
+            info.assume_init().page_size as usize
+        }
+    }
+
+    pub fn lock(ptr: *mut u8, len: usize) -> bool {
+        unsafe { VirtualLock(ptr as *mut c_void, len) != 0 }
+    }
+
+    pub fn unlock(ptr: *mut u8, len: usize) {
+        unsafe { VirtualUnlock(ptr as *mut c_void, len); }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    pub fn page_len() -> usize {
+        0
+    }
+
+    pub fn lock(_ptr: *mut u8, _len: usize) -> bool {
+        false
+    }
+
+    pub fn unlock(_ptr: *mut u8, _len: usize) {}
+}
+
+/// the system page length, the region is page-aligned, so the unlocking never unlocks the pages of the other
+/// allocations
+pub(super) fn page_len() -> usize {
+    let len = sys::page_len();
+    if len.is_power_of_two() { len } else { DEFAULT_PAGE_LEN }
+}
+
+/// The zeroed memory region, it's wiped before it's unlocked and freed
+pub(super) struct LockedRegion {
+    ptr: *mut u8,
+    layout: Layout,
+    is_locked: bool,
+}
+
+// SAFETY: the region is uniquely owned by the `LockedRegion`, it's freed only once by the owner's drop, and there is no
+// interior mutability through the `&LockedRegion`: the `SecretBytes` writes the region by the `&mut self` only, and the
+// `SecretArena` which writes the disjoint slices by the `&self` is `!Sync` because of its `Cell`.
+unsafe impl Send for LockedRegion {}
+unsafe impl Sync for LockedRegion {}
+
+impl LockedRegion {
+    /// allocate at least `len` bytes, the region is not locked if the locking failed(e.g. the `RLIMIT_MEMLOCK`
+    /// is exceeded), it's checked by the `is_locked`.
+    pub(super) fn new(len: usize) -> Result<Self, CryptoError> {
+        let page = page_len();
+        let layout = match len.max(1).div_ceil(page).checked_mul(page).map(|cap| Layout::from_size_align(cap, page)) {
+            Some(Ok(l)) => l,
+            _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("The secret length {} is too large", len))),
+        };
+        let cap = layout.size();
+
+        let ptr = unsafe { alloc_zeroed(layout) };
+        if ptr.is_null() {
+            return Err(CryptoError::new(CryptoErrorKind::InnerErr, format!("Failed to allocate {} bytes for the secret", cap)));
+        }
+        let is_locked = sys::lock(ptr, cap);
+        Ok(Self { ptr, layout, is_locked })
+    }
+
+    pub(super) fn is_locked(&self) -> bool {
+        self.is_locked
+    }
+
+    pub(super) fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    pub(super) fn as_ptr(&self) -> *mut u8 {
+        self.ptr
+    }
+}
+
+/// overwrite the `buf` by zeros, the volatile writes are not optimized out
+pub(super) fn wipe(buf: &mut [u8]) {
+    buf.iter_mut().for_each(|x| unsafe { std::ptr::write_volatile(x, 0) });
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+impl Drop for LockedRegion {
+    fn drop(&mut self) {
+        unsafe {
+            wipe(std::slice::from_raw_parts_mut(self.ptr, self.layout.size()));
+            if self.is_locked {
+                sys::unlock(self.ptr, self.layout.size());
+            }
+            dealloc(self.ptr, self.layout);
+        }
+    }
+}
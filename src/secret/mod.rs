@@ -0,0 +1,19 @@
+//! The secret key material in the locked memory
+//!
+//! The `SecretBytes` and the `SecretArena` allocate the page-aligned memory which is locked into the RAM by the
+//! `mlock`(unix) or `VirtualLock`(windows), so the secrets are not swapped to the disk, and the memory is wiped
+//! before it's freed. The locking is best-effort(e.g. the `RLIMIT_MEMLOCK`), it's checked by the `is_locked`.
+//!
+//! With the `mlock` feature, the private keys of the RSA, DSA and the elliptic curves keep their secret integers in
+//! the `SecretBytes`, and they can be exported to the locked memory by the `to_secret_bytes`. Note that the transient
+//! big integers in the arithmetic are allocated by the `rmath`, they are not locked.
+//!
+//! The `SecretBytes` isn't `Clone`, the `try_clone` reports the allocation or locking failure instead of panic.
+
+mod locked;
+
+mod secret_bytes;
+pub use secret_bytes::{SecretBytes, SecretArena, ArenaSecret};
+
+#[cfg(test)]
+mod secret_test;
\ No newline at end of file
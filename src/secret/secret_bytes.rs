@@ -0,0 +1,184 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::secret::locked::{LockedRegion, wipe};
+
+/// The secret bytes in the locked memory, the bytes are wiped when it's dropped
+pub struct SecretBytes {
+    region: LockedRegion,
+    len: usize,
+}
+
+impl SecretBytes {
+    /// the `len` zero bytes
+    pub fn new(len: usize) -> Result<Self, CryptoError> {
+        Ok(Self { region: LockedRegion::new(len)?, len })
+    }
+
+    /// copy the `data` to the locked memory, the caller should wipe the `data` after that
+    pub fn from_slice(data: &[u8]) -> Result<Self, CryptoError> {
+        let mut s = Self::new(data.len())?;
+        s.as_mut_slice().copy_from_slice(data);
+        Ok(s)
+    }
+
+    /// move the `data` to the locked memory, the `data` is wiped
+    pub fn from_vec(mut data: Vec<u8>) -> Result<Self, CryptoError> {
+        let s = Self::from_slice(data.as_slice());
+        wipe(data.as_mut_slice());
+        s
+    }
+
+    /// the `from_vec` which aborts on the allocation failure like the `Vec`, it's used by the infallible constructors
+    /// and clones of the private key types
+    #[cfg(any(feature = "rsa", feature = "dsa", feature = "ec"))]
+    pub(crate) fn from_vec_or_abort(data: Vec<u8>) -> Self {
+        use std::alloc::{handle_alloc_error, Layout};
+        let len = data.len();
+        Self::from_vec(data).unwrap_or_else(|_| handle_alloc_error(Layout::from_size_align(len.max(1), 1).unwrap_or_else(|_| Layout::new::<u8>())))
+    }
+
+    /// the copy in the new locked memory, the error is returned if the memory cannot be allocated
+    pub fn try_clone(&self) -> Result<Self, CryptoError> {
+        Self::from_slice(self.as_slice())
+    }
+
+    /// the same as the `new`, but the `NotSupportUsage` is returned if the memory cannot be locked
+    pub fn new_locked(len: usize) -> Result<Self, CryptoError> {
+        let s = Self::new(len)?;
+        if s.is_locked() {
+            Ok(s)
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The memory cannot be locked, the RLIMIT_MEMLOCK may be exceeded"))
+        }
+    }
+
+    /// whether the memory is locked into the RAM, the locking is best-effort
+    pub fn is_locked(&self) -> bool {
+        self.region.is_locked()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.region.as_ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.region.as_ptr(), self.len) }
+    }
+
+    /// constant-time comparison
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        self.len == other.len() && self.as_slice().iter().zip(other.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) == 0
+    }
+}
+
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl std::fmt::Debug for SecretBytes {
+    /// the secret is not printed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretBytes {{ len: {}, is_locked: {} }}", self.len, self.is_locked())
+    }
+}
+
+/// The arena of the short-lived secrets(e.g. the derived keys, the private scalars), the secrets are allocated
+/// from one locked region, so only one `mlock` is needed for them.
+pub struct SecretArena {
+    region: LockedRegion,
+    used: Cell<usize>,
+}
+
+/// The secret which is allocated from the `SecretArena`, it's wiped when it's dropped
+pub struct ArenaSecret<'a> {
+    ptr: *mut u8,
+    len: usize,
+    _arena: PhantomData<&'a SecretArena>,
+}
+
+impl SecretArena {
+    /// the arena of at least `capacity` bytes
+    pub fn new(capacity: usize) -> Result<Self, CryptoError> {
+        Ok(Self { region: LockedRegion::new(capacity)?, used: Cell::new(0) })
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.region.is_locked()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.region.capacity()
+    }
+
+    /// the allocated bytes
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// allocate the `len` zero bytes, the `LimitExceeded` is returned if the arena is exhausted.
+    pub fn alloc(&self, len: usize) -> Result<ArenaSecret<'_>, CryptoError> {
+        let used = self.used.get();
+        if len > self.capacity() - used {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded,
+                format!("The secret arena is exhausted: {} bytes remain, {} bytes required", self.capacity() - used, len)));
+        }
+        self.used.set(used + len);
+        Ok(ArenaSecret { ptr: unsafe { self.region.as_ptr().add(used) }, len, _arena: PhantomData })
+    }
+
+    /// allocate and copy the `data`
+    pub fn alloc_from_slice(&self, data: &[u8]) -> Result<ArenaSecret<'_>, CryptoError> {
+        let mut s = self.alloc(data.len())?;
+        s.as_mut_slice().copy_from_slice(data);
+        Ok(s)
+    }
+
+    /// release all the secrets, the borrow checker makes sure that no secret is alive
+    pub fn reset(&mut self) {
+        let used = self.used.get();
+        unsafe { wipe(std::slice::from_raw_parts_mut(self.region.as_ptr(), used)); }
+        self.used.set(0);
+    }
+}
+
+impl<'a> ArenaSecret<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<'a> AsRef<[u8]> for ArenaSecret<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<'a> Drop for ArenaSecret<'a> {
+    fn drop(&mut self) {
+        wipe(self.as_mut_slice());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::secret::{SecretBytes, SecretArena};
+use crate::CryptoErrorKind;
+
+#[test]
+fn secret_bytes() {
+    let key = (0u8..32).collect::<Vec<_>>();
+    let mut s = SecretBytes::from_slice(key.as_slice()).unwrap();
+    assert_eq!((s.len(), s.as_slice()), (32, key.as_slice()));
+    assert!(s.ct_eq(key.as_slice()));
+    assert!(!s.ct_eq(&key[1..]));
+    s.as_mut_slice()[0] = 0xff;
+    assert!(!s.ct_eq(key.as_slice()));
+
+    let c = s.try_clone().unwrap();
+    assert_eq!(c.as_slice(), s.as_slice());
+    assert_eq!(c.is_locked(), s.is_locked());
+    assert!(!format!("{:?}", c).contains("255"));
+    if s.is_locked() {
+        assert!(SecretBytes::new_locked(16).is_ok());
+    }
+
+    let e = SecretBytes::from_vec(Vec::new()).unwrap();
+    assert!(e.is_empty());
+    assert!(SecretBytes::new(usize::MAX).is_err());
+
+    // the `SecretBytes` is `Send` and `Sync`
+    let shared = std::sync::Arc::new(SecretBytes::from_slice(key.as_slice()).unwrap());
+    let other = shared.clone();
+    assert!(std::thread::spawn(move || other.ct_eq(key.as_slice())).join().unwrap());
+    assert_eq!(shared.len(), 32);
+}
+
+#[test]
+fn secret_arena() {
+    // one page of the system page size
+    let mut arena = SecretArena::new(100).unwrap();
+    let cap = arena.capacity();
+    assert!(cap >= 4096 && cap.is_power_of_two());
+    {
+        let a = arena.alloc_from_slice(&[1u8; 32]).unwrap();
+        let mut b = arena.alloc(cap - 96).unwrap();
+        b.as_mut_slice()[cap - 97] = 2;
+        assert_eq!((a.as_slice(), b.len(), arena.used()), (&[1u8; 32][..], cap - 96, cap - 64));
+        assert_eq!(arena.alloc(65).err().unwrap().kind(), CryptoErrorKind::LimitExceeded);
+        assert!(arena.alloc(64).is_ok());
+    }
+    arena.reset();
+    assert_eq!(arena.used(), 0);
+    // the released secrets are wiped
+    assert!(arena.alloc(cap).unwrap().as_slice().iter().all(|&x| x == 0));
+}
+
+#[cfg(feature = "ec")]
+#[test]
+fn secret_private_key() {
+    use std::str::FromStr;
+    use rmath::bigint::BigInt;
+    use crate::elliptic::{PrivateKey, PublicKey};
+
+    let d = BigInt::from_str("0x0102030405").unwrap();
+    let sk = PrivateKey::new_uncheck(PublicKey::new_uncheck(&BigInt::from(1u32), &BigInt::from(2u32)), &d);
+    assert_eq!(sk.to_secret_bytes().unwrap().as_slice(), &[1u8, 2, 3, 4, 5]);
+}
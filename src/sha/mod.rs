@@ -26,7 +26,7 @@ mod sha1;
 pub use sha1::SHA1;
 
 mod sha256;
-pub use sha256::{SHA256, SHA224};
+pub use sha256::{SHA256, SHA224, sha256d, tagged_hash};
 
 mod sha512;
 
@@ -141,5 +141,5 @@ impl Digest for SHA {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "md5"))]
 mod tests;
\ No newline at end of file
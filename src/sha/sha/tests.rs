@@ -1,4 +1,5 @@
-use crate::{SHA, Digest};
+use crate::{SHA, Digest, DigestMidState, MD5};
+use crate::sha::{SHA1, SHA256, sha256d, tagged_hash};
 
 fn cvt_bytes_to_str(b: &[u8]) -> String {
     let mut s= String::new();
@@ -112,6 +113,25 @@ fn sha256() {
     });
 }
 
+#[test]
+fn sha256d_tagged_hash() {
+    let cases = [
+        ("5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456", ""),
+        ("9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50", "hello"),
+    ];
+    cases.iter().for_each(|e| {
+        assert_eq!(cvt_bytes_to_str(sha256d(e.1.as_bytes()).as_slice()).as_str(), e.0, "case=>{}", e.1);
+    });
+
+    let cases = [
+        ("2dba5dbc339e7316aea2683faf839c1b7b1ee2313db792112588118df066aa35", "", ""),
+        ("770a5b7e7c304bbcc3ea107343ff951dd404312ef418db0c3b94e2ebfbb50087", "BIP0340/challenge", "abc"),
+    ];
+    cases.iter().for_each(|e| {
+        assert_eq!(cvt_bytes_to_str(tagged_hash(e.1.as_bytes(), e.2.as_bytes()).as_slice()).as_str(), e.0, "case=>{}", e.1);
+    });
+}
+
 #[test]
 fn sha224() {
     let cases = [
@@ -738,3 +758,61 @@ fn sha512t256() {
         sha.reset();
     });
 }
+
+/// the Merkle–Damgård padding of the `len` bytes message
+fn md_padding(len: usize, is_be: bool) -> Vec<u8> {
+    let mut pad = vec![0x80u8];
+    pad.resize(((55usize.wrapping_sub(len)) % 64) + 1, 0);
+    let bits = (len as u64) << 3;
+    pad.extend_from_slice(if is_be {bits.to_be_bytes()} else {bits.to_le_bytes()}.as_ref());
+    pad
+}
+
+fn mid_state_case<D: DigestMidState + Clone>(digest: D, is_be: bool) {
+    let msg = (0..200u8).collect::<Vec<_>>();
+    let (mut tgt, mut dgst) = (Vec::new(), Vec::new());
+    let mut h = digest.clone();
+    h.write(msg.as_slice());
+    h.checksum(&mut tgt);
+    assert!(h.export_mid_state().is_err());
+    
+    // hand over the hashing at the block boundary
+    for &n in [0usize, 64, 128, 192].iter() {
+        let mut h = digest.clone();
+        h.write(&msg[..(n / 2)]);
+        h.write(&msg[(n / 2)..n]);
+        let (state, len) = h.export_mid_state().unwrap();
+        assert_eq!(len, n as u64);
+        let mut h = D::from_mid_state(state.as_slice(), len).unwrap();
+        h.write(&msg[n..]);
+        h.checksum(&mut dgst);
+        assert_eq!(tgt, dgst, "case: {}", n);
+    }
+    let mut h = digest.clone();
+    h.write(&msg[..65]);
+    assert!(h.export_mid_state().is_err());
+    assert!(D::from_mid_state(&tgt[1..], 64).is_err());
+    assert!(D::from_mid_state(tgt.as_slice(), 65).is_err());
+    
+    // length extension: H(secret || msg) => H(secret || msg || pad || ext)
+    let (secret_msg, ext) = ("secret key:user=alice".as_bytes(), "&role=admin".as_bytes());
+    let mut h = digest.clone();
+    h.write(secret_msg);
+    h.checksum(&mut tgt);
+    let pad = md_padding(secret_msg.len(), is_be);
+    let mut h = D::from_mid_state(tgt.as_slice(), (secret_msg.len() + pad.len()) as u64).unwrap();
+    h.write(ext);
+    h.checksum(&mut dgst);
+    
+    let mut h = digest;
+    h.write_vectored(&[secret_msg, pad.as_slice(), ext]);
+    h.checksum(&mut tgt);
+    assert_eq!(tgt, dgst);
+}
+
+#[test]
+fn sha_mid_state() {
+    mid_state_case(SHA1::new(), true);
+    mid_state_case(SHA256::new(), true);
+    mid_state_case(MD5::new(), false);
+}
@@ -2,7 +2,7 @@
 //! https://www.cnblogs.com/mengsuenyan/p/12697811.html#toc  
 
 use crate::sha::const_tables::{SHA1_DIGEST_WSIZE, SHA1_BLOCK_SIZE, SHA1_INIT, SHA1_DIGEST_SIZE};
-use crate::Digest;
+use crate::{Digest, DigestMidState, CryptoError, CryptoErrorKind};
 
 #[derive(Clone)]
 pub struct SHA1 {
@@ -23,6 +23,20 @@ impl SHA1 {
             is_checked: false,
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(20));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
+}
+
+impl Default for SHA1 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Digest for SHA1 {
@@ -97,3 +111,38 @@ impl Digest for SHA1 {
         *self = SHA1::new();
     }
 }
+
+impl DigestMidState for SHA1 {
+    fn export_mid_state(&self) -> Result<(Vec<u8>, u64), CryptoError> {
+        let mut h = self.clone();
+        if h.idx == SHA1_BLOCK_SIZE {
+            h.update(None);
+            h.idx = 0;
+        }
+        
+        if h.is_checked {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The digest has been finalized"))
+        } else if h.idx != 0 {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, 
+                format!("The processed message length {} is not a multiple of the block size", h.len)))
+        } else {
+            let mut state = Vec::with_capacity(SHA1_DIGEST_WSIZE << 2);
+            h.digest.iter().for_each(|&e| state.extend_from_slice(e.to_be_bytes().as_ref()));
+            Ok((state, h.len as u64))
+        }
+    }
+
+    fn from_mid_state(state: &[u8], len: u64) -> Result<Self, CryptoError> {
+        if state.len() != (SHA1_DIGEST_WSIZE << 2) || !len.is_multiple_of(SHA1_BLOCK_SIZE as u64) || len > (usize::MAX as u64) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+                format!("Invalid mid-state: the state must be {} bytes, and the length must be a multiple of {}", SHA1_DIGEST_WSIZE << 2, SHA1_BLOCK_SIZE)));
+        }
+        
+        let mut h = SHA1::new();
+        h.digest.iter_mut().zip(state.chunks_exact(4)).for_each(|(a, b)| {
+            *a = u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+        });
+        h.len = len as usize;
+        Ok(h)
+    }
+}
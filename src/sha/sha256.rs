@@ -2,7 +2,7 @@
 //! https://www.cnblogs.com/mengsuenyan/p/12697811.html#toc  
 
 use crate::sha::const_tables::{SHA256_DIGEST_WSIZE, SHA256_BLOCK_SIZE, SHA256_INIT, SHA256_DIGEST_SIZE, SHA224_INIT, SHA224_BLOCK_SIZE, SHA224_DIGEST_SIZE};
-use crate::Digest;
+use crate::{Digest, DigestMidState, CryptoError, CryptoErrorKind};
 
 #[derive(Clone)]
 pub struct SHA256 {
@@ -23,6 +23,36 @@ impl SHA256 {
             is_checked: false,
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(32));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
+}
+
+/// the double SHA-256 `SHA256(SHA256(data))`, e.g. the Bitcoin block hash and the transaction ID
+pub fn sha256d(data: &[u8]) -> Vec<u8> {
+    SHA256::digest(SHA256::digest(data).as_slice())
+}
+
+/// the BIP-340 tagged hash `SHA256(SHA256(tag) || SHA256(tag) || msg)`, e.g. the `tag` is `b"BIP0340/challenge"`
+pub fn tagged_hash(tag: &[u8], msg: &[u8]) -> Vec<u8> {
+    let (mut h, mut d) = (SHA256::new(), Vec::with_capacity(32));
+    let t = SHA256::digest(tag);
+    h.write(t.as_slice());
+    h.write(t.as_slice());
+    h.write(msg);
+    h.checksum(&mut d);
+    d
+}
+
+impl Default for SHA256 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Digest for SHA256 {
@@ -96,6 +126,41 @@ impl Digest for SHA256 {
     }
 }
 
+impl DigestMidState for SHA256 {
+    fn export_mid_state(&self) -> Result<(Vec<u8>, u64), CryptoError> {
+        let mut h = self.clone();
+        if h.idx == SHA256_BLOCK_SIZE {
+            h.sha256_update(None);
+            h.idx = 0;
+        }
+        
+        if h.is_checked {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The digest has been finalized"))
+        } else if h.idx != 0 {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, 
+                format!("The processed message length {} is not a multiple of the block size", h.len)))
+        } else {
+            let mut state = Vec::with_capacity(SHA256_DIGEST_WSIZE << 2);
+            h.digest.iter().for_each(|&e| state.extend_from_slice(e.to_be_bytes().as_ref()));
+            Ok((state, h.len as u64))
+        }
+    }
+
+    fn from_mid_state(state: &[u8], len: u64) -> Result<Self, CryptoError> {
+        if state.len() != (SHA256_DIGEST_WSIZE << 2) || !len.is_multiple_of(SHA256_BLOCK_SIZE as u64) || len > (usize::MAX as u64) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, 
+                format!("Invalid mid-state: the state must be {} bytes, and the length must be a multiple of {}", SHA256_DIGEST_WSIZE << 2, SHA256_BLOCK_SIZE)));
+        }
+        
+        let mut h = SHA256::new();
+        h.digest.iter_mut().zip(state.chunks_exact(4)).for_each(|(a, b)| {
+            *a = u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+        });
+        h.len = len as usize;
+        Ok(h)
+    }
+}
+
 #[derive(Clone)]
 pub struct SHA224 {
     sha_: SHA256,
@@ -113,9 +178,23 @@ impl SHA224 {
             }
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(28));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
 }
 
 
+impl Default for SHA224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Digest for SHA224 {
     fn block_size(&self) -> Option<usize> {
         Some(64)
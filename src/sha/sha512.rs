@@ -26,6 +26,20 @@ impl SHA512 {
             is_checked: false,
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(64));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
+}
+
+impl Default for SHA512 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Digest for SHA512 {
@@ -115,6 +129,14 @@ impl SHA384 {
             }
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(48));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
 }
 
 /// SHA512/256
@@ -135,6 +157,14 @@ impl SHA512T256 {
             }
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(32));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
 }
 
 /// SHA512/224
@@ -155,10 +185,24 @@ impl SHA512T224 {
             }
         }
     }
+    
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(28));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
 }
 
 macro_rules! impl_digest_for_sha512_series {
     ($S: ident, $L: ident) => {
+        impl Default for $S {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        
         impl Digest for $S {
             fn block_size(&self) -> Option<usize> {
                 Some(128)
@@ -0,0 +1,114 @@
+//! The builder of the construction-time parameters of the SHAKE, cSHAKE, KMAC, ParallelHash and KangarooTwelve
+//!
+//! the builder is `Clone` and it's not consumed by the `build_*` functions, so one configured builder can create
+//! the digesters for the pool. The default digest length is the twice of the security strength, e.g. 256 bits of
+//! the `cshake128` and 512 bits of the `cshake256`.
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::sha3::{Shake128, Shake256, CShake128, CShake256, KMAC128, KMAC256, ParallelHash128, ParallelHash256, KangarooTwelve};
+
+#[derive(Clone, Default)]
+pub struct XofBuilder {
+    digest_bits_len: Option<usize>,
+    key: Option<Vec<u8>>,
+    function_name: Vec<u8>,
+    customization: Vec<u8>,
+    block_size: Option<usize>,
+    is_xof: bool,
+}
+
+impl XofBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// the output length in bits
+    pub fn digest_bits_len(mut self, bits_len: usize) -> Self {
+        self.digest_bits_len = Some(bits_len);
+        self
+    }
+    
+    /// the key of the KMAC, it's required by the KMAC
+    pub fn key(mut self, key: &[u8]) -> Self {
+        self.key = Some(key.to_vec());
+        self
+    }
+    
+    /// the function name `N` of the cSHAKE, it's reserved for the NIST
+    pub fn function_name(mut self, function_name: &[u8]) -> Self {
+        self.function_name = function_name.to_vec();
+        self
+    }
+    
+    /// the customization string `S` of the cSHAKE, KMAC, ParallelHash and the `C` of the KangarooTwelve
+    pub fn customization(mut self, customization: &[u8]) -> Self {
+        self.customization = customization.to_vec();
+        self
+    }
+    
+    /// the block size `B` in bytes of the ParallelHash, it's required by the ParallelHash
+    pub fn block_size(mut self, block_size: usize) -> Self {
+        self.block_size = Some(block_size);
+        self
+    }
+    
+    /// the KMACXOF and ParallelHashXOF
+    pub fn xof(mut self, is_xof: bool) -> Self {
+        self.is_xof = is_xof;
+        self
+    }
+    
+    fn bits_len(&self, default_bits_len: usize) -> usize {
+        self.digest_bits_len.unwrap_or(default_bits_len)
+    }
+    
+    fn required_key(&self, name: &str) -> Result<&[u8], CryptoError> {
+        self.key.as_deref().ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The {} key is not set", name)))
+    }
+    
+    fn required_block_size(&self, name: &str) -> Result<usize, CryptoError> {
+        self.block_size.ok_or_else(|| CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The {} block size is not set", name)))
+    }
+    
+    pub fn build_shake128(&self) -> Shake128 {
+        Shake128::new(self.bits_len(256))
+    }
+    
+    pub fn build_shake256(&self) -> Shake256 {
+        Shake256::new(self.bits_len(512))
+    }
+    
+    pub fn build_cshake128(&self) -> CShake128 {
+        CShake128::new(self.bits_len(256), self.function_name.as_slice(), self.customization.as_slice())
+    }
+    
+    pub fn build_cshake256(&self) -> CShake256 {
+        CShake256::new(self.bits_len(512), self.function_name.as_slice(), self.customization.as_slice())
+    }
+    
+    pub fn build_kmac128(&self) -> Result<KMAC128, CryptoError> {
+        let kmac = KMAC128::new(self.required_key("KMAC128")?, self.bits_len(256), self.customization.as_slice());
+        Ok(if self.is_xof { kmac.to_xof() } else { kmac })
+    }
+    
+    pub fn build_kmac256(&self) -> Result<KMAC256, CryptoError> {
+        let kmac = KMAC256::new(self.required_key("KMAC256")?, self.bits_len(512), self.customization.as_slice());
+        Ok(if self.is_xof { kmac.to_xof() } else { kmac })
+    }
+    
+    pub fn build_parallel_hash128(&self) -> Result<ParallelHash128, CryptoError> {
+        let h = ParallelHash128::new(self.required_block_size("ParallelHash128")?, self.bits_len(256), self.customization.as_slice())?;
+        Ok(if self.is_xof { h.to_xof() } else { h })
+    }
+    
+    pub fn build_parallel_hash256(&self) -> Result<ParallelHash256, CryptoError> {
+        let h = ParallelHash256::new(self.required_block_size("ParallelHash256")?, self.bits_len(512), self.customization.as_slice())?;
+        Ok(if self.is_xof { h.to_xof() } else { h })
+    }
+    
+    pub fn build_kangaroo_twelve(&self) -> KangarooTwelve {
+        KangarooTwelve::new(self.bits_len(256), self.customization.as_slice())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+//! cSHAKE: SP 800-185 3
+//! 
+//! cSHAKE128(X, L, N, S) = KECCAK[256](bytepad(encode_string(N) || encode_string(S), 168) || X || 00, L)  
+//! cSHAKE256(X, L, N, S) = KECCAK[512](bytepad(encode_string(N) || encode_string(S), 136) || X || 00, L)  
+//! the cSHAKE is the SHAKE if both the function name `N` and the customization string `S` are empty.
+
+use crate::{KeccakSponge, Digest, Keccak, DigestXOF};
+use crate::sha3::sp800_185::{bytepad, encode_string};
+
+macro_rules! impl_cshake {
+    ($Type0: ident, $BITS_LEN: literal) => {
+        #[derive(Clone)]
+        pub struct $Type0 {
+            digest: Vec<u8>,
+            sponge: KeccakSponge,
+            want_bits_len: usize,
+            prefix: Vec<u8>,
+            suffix: [u8;1],
+            suffix_len: usize,
+            is_checked: bool,
+        }
+        
+        impl $Type0 {
+            /// the `function_name` is reserved for the functions defined by the NIST(e.g. `b"KMAC"`), 
+            /// the `customization` is the domain separation string.
+            pub fn new(digest_bits_len: usize, function_name: &[u8], customization: &[u8]) -> Self {
+                let rate = 200 - ($BITS_LEN >> 2);
+                let (prefix, suffix, suffix_len) = if function_name.is_empty() && customization.is_empty() {
+                    (Vec::new(), [0b1111u8], 4)
+                } else {
+                    let mut ns = encode_string(function_name);
+                    ns.extend(encode_string(customization));
+                    (bytepad(ns.as_slice(), rate), [0b00u8], 2)
+                };
+                
+                let mut sponge = Keccak::new(1600, 24).unwrap().sponge(rate << 3).unwrap();
+                sponge.absorb(prefix.as_slice(), prefix.len() << 3);
+                Self {
+                    digest: Vec::with_capacity(64),
+                    sponge,
+                    want_bits_len: digest_bits_len,
+                    prefix,
+                    suffix,
+                    suffix_len,
+                    is_checked: false,
+                }
+            }
+        }
+        
+        impl Digest for $Type0 {
+            fn block_size(&self) -> Option<usize> {
+                None
+            }
+        
+            fn bits_len(&self) -> usize {
+                self.want_bits_len
+            }
+        
+            fn write(&mut self, data: &[u8]) {
+                self.sponge.absorb(data, data.len() << 3);
+                
+                self.is_checked = false;
+            }
+        
+            fn checksum(&mut self, digest: &mut Vec<u8>) {
+                if !self.is_checked {
+                    self.sponge.absorb(self.suffix.as_ref(), self.suffix_len);
+                    self.sponge.squeeze(self.bits_len(), &mut self.digest);
+                    
+                    self.sponge.reset();
+                    self.sponge.absorb(self.prefix.as_slice(), self.prefix.len() << 3);
+                    self.is_checked = true;
+                }
+                
+                digest.clear();
+                digest.extend(self.digest.iter());
+            }
+        
+            fn reset(&mut self) {
+                self.sponge.reset();
+                self.sponge.absorb(self.prefix.as_slice(), self.prefix.len() << 3);
+                self.digest.clear();
+                self.is_checked = false;
+            }
+        }
+        
+        impl DigestXOF for $Type0 {
+            fn set_digest_len(&mut self, bits_len: usize) {
+                self.want_bits_len = bits_len;
+                self.reset();
+            }
+        }
+    };
+}
+
+impl_cshake!(CShake128, 128);
+impl_cshake!(CShake256, 256);
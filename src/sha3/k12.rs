@@ -0,0 +1,156 @@
+//! KangarooTwelve: RFC 9861
+//! 
+//! TurboSHAKE128(M, D, L) = Sponge[Keccak-p[1600, 12], pad10*1, 1344](M || D, L), the `D` is the domain separation suffix  
+//! S = M || C || length_encode(|C|), the `C` is the customization string  
+//! K12(M, C, L) = TurboSHAKE128(S, 07, L) if |S| <= 8192 bytes, otherwise the `S` is split into the 8192 bytes chunks
+//! `S_0 || S_1 || ... || S_{n-1}`, `CV_i = TurboSHAKE128(S_i, 0B, 32)`, and
+//! K12(M, C, L) = TurboSHAKE128(S_0 || 03 00^7 || CV_1 || ... || CV_{n-1} || length_encode(n-1) || FF FF, 06, L).  
+//! The chaining values of the chunks are independent of each other.
+
+use crate::{KeccakSponge, Digest, Keccak, DigestXOF};
+
+const K12_CHUNK_LEN: usize = 8192;
+const K12_CV_BITS_LEN: usize = 256;
+// the bits of the domain separation suffix before the pad10*1
+const K12_SINGLE_SUFFIX: (u8, usize) = (0b11, 2);
+const K12_LEAF_SUFFIX: (u8, usize) = (0b011, 3);
+const K12_FINAL_SUFFIX: (u8, usize) = (0b10, 2);
+
+/// `length_encode(x) = x || n`, where the `x` is the big-endian bytes without the leading zeros and `n` is its length
+fn length_encode(x: u64) -> Vec<u8> {
+    let b = x.to_be_bytes();
+    let i = b.iter().position(|&e| e != 0).unwrap_or(b.len());
+    let mut v = b[i..].to_vec();
+    v.push((b.len() - i) as u8);
+    v
+}
+
+#[derive(Clone)]
+pub struct KangarooTwelve {
+    customization: Vec<u8>,
+    want_bits_len: usize,
+    // the current chunk, it's the `S_0` if the `final_node` is `None`
+    chunk: Vec<u8>,
+    final_node: Option<KeccakSponge>,
+    chunks: u64,
+    digest: Vec<u8>,
+    is_checked: bool,
+}
+
+impl KangarooTwelve {
+    pub fn new(digest_bits_len: usize, customization: &[u8]) -> Self {
+        Self {
+            customization: customization.to_vec(),
+            want_bits_len: digest_bits_len,
+            chunk: Vec::with_capacity(K12_CHUNK_LEN),
+            final_node: None,
+            chunks: 0,
+            digest: Vec::with_capacity(64),
+            is_checked: false,
+        }
+    }
+    
+    fn turbo_shake() -> KeccakSponge {
+        Keccak::new(1600, 12).unwrap().sponge(1344).unwrap()
+    }
+    
+    fn chaining_value(chunk: &[u8]) -> Vec<u8> {
+        let (mut sponge, mut cv) = (Self::turbo_shake(), Vec::with_capacity(K12_CV_BITS_LEN >> 3));
+        sponge.absorb(chunk, chunk.len() << 3);
+        sponge.absorb(&[K12_LEAF_SUFFIX.0], K12_LEAF_SUFFIX.1);
+        sponge.squeeze(K12_CV_BITS_LEN, &mut cv);
+        cv
+    }
+    
+    /// absorb the data of the `S`
+    fn absorb(&mut self, data: &[u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            if self.chunk.len() == K12_CHUNK_LEN {
+                match self.final_node.as_mut() {
+                    None => {
+                        // the `S` is more than one chunk, switch to the tree hashing
+                        let mut sponge = Self::turbo_shake();
+                        sponge.absorb(self.chunk.as_slice(), K12_CHUNK_LEN << 3);
+                        sponge.absorb(&[3, 0, 0, 0, 0, 0, 0, 0], 64);
+                        self.final_node = Some(sponge);
+                    },
+                    Some(sponge) => {
+                        let cv = Self::chaining_value(self.chunk.as_slice());
+                        sponge.absorb(cv.as_slice(), K12_CV_BITS_LEN);
+                        self.chunks += 1;
+                    },
+                }
+                self.chunk.clear();
+            }
+            
+            let l = (K12_CHUNK_LEN - self.chunk.len()).min(data.len());
+            self.chunk.extend_from_slice(&data[..l]);
+            data = &data[l..];
+        }
+    }
+    
+    fn finalize(mut self, digest: &mut Vec<u8>) {
+        let mut suffix = self.customization.clone();
+        suffix.extend(length_encode(self.customization.len() as u64));
+        self.absorb(suffix.as_slice());
+        
+        match self.final_node.take() {
+            None => {
+                let mut sponge = Self::turbo_shake();
+                sponge.absorb(self.chunk.as_slice(), self.chunk.len() << 3);
+                sponge.absorb(&[K12_SINGLE_SUFFIX.0], K12_SINGLE_SUFFIX.1);
+                sponge.squeeze(self.want_bits_len, digest);
+            },
+            Some(mut sponge) => {
+                let cv = Self::chaining_value(self.chunk.as_slice());
+                sponge.absorb(cv.as_slice(), K12_CV_BITS_LEN);
+                let mut tail = length_encode(self.chunks + 1);
+                tail.extend_from_slice(&[0xff, 0xff]);
+                sponge.absorb(tail.as_slice(), tail.len() << 3);
+                sponge.absorb(&[K12_FINAL_SUFFIX.0], K12_FINAL_SUFFIX.1);
+                sponge.squeeze(self.want_bits_len, digest);
+            },
+        }
+    }
+}
+
+impl Digest for KangarooTwelve {
+    fn block_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn bits_len(&self) -> usize {
+        self.want_bits_len
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        self.absorb(data);
+        self.is_checked = false;
+    }
+
+    fn checksum(&mut self, digest: &mut Vec<u8>) {
+        if !self.is_checked {
+            self.clone().finalize(&mut self.digest);
+            self.is_checked = true;
+        }
+        
+        digest.clear();
+        digest.extend(self.digest.iter());
+    }
+
+    fn reset(&mut self) {
+        self.chunk.clear();
+        self.final_node = None;
+        self.chunks = 0;
+        self.digest.clear();
+        self.is_checked = false;
+    }
+}
+
+impl DigestXOF for KangarooTwelve {
+    fn set_digest_len(&mut self, bits_len: usize) {
+        self.want_bits_len = bits_len;
+        self.reset();
+    }
+}
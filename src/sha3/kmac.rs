@@ -0,0 +1,97 @@
+//! KMAC: SP 800-185 4
+//!
+//! KMAC128(K, X, L, S) = cSHAKE128(bytepad(encode_string(K), 168) || X || right_encode(L), L, "KMAC", S)
+//! KMAC256(K, X, L, S) = cSHAKE256(bytepad(encode_string(K), 136) || X || right_encode(L), L, "KMAC", S)
+//! the KMACXOF replaces the `right_encode(L)` by the `right_encode(0)`.
+
+use crate::{Digest, DigestXOF};
+use crate::sha3::{CShake128, CShake256};
+use crate::sha3::sp800_185::{bytepad, encode_string, right_encode};
+
+macro_rules! impl_kmac {
+    ($Type0: ident, $CShake: ident, $BITS_LEN: literal) => {
+        #[derive(Clone)]
+        pub struct $Type0 {
+            cshake: $CShake,
+            key: Vec<u8>,
+            digest: Vec<u8>,
+            is_xof: bool,
+            is_checked: bool,
+        }
+
+        impl $Type0 {
+            /// the `customization` is the domain separation string, the key should be at least the security strength
+            /// of the KMAC.
+            pub fn new(key: &[u8], digest_bits_len: usize, customization: &[u8]) -> Self {
+                let key = bytepad(encode_string(key).as_slice(), 200 - ($BITS_LEN >> 2));
+                let mut cshake = $CShake::new(digest_bits_len, b"KMAC", customization);
+                cshake.write(key.as_slice());
+                Self {
+                    cshake,
+                    key,
+                    digest: Vec::with_capacity(64),
+                    is_xof: false,
+                    is_checked: false,
+                }
+            }
+
+            pub fn is_xof(&self) -> bool {
+                self.is_xof
+            }
+
+            /// the KMACXOF, the output doesn't depend on the output length
+            pub fn to_xof(mut self) -> Self {
+                self.is_xof = true;
+                self.reset();
+                self
+            }
+        }
+
+        impl Digest for $Type0 {
+            fn block_size(&self) -> Option<usize> {
+                None
+            }
+
+            fn bits_len(&self) -> usize {
+                self.cshake.bits_len()
+            }
+
+            fn write(&mut self, data: &[u8]) {
+                self.cshake.write(data);
+                self.is_checked = false;
+            }
+
+            fn checksum(&mut self, digest: &mut Vec<u8>) {
+                if !self.is_checked {
+                    let l = if self.is_xof {0} else {self.bits_len() as u64};
+                    self.cshake.write(right_encode(l).as_slice());
+                    self.cshake.checksum(&mut self.digest);
+
+                    self.cshake.reset();
+                    self.cshake.write(self.key.as_slice());
+                    self.is_checked = true;
+                }
+
+                digest.clear();
+                digest.extend(self.digest.iter());
+            }
+
+            fn reset(&mut self) {
+                self.cshake.reset();
+                self.cshake.write(self.key.as_slice());
+                self.digest.clear();
+                self.is_checked = false;
+            }
+        }
+
+        impl DigestXOF for $Type0 {
+            fn set_digest_len(&mut self, bits_len: usize) {
+                self.cshake.set_digest_len(bits_len);
+                self.reset();
+            }
+        }
+    };
+}
+
+impl_kmac!(KMAC128, CShake128, 128);
+impl_kmac!(KMAC256, CShake256, 256);
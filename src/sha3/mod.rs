@@ -38,3 +38,20 @@ pub use sha3::{SHA3, SHA224, SHA256, SHA384, SHA512};
 
 mod shake;
 pub use shake::{Shake256, Shake128};
+
+mod sp800_185;
+
+mod cshake;
+pub use cshake::{CShake128, CShake256};
+
+mod kmac;
+pub use kmac::{KMAC128, KMAC256};
+
+mod parallel_hash;
+pub use parallel_hash::{ParallelHash128, ParallelHash256};
+
+mod k12;
+pub use k12::KangarooTwelve;
+
+mod builder;
+pub use builder::XofBuilder;
\ No newline at end of file
@@ -0,0 +1,177 @@
+//! ParallelHash: SP 800-185 6
+//! 
+//! newX = left_encode(B) || cSHAKE(X_0, 2c) || ... || cSHAKE(X_{n-1}, 2c) || right_encode(n) || right_encode(L)  
+//! ParallelHash(X, B, L, S) = cSHAKE(newX, L, "ParallelHash", S)  
+//! where the `X_i` is the `B` bytes block of the `X`(the last block may be shorter), the `c` is the security strength.
+//! The blocks are hashed independently, so they can be hashed by the worker threads(the `write_parallel`),
+//! the ParallelHashXOF uses the `right_encode(0)` instead of the `right_encode(L)`.
+
+use crate::{Digest, DigestXOF, CryptoError, CryptoErrorKind};
+use crate::sha3::{CShake128, CShake256, Shake128, Shake256};
+use crate::sha3::sp800_185::{left_encode, right_encode};
+
+macro_rules! impl_parallel_hash {
+    ($Type0: ident, $CShake: ident, $Shake: ident, $BITS_LEN: literal) => {
+        #[derive(Clone)]
+        pub struct $Type0 {
+            block_size: usize,
+            want_bits_len: usize,
+            customization: Vec<u8>,
+            is_xof: bool,
+            cshake: $CShake,
+            buf: Vec<u8>,
+            blocks: u64,
+            digest: Vec<u8>,
+            is_checked: bool,
+        }
+        
+        impl $Type0 {
+            /// the `block_size` is the `B` bytes, it must be great than 0.
+            pub fn new(block_size: usize, digest_bits_len: usize, customization: &[u8]) -> Result<Self, CryptoError> {
+                if block_size == 0 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The ParallelHash block size must be great than 0"));
+                }
+                
+                let mut h = Self {
+                    block_size,
+                    want_bits_len: digest_bits_len,
+                    customization: customization.to_vec(),
+                    is_xof: false,
+                    cshake: $CShake::new(digest_bits_len, b"ParallelHash", customization),
+                    buf: Vec::with_capacity(block_size),
+                    blocks: 0,
+                    digest: Vec::with_capacity(64),
+                    is_checked: false,
+                };
+                h.reset();
+                Ok(h)
+            }
+            
+            pub fn is_xof(&self) -> bool {
+                self.is_xof
+            }
+            
+            /// to the ParallelHashXOF
+            pub fn to_xof(self) -> Self {
+                let mut tmp = self;
+                tmp.is_xof = true;
+                tmp.reset();
+                tmp
+            }
+            
+            pub fn block_size(&self) -> usize {
+                self.block_size
+            }
+            
+            /// the state of the empty message, the cached digest is kept
+            fn reset_state(&mut self) {
+                self.cshake = $CShake::new(self.want_bits_len, b"ParallelHash", self.customization.as_slice());
+                self.cshake.write(left_encode(self.block_size as u64).as_slice());
+                self.buf.clear();
+                self.blocks = 0;
+            }
+            
+            fn chaining_value(block: &[u8]) -> Vec<u8> {
+                let (mut shake, mut z) = ($Shake::new($BITS_LEN << 1), Vec::with_capacity($BITS_LEN >> 2));
+                shake.write(block);
+                shake.checksum(&mut z);
+                z
+            }
+            
+            /// `write` the `data` with the full blocks hashed by the `threads` worker threads, the digest doesn't
+            /// depend on the `threads`.
+            #[cfg(feature = "parallel")]
+            pub fn write_parallel(&mut self, data: &[u8], threads: usize) -> Result<(), CryptoError> {
+                if threads == 0 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The number of threads must be great than 0"));
+                }
+                
+                // fill the pending block at first
+                let l = if self.buf.is_empty() { 0 } else { (self.block_size - self.buf.len()).min(data.len()) };
+                self.write(&data[..l]);
+                let data = &data[l..];
+                let (blocks, remain) = data.split_at(data.len() - data.len() % self.block_size);
+                
+                let blocks = blocks.chunks(self.block_size).collect::<Vec<_>>();
+                let mut zs = vec![Vec::new(); blocks.len()];
+                let per_thread = blocks.len().div_ceil(threads).max(1);
+                std::thread::scope(|s| {
+                    for (z, b) in zs.chunks_mut(per_thread).zip(blocks.chunks(per_thread)) {
+                        s.spawn(move || {
+                            z.iter_mut().zip(b.iter()).for_each(|(z, b)| *z = Self::chaining_value(b));
+                        });
+                    }
+                });
+                
+                zs.iter().for_each(|z| self.cshake.write(z.as_slice()));
+                self.blocks += zs.len() as u64;
+                self.write(remain);
+                self.is_checked = false;
+                Ok(())
+            }
+        }
+        
+        impl Digest for $Type0 {
+            fn block_size(&self) -> Option<usize> {
+                None
+            }
+        
+            fn bits_len(&self) -> usize {
+                self.want_bits_len
+            }
+        
+            fn write(&mut self, data: &[u8]) {
+                let mut data = data;
+                while !data.is_empty() {
+                    let l = (self.block_size - self.buf.len()).min(data.len());
+                    self.buf.extend_from_slice(&data[..l]);
+                    data = &data[l..];
+                    
+                    if self.buf.len() == self.block_size {
+                        let z = Self::chaining_value(self.buf.as_slice());
+                        self.cshake.write(z.as_slice());
+                        self.blocks += 1;
+                        self.buf.clear();
+                    }
+                }
+                
+                self.is_checked = false;
+            }
+        
+            fn checksum(&mut self, digest: &mut Vec<u8>) {
+                if !self.is_checked {
+                    if !self.buf.is_empty() {
+                        let z = Self::chaining_value(self.buf.as_slice());
+                        self.cshake.write(z.as_slice());
+                        self.blocks += 1;
+                    }
+                    self.cshake.write(right_encode(self.blocks).as_slice());
+                    self.cshake.write(right_encode(if self.is_xof { 0 } else { self.want_bits_len as u64 }).as_slice());
+                    self.cshake.checksum(&mut self.digest);
+                    
+                    self.reset_state();
+                    self.is_checked = true;
+                }
+                
+                digest.clear();
+                digest.extend(self.digest.iter());
+            }
+        
+            fn reset(&mut self) {
+                self.reset_state();
+                self.digest.clear();
+                self.is_checked = false;
+            }
+        }
+        
+        impl DigestXOF for $Type0 {
+            fn set_digest_len(&mut self, bits_len: usize) {
+                self.want_bits_len = bits_len;
+                self.reset();
+            }
+        }
+    };
+}
+
+impl_parallel_hash!(ParallelHash128, CShake128, Shake128, 128);
+impl_parallel_hash!(ParallelHash256, CShake256, Shake256, 256);
@@ -130,16 +130,30 @@ macro_rules! impl_sha3sub {
                 }
             }
             
+            /// the one-shot digest of the `data`
+            pub fn digest(data: &[u8]) -> Vec<u8> {
+                let (mut h, mut d) = (Self::new(), Vec::with_capacity($BITS_LEN >> 3));
+                h.write(data);
+                h.checksum(&mut d);
+                d
+            }
+            
             /// only the least bit effective
             pub fn write_bit(&mut self, bit: u8) {
                 let mut data = [0u8;1];
                 data[0] = bit;
-                self.sponge.write_to_buf(data.as_ref(), 1);
+                self.sponge.absorb(data.as_ref(), 1);
                 
                 self.is_checked = false;
             }
         }
         
+        impl Default for $Type0 {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        
         impl Digest for $Type0 {
             fn block_size(&self) -> Option<usize> {
                 Some((1600 - ($BITS_LEN << 1)) >> 3)
@@ -150,7 +164,7 @@ macro_rules! impl_sha3sub {
             }
         
             fn write(&mut self, data: &[u8]) {
-                self.sponge.write_to_buf(data, data.len() << 3);
+                self.sponge.absorb(data, data.len() << 3);
                 
                 self.is_checked = false;
             }
@@ -159,10 +173,10 @@ macro_rules! impl_sha3sub {
                 if !self.is_checked {
                     const SUFFIX_BITS_LEN: usize = $SUFFIX_LEN;
                     const SUFFIX: [u8;1] = [$SUFFIX];
-                    self.sponge.write_to_buf(&SUFFIX, SUFFIX_BITS_LEN);
-                    self.sponge.sponge_buf(self.bits_len(), &mut self.digest);
+                    self.sponge.absorb(&SUFFIX, SUFFIX_BITS_LEN);
+                    self.sponge.squeeze(self.bits_len(), &mut self.digest);
                     
-                    self.sponge.clear_buf();
+                    self.sponge.reset();
                     self.is_checked = true;
                 }
                 
@@ -171,7 +185,7 @@ macro_rules! impl_sha3sub {
             }
         
             fn reset(&mut self) {
-                self.sponge.clear_buf();
+                self.sponge.reset();
                 self.digest.clear();
                 self.is_checked = false;
             }
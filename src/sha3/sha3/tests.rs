@@ -1,8 +1,8 @@
 //! 
 //! https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
 
-use crate::{SHA3, Digest, DigestXOF};
-use crate::sha3::Shake128;
+use crate::{SHA3, Digest, DigestXOF, CryptoErrorKind};
+use crate::sha3::{Shake128, CShake128, CShake256, KMAC128, KMAC256, ParallelHash128, ParallelHash256, KangarooTwelve, XofBuilder};
 
 fn cvt_bytes_to_str(b: &[u8]) -> String {
     let mut s= String::new();
@@ -150,4 +150,214 @@ fn shake128() {
     });
 }
 
+#[test]
+fn cshake() {
+    // SP 800-185 cSHAKE samples
+    let mut digest = Vec::new();
+    let mut cshake = CShake128::new(256, b"", b"Email Signature");
+    cshake.write(&[0, 1, 2, 3]);
+    cshake.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "C1C36925B6409A04F1B504FCBCA9D82B4017277CB5ED2B2065FC1D3814D5AAF5");
+    cshake.write(&[0, 1, 2, 3]);
+    cshake.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "C1C36925B6409A04F1B504FCBCA9D82B4017277CB5ED2B2065FC1D3814D5AAF5");
+    
+    let mut cshake = CShake256::new(512, b"", b"Email Signature");
+    cshake.write((0..200u8).collect::<Vec<_>>().as_slice());
+    cshake.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "07DC27B11E51FBAC75BC7B3C1D983E8B4B85FB1DEFAF218912AC86430273091727F42B17ED1DF63E8EC118F04B23633C1DFB1574C8FB55CB45DA8E25AFB092BB");
+    
+    // the empty N and S is the SHAKE
+    let (mut cshake, mut shake) = (CShake128::new(256, b"", b""), Shake128::new(256));
+    cshake.write(b"abc");
+    shake.write(b"abc");
+    cshake.checksum(&mut digest);
+    let mut tgt = Vec::new();
+    shake.checksum(&mut tgt);
+    assert_eq!(digest, tgt);
+}
+
+#[test]
+fn kmac() {
+    // SP 800-185 KMAC samples
+    let key = (0x40..0x60u8).collect::<Vec<_>>();
+    let mut digest = Vec::new();
+    let cases = [
+        ("E5780B0D3EA6F7D3A429C5706AA43A00FADBD7D49628839E3187243F456EE14E", ""),
+        ("3B1FBA963CD8B0B59E8C1A6D71888B7143651AF8BA0A7070C0979E2811324AA5", "My Tagged Application"),
+    ];
+    for &(tgt, s) in cases.iter() {
+        let mut kmac = KMAC128::new(key.as_slice(), 256, s.as_bytes());
+        kmac.write(&[0, 1, 2, 3]);
+        kmac.checksum(&mut digest);
+        assert_eq!(cvt_bytes_to_str(digest.as_slice()), tgt, "case: {}", s);
+        kmac.write(&[0, 1]);
+        kmac.write(&[2, 3]);
+        kmac.checksum(&mut digest);
+        assert_eq!(cvt_bytes_to_str(digest.as_slice()), tgt, "case: {}", s);
+    }
+    
+    let mut kmac = KMAC256::new(key.as_slice(), 512, b"My Tagged Application");
+    kmac.write((0..200u8).collect::<Vec<_>>().as_slice());
+    kmac.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "B58618F71F92E1D56C1B8C55DDD7CD188B97B4CA4D99831EB2699A837DA2E4D970FBACFDE50033AEA585F1A2708510C32D07880801BD182898FE476876FC8965");
+    
+    // the KMACXOF output is the prefix of the longer output, and differs from the KMAC
+    let mut xof = KMAC128::new(key.as_slice(), 256, b"").to_xof();
+    xof.write(&[0, 1, 2, 3]);
+    xof.checksum(&mut digest);
+    assert_ne!(cvt_bytes_to_str(digest.as_slice()), cases[0].0);
+    xof.set_digest_len(512);
+    xof.write(&[0, 1, 2, 3]);
+    let mut long = Vec::new();
+    xof.checksum(&mut long);
+    assert_eq!(&long[..32], digest.as_slice());
+}
 
+#[test]
+fn parallel_hash() {
+    // SP 800-185 ParallelHash samples
+    let x = [0u8, 1, 2, 3, 4, 5, 6, 7, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27];
+    let mut digest = Vec::new();
+    let cases = [
+        ("BA8DC1D1D979331D3F813603C67F72609AB5E44B94A0B8F9AF46514454A2B4F5", ""),
+        ("FC484DCB3F84DCEEDC353438151BEE58157D6EFED0445A81F165E495795B7206", "Parallel Data"),
+    ];
+    for &(tgt, s) in cases.iter() {
+        let mut ph = ParallelHash128::new(8, 256, s.as_bytes()).unwrap();
+        for &step in [1usize, 5, 24].iter() {
+            ph.reset();
+            x.chunks(step).for_each(|x| ph.write(x));
+            ph.checksum(&mut digest);
+            assert_eq!(cvt_bytes_to_str(digest.as_slice()), tgt, "case: {}, step: {}", s, step);
+        }
+        // the state is reset after the checksum like the cSHAKE
+        ph.write(x.as_ref());
+        ph.checksum(&mut digest);
+        assert_eq!(cvt_bytes_to_str(digest.as_slice()), tgt, "case: {}, rewrite", s);
+    }
+    let mut ph = ParallelHash256::new(8, 512, b"").unwrap();
+    ph.write(x.as_ref());
+    ph.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "BC1EF124DA34495E948EAD207DD9842235DA432D2BBC54B4C110E64C451105531B7F2A3E0CE055C02805E7C2DE1FB746AF97A1DD01F43B824E31B87612410429");
+    
+    // the last block is shorter than the block size
+    let x = (0..100u8).collect::<Vec<_>>();
+    let mut ph = ParallelHash128::new(12, 256, b"Parallel Data").unwrap();
+    ph.write(x.as_slice());
+    ph.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "F77B8E44A8CD990643CC47C735636C837DFB0BFB759D2A01837308E0202FDCA6");
+    let mut ph = ParallelHash256::new(12, 512, b"Parallel Data").unwrap();
+    ph.write(x.as_slice());
+    ph.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "028656C53FF048F09DFD504E48526BB2AB2B95F50AFFE452BEBBD1E09A940AF816ED9E9828F0F701471E09517EDFA9B42BCF03143F6CDD0ACA315AF76F05090C");
+    let mut ph = ParallelHash128::new(7, 200, b"").unwrap().to_xof();
+    ph.write(x.as_slice());
+    ph.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "5899C6CB712B372FA402513CE45C3FE07C55FFD9AA5908916E");
+    // the output of the XOF doesn't depend on the length
+    ph.set_digest_len(256);
+    ph.write(x.as_slice());
+    ph.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(&digest[..25]), "5899C6CB712B372FA402513CE45C3FE07C55FFD9AA5908916E");
+    
+    let mut ph = ParallelHash128::new(8, 256, b"").unwrap();
+    ph.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "96427C30224408859F95E89E4FA84E1C7A1478DBF2008AC982CE61A77F37A272");
+    
+    assert!(ParallelHash128::new(0, 256, b"").is_err());
+}
+
+#[test]
+fn kangaroo_twelve() {
+    // RFC 9861 5
+    let ptn = |n: usize| (0..n).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let cases = [
+        ("1AC2D450FC3B4205D19DA7BFCA1B37513C0803577AC7167F06FE2CE1F0EF39E5", vec![], vec![]),
+        ("6BF75FA2239198DB4772E36478F8E19B0F371205F6A9A93A273F51DF37122888", ptn(17), vec![]),
+        ("0C315EBCDEDBF61426DE7DCF8FB725D1E74675D7F5327A5067F367B108ECB67C", ptn(17 * 17), vec![]),
+        ("CB552E2EC77D9910701D578B457DDF772C12E322E4EE7FE417F92C758F0D59D0", ptn(17 * 17 * 17), vec![]),
+        ("8701045E22205345FF4DDA05555CBB5C3AF1A771C2B89BAEF37DB43D9998B9FE", ptn(17 * 17 * 17 * 17), vec![]),
+        ("FAB658DB63E94A246188BF7AF69A133045F46EE984C56E3C3328CAAF1AA1A583", vec![], ptn(1)),
+        ("D848C5068CED736F4462159B9867FD4C20B808ACC3D5BC48E0B06BA0A3762EC4", vec![0xff], ptn(41)),
+        ("C389E5009AE57120854C2E8C64670AC01358CF4C1BAF89447A724234DC7CED74", vec![0xff; 3], ptn(41 * 41)),
+        // the `S` of the 8191 bytes `M` is one chunk(with the 1 byte `length_encode(0)`), the 8192 bytes `M` is two chunks
+        ("1B577636F723643E990CC7D6A659837436FD6A103626600EB8301CD1DBE553D6", ptn(8191), vec![]),
+        ("48F256F6772F9EDFB6A8B661EC92DC93B95EBD05A08A17B39AE3490870C926C3", ptn(8192), vec![]),
+        ("3ED12F70FB05DDB58689510AB3E4D23C6C6033849AA01E1D8C220A297FEDCD0B", ptn(8192), ptn(8189)),
+        ("6A7C1B6A5CD0D8C9CA943A4A216CC64604559A2EA45F78570A15253D67BA00AE", ptn(8192), ptn(8190)),
+    ];
+    
+    let mut digest = Vec::new();
+    for (tgt, m, c) in cases.iter() {
+        let mut k12 = KangarooTwelve::new(256, c.as_slice());
+        for &step in [m.len().max(1), 1000, 8192].iter() {
+            k12.reset();
+            m.chunks(step).for_each(|m| k12.write(m));
+            k12.checksum(&mut digest);
+            assert_eq!(cvt_bytes_to_str(digest.as_slice()), *tgt, "m: {}, c: {}, step: {}", m.len(), c.len(), step);
+        }
+    }
+    
+    let mut k12 = KangarooTwelve::new(256, b"");
+    k12.set_digest_len(512);
+    k12.checksum(&mut digest);
+    assert_eq!(cvt_bytes_to_str(digest.as_slice()), "1AC2D450FC3B4205D19DA7BFCA1B37513C0803577AC7167F06FE2CE1F0EF39E54269C056B8C82E48276038B6D292966CC07A3D4645272E31FF38508139EB0A71");
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_hash_threads() {
+    let x = (0..100u8).collect::<Vec<_>>();
+    let (mut digest, mut tgt) = (Vec::new(), Vec::new());
+    let mut ph = ParallelHash128::new(12, 256, b"Parallel Data").unwrap();
+    ph.write(x.as_slice());
+    ph.checksum(&mut tgt);
+    for &threads in [1usize, 3, 16].iter() {
+        ph.reset();
+        ph.write(&x[..5]);
+        ph.write_parallel(&x[5..], threads).unwrap();
+        ph.checksum(&mut digest);
+        assert_eq!(digest, tgt, "threads: {}", threads);
+    }
+    assert!(ph.write_parallel(x.as_slice(), 0).is_err());
+}
+
+#[test]
+fn xof_builder() {
+    let (msg, mut d1, mut d2) = ((0u8..200).collect::<Vec<_>>(), Vec::new(), Vec::new());
+    fn digest<D: Digest>(mut d: D, msg: &[u8], digest: &mut Vec<u8>) {
+        d.write(msg);
+        d.checksum(digest);
+        // the reset digester is the same as the new one
+        let tgt = digest.clone();
+        d.reset();
+        d.write(msg);
+        d.checksum(digest);
+        assert_eq!(*digest, tgt);
+    }
+    
+    let builder = XofBuilder::new().customization(b"Email Signature");
+    digest(builder.build_cshake128(), msg.as_slice(), &mut d1);
+    digest(CShake128::new(256, b"", b"Email Signature"), msg.as_slice(), &mut d2);
+    assert_eq!(d1, d2);
+    digest(builder.clone().digest_bits_len(300).build_cshake256(), msg.as_slice(), &mut d1);
+    digest(CShake256::new(300, b"", b"Email Signature"), msg.as_slice(), &mut d2);
+    assert_eq!(d1, d2);
+    digest(builder.build_kangaroo_twelve(), msg.as_slice(), &mut d1);
+    digest(KangarooTwelve::new(256, b"Email Signature"), msg.as_slice(), &mut d2);
+    assert_eq!(d1, d2);
+    digest(builder.build_shake128(), msg.as_slice(), &mut d1);
+    digest(Shake128::new(256), msg.as_slice(), &mut d2);
+    assert_eq!(d1, d2);
+    
+    assert_eq!(builder.build_kmac128().err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(builder.build_parallel_hash256().err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    let builder = builder.key(&[0x40; 32]).block_size(8).xof(true);
+    digest(builder.build_kmac256().unwrap(), msg.as_slice(), &mut d1);
+    digest(KMAC256::new(&[0x40; 32], 512, b"Email Signature").to_xof(), msg.as_slice(), &mut d2);
+    assert_eq!(d1, d2);
+    digest(builder.build_parallel_hash128().unwrap(), msg.as_slice(), &mut d1);
+    digest(ParallelHash128::new(8, 256, b"Email Signature").unwrap().to_xof(), msg.as_slice(), &mut d2);
+    assert_eq!(d1, d2);
+}
\ No newline at end of file
@@ -58,17 +58,17 @@ macro_rules! impl_shake {
             }
         
             fn write(&mut self, data: &[u8]) {
-                self.sponge.write_to_buf(data, data.len() << 3);
+                self.sponge.absorb(data, data.len() << 3);
                 
                 self.is_checked = false;
             }
         
             fn checksum(&mut self, digest: &mut Vec<u8>) {
                 if !self.is_checked {
-                    self.sponge.write_to_buf(self.suffix.as_ref(), self.suffix_len);
-                    self.sponge.sponge_buf(self.bits_len(), &mut self.digest);
+                    self.sponge.absorb(self.suffix.as_ref(), self.suffix_len);
+                    self.sponge.squeeze(self.bits_len(), &mut self.digest);
                     
-                    self.sponge.clear_buf();
+                    self.sponge.reset();
                     self.is_checked = true;
                 }
                 
@@ -77,7 +77,7 @@ macro_rules! impl_shake {
             }
         
             fn reset(&mut self) {
-                self.sponge.clear_buf();
+                self.sponge.reset();
                 self.digest.clear();
                 self.is_checked = false;
             }
@@ -0,0 +1,33 @@
+//! The string encodings of the SP 800-185 2.3
+
+/// `left_encode(x) = n || x`, where the `x` is the big-endian bytes without the leading zeros and `n` is its length
+pub(crate) fn left_encode(x: u64) -> Vec<u8> {
+    let b = x.to_be_bytes();
+    let i = b.iter().position(|&e| e != 0).unwrap_or(b.len() - 1);
+    let mut v = Vec::with_capacity(9);
+    v.push((b.len() - i) as u8);
+    v.extend_from_slice(&b[i..]);
+    v
+}
+
+/// `right_encode(x) = x || n`
+pub(crate) fn right_encode(x: u64) -> Vec<u8> {
+    let mut v = left_encode(x);
+    v.rotate_left(1);
+    v
+}
+
+/// `encode_string(s) = left_encode(len(s)) || s`, the length is in bits
+pub(crate) fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut v = left_encode((s.len() as u64) << 3);
+    v.extend_from_slice(s);
+    v
+}
+
+/// `bytepad(x, w) = left_encode(w) || x || 0^k`, the length is padded to a multiple of the `w` bytes
+pub(crate) fn bytepad(x: &[u8], w: usize) -> Vec<u8> {
+    let mut v = left_encode(w as u64);
+    v.extend_from_slice(x);
+    v.resize(v.len().div_ceil(w) * w, 0);
+    v
+}
@@ -0,0 +1,153 @@
+//! Feldman's verifiable secret sharing
+//!
+//! The dealer shares the secret by the Shamir's secret sharing over the order $q$ of the base point $G$, and publishes
+//! the commitments $C_j = a_j G$ of the coefficients of the polynomial, the recipient of the share $(i, s_i)$ checks
+//! $s_i G = \sum_j i^j C_j$ without learning the secret. $C_0 = s G$ is the public key of the secret, so the
+//! commitments hide the secret only computationally(the discrete logarithm).
+
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::elliptic::{EllipticCurve, PublicKey};
+use crate::sharing::{Shamir, Share};
+
+fn vss_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid Feldman VSS commitments: {}", msg)))
+}
+
+fn to_fixed_be_bytes(x: &BigInt, len: usize) -> Vec<u8> {
+    let b = x.to_be_bytes();
+    let mut buf = vec![0u8; len.saturating_sub(b.len())];
+    buf.extend_from_slice(b.as_slice());
+    buf
+}
+
+/// The published commitments $(C_0, ..., C_{k-1})$ of the polynomial coefficients
+#[derive(Clone)]
+pub struct FeldmanCommitments {
+    points: Vec<PublicKey>,
+}
+
+impl FeldmanCommitments {
+    /// the threshold of the sharing, i.e. the number of the commitments
+    pub fn threshold(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn points(&self) -> &[PublicKey] {
+        self.points.as_slice()
+    }
+
+    /// the commitment $C_0 = s G$ of the secret
+    pub fn public_key(&self) -> &PublicKey {
+        &self.points[0]
+    }
+
+    /// the SEC1 uncompressed points `0x04 || x || y` of the commitments
+    pub fn to_bytes<C: EllipticCurve>(&self, curve: &C) -> Vec<u8> {
+        let f_len = (curve.curve_params().field_bits_size() + 7) >> 3;
+        let mut buf = Vec::with_capacity(self.points.len() * (1 + (f_len << 1)));
+        for p in self.points.iter() {
+            buf.push(0x04);
+            buf.append(&mut to_fixed_be_bytes(&p.qx, f_len));
+            buf.append(&mut to_fixed_be_bytes(&p.qy, f_len));
+        }
+        buf
+    }
+
+    /// parse the `to_bytes`, every commitment is validated as the public point of the `curve`
+    pub fn from_bytes<C: EllipticCurve>(curve: &C, bytes: &[u8]) -> Result<Self, CryptoError> {
+        let f_len = (curve.curve_params().field_bits_size() + 7) >> 3;
+        let p_len = 1 + (f_len << 1);
+        if bytes.is_empty() || !bytes.len().is_multiple_of(p_len) {
+            return vss_err("the length is not the multiple of the point length");
+        }
+
+        let mut points = Vec::with_capacity(bytes.len() / p_len);
+        for p in bytes.chunks_exact(p_len) {
+            if p[0] != 0x04 {
+                return vss_err("the point is not uncompressed");
+            }
+            let (x, y) = (BigInt::from_be_bytes(&p[1..(1 + f_len)]), BigInt::from_be_bytes(&p[(1 + f_len)..]));
+            crate::dh::validate_ec_public_point(curve, &x, &y)?;
+            points.push(PublicKey::new_uncheck(&x, &y));
+        }
+
+        Ok(Self { points })
+    }
+}
+
+pub struct FeldmanVss<C: EllipticCurve> {
+    curve: C,
+    shamir: Shamir,
+}
+
+impl<C: EllipticCurve> FeldmanVss<C> {
+    /// `threshold` shares of the `shares` shares recover the secret, `1 <= threshold <= shares`
+    pub fn new(curve: C, threshold: usize, shares: usize) -> Result<Self, CryptoError> {
+        let shamir = Shamir::new(curve.curve_params().base_point_order(), threshold, shares)?;
+        Ok(Self { curve, shamir })
+    }
+
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// the Shamir's secret sharing over the order of the base point
+    pub fn shamir(&self) -> &Shamir {
+        &self.shamir
+    }
+
+    /// split the `secret` in the range `[1, q)` to the shares, and commit the polynomial
+    pub fn split<R: IterSource<u32>>(&self, secret: &BigInt, rd: &mut R) -> Result<(Vec<Share>, FeldmanCommitments), CryptoError> {
+        if secret.signnum() == Some(0) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The Feldman VSS secret must be great than 0"));
+        }
+
+        let (coefficients, shares) = self.shamir.split_polynomial(secret, rd)?;
+        let points = coefficients.iter().map(|a| {
+            let (x, y) = self.curve.scalar_base_point(a.as_ref());
+            PublicKey { qx: x, qy: y }
+        }).collect();
+
+        Ok((shares, FeldmanCommitments { points }))
+    }
+
+    /// verify the `share` against the `commitments`, the `VerificationFailed` is returned if the share is not
+    /// consistent with the commitments.
+    pub fn verify_share(&self, share: &Share, commitments: &FeldmanCommitments) -> Result<(), CryptoError> {
+        let q = self.shamir.modulus();
+        if commitments.threshold() != self.shamir.threshold() {
+            return vss_err("the number of the commitments is not the threshold");
+        } else if share.index() == 0 || (share.index() as usize) > self.shamir.shares() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Wrong share index: {}", share.index())));
+        } else if share.value().is_nan() || share.value().is_negative() || share.value() >= q {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The share value must be in the range [0, q)"));
+        }
+
+        // $\sum_j i^j C_j$ by the Horner's method
+        let i = BigInt::from(share.index());
+        let mut points = commitments.points.iter().rev();
+        let last = points.next().unwrap();
+        let (mut x, mut y) = (last.qx.deep_clone(), last.qy.deep_clone());
+        for c in points {
+            let (tx, ty) = self.curve.scalar(&x, &y, i.as_ref());
+            let (tx, ty) = self.curve.add(&tx, &ty, &c.qx, &c.qy);
+            x = tx;
+            y = ty;
+        }
+
+        let (sx, sy) = self.curve.scalar_base_point(share.value().as_ref());
+        if sx == x && sy == y {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed,
+                format!("The share {} is not consistent with the Feldman VSS commitments", share.index())))
+        }
+    }
+
+    /// recover the secret from the first `threshold` shares
+    pub fn combine(&self, shares: &[Share]) -> Result<BigInt, CryptoError> {
+        self.shamir.combine(shares)
+    }
+}
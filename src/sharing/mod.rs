@@ -0,0 +1,22 @@
+//! The threshold secret sharing
+//!
+//! The `Shamir` splits the secret over a prime field, the `FeldmanVss` adds the elliptic curve commitments of the
+//! polynomial, so the share recipients can verify their shares against the dealer's published commitments. The
+//! `Slip39` is the SLIP-39 group sharing of the master secret for the mnemonic shares with the `kdf` feature.
+
+mod shamir;
+pub use shamir::{Shamir, Share};
+
+mod feldman;
+pub use feldman::{FeldmanVss, FeldmanCommitments};
+
+#[cfg(feature = "kdf")]
+mod slip39;
+#[cfg(feature = "kdf")]
+pub use slip39::{Slip39, Slip39Share, Slip39Wordlist, SLIP39_WORDLIST_LEN, SLIP39_MAX_SHARE_COUNT, SLIP39_MIN_SECRET_LEN};
+
+#[cfg(test)]
+mod sharing_test;
+
+#[cfg(all(test, feature = "kdf"))]
+mod slip39_test;
\ No newline at end of file
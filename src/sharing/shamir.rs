@@ -0,0 +1,157 @@
+//! Shamir's secret sharing
+//!
+//! The secret $s$ is the constant term of the random polynomial $f(x) = s + a_1 x + ... + a_{k-1} x^{k-1} \mod q$,
+//! the share $i$ is $(i, f(i))$ for $i$ in $[1, n]$, any `k` shares recover the $s = f(0)$ by the Lagrange
+//! interpolation, and less than `k` shares reveal nothing about the $s$. The modulus $q$ must be a prime.
+
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::numeric::{mod_inverse, mod_reduce};
+
+#[derive(Clone, Debug)]
+pub struct Share {
+    index: u32,
+    value: BigInt,
+}
+
+impl Share {
+    pub fn new(index: u32, value: &BigInt) -> Self {
+        Self { index, value: value.deep_clone() }
+    }
+
+    /// the x coordinate $i$ of the share, it's great than 0
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// the y coordinate $f(i)$ of the share
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+}
+
+#[derive(Clone)]
+pub struct Shamir {
+    modulus: BigInt,
+    threshold: usize,
+    shares: usize,
+}
+
+impl Shamir {
+    /// `threshold` shares of the `shares` shares recover the secret, `1 <= threshold <= shares < modulus`
+    pub fn new(modulus: &BigInt, threshold: usize, shares: usize) -> Result<Self, CryptoError> {
+        if modulus.is_nan() || modulus <= &BigInt::from(2u32) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The modulus must be a prime great than 2"));
+        } else if threshold == 0 || threshold > shares {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong threshold: {}, it must be in the range [1, {}]", threshold, shares)));
+        } else if shares > (u32::MAX as usize) || &BigInt::from(shares as u32) >= modulus {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong number of the shares: {}, it must be less than the modulus and 2^32", shares)));
+        }
+
+        Ok(Self { modulus: modulus.deep_clone(), threshold, shares })
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.modulus
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn shares(&self) -> usize {
+        self.shares
+    }
+
+    /// split the `secret` in the range `[0, modulus)` to the shares with the indices `1..=shares`
+    pub fn split<R: IterSource<u32>>(&self, secret: &BigInt, rd: &mut R) -> Result<Vec<Share>, CryptoError> {
+        self.split_polynomial(secret, rd).map(|(_, shares)| shares)
+    }
+
+    /// the coefficients $(s, a_1, ..., a_{k-1})$ of the polynomial and the shares, the $a_i$ are in the range
+    /// `[1, modulus)`
+    pub(super) fn split_polynomial<R: IterSource<u32>>(&self, secret: &BigInt, rd: &mut R) -> Result<(Vec<BigInt>, Vec<Share>), CryptoError> {
+        if secret.is_nan() || secret.is_negative() || secret >= &self.modulus {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The secret must be in the range [0, modulus)"));
+        }
+
+        let mut coefficients = Vec::with_capacity(self.threshold);
+        coefficients.push(secret.deep_clone());
+        (1..self.threshold).for_each(|_| coefficients.push(random_scalar(&self.modulus, rd)));
+
+        let mut shares = Vec::with_capacity(self.shares);
+        for i in 1..=(self.shares as u32) {
+            shares.push(Share { index: i, value: self.evaluate(coefficients.as_slice(), i)? });
+        }
+
+        Ok((coefficients, shares))
+    }
+
+    /// $f(x)$ by the Horner's method
+    fn evaluate(&self, coefficients: &[BigInt], x: u32) -> Result<BigInt, CryptoError> {
+        let x = BigInt::from(x);
+        let mut y = BigInt::from(0u32);
+        for a in coefficients.iter().rev() {
+            y = mod_reduce(&(y * x.clone() + a.clone()), &self.modulus)?;
+        }
+        Ok(y)
+    }
+
+    /// recover the secret from the first `threshold` shares, the indices of the shares must be distinct
+    pub fn combine(&self, shares: &[Share]) -> Result<BigInt, CryptoError> {
+        if shares.len() < self.threshold {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Need at least {} shares to recover the secret, but {} shares are given", self.threshold, shares.len())));
+        }
+
+        let shares = &shares[..self.threshold];
+        for (j, s) in shares.iter().enumerate() {
+            if s.index == 0 || shares[..j].iter().any(|x| x.index == s.index) {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("Wrong share index: {}, the indices must be distinct and great than 0", s.index)));
+            } else if s.value.is_nan() || s.value.is_negative() || s.value >= self.modulus {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The share value must be in the range [0, modulus)"));
+            }
+        }
+
+        // $s = \sum_j y_j \prod_{m \ne j} x_m / (x_m - x_j)$
+        let mut secret = BigInt::from(0u32);
+        for (j, sj) in shares.iter().enumerate() {
+            let (mut num, mut den) = (BigInt::from(1u32), BigInt::from(1u32));
+            for (m, sm) in shares.iter().enumerate() {
+                if m != j {
+                    num = mod_reduce(&(num * BigInt::from(sm.index)), &self.modulus)?;
+                    den = mod_reduce(&(den * (BigInt::from(sm.index) - BigInt::from(sj.index))), &self.modulus)?;
+                }
+            }
+
+            let l = mod_reduce(&(num * mod_inverse(&den, &self.modulus)?), &self.modulus)?;
+            secret = mod_reduce(&(secret + l * sj.value.clone()), &self.modulus)?;
+        }
+
+        Ok(secret)
+    }
+}
+
+/// the uniform random number in the range `[1, modulus)` by the rejection sampling
+pub(super) fn random_scalar<R: IterSource<u32>>(modulus: &BigInt, rd: &mut R) -> BigInt {
+    let bits_len = modulus.bits_len();
+    let b_len = (bits_len + 7) >> 3;
+    let mut buf = Vec::with_capacity(b_len + 3);
+    loop {
+        buf.clear();
+        rd.iter_mut().take(b_len.div_ceil(4)).for_each(|x| buf.extend_from_slice(x.to_be_bytes().as_ref()));
+        buf.truncate(b_len);
+        if bits_len & 7 != 0 {
+            buf[0] &= (1u8 << (bits_len & 7)) - 1;
+        }
+
+        let k = BigInt::from_be_bytes(buf.as_slice());
+        if k.signnum() != Some(0) && &k < modulus {
+            return k;
+        }
+    }
+}
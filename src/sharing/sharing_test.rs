@@ -0,0 +1,67 @@
+use rmath::bigint::BigInt;
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::CryptoErrorKind;
+use crate::elliptic::{CurveP256, EllipticCurve};
+use crate::sharing::{Shamir, Share, FeldmanVss, FeldmanCommitments};
+
+#[test]
+fn shamir() {
+    // f(x) = 1234 + 166x + 94x^2 mod 1613
+    let shamir = Shamir::new(&BigInt::from(1613u32), 3, 6).unwrap();
+    let points = [(1u32, 1494u32), (2, 329), (3, 965), (4, 176), (5, 1188), (6, 775)];
+    let shares = points.iter().map(|&(i, y)| Share::new(i, &BigInt::from(y))).collect::<Vec<_>>();
+    for i in 0..4 {
+        assert_eq!(shamir.combine(&shares[i..]).unwrap(), BigInt::from(1234u32), "case: {}", i);
+    }
+    let picked = [shares[5].clone(), shares[0].clone(), shares[3].clone()];
+    assert_eq!(shamir.combine(picked.as_ref()).unwrap(), BigInt::from(1234u32));
+    assert!(shamir.combine(&shares[..2]).is_err());
+    assert!(shamir.combine(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]).is_err());
+
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let q = BigInt::from_be_bytes(&[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    let secret = BigInt::from_be_bytes(b"0123456789abcde");
+    let shamir = Shamir::new(&q, 2, 5).unwrap();
+    let shares = shamir.split(&secret, &mut rd).unwrap();
+    assert_eq!(shares.iter().map(|s| s.index()).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(shamir.combine(&shares[3..]).unwrap(), secret);
+    assert_ne!(shamir.combine(&[shares[0].clone(), Share::new(2, &BigInt::from(1u32))]).unwrap(), secret);
+    assert!(shamir.split(&q, &mut rd).is_err());
+
+    assert!(Shamir::new(&q, 0, 5).is_err());
+    assert!(Shamir::new(&q, 6, 5).is_err());
+    assert!(Shamir::new(&BigInt::from(5u32), 2, 5).is_err());
+}
+
+#[test]
+fn feldman_vss() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let curve = CurveP256::new().unwrap();
+    let key = curve.curve_params().generate_key(&mut rd).unwrap();
+    let vss = FeldmanVss::new(curve, 3, 5).unwrap();
+    let (shares, commitments) = vss.split(&key.d.get(), &mut rd).unwrap();
+    assert_eq!(commitments.threshold(), 3);
+    assert!(commitments.public_key().qx == key.public_key().qx && commitments.public_key().qy == key.public_key().qy);
+
+    let published = commitments.to_bytes(vss.curve());
+    assert_eq!(published.len(), 3 * 65);
+    let commitments = FeldmanCommitments::from_bytes(vss.curve(), published.as_slice()).unwrap();
+    for share in shares.iter() {
+        assert!(vss.verify_share(share, &commitments).is_ok(), "case: {}", share.index());
+    }
+    assert_eq!(vss.combine(&shares[2..]).unwrap(), key.d.get());
+    assert_eq!(vss.combine(&[shares[4].clone(), shares[1].clone(), shares[0].clone()]).unwrap(), key.d.get());
+
+    let forged = Share::new(2, &(shares[1].value().clone() + BigInt::from(1u32)));
+    assert_eq!(vss.verify_share(&forged, &commitments).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    let swapped = Share::new(3, shares[1].value());
+    assert_eq!(vss.verify_share(&swapped, &commitments).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(vss.verify_share(&Share::new(6, shares[1].value()), &commitments).is_err());
+
+    let mut tampered = published.clone();
+    tampered[70] ^= 1;
+    assert!(FeldmanCommitments::from_bytes(vss.curve(), tampered.as_slice()).is_err());
+    assert!(FeldmanCommitments::from_bytes(vss.curve(), &published[..130]).map(|c| vss.verify_share(&shares[0], &c)).unwrap().is_err());
+    assert!(FeldmanCommitments::from_bytes(vss.curve(), &published[1..]).is_err());
+    assert!(vss.split(&BigInt::from(0u32), &mut rd).is_err());
+}
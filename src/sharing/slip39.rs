@@ -0,0 +1,495 @@
+//! SLIP-39 Shamir's secret sharing for the mnemonic codes
+//! https://github.com/satoshilabs/slips/blob/master/slip-0039.md
+//!
+//! The master secret is encrypted to the EMS by the 4 rounds Feistel network with the PBKDF2-HMAC-SHA256 round
+//! function, the EMS is split to the group shares, and every group share is split to the member shares again. The
+//! sharing is over the GF(256) with the Rijndael polynomial $x^8 + x^4 + x^3 + x + 1$, the shared secret is at the
+//! $x = 255$ and the digest share `HMAC-SHA256(R, S)[..4] || R` is at the $x = 254$.
+//!
+//! The share is encoded as the 10 bits words with the RS1024 checksum, the `Slip39Share::to_indices` are the word
+//! indices of the mnemonic. The official 1024 words list isn't shipped by the crate, the caller loads it to the
+//! `Slip39Wordlist` to convert between the share and the mnemonic words.
+
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind, Digest, HMAC};
+use crate::kdf::PBKDF2;
+use crate::sha::SHA256;
+
+/// the number of the words in the SLIP-39 words list
+pub const SLIP39_WORDLIST_LEN: usize = 1024;
+/// the maximum number of the groups and the members of the group
+pub const SLIP39_MAX_SHARE_COUNT: usize = 16;
+/// the minimum length of the master secret in bytes
+pub const SLIP39_MIN_SECRET_LEN: usize = 16;
+
+const RADIX_BITS: usize = 10;
+const CHECKSUM_LEN_WORDS: usize = 3;
+/// the identifier, the extendable flag, the iteration exponent, and the group/member parameters
+const HEADER_LEN_WORDS: usize = 4;
+const MIN_MNEMONIC_LEN_WORDS: usize = HEADER_LEN_WORDS + CHECKSUM_LEN_WORDS + (SLIP39_MIN_SECRET_LEN * 8).div_ceil(RADIX_BITS);
+const DIGEST_LEN: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const ROUND_COUNT: u8 = 4;
+const BASE_ITERATION_COUNT: usize = 10000;
+const MAX_ITERATION_EXPONENT: u8 = 15;
+
+const CUSTOMIZATION: &[u8] = b"shamir";
+const CUSTOMIZATION_EXTENDABLE: &[u8] = b"shamir_extendable";
+const RS1024_GEN: [u32; 10] = [
+    0x00E0E040, 0x01C1C080, 0x03838100, 0x07070200, 0x0E0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48, 0x21B1F890, 0x03F3F120,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Slip39Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+impl Slip39Share {
+    /// the random 15 bits identifier of the master secret
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    /// the salt of the encryption doesn't depend on the identifier if it's true
+    pub fn extendable(&self) -> bool {
+        self.extendable
+    }
+
+    /// the iteration count of the round function is `2500 << iteration_exponent`
+    pub fn iteration_exponent(&self) -> u8 {
+        self.iteration_exponent
+    }
+
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    /// the share value of the member
+    pub fn value(&self) -> &[u8] {
+        self.value.as_slice()
+    }
+
+    /// the 10 bits word indices of the share with the 3 words RS1024 checksum
+    pub fn to_indices(&self) -> Vec<u16> {
+        let header = ((self.identifier as u64) << 25) | ((self.extendable as u64) << 24) | ((self.iteration_exponent as u64) << 20)
+            | ((self.group_index as u64) << 16) | (((self.group_threshold - 1) as u64) << 12) | (((self.group_count - 1) as u64) << 8)
+            | ((self.member_index as u64) << 4) | ((self.member_threshold - 1) as u64);
+        let n = (self.value.len() * 8).div_ceil(RADIX_BITS);
+        let mut words = Vec::with_capacity(HEADER_LEN_WORDS + n + CHECKSUM_LEN_WORDS);
+        (0..HEADER_LEN_WORDS).rev().for_each(|i| words.push(((header >> (i * RADIX_BITS)) & 0x3ff) as u16));
+
+        // the value is left padded by the zero bits to the multiple of the 10 bits
+        let (mut acc, mut acc_bits) = (0u32, n * RADIX_BITS - self.value.len() * 8);
+        for &b in self.value.iter() {
+            acc = (acc << 8) | (b as u32);
+            acc_bits += 8;
+            if acc_bits >= RADIX_BITS {
+                acc_bits -= RADIX_BITS;
+                words.push(((acc >> acc_bits) & 0x3ff) as u16);
+                acc &= (1 << acc_bits) - 1;
+            }
+        }
+
+        let checksum = rs1024_polymod(customization(self.extendable), words.iter().copied().chain([0u16; CHECKSUM_LEN_WORDS])) ^ 1;
+        (0..CHECKSUM_LEN_WORDS).rev().for_each(|i| words.push(((checksum >> (i * RADIX_BITS)) & 0x3ff) as u16));
+        words
+    }
+
+    /// parse the share from the word indices of the mnemonic, and verify the RS1024 checksum
+    pub fn from_indices(words: &[u16]) -> Result<Self, CryptoError> {
+        if words.len() < MIN_MNEMONIC_LEN_WORDS {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Invalid SLIP-39 mnemonic length: {}, it must be at least {} words", words.len(), MIN_MNEMONIC_LEN_WORDS)));
+        } else if let Some(w) = words.iter().find(|&&w| w as usize >= SLIP39_WORDLIST_LEN) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid SLIP-39 word index: {}", w)));
+        }
+
+        let value_words = &words[HEADER_LEN_WORDS..(words.len() - CHECKSUM_LEN_WORDS)];
+        let padding = (value_words.len() * RADIX_BITS) % 16;
+        if padding > 8 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Invalid SLIP-39 mnemonic length: {}", words.len())));
+        }
+
+        let header = words[..HEADER_LEN_WORDS].iter().fold(0u64, |h, &w| (h << RADIX_BITS) | (w as u64));
+        let extendable = (header >> 24) & 1 == 1;
+        if rs1024_polymod(customization(extendable), words.iter().copied()) != 1 {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid SLIP-39 mnemonic checksum"));
+        }
+
+        let (mut acc, mut acc_bits) = (0u32, 0usize);
+        let mut value = Vec::with_capacity((value_words.len() * RADIX_BITS - padding) / 8);
+        for (i, &w) in value_words.iter().enumerate() {
+            acc = (acc << RADIX_BITS) | (w as u32);
+            acc_bits += RADIX_BITS;
+            if i == 0 {
+                acc_bits -= padding;
+                if (acc >> acc_bits) != 0 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid SLIP-39 mnemonic padding"));
+                }
+            }
+            while acc_bits >= 8 {
+                acc_bits -= 8;
+                value.push((acc >> acc_bits) as u8);
+            }
+            acc &= (1 << acc_bits) - 1;
+        }
+
+        let field = |shift: usize| ((header >> shift) & 0xf) as u8;
+        let share = Self {
+            identifier: (header >> 25) as u16,
+            extendable,
+            iteration_exponent: field(20),
+            group_index: field(16),
+            group_threshold: field(12) + 1,
+            group_count: field(8) + 1,
+            member_index: field(4),
+            member_threshold: field(0) + 1,
+            value,
+        };
+        if share.group_threshold > share.group_count {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                "Invalid SLIP-39 mnemonic, the group threshold is great than the group count"));
+        }
+        Ok(share)
+    }
+}
+
+/// The SLIP-39 words list, the words are sorted and their first 4 letters are unique
+#[derive(Clone)]
+pub struct Slip39Wordlist {
+    words: Vec<String>,
+}
+
+impl Slip39Wordlist {
+    /// the `words` are the 1024 lowercase words of the official words list in order
+    pub fn new(words: &[&str]) -> Result<Self, CryptoError> {
+        if words.len() != SLIP39_WORDLIST_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The SLIP-39 words list must have {} words, but {} words are given", SLIP39_WORDLIST_LEN, words.len())));
+        } else if let Some(w) = words.iter().find(|w| w.len() < 4 || w.len() > 8 || !w.bytes().all(|c| c.is_ascii_lowercase())) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Invalid SLIP-39 word: {}, it must be 4 to 8 lowercase letters", w)));
+        } else if let Some(w) = words.windows(2).find(|w| w[0] >= w[1] || w[0][..4] == w[1][..4]) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The SLIP-39 words must be sorted with the unique first 4 letters: {}, {}", w[0], w[1])));
+        }
+
+        Ok(Self { words: words.iter().map(|w| w.to_string()).collect() })
+    }
+
+    pub fn word(&self, index: u16) -> Option<&str> {
+        self.words.get(index as usize).map(|w| w.as_str())
+    }
+
+    pub fn index_of(&self, word: &str) -> Option<u16> {
+        self.words.binary_search_by(|w| w.as_str().cmp(word)).ok().map(|i| i as u16)
+    }
+
+    /// the mnemonic words of the share separated by the space
+    pub fn to_mnemonic(&self, share: &Slip39Share) -> String {
+        share.to_indices().into_iter().map(|i| self.words[i as usize].as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn from_mnemonic(&self, mnemonic: &str) -> Result<Slip39Share, CryptoError> {
+        let mut words = Vec::new();
+        for w in mnemonic.split_whitespace() {
+            match self.index_of(w.to_ascii_lowercase().as_str()) {
+                Some(i) => words.push(i),
+                None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid SLIP-39 mnemonic word: {}", w))),
+            }
+        }
+        Slip39Share::from_indices(words.as_slice())
+    }
+}
+
+#[derive(Clone)]
+pub struct Slip39 {
+    group_threshold: usize,
+    groups: Vec<(usize, usize)>,
+    iteration_exponent: u8,
+    extendable: bool,
+}
+
+impl Slip39 {
+    /// `group_threshold` groups of the `groups` recover the master secret, the `groups` are the `(member threshold,
+    /// member count)` of every group, the `iteration_exponent` must be less than 16. The member count must be 1 if
+    /// the member threshold is 1.
+    pub fn new(group_threshold: usize, groups: &[(usize, usize)], iteration_exponent: u8, extendable: bool) -> Result<Self, CryptoError> {
+        if groups.is_empty() || groups.len() > SLIP39_MAX_SHARE_COUNT {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong number of the groups: {}, it must be in the range [1, {}]", groups.len(), SLIP39_MAX_SHARE_COUNT)));
+        } else if group_threshold == 0 || group_threshold > groups.len() {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong group threshold: {}, it must be in the range [1, {}]", group_threshold, groups.len())));
+        } else if iteration_exponent > MAX_ITERATION_EXPONENT {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong iteration exponent: {}, it must be less than or equal to {}", iteration_exponent, MAX_ITERATION_EXPONENT)));
+        }
+
+        for &(threshold, count) in groups.iter() {
+            if threshold == 0 || threshold > count || count > SLIP39_MAX_SHARE_COUNT {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("Wrong member threshold {} of the {} members, it must be in the range [1, members] and the members must be at most {}",
+                        threshold, count, SLIP39_MAX_SHARE_COUNT)));
+            } else if threshold == 1 && count > 1 {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    "The multiple member shares with the member threshold 1 aren't allowed, use the 1-of-1 member sharing instead"));
+            }
+        }
+
+        Ok(Self { group_threshold, groups: groups.to_vec(), iteration_exponent, extendable })
+    }
+
+    pub fn group_threshold(&self) -> usize {
+        self.group_threshold
+    }
+
+    pub fn groups(&self) -> &[(usize, usize)] {
+        self.groups.as_slice()
+    }
+
+    /// split the `master_secret` to the member shares of every group, the `master_secret` must be the even number of
+    /// bytes and at least 16 bytes, the `passphrase` must be the printable ASCII characters.
+    pub fn split<R: IterSource<u32>>(&self, master_secret: &[u8], passphrase: &[u8], rd: &mut R) -> Result<Vec<Vec<Slip39Share>>, CryptoError> {
+        if master_secret.len() < SLIP39_MIN_SECRET_LEN || master_secret.len() & 1 != 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong master secret length: {}, it must be the even number and at least {}", master_secret.len(), SLIP39_MIN_SECRET_LEN)));
+        }
+
+        let identifier = (rd.iter_mut().next().unwrap_or(0) & 0x7fff) as u16;
+        let ems = feistel(master_secret, passphrase, self.iteration_exponent, identifier, self.extendable, false)?;
+        let group_shares = split_secret(self.group_threshold, self.groups.len(), ems.as_slice(), rd)?;
+
+        let mut shares = Vec::with_capacity(self.groups.len());
+        for (&(threshold, count), (group_index, group_share)) in self.groups.iter().zip(group_shares) {
+            let members = split_secret(threshold, count, group_share.as_slice(), rd)?;
+            shares.push(members.into_iter().map(|(member_index, value)| Slip39Share {
+                identifier,
+                extendable: self.extendable,
+                iteration_exponent: self.iteration_exponent,
+                group_index,
+                group_threshold: self.group_threshold as u8,
+                group_count: self.groups.len() as u8,
+                member_index,
+                member_threshold: threshold as u8,
+                value,
+            }).collect());
+        }
+
+        Ok(shares)
+    }
+
+    /// recover the master secret from the shares of exactly `group_threshold` groups, every group must have exactly
+    /// the member threshold shares. The wrong `passphrase` recovers a different master secret without the error.
+    pub fn combine(shares: &[Slip39Share], passphrase: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let first = match shares.first() {
+            Some(s) => s,
+            None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "No SLIP-39 share is given")),
+        };
+        if shares.iter().any(|s| s.identifier != first.identifier || s.extendable != first.extendable
+            || s.iteration_exponent != first.iteration_exponent || s.group_threshold != first.group_threshold
+            || s.group_count != first.group_count || s.value.len() != first.value.len()) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The SLIP-39 shares don't belong to the same master secret"));
+        }
+
+        let mut groups: Vec<(u8, Vec<&Slip39Share>)> = Vec::new();
+        for s in shares.iter() {
+            match groups.iter_mut().find(|(i, _)| *i == s.group_index) {
+                Some((_, g)) => g.push(s),
+                None => groups.push((s.group_index, vec![s])),
+            }
+        }
+        if groups.len() != first.group_threshold as usize {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong number of the SLIP-39 groups: {}, it must be the group threshold {}", groups.len(), first.group_threshold)));
+        }
+
+        let mut group_shares = Vec::with_capacity(groups.len());
+        for (group_index, members) in groups.iter() {
+            let threshold = members[0].member_threshold;
+            if members.iter().any(|m| m.member_threshold != threshold) || members.len() != threshold as usize {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("Wrong number of the SLIP-39 shares in the group {}, it must be the member threshold {}", group_index, threshold)));
+            } else if members.iter().enumerate().any(|(j, m)| members[..j].iter().any(|x| x.member_index == m.member_index)) {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                    format!("The member indices of the SLIP-39 group {} must be distinct", group_index)));
+            }
+
+            let points = members.iter().map(|m| (m.member_index, m.value.as_slice())).collect::<Vec<_>>();
+            group_shares.push((*group_index, recover_secret(threshold as usize, points.as_slice())?));
+        }
+
+        let points = group_shares.iter().map(|(i, v)| (*i, v.as_slice())).collect::<Vec<_>>();
+        let ems = recover_secret(first.group_threshold as usize, points.as_slice())?;
+        feistel(ems.as_slice(), passphrase, first.iteration_exponent, first.identifier, first.extendable, true)
+    }
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {CUSTOMIZATION_EXTENDABLE} else {CUSTOMIZATION}
+}
+
+fn rs1024_polymod<I: Iterator<Item=u16>>(cs: &[u8], words: I) -> u32 {
+    let mut chk = 1u32;
+    for v in cs.iter().map(|&c| c as u32).chain(words.map(|w| w as u32)) {
+        let b = chk >> 20;
+        chk = ((chk & 0xfffff) << 10) ^ v;
+        RS1024_GEN.iter().enumerate().for_each(|(i, &g)| chk ^= g & 0u32.wrapping_sub((b >> i) & 1));
+    }
+    chk
+}
+
+/// the multiplication of the GF(256) without the table lookup
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut p = 0u8;
+    for _ in 0..8 {
+        p ^= a & 0u8.wrapping_sub(b & 1);
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7));
+        b >>= 1;
+    }
+    p
+}
+
+/// $a^{-1} = a^{254}$
+fn gf_inv(a: u8) -> u8 {
+    let (mut r, mut x) = (1u8, a);
+    for _ in 0..7 {
+        x = gf_mul(x, x);
+        r = gf_mul(r, x);
+    }
+    r
+}
+
+/// $f(x) = \sum_j y_j \prod_{m \ne j} (x - x_m) / (x_j - x_m)$, the subtraction of the GF(256) is the xor
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, y)) = points.iter().find(|(i, _)| *i == x) {
+        return y.to_vec();
+    }
+
+    let mut y = vec![0u8; points[0].1.len()];
+    for (j, &(xj, yj)) in points.iter().enumerate() {
+        let (mut num, mut den) = (1u8, 1u8);
+        for (m, &(xm, _)) in points.iter().enumerate() {
+            if m != j {
+                num = gf_mul(num, x ^ xm);
+                den = gf_mul(den, xj ^ xm);
+            }
+        }
+
+        let l = gf_mul(num, gf_inv(den));
+        y.iter_mut().zip(yj.iter()).for_each(|(a, &b)| *a ^= gf_mul(l, b));
+    }
+    y
+}
+
+fn random_bytes<R: IterSource<u32>>(rd: &mut R, len: usize) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(len + 3);
+    rd.iter_mut().take(len.div_ceil(4)).for_each(|x| buf.extend_from_slice(x.to_be_bytes().as_ref()));
+    buf.truncate(len);
+    buf
+}
+
+fn digest(random: &[u8], secret: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let mut hmac = HMAC::new(random.to_vec(), SHA256::new())?;
+    hmac.write(secret);
+    let mut d = Vec::with_capacity(32);
+    hmac.checksum(&mut d);
+    d.truncate(DIGEST_LEN);
+    Ok(d)
+}
+
+/// the shares with the indices `0..count`, the first `threshold - 2` shares are random
+fn split_secret<R: IterSource<u32>>(threshold: usize, count: usize, secret: &[u8], rd: &mut R) -> Result<Vec<(u8, Vec<u8>)>, CryptoError> {
+    if threshold == 1 {
+        return Ok((0..count).map(|i| (i as u8, secret.to_vec())).collect());
+    }
+
+    let mut shares = (0..(threshold - 2)).map(|i| (i as u8, random_bytes(rd, secret.len()))).collect::<Vec<_>>();
+    let random = random_bytes(rd, secret.len() - DIGEST_LEN);
+    let mut digest_share = digest(random.as_slice(), secret)?;
+    digest_share.extend_from_slice(random.as_slice());
+
+    let mut base = shares.iter().map(|(i, v)| (*i, v.as_slice())).collect::<Vec<_>>();
+    base.push((DIGEST_INDEX, digest_share.as_slice()));
+    base.push((SECRET_INDEX, secret));
+    let rest = ((threshold - 2)..count).map(|i| (i as u8, interpolate(base.as_slice(), i as u8))).collect::<Vec<_>>();
+    shares.extend(rest);
+    Ok(shares)
+}
+
+fn recover_secret(threshold: usize, points: &[(u8, &[u8])]) -> Result<Vec<u8>, CryptoError> {
+    if threshold == 1 {
+        return Ok(points[0].1.to_vec());
+    }
+
+    let secret = interpolate(points, SECRET_INDEX);
+    let digest_share = interpolate(points, DIGEST_INDEX);
+    let d = digest(&digest_share[DIGEST_LEN..], secret.as_slice())?;
+    if d.iter().zip(digest_share.iter()).fold(0u8, |x, (a, b)| x | (a ^ b)) != 0 {
+        return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "Invalid digest of the SLIP-39 shared secret"));
+    }
+    Ok(secret)
+}
+
+/// the encryption(or the decryption) of the master secret, the round function is
+/// `PBKDF2(i || passphrase, salt || R, 2500 << e)` and the salt is `"shamir" || identifier` if not `extendable`
+fn feistel(secret: &[u8], passphrase: &[u8], e: u8, identifier: u16, extendable: bool, decrypt: bool) -> Result<Vec<u8>, CryptoError> {
+    if let Some(c) = passphrase.iter().find(|&&c| !(32..=126).contains(&c)) {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The SLIP-39 passphrase must be the printable ASCII characters, but 0x{:02x} is given", c)));
+    }
+
+    let mut salt = Vec::with_capacity(CUSTOMIZATION.len() + 2 + secret.len() / 2);
+    if !extendable {
+        salt.extend_from_slice(CUSTOMIZATION);
+        salt.extend_from_slice(identifier.to_be_bytes().as_ref());
+    }
+    let salt_len = salt.len();
+    let mut password = Vec::with_capacity(passphrase.len() + 1);
+    let mut kdf = PBKDF2::new(SHA256::new(), (BASE_ITERATION_COUNT << e) / (ROUND_COUNT as usize))?;
+
+    let half = secret.len() / 2;
+    let (mut l, mut r) = (secret[..half].to_vec(), secret[half..].to_vec());
+    let mut f = Vec::with_capacity(half);
+    for i in 0..ROUND_COUNT {
+        let i = if decrypt {ROUND_COUNT - 1 - i} else {i};
+        password.clear();
+        password.push(i);
+        password.extend_from_slice(passphrase);
+        salt.truncate(salt_len);
+        salt.extend_from_slice(r.as_slice());
+        kdf.derive(password.as_slice(), salt.as_slice(), half, &mut f)?;
+
+        l.iter_mut().zip(f.iter()).for_each(|(a, &b)| *a ^= b);
+        std::mem::swap(&mut l, &mut r);
+    }
+
+    r.extend_from_slice(l.as_slice());
+    Ok(r)
+}
@@ -0,0 +1,143 @@
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::CryptoErrorKind;
+use crate::encoding::Hex;
+use crate::sharing::{Slip39, Slip39Share, Slip39Wordlist, SLIP39_WORDLIST_LEN};
+
+#[test]
+fn slip39_vectors() {
+    // the word indices of the SLIP-39 test vectors in the official words list, the passphrase is "TREZOR"
+    let cases: [(&[&[u16]], &str); 4] = [
+        // "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband
+        // erode duke ajar critical decision keyboard"
+        (&[
+            &[248, 288, 0, 0, 17, 753, 521, 840, 372, 497, 155, 670, 192, 448, 297, 249, 23, 173, 196, 496],
+        ], "bb54aac4b89dc868ba37d9cc21b2cece"),
+        // "shadow pistol academic always adequate wildlife ...", "shadow pistol academic acid actress prayer ..."
+        (&[
+            &[801, 674, 0, 33, 8, 1004, 331, 411, 617, 184, 605, 1016, 749, 980, 808, 635, 354, 555, 157, 53],
+            &[801, 674, 0, 1, 6, 685, 143, 957, 190, 882, 206, 354, 942, 956, 167, 253, 878, 14, 416, 832],
+        ], "b43ceb7e57a0ea8766221624d01b0864"),
+        // the 2 of 4 groups, "eraser senior decision scared ...", "eraser senior decision roster ...",
+        // "eraser senior ceramic snake ...", "eraser senior ceramic shaft ...", "eraser senior ceramic round ..."
+        (&[
+            &[296, 800, 196, 785, 125, 911, 215, 452, 200, 590, 182, 460, 642, 93, 612, 818, 731, 986, 64, 486],
+            &[296, 800, 196, 769, 68, 933, 453, 414, 779, 463, 327, 61, 910, 179, 79, 129, 750, 239, 277, 162],
+            &[296, 800, 132, 834, 144, 969, 444, 614, 52, 436, 57, 128, 919, 102, 406, 423, 662, 793, 513, 668],
+            &[296, 800, 132, 802, 252, 70, 491, 1016, 813, 655, 360, 567, 31, 155, 34, 799, 1020, 971, 455, 636],
+            &[296, 800, 132, 770, 158, 427, 941, 57, 833, 803, 28, 406, 807, 534, 660, 160, 138, 822, 377, 8],
+        ], "7c3397a292a5941682d7a4ae2d898d11"),
+        // the extendable share, "testify swimming academic academic column loyalty smear include exotic bedroom
+        // exotic wrist lobe cover grief golden smart junior estimate learn"
+        (&[
+            &[906, 883, 0, 0, 158, 541, 828, 460, 315, 71, 315, 1016, 537, 164, 407, 400, 827, 491, 300, 514],
+        ], "1679b4516e0ee5954351d288a838f45e"),
+    ];
+
+    for (i, &(mnemonics, tgt)) in cases.iter().enumerate() {
+        let shares = mnemonics.iter().map(|m| Slip39Share::from_indices(m).unwrap()).collect::<Vec<_>>();
+        for (m, s) in mnemonics.iter().zip(shares.iter()) {
+            assert_eq!(s.to_indices().as_slice(), *m, "case: {}", i);
+        }
+        let secret = Slip39::combine(shares.as_slice(), b"TREZOR").unwrap();
+        assert_eq!(Hex::lower().encode(secret.as_slice()), tgt, "case: {}", i);
+        if shares.len() > 1 {
+            assert_eq!(Slip39::combine(&shares[1..], b"TREZOR").unwrap_err().kind(), CryptoErrorKind::InvalidParameter, "case: {}", i);
+        }
+    }
+
+    let extendable = Slip39Share::from_indices(cases[3].0[0]).unwrap();
+    assert!(extendable.extendable() && extendable.iteration_exponent() == 3);
+    assert_eq!((extendable.group_threshold(), extendable.group_count(), extendable.member_threshold()), (1, 1, 1));
+
+    // "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband
+    // erode duke ajar music cargo fitness", the invalid checksum
+    let invalid = [248u16, 288, 0, 0, 17, 753, 521, 840, 372, 497, 155, 670, 192, 448, 297, 249, 23, 604, 125, 348];
+    assert_eq!(Slip39Share::from_indices(&invalid).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(Slip39Share::from_indices(&cases[0].0[0][1..]).is_err());
+
+    let shares = cases[2].0.iter().map(|m| Slip39Share::from_indices(m).unwrap()).collect::<Vec<_>>();
+    assert!(Slip39::combine(&shares[1..], b"TREZOR").is_err());
+    assert_ne!(Slip39::combine(shares.as_slice(), b"").unwrap(), Slip39::combine(shares.as_slice(), b"TREZOR").unwrap());
+}
+
+#[test]
+fn slip39_split() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let secret = b"0123456789abcdef0123456789abcdef";
+    for &extendable in [false, true].iter() {
+        let slip39 = Slip39::new(2, &[(1, 1), (2, 3), (3, 5)], 0, extendable).unwrap();
+        let groups = slip39.split(secret.as_ref(), b"TREZOR", &mut rd).unwrap();
+        assert_eq!(groups.iter().map(|g| g.len()).collect::<Vec<_>>(), vec![1, 3, 5]);
+        let identifier = groups[0][0].identifier();
+        for (i, g) in groups.iter().enumerate() {
+            for (j, s) in g.iter().enumerate() {
+                assert_eq!((s.identifier(), s.extendable(), s.group_index(), s.member_index()), (identifier, extendable, i as u8, j as u8));
+                assert_eq!(s.to_indices().len(), 4 + 26 + 3);
+                assert_eq!(&Slip39Share::from_indices(s.to_indices().as_slice()).unwrap(), s);
+            }
+        }
+
+        let picks = [
+            vec![groups[0][0].clone(), groups[2][4].clone(), groups[2][0].clone(), groups[2][2].clone()],
+            vec![groups[1][2].clone(), groups[1][0].clone(), groups[0][0].clone()],
+            vec![groups[2][1].clone(), groups[1][1].clone(), groups[2][3].clone(), groups[1][2].clone(), groups[2][4].clone()],
+        ];
+        for (i, p) in picks.iter().enumerate() {
+            assert_eq!(Slip39::combine(p.as_slice(), b"TREZOR").unwrap().as_slice(), secret.as_ref(), "case: {}", i);
+        }
+
+        let missing = [groups[0][0].clone(), groups[1][0].clone()];
+        assert_eq!(Slip39::combine(missing.as_ref(), b"TREZOR").unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+        let duplicated = [groups[0][0].clone(), groups[1][0].clone(), groups[1][0].clone()];
+        assert_eq!(Slip39::combine(duplicated.as_ref(), b"TREZOR").unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+        assert!(Slip39::combine(&[groups[0][0].clone()], b"TREZOR").is_err());
+
+        let mut tampered = groups[1][1].to_indices();
+        tampered[10] ^= 1;
+        assert_eq!(Slip39Share::from_indices(tampered.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+        assert_ne!(Slip39::combine(&picks[1], b"").unwrap().as_slice(), secret.as_ref());
+        assert!(Slip39::combine(&picks[1], b"TREZOR\n").is_err());
+    }
+
+    let other = Slip39::new(1, &[(2, 2)], 0, true).unwrap().split(secret.as_ref(), b"", &mut rd).unwrap();
+    assert!(Slip39::combine(&[other[0][0].clone(), other[0][0].clone()], b"").is_err());
+    assert_eq!(Slip39::combine(&other[0], b"").unwrap().as_slice(), secret.as_ref());
+
+    assert!(Slip39::new(1, &[(1, 2)], 0, true).is_err());
+    assert!(Slip39::new(2, &[(1, 1)], 0, true).is_err());
+    assert!(Slip39::new(1, &[(3, 2)], 0, true).is_err());
+    assert!(Slip39::new(1, &[(2, 17)], 0, true).is_err());
+    assert!(Slip39::new(1, &[(1, 1)], 16, true).is_err());
+    assert!(Slip39::new(1, &[], 0, true).is_err());
+    let slip39 = Slip39::new(1, &[(1, 1)], 0, true).unwrap();
+    assert!(slip39.split(&secret[..15], b"", &mut rd).is_err());
+    assert!(slip39.split(&secret[..17], b"", &mut rd).is_err());
+}
+
+#[test]
+fn slip39_wordlist() {
+    // a synthetic words list, the words are "a" and the 3 letters base 26 digits of the index
+    let words = (0..SLIP39_WORDLIST_LEN).map(|i| {
+        let d = |x: usize| (b'a' + (x % 26) as u8) as char;
+        format!("a{}{}{}", d(i / 676), d(i / 26), d(i))
+    }).collect::<Vec<_>>();
+    let words = words.iter().map(|w| w.as_str()).collect::<Vec<_>>();
+    let wordlist = Slip39Wordlist::new(words.as_slice()).unwrap();
+    assert_eq!((wordlist.word(27), wordlist.index_of("aabb"), wordlist.index_of("zzzz")), (Some("aabb"), Some(27), None));
+
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let shares = Slip39::new(1, &[(2, 3)], 1, false).unwrap().split(b"0123456789abcdef".as_ref(), b"", &mut rd).unwrap();
+    let mnemonic = wordlist.to_mnemonic(&shares[0][1]);
+    assert_eq!(mnemonic.split(' ').count(), 20);
+    assert_eq!(wordlist.from_mnemonic(mnemonic.as_str()).unwrap(), shares[0][1]);
+    assert_eq!(wordlist.from_mnemonic(mnemonic.to_uppercase().as_str()).unwrap(), shares[0][1]);
+    assert!(wordlist.from_mnemonic(format!("{} zzzz", mnemonic).as_str()).is_err());
+
+    let mut unsorted = words.clone();
+    unsorted.swap(3, 4);
+    assert!(Slip39Wordlist::new(unsorted.as_slice()).is_err());
+    let mut prefix = words.clone();
+    prefix[5] = "aaaeb";
+    assert!(Slip39Wordlist::new(prefix.as_slice()).is_err());
+    assert!(Slip39Wordlist::new(&words[1..]).is_err());
+}
@@ -0,0 +1,11 @@
+//! The fast random numbers for the simulations
+//!
+//! The `SimRand` is NOT the cryptographically secure random number generator, it is seeded by the caller for the
+//! reproducible simulations, the Monte Carlo methods and the randomized tests. Use the `rmath::rand::CryptoRand`
+//! to generate the keys, the nonces and the other secrets.
+
+mod sim_rand;
+pub use sim_rand::SimRand;
+
+#[cfg(test)]
+mod sim_test;
\ No newline at end of file
@@ -0,0 +1,133 @@
+//! The ChaCha8 based random number generator
+//!
+//! The state is the ChaCha state with the 256 bits seed as the key, the 64 bits block counter(word 12, 13) and the
+//! 64 bits stream id(word 14, 15), the output is the key stream words of the ChaCha8 block function. The different
+//! stream ids of the same seed are the independent sequences for the parallel simulations.
+
+use rmath::rand::{IterSource, Source, Seed, Iter, RandError};
+use crate::chacha::{chacha_block_words, CHACHA_CONSTANTS};
+
+const SIM_RAND_ROUNDS: usize = 8;
+
+/// The seeded fast random number generator for the simulations, it is NOT for the cryptographic usage
+#[derive(Clone, Debug)]
+pub struct SimRand {
+    state: [u32; 16],
+    buf: [u32; 16],
+    // the index of the next unused word in the `buf`
+    idx: usize,
+}
+
+impl SimRand {
+    /// the stream 0 of the `seed`
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let mut state = [0u32; 16];
+        state[..4].copy_from_slice(CHACHA_CONSTANTS.as_ref());
+        state[4..12].iter_mut().zip(seed.chunks(4)).for_each(|(s, k)| *s = u32::from_le_bytes([k[0], k[1], k[2], k[3]]));
+        Self { state, buf: [0u32; 16], idx: 16 }
+    }
+
+    /// expand the `seed` to the 256 bits seed by the SplitMix64
+    pub fn seed_from_u64(seed: u64) -> Self {
+        let mut x = seed;
+        let mut buf = [0u8; 32];
+        buf.chunks_mut(8).for_each(|b| {
+            x = x.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            b.copy_from_slice((z ^ (z >> 31)).to_le_bytes().as_ref());
+        });
+        Self::from_seed(buf)
+    }
+
+    pub fn stream(&self) -> u64 {
+        (self.state[14] as u64) | ((self.state[15] as u64) << 32)
+    }
+
+    /// switch to the beginning of the `stream`
+    pub fn set_stream(&mut self, stream: u64) {
+        self.state[12] = 0;
+        self.state[13] = 0;
+        self.state[14] = stream as u32;
+        self.state[15] = (stream >> 32) as u32;
+        self.idx = 16;
+    }
+
+    /// the number of the words have been generated in the current stream
+    pub fn word_pos(&self) -> u128 {
+        let counter = (self.state[12] as u128) | ((self.state[13] as u128) << 32);
+        (counter << 4) - (16 - self.idx) as u128
+    }
+
+    fn refill(&mut self) {
+        self.buf = chacha_block_words(&self.state, SIM_RAND_ROUNDS);
+        let counter = ((self.state[12] as u64) | ((self.state[13] as u64) << 32)).wrapping_add(1);
+        self.state[12] = counter as u32;
+        self.state[13] = (counter >> 32) as u32;
+        self.idx = 0;
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.idx == 16 {
+            self.refill();
+        }
+        self.idx += 1;
+        self.buf[self.idx - 1]
+    }
+
+    /// the little-endian composition of the two words
+    pub fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        lo | ((self.next_u32() as u64) << 32)
+    }
+
+    pub fn fill_bytes(&mut self, dst: &mut [u8]) {
+        dst.chunks_mut(4).for_each(|d| {
+            let x = self.next_u32().to_le_bytes();
+            d.copy_from_slice(&x[..d.len()]);
+        });
+    }
+
+    /// the uniform random number in the range `[0, bound)`, the `bound` 0 means the range `[0, 2^64)`
+    pub fn gen_range(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return self.next_u64();
+        }
+
+        // Lemire's multiply-and-reject
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let m = (self.next_u64() as u128) * (bound as u128);
+            if (m as u64) >= threshold {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// the uniform random number in the range `[0, 1)` with the 53 bits precision
+    pub fn gen_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+impl Source<u32> for SimRand {
+    fn gen(&mut self) -> Result<u32, RandError> {
+        Ok(self.next_u32())
+    }
+
+    /// reseed by the 8 words of the `sd`, the stream id is kept
+    fn reset<Sd: Seed<u32>>(&mut self, sd: &Sd) -> Result<(), RandError> {
+        for s in self.state[4..12].iter_mut() {
+            *s = sd.seed()?;
+        }
+        self.set_stream(self.stream());
+        Ok(())
+    }
+}
+
+impl IterSource<u32> for SimRand {
+    fn iter_mut(&mut self) -> Iter<'_, Self, u32> where Self: std::marker::Sized {
+        Iter::new(self)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::ChaCha8;
+use crate::sim::SimRand;
+use rmath::rand::{IterSource, Source, DefaultSeed};
+
+fn words(block: [u8; 64]) -> Vec<u32> {
+    block.chunks(4).map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]])).collect()
+}
+
+#[test]
+fn sim_rand() {
+    let seed = [0x5au8; 32];
+    let mut rd = SimRand::from_seed(seed);
+    assert_eq!((rd.stream(), rd.word_pos()), (0, 0));
+    // the key stream words of the ChaCha8 with the 64 bits counter and the 64 bits stream id
+    for stream in [0u64, 1, 0x0123456789abcdef] {
+        rd.set_stream(stream);
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(stream.to_le_bytes().as_ref());
+        let mut tgt = words(ChaCha8::new(seed, nonce, 0).block());
+        tgt.extend(words(ChaCha8::new(seed, nonce, 1).block()));
+        let out = (0..32).map(|_| rd.next_u32()).collect::<Vec<_>>();
+        assert_eq!(out, tgt, "stream: {:#x}", stream);
+        assert_eq!(rd.word_pos(), 32);
+    }
+
+    let (mut a, mut b) = (SimRand::seed_from_u64(42), SimRand::seed_from_u64(42));
+    let lo = a.next_u32() as u64;
+    assert_eq!(b.next_u64(), lo | ((a.next_u32() as u64) << 32));
+    let (mut x, mut y) = ([0u8; 37], [0u8; 37]);
+    a.fill_bytes(&mut x);
+    b.fill_bytes(&mut y);
+    assert_eq!(x, y);
+    assert_eq!(a.word_pos(), 12);
+    assert_ne!(SimRand::seed_from_u64(43).next_u64(), SimRand::seed_from_u64(42).next_u64());
+
+    let mut hist = [0usize; 6];
+    for _ in 0..6000 {
+        let (r, f) = (a.gen_range(6), a.gen_f64());
+        assert!((0.0..1.0).contains(&f));
+        hist[r as usize] += 1;
+    }
+    assert!(hist.iter().all(|&c| c > 800 && c < 1200), "{:?}", hist);
+    a.gen_range(0);
+
+    let v = a.iter_mut().take(4).collect::<Vec<_>>();
+    assert_eq!(v.len(), 4);
+    a.set_stream(7);
+    a.reset(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    assert_eq!((a.stream(), a.word_pos()), (7, 0));
+    assert!(a.gen().is_ok());
+}
@@ -0,0 +1,18 @@
+//! The lightweight block ciphers Simon and Speck
+//! Beaulieu, Shors, Smith, Treatman-Clark, Weeks, Wingers. The SIMON and SPECK Families of Lightweight Block Ciphers, 2013.
+//!
+//! They are provided for the research comparisons only(the `research` feature), all the 10 standard block/key
+//! sizes are supported. The blocks and the keys are the little-endian words as the implementation guide of the
+//! designers, i.e. the block bytes are `y || x` and the key bytes are `k_0 || l_0 || l_1 || ...`, which is the
+//! reversed byte order of the test vectors shown in the paper.
+
+mod words;
+
+mod speck;
+pub use speck::Speck;
+
+mod simon;
+pub use simon::Simon;
+
+#[cfg(test)]
+mod simon_speck_test;
\ No newline at end of file
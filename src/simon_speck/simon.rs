@@ -0,0 +1,110 @@
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::simon_speck::words::{word_params, bytes_to_words, words_to_bytes, ror, rol};
+
+/// the constant sequences z_0..z_4 of the key schedule, the bit 61 is the first bit
+const SIMON_Z: [u64; 5] = [
+    0b11111010001001010110000111001101111101000100101011000011100110,
+    0b10001110111110010011000010110101000111011111001001100001011010,
+    0b10101111011100000011010010011000101000010001111110010110110011,
+    0b11011011101011000110010111100000010010001010011100110100001111,
+    0b11010001111001101011011000100000010111000011001010010011101111,
+];
+
+/// Simon block cipher, the Feistel cipher optimized for the hardware
+///
+/// Simon`2n`/`mn`: the `2n` bits block and the `m` words key, the `n` is 16, 24, 32, 48 or 64.
+#[derive(Clone)]
+pub struct Simon {
+    n: usize,
+    mask: u64,
+    rk: Vec<u64>,
+}
+
+impl Simon {
+    /// `block_len`: the block length in bytes(4, 6, 8, 12 or 16), the key length determines the key size of the
+    /// variant with the block length.
+    pub fn new(block_len: usize, key: &[u8]) -> Result<Self, CryptoError> {
+        let (n, m) = word_params("Simon", block_len, key.len())?;
+        let (rounds, j) = match (n, m) {
+            (16, _) => (32, 0),
+            (24, 3) => (36, 0),
+            (24, _) => (36, 1),
+            (32, 3) => (42, 2),
+            (32, _) => (44, 3),
+            (48, 2) => (52, 2),
+            (48, _) => (54, 3),
+            (_, 2) => (68, 2),
+            (_, 3) => (69, 3),
+            (_, _) => (72, 4),
+        };
+        let mask = u64::MAX >> (64 - n);
+
+        let mut rk = bytes_to_words(key, n);
+        for i in m..rounds {
+            let mut t = ror(rk[i - 1], 3, n, mask);
+            if m == 4 {
+                t ^= rk[i - 3];
+            }
+            t ^= ror(t, 1, n, mask);
+            let z = (SIMON_Z[j] >> (61 - (i - m) % 62)) & 1;
+            rk.push((!rk[i - m] & mask) ^ t ^ z ^ 3);
+        }
+
+        Ok(Self { n, mask, rk })
+    }
+
+    /// the number of the rounds
+    pub fn rounds(&self) -> usize {
+        self.rk.len()
+    }
+
+    #[inline]
+    fn f(&self, x: u64) -> u64 {
+        let (n, mask) = (self.n, self.mask);
+        (rol(x, 1, n, mask) & rol(x, 8, n, mask)) ^ rol(x, 2, n, mask)
+    }
+
+    fn check_block(&self, block: &[u8]) -> Result<(), CryptoError> {
+        if block.len() != (self.n >> 2) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The length of data block must be {} in bytes", self.n >> 2)))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Cipher for Simon {
+    type Output = usize;
+    fn block_size(&self) -> Option<usize> {
+        Some(self.n >> 2)
+    }
+
+    fn encrypt(&self, dst: &mut Vec<u8>, plaintext_block: &[u8]) -> Result<usize, CryptoError> {
+        self.check_block(plaintext_block)?;
+        let w = bytes_to_words(plaintext_block, self.n);
+        let (mut y, mut x) = (w[0], w[1]);
+        for &k in self.rk.iter() {
+            let t = x;
+            x = y ^ self.f(x) ^ k;
+            y = t;
+        }
+
+        words_to_bytes(dst, &[y, x], self.n);
+        Ok(dst.len())
+    }
+
+    fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<usize, CryptoError> {
+        self.check_block(cipher_block)?;
+        let w = bytes_to_words(cipher_block, self.n);
+        let (mut y, mut x) = (w[0], w[1]);
+        for &k in self.rk.iter().rev() {
+            let t = y;
+            y = x ^ self.f(y) ^ k;
+            x = t;
+        }
+
+        words_to_bytes(dst, &[y, x], self.n);
+        Ok(dst.len())
+    }
+}
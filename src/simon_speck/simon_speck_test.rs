@@ -0,0 +1,73 @@
+use crate::{Simon, Speck, Cipher, CryptoErrorKind};
+use crate::test_util::hex;
+
+fn check<C: Cipher<Output = usize>>(c: &C, pt: &[u8], ct: &str, case: &str) {
+    let (mut buf, mut m) = (Vec::new(), Vec::new());
+    c.encrypt(&mut buf, pt).unwrap();
+    assert_eq!(buf, hex(ct), "case: {}", case);
+    c.decrypt(&mut m, buf.as_slice()).unwrap();
+    assert_eq!(m, pt, "case: {}", case);
+}
+
+// (block bytes, key bytes, rounds, the ciphertext of the key `00 01 02 ...` and the plaintext `80 81 82 ...`)
+const SPECK_CASES: [(usize, usize, usize, &str); 10] = [
+    (4, 8, 22, "2260350f"),
+    (6, 9, 22, "1b38426eb0fd"),
+    (6, 12, 23, "e5025441847e"),
+    (8, 12, 26, "084cf3b7dc6f6075"),
+    (8, 16, 27, "28b9b0cbca969879"),
+    (12, 12, 28, "25468aba2720a73400902a9b"),
+    (12, 18, 29, "85985e2e4e970b9b8fe4dd65"),
+    (16, 16, 32, "cf416a08a722f2a7f0a6d832356093e4"),
+    (16, 24, 33, "71812e230b36fffca3d5d0020cba7a8c"),
+    (16, 32, 34, "abb228dbeaeded332d566797a7682c66"),
+];
+
+const SIMON_CASES: [(usize, usize, usize, &str); 10] = [
+    (4, 8, 32, "d48a24f3"),
+    (6, 9, 36, "c24cbb4fb043"),
+    (6, 12, 36, "2e5d4a1f4cf0"),
+    (8, 12, 42, "91d9fe38b693a849"),
+    (8, 16, 44, "4e32cef3945930e9"),
+    (12, 12, 52, "24045cabb61946862a40049d"),
+    (12, 18, 54, "187d044b031475943a808961"),
+    (16, 16, 68, "5404fda3ab88adf3df7df97be8a1afe1"),
+    (16, 24, 69, "ad6078fc61d9e48e054cac1d2203cf60"),
+    (16, 32, 72, "c900ac218fbedda53581f06f6d9ae391"),
+];
+
+#[test]
+fn speck() {
+    // the test vectors of the paper in the byte order of the implementation guide
+    let key = (0u8..16).collect::<Vec<_>>();
+    check(&Speck::new(16, key.as_slice()).unwrap(), hex("206d616465206974206571756976616c").as_slice(), "180d575cdffe60786532787951985da6", "Speck128/128");
+    check(&Speck::new(4, hex("0001080910111819").as_slice()).unwrap(), hex("4c697465").as_slice(), "f24268a8", "Speck32/64");
+
+    for &(b_len, k_len, rounds, ct) in SPECK_CASES.iter() {
+        let (key, pt) = ((0..k_len as u8).collect::<Vec<_>>(), (0x80..(0x80 + b_len as u8)).collect::<Vec<_>>());
+        let speck = Speck::new(b_len, key.as_slice()).unwrap();
+        assert_eq!((speck.block_size(), speck.rounds()), (Some(b_len), rounds));
+        check(&speck, pt.as_slice(), ct, &format!("Speck{}/{}", b_len * 8, k_len * 8));
+    }
+
+    assert_eq!(Speck::new(16, &[0u8; 15]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(Speck::new(4, &[0u8; 16]).is_err());
+    assert!(Speck::new(16, &[0u8; 16]).unwrap().encrypt(&mut Vec::new(), &[0u8; 8]).is_err());
+}
+
+#[test]
+fn simon() {
+    let key = (0u8..16).collect::<Vec<_>>();
+    check(&Simon::new(16, key.as_slice()).unwrap(), hex("2074726176656c6c6572732064657363").as_slice(), "bc0b4ef82a83aa653ffe541e1e1b6849", "Simon128/128");
+    check(&Simon::new(4, hex("0001080910111819").as_slice()).unwrap(), hex("77686565").as_slice(), "bbe99bc6", "Simon32/64");
+
+    for &(b_len, k_len, rounds, ct) in SIMON_CASES.iter() {
+        let (key, pt) = ((0..k_len as u8).collect::<Vec<_>>(), (0x80..(0x80 + b_len as u8)).collect::<Vec<_>>());
+        let simon = Simon::new(b_len, key.as_slice()).unwrap();
+        assert_eq!((simon.block_size(), simon.rounds()), (Some(b_len), rounds));
+        check(&simon, pt.as_slice(), ct, &format!("Simon{}/{}", b_len * 8, k_len * 8));
+    }
+
+    assert_eq!(Simon::new(12, &[0u8; 16]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert!(Simon::new(8, &[0u8; 12]).unwrap().decrypt(&mut Vec::new(), &[0u8; 16]).is_err());
+}
@@ -0,0 +1,90 @@
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::simon_speck::words::{word_params, bytes_to_words, words_to_bytes, ror, rol};
+
+/// Speck block cipher, the ARX cipher optimized for the software
+///
+/// Speck`2n`/`mn`: the `2n` bits block and the `m` words key, the `n` is 16, 24, 32, 48 or 64.
+#[derive(Clone)]
+pub struct Speck {
+    n: usize,
+    mask: u64,
+    alpha: usize,
+    beta: usize,
+    rk: Vec<u64>,
+}
+
+impl Speck {
+    /// `block_len`: the block length in bytes(4, 6, 8, 12 or 16), the key length determines the key size of the
+    /// variant with the block length.
+    pub fn new(block_len: usize, key: &[u8]) -> Result<Self, CryptoError> {
+        let (n, m) = word_params("Speck", block_len, key.len())?;
+        let rounds = match (n, m) {
+            (16, _) => 22,
+            (24, m) => 19 + m,
+            (32, m) => 23 + m,
+            (48, m) => 26 + m,
+            (_, m) => 30 + m,
+        };
+        let (alpha, beta) = if n == 16 { (7, 2) } else { (8, 3) };
+        let mask = u64::MAX >> (64 - n);
+
+        let kw = bytes_to_words(key, n);
+        let (mut l, mut rk) = (kw[1..].to_vec(), Vec::with_capacity(rounds));
+        rk.push(kw[0]);
+        for i in 0..(rounds - 1) {
+            l.push((rk[i].wrapping_add(ror(l[i], alpha, n, mask)) & mask) ^ (i as u64));
+            rk.push(rol(rk[i], beta, n, mask) ^ l[i + m - 1]);
+        }
+
+        Ok(Self { n, mask, alpha, beta, rk })
+    }
+
+    /// the number of the rounds
+    pub fn rounds(&self) -> usize {
+        self.rk.len()
+    }
+
+    fn check_block(&self, block: &[u8]) -> Result<(), CryptoError> {
+        if block.len() != (self.n >> 2) {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The length of data block must be {} in bytes", self.n >> 2)))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Cipher for Speck {
+    type Output = usize;
+    fn block_size(&self) -> Option<usize> {
+        Some(self.n >> 2)
+    }
+
+    fn encrypt(&self, dst: &mut Vec<u8>, plaintext_block: &[u8]) -> Result<usize, CryptoError> {
+        self.check_block(plaintext_block)?;
+        let (n, mask) = (self.n, self.mask);
+        let w = bytes_to_words(plaintext_block, n);
+        let (mut y, mut x) = (w[0], w[1]);
+        for &k in self.rk.iter() {
+            x = (ror(x, self.alpha, n, mask).wrapping_add(y) & mask) ^ k;
+            y = rol(y, self.beta, n, mask) ^ x;
+        }
+
+        words_to_bytes(dst, &[y, x], n);
+        Ok(dst.len())
+    }
+
+    fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<usize, CryptoError> {
+        self.check_block(cipher_block)?;
+        let (n, mask) = (self.n, self.mask);
+        let w = bytes_to_words(cipher_block, n);
+        let (mut y, mut x) = (w[0], w[1]);
+        for &k in self.rk.iter().rev() {
+            y = ror(y ^ x, self.beta, n, mask);
+            x = rol((x ^ k).wrapping_sub(y) & mask, self.alpha, n, mask);
+        }
+
+        words_to_bytes(dst, &[y, x], n);
+        Ok(dst.len())
+    }
+}
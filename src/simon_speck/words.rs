@@ -0,0 +1,33 @@
+use crate::{CryptoError, CryptoErrorKind};
+
+/// (block bytes, key bytes) => the word bits and the number of the key words
+pub(super) fn word_params(name: &str, block_len: usize, key_len: usize) -> Result<(usize, usize), CryptoError> {
+    match (block_len, key_len) {
+        (4, 8) | (6, 9) | (6, 12) | (8, 12) | (8, 16) | (12, 12) | (12, 18) | (16, 16) | (16, 24) | (16, 32) => {
+            Ok((block_len << 2, (key_len << 1) / block_len))
+        },
+        _ => Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("Wrong {} block/key length: {}/{} bytes, it must be the one of the 32/64, 48/72, 48/96, 64/96, \
+            64/128, 96/96, 96/144, 128/128, 128/192 and 128/256 bits", name, block_len, key_len))),
+    }
+}
+
+/// the n-bits words of the little-endian bytes
+pub(super) fn bytes_to_words(bytes: &[u8], n: usize) -> Vec<u64> {
+    bytes.chunks(n >> 3).map(|w| w.iter().rev().fold(0u64, |a, &b| (a << 8) | (b as u64))).collect()
+}
+
+pub(super) fn words_to_bytes(dst: &mut Vec<u8>, words: &[u64], n: usize) {
+    dst.clear();
+    words.iter().for_each(|&w| dst.extend_from_slice(&w.to_le_bytes()[..(n >> 3)]));
+}
+
+#[inline]
+pub(super) fn ror(x: u64, r: usize, n: usize, mask: u64) -> u64 {
+    ((x >> r) | (x << (n - r))) & mask
+}
+
+#[inline]
+pub(super) fn rol(x: u64, r: usize, n: usize, mask: u64) -> u64 {
+    ((x << r) | (x >> (n - r))) & mask
+}
@@ -0,0 +1,434 @@
+//! The SM2 certificate profile
+//! GM/T 0015-2012, GB/T 38636-2020(TLCP)
+//!
+//! the subject public key is the `id-ecPublicKey` with the `sm2p256v1` named curve, and the certificate is signed by
+//! the `SM2-with-SM3` over the DER encoded `TBSCertificate` with the `SM2_DEFAULT_ID`. There is no X.509 path validation
+//! in the crate, so only the signatures and the issuer/subject names of the chain are verified, the validity period, the
+//! extensions(e.g. the `basicConstraints`, `keyUsage`) and the revocation status are left to the caller.
+
+use rmath::bigint::BigInt;
+use crate::{CryptoError, CryptoErrorKind, Signature};
+use crate::elliptic::{CurveParams, EllipticCurve, KeyPair, PublicKey};
+use crate::encoding::der::{self, DerReader};
+use crate::dsa::SignatureContent;
+use crate::sm2::SM2;
+use crate::oid::Oid;
+use crate::rand::VerifyOnlyRand;
+
+/// the `sm2p256v1` named curve, 1.2.156.10197.1.301, see `oid::Oid::SM2P256V1`
+pub const OID_SM2P256V1: &[u8] = Oid::SM2P256V1.as_der();
+/// the `SM2-with-SM3` signature algorithm, 1.2.156.10197.1.501, see `oid::Oid::SM2_WITH_SM3`
+pub const OID_SM2_WITH_SM3: &[u8] = Oid::SM2_WITH_SM3.as_der();
+
+/// the `id-ce-basicConstraints`, 2.5.29.19
+const OID_BASIC_CONSTRAINTS: &[u8] = &[0x55, 0x1d, 0x13];
+/// the `id-ce-keyUsage`, 2.5.29.15
+const OID_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x0f];
+
+// the `KeyUsage` bits(RFC 5280 4.2.1.3), the bit 0 is the most significant bit of the first octet
+pub const KEY_USAGE_DIGITAL_SIGNATURE: u16 = 0x8000;
+pub const KEY_USAGE_NON_REPUDIATION: u16 = 0x4000;
+pub const KEY_USAGE_KEY_ENCIPHERMENT: u16 = 0x2000;
+pub const KEY_USAGE_DATA_ENCIPHERMENT: u16 = 0x1000;
+pub const KEY_USAGE_KEY_AGREEMENT: u16 = 0x0800;
+pub const KEY_USAGE_KEY_CERT_SIGN: u16 = 0x0400;
+pub const KEY_USAGE_CRL_SIGN: u16 = 0x0200;
+pub const KEY_USAGE_ENCIPHER_ONLY: u16 = 0x0100;
+pub const KEY_USAGE_DECIPHER_ONLY: u16 = 0x0080;
+
+fn cert_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid SM2 certificate: {}", msg)))
+}
+
+fn verify_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::VerificationFailed, msg))
+}
+
+fn parse_digits(d: &[u8]) -> Result<u64, CryptoError> {
+    d.iter().try_fold(0u64, |x, &c| {
+        if c.is_ascii_digit() { Ok(x * 10 + u64::from(c - b'0')) } else { cert_err("the time isn't the decimal digits") }
+    })
+}
+
+/// parse the `Time`(RFC 5280 4.1.2.5) to the seconds since the Unix epoch, the `UTCTime` is `YYMMDDHHMMSSZ` and the
+/// `GeneralizedTime` is `YYYYMMDDHHMMSSZ`.
+fn parse_time(t: &der::DerTLV) -> Result<u64, CryptoError> {
+    let (year, rest) = match (t.tag, t.value.len()) {
+        (der::TAG_UTC_TIME, 13) => {
+            let yy = parse_digits(&t.value[..2])?;
+            (if yy < 50 { 2000 + yy } else { 1900 + yy }, &t.value[2..])
+        },
+        (der::TAG_GENERALIZED_TIME, 15) => (parse_digits(&t.value[..4])?, &t.value[4..]),
+        _ => return cert_err("the time isn't the UTCTime or the GeneralizedTime"),
+    };
+    if rest[10] != b'Z' {
+        return cert_err("the time isn't the UTC");
+    }
+    let (month, day) = (parse_digits(&rest[..2])?, parse_digits(&rest[2..4])?);
+    let (hour, min, sec) = (parse_digits(&rest[4..6])?, parse_digits(&rest[6..8])?, parse_digits(&rest[8..10])?);
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_of_month = [31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    if year < 1970 || !(1..=12).contains(&month) || day < 1 || day > days_of_month[month as usize - 1] || hour > 23 || min > 59 || sec > 59 {
+        return cert_err("the time is out of range");
+    }
+
+    // the days from the civil date, the year begins at March
+    let (y, m) = if month <= 2 { (year - 1, month + 9) } else { (year, month - 3) };
+    let (era, yoe) = (y / 400, y % 400);
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let days = era * 146097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719468;
+    Ok(days * 86400 + hour * 3600 + min * 60 + sec)
+}
+
+fn parse_boolean(b: &der::DerTLV) -> Result<bool, CryptoError> {
+    match b.value {
+        [0x00] => Ok(false),
+        [0xff] => Ok(true),
+        _ => cert_err("the BOOLEAN isn't 0x00 or 0xff"),
+    }
+}
+
+/// DER encode the `SubjectPublicKeyInfo` of the SM2 public key, the public key is encoded as the uncompressed point.
+pub fn sm2_spki_der(key: &PublicKey) -> Result<Vec<u8>, CryptoError> {
+    let curve = CurveParams::sm2p256v1()?;
+    let (x, y) = (key.qx.to_be_bytes(), key.qy.to_be_bytes());
+    if x.len() > 32 || y.len() > 32 || !curve.is_on_curve(&key.qx, &key.qy) {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The public key is not on the sm2p256v1"));
+    }
+    let mut bits = vec![0u8; 66];
+    bits[1] = 0x04;
+    bits[(34 - x.len())..34].copy_from_slice(x.as_slice());
+    bits[(66 - y.len())..].copy_from_slice(y.as_slice());
+
+    let mut alg = Vec::new();
+    der::encode_tlv(der::TAG_OID, Oid::EC_PUBLIC_KEY.as_der(), &mut alg);
+    der::encode_tlv(der::TAG_OID, OID_SM2P256V1, &mut alg);
+
+    let mut spki = Vec::new();
+    der::encode_tlv(der::TAG_SEQUENCE, alg.as_slice(), &mut spki);
+    der::encode_tlv(der::TAG_BIT_STRING, bits.as_slice(), &mut spki);
+    let mut dst = Vec::with_capacity(spki.len() + 2);
+    der::encode_tlv(der::TAG_SEQUENCE, spki.as_slice(), &mut dst);
+    Ok(dst)
+}
+
+/// parse the SM2 public key from the DER encoded `SubjectPublicKeyInfo`, the public key must be an uncompressed point
+/// on the `sm2p256v1`.
+pub fn sm2_public_key_from_spki_der(spki: &[u8]) -> Result<PublicKey, CryptoError> {
+    let mut r = DerReader::new(spki);
+    let s = r.read_expect(der::TAG_SEQUENCE)?;
+    if !r.is_empty() {
+        return cert_err("trailing bytes after the SubjectPublicKeyInfo");
+    }
+
+    let mut s = DerReader::new(s.value);
+    let mut alg = DerReader::new(s.read_expect(der::TAG_SEQUENCE)?.value);
+    if alg.read_expect(der::TAG_OID)?.value != Oid::EC_PUBLIC_KEY.as_der() {
+        return cert_err("the public key algorithm isn't the id-ecPublicKey");
+    }
+    if alg.read_expect(der::TAG_OID)?.value != OID_SM2P256V1 || !alg.is_empty() {
+        return cert_err("the named curve isn't the sm2p256v1");
+    }
+
+    let bits = s.read_expect(der::TAG_BIT_STRING)?.value;
+    if !s.is_empty() || bits.len() != 66 || bits[0] != 0 || bits[1] != 0x04 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The SM2 public key must be the 65 bytes uncompressed point"));
+    }
+    let (qx, qy) = (BigInt::from_be_bytes(&bits[2..34]), BigInt::from_be_bytes(&bits[34..]));
+    let curve = CurveParams::sm2p256v1()?;
+    crate::dh::validate_ec_public_key(&curve, &PublicKey::new_uncheck(&qx, &qy))?;
+    Ok(PublicKey::new_uncheck(&qx, &qy))
+}
+
+/// The X.509 certificate signed by the `SM2-with-SM3`(GM/T 0015-2012)
+pub struct Sm2Certificate<'a> {
+    raw: &'a [u8],
+    tbs: &'a [u8],
+    issuer: &'a [u8],
+    subject: &'a [u8],
+    spki: &'a [u8],
+    not_before: u64,
+    not_after: u64,
+    is_ca: bool,
+    key_usage: Option<u16>,
+    public_key: PublicKey,
+    signature: SignatureContent,
+}
+
+impl<'a> Sm2Certificate<'a> {
+    /// parse the DER encoded certificate, both the signature algorithms must be the `SM2-with-SM3`, and the subject
+    /// public key must be the SM2 public key.
+    pub fn from_der(cert: &'a [u8]) -> Result<Self, CryptoError> {
+        let mut r = DerReader::new(cert);
+        let c = r.read_expect(der::TAG_SEQUENCE)?;
+        if !r.is_empty() {
+            return cert_err("trailing bytes after the certificate");
+        }
+
+        // Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+        let mut c = DerReader::new(c.value);
+        let tbs = c.read_expect(der::TAG_SEQUENCE)?;
+        Self::check_signature_algorithm(c.read_expect(der::TAG_SEQUENCE)?.value)?;
+        let sig = c.read_expect(der::TAG_BIT_STRING)?.value;
+        if !c.is_empty() || sig.first() != Some(&0) {
+            return cert_err("the signatureValue isn't the octets");
+        }
+        let mut sr = DerReader::new(&sig[1..]);
+        let mut rs = DerReader::new(sr.read_expect(der::TAG_SEQUENCE)?.value);
+        let (sig_r, sig_s) = (rs.read_unsigned_integer()?, rs.read_unsigned_integer()?);
+        if !sr.is_empty() || !rs.is_empty() {
+            return cert_err("trailing bytes after the SM2 signature");
+        }
+        let signature = SignatureContent::form_bigint(&BigInt::from_be_bytes(sig_r), &BigInt::from_be_bytes(sig_s));
+
+        // version, serialNumber, signature, issuer, validity, subject, subjectPublicKeyInfo
+        let mut t = DerReader::new(tbs.value);
+        t.read_optional(der::tag_context(0))?;
+        t.read_expect(der::TAG_INTEGER)?;
+        Self::check_signature_algorithm(t.read_expect(der::TAG_SEQUENCE)?.value)?;
+        let issuer = t.read_expect(der::TAG_SEQUENCE)?.raw;
+        let mut v = DerReader::new(t.read_expect(der::TAG_SEQUENCE)?.value);
+        let (not_before, not_after) = (parse_time(&v.read()?)?, parse_time(&v.read()?)?);
+        if !v.is_empty() {
+            return cert_err("trailing bytes after the validity");
+        }
+        let subject = t.read_expect(der::TAG_SEQUENCE)?.raw;
+        let spki = t.read_expect(der::TAG_SEQUENCE)?.raw;
+        let public_key = sm2_public_key_from_spki_der(spki)?;
+
+        // issuerUniqueID [1], subjectUniqueID [2], extensions [3]
+        t.read_optional(0x81)?;
+        t.read_optional(0x82)?;
+        let (is_ca, key_usage) = match t.read_optional(der::tag_context(3))? {
+            Some(e) => Self::parse_extensions(e.value)?,
+            None => (false, None),
+        };
+        if !t.is_empty() {
+            return cert_err("trailing bytes after the extensions");
+        }
+
+        Ok(
+            Self {
+                raw: cert,
+                tbs: tbs.raw,
+                issuer,
+                subject,
+                spki,
+                not_before,
+                not_after,
+                is_ca,
+                key_usage,
+                public_key,
+                signature,
+            }
+        )
+    }
+
+    /// parse the `basicConstraints` and the `keyUsage`, the others must be non-critical
+    fn parse_extensions(exts: &[u8]) -> Result<(bool, Option<u16>), CryptoError> {
+        let mut r = DerReader::new(exts);
+        let mut exts = DerReader::new(r.read_expect(der::TAG_SEQUENCE)?.value);
+        if !r.is_empty() || exts.is_empty() {
+            return cert_err("the extensions isn't the sequence of the extension");
+        }
+
+        let (mut ids, mut is_ca, mut key_usage) = (Vec::new(), false, None);
+        while !exts.is_empty() {
+            // Extension ::= SEQUENCE { extnID, critical BOOLEAN DEFAULT FALSE, extnValue OCTET STRING }
+            let mut e = DerReader::new(exts.read_expect(der::TAG_SEQUENCE)?.value);
+            let id = e.read_expect(der::TAG_OID)?.value;
+            let critical = match e.read_optional(der::TAG_BOOLEAN)? {
+                Some(b) => parse_boolean(&b)?,
+                None => false,
+            };
+            let value = e.read_expect(der::TAG_OCTET_STRING)?.value;
+            if !e.is_empty() || ids.contains(&id) {
+                return cert_err("the duplicate extension or the trailing bytes after the extension");
+            }
+            ids.push(id);
+
+            let mut v = DerReader::new(value);
+            if id == OID_BASIC_CONSTRAINTS {
+                // BasicConstraints ::= SEQUENCE { cA BOOLEAN DEFAULT FALSE, pathLenConstraint INTEGER OPTIONAL }
+                let mut bc = DerReader::new(v.read_expect(der::TAG_SEQUENCE)?.value);
+                is_ca = match bc.read_optional(der::TAG_BOOLEAN)? {
+                    Some(b) => parse_boolean(&b)?,
+                    None => false,
+                };
+                bc.read_optional(der::TAG_INTEGER)?;
+                if !bc.is_empty() {
+                    return cert_err("trailing bytes after the basicConstraints");
+                }
+            } else if id == OID_KEY_USAGE {
+                let bits = v.read_expect(der::TAG_BIT_STRING)?.value;
+                if bits.len() < 2 || bits.len() > 3 || bits[0] > 7 {
+                    return cert_err("the keyUsage isn't the 9 bits string");
+                }
+                key_usage = Some((u16::from(bits[1]) << 8) | u16::from(bits.get(2).copied().unwrap_or(0)));
+            } else if critical {
+                return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The unrecognized critical extension in the SM2 certificate"));
+            } else {
+                continue;
+            }
+            if !v.is_empty() {
+                return cert_err("trailing bytes after the extension value");
+            }
+        }
+        Ok((is_ca, key_usage))
+    }
+
+    fn check_signature_algorithm(alg: &[u8]) -> Result<(), CryptoError> {
+        let mut r = DerReader::new(alg);
+        if r.read_expect(der::TAG_OID)?.value != OID_SM2_WITH_SM3 {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The signature algorithm isn't the SM2-with-SM3"));
+        }
+        // the absent or NULL parameters
+        if r.read_optional(der::TAG_NULL)?.map(|x| !x.value.is_empty()).unwrap_or(false) || !r.is_empty() {
+            return cert_err("the SM2-with-SM3 has no parameters");
+        }
+        Ok(())
+    }
+
+    /// the whole DER encoding of the certificate
+    pub fn as_der(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// the DER encoded `TBSCertificate`, which is signed by the issuer
+    pub fn tbs_der(&self) -> &'a [u8] {
+        self.tbs
+    }
+
+    /// the DER encoded issuer `Name`
+    pub fn issuer_der(&self) -> &'a [u8] {
+        self.issuer
+    }
+
+    /// the DER encoded subject `Name`
+    pub fn subject_der(&self) -> &'a [u8] {
+        self.subject
+    }
+
+    /// the DER encoded `SubjectPublicKeyInfo`
+    pub fn spki_der(&self) -> &'a [u8] {
+        self.spki
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn signature(&self) -> &SignatureContent {
+        &self.signature
+    }
+
+    /// the `notBefore` of the validity, the seconds since the Unix epoch
+    pub fn not_before(&self) -> u64 {
+        self.not_before
+    }
+
+    /// the `notAfter` of the validity, the seconds since the Unix epoch
+    pub fn not_after(&self) -> u64 {
+        self.not_after
+    }
+
+    /// the `cA` of the `basicConstraints`, it's false when the extension is absent
+    pub fn is_ca(&self) -> bool {
+        self.is_ca
+    }
+
+    /// the `keyUsage` bits, see the `KEY_USAGE_*`
+    pub fn key_usage(&self) -> Option<u16> {
+        self.key_usage
+    }
+
+    /// the `keyUsage` contains any of the `usage` bits, it's false when the extension is absent
+    pub fn has_key_usage(&self, usage: u16) -> bool {
+        self.key_usage.map(|x| x & usage != 0).unwrap_or(false)
+    }
+
+    /// the `time`(the seconds since the Unix epoch) is in the validity period
+    pub fn is_valid_at(&self, time: u64) -> bool {
+        self.not_before <= time && time <= self.not_after
+    }
+
+    /// the certificate can issue the other certificates, the `cA` is true and the `keyCertSign` is asserted
+    pub fn is_issuer(&self) -> bool {
+        self.is_ca && self.has_key_usage(KEY_USAGE_KEY_CERT_SIGN)
+    }
+
+    /// the issuer name is the same as the subject name, the names are compared by the DER encoding
+    pub fn is_self_issued(&self) -> bool {
+        self.issuer == self.subject
+    }
+
+    /// verify the signature of the certificate by the issuer's public key with the `SM2_DEFAULT_ID`
+    pub fn verify_signed_by(&self, issuer_key: &PublicKey) -> Result<(), CryptoError> {
+        let mut sm2 = SM2::with_default_id(VerifyOnlyRand, CurveParams::sm2p256v1()?, KeyPair::from(issuer_key.clone()))?;
+        sm2.verify(&self.signature, self.tbs)
+    }
+
+    /// the `issuer` issued the certificate, the name chaining is checked before the signature
+    pub fn verify_issued_by(&self, issuer: &Sm2Certificate) -> Result<(), CryptoError> {
+        if self.issuer != issuer.subject {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The issuer name doesn't match the subject name of the issuer certificate"));
+        }
+        self.verify_signed_by(issuer.public_key())
+    }
+}
+
+/// verify the certificate chain at the `time`(the seconds since the Unix epoch), the `chain[0]` is the end-entity
+/// certificate, and the `chain[i]` is issued by the `chain[i+1]`. The last certificate of the chain must be one of the
+/// `trust_anchors` or be issued by one of them. Every certificate of the chain must be valid at the `time`, and every
+/// issuer(including the trust anchor which issues the last certificate) must be the CA with the `keyCertSign`.
+pub fn verify_sm2_certificate_chain(chain: &[&[u8]], trust_anchors: &[&[u8]], time: u64) -> Result<(), CryptoError> {
+    let certs = chain.iter().map(|c| Sm2Certificate::from_der(c)).collect::<Result<Vec<_>, _>>()?;
+    let anchors = trust_anchors.iter().map(|c| Sm2Certificate::from_der(c)).collect::<Result<Vec<_>, _>>()?;
+    let last = match certs.last() {
+        Some(c) => c,
+        None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The certificate chain is empty")),
+    };
+
+    if certs.iter().any(|c| !c.is_valid_at(time)) {
+        return verify_err("The certificate isn't valid at the verification time");
+    }
+    for w in certs.windows(2) {
+        if !w[1].is_issuer() {
+            return verify_err("The issuer certificate isn't the CA with the keyCertSign");
+        }
+        w[0].verify_issued_by(&w[1])?;
+    }
+
+    if anchors.iter().any(|a| a.as_der() == last.as_der()) {
+        return Ok(());
+    }
+    for a in anchors.iter().filter(|a| a.subject_der() == last.issuer_der() && a.is_issuer()) {
+        if last.verify_signed_by(a.public_key()).is_ok() {
+            return Ok(());
+        }
+    }
+    verify_err("The certificate chain isn't issued by any trust anchor")
+}
+
+/// verify the TLCP(GB/T 38636-2020) double certificates at the `time`, the `sign_chain` is the signing certificate
+/// chain as the `verify_sm2_certificate_chain`, and the `enc_cert` is the encryption certificate, which must be issued
+/// by the same CA as the signing certificate. The signing certificate must assert the `digitalSignature`, and the
+/// encryption certificate must assert the `keyEncipherment` or the `keyAgreement`.
+pub fn verify_tlcp_certificates(sign_chain: &[&[u8]], enc_cert: &[u8], trust_anchors: &[&[u8]], time: u64) -> Result<(), CryptoError> {
+    verify_sm2_certificate_chain(sign_chain, trust_anchors, time)?;
+
+    let (sign, enc) = (Sm2Certificate::from_der(sign_chain[0])?, Sm2Certificate::from_der(enc_cert)?);
+    if sign.issuer_der() != enc.issuer_der() {
+        return verify_err("The encryption certificate isn't issued by the CA of the signing certificate");
+    }
+    if !sign.has_key_usage(KEY_USAGE_DIGITAL_SIGNATURE) {
+        return verify_err("The signing certificate doesn't assert the digitalSignature");
+    }
+    if !enc.has_key_usage(KEY_USAGE_KEY_ENCIPHERMENT | KEY_USAGE_KEY_AGREEMENT) {
+        return verify_err("The encryption certificate doesn't assert the keyEncipherment or the keyAgreement");
+    }
+    let mut chain = Vec::with_capacity(sign_chain.len());
+    chain.push(enc_cert);
+    chain.extend_from_slice(&sign_chain[1..]);
+    verify_sm2_certificate_chain(chain.as_slice(), trust_anchors, time)
+}
\ No newline at end of file
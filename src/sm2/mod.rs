@@ -0,0 +1,16 @@
+//! SM2 digital signature algorithm
+//!
+//! GB/T 32918.1-2016, GB/T 32918.2-2016, GB/T 32918.5-2017  
+//! GM/T 0009-2012(the default user identity), GM/T 0015-2012(the certificate profile)
+
+mod sm2;
+pub use sm2::{SM2, compute_za, SM2_DEFAULT_ID};
+
+mod cert;
+pub use cert::{Sm2Certificate, sm2_spki_der, sm2_public_key_from_spki_der, verify_sm2_certificate_chain,
+    verify_tlcp_certificates, OID_SM2P256V1, OID_SM2_WITH_SM3, KEY_USAGE_DIGITAL_SIGNATURE, KEY_USAGE_NON_REPUDIATION,
+    KEY_USAGE_KEY_ENCIPHERMENT, KEY_USAGE_DATA_ENCIPHERMENT, KEY_USAGE_KEY_AGREEMENT, KEY_USAGE_KEY_CERT_SIGN,
+    KEY_USAGE_CRL_SIGN, KEY_USAGE_ENCIPHER_ONLY, KEY_USAGE_DECIPHER_ONLY};
+
+#[cfg(test)]
+mod sm2_test;
\ No newline at end of file
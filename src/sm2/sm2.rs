@@ -0,0 +1,271 @@
+use rmath::bigint::BigInt;
+use rmath::rand::IterSource;
+use crate::elliptic::{EllipticCurve, KeyPair, PublicKey, PrivateKey, CurveParams, ScalarField};
+use crate::ecdsa::csp_rng::CSPRng;
+use crate::dsa::SignatureContent;
+use crate::sha::SHA512;
+use crate::{SM3, Digest, Signature, CryptoError, CryptoErrorKind};
+use crate::numeric::{ct_mod_inverse, SecretScalar};
+
+/// the default user identity of the GM/T 0009-2012, it's used when the user identity isn't agreed
+pub const SM2_DEFAULT_ID: &[u8] = b"1234567812345678";
+
+/// the `len` bytes big-endian encoding of the field element
+fn to_fixed_be(x: &BigInt, len: usize, buf: &mut Vec<u8>) {
+    let b = x.to_be_bytes();
+    let b = if b.len() > len { &b[(b.len() - len)..] } else { b.as_slice() };
+    buf.resize(buf.len() + len - b.len(), 0);
+    buf.extend_from_slice(b);
+}
+
+/// ZA = SM3(ENTL || ID || a || b || xG || yG || xA || yA)(GB/T 32918.2 5.5), the `ENTL` is the 2 bytes bit length
+/// of the `id`, so the `id` must be less than 8192 bytes.
+pub fn compute_za(curve: &CurveParams, id: &[u8], pk: &PublicKey) -> Result<Vec<u8>, CryptoError> {
+    if id.len() >= 8192 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+            format!("The SM2 user identity must be less than 8192 bytes, but got {} bytes", id.len())));
+    }
+
+    let len = (curve.field_bits_size() + 7) >> 3;
+    let mut buf = Vec::with_capacity(2 + id.len() + 6 * len);
+    buf.extend_from_slice(((id.len() << 3) as u16).to_be_bytes().as_ref());
+    buf.extend_from_slice(id);
+    let (a, (gx, gy)) = (curve.coefficient_a(), curve.base_point());
+    for x in [&a, curve.coefficient_b(), gx, gy, &pk.qx, &pk.qy].iter() {
+        to_fixed_be(x, len, &mut buf);
+    }
+
+    let (mut sm3, mut za) = (SM3::new(), Vec::with_capacity(32));
+    sm3.write(buf.as_slice());
+    sm3.checksum(&mut za);
+    Ok(za)
+}
+
+/// SM2 digital signature algorithm
+/// GB/T 32918.2-2016
+///
+/// the message `M` is signed as the `e = SM3(ZA || M)`, the `ZA` binds the user identity, the curve and the public
+/// key(see the `compute_za`), so the signer and the verifier must agree on the user identity. The nonce `k` is derived
+/// from the private key, the entropy and the `e` like the `ECDSA`, it isn't the `k` of the standard's test vectors.
+pub struct SM2<R, C> {
+    curve: C,
+    rd: R,
+    kp: KeyPair,
+    id: Vec<u8>,
+    za: Vec<u8>,
+    sm3: SM3,
+    md: SHA512,
+    // (1 + d)^{-1} mod n
+    d_inv: Option<SecretScalar>,
+    hash_buf: Vec<u8>,
+}
+
+impl<R, C> SM2<R, C>
+    where R: IterSource<u32>, C: EllipticCurve {
+    /// the public key is validated by the `dh::validate_ec_public_key`, and the private key must be in the range
+    /// [1, n-2](GB/T 32918.1 6.1).
+    pub fn new(rd: R, curve: C, key_pair: KeyPair, id: &[u8]) -> Result<Self, CryptoError> {
+        crate::dh::validate_ec_public_key(&curve, key_pair.public_key())?;
+        let cp = curve.curve_params();
+        let za = compute_za(cp, id, key_pair.public_key())?;
+
+        let d_inv = match key_pair.private_key() {
+            Some(k) => {
+                let nm1 = cp.base_point_order().clone() - BigInt::from(1u32);
+                let d = k.d.get();
+                if d.signnum() != Some(1) || d >= nm1 {
+                    return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "The SM2 private key must be in the range [1, n-2]"));
+                }
+                Some(SecretScalar::new(&Self::invert(cp, &(d + BigInt::from(1u32)))?))
+            },
+            None => None,
+        };
+
+        Ok(
+            Self {
+                curve,
+                rd,
+                kp: key_pair,
+                id: id.to_vec(),
+                za,
+                sm3: SM3::new(),
+                md: SHA512::new(),
+                d_inv,
+                hash_buf: Vec::with_capacity(32),
+            }
+        )
+    }
+
+    /// the `new` with the `SM2_DEFAULT_ID`
+    pub fn with_default_id(rd: R, curve: C, key_pair: KeyPair) -> Result<Self, CryptoError> {
+        Self::new(rd, curve, key_pair, SM2_DEFAULT_ID)
+    }
+
+    pub fn auto_generate_key(mut rd: R, curve: C, id: &[u8]) -> Result<Self, CryptoError> {
+        let params = curve.curve_params();
+        let mut buf = Vec::with_capacity((params.field_bits_size() >> 3) + 12);
+        for e in rd.iter_mut() {
+            buf.extend_from_slice(e.to_be_bytes().as_ref());
+            if buf.len() >= (params.field_bits_size() >> 3) + 8 {
+                break;
+            }
+        }
+        let d = Self::rand_scalar(params, buf.as_slice(), 2);
+        let (qx, qy) = curve.scalar_base_point(d.as_ref());
+        let pk = PrivateKey::new_uncheck(PublicKey { qx, qy }, &d);
+        Self::new(rd, curve, KeyPair::from(pk), id)
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        self.kp.public_key()
+    }
+
+    /// the user identity
+    pub fn id(&self) -> &[u8] {
+        self.id.as_slice()
+    }
+
+    /// the `ZA` of the user identity and the public key
+    pub fn za(&self) -> &[u8] {
+        self.za.as_slice()
+    }
+
+    /// (buf mod (n - sub)) + 1
+    fn rand_scalar(params: &CurveParams, buf: &[u8], sub: u32) -> BigInt {
+        let mut k = BigInt::from_be_bytes(buf);
+        k.rem_euclid_assign(params.base_point_order().clone() - BigInt::from(sub));
+        k += BigInt::from(1u32);
+        k
+    }
+
+    fn invert(params: &CurveParams, k: &BigInt) -> Result<BigInt, CryptoError> {
+        let n = params.base_point_order();
+        match ScalarField::new(n) {
+            Some(f) => f.invert(k),
+            None => ct_mod_inverse(k, n),
+        }
+    }
+
+    /// e = SM3(ZA || M)
+    fn hash_message(&mut self, message: &[u8]) {
+        self.sm3.reset();
+        self.sm3.write(self.za.as_slice());
+        self.sm3.write(message);
+        self.sm3.checksum(&mut self.hash_buf);
+    }
+
+    /// r = (e + x1) mod n, s = (1 + d)^{-1}(k - rd) mod n, `None` means that the `k` should be regenerated
+    fn sign_with_k(&self, e: &BigInt, k: &BigInt) -> Option<(BigInt, BigInt)> {
+        let (n, d, d_inv) = match (self.kp.private_key(), self.d_inv.as_ref()) {
+            (Some(pk), Some(d_inv)) => (self.curve.curve_params().base_point_order(), pk.d.get(), d_inv.get()),
+            _ => return None,
+        };
+
+        let (x1, _) = self.curve.scalar_base_point(k.as_ref());
+        let mut r = e.clone() + x1;
+        r.rem_euclid_assign(n.clone());
+        if r.signnum() != Some(1) || &(r.clone() + k.clone()) == n {
+            return None;
+        }
+
+        let mut rd = r.clone() * d;
+        rd.rem_euclid_assign(n.clone());
+        let mut s = (k.clone() + n.clone() - rd) * d_inv;
+        s.rem_euclid_assign(n.clone());
+        if s.signnum() == Some(1) {
+            Some((r, s))
+        } else {
+            None
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn sign_with_nonce(&mut self, message: &[u8], k: &BigInt) -> Option<(BigInt, BigInt)> {
+        self.hash_message(message);
+        let e = BigInt::from_be_bytes(self.hash_buf.as_slice());
+        self.sign_with_k(&e, k)
+    }
+
+    fn sign_inner(&mut self) -> Result<(BigInt, BigInt), CryptoError> {
+        let d_bytes = self.kp.private_key().ok_or(CryptoError::new(CryptoErrorKind::InvalidPrivateKey, "Public key cannot used to sign"))?
+            .d.get().to_be_bytes();
+
+        let mut entropy = Vec::with_capacity(36);
+        for e in self.rd.iter_mut() {
+            entropy.extend_from_slice(e.to_be_bytes().as_ref());
+            if entropy.len() >= 32 {
+                break;
+            }
+        }
+        entropy.truncate(32);
+
+        // the hedged nonce: the key of the AES-CTR is the SHA-512(d || entropy || e)
+        self.md.reset();
+        self.md.write(d_bytes.as_slice());
+        self.md.write(entropy.as_slice());
+        self.md.write(self.hash_buf.as_slice());
+        let mut key = entropy;
+        self.md.checksum(&mut key);
+        key.truncate(32);
+        let mut csprng = CSPRng::new(key, b"IV for SM2 k CTR".to_vec())?;
+
+        let params = self.curve.curve_params();
+        let (e, b) = (BigInt::from_be_bytes(self.hash_buf.as_slice()), (params.field_bits_size() >> 3) + 8);
+        let mut buf = Vec::with_capacity(b);
+        loop {
+            csprng.read_full(&mut buf, b)?;
+            let k = Self::rand_scalar(params, buf.as_slice(), 1);
+            if let Some(rs) = self.sign_with_k(&e, &k) {
+                break Ok(rs);
+            }
+        }
+    }
+
+    fn verify_inner(&self, r: &BigInt, s: &BigInt) -> Result<(), CryptoError> {
+        let (c, pk) = (self.curve.curve_params(), self.kp.public_key());
+        let n = c.base_point_order();
+        if r.signnum() != Some(1) || s.signnum() != Some(1) || r >= n || s >= n {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The signature (r, s) is out of the range [1, n-1]"));
+        }
+
+        // t = (r + s) mod n, (x1, y1) = sG + tP
+        let mut t = r.clone() + s.clone();
+        t.rem_euclid_assign(n.clone());
+        if t.signnum() != Some(1) {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The r + s is 0 modulo n"));
+        }
+
+        let (x1, y1) = self.curve.scalar_base_point(s.as_ref());
+        let (x2, y2) = self.curve.scalar(&pk.qx, &pk.qy, t.as_ref());
+        let (x, y) = self.curve.add(&x1, &y1, &x2, &y2);
+        if x.is_nan() || y.is_nan() || (x.signnum() != Some(1) && y.signnum() != Some(1)) {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The sG + tP is the infinite point"));
+        }
+
+        let mut v = BigInt::from_be_bytes(self.hash_buf.as_slice()) + x;
+        v.rem_euclid_assign(n.clone());
+        if &v == r {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, ""))
+        }
+    }
+}
+
+impl<R, C> Signature<SignatureContent> for SM2<R, C>
+    where R: IterSource<u32>, C: EllipticCurve {
+    type Output = ();
+
+    fn sign(&mut self, signature: &mut SignatureContent, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.hash_message(message);
+        let (r, s) = self.sign_inner()?;
+        signature.set(r, s);
+        Ok(())
+    }
+
+    fn verify(&mut self, signature: &SignatureContent, message: &[u8]) -> Result<Self::Output, CryptoError> {
+        self.hash_message(message);
+        let (r, s) = signature.to_bigint();
+        self.verify_inner(&r, &s)
+    }
+}
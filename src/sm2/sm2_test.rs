@@ -0,0 +1,243 @@
+use std::str::FromStr;
+use rmath::bigint::BigInt;
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::elliptic::{CurveParams, CurveDomain, EllipticCurve, KeyPair, PrivateKey, PublicKey};
+use crate::sm2::{SM2, SM2_DEFAULT_ID, Sm2Certificate, sm2_spki_der, verify_sm2_certificate_chain, verify_tlcp_certificates,
+    KEY_USAGE_DIGITAL_SIGNATURE, KEY_USAGE_KEY_CERT_SIGN, KEY_USAGE_CRL_SIGN};
+use crate::dsa::SignatureContent;
+use crate::{Signature, CryptoErrorKind};
+use crate::test_util::hex;
+
+fn bi(s: &str) -> BigInt {
+    BigInt::from_str(format!("0x{}", s).as_str()).unwrap()
+}
+
+fn root_cert() -> Vec<u8> {
+    hex(concat!(
+        "308201783082011ea003020102020101300a06082a811ccf5501837530133111300f06035504030c08534d3220526f6f74301e170d32363130",
+        "31343030303030305a170d3336313031343030303030305a30133111300f06035504030c08534d3220526f6f743059301306072a8648ce3d02",
+        "0106082a811ccf5501822d0342000473118ca0a333d29dc4b3867ccb40e9607937fc59f6557380a1f6a43987fef1b74fb19e8a0c5483839b0b",
+        "7c1e6ed427f885fe4a9f8f4be0e44de9942b989a3c2fa3633061301d0603551d0e041604142821a20b34e4c939149cbd5e9693342faa7ab439",
+        "301f0603551d230418301680142821a20b34e4c939149cbd5e9693342faa7ab439300f0603551d130101ff040530030101ff300e0603551d0f",
+        "0101ff040403020106300a06082a811ccf550183750348003045022100e5004f34194dac4b054a821f3a0cb35507301a9026f5d9c89deee1ab",
+        "0c7ff64b02201979871bac721ccd2474bd5e9cbe65442ab7f5ad0d5719605e144672d60dcc2e",
+    ))
+}
+
+fn sub_cert() -> Vec<u8> {
+    hex(concat!(
+        "308201773082011da003020102020102300a06082a811ccf5501837530133111300f06035504030c08534d3220526f6f74301e170d32363130",
+        "31343030303030305a170d3336313031343030303030305a30123110300e06035504030c07534d32207375623059301306072a8648ce3d0201",
+        "06082a811ccf5501822d03420004ff800d76c2c28e31531af7d76d7c3db7f60daf39127c5d2d3a5f97cc7aaf0fd5fd8c2abc8f3c5b4f64caaf",
+        "cc487020ac02add6c67357713e2c11ef079b08368aa3633061300f0603551d130101ff040530030101ff300e0603551d0f0101ff0404030201",
+        "06301d0603551d0e041604143dd1c4a0c895a0c47ea28b9262388b8bd7515a35301f0603551d230418301680142821a20b34e4c939149cbd5e",
+        "9693342faa7ab439300a06082a811ccf550183750348003045022100885f551f8807b4f5034e3c5e24d1120d6abff42d2ee4bc6cb4f9030a42",
+        "1e89830220100e947b706eca29e658ed9ac407a925b624678f4e6578246d68e146cf8f626e",
+    ))
+}
+
+fn sign_cert() -> Vec<u8> {
+    hex(concat!(
+        "308201733082011aa003020102020103300a06082a811ccf5501837530123110300e06035504030c07534d3220737562301e170d3236313031",
+        "343030303030305a170d3336313031343030303030305a30133111300f06035504030c08534d32207369676e3059301306072a8648ce3d0201",
+        "06082a811ccf5501822d03420004376801115087b0c516e1935b3a81801fe15b5f7dd0db9948c448df217ec5f1222a8c3de3b81f7035fbbf39",
+        "d3b231dc6cb5cd435313b1f32039221097ecf86c4da360305e300c0603551d130101ff04023000300e0603551d0f0101ff040403020780301d",
+        "0603551d0e04160414be896ed8eb6fdb5219203852500add2eddb50352301f0603551d230418301680143dd1c4a0c895a0c47ea28b9262388b",
+        "8bd7515a35300a06082a811ccf55018375034700304402205527f8780535bbfa9546438c6cb067a267f8dea8a8e87f6ba4fc1f7d0bd5b06c02",
+        "20655caf7311ea5b27a2b3b89b0d2cc6860d45d9b38b8717433a10c0a89e5ec346",
+    ))
+}
+
+fn enc_cert() -> Vec<u8> {
+    hex(concat!(
+        "3082017330820119a003020102020103300a06082a811ccf5501837530123110300e06035504030c07534d3220737562301e170d3236313031",
+        "343030303030305a170d3336313031343030303030305a30123110300e06035504030c07534d3220656e633059301306072a8648ce3d020106",
+        "082a811ccf5501822d03420004f1f241cd88a34af6f82b1fa537c5999026aa94c066814e91c35ac19e5e41b3530120b29eab10df67bbf344f0",
+        "e168f2e53f05b7114e5514a24c4ee82d02b86248a360305e300c0603551d130101ff04023000300e0603551d0f0101ff040403020338301d06",
+        "03551d0e04160414191ba19900516f3fc68d0a452460c4cf9fb8a63b301f0603551d230418301680143dd1c4a0c895a0c47ea28b9262388b8b",
+        "d7515a35300a06082a811ccf550183750348003045022100bd05442ffeac33451ffa792afcfafe7488c537cea41268cbb7527dd32625c96302",
+        "20459b4ca664240dabd036922e9b3a4eccfeb8077762090d87a1f37fd656717c12",
+    ))
+}
+
+// the expired signing certificate issued by the sub CA, 2020-01-01 to 2021-01-01
+fn old_cert() -> Vec<u8> {
+    hex(concat!(
+        "3082017430820119a003020102020104300a06082a811ccf5501837530123110300e06035504030c07534d3220737562301e170d3230303130",
+        "313030303030305a170d3231303130313030303030305a30123110300e06035504030c07534d32206f6c643059301306072a8648ce3d020106",
+        "082a811ccf5501822d034200049f5e4f2c82ced8d179ba23346873bfba3f583354843e425ba8edbe7d59bee2691e7aa4fd324fda7f409222aa",
+        "d9f169d93ee8817183460d2185a0bdb79e5bb3cca360305e300c0603551d130101ff04023000300e0603551d0f0101ff040403020780301d06",
+        "03551d0e041604146f16386ab107390e813fec40884fb4b0ed6e45e0301f0603551d230418301680143dd1c4a0c895a0c47ea28b9262388b8b",
+        "d7515a35300a06082a811ccf55018375034900304602210089bac16f7ae4721cf6928ed835618233dafa8f20f96a8d6e40a452c3f9b3c84702",
+        "2100cee336919673a96a81839179368ab1996c8e6bf2ba320e0252e8e9dd1089944a",
+    ))
+}
+
+// the end-entity certificate issued by the signing certificate
+fn leaf_cert() -> Vec<u8> {
+    hex(concat!(
+        "308201753082011ba003020102020105300a06082a811ccf5501837530133111300f06035504030c08534d32207369676e301e170d32363130",
+        "31343030303030305a170d3336313031343030303030305a30133111300f06035504030c08534d32206c6561663059301306072a8648ce3d02",
+        "0106082a811ccf5501822d0342000445577705142893c6ab65b5f7607061048ec65a5b61cdddce1e1258143c5fb090d46ab34b25c598d32cd6",
+        "f3f43071ba0ac5effad1ec5978918d3a9b09fd4735e0a360305e300c0603551d130101ff04023000300e0603551d0f0101ff04040302078030",
+        "1d0603551d0e0416041450be6392c160b069d7df7cb3ae5fe0d7b5fb9abb301f0603551d23041830168014be896ed8eb6fdb5219203852500a",
+        "dd2eddb50352300a06082a811ccf550183750348003045022048412985d4bb762d9c3a82d66449761d79a3b09b15741b9cbb0177f123bb5702",
+        "022100c83f47bc7d24f61ee703df65eda0168aa590ccd1f267058b2f0c3d08509180ef",
+    ))
+}
+
+// the certificate with the unrecognized critical extension 1.2.3.4
+fn crit_cert() -> Vec<u8> {
+    hex(concat!(
+        "3082018330820129a003020102020106300a06082a811ccf5501837530123110300e06035504030c07534d3220737562301e170d3236313031",
+        "343030303030305a170d3336313031343030303030305a30133111300f06035504030c08534d32206c6561663059301306072a8648ce3d0201",
+        "06082a811ccf5501822d0342000445577705142893c6ab65b5f7607061048ec65a5b61cdddce1e1258143c5fb090d46ab34b25c598d32cd6f3",
+        "f43071ba0ac5effad1ec5978918d3a9b09fd4735e0a36f306d300c0603551d130101ff04023000300e0603551d0f0101ff040403020780300d",
+        "06032a03040101ff04030c0178301d0603551d0e0416041450be6392c160b069d7df7cb3ae5fe0d7b5fb9abb301f0603551d23041830168014",
+        "3dd1c4a0c895a0c47ea28b9262388b8bd7515a35300a06082a811ccf55018375034800304502202fd35e1b94791e63e1981fdc316260587477",
+        "25a2f1a429228a1dd1276b8e7439022100ce946ffaf2f901bbab329933f27ba9e31fe0e10102b7b3ee03ef295fe478a1aa",
+    ))
+}
+
+fn key_pair<C: EllipticCurve>(curve: &C, d: &BigInt) -> KeyPair {
+    let (qx, qy) = curve.scalar_base_point(d.as_ref());
+    KeyPair::from(PrivateKey::new_uncheck(PublicKey::new_uncheck(&qx, &qy), d))
+}
+
+#[test]
+fn sm2_sign_vectors() {
+    let rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+
+    // GB/T 32918.2-2016 Appendix A.2, the Fp-256 example curve
+    let curve = CurveParams::new_uncheck_with_a("GB/T 32918 Fp-256", &CurveDomain {
+        p: &bi("8542d69e4c044f18e8b92435bf6ff7de457283915c45517d722edb8b08f1dfc3"),
+        n: &bi("8542d69e4c044f18e8b92435bf6ff7dd297720630485628d5ae74ee7c32e79b7"),
+        h: &BigInt::from(1u32),
+        a: &bi("787968b4fa32c3fd2417842e73bbfeff2f3c848b6831d7e0ec65228b3937e498"),
+        b: &bi("63e4c6d3b23b0c849cf84241484bfe48f61d59a5b16ba06e6e12d1da27c5249a"),
+        gx: &bi("421debd61b62eab6746434ebc3cc315e32220b3badd50bdc4c4e6c147fedd43d"),
+        gy: &bi("0680512bcbb42c07d47349d2153b70c4e5d7fdfcbfa36ea1a85841b9e46e09a2"),
+    });
+    let kp = key_pair(&curve, &bi("128b2fa8bd433c6c068c8d803dff79792a519a55171b1b650c23661d15897263"));
+    assert_eq!(kp.public_key().qx, bi("0ae4c7798aa0f119471bee11825be46202bb79e2a5844495e97c04ff4df2548a"));
+    let mut sm2 = SM2::new(rd.clone(), curve, kp, b"ALICE123@YAHOO.COM").unwrap();
+    assert_eq!(BigInt::from_be_bytes(sm2.za()), bi("f4a38489e32b45b6f876e3ac2168ca392362dc8f23459c1d1146fc3dbfb7bc9a"));
+    let (r, s) = sm2.sign_with_nonce(b"message digest", &bi("6cb28d99385c175c94f94e934817663fc176d925dd72b727260dbaae1fb2f96f")).unwrap();
+    assert_eq!(r, bi("40f1ec59f793d9f49e09dcef49130d4194f79fb1eed2caa55bacdb49c4e755d1"));
+    assert_eq!(s, bi("6fc6dac32c5d5cf10c77dfb20f7c2eb667a457872fb09ec56327a67ec7deebe7"));
+    let sig = SignatureContent::form_bigint(&r, &s);
+    sm2.verify(&sig, b"message digest").unwrap();
+    assert!(sm2.verify(&sig, b"message digesT").is_err());
+
+    // GM/T 0003.5-2012, the sm2p256v1 with the default user identity
+    let curve = CurveParams::sm2p256v1().unwrap();
+    let kp = key_pair(&curve, &bi("3945208f7b2144b13f36e38ac6d39f95889393692860b51a42fb81ef4df7c5b8"));
+    assert_eq!(kp.public_key().qx, bi("09f9df311e5421a150dd7d161e4bc5c672179fad1833fc076bb08ff356f35020"));
+    let mut sm2 = SM2::with_default_id(rd, curve, kp).unwrap();
+    assert_eq!(sm2.id(), SM2_DEFAULT_ID);
+    let (r, s) = sm2.sign_with_nonce(b"message digest", &bi("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21")).unwrap();
+    assert_eq!(r, bi("f5a03b0648d2c4630eeac513e1bb81a15944da3827d5b74143ac7eaceee720b3"));
+    assert_eq!(s, bi("b1b6aa29df212fd8763182bc0d421ca1bb9038fd1f7f42d4840b69c485bbc1aa"));
+    sm2.verify(&SignatureContent::form_bigint(&r, &s), b"message digest").unwrap();
+}
+
+#[test]
+fn sm2_sign_verify() {
+    let rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let curve = CurveParams::sm2p256v1().unwrap();
+    let mut signer = SM2::auto_generate_key(rd.clone(), curve.clone(), b"alice@rcrypto").unwrap();
+    assert!(curve.is_on_curve(&signer.public_key().qx, &signer.public_key().qy));
+
+    let (mut sig, msg) = (SignatureContent::new(), b"the SM2 signature with the SM3 digest".to_vec());
+    signer.sign(&mut sig, msg.as_slice()).unwrap();
+    signer.verify(&sig, msg.as_slice()).unwrap();
+
+    // the verifier needs the public key and the same user identity
+    let pk = KeyPair::from(signer.public_key().clone());
+    let mut verifier = SM2::new(rd.clone(), curve.clone(), pk.clone(), b"alice@rcrypto").unwrap();
+    verifier.verify(&sig, msg.as_slice()).unwrap();
+    assert_eq!(verifier.sign(&mut sig, msg.as_slice()).err().unwrap().kind(), CryptoErrorKind::InvalidPrivateKey);
+    let mut verifier = SM2::with_default_id(rd.clone(), curve.clone(), pk).unwrap();
+    assert_eq!(verifier.verify(&sig, msg.as_slice()).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+
+    let (r, s) = sig.to_bigint();
+    assert!(signer.verify(&SignatureContent::form_bigint(&s, &r), msg.as_slice()).is_err());
+    assert!(signer.verify(&SignatureContent::form_bigint(&r, &(curve.base_point_order().clone() - s)), msg.as_slice()).is_err());
+
+    // the private key n-1 isn't allowed, the 1 + d is not invertible
+    let d = curve.base_point_order().clone() - BigInt::from(1u32);
+    assert_eq!(SM2::new(rd.clone(), curve.clone(), key_pair(&curve, &d), SM2_DEFAULT_ID).err().unwrap().kind(), CryptoErrorKind::InvalidPrivateKey);
+    assert!(SM2::new(rd, curve.clone(), key_pair(&curve, &BigInt::from(7u32)), vec![0u8; 8192].as_slice()).is_err());
+}
+
+#[test]
+fn sm2_certificate_chain() {
+    let (root, sub, sign, enc, old, leaf) = (root_cert(), sub_cert(), sign_cert(), enc_cert(), old_cert(), leaf_cert());
+    let (root_c, sub_c, sign_c) = (Sm2Certificate::from_der(root.as_slice()).unwrap(),
+        Sm2Certificate::from_der(sub.as_slice()).unwrap(), Sm2Certificate::from_der(sign.as_slice()).unwrap());
+    assert!(root_c.is_self_issued() && !sub_c.is_self_issued());
+    root_c.verify_signed_by(root_c.public_key()).unwrap();
+    sub_c.verify_issued_by(&root_c).unwrap();
+    sign_c.verify_issued_by(&sub_c).unwrap();
+    assert!(sign_c.verify_issued_by(&root_c).is_err());
+    assert_eq!(sm2_spki_der(sign_c.public_key()).unwrap().as_slice(), sign_c.spki_der());
+
+    // the validity 20261014000000Z to 20361014000000Z, and the extensions
+    assert_eq!((sign_c.not_before(), sign_c.not_after()), (1791936000, 2107555200));
+    assert!(root_c.is_issuer() && sub_c.is_issuer() && !sign_c.is_ca() && !sign_c.is_issuer());
+    assert_eq!(sub_c.key_usage(), Some(KEY_USAGE_KEY_CERT_SIGN | KEY_USAGE_CRL_SIGN));
+    assert_eq!(sign_c.key_usage(), Some(KEY_USAGE_DIGITAL_SIGNATURE));
+    let old_c = Sm2Certificate::from_der(old.as_slice()).unwrap();
+    assert_eq!((old_c.not_before(), old_c.not_after()), (1577836800, 1609459200));
+
+    let (anchors, now) = ([root.as_slice()], 1800000000);
+    verify_sm2_certificate_chain(&[sign.as_slice(), sub.as_slice()], &anchors, now).unwrap();
+    verify_sm2_certificate_chain(&[sign.as_slice(), sub.as_slice(), root.as_slice()], &anchors, now).unwrap();
+    verify_sm2_certificate_chain(&[sign.as_slice()], &[sub.as_slice()], now).unwrap();
+    assert!(verify_sm2_certificate_chain(&[sign.as_slice()], &anchors, now).is_err());
+    assert!(verify_sm2_certificate_chain(&[sign.as_slice(), sub.as_slice()], &[sign.as_slice()], now).is_err());
+    assert!(verify_sm2_certificate_chain(&[], &anchors, now).is_err());
+
+    // out of the validity period, the expired certificate
+    verify_sm2_certificate_chain(&[sign.as_slice(), sub.as_slice()], &anchors, 1791936000).unwrap();
+    for &t in [1791935999, 2107555201].iter() {
+        let e = verify_sm2_certificate_chain(&[sign.as_slice(), sub.as_slice()], &anchors, t).unwrap_err();
+        assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+    }
+    verify_sm2_certificate_chain(&[old.as_slice(), sub.as_slice()], &anchors, 1600000000).unwrap_err();
+    let e = verify_sm2_certificate_chain(&[old.as_slice(), sub.as_slice()], &anchors, now).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+
+    // the end-entity certificate acts as the intermediate CA or the trust anchor
+    let leaf_c = Sm2Certificate::from_der(leaf.as_slice()).unwrap();
+    leaf_c.verify_issued_by(&sign_c).unwrap();
+    let e = verify_sm2_certificate_chain(&[leaf.as_slice(), sign.as_slice(), sub.as_slice()], &anchors, now).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+    let e = verify_sm2_certificate_chain(&[leaf.as_slice()], &[sign.as_slice()], now).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+
+    // the TLCP signing and encryption certificates
+    verify_tlcp_certificates(&[sign.as_slice(), sub.as_slice()], enc.as_slice(), &anchors, now).unwrap();
+    assert!(verify_tlcp_certificates(&[sign.as_slice(), sub.as_slice()], sub.as_slice(), &anchors, now).is_err());
+    let e = verify_tlcp_certificates(&[enc.as_slice(), sub.as_slice()], sign.as_slice(), &anchors, now).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+    let e = verify_tlcp_certificates(&[sign.as_slice(), sub.as_slice()], sign.as_slice(), &anchors, now).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+    let e = verify_tlcp_certificates(&[sign.as_slice(), sub.as_slice()], enc.as_slice(), &anchors, 2107555201).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+
+    // the tampered TBSCertificate
+    let mut tampered = sign.clone();
+    let pos = tampered.windows(8).position(|w| w == b"SM2 sign").unwrap();
+    tampered[pos + 7] = b'x';
+    assert_eq!(verify_sm2_certificate_chain(&[tampered.as_slice(), sub.as_slice()], &anchors, now).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+
+    // the signature isn't the SM2-with-SM3
+    let mut ecdsa = sign.clone();
+    let pos = ecdsa.windows(8).rposition(|w| w == [0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x83, 0x75]).unwrap();
+    ecdsa[pos + 7] = 0x76;
+    assert_eq!(Sm2Certificate::from_der(ecdsa.as_slice()).err().unwrap().kind(), CryptoErrorKind::NotSupportUsage);
+
+    // the unrecognized critical extension
+    assert_eq!(Sm2Certificate::from_der(crit_cert().as_slice()).err().unwrap().kind(), CryptoErrorKind::NotSupportUsage);
+}
\ No newline at end of file
@@ -30,6 +30,14 @@ impl SM3 {
         }
     }
     
+    /// the one-shot digest of the `data`
+    pub fn digest(data: &[u8]) -> Vec<u8> {
+        let (mut h, mut d) = (Self::new(), Vec::with_capacity(32));
+        h.write(data);
+        h.checksum(&mut d);
+        d
+    }
+    
     #[inline]
     const fn t_j(round_idx: usize) -> u32 {
         if round_idx < 16 {
@@ -114,6 +122,12 @@ impl SM3 {
 }
 
 
+impl Default for SM3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Digest for SM3 {
     fn block_size(&self) -> Option<usize> {
         Some(64)
@@ -80,13 +80,53 @@ impl SM4 {
         Self::from_slice(key.as_ref()).unwrap()
     }
     
+    /// the expanded round keys, the `rk[i]` is the round key of the `i`th encryption round.
+    /// They're as secret as the key, e.g. they can be cached in the shared memory of the worker processes.
+    #[cfg(feature = "keyschedule")]
+    pub fn round_keys(&self) -> [u32; 32] {
+        *self.get_rk_ref()
+    }
+
+    /// the SM4 by the pre-expanded round keys which are exported by the `round_keys`
+    #[cfg(feature = "keyschedule")]
+    pub fn from_round_keys(rk: [u32; 32]) -> SM4 {
+        SM4 {
+            rk: Cell::new(rk)
+        }
+    }
+
+    /// encrypt the block by the first `rounds` rounds and then the reverse transformation,
+    /// it's for the round-reduced cryptanalysis, `rounds` must be in the `[1, 32]`.
+    #[cfg(feature = "keyschedule")]
+    pub fn encrypt_rounds(&self, dst: &mut Vec<u8>, plaintext_block: &[u8], rounds: usize) -> Result<usize, CryptoError> {
+        Self::check_rounds(rounds)?;
+        self.ed_inner(dst, plaintext_block, rounds, |rk: &[u32; 32], _r: usize, idx: usize| -> u32 {rk[idx]})
+    }
+
+    /// the inverse of the `encrypt_rounds`
+    #[cfg(feature = "keyschedule")]
+    pub fn decrypt_rounds(&self, dst: &mut Vec<u8>, cipher_block: &[u8], rounds: usize) -> Result<usize, CryptoError> {
+        Self::check_rounds(rounds)?;
+        self.ed_inner(dst, cipher_block, rounds, |rk: &[u32; 32], r: usize, idx: usize| -> u32 {rk[r-1-idx]})
+    }
+
+    #[cfg(feature = "keyschedule")]
+    fn check_rounds(rounds: usize) -> Result<(), CryptoError> {
+        if rounds == 0 || rounds > 32 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The rounds of SM4 must be in the [1, 32], but got {}", rounds)))
+        } else {
+            Ok(())
+        }
+    }
+    
     fn get_rk_ref(&self) -> &[u32; 32] {
         unsafe {
             & (*self.rk.as_ptr())
         }
     }
     
-    fn ed_inner(&self, dst: &mut Vec<u8>, data: &[u8], rk: fn(&[u32; 32], usize) -> u32) -> Result<usize, CryptoError> {
+    fn ed_inner(&self, dst: &mut Vec<u8>, data: &[u8], rounds: usize, rk: fn(&[u32; 32], usize, usize) -> u32) -> Result<usize, CryptoError> {
         if data.len() != SM4_BLOCK_SIZE {
             return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
                                         format!("The length of data block must be 16 in bytes")));
@@ -98,11 +138,11 @@ impl SM4 {
         x[2] = Self::u8_to_u32(data[8], data[9], data[10], data[11]);
         x[3] = Self::u8_to_u32(data[12], data[13], data[14], data[15]);
 
-        for i in 0..32 {
-            x[i + 4] = Self::round_f(x[i], x[i+1], x[i+2], x[i+3], rk(self.get_rk_ref(), i));
+        for i in 0..rounds {
+            x[i + 4] = Self::round_f(x[i], x[i+1], x[i+2], x[i+3], rk(self.get_rk_ref(), rounds, i));
         }
         dst.clear();
-        for i in (32..=35).rev() {
+        for i in (rounds..(rounds + 4)).rev() {
             dst.extend(x[i].to_be_bytes().iter());
         }
 
@@ -117,10 +157,10 @@ impl Cipher for SM4 {
     }
 
     fn encrypt(&self, dst: &mut Vec<u8>, plaintext_block: &[u8]) -> Result<usize, CryptoError> {
-        self.ed_inner(dst, plaintext_block, |rk: &[u32; 32], idx: usize| -> u32 {rk[idx]})
+        self.ed_inner(dst, plaintext_block, 32, |rk: &[u32; 32], _r: usize, idx: usize| -> u32 {rk[idx]})
     }
 
     fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<usize, CryptoError> {
-        self.ed_inner(dst, cipher_block, |rk: &[u32; 32], idx: usize| -> u32 {rk[31-idx]})
+        self.ed_inner(dst, cipher_block, 32, |rk: &[u32; 32], r: usize, idx: usize| -> u32 {rk[r-1-idx]})
     }
 }
\ No newline at end of file
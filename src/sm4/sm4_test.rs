@@ -42,4 +42,30 @@ fn sm4() {
         }
         assert_eq!(buf1.as_slice(), ele.0.as_ref(), "encrypt-case:{}", i);
     });
+}
+#[cfg(feature = "keyschedule")]
+#[test]
+fn sm4_round_keys() {
+    // GM/T 0002-2012 Appendix A.1
+    let key = [0x01u8, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
+    let sm4 = SM4::new(key);
+    let rk = sm4.round_keys();
+    assert_eq!((rk[0], rk[1], rk[31]), (0xf12186f9, 0x41662b61, 0x9124a012));
+
+    let other = SM4::from_round_keys(rk);
+    let (mut ct, mut buf) = (Vec::new(), Vec::new());
+    sm4.encrypt(&mut ct, key.as_ref()).unwrap();
+    other.encrypt(&mut buf, key.as_ref()).unwrap();
+    assert_eq!(ct, buf);
+
+    for rounds in 1..=32 {
+        sm4.encrypt_rounds(&mut ct, key.as_ref(), rounds).unwrap();
+        sm4.decrypt_rounds(&mut buf, ct.as_slice(), rounds).unwrap();
+        assert_eq!(buf.as_slice(), key.as_ref(), "rounds=>{}", rounds);
+    }
+    sm4.encrypt(&mut buf, key.as_ref()).unwrap();
+    assert_eq!(ct, buf);
+
+    assert!(sm4.encrypt_rounds(&mut ct, key.as_ref(), 0).is_err());
+    assert!(sm4.decrypt_rounds(&mut buf, key.as_ref(), 33).is_err());
 }
\ No newline at end of file
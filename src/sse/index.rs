@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use crate::{CryptoError, CryptoErrorKind, Digest, AES, HMAC};
+use crate::cipher_mode::SIV;
+use crate::sha::SHA256;
+
+/// the length of the master key, the keyword tag and the epoch key
+pub const SSE_KEY_LEN: usize = 32;
+const SSE_TAG_LEN: usize = 16;
+
+const SSE_DOMAIN_TAG: u8 = 0x00;
+const SSE_DOMAIN_EPOCH: u8 = 0x01;
+
+fn prf(key: &[u8], parts: &[&[u8]]) -> Result<Vec<u8>, CryptoError> {
+    let mut hmac = HMAC::new(key.to_vec(), SHA256::new())?;
+    parts.iter().for_each(|p| hmac.write(p));
+    let mut out = Vec::with_capacity(SSE_KEY_LEN);
+    hmac.checksum(&mut out);
+    Ok(out)
+}
+
+/// The encrypted index entry
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub address: Vec<u8>,
+    /// `V || C`, the synthetic IV(16 bytes) and the ciphertext
+    pub ciphertext: Vec<u8>,
+}
+
+/// The search token of the keyword, it contains the epoch keys and the number of the entries of every epoch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchToken {
+    epochs: Vec<(Vec<u8>, u32)>,
+}
+
+impl SearchToken {
+    /// (epoch key, the number of the entries)
+    pub fn epochs(&self) -> &[(Vec<u8>, u32)] {
+        self.epochs.as_slice()
+    }
+
+    /// the addresses of all the entries of the keyword
+    pub fn addresses(&self) -> Result<Vec<Vec<u8>>, CryptoError> {
+        let mut addrs = Vec::new();
+        for (key, count) in self.epochs.iter() {
+            for i in 0..*count {
+                addrs.push(prf(key.as_slice(), &[i.to_be_bytes().as_ref()])?);
+            }
+        }
+        Ok(addrs)
+    }
+}
+
+/// The client which holds the keys and the per-keyword counters
+///
+/// The counters must be persisted with the master key, the reused counter overwrites the previous entry. The entry is
+/// encrypted by the AES-SIV with the address as the associated data, so the reused counter(e.g. the stale counters
+/// are restored by the `set_counters`) only discloses whether the two ids are equal rather than the keystream.
+#[derive(Clone)]
+pub struct SSEClient {
+    tag_key: Vec<u8>,
+    siv: SIV<AES>,
+    /// keyword tag -> the number of the entries of every epoch, the last one is the current epoch
+    counters: HashMap<Vec<u8>, Vec<u32>>,
+}
+
+impl SSEClient {
+    /// the tag key and the entry key are derived from the 32 bytes `master_key`
+    pub fn new(master_key: &[u8]) -> Result<Self, CryptoError> {
+        if master_key.len() != SSE_KEY_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong master key length: {}, the SSE master key must be {} bytes", master_key.len(), SSE_KEY_LEN)));
+        }
+
+        let tag_key = prf(master_key, &[b"rcrypto sse tag key"])?;
+        let mac_key = prf(master_key, &[b"rcrypto sse entry mac key"])?;
+        let ctr_key = prf(master_key, &[b"rcrypto sse entry ctr key"])?;
+        Ok(Self {
+            tag_key,
+            siv: SIV::new(AES::new(mac_key)?, AES::new(ctr_key)?)?,
+            counters: HashMap::new(),
+        })
+    }
+
+    /// the deterministic tag of the `keyword`, the same keyword always has the same tag
+    pub fn keyword_tag(&self, keyword: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        prf(self.tag_key.as_slice(), &[&[SSE_DOMAIN_TAG], keyword])
+    }
+
+    fn epoch_key(&self, keyword: &[u8], epoch: u32) -> Result<Vec<u8>, CryptoError> {
+        prf(self.tag_key.as_slice(), &[&[SSE_DOMAIN_EPOCH], epoch.to_be_bytes().as_ref(), keyword])
+    }
+
+    /// the number of the entries of every epoch of the `keyword`
+    pub fn counters(&self, keyword: &[u8]) -> Result<Vec<u32>, CryptoError> {
+        Ok(self.counters.get(&self.keyword_tag(keyword)?).cloned().unwrap_or_default())
+    }
+
+    /// restore the persisted counters of the `keyword`
+    pub fn set_counters(&mut self, keyword: &[u8], counters: Vec<u32>) -> Result<(), CryptoError> {
+        let tag = self.keyword_tag(keyword)?;
+        self.counters.insert(tag, counters);
+        Ok(())
+    }
+
+    /// encrypt the `id`(e.g. the document id) to the new entry of the `keyword`
+    pub fn add(&mut self, keyword: &[u8], id: &[u8]) -> Result<IndexEntry, CryptoError> {
+        let tag = self.keyword_tag(keyword)?;
+        let (epoch, i) = self.counters.get(&tag).and_then(|c| c.last().map(|&i| (c.len() - 1, i))).unwrap_or((0, 0));
+        if i == u32::MAX {
+            return Err(CryptoError::new(CryptoErrorKind::LimitExceeded, "The number of the entries of the keyword exceeds 2^32-1"));
+        }
+
+        let address = prf(self.epoch_key(keyword, epoch as u32)?.as_slice(), &[i.to_be_bytes().as_ref()])?;
+        let mut ct = Vec::with_capacity(id.len() + SSE_TAG_LEN);
+        self.siv.encrypt(&mut ct, &[address.as_slice()], id)?;
+
+        let counters = self.counters.entry(tag).or_insert_with(|| vec![0]);
+        *counters.last_mut().unwrap() = i + 1;
+        Ok(IndexEntry { address, ciphertext: ct })
+    }
+
+    /// the search token of the `keyword`, the keyword is moved to the new epoch if it has the entries in the current epoch
+    pub fn search_token(&mut self, keyword: &[u8]) -> Result<SearchToken, CryptoError> {
+        let tag = self.keyword_tag(keyword)?;
+        let counters = self.counters.get(&tag).cloned().unwrap_or_default();
+        let mut epochs = Vec::with_capacity(counters.len());
+        for (e, &count) in counters.iter().enumerate() {
+            if count > 0 {
+                epochs.push((self.epoch_key(keyword, e as u32)?, count));
+            }
+        }
+
+        if counters.last().copied().unwrap_or(0) > 0 {
+            if counters.len() > (u32::MAX as usize) {
+                return Err(CryptoError::new(CryptoErrorKind::LimitExceeded, "The number of the epochs of the keyword exceeds 2^32"));
+            }
+            self.counters.entry(tag).or_default().push(0);
+        }
+        Ok(SearchToken { epochs })
+    }
+
+    /// decrypt the entry, the `VerificationFailed` is returned if the entry is tampered or moved to the other address
+    pub fn open(&self, entry: &IndexEntry) -> Result<Vec<u8>, CryptoError> {
+        if entry.address.len() != SSE_KEY_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong address length: {}, the SSE address must be {} bytes", entry.address.len(), SSE_KEY_LEN)));
+        }
+
+        let mut id = Vec::with_capacity(entry.ciphertext.len());
+        self.siv.decrypt(&mut id, &[entry.address.as_slice()], entry.ciphertext.as_slice())?;
+        Ok(id)
+    }
+}
+
+/// The server-side encrypted index, it only stores the addresses and the ciphertexts
+#[derive(Clone, Debug, Default)]
+pub struct SSEIndex {
+    entries: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl SSEIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn insert(&mut self, entry: IndexEntry) {
+        self.entries.insert(entry.address, entry.ciphertext);
+    }
+
+    /// the entries which match the `token`
+    pub fn search(&self, token: &SearchToken) -> Result<Vec<IndexEntry>, CryptoError> {
+        Ok(token.addresses()?.into_iter().filter_map(|address| {
+            self.entries.get(&address).map(|ct| IndexEntry { ciphertext: ct.clone(), address })
+        }).collect())
+    }
+}
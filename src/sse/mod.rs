@@ -0,0 +1,23 @@
+//! The searchable symmetric encryption(SSE) building block
+//!
+//! The client derives the deterministic keyword tag by the HMAC-SHA256, and encrypts the index entry(e.g. the
+//! document id) by the AES-256-SIV, the entry is stored at the pseudorandom address:
+//!
+//! ```text
+//! tag(w) = HMAC(K_t, 0x00 || w)
+//! K_{w,e} = HMAC(K_t, 0x01 || [e]^4 || w)
+//! address = HMAC(K_{w,e}, [i]^4), the i-th entry of the keyword w in the epoch e
+//! entry = AES-SIV(K_e, ad = address, id)
+//! ```
+//!
+//! The search token discloses the epoch keys $K_{w,e}$ to the server, and the client moves the keyword to a new epoch,
+//! so the entries which are added after the search cannot be linked to the previous search tokens(the forward privacy).
+//! The AES-SIV is nonce-free, so the address which is reused by the stale counters doesn't reuse the keystream.
+//! The server learns the access pattern and the search pattern, it's only a building block for the encrypted-search
+//! prototypes rather than the complete scheme.
+
+mod index;
+pub use index::{SSEClient, SSEIndex, IndexEntry, SearchToken, SSE_KEY_LEN};
+
+#[cfg(test)]
+mod sse_test;
\ No newline at end of file
@@ -0,0 +1,53 @@
+use crate::sse::{SSEClient, SSEIndex};
+use crate::CryptoErrorKind;
+
+#[test]
+fn sse_search() {
+    let mut client = SSEClient::new(&[0x42u8; 32]).unwrap();
+    let mut index = SSEIndex::new();
+    assert_eq!(client.keyword_tag(b"alice").unwrap(), client.keyword_tag(b"alice").unwrap());
+    assert_ne!(client.keyword_tag(b"alice").unwrap(), client.keyword_tag(b"bob").unwrap());
+    assert_ne!(client.keyword_tag(b"alice").unwrap(), SSEClient::new(&[0x43u8; 32]).unwrap().keyword_tag(b"alice").unwrap());
+
+    for (w, id) in [(&b"alice"[..], &b"doc1"[..]), (b"alice", b"doc2"), (b"bob", b"doc2")].iter() {
+        let entry = client.add(w, id).unwrap();
+        assert_ne!(entry.ciphertext.as_slice(), *id);
+        index.insert(entry);
+    }
+    assert_eq!(index.len(), 3);
+    assert_eq!(client.counters(b"alice").unwrap(), vec![2]);
+
+    let token = client.search_token(b"alice").unwrap();
+    let found = index.search(&token).unwrap().iter().map(|e| client.open(e).unwrap()).collect::<Vec<_>>();
+    assert_eq!(found, vec![b"doc1".to_vec(), b"doc2".to_vec()]);
+    assert_eq!(client.counters(b"alice").unwrap(), vec![2, 0]);
+
+    // the new entry is in the new epoch, so it isn't found by the previous token
+    index.insert(client.add(b"alice", b"doc3").unwrap());
+    assert_eq!(index.search(&token).unwrap().len(), 2);
+    let token2 = client.search_token(b"alice").unwrap();
+    assert_eq!(token2.epochs().len(), 2);
+    assert_eq!(index.search(&token2).unwrap().len(), 3);
+    assert_eq!(client.counters(b"alice").unwrap(), vec![2, 1, 0]);
+    assert_eq!(client.search_token(b"alice").unwrap(), client.search_token(b"alice").unwrap());
+    assert!(index.search(&client.search_token(b"carol").unwrap()).unwrap().is_empty());
+
+    // the restored client finds the same entries
+    let mut restored = SSEClient::new(&[0x42u8; 32]).unwrap();
+    restored.set_counters(b"bob", client.counters(b"bob").unwrap()).unwrap();
+    let entries = index.search(&restored.search_token(b"bob").unwrap()).unwrap();
+    assert_eq!(restored.open(&entries[0]).unwrap(), b"doc2".to_vec());
+
+    let mut entry = entries[0].clone();
+    entry.ciphertext[0] ^= 1;
+    assert_eq!(client.open(&entry).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(SSEClient::new(&[0u8; 16]).is_err());
+
+    // the stale counters reuse the address, the different ids don't share the keystream
+    let mut stale = SSEClient::new(&[0x42u8; 32]).unwrap();
+    let (e1, e2) = (stale.add(b"dave", b"doc1").unwrap(), client.add(b"dave", b"doc2").unwrap());
+    assert_eq!(e1.address, e2.address);
+    let x = e1.ciphertext.iter().zip(e2.ciphertext.iter()).map(|(a, b)| a ^ b).collect::<Vec<_>>();
+    assert_ne!(&x[16..], &[0, 0, 0, 2]);
+    assert_eq!(client.open(&e1).unwrap(), b"doc1".to_vec());
+}
@@ -0,0 +1,124 @@
+//! The conformance suite of the block ciphers, it checks the `Cipher` implementation by the ECB known answers and the
+//! error semantics which are shared by the block ciphers of the crate:
+//!
+//! - the key of the unsupported length is rejected by the `CryptoErrorKind::InvalidParameter`;
+//! - the `block_size` is `Some(n)`, the encryption/decryption of the `n` bytes block overwrites the `dst` with the
+//!   `n` bytes and returns the `n`;
+//! - the block of the other length is rejected by the `CryptoErrorKind::InvalidParameter` and the `dst` is unchanged;
+//! - the result doesn't depend on the alignment of the input block.
+
+use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::test_util::XorShiftRand;
+
+/// the seed of the random keys and blocks, the suite is deterministic
+const CONFORMANCE_SEED: u64 = 0x636f6e666f726d;
+/// the number of the random blocks of every key length in the round-trip check
+const ROUND_TRIP_BLOCKS: usize = 16;
+/// the maximum offset of the input block in the alignment check
+const MAX_OFFSET: usize = 8;
+
+/// The ECB known answer of the block cipher
+#[derive(Clone, Copy, Debug)]
+pub struct BlockCipherKat<'a> {
+    pub key: &'a [u8],
+    pub plaintext: &'a [u8],
+    pub ciphertext: &'a [u8],
+}
+
+/// check the block cipher `name` by the conformance suite, `new` creates the cipher from the key bytes, `key_lens`
+/// are all the supported key lengths in bytes, the other lengths up to `max(key_lens) + 8` must be rejected.
+///
+/// # Panics
+///
+/// This function panics with the violated rule if the cipher doesn't conform to the suite
+pub fn check_block_cipher<C, F>(name: &str, new: F, key_lens: &[usize], kats: &[BlockCipherKat])
+    where C: Cipher<Output = usize>, F: Fn(&[u8]) -> Result<C, CryptoError> {
+    assert!(!key_lens.is_empty(), "{}: no key length", name);
+    let mut rd = XorShiftRand::new(CONFORMANCE_SEED);
+    let mut random_bytes = |len: usize| {
+        let mut v = vec![0u8; len];
+        rd.fill_bytes(v.as_mut_slice());
+        v
+    };
+
+    let mut block_len = None;
+    for &k_len in key_lens.iter() {
+        let key = random_bytes(k_len);
+        let cipher = new(key.as_slice()).unwrap_or_else(|e| panic!("{}: the {} bytes key is rejected: {}", name, k_len, e));
+        let n = match cipher.block_size() {
+            Some(n) if n > 0 => n,
+            _ => panic!("{}: the block cipher must have the block size", name),
+        };
+        assert_eq!(*block_len.get_or_insert(n), n, "{}: the block size depends on the key length", name);
+
+        let blocks = (0..ROUND_TRIP_BLOCKS).map(|_| random_bytes(n)).collect::<Vec<_>>();
+        check_round_trip(name, &cipher, blocks.as_slice());
+        check_alignment(name, &cipher, random_bytes(n + MAX_OFFSET).as_slice());
+        check_length_error(name, &cipher);
+    }
+
+    let max_len = key_lens.iter().max().copied().unwrap_or(0);
+    for k_len in (0..=(max_len + 8)).filter(|l| !key_lens.contains(l)) {
+        match new(random_bytes(k_len).as_slice()) {
+            Ok(_) => panic!("{}: the {} bytes key is accepted", name, k_len),
+            Err(e) => assert_eq!(e.kind(), CryptoErrorKind::InvalidParameter, "{}: the {} bytes key", name, k_len),
+        }
+    }
+
+    for (i, kat) in kats.iter().enumerate() {
+        let cipher = new(kat.key).unwrap_or_else(|e| panic!("{}: the key of the KAT {} is rejected: {}", name, i, e));
+        let mut dst = Vec::new();
+        assert_eq!(cipher.encrypt(&mut dst, kat.plaintext).ok(), Some(kat.ciphertext.len()), "{}: encrypt the KAT {}", name, i);
+        assert_eq!(dst.as_slice(), kat.ciphertext, "{}: the ciphertext of the KAT {}", name, i);
+        assert_eq!(cipher.decrypt(&mut dst, kat.ciphertext).ok(), Some(kat.plaintext.len()), "{}: decrypt the KAT {}", name, i);
+        assert_eq!(dst.as_slice(), kat.plaintext, "{}: the plaintext of the KAT {}", name, i);
+    }
+}
+
+fn check_round_trip<C: Cipher<Output = usize>>(name: &str, cipher: &C, blocks: &[Vec<u8>]) {
+    let (mut ct, mut ct2, mut pt) = (Vec::new(), vec![0xa5u8; 3], vec![0xa5u8; 64]);
+    for block in blocks.iter() {
+        let n = block.len();
+        assert_eq!(cipher.encrypt(&mut ct, block.as_slice()).ok(), Some(n), "{}: encrypt the {} bytes block", name, n);
+        assert_eq!(cipher.encrypt(&mut ct2, block.as_slice()).ok(), Some(n), "{}: encrypt the {} bytes block", name, n);
+        assert_eq!(ct, ct2, "{}: the encryption isn't deterministic or the dst isn't overwritten", name);
+        assert_eq!(cipher.decrypt(&mut pt, ct.as_slice()).ok(), Some(n), "{}: decrypt the {} bytes block", name, n);
+        assert_eq!(&pt, block, "{}: the decryption doesn't recover the plaintext", name);
+    }
+}
+
+fn check_alignment<C: Cipher<Output = usize>>(name: &str, cipher: &C, buf: &[u8]) {
+    let n = buf.len() - MAX_OFFSET;
+    let (mut expect, mut dst) = (Vec::new(), Vec::new());
+    for off in 1..=MAX_OFFSET {
+        let block = buf[off..(off + n)].to_vec();
+        cipher.encrypt(&mut expect, block.as_slice()).unwrap();
+        cipher.encrypt(&mut dst, &buf[off..(off + n)]).unwrap();
+        assert_eq!(dst, expect, "{}: the encryption depends on the alignment, the offset: {}", name, off);
+        cipher.decrypt(&mut expect, block.as_slice()).unwrap();
+        cipher.decrypt(&mut dst, &buf[off..(off + n)]).unwrap();
+        assert_eq!(dst, expect, "{}: the decryption depends on the alignment, the offset: {}", name, off);
+    }
+}
+
+fn check_length_error<C: Cipher<Output = usize>>(name: &str, cipher: &C) {
+    let n = cipher.block_size().unwrap_or(0);
+    let mut lens = vec![0, 1, n - 1, n + 1, n << 1];
+    lens.sort_unstable();
+    lens.dedup();
+
+    let origin = vec![0xa5u8; 3];
+    for len in lens.into_iter().filter(|&l| l != n) {
+        let (data, mut dst) = (vec![0u8; len], origin.clone());
+        match cipher.encrypt(&mut dst, data.as_slice()) {
+            Ok(_) => panic!("{}: the {} bytes block is encrypted", name, len),
+            Err(e) => assert_eq!(e.kind(), CryptoErrorKind::InvalidParameter, "{}: encrypt the {} bytes block", name, len),
+        }
+        assert_eq!(dst, origin, "{}: the dst is changed by the failed encryption", name);
+        match cipher.decrypt(&mut dst, data.as_slice()) {
+            Ok(_) => panic!("{}: the {} bytes block is decrypted", name, len),
+            Err(e) => assert_eq!(e.kind(), CryptoErrorKind::InvalidParameter, "{}: decrypt the {} bytes block", name, len),
+        }
+        assert_eq!(dst, origin, "{}: the dst is changed by the failed decryption", name);
+    }
+}
@@ -0,0 +1,28 @@
+//! The test support utilities for the downstream crates
+//!
+//! The `XorShiftRand` is the weak seeded random number generator which can be passed to the randomized APIs
+//! (e.g. the key generation and the signing), and the `with_test_seed` prints the seed of the failed test so that
+//! the failure can be replayed by the `RCRYPTO_TEST_SEED`. They are only for the tests, never use them to
+//! generate the keys, the nonces and the other secrets.
+//!
+//! The `check_block_cipher` is the conformance suite of the block ciphers, the downstream implementation of the
+//! `Cipher` can be checked by the same known answer, round-trip, key length and block length rules as the ciphers
+//! of the crate.
+
+mod xorshift;
+pub use xorshift::XorShiftRand;
+
+mod seed;
+pub use seed::{TEST_SEED_ENV, test_seed, with_test_seed};
+
+mod conformance;
+pub use conformance::{BlockCipherKat, check_block_cipher};
+
+/// decode the hex string of the test vectors
+#[cfg(test)]
+pub(crate) fn hex(s: &str) -> Vec<u8> {
+    crate::encoding::Hex::lower().decode(s).unwrap()
+}
+
+#[cfg(test)]
+mod test_util_test;
\ No newline at end of file
@@ -0,0 +1,57 @@
+//! Capture and replay the seeds of the randomized tests
+
+use crate::test_util::XorShiftRand;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// the environment variable of the replayed seed, the decimal or the `0x` prefixed hexadecimal
+pub const TEST_SEED_ENV: &str = "RCRYPTO_TEST_SEED";
+
+static TEST_SEED_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub(super) fn parse_seed(s: &str) -> Option<u64> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(h) => u64::from_str_radix(h, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// the seed of the `TEST_SEED_ENV` if it's set, otherwise the new seed from the system time, every call returns
+/// the different seed in the same process.
+///
+/// # Panics
+///
+/// This function panics if the `TEST_SEED_ENV` is set but it's not a valid seed
+pub fn test_seed() -> u64 {
+    match std::env::var(TEST_SEED_ENV) {
+        Ok(s) => parse_seed(s.as_str()).unwrap_or_else(|| panic!("Invalid {}: {}", TEST_SEED_ENV, s)),
+        Err(_) => {
+            let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0);
+            let c = TEST_SEED_COUNTER.fetch_add(1, Ordering::Relaxed);
+            nanos ^ c.wrapping_mul(0x9e3779b97f4a7c15) ^ ((std::process::id() as u64) << 32)
+        }
+    }
+}
+
+/// run the test `f` with the `XorShiftRand` seeded by the `test_seed()`, the seed is printed to the stderr
+/// if the `f` panics, then the panic is resumed. The failure can be replayed by setting the `TEST_SEED_ENV`.
+///
+/// ```Rust
+/// with_test_seed("rsa_sign", |rd| {
+///     let key = PrivateKey::generate_key(1024, 19, rd).unwrap();
+///     ...
+/// });
+/// ```
+pub fn with_test_seed<T, F: FnOnce(&mut XorShiftRand) -> T>(name: &str, f: F) -> T {
+    let seed = test_seed();
+    let mut rd = XorShiftRand::new(seed);
+    match catch_unwind(AssertUnwindSafe(|| f(&mut rd))) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{} failed with the seed {:#x}, replay it by the {}={:#x}", name, seed, TEST_SEED_ENV, seed);
+            resume_unwind(e)
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::test_util::{XorShiftRand, with_test_seed, TEST_SEED_ENV, BlockCipherKat};
+#[allow(unused_imports)]
+use crate::test_util::check_block_cipher;
+use crate::test_util::seed::parse_seed;
+use rmath::rand::{Seed, Source, IterSource, RandError};
+use std::cell::Cell;
+use crate::test_util::hex;
+
+#[test]
+fn xorshift_rand() {
+    struct WordsSeed(Cell<usize>);
+    impl Seed<u32> for WordsSeed {
+        fn seed(&self) -> Result<u32, RandError> {
+            let i = self.0.get();
+            self.0.set(i + 1);
+            Ok([123456789u32, 362436069, 521288629, 88675123][i & 3])
+        }
+    }
+
+    // the state of the Marsaglia's paper
+    let mut rd = XorShiftRand::new(42);
+    rd.reset(&WordsSeed(Cell::new(0))).unwrap();
+    assert_eq!((0..3).map(|_| rd.next_u32()).collect::<Vec<_>>(), vec![3701687786u32, 458299110, 2500872618]);
+    assert_eq!(rd.seed(), 42);
+
+    let (mut a, mut b) = (XorShiftRand::new(42), XorShiftRand::new(42));
+    assert_eq!(a.next_u32(), 0x5c04bf7d);
+    assert_eq!(b.iter_mut().take(1).collect::<Vec<u32>>(), vec![0x5c04bf7d]);
+    assert_eq!(a.next_u64(), b.next_u64());
+    let (mut x, mut y) = ([0u8; 7], [0u8; 7]);
+    a.fill_bytes(&mut x);
+    b.fill_bytes(&mut y);
+    assert_eq!(x, y);
+    assert_ne!(XorShiftRand::new(0).next_u32(), XorShiftRand::new(1).next_u32());
+}
+
+#[test]
+fn test_seed_replay() {
+    assert_eq!(parse_seed("12345"), Some(12345));
+    assert_eq!(parse_seed(" 0xdeadbeef\n"), Some(0xdeadbeef));
+    assert_eq!(parse_seed("0x"), None);
+    assert_eq!(parse_seed("seed"), None);
+
+    // the panic is resumed after the seed is printed
+    assert!(std::panic::catch_unwind(|| with_test_seed("failing", |_| panic!("expected failure"))).is_err());
+
+    let (a, b) = (with_test_seed("a", |rd| rd.seed()), with_test_seed("b", |rd| rd.seed()));
+    if std::env::var(TEST_SEED_ENV).is_err() {
+        assert_ne!(a, b);
+    } else {
+        assert_eq!(a, b);
+    }
+}
+
+
+#[test]
+fn block_cipher_conformance() {
+    #[allow(unused)]
+    let kat = |key: &str, pt: &str, ct: &str| (hex(key), hex(pt), hex(ct));
+    #[allow(unused)]
+    let check = |name: &str, cases: &[(Vec<u8>, Vec<u8>, Vec<u8>)], f: &dyn Fn(&str, &[BlockCipherKat])| {
+        let kats = cases.iter().map(|c| BlockCipherKat { key: c.0.as_slice(), plaintext: c.1.as_slice(), ciphertext: c.2.as_slice() })
+            .collect::<Vec<_>>();
+        f(name, kats.as_slice());
+    };
+
+    #[cfg(feature = "aes")]
+    {
+        use crate::AES;
+        // FIPS 197 Appendix C
+        let cases = [
+            kat("000102030405060708090a0b0c0d0e0f", "00112233445566778899aabbccddeeff", "69c4e0d86a7b0430d8cdb78070b4c55a"),
+            kat("000102030405060708090a0b0c0d0e0f1011121314151617", "00112233445566778899aabbccddeeff", "dda97ca4864cdfe06eaf70a0ec0d7191"),
+            kat("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "00112233445566778899aabbccddeeff",
+                "8ea2b7ca516745bfeafc49904b496089"),
+        ];
+        check("AES", &cases, &|name, kats| check_block_cipher(name, |k| AES::new(k.to_vec()), &[16, 24, 32], kats));
+        // the AES-192 key is accepted
+        assert!(std::panic::catch_unwind(|| check_block_cipher("AES", |k| AES::new(k.to_vec()), &[16, 32], &[])).is_err());
+    }
+
+    #[cfg(feature = "des")]
+    {
+        use crate::{DES, TDES};
+        let cases = [kat("0000000000000000", "0000000000000000", "8ca64de9c1b123a7")];
+        check("DES", &cases, &|name, kats| check_block_cipher(name, DES::from_slice, &[8], kats));
+        let cases = [
+            kat("0123456789abcdef23456789abcdef01456789abcdef0123", "6bc1bee22e409f96", "714772f339841d34"),
+            kat("0123456789abcdef23456789abcdef01", "6bc1bee22e409f96", "06ede3d82884090a"),
+        ];
+        check("TDES", &cases, &|name, kats| check_block_cipher(name, TDES::from_slice, &[16, 24], kats));
+    }
+
+    #[cfg(feature = "sm")]
+    {
+        use crate::SM4;
+        // GM/T 0002-2012 Appendix A.1
+        let cases = [kat("0123456789abcdeffedcba9876543210", "0123456789abcdeffedcba9876543210", "681edf34d206965e86b3e94f536e4246")];
+        check("SM4", &cases, &|name, kats| check_block_cipher(name, SM4::from_slice, &[16], kats));
+    }
+
+    #[cfg(feature = "research")]
+    {
+        use crate::{Simon, Speck};
+        let cases = [kat("000102030405060708090a0b0c0d0e0f", "206d616465206974206571756976616c", "180d575cdffe60786532787951985da6")];
+        check("Speck128", &cases, &|name, kats| check_block_cipher(name, |k| Speck::new(16, k), &[16, 24, 32], kats));
+        let cases = [kat("000102030405060708090a0b0c0d0e0f", "808182838485868788898a8b8c8d8e8f", "5404fda3ab88adf3df7df97be8a1afe1")];
+        check("Simon128", &cases, &|name, kats| check_block_cipher(name, |k| Simon::new(16, k), &[16, 24, 32], kats));
+        check("Speck32", &[], &|name, kats| check_block_cipher(name, |k| Speck::new(4, k), &[8], kats));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+//! The xorshift128 random number generator
+//! Marsaglia. Xorshift RNGs, Journal of Statistical Software 2003.
+
+use rmath::rand::{IterSource, Source, Seed, Iter, RandError};
+
+/// The seeded weak random number generator for the reproducible tests, it is NOT for the cryptographic usage
+#[derive(Clone, Debug)]
+pub struct XorShiftRand {
+    seed: u64,
+    state: [u32; 4],
+}
+
+impl XorShiftRand {
+    /// the 128 bits state is expanded from the `seed` by the SplitMix64
+    pub fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut state = [0u32; 4];
+        state.chunks_mut(2).for_each(|s| {
+            x = x.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^= z >> 31;
+            s[0] = z as u32;
+            s[1] = (z >> 32) as u32;
+        });
+        let mut rd = Self { seed, state };
+        rd.avoid_zero_state();
+        rd
+    }
+
+    /// the seed of the `new`, it can be used to replay the sequence
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // the all-zero state is the fixed point of the xorshift
+    fn avoid_zero_state(&mut self) {
+        if self.state.iter().all(|&x| x == 0) {
+            self.state[3] = 1;
+        }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let [x, y, z, w] = self.state;
+        let t = x ^ (x << 11);
+        let next = w ^ (w >> 19) ^ t ^ (t >> 8);
+        self.state = [y, z, w, next];
+        next
+    }
+
+    /// the little-endian composition of the two words
+    pub fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        lo | ((self.next_u32() as u64) << 32)
+    }
+
+    pub fn fill_bytes(&mut self, dst: &mut [u8]) {
+        dst.chunks_mut(4).for_each(|d| {
+            let x = self.next_u32().to_le_bytes();
+            d.copy_from_slice(&x[..d.len()]);
+        });
+    }
+}
+
+impl Source<u32> for XorShiftRand {
+    fn gen(&mut self) -> Result<u32, RandError> {
+        Ok(self.next_u32())
+    }
+
+    /// reseed the state by the 4 words of the `sd`, the `seed()` is not changed
+    fn reset<Sd: Seed<u32>>(&mut self, sd: &Sd) -> Result<(), RandError> {
+        for s in self.state.iter_mut() {
+            *s = sd.seed()?;
+        }
+        self.avoid_zero_state();
+        Ok(())
+    }
+}
+
+impl IterSource<u32> for XorShiftRand {
+    fn iter_mut(&mut self) -> Iter<'_, Self, u32> where Self: std::marker::Sized {
+        Iter::new(self)
+    }
+}
@@ -0,0 +1,10 @@
+//! Time-lock puzzles
+//!
+//! The RSW time-lock puzzle hides the secret behind the sequential squarings in the RSA group, so the secret is
+//! revealed after the expected delay(e.g. the sealed bid, the timed-release message).
+
+mod puzzle;
+pub use puzzle::{TimeLockPuzzle, TimeLockSolver, TIMELOCK_MIN_MODULUS_BITS};
+
+#[cfg(test)]
+mod timelock_test;
\ No newline at end of file
@@ -0,0 +1,189 @@
+//! RSW time-lock puzzle
+//! Rivest, Shamir, Wagner. Time-lock puzzles and timed-release crypto, 1996.
+//!
+//! The creator selects the RSA modulus $N = pq$, the random base $a$ and the number of the squarings $t$, and
+//! locks the secret $K$ as $C_K = K + a^{2^t} \mod N$. The creator computes $a^{2^t}$ quickly by the exponent
+//! $e = 2^t \mod \phi(N)$, while the solver without the factors has to perform the $t$ sequential squarings modulo
+//! $N$, so the time of solving is controlled by the $t$ and the speed of the squaring.
+
+use rmath::bigint::{BigInt, Nat};
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::numeric::{mod_exp_public, mod_reduce};
+
+/// the minimum bits length of the modulus
+pub const TIMELOCK_MIN_MODULUS_BITS: usize = 1024;
+
+/// The published puzzle $(N, a, t, C_K)$ and the length of the secret
+#[derive(Clone, Debug)]
+pub struct TimeLockPuzzle {
+    modulus: BigInt,
+    base: BigInt,
+    squarings: u64,
+    locked: BigInt,
+    secret_len: usize,
+}
+
+impl TimeLockPuzzle {
+    /// lock the `secret` by the `squarings` sequential squarings in the new `modulus_bits` bits RSA group,
+    /// the `secret` must be shorter than the modulus, the factors of the modulus are dropped after the generation.
+    pub fn generate<R: IterSource<u32>>(secret: &[u8], squarings: u64, modulus_bits: usize, prime_test_round_num: usize, rd: &mut R) -> Result<Self, CryptoError> {
+        if modulus_bits < TIMELOCK_MIN_MODULUS_BITS {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong modulus bits length: {}, it must be at least {}", modulus_bits, TIMELOCK_MIN_MODULUS_BITS)));
+        } else if secret.len() >= (modulus_bits >> 3) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The secret of {} bytes is too long for the {} bits modulus", secret.len(), modulus_bits)));
+        } else if squarings == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The number of the squarings must be great than 0"));
+        }
+
+        let one = BigInt::from(1u32);
+        let (modulus, totient) = loop {
+            let mut primes = Vec::with_capacity(2);
+            for bits_len in [modulus_bits >> 1, modulus_bits - (modulus_bits >> 1)] {
+                let p = Nat::generate_prime(bits_len, prime_test_round_num, rd).map(BigInt::from)
+                    .map_err(|e| CryptoError::new(CryptoErrorKind::OuterErr, e))?;
+                primes.push(p);
+            }
+
+            let n = primes[0].clone() * primes[1].clone();
+            if primes[0] != primes[1] && n.bits_len() == modulus_bits {
+                break (n, (primes[0].clone() - one.clone()) * (primes[1].clone() - one.clone()));
+            }
+        };
+
+        let base = loop {
+            let mut buf = Vec::with_capacity(((modulus_bits + 31) >> 5) << 2);
+            rd.iter_mut().take((modulus_bits + 31) >> 5).for_each(|x| buf.extend_from_slice(x.to_be_bytes().as_ref()));
+            let a = mod_reduce(&BigInt::from_be_bytes(buf.as_slice()), &modulus)?;
+            if a > one {
+                break a;
+            }
+        };
+
+        let e = mod_exp_public(&BigInt::from(2u32), &BigInt::from(squarings), &totient)?;
+        let b = mod_exp_public(&base, &e, &modulus)?;
+        let locked = mod_reduce(&(BigInt::from_be_bytes(secret) + b), &modulus)?;
+
+        Ok(Self { modulus, base, squarings, locked, secret_len: secret.len() })
+    }
+
+    /// the published puzzle, the `base` and the `locked` must be in the range `[0, modulus)`
+    pub fn new(modulus: &BigInt, base: &BigInt, squarings: u64, locked: &BigInt, secret_len: usize) -> Result<Self, CryptoError> {
+        if modulus.is_nan() || modulus.bits_len() < TIMELOCK_MIN_MODULUS_BITS {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The modulus must be at least {} bits", TIMELOCK_MIN_MODULUS_BITS)));
+        } else if base.is_nan() || base <= &BigInt::from(1u32) || base >= modulus {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The base must be in the range (1, modulus)"));
+        } else if locked.is_nan() || locked.is_negative() || locked >= modulus {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The locked value must be in the range [0, modulus)"));
+        } else if secret_len >= (modulus.bits_len() >> 3) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The secret length is too long for the modulus"));
+        }
+
+        Ok(Self { modulus: modulus.deep_clone(), base: base.deep_clone(), squarings, locked: locked.deep_clone(), secret_len })
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.modulus
+    }
+
+    pub fn base(&self) -> &BigInt {
+        &self.base
+    }
+
+    pub fn squarings(&self) -> u64 {
+        self.squarings
+    }
+
+    pub fn locked(&self) -> &BigInt {
+        &self.locked
+    }
+
+    pub fn secret_len(&self) -> usize {
+        self.secret_len
+    }
+
+    /// the resumable solver of the puzzle
+    pub fn solver(&self) -> TimeLockSolver<'_> {
+        TimeLockSolver { puzzle: self, x: self.base.deep_clone(), done: 0 }
+    }
+
+    /// solve the puzzle by the `squarings` sequential squarings
+    pub fn solve(&self) -> Result<Vec<u8>, CryptoError> {
+        let mut solver = self.solver();
+        solver.step(self.squarings);
+        solver.secret()
+    }
+}
+
+/// The solver performs the squarings step by step, so the solving can be interrupted, reported, and resumed.
+pub struct TimeLockSolver<'a> {
+    puzzle: &'a TimeLockPuzzle,
+    x: BigInt,
+    done: u64,
+}
+
+impl<'a> TimeLockSolver<'a> {
+    /// resume the solving from the `checkpoint` after the `done` squarings
+    pub fn resume(puzzle: &'a TimeLockPuzzle, checkpoint: &BigInt, done: u64) -> Result<Self, CryptoError> {
+        if done > puzzle.squarings {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The done squarings {} is more than the squarings {} of the puzzle", done, puzzle.squarings)));
+        } else if checkpoint.is_nan() || checkpoint.is_negative() || checkpoint >= &puzzle.modulus {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The checkpoint must be in the range [0, modulus)"));
+        }
+
+        Ok(Self { puzzle, x: checkpoint.deep_clone(), done })
+    }
+
+    /// perform at most the `max_squarings` squarings, return whether all the squarings are done
+    pub fn step(&mut self, max_squarings: u64) -> bool {
+        let n = std::cmp::min(max_squarings, self.remaining());
+        for _ in 0..n {
+            let mut x = self.x.sqr();
+            x.rem_euclid_assign(self.puzzle.modulus.clone());
+            self.x = x;
+        }
+
+        self.done += n;
+        self.is_finished()
+    }
+
+    /// the number of the squarings have been performed
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.puzzle.squarings - self.done
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done == self.puzzle.squarings
+    }
+
+    /// the current value $a^{2^{done}} \mod N$, it can be saved to resume the solving by the `resume`
+    pub fn checkpoint(&self) -> &BigInt {
+        &self.x
+    }
+
+    /// unlock the secret $K = C_K - a^{2^t} \mod N$ after all the squarings are done
+    pub fn secret(&self) -> Result<Vec<u8>, CryptoError> {
+        if !self.is_finished() {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage,
+                format!("The puzzle is not solved, {} squarings remain", self.remaining())));
+        }
+
+        let k = mod_reduce(&(self.puzzle.locked.clone() - self.x.clone()), &self.puzzle.modulus)?;
+        let k = k.to_be_bytes();
+        if k.len() > self.puzzle.secret_len && k[..(k.len() - self.puzzle.secret_len)].iter().any(|&x| x != 0) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The unlocked value is longer than the secret length"));
+        }
+
+        let mut secret = vec![0u8; self.puzzle.secret_len.saturating_sub(k.len())];
+        secret.extend_from_slice(&k[k.len().saturating_sub(self.puzzle.secret_len)..]);
+        Ok(secret)
+    }
+}
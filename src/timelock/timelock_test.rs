@@ -0,0 +1,36 @@
+use rmath::bigint::BigInt;
+use rmath::rand::{CryptoRand, DefaultSeed};
+use crate::CryptoErrorKind;
+use crate::timelock::{TimeLockPuzzle, TimeLockSolver};
+
+#[test]
+fn timelock_puzzle() {
+    let mut rd = CryptoRand::new(&DefaultSeed::<u32>::new().unwrap()).unwrap();
+    let secret = b"\x00\x00the sealed bid: 42";
+    let puzzle = TimeLockPuzzle::generate(secret.as_ref(), 1000, 1024, 20, &mut rd).unwrap();
+    assert_eq!(puzzle.modulus().bits_len(), 1024);
+    assert_eq!(puzzle.solve().unwrap(), secret.to_vec());
+
+    // interrupt and resume
+    let mut solver = puzzle.solver();
+    assert!(!solver.step(300));
+    assert_eq!((solver.done(), solver.remaining()), (300, 700));
+    assert_eq!(solver.secret().unwrap_err().kind(), CryptoErrorKind::NotSupportUsage);
+    let checkpoint = solver.checkpoint().deep_clone();
+    let mut solver = TimeLockSolver::resume(&puzzle, &checkpoint, 300).unwrap();
+    assert!(solver.step(u64::MAX));
+    assert_eq!(solver.secret().unwrap(), secret.to_vec());
+
+    // the published parts
+    let published = TimeLockPuzzle::new(puzzle.modulus(), puzzle.base(), puzzle.squarings(), puzzle.locked(), puzzle.secret_len()).unwrap();
+    assert_eq!(published.solve().unwrap(), secret.to_vec());
+    let early = TimeLockPuzzle::new(puzzle.modulus(), puzzle.base(), puzzle.squarings() - 1, puzzle.locked(), puzzle.secret_len()).unwrap();
+    assert_ne!(early.solve().ok(), Some(secret.to_vec()));
+    assert!(TimeLockPuzzle::new(puzzle.modulus(), &BigInt::from(1u32), 10, puzzle.locked(), 16).is_err());
+    assert!(TimeLockPuzzle::new(puzzle.modulus(), puzzle.base(), 10, puzzle.modulus(), 16).is_err());
+    assert!(TimeLockSolver::resume(&puzzle, &checkpoint, 1001).is_err());
+
+    assert!(TimeLockPuzzle::generate(secret.as_ref(), 1000, 512, 20, &mut rd).is_err());
+    assert!(TimeLockPuzzle::generate(&[1u8; 128], 1000, 1024, 20, &mut rd).is_err());
+    assert!(TimeLockPuzzle::generate(secret.as_ref(), 0, 1024, 20, &mut rd).is_err());
+}
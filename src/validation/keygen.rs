@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter};
+
+/// The path which the key pair is generated by, it's returned by the `generate_key_with_mode` of the key types
+/// (e.g. `rsa::PrivateKey::generate_key_with_mode`, `elliptic::CurveParams::generate_key_with_mode`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeygenPath {
+    /// the default key generation when the `fips_mode` is false, e.g. the RSA primes with the most significant two
+    /// bits set and d = e^{-1} mod φ(n), the EC private key by the rejection sampling
+    Default,
+    /// the RSA key pair by the FIPS 186-5 A.1.5(the probable primes based on the auxiliary probable primes) and
+    /// the SP 800-56B Rev. 2 6.3.1.1(the fixed public exponent, d = e^{-1} mod λ(n))
+    FipsRsaAuxiliaryPrimes,
+    /// the EC key pair by the FIPS 186-5 A.2.1(the key pair generation using the extra random bits) on the
+    /// SP 800-186 curve
+    FipsEcExtraRandomBits,
+}
+
+impl KeygenPath {
+    /// the key is generated by the FIPS path
+    pub fn is_fips(&self) -> bool {
+        *self != KeygenPath::Default
+    }
+}
+
+impl Display for KeygenPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeygenPath::Default => write!(f, "default"),
+            KeygenPath::FipsRsaAuxiliaryPrimes => write!(f, "FIPS 186-5 A.1.5/SP 800-56B Rev. 2 6.3.1.1"),
+            KeygenPath::FipsEcExtraRandomBits => write!(f, "FIPS 186-5 A.2.1"),
+        }
+    }
+}
\ No newline at end of file
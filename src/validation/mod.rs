@@ -0,0 +1,29 @@
+//! Key validation
+//! NIST SP 800-89(the assurances of the public key validity and the private key possession)  
+//! FIPS 186-4 B.3.1, NIST SP 800-56A Rev. 3 5.6.2
+//!
+//! The `validate` methods of the key types run the parameter sanity checks, the pairwise consistency checks
+//! and the FIPS bounds checks, and return the `ValidationReport` which contains the result of every check.
+//! The FIPS bounds checks are reported separately, so the legacy keys(e.g. the RSA-1024 key) can be accepted
+//! by `is_valid` but rejected by `is_fips_compliant`.
+//!
+//! The weak keys and the degenerate parameters(e.g. the DES weak keys, the ROCA RSA modulus, the reused ECDSA nonce)
+//! are detected by the functions of the registry, which are also used by the `validate` methods and the checked
+//! constructors(e.g. `DES::new_checked`).
+//!
+//! The `generate_key_with_mode` of the key types with the `fips_mode` generates the key pair by the FIPS 186-5 and
+//! SP 800-56B Rev. 2 key generation, and returns the `KeygenPath` which records the path the key is generated by.
+
+mod report;
+pub use report::{ValidationReport, ValidationCheck, CheckCategory, CheckStatus};
+
+mod keygen;
+pub use keygen::KeygenPath;
+
+mod weak_key;
+pub use weak_key::{is_des_weak_key, is_des_semi_weak_key, rsa_small_factor, rsa_fermat_factor, is_roca_fingerprint};
+#[cfg(feature = "ec")]
+pub use weak_key::{ecdsa_signature_anomaly, ecdsa_nonce_reuse};
+
+#[cfg(all(test, feature = "des", feature = "ec"))]
+mod validation_test;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use crate::{CryptoError, CryptoErrorKind};
+use std::fmt::{Display, Formatter};
+
+/// The category of the check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckCategory {
+    /// the mathematical sanity of the key parameters, e.g. the range of the value, the primality
+    Parameter,
+    /// the consistency between the private key and the public key, e.g. sign/verify, encrypt/decrypt
+    Pairwise,
+    /// the bounds required by the FIPS 186-4, e.g. the modulus size, the public exponent range
+    Fips,
+}
+
+/// The result of the check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Passed,
+    Failed(String),
+    /// the check is not applicable to the key, e.g. the pairwise checks of the public key
+    Skipped(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationCheck {
+    pub name: &'static str,
+    pub category: CheckCategory,
+    pub status: CheckStatus,
+}
+
+/// The detailed report of the key validation
+#[derive(Clone, Debug)]
+pub struct ValidationReport {
+    key_type: String,
+    is_private: bool,
+    checks: Vec<ValidationCheck>,
+}
+
+// the builders are only used by the key types of the `rsa`, `dsa` and `ec` features
+#[cfg_attr(not(any(feature = "rsa", feature = "dsa", feature = "ec")), allow(unused))]
+impl ValidationReport {
+    /// `key_type`: e.g. `RSA public key`, `ECDSA P-256 private key`;
+    pub(crate) fn new(key_type: &str, is_private: bool) -> Self {
+        Self {
+            key_type: key_type.to_string(),
+            is_private,
+            checks: Vec::new(),
+        }
+    }
+
+    /// record the check, the `is_passed` is false means the check failed with the `msg`
+    pub(crate) fn check(&mut self, name: &'static str, category: CheckCategory, is_passed: bool, msg: &str) -> bool {
+        let status = if is_passed { CheckStatus::Passed } else { CheckStatus::Failed(msg.to_string()) };
+        self.checks.push(ValidationCheck { name, category, status });
+        is_passed
+    }
+
+    pub(crate) fn skip(&mut self, name: &'static str, category: CheckCategory, reason: &str) {
+        self.checks.push(ValidationCheck { name, category, status: CheckStatus::Skipped(reason.to_string()) });
+    }
+
+    pub fn key_type(&self) -> &str {
+        self.key_type.as_str()
+    }
+
+    pub fn checks(&self) -> &[ValidationCheck] {
+        self.checks.as_slice()
+    }
+
+    /// the failed checks
+    pub fn failures(&self) -> impl Iterator<Item = &ValidationCheck> {
+        self.checks.iter().filter(|c| matches!(c.status, CheckStatus::Failed(_)))
+    }
+
+    /// all the parameter and pairwise checks passed, the FIPS bounds checks are ignored
+    pub fn is_valid(&self) -> bool {
+        self.failures().all(|c| c.category == CheckCategory::Fips)
+    }
+
+    /// all the checks passed
+    pub fn is_fips_compliant(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// convert to the error which contains all the failures if the key is not valid(or not FIPS compliant
+    /// when `is_require_fips` is true)
+    pub fn to_result(&self, is_require_fips: bool) -> Result<(), CryptoError> {
+        let failures = self.failures().filter(|c| is_require_fips || c.category != CheckCategory::Fips)
+            .map(|c| match &c.status {
+                CheckStatus::Failed(msg) => format!("{}: {}", c.name, msg),
+                _ => String::new(),
+            }).collect::<Vec<_>>();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            let kind = if self.is_private { CryptoErrorKind::InvalidPrivateKey } else { CryptoErrorKind::InvalidPublicKey };
+            Err(CryptoError::new(kind, format!("Invalid {}: {}", self.key_type, failures.join("; "))))
+        }
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} validation report:", self.key_type)?;
+        for c in self.checks.iter() {
+            match &c.status {
+                CheckStatus::Passed => writeln!(f, "  [pass] {:?}/{}", c.category, c.name)?,
+                CheckStatus::Failed(msg) => writeln!(f, "  [fail] {:?}/{}: {}", c.category, c.name, msg)?,
+                CheckStatus::Skipped(msg) => writeln!(f, "  [skip] {:?}/{}: {}", c.category, c.name, msg)?,
+            }
+        }
+        Ok(())
+    }
+}
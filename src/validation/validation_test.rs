@@ -0,0 +1,109 @@
+use crate::validation::{CheckCategory, CheckStatus, is_des_weak_key, is_des_semi_weak_key, rsa_small_factor, 
+    rsa_fermat_factor, is_roca_fingerprint, ecdsa_signature_anomaly, ecdsa_nonce_reuse};
+use crate::elliptic::{CurveP256, EllipticCurve, PublicKey};
+use crate::ecdsa::ECDSA;
+use crate::dsa::SignatureContent;
+use crate::{CryptoErrorKind, DES, TDES, Signature};
+use crate::sha::SHA256;
+use rmath::bigint::{BigInt, Nat};
+use rmath::rand::{DefaultSeed, CryptoRand};
+use std::str::FromStr;
+
+#[test]
+fn validation_report() {
+    let p256 = CurveP256::new().unwrap();
+    let (gx, gy) = p256.curve_params().base_point();
+    
+    let report = PublicKey::new_uncheck(gx, gy).validate(&p256);
+    assert!(report.is_valid() && report.is_fips_compliant());
+    assert_eq!(report.key_type(), "EC P-256 public key");
+    assert_eq!(report.checks().len(), 3);
+    assert_eq!(report.checks()[2].category, CheckCategory::Pairwise);
+    assert!(matches!(report.checks()[2].status, CheckStatus::Skipped(_)));
+    assert!(report.to_result(true).is_ok());
+    
+    let report = PublicKey::new_uncheck(gx, &(gy.clone() + BigInt::from(1u32))).validate(&p256);
+    assert!(!report.is_valid() && !report.is_fips_compliant());
+    assert_eq!(report.failures().count(), 1);
+    assert_eq!(report.checks()[0].category, CheckCategory::Parameter);
+    let e = report.to_result(false).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::InvalidPublicKey);
+    assert!(format!("{}", report).contains("[fail] Parameter/public point"));
+}
+
+#[test]
+fn validation_des_weak_key() {
+    let weak = [0x01u8, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01];
+    let weak_parity = [0x00u8, 0x01, 0x00, 0x01, 0x01, 0x00, 0x01, 0x00];
+    let semi_weak = [0xe0u8, 0x1f, 0xe0, 0x1f, 0xf1, 0x0e, 0xf1, 0x0e];
+    let normal = [0x13u8, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1];
+    let normal2 = [0x0eu8, 0x32, 0x92, 0x32, 0xea, 0x6d, 0x0d, 0x73];
+    
+    assert!(is_des_weak_key(&weak) && is_des_weak_key(&weak_parity) && !is_des_semi_weak_key(&weak));
+    assert!(is_des_semi_weak_key(&semi_weak) && !is_des_weak_key(&semi_weak));
+    assert!(!is_des_weak_key(&normal) && !is_des_semi_weak_key(&normal));
+    
+    assert!(DES::new_checked(weak).is_err());
+    assert!(DES::new_checked(semi_weak).is_err());
+    assert!(DES::new_checked(normal).is_ok());
+    
+    assert!(TDES::new_checked(normal, normal2, normal).is_ok());
+    assert!(TDES::new_checked(normal, normal, normal2).is_err());
+    assert!(TDES::new_checked(normal, normal2, normal2).is_err());
+    assert!(TDES::new_checked(normal, weak, normal2).is_err());
+}
+
+#[test]
+fn validation_rsa_weak_modulus() {
+    let (p, q) = (BigInt::from_str("6703903964971298549787012499102923063739682910296196688861780721860882015036773488400937149083451713845015929093243025426876941405973284985562503404276661").unwrap(),
+        BigInt::from_str("6703903964971298549787012499102923063739682910296196688861780721860882015036773488400937149083451713845015929093243025426876941405973284985563602915905189").unwrap());
+    let n = p.clone() * q.clone();
+    assert_eq!(rsa_fermat_factor(&n, 16), Some((p.clone(), q.clone())));
+    assert_eq!(rsa_small_factor(&n, 1 << 16), None);
+    assert!(!is_roca_fingerprint(&n));
+    
+    let n2 = n.clone() * BigInt::from(65521u32);
+    assert_eq!(rsa_small_factor(&n2, 1 << 16), Some(65521));
+    assert_eq!(rsa_small_factor(&(n.clone() * BigInt::from(3u32)), 1 << 16), Some(3));
+    
+    let e = BigInt::from(65537u32);
+    let report = crate::rsa::PublicKey::from_bigint(&n, &e).unwrap().validate();
+    assert_eq!(report.failures().map(|c| c.name).collect::<Vec<_>>(), vec!["close primes", "modulus size"]);
+    
+    // n = k * M + (65537^a mod M), M is the product of the ROCA primes
+    let m = [3u32, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101,
+        103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167].iter().fold(BigInt::from(1u32), |m, &r| m * BigInt::from(r));
+    let mut roca = n.clone() * m.clone();
+    roca += e.exp(&BigInt::from(0x1234567u32), &m);
+    assert!(is_roca_fingerprint(&roca));
+    let report = crate::rsa::PublicKey::from_bigint(&roca, &e).unwrap().validate();
+    assert!(report.failures().any(|c| c.name == "ROCA fingerprint"), "{}", report);
+}
+
+#[test]
+fn validation_ecdsa_anomaly() {
+    let seed = DefaultSeed::<u32>::new().unwrap();
+    let rd = CryptoRand::new(&seed).unwrap();
+    let p256 = CurveP256::new().unwrap();
+    let mut ecdsa = ECDSA::auto_generate_key(SHA256::new(), rd, p256.clone()).unwrap();
+    
+    let (mut sig1, mut sig2) = (SignatureContent::new(), SignatureContent::new());
+    ecdsa.sign(&mut sig1, b"message1").unwrap();
+    ecdsa.sign(&mut sig2, b"message2").unwrap();
+    assert!(ecdsa_signature_anomaly(&p256, &sig1, 64).is_ok());
+    
+    let ((r1, s1), (r2, s2)) = (sig1.to_bigint(), sig2.to_bigint());
+    let mut sigs = vec![sig1, sig2];
+    assert_eq!(ecdsa_nonce_reuse(sigs.as_slice()), None);
+    sigs.push(SignatureContent::form_bigint(&r1, &s2));
+    assert_eq!(ecdsa_nonce_reuse(sigs.as_slice()), Some((0, 2)));
+    
+    let (x, _) = p256.scalar_base_point(&Nat::from(37u32));
+    let n = p256.curve_params().base_point_order().clone();
+    let small = SignatureContent::form_bigint(&x.rem_euclid(n.clone()), &s1);
+    assert_eq!(ecdsa_signature_anomaly(&p256, &small, 64).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(ecdsa_signature_anomaly(&p256, &small, 16).is_ok());
+    
+    assert!(ecdsa_signature_anomaly(&p256, &SignatureContent::form_bigint(&n, &s1), 0).is_err());
+    assert!(ecdsa_signature_anomaly(&p256, &SignatureContent::form_bigint(&r2, &BigInt::from(0u32)), 0).is_err());
+}
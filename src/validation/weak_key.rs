@@ -0,0 +1,189 @@
+//! The registry of the weak keys and the degenerate parameters
+//!
+//! - DES weak and semi-weak keys: FIPS 74 3.6, NIST SP 800-67 r2 3.3.2;
+//! - RSA modulus with the small factors(trial division) or the close primes(Fermat's factorization);
+//! - RSA modulus generated by the Infineon RSALib(ROCA, CVE-2017-15361), the modulus of the form
+//!   $k \cdot M + (65537^a \mod M)$ is detected by the residues modulo the small primes;
+//! - ECDSA signature with the out of range `r/s`, the small nonce `k`, or the reused nonce `k`(the same `r`);
+
+use rmath::bigint::BigInt;
+#[cfg(feature = "ec")]
+use crate::{CryptoError, CryptoErrorKind};
+#[cfg(feature = "ec")]
+use crate::elliptic::EllipticCurve;
+#[cfg(feature = "ec")]
+use crate::dsa::SignatureContent;
+
+const DES_WEAK_KEYS: [[u8; 8]; 4] = [
+    [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01],
+    [0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe],
+    [0xe0, 0xe0, 0xe0, 0xe0, 0xf1, 0xf1, 0xf1, 0xf1],
+    [0x1f, 0x1f, 0x1f, 0x1f, 0x0e, 0x0e, 0x0e, 0x0e],
+];
+
+const DES_SEMI_WEAK_KEYS: [[u8; 8]; 12] = [
+    [0x01, 0xfe, 0x01, 0xfe, 0x01, 0xfe, 0x01, 0xfe],
+    [0xfe, 0x01, 0xfe, 0x01, 0xfe, 0x01, 0xfe, 0x01],
+    [0x1f, 0xe0, 0x1f, 0xe0, 0x0e, 0xf1, 0x0e, 0xf1],
+    [0xe0, 0x1f, 0xe0, 0x1f, 0xf1, 0x0e, 0xf1, 0x0e],
+    [0x01, 0xe0, 0x01, 0xe0, 0x01, 0xf1, 0x01, 0xf1],
+    [0xe0, 0x01, 0xe0, 0x01, 0xf1, 0x01, 0xf1, 0x01],
+    [0x1f, 0xfe, 0x1f, 0xfe, 0x0e, 0xfe, 0x0e, 0xfe],
+    [0xfe, 0x1f, 0xfe, 0x1f, 0xfe, 0x0e, 0xfe, 0x0e],
+    [0x01, 0x1f, 0x01, 0x1f, 0x01, 0x0e, 0x01, 0x0e],
+    [0x1f, 0x01, 0x1f, 0x01, 0x0e, 0x01, 0x0e, 0x01],
+    [0xe0, 0xfe, 0xe0, 0xfe, 0xf1, 0xfe, 0xf1, 0xfe],
+    [0xfe, 0xe0, 0xfe, 0xe0, 0xfe, 0xf1, 0xfe, 0xf1],
+];
+
+/// the primes which are used by the ROCA detection
+const ROCA_PRIMES: [u32; 38] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101,
+    103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167,
+];
+
+fn is_des_key_in(key: &[u8; 8], keys: &[[u8; 8]]) -> bool {
+    // the least significant bit of each byte is the parity bit which is ignored by the key schedule
+    keys.iter().any(|k| k.iter().zip(key.iter()).all(|(&a, &b)| (a & 0xfe) == (b & 0xfe)))
+}
+
+/// the DES weak key which makes the encryption equal to the decryption, the parity bits are ignored
+pub fn is_des_weak_key(key: &[u8; 8]) -> bool {
+    is_des_key_in(key, &DES_WEAK_KEYS)
+}
+
+/// the DES semi-weak key which has a pair key that decrypts the data encrypted by it, the parity bits are ignored
+pub fn is_des_semi_weak_key(key: &[u8; 8]) -> bool {
+    is_des_key_in(key, &DES_SEMI_WEAK_KEYS)
+}
+
+/// $n \mod m$
+fn mod_u32(n: &[u8], m: u32) -> u32 {
+    n.iter().fold(0u64, |r, &b| ((r << 8) | (b as u64)) % (m as u64)) as u32
+}
+
+/// return the smallest prime factor of the `n` which is less than the `bound` by the trial division
+pub fn rsa_small_factor(n: &BigInt, bound: u32) -> Option<u32> {
+    if n.is_nan() || n.signnum() != Some(1) || bound < 3 {
+        return None;
+    }
+
+    let n_bytes = n.to_be_bytes();
+    let mut sieve = vec![true; bound as usize];
+    for i in 2..(bound as usize) {
+        if !sieve[i] {
+            continue;
+        }
+
+        if mod_u32(n_bytes.as_slice(), i as u32) == 0 && n != &BigInt::from(i as u32) {
+            return Some(i as u32);
+        }
+
+        let mut j = i * i;
+        while j < sieve.len() {
+            sieve[j] = false;
+            j += i;
+        }
+    }
+
+    None
+}
+
+/// factor the `n` by the Fermat's factorization method in the `max_steps` steps, it succeeds quickly if
+/// the two prime factors are close to $\sqrt{n}$.
+pub fn rsa_fermat_factor(n: &BigInt, max_steps: usize) -> Option<(BigInt, BigInt)> {
+    let (zero, one) = (BigInt::from(0u32), BigInt::from(1u32));
+    if n.is_nan() || n <= &one || n.rem_euclid(BigInt::from(2u32)) == zero {
+        return None;
+    }
+
+    let mut a = n.sqrt();
+    if &(a.clone() * a.clone()) < n {
+        a += one.clone();
+    }
+    let mut b2 = a.clone() * a.clone() - n.clone();
+
+    for _ in 0..max_steps {
+        let b = b2.sqrt();
+        if b.clone() * b.clone() == b2 {
+            let (p, q) = (a.clone() - b.clone(), a.clone() + b);
+            if p > one {
+                return Some((p, q));
+            }
+            return None;
+        }
+
+        // (a+1)^2 - n = a^2 - n + 2a + 1
+        b2 += (a.clone() << 1) + one.clone();
+        a += one.clone();
+    }
+
+    None
+}
+
+/// the modulus `n` has the fingerprint of the ROCA vulnerable keys, i.e. $n \mod r$ lies in the subgroup generated
+/// by 65537 modulo the small prime `r` for all the ROCA primes.
+/// The false positive rate for the random modulus is about $2^{-154}$.
+pub fn is_roca_fingerprint(n: &BigInt) -> bool {
+    if n.is_nan() || n.signnum() != Some(1) {
+        return false;
+    }
+
+    let n_bytes = n.to_be_bytes();
+    ROCA_PRIMES.iter().all(|&r| {
+        let (residue, g) = (mod_u32(n_bytes.as_slice(), r), 65537 % r);
+        let mut x = 1u32;
+        loop {
+            if x == residue {
+                break true;
+            }
+            x = (x * g) % r;
+            if x == 1 {
+                break false;
+            }
+        }
+    })
+}
+
+#[cfg(feature = "ec")]
+fn anomaly_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::VerificationFailed, format!("Anomalous ECDSA signature: {}", msg)))
+}
+
+/// check the ECDSA signature on the `curve`: $1 \le r, s < n$, and the nonce `k` is not in the range
+/// $[1, small_nonce_bound]$ or $[n - small_nonce_bound, n-1]$.
+#[cfg(feature = "ec")]
+pub fn ecdsa_signature_anomaly<C: EllipticCurve>(curve: &C, signature: &SignatureContent, small_nonce_bound: u32) -> Result<(), CryptoError> {
+    let (r, s) = signature.to_bigint();
+    let n = curve.curve_params().base_point_order();
+    if r.is_nan() || s.is_nan() || r.signnum() != Some(1) || s.signnum() != Some(1) || &r >= n || &s >= n {
+        return anomaly_err("the r or s is out of the range [1, n-1]");
+    }
+
+    // the x-coordinate of k*G equals to that of (n-k)*G
+    let (gx, gy) = curve.curve_params().base_point();
+    let (mut x, mut y) = (gx.clone(), gy.clone());
+    for k in 1..=small_nonce_bound {
+        if x.rem_euclid(n.clone()) == r {
+            return anomaly_err(format!("the nonce k is small({} or n-{})", k, k).as_str());
+        }
+        let (tx, ty) = curve.add(&x, &y, gx, gy);
+        x = tx;
+        y = ty;
+    }
+
+    Ok(())
+}
+
+/// find the two signatures which have the same `r`, which means the same nonce `k` is reused and the private key
+/// can be recovered from them.
+#[cfg(feature = "ec")]
+pub fn ecdsa_nonce_reuse(signatures: &[SignatureContent]) -> Option<(usize, usize)> {
+    let rs = signatures.iter().map(|s| s.to_bigint().0).collect::<Vec<_>>();
+    for (i, ri) in rs.iter().enumerate() {
+        if let Some(j) = rs.iter().skip(i + 1).position(|rj| rj == ri) {
+            return Some((i, i + 1 + j));
+        }
+    }
+    None
+}
@@ -0,0 +1,161 @@
+use crate::{CryptoError, CryptoErrorKind};
+use crate::sha::SHA256;
+use std::sync::{Mutex, MutexGuard};
+
+const CACHE_KEY_LEN: usize = 32;
+
+struct CacheEntry {
+    key: [u8; CACHE_KEY_LEN],
+    is_valid: bool,
+    last_used: u64,
+}
+
+struct CacheInner {
+    entries: Vec<CacheEntry>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// The thread-safe LRU cache of the signature verification results, which can be shared by the worker threads
+/// (e.g. `Arc<VerificationCache>`).
+pub struct VerificationCache {
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+}
+
+impl VerificationCache {
+    /// `capacity`: the maximum number of the cached results, it must be great than 0;
+    pub fn new(capacity: usize) -> Result<Self, CryptoError> {
+        if capacity == 0 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The capacity of the verification cache must be great than 0"));
+        }
+
+        Ok(Self {
+            capacity,
+            inner: Mutex::new(CacheInner {
+                entries: Vec::with_capacity(capacity),
+                tick: 0,
+                hits: 0,
+                misses: 0,
+            }),
+        })
+    }
+
+    fn cache_key(key_fingerprint: &[u8], message_hash: &[u8], signature: &[u8]) -> [u8; CACHE_KEY_LEN] {
+        let mut data = Vec::with_capacity(key_fingerprint.len() + message_hash.len() + signature.len() + 24);
+        for x in [key_fingerprint, message_hash, signature].iter() {
+            data.extend_from_slice((x.len() as u64).to_be_bytes().as_ref());
+            data.extend_from_slice(x);
+        }
+
+        let mut key = [0u8; CACHE_KEY_LEN];
+        key.copy_from_slice(SHA256::digest(data.as_slice()).as_slice());
+        key
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheInner> {
+        // the cache is always consistent between the operations, so the poisoned lock can be recovered
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// compare the `key` with all the entries, return the index of the matched entry
+    fn find(entries: &[CacheEntry], key: &[u8; CACHE_KEY_LEN]) -> Option<usize> {
+        let (mut idx, mut is_found) = (0usize, 0usize);
+        for (i, e) in entries.iter().enumerate() {
+            let diff = e.key.iter().zip(key.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y));
+            // mask = usize::MAX if diff == 0 else 0
+            let mask = (((diff as usize) | (diff as usize).wrapping_neg()) >> (usize::BITS - 1)).wrapping_sub(1);
+            idx |= i & mask;
+            is_found |= mask;
+        }
+
+        if is_found != 0 { Some(idx) } else { None }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the number of the cache hits and misses of the `lookup`
+    pub fn stats(&self) -> (u64, u64) {
+        let inner = self.lock();
+        (inner.hits, inner.misses)
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.hits = 0;
+        inner.misses = 0;
+    }
+
+    /// return the cached verification result
+    pub fn lookup(&self, key_fingerprint: &[u8], message_hash: &[u8], signature: &[u8]) -> Option<bool> {
+        let key = Self::cache_key(key_fingerprint, message_hash, signature);
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        match Self::find(inner.entries.as_slice(), &key) {
+            Some(i) => {
+                inner.hits += 1;
+                let e = &mut inner.entries[i];
+                e.last_used = tick;
+                Some(e.is_valid)
+            },
+            None => {
+                inner.misses += 1;
+                None
+            },
+        }
+    }
+
+    /// cache the verification result, the least recently used entry is evicted if the cache is full
+    pub fn insert(&self, key_fingerprint: &[u8], message_hash: &[u8], signature: &[u8], is_valid: bool) {
+        let key = Self::cache_key(key_fingerprint, message_hash, signature);
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if let Some(i) = Self::find(inner.entries.as_slice(), &key) {
+            let e = &mut inner.entries[i];
+            e.is_valid = is_valid;
+            e.last_used = tick;
+        } else if inner.entries.len() < self.capacity {
+            inner.entries.push(CacheEntry { key, is_valid, last_used: tick });
+        } else if let Some(e) = inner.entries.iter_mut().min_by_key(|e| e.last_used) {
+            *e = CacheEntry { key, is_valid, last_used: tick };
+        }
+    }
+
+    /// return the cached result, or call the `verify` and cache its result.
+    /// The `VerificationFailed` error is cached as the invalid signature, the other errors(e.g. the invalid
+    /// parameters) are returned directly and not cached.
+    pub fn verify_with<F>(&self, key_fingerprint: &[u8], message_hash: &[u8], signature: &[u8], verify: F) -> Result<(), CryptoError>
+        where F: FnOnce() -> Result<(), CryptoError> {
+        match self.lookup(key_fingerprint, message_hash, signature) {
+            Some(true) => Ok(()),
+            Some(false) => Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The signature is invalid(cached)")),
+            None => {
+                let r = verify();
+                match r.as_ref() {
+                    Ok(_) => self.insert(key_fingerprint, message_hash, signature, true),
+                    Err(e) if e.kind() == CryptoErrorKind::VerificationFailed => {
+                        self.insert(key_fingerprint, message_hash, signature, false)
+                    },
+                    Err(_) => {},
+                }
+                r
+            },
+        }
+    }
+}
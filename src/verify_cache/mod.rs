@@ -0,0 +1,15 @@
+//! Signature verification cache
+//!
+//! The result of the signature verification is cached by the key
+//! `SHA-256(len(key_fingerprint) || key_fingerprint || len(message_hash) || message_hash || len(signature) || signature)`,
+//! the lookup compares the key with every cached key in constant time, so the time doesn't depend on which
+//! entry is matched. The least recently used entry is evicted when the cache is full.
+//!
+//! The cache is opt-in by the `cache` feature, and should only be used by the gateway which repeatedly verifies
+//! the identical tokens, the caller is responsible for the key fingerprint identifying the public key exactly.
+
+mod lru_cache;
+pub use lru_cache::VerificationCache;
+
+#[cfg(test)]
+mod verify_cache_test;
@@ -0,0 +1,74 @@
+use crate::verify_cache::VerificationCache;
+use crate::{CryptoError, CryptoErrorKind};
+use std::sync::Arc;
+use std::cell::Cell;
+
+#[test]
+fn verify_cache_lru() {
+    assert!(VerificationCache::new(0).is_err());
+    
+    let cache = VerificationCache::new(2).unwrap();
+    assert!(cache.is_empty());
+    assert_eq!(cache.lookup(b"key", b"hash", b"sig"), None);
+    
+    cache.insert(b"key", b"hash", b"sig", true);
+    cache.insert(b"key", b"hash", b"bad", false);
+    assert_eq!(cache.lookup(b"key", b"hash", b"sig"), Some(true));
+    assert_eq!(cache.lookup(b"key", b"hash", b"bad"), Some(false));
+    // the components are length-prefixed
+    assert_eq!(cache.lookup(b"keyh", b"ash", b"sig"), None);
+    
+    // the (key, hash, sig) is the least recently used
+    assert_eq!(cache.lookup(b"key", b"hash", b"bad"), Some(false));
+    cache.insert(b"key2", b"hash", b"sig", true);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.lookup(b"key", b"hash", b"sig"), None);
+    assert_eq!(cache.lookup(b"key", b"hash", b"bad"), Some(false));
+    assert_eq!(cache.lookup(b"key2", b"hash", b"sig"), Some(true));
+    assert_eq!(cache.stats(), (5, 3));
+    
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.stats(), (0, 0));
+}
+
+#[test]
+fn verify_cache_verify_with() {
+    let (cache, calls) = (VerificationCache::new(16).unwrap(), Cell::new(0));
+    let verify = |r: Result<(), CryptoError>| {
+        calls.set(calls.get() + 1);
+        r
+    };
+    
+    for _ in 0..3 {
+        assert!(cache.verify_with(b"key", b"hash", b"sig", || verify(Ok(()))).is_ok());
+        let e = cache.verify_with(b"key", b"hash", b"bad", || verify(Err(CryptoError::new(CryptoErrorKind::VerificationFailed, ""))));
+        assert_eq!(e.unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    }
+    assert_eq!(calls.get(), 2);
+    
+    // the other errors are not cached
+    for _ in 0..3 {
+        let e = cache.verify_with(b"key", b"hash", b"", || verify(Err(CryptoError::new(CryptoErrorKind::InvalidParameter, ""))));
+        assert_eq!(e.unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    }
+    assert_eq!(calls.get(), 5);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn verify_cache_shared() {
+    let cache = Arc::new(VerificationCache::new(64).unwrap());
+    let workers = (0..4u8).map(|t| {
+        let cache = cache.clone();
+        std::thread::spawn(move || {
+            for i in 0..32u8 {
+                cache.insert(&[t], &[i], &[i, t], (i & 1) == 0);
+                assert_eq!(cache.lookup(&[t], &[i], &[i, t]), Some((i & 1) == 0));
+            }
+        })
+    }).collect::<Vec<_>>();
+    workers.into_iter().for_each(|w| w.join().unwrap());
+    
+    assert_eq!(cache.len(), 64);
+}
@@ -0,0 +1,203 @@
+//! The `packed` attestation certificate
+//! WebAuthn Level 2, 8.2.1 Packed Attestation Statement Certificate Requirements
+//!
+//! the certificate must be the X.509 v3, the subject must have the C, O, CN and the OU `Authenticator Attestation`,
+//! the `basicConstraints` must not be the CA, and the `id-fido-gen-ce-aaguid` extension must match the AAGUID of the
+//! authenticator data if it exists. The certificate signature and the path to the trust anchor aren't verified here.
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::encoding::der::{self, DerReader};
+use crate::oid::Oid;
+use crate::webauthn::CoseKey;
+
+/// the `id-at-countryName`, 2.5.4.6
+const OID_COUNTRY_NAME: &[u8] = &[0x55, 0x04, 0x06];
+/// the `id-at-organizationName`, 2.5.4.10
+const OID_ORGANIZATION_NAME: &[u8] = &[0x55, 0x04, 0x0a];
+/// the `id-at-organizationalUnitName`, 2.5.4.11
+const OID_ORGANIZATIONAL_UNIT_NAME: &[u8] = &[0x55, 0x04, 0x0b];
+/// the `id-at-commonName`, 2.5.4.3
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+/// the `id-ce-basicConstraints`, 2.5.29.19
+const OID_BASIC_CONSTRAINTS: &[u8] = &[0x55, 0x1d, 0x13];
+/// the `id-ce-keyUsage`, 2.5.29.15
+const OID_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x0f];
+/// the `id-fido-gen-ce-aaguid`, 1.3.6.1.4.1.45724.1.1.4
+const OID_FIDO_GEN_CE_AAGUID: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xe5, 0x1c, 0x01, 0x01, 0x04];
+
+const ATTESTATION_OU: &[u8] = b"Authenticator Attestation";
+
+fn cert_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid attestation certificate: {}", msg)))
+}
+
+fn verify_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::VerificationFailed, format!("The attestation certificate {}", msg)))
+}
+
+fn parse_boolean(b: &der::DerTLV) -> Result<bool, CryptoError> {
+    match b.value {
+        [0x00] => Ok(false),
+        [0xff] => Ok(true),
+        _ => cert_err("the BOOLEAN isn't 0x00 or 0xff"),
+    }
+}
+
+/// check the requirements of the DER encoded attestation certificate `x5c[0]`, and return its public key
+pub(super) fn packed_attestation_public_key(cert: &[u8], aaguid: &[u8; 16]) -> Result<CoseKey, CryptoError> {
+    let mut r = DerReader::new(cert);
+    let c = r.read_expect(der::TAG_SEQUENCE)?;
+    if !r.is_empty() {
+        return cert_err("trailing bytes after the certificate");
+    }
+
+    // version, serialNumber, signature, issuer, validity, subject, subjectPublicKeyInfo
+    let mut c = DerReader::new(c.value);
+    let mut t = DerReader::new(c.read_expect(der::TAG_SEQUENCE)?.value);
+    let version = match t.read_optional(der::tag_context(0))? {
+        Some(v) => DerReader::new(v.value).read_unsigned_integer()?.to_vec(),
+        None => Vec::new(),
+    };
+    if version.as_slice() != [2] {
+        return verify_err("isn't the X.509 v3");
+    }
+    t.read_expect(der::TAG_INTEGER)?;
+    for _ in 0..3 {
+        t.read_expect(der::TAG_SEQUENCE)?;
+    }
+    check_subject(t.read_expect(der::TAG_SEQUENCE)?.value)?;
+    let public_key = public_key_from_spki(t.read_expect(der::TAG_SEQUENCE)?.value)?;
+
+    // issuerUniqueID [1], subjectUniqueID [2], extensions [3]
+    t.read_optional(0x81)?;
+    t.read_optional(0x82)?;
+    let exts = match t.read_optional(der::tag_context(3))? {
+        Some(e) => e.value,
+        None => return verify_err("has no basicConstraints"),
+    };
+    if !t.is_empty() {
+        return cert_err("trailing bytes after the extensions");
+    }
+    check_extensions(exts, aaguid)?;
+    Ok(public_key)
+}
+
+/// Name ::= SEQUENCE OF RelativeDistinguishedName, RelativeDistinguishedName ::= SET OF AttributeTypeAndValue
+fn check_subject(name: &[u8]) -> Result<(), CryptoError> {
+    let (mut r, mut attrs) = (DerReader::new(name), Vec::new());
+    while !r.is_empty() {
+        let mut rdn = DerReader::new(r.read_expect(der::TAG_SET)?.value);
+        while !rdn.is_empty() {
+            let mut a = DerReader::new(rdn.read_expect(der::TAG_SEQUENCE)?.value);
+            let (ty, value) = (a.read_expect(der::TAG_OID)?.value, a.read()?.value);
+            if !a.is_empty() {
+                return cert_err("trailing bytes after the attribute value");
+            }
+            attrs.push((ty, value));
+        }
+    }
+
+    let has = |ty: &[u8]| attrs.iter().any(|&(x, v)| x == ty && !v.is_empty());
+    if !has(OID_COUNTRY_NAME) || !has(OID_ORGANIZATION_NAME) || !has(OID_COMMON_NAME) {
+        verify_err("subject must have the C, O and CN")
+    } else if !attrs.iter().any(|&(x, v)| x == OID_ORGANIZATIONAL_UNIT_NAME && v == ATTESTATION_OU) {
+        verify_err("subject OU isn't the `Authenticator Attestation`")
+    } else {
+        Ok(())
+    }
+}
+
+/// the P-256 `id-ecPublicKey` is the ES256 key, the `rsaEncryption` is the RS256 key
+fn public_key_from_spki(spki: &[u8]) -> Result<CoseKey, CryptoError> {
+    let mut s = DerReader::new(spki);
+    let mut alg = DerReader::new(s.read_expect(der::TAG_SEQUENCE)?.value);
+    let bits = s.read_expect(der::TAG_BIT_STRING)?.value;
+    if !s.is_empty() || bits.first() != Some(&0) {
+        return cert_err("the subjectPublicKey isn't the octets");
+    }
+
+    let (id, bits) = (alg.read_expect(der::TAG_OID)?.value, &bits[1..]);
+    if id == Oid::EC_PUBLIC_KEY.as_der() {
+        if alg.read_expect(der::TAG_OID)?.value != Oid::P256.as_der() || !alg.is_empty() {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "Only the P-256 attestation public key is supported"));
+        }
+        if bits.len() != 65 || bits[0] != 0x04 {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The P-256 public key must be the 65 bytes uncompressed point"));
+        }
+        CoseKey::es256(&bits[1..33], &bits[33..])
+    } else if id == Oid::RSA_ENCRYPTION.as_der() {
+        // the NULL parameters, RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+        if alg.read_optional(der::TAG_NULL)?.map(|x| !x.value.is_empty()).unwrap_or(false) || !alg.is_empty() {
+            return cert_err("the rsaEncryption has the NULL parameters");
+        }
+        let mut r = DerReader::new(bits);
+        let mut k = DerReader::new(r.read_expect(der::TAG_SEQUENCE)?.value);
+        let (n, e) = (k.read_unsigned_integer()?, k.read_unsigned_integer()?);
+        if !r.is_empty() || !k.is_empty() {
+            return cert_err("trailing bytes after the RSA public key");
+        }
+        Ok(CoseKey::RS256(crate::rsa::PublicKey::from_be_bytes(n, e)?))
+    } else {
+        Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "Only the EC and RSA attestation public keys are supported"))
+    }
+}
+
+fn check_extensions(exts: &[u8], aaguid: &[u8; 16]) -> Result<(), CryptoError> {
+    let mut r = DerReader::new(exts);
+    let mut exts = DerReader::new(r.read_expect(der::TAG_SEQUENCE)?.value);
+    if !r.is_empty() {
+        return cert_err("the extensions isn't the sequence of the extension");
+    }
+
+    let (mut ids, mut has_bc) = (Vec::new(), false);
+    while !exts.is_empty() {
+        // Extension ::= SEQUENCE { extnID, critical BOOLEAN DEFAULT FALSE, extnValue OCTET STRING }
+        let mut e = DerReader::new(exts.read_expect(der::TAG_SEQUENCE)?.value);
+        let id = e.read_expect(der::TAG_OID)?.value;
+        let critical = match e.read_optional(der::TAG_BOOLEAN)? {
+            Some(b) => parse_boolean(&b)?,
+            None => false,
+        };
+        let value = e.read_expect(der::TAG_OCTET_STRING)?.value;
+        if !e.is_empty() || ids.contains(&id) {
+            return cert_err("the duplicate extension or the trailing bytes after the extension");
+        }
+        ids.push(id);
+
+        let mut v = DerReader::new(value);
+        if id == OID_BASIC_CONSTRAINTS {
+            // BasicConstraints ::= SEQUENCE { cA BOOLEAN DEFAULT FALSE, pathLenConstraint INTEGER OPTIONAL }
+            let mut bc = DerReader::new(v.read_expect(der::TAG_SEQUENCE)?.value);
+            let is_ca = match bc.read_optional(der::TAG_BOOLEAN)? {
+                Some(b) => parse_boolean(&b)?,
+                None => false,
+            };
+            bc.read_optional(der::TAG_INTEGER)?;
+            if !bc.is_empty() {
+                return cert_err("trailing bytes after the basicConstraints");
+            } else if is_ca {
+                return verify_err("is the CA certificate");
+            }
+            has_bc = true;
+        } else if id == OID_FIDO_GEN_CE_AAGUID {
+            if critical {
+                return verify_err("AAGUID extension must not be critical");
+            } else if v.read_expect(der::TAG_OCTET_STRING)?.value != aaguid.as_ref() {
+                return verify_err("AAGUID doesn't match the authenticator data");
+            }
+        } else if critical && id != OID_KEY_USAGE {
+            return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The unrecognized critical extension in the attestation certificate"));
+        } else {
+            continue;
+        }
+        if !v.is_empty() {
+            return cert_err("trailing bytes after the extension value");
+        }
+    }
+
+    if has_bc {
+        Ok(())
+    } else {
+        verify_err("has no basicConstraints")
+    }
+}
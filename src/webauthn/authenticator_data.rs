@@ -0,0 +1,115 @@
+//! Authenticator data
+//! WebAuthn Level 2, 6.1
+//!
+//! rpIdHash(32) || flags(1) || signCount(4) || attestedCredentialData(variable) || extensions(variable)
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::webauthn::cbor::CborValue;
+use crate::webauthn::CoseKey;
+
+/// attestedCredentialData: aaguid(16) || credentialIdLength(2) || credentialId || credentialPublicKey
+#[derive(Clone)]
+pub struct AttestedCredentialData {
+    pub aaguid: [u8; 16],
+    pub credential_id: Vec<u8>,
+    pub public_key: CoseKey,
+}
+
+#[derive(Clone)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredentialData>,
+    /// the CBOR encoded extension outputs
+    pub extensions: Option<Vec<u8>>,
+}
+
+fn auth_data_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid authenticator data: {}", msg)))
+}
+
+impl AuthenticatorData {
+    /// user present
+    pub const FLAG_UP: u8 = 0x01;
+    /// user verified
+    pub const FLAG_UV: u8 = 0x04;
+    /// backup eligibility
+    pub const FLAG_BE: u8 = 0x08;
+    /// backup state
+    pub const FLAG_BS: u8 = 0x10;
+    /// attested credential data included
+    pub const FLAG_AT: u8 = 0x40;
+    /// extension data included
+    pub const FLAG_ED: u8 = 0x80;
+
+    pub fn parse(data: &[u8]) -> Result<Self, CryptoError> {
+        if data.len() < 37 {
+            return auth_data_err("too short");
+        }
+
+        let mut rp_id_hash = [0u8; 32];
+        rp_id_hash.copy_from_slice(&data[..32]);
+        let flags = data[32];
+        let mut sign_count = [0u8; 4];
+        sign_count.copy_from_slice(&data[33..37]);
+        let mut pos = 37;
+
+        let attested_credential = if (flags & Self::FLAG_AT) != 0 {
+            if data.len() < pos + 18 {
+                return auth_data_err("attested credential data too short");
+            }
+            let mut aaguid = [0u8; 16];
+            aaguid.copy_from_slice(&data[pos..(pos + 16)]);
+            let id_len = ((data[pos + 16] as usize) << 8) | (data[pos + 17] as usize);
+            pos += 18;
+            if data.len() < pos + id_len {
+                return auth_data_err("credential id too short");
+            }
+            let credential_id = data[pos..(pos + id_len)].to_vec();
+            pos += id_len;
+
+            let (key, len) = CborValue::decode(&data[pos..])?;
+            pos += len;
+            Some(AttestedCredentialData {
+                aaguid,
+                credential_id,
+                public_key: CoseKey::from_cbor(&key)?,
+            })
+        } else {
+            None
+        };
+
+        let extensions = if (flags & Self::FLAG_ED) != 0 {
+            let (ext, len) = CborValue::decode(&data[pos..])?;
+            if !matches!(ext, CborValue::Map(_)) {
+                return auth_data_err("the extensions must be a CBOR map");
+            }
+            let ext = data[pos..(pos + len)].to_vec();
+            pos += len;
+            Some(ext)
+        } else {
+            None
+        };
+
+        if pos != data.len() {
+            return auth_data_err("trailing bytes");
+        }
+
+        Ok(Self {
+            rp_id_hash,
+            flags,
+            sign_count: u32::from_be_bytes(sign_count),
+            attested_credential,
+            extensions,
+        })
+    }
+
+    pub fn is_user_present(&self) -> bool {
+        (self.flags & Self::FLAG_UP) != 0
+    }
+
+    pub fn is_user_verified(&self) -> bool {
+        (self.flags & Self::FLAG_UV) != 0
+    }
+}
@@ -0,0 +1,149 @@
+//! The minimal CBOR(RFC 8949) decoder for the WebAuthn attestation object and the COSE key
+//!
+//! Only the definite length items which are used by the CTAP2 canonical encoding are supported.
+
+use crate::{CryptoError, CryptoErrorKind};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum CborValue {
+    Integer(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<CborValue>),
+    Map(Vec<(CborValue, CborValue)>),
+    Bool(bool),
+    Null,
+}
+
+const CBOR_MAX_DEPTH: usize = 16;
+
+fn cbor_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid CBOR: {}", msg)))
+}
+
+impl CborValue {
+    /// decode one CBOR item from the head of the `data`, and return the item and the length in bytes it used
+    pub(super) fn decode(data: &[u8]) -> Result<(CborValue, usize), CryptoError> {
+        let mut pos = 0;
+        let v = Self::decode_inner(data, &mut pos, 0)?;
+        Ok((v, pos))
+    }
+
+    fn take<'a>(data: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8], CryptoError> {
+        if data.len() - *pos < len {
+            return cbor_err("unexpected end of data");
+        }
+        let s = &data[*pos..(*pos + len)];
+        *pos += len;
+        Ok(s)
+    }
+
+    fn decode_inner(data: &[u8], pos: &mut usize, depth: usize) -> Result<CborValue, CryptoError> {
+        if depth > CBOR_MAX_DEPTH {
+            return cbor_err("nesting too deep");
+        }
+
+        let head = Self::take(data, pos, 1)?[0];
+        let (major, info) = (head >> 5, head & 0x1f);
+        let arg = match info {
+            0..=23 => info as u64,
+            24..=27 => {
+                let b = Self::take(data, pos, 1 << (info - 24))?;
+                b.iter().fold(0u64, |a, &x| (a << 8) | (x as u64))
+            },
+            _ => return cbor_err("indefinite length or reserved additional information"),
+        };
+
+        let to_len = |arg: u64| -> Result<usize, CryptoError> {
+            if arg > ((data.len() - *pos) as u64) {
+                cbor_err("unexpected end of data")
+            } else {
+                Ok(arg as usize)
+            }
+        };
+
+        match major {
+            0 => Ok(CborValue::Integer(arg as i128)),
+            1 => Ok(CborValue::Integer(-1 - (arg as i128))),
+            2 => {
+                let len = to_len(arg)?;
+                Ok(CborValue::Bytes(Self::take(data, pos, len)?.to_vec()))
+            },
+            3 => {
+                let len = to_len(arg)?;
+                let s = Self::take(data, pos, len)?;
+                match String::from_utf8(s.to_vec()) {
+                    Ok(s) => Ok(CborValue::Text(s)),
+                    Err(_) => cbor_err("invalid UTF-8 text"),
+                }
+            },
+            4 => {
+                let len = to_len(arg)?;
+                let mut arr = Vec::with_capacity(len);
+                for _ in 0..len {
+                    arr.push(Self::decode_inner(data, pos, depth + 1)?);
+                }
+                Ok(CborValue::Array(arr))
+            },
+            5 => {
+                let len = to_len(arg)?;
+                let mut map: Vec<(CborValue, CborValue)> = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let k = Self::decode_inner(data, pos, depth + 1)?;
+                    if map.iter().any(|(x, _)| x == &k) {
+                        return cbor_err("duplicate map key");
+                    }
+                    let v = Self::decode_inner(data, pos, depth + 1)?;
+                    map.push((k, v));
+                }
+                Ok(CborValue::Map(map))
+            },
+            7 => match info {
+                20 => Ok(CborValue::Bool(false)),
+                21 => Ok(CborValue::Bool(true)),
+                22 => Ok(CborValue::Null),
+                _ => cbor_err("unsupported simple value or float"),
+            },
+            _ => cbor_err("unsupported tag"),
+        }
+    }
+
+    /// get the value of the integer `key` in the map
+    pub(super) fn get_int(&self, key: i128) -> Option<&CborValue> {
+        match self {
+            CborValue::Map(m) => m.iter().find(|(k, _)| k == &CborValue::Integer(key)).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// get the value of the text `key` in the map
+    pub(super) fn get_text(&self, key: &str) -> Option<&CborValue> {
+        match self {
+            CborValue::Map(m) => m.iter().find(|(k, _)| {
+                matches!(k, CborValue::Text(t) if t == key)
+            }).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_integer(&self) -> Option<i128> {
+        match self {
+            CborValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            CborValue::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_text(&self) -> Option<&str> {
+        match self {
+            CborValue::Text(t) => Some(t.as_str()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! COSE key
+//! RFC 8152 chapter 13, RFC 8230
+//!
+//! Only the ES256(ECDSA P-256 with SHA-256) and RS256(RSASSA-PKCS1-v1_5 with SHA-256) credential
+//! public keys are supported, which are required by the WebAuthn relying party.
+
+use crate::{CryptoError, CryptoErrorKind, Signature};
+use crate::webauthn::cbor::CborValue;
+use crate::elliptic::{CurveP256, EllipticCurve};
+use crate::ecdsa::ECDSA;
+use crate::rsa::PKCS1;
+use crate::sha::SHA256;
+use crate::rand::VerifyOnlyRand;
+use rmath::bigint::BigInt;
+
+/// The credential public key
+#[derive(Clone)]
+pub enum CoseKey {
+    ES256(crate::elliptic::PublicKey),
+    RS256(crate::rsa::PublicKey),
+}
+
+fn cose_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, format!("Invalid COSE key: {}", msg)))
+}
+
+impl CoseKey {
+    /// COSE algorithm identifier
+    pub const ALG_ES256: i64 = -7;
+    pub const ALG_RS256: i64 = -257;
+
+    const KTY_EC2: i128 = 2;
+    const KTY_RSA: i128 = 3;
+    const CRV_P256: i128 = 1;
+
+    pub(super) fn from_cbor(key: &CborValue) -> Result<Self, CryptoError> {
+        let kty = key.get_int(1).and_then(|x| x.as_integer());
+        let alg = key.get_int(3).and_then(|x| x.as_integer());
+
+        match (kty, alg) {
+            (Some(Self::KTY_EC2), Some(a)) if a == (Self::ALG_ES256 as i128) => {
+                let crv = key.get_int(-1).and_then(|x| x.as_integer());
+                let x = key.get_int(-2).and_then(|x| x.as_bytes());
+                let y = key.get_int(-3).and_then(|x| x.as_bytes());
+                match (crv, x, y) {
+                    (Some(Self::CRV_P256), Some(x), Some(y)) if x.len() == 32 && y.len() == 32 => Self::es256(x, y),
+                    _ => cose_err("invalid EC2 key parameters"),
+                }
+            },
+            (Some(Self::KTY_RSA), Some(a)) if a == (Self::ALG_RS256 as i128) => {
+                let n = key.get_int(-1).and_then(|x| x.as_bytes());
+                let e = key.get_int(-2).and_then(|x| x.as_bytes());
+                match (n, e) {
+                    (Some(n), Some(e)) => {
+                        Ok(CoseKey::RS256(crate::rsa::PublicKey::from_be_bytes(n, e)?))
+                    },
+                    _ => cose_err("invalid RSA key parameters"),
+                }
+            },
+            _ => Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "Only the ES256 and RS256 COSE keys are supported")),
+        }
+    }
+
+    /// the ES256 key of the big-endian P-256 point coordinates
+    pub(super) fn es256(x: &[u8], y: &[u8]) -> Result<Self, CryptoError> {
+        let (x, y) = (BigInt::from_be_bytes(x), BigInt::from_be_bytes(y));
+        let curve = CurveP256::new()?;
+        let p = curve.curve_params().field_order();
+        if &x >= p || &y >= p || !curve.is_on_curve(&x, &y) {
+            cose_err("the point is not on the P-256")
+        } else {
+            Ok(CoseKey::ES256(crate::elliptic::PublicKey::new_uncheck(&x, &y)))
+        }
+    }
+
+    /// decode the CBOR encoded COSE key
+    pub fn from_bytes(key: &[u8]) -> Result<Self, CryptoError> {
+        let (v, len) = CborValue::decode(key)?;
+        if len != key.len() {
+            return cose_err("trailing bytes");
+        }
+        Self::from_cbor(&v)
+    }
+
+    /// COSE algorithm identifier
+    pub fn alg(&self) -> i64 {
+        match self {
+            CoseKey::ES256(_) => Self::ALG_ES256,
+            CoseKey::RS256(_) => Self::ALG_RS256,
+        }
+    }
+
+    /// verify the `signature` over the `message`, the ES256 signature is DER encoded as the WebAuthn required.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), CryptoError> {
+        match self {
+            CoseKey::ES256(pk) => {
+                let sig = crate::ecdsa::SignatureContent::from_der(signature).map_err(|e| {
+                    CryptoError::new(CryptoErrorKind::VerificationFailed, format!("Invalid DER ECDSA signature: {}", e))
+                })?;
+                let mut ecdsa = ECDSA::new_unchcek(SHA256::new(), VerifyOnlyRand, CurveP256::new()?,
+                    crate::elliptic::KeyPair::from(pk.clone()))?;
+                ecdsa.verify(&sig, message)
+            },
+            CoseKey::RS256(pk) => {
+                let mut pkcs1 = PKCS1::new(SHA256::new(), VerifyOnlyRand,
+                    crate::rsa::KeyPair::from(pk.clone()), false)?;
+                pkcs1.verify(&crate::rsa::SignatureContent::from(signature), message)
+            },
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! WebAuthn/FIDO2 relying party
+//!
+//! Web Authentication Level 2: https://www.w3.org/TR/webauthn-2/
+
+mod cbor;
+
+mod cose;
+pub use cose::CoseKey;
+
+mod authenticator_data;
+pub use authenticator_data::{AuthenticatorData, AttestedCredentialData};
+
+mod attestation;
+
+mod relying_party;
+pub use relying_party::{RelyingParty, Registration, AttestationFormat};
+
+#[cfg(test)]
+mod webauthn_test;
@@ -0,0 +1,215 @@
+//! WebAuthn relying party operations
+//! WebAuthn Level 2, 7.1 Registering a New Credential, 7.2 Verifying an Authentication Assertion
+//!
+//! The `none` and the `packed` attestation formats are supported. For the `packed` attestation with the `x5c`
+//! certificate chain, the signature is verified by the attestation certificate `x5c[0]` and the certificate
+//! requirements(8.2.1) are checked, but there is no X.509 path validation in this crate, so the relying party must
+//! validate the `Registration::attestation_certificates` to its trust anchors(e.g. the FIDO metadata service) if it
+//! trusts the attestation.
+
+use crate::{CryptoError, CryptoErrorKind, Digest};
+use crate::webauthn::cbor::CborValue;
+use crate::webauthn::{AuthenticatorData, CoseKey};
+use crate::webauthn::attestation::packed_attestation_public_key;
+use crate::jcs::JsonValue;
+use crate::encoding::Base64;
+use crate::sha::SHA256;
+
+/// The attestation statement format of the registration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttestationFormat {
+    None,
+    /// the `packed` attestation signed by the credential private key itself
+    PackedSelf,
+    /// the `packed` attestation signed by the attestation certificate, the certificate chain isn't validated
+    PackedFull,
+}
+
+/// The verified credential which need to be stored by the relying party
+#[derive(Clone)]
+pub struct Registration {
+    pub credential_id: Vec<u8>,
+    pub aaguid: [u8; 16],
+    pub public_key: CoseKey,
+    pub sign_count: u32,
+    pub attestation_format: AttestationFormat,
+    /// the DER encoded `x5c` certificates of the `PackedFull`, the attestation certificate is the first, it's empty
+    /// for the other formats
+    pub attestation_certificates: Vec<Vec<u8>>,
+    pub authenticator_data: AuthenticatorData,
+}
+
+pub struct RelyingParty {
+    rp_id: String,
+    rp_id_hash: Vec<u8>,
+    origins: Vec<String>,
+    is_require_uv: bool,
+}
+
+fn verify_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::VerificationFailed, msg))
+}
+
+impl RelyingParty {
+    /// `rp_id`: the relying party identifier, e.g. `example.com`;
+    /// `origin`: the expected origin of the client data, e.g. `https://example.com`;
+    pub fn new(rp_id: &str, origin: &str) -> Self {
+        let mut sha = SHA256::new();
+        let mut rp_id_hash = Vec::with_capacity(32);
+        sha.write(rp_id.as_bytes());
+        sha.checksum(&mut rp_id_hash);
+
+        Self {
+            rp_id: rp_id.to_string(),
+            rp_id_hash,
+            origins: vec![origin.to_string()],
+            is_require_uv: false,
+        }
+    }
+
+    pub fn rp_id(&self) -> &str {
+        self.rp_id.as_str()
+    }
+
+    /// add another allowed origin, e.g. the subdomain
+    pub fn add_origin(&mut self, origin: &str) {
+        self.origins.push(origin.to_string());
+    }
+
+    /// require the user verified(UV) flag to be set, it's disabled by default
+    pub fn set_require_user_verification(&mut self, is_require: bool) {
+        self.is_require_uv = is_require;
+    }
+
+    /// verify the `clientDataJSON`, and return its SHA-256 hash
+    fn verify_client_data(&self, client_data_json: &[u8], ty: &str, challenge: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let json = std::str::from_utf8(client_data_json).map_err(|e| {
+            CryptoError::new(CryptoErrorKind::InvalidParameter, e)
+        })?;
+        let cd = match JsonValue::parse(json)? {
+            JsonValue::Object(obj) => obj,
+            _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The client data must be a JSON object")),
+        };
+        let get = |k: &str| cd.iter().find(|(x, _)| x == k).map(|(_, v)| v);
+
+        match get("type") {
+            Some(JsonValue::String(t)) if t == ty => {},
+            _ => return verify_err("The client data type not match"),
+        }
+        match get("challenge") {
+            Some(JsonValue::String(c)) if c == &Base64::url_safe().encode(challenge) => {},
+            _ => return verify_err("The client data challenge not match"),
+        }
+        match get("origin") {
+            Some(JsonValue::String(o)) if self.origins.iter().any(|x| x == o) => {},
+            _ => return verify_err("The client data origin not match"),
+        }
+        if let Some(JsonValue::Bool(true)) = get("crossOrigin") {
+            return verify_err("The cross origin request is not allowed");
+        }
+
+        let (mut sha, mut hash) = (SHA256::new(), Vec::with_capacity(32));
+        sha.write(client_data_json);
+        sha.checksum(&mut hash);
+        Ok(hash)
+    }
+
+    fn verify_auth_data(&self, auth_data: &AuthenticatorData) -> Result<(), CryptoError> {
+        if auth_data.rp_id_hash.as_ref() != self.rp_id_hash.as_slice() {
+            verify_err("The RP ID hash not match")
+        } else if !auth_data.is_user_present() {
+            verify_err("The user present flag is not set")
+        } else if self.is_require_uv && !auth_data.is_user_verified() {
+            verify_err("The user verified flag is not set")
+        } else {
+            Ok(())
+        }
+    }
+
+    /// verify the registration ceremony, the `challenge` is the raw bytes which is sent to the client.
+    pub fn verify_registration(&self, attestation_object: &[u8], client_data_json: &[u8], challenge: &[u8]) -> Result<Registration, CryptoError> {
+        let client_data_hash = self.verify_client_data(client_data_json, "webauthn.create", challenge)?;
+
+        let (att, len) = CborValue::decode(attestation_object)?;
+        let (fmt, att_stmt, raw_auth_data) = match (att.get_text("fmt").and_then(|x| x.as_text()),
+            att.get_text("attStmt"), att.get_text("authData").and_then(|x| x.as_bytes())) {
+            (Some(f), Some(s @ CborValue::Map(_)), Some(a)) if len == attestation_object.len() => (f, s, a),
+            _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "Invalid attestation object")),
+        };
+
+        let auth_data = AuthenticatorData::parse(raw_auth_data)?;
+        self.verify_auth_data(&auth_data)?;
+        let cred = match auth_data.attested_credential.as_ref() {
+            Some(c) => c,
+            None => return verify_err("The attested credential data is not included"),
+        };
+
+        let mut attestation_certificates = Vec::new();
+        let attestation_format = match fmt {
+            "none" => {
+                if att_stmt != &CborValue::Map(Vec::new()) {
+                    return verify_err("The attestation statement of the none format must be empty");
+                }
+                AttestationFormat::None
+            },
+            "packed" => {
+                let (format, key) = match att_stmt.get_text("x5c") {
+                    None => (AttestationFormat::PackedSelf, cred.public_key.clone()),
+                    Some(CborValue::Array(x5c)) if !x5c.is_empty() => {
+                        for c in x5c.iter() {
+                            match c.as_bytes() {
+                                Some(c) => attestation_certificates.push(c.to_vec()),
+                                None => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The x5c must be the certificates")),
+                            }
+                        }
+                        (AttestationFormat::PackedFull, packed_attestation_public_key(attestation_certificates[0].as_slice(), &cred.aaguid)?)
+                    },
+                    _ => return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The x5c must be the non-empty array")),
+                };
+                let alg = att_stmt.get_text("alg").and_then(|x| x.as_integer());
+                let sig = att_stmt.get_text("sig").and_then(|x| x.as_bytes());
+                match (alg, sig) {
+                    (Some(alg), Some(sig)) if alg == (key.alg() as i128) => {
+                        let mut msg = raw_auth_data.to_vec();
+                        msg.extend_from_slice(client_data_hash.as_slice());
+                        key.verify(msg.as_slice(), sig)?;
+                    },
+                    _ => return verify_err("The packed attestation algorithm not match"),
+                }
+                format
+            },
+            _ => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage,
+                format!("The attestation format {} is not supported", fmt))),
+        };
+
+        Ok(Registration {
+            credential_id: cred.credential_id.clone(),
+            aaguid: cred.aaguid,
+            public_key: cred.public_key.clone(),
+            sign_count: auth_data.sign_count,
+            attestation_format,
+            attestation_certificates,
+            authenticator_data: auth_data,
+        })
+    }
+
+    /// verify the authentication assertion by the stored `credential` public key, and return the new signature counter
+    /// which need to be stored.
+    /// The assertion is rejected if the signature counter doesn't increase, because the authenticator may be cloned.
+    pub fn verify_assertion(&self, credential: &CoseKey, authenticator_data: &[u8], client_data_json: &[u8],
+                            signature: &[u8], challenge: &[u8], stored_sign_count: u32) -> Result<u32, CryptoError> {
+        let client_data_hash = self.verify_client_data(client_data_json, "webauthn.get", challenge)?;
+        let auth_data = AuthenticatorData::parse(authenticator_data)?;
+        self.verify_auth_data(&auth_data)?;
+
+        let mut msg = authenticator_data.to_vec();
+        msg.extend_from_slice(client_data_hash.as_slice());
+        credential.verify(msg.as_slice(), signature)?;
+
+        if (auth_data.sign_count != 0 || stored_sign_count != 0) && auth_data.sign_count <= stored_sign_count {
+            return verify_err("The signature counter doesn't increase, the authenticator may be cloned");
+        }
+
+        Ok(auth_data.sign_count)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::webauthn::{RelyingParty, AttestationFormat, AuthenticatorData, CoseKey};
+use crate::webauthn::attestation::packed_attestation_public_key;
+use crate::CryptoErrorKind;
+use crate::test_util::hex;
+
+fn challenges() -> (Vec<u8>, Vec<u8>) {
+    ((0..32u8).collect(), (100..132u8).collect())
+}
+
+#[test]
+fn webauthn_es256_packed_self() {
+    let (reg_chal, auth_chal) = challenges();
+    let rp = RelyingParty::new("example.com", "https://example.com");
+    
+    let att_obj = hex(concat!(
+        "a363666d74667061636b65646761747453746d74a263616c67266373696758473045022100bbe16be2c560f55517d200e413821e5298a1a3",
+        "4f2b3082500d4b6e5775f28db5022061cf5289c24f6f896738e6e510866365b47f66104f25ada870788381f51dc3b7686175746844617461",
+        "5897a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19474500000000101112131415161718191a1b1c1d1e1f00",
+        "1365733235362d63726564656e7469616c2d6964a5010203262001215820471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7",
+        "b0d76dfa714c225820dd520f7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858",
+    ));
+    let reg_cd = r#"{"type":"webauthn.create","challenge":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8","origin":"https://example.com","crossOrigin":false}"#;
+    let reg = rp.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).unwrap();
+    assert_eq!(reg.attestation_format, AttestationFormat::PackedSelf);
+    assert_eq!(reg.credential_id.as_slice(), b"es256-credential-id".as_ref());
+    assert_eq!(reg.aaguid.to_vec(), (0x10..0x20u8).collect::<Vec<_>>());
+    assert_eq!(reg.sign_count, 0);
+    assert_eq!(reg.public_key.alg(), CoseKey::ALG_ES256);
+    assert!(reg.authenticator_data.is_user_verified());
+    
+    // the challenge, origin and RP ID must match
+    assert!(rp.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), auth_chal.as_slice()).is_err());
+    let other = RelyingParty::new("example.com", "https://evil.example");
+    assert!(other.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).is_err());
+    let other = RelyingParty::new("example.org", "https://example.com");
+    assert!(other.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).is_err());
+    
+    // the attestation signature covers the client data
+    let tampered_cd = reg_cd.replace("\"crossOrigin\":false", "\"crossOrigin\":false ");
+    assert!(rp.verify_registration(att_obj.as_slice(), tampered_cd.as_bytes(), reg_chal.as_slice()).is_err());
+    assert!(rp.verify_registration(&att_obj[..(att_obj.len() - 1)], reg_cd.as_bytes(), reg_chal.as_slice()).is_err());
+    
+    let auth_data = hex("a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19470500000005");
+    let auth_cd = r#"{"type":"webauthn.get","challenge":"ZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXp7fH1-f4CBgoM","origin":"https://example.com"}"#;
+    let sig = hex(concat!(
+        "3046022100d687554d5f10e60fefe26872facbe3bbe4c5d6f4813ed23e5c8ae7fc4170dd9b022100a5a181597203d5b0cb1ec5aa7ba26dd0",
+        "8dab13e44af2c635e233e24041594638",
+    ));
+    let count = rp.verify_assertion(&reg.public_key, auth_data.as_slice(), auth_cd.as_bytes(), sig.as_slice(),
+        auth_chal.as_slice(), reg.sign_count).unwrap();
+    assert_eq!(count, 5);
+    
+    // the cloned authenticator
+    let e = rp.verify_assertion(&reg.public_key, auth_data.as_slice(), auth_cd.as_bytes(), sig.as_slice(),
+        auth_chal.as_slice(), count).unwrap_err();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+    
+    let mut bad_sig = sig.clone();
+    let l = bad_sig.len();
+    bad_sig[l - 1] ^= 1;
+    assert!(rp.verify_assertion(&reg.public_key, auth_data.as_slice(), auth_cd.as_bytes(), bad_sig.as_slice(),
+        auth_chal.as_slice(), 0).is_err());
+    assert!(rp.verify_assertion(&reg.public_key, auth_data.as_slice(), auth_cd.as_bytes(), sig.as_slice(),
+        reg_chal.as_slice(), 0).is_err());
+    // the registration client data cannot be used as the assertion
+    assert!(rp.verify_assertion(&reg.public_key, auth_data.as_slice(), reg_cd.as_bytes(), sig.as_slice(),
+        reg_chal.as_slice(), 0).is_err());
+}
+
+#[test]
+fn webauthn_es256_packed_full() {
+    // the attestation certificate and its root are generated by the Python cryptography package, the attestation
+    // signature is signed by the attestation private key
+    let (reg_chal, _) = challenges();
+    let rp = RelyingParty::new("example.com", "https://example.com");
+    let att_obj = hex(concat!(
+        "a363666d74667061636b65646761747453746d74a363616c67266373696758473045022100e1d69e4ce44f04cefb838fed2a58f2cabdfc9c",
+        "e7e8887265e9ac10beec02000b02203945721f0411e2bc0738c139126c6f3ab70573c26a9a2f0f871fa0470ee2395663783563825901c430",
+        "8201c030820166a003020102020102300a06082a8648ce3d0403023042310b30090603550406130255533110300e060355040a0c07726372",
+        "7970746f3121301f06035504030c187263727970746f206174746573746174696f6e20726f6f74301e170d3234303130313030303030305a",
+        "170d3434303130313030303030305a305c310b30090603550406130255533110300e060355040a0c077263727970746f3122302006035504",
+        "0b0c1941757468656e74696361746f72204174746573746174696f6e3117301506035504030c0e7263727970746f207061636b6564305930",
+        "1306072a8648ce3d020106082a8648ce3d03010703420004f28bf84e98b09b418caf1974d37fcd851e660f070456a96342b713608d177744",
+        "97271fbf2ed09de2b378672f9112eaca5b826bfd806419ab18677f591e493d97a3333031300c0603551d130101ff040230003021060b2b06",
+        "01040182e51c01010404120410101112131415161718191a1b1c1d1e1f300a06082a8648ce3d0403020348003045022072dd7d7ab42ac112",
+        "d4ca99e680f12dc4bf55073425deea67fe80245861cbdb6d022100b2ca84060681b9f672e3f5f2cc95f3edbdccfc54862c01807bdf089f59",
+        "ef330959018b308201873082012ca003020102020101300a06082a8648ce3d0403023042310b30090603550406130255533110300e060355",
+        "040a0c077263727970746f3121301f06035504030c187263727970746f206174746573746174696f6e20726f6f74301e170d323430313031",
+        "3030303030305a170d3434303130313030303030305a3042310b30090603550406130255533110300e060355040a0c077263727970746f31",
+        "21301f06035504030c187263727970746f206174746573746174696f6e20726f6f743059301306072a8648ce3d020106082a8648ce3d0301",
+        "0703420004c1b4bffd64c2c07dead982d3f7ad69a56f57612dee0ab97e27cc2ee669495dd6d40787d155743c155a114222fb6f18672703e9",
+        "34932f0bd39d50e7ffc8162b1da3133011300f0603551d130101ff040530030101ff300a06082a8648ce3d0403020349003046022100e74f",
+        "da738a02d424e5bbcec65eccd8d0f026f2211ec85422ba0ff9b4946052ca022100833976e40d02bdfdc7bce812b832d5a75a44aebcb16f15",
+        "370a2f9b8d7aa22c2f686175746844617461589da379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce194745000000",
+        "00101112131415161718191a1b1c1d1e1f00197061636b65642d66756c6c2d63726564656e7469616c2d6964a501020326200121582071bc",
+        "96844767e27b1eaf78ec7671f7f0f6cb04f132c5b881faeea97cf7c1b6402258202e9493f424bc85d4d41cada62976823dc08bcf618da05c",
+        "efbf50afaafb4f4928",
+    ));
+    let reg_cd = r#"{"type":"webauthn.create","challenge":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8","origin":"https://example.com"}"#;
+    let reg = rp.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).unwrap();
+    assert_eq!(reg.attestation_format, AttestationFormat::PackedFull);
+    assert_eq!(reg.credential_id.as_slice(), b"packed-full-credential-id".as_ref());
+    assert_eq!(reg.public_key.alg(), CoseKey::ALG_ES256);
+    assert_eq!(reg.attestation_certificates.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![452, 395]);
+    assert_eq!(reg.attestation_certificates[0].as_slice(), &att_obj[111..563]);
+
+    let e = rp.verify_registration(att_obj.as_slice(), reg_cd.replace("}", " }").as_bytes(), reg_chal.as_slice()).err().unwrap();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+    // the subject OU of the attestation certificate
+    let mut tampered = att_obj.clone();
+    let pos = tampered.windows(13).position(|x| x == b"Authenticator").unwrap();
+    tampered[pos] ^= 0x20;
+    let e = rp.verify_registration(tampered.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).err().unwrap();
+    assert_eq!(e.kind(), CryptoErrorKind::VerificationFailed);
+
+    // the AAGUID extension doesn't match, the root is the CA certificate without the OU
+    let (cert, root) = (reg.attestation_certificates[0].as_slice(), reg.attestation_certificates[1].as_slice());
+    assert_eq!(packed_attestation_public_key(cert, &reg.aaguid).unwrap().alg(), CoseKey::ALG_ES256);
+    assert_eq!(packed_attestation_public_key(cert, &[0u8; 16]).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+    assert_eq!(packed_attestation_public_key(root, &reg.aaguid).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+    assert!(packed_attestation_public_key(&cert[..(cert.len() - 1)], &reg.aaguid).is_err());
+}
+
+#[test]
+fn webauthn_rs256_none() {
+    let (reg_chal, auth_chal) = challenges();
+    let mut rp = RelyingParty::new("example.com", "https://example.com");
+    
+    let att_obj = hex(concat!(
+        "a363666d74646e6f6e656761747453746d74a068617574684461746159015aa379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13",
+        "d2125586ce1947410000000000000000000000000000000000000000001372733235362d63726564656e7469616c2d6964a4010303390100",
+        "20590100b6bd7a95f4975517cd8be7a13d7f31410c685062a294f2790215a4b1340f2b23cc4a3215374da87663693ec34ae7f122d0bb1944",
+        "f274426738b7b26e49518fdba2c6e2a434bc6336d816d8784dd6b3b3d56deb3d83217263fcb572715bfb040f573b702723482b473df72332",
+        "26a5385ada3ea0eeb4fbaa7a13a1d1e83a7126dc59dd47bcf812939178f79f1ef866da2f44e61da99d5f4c4d0bfa166123db1aa1cad079a7",
+        "e390ec57c35018114f9cc939786957edc0f975d349e6a27f39b303680d6784e03a342aee119fd4a338cca175911cc1fd5aa01add42069f53",
+        "eb23e00826cdd7222501a7db9b006b8a6366bec02d71ab5101789029a28971666e9891f32143010001",
+    ));
+    let reg_cd = r#"{"type":"webauthn.create","challenge":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8","origin":"https://example.com"}"#;
+    let reg = rp.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).unwrap();
+    assert_eq!(reg.attestation_format, AttestationFormat::None);
+    assert_eq!(reg.credential_id.as_slice(), b"rs256-credential-id".as_ref());
+    assert_eq!(reg.public_key.alg(), CoseKey::ALG_RS256);
+    
+    let auth_data = hex("a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19470100000000");
+    let auth_cd = r#"{"type":"webauthn.get","challenge":"ZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXp7fH1-f4CBgoM","origin":"https://example.com"}"#;
+    let sig = hex(concat!(
+        "590eb86112510286cb4bfeafcec10f8e66e11715fed327ea4b209e364eac214d860712a2061ce6fdd36bed4ffcdfcea2353ba0e9bc1bc870",
+        "f48915509ffa1e4d36121d2d692712ae1140b32d1c22dc05c9c39a7c119035a072bf171d0d77b06c06e3276393bebb7227ac350b6100d996",
+        "6db6ce61a9a56673aa45c6fd6164735ba76c2bcb2ecfa0a4adab48f36356378fff19d6d4d69232c0f0ebc5357793a698d27dfa2e02c08be2",
+        "0495d9d44013f63cb35922b27400c39af8c2fb4e99dbcbc0bbc5a9cbaed43bcd6472020c9e578be5ab1500d4873d1d025686ea695914877e",
+        "01ed3a22aa6047af78958b3840a60618493c9957491e33daf3e0264bb39dad6e",
+    ));
+    // the authenticator doesn't support the signature counter
+    let count = rp.verify_assertion(&reg.public_key, auth_data.as_slice(), auth_cd.as_bytes(), sig.as_slice(),
+        auth_chal.as_slice(), 0).unwrap();
+    assert_eq!(count, 0);
+    
+    let ad = AuthenticatorData::parse(auth_data.as_slice()).unwrap();
+    assert!(ad.is_user_present() && !ad.is_user_verified() && ad.attested_credential.is_none());
+    rp.set_require_user_verification(true);
+    assert!(rp.verify_assertion(&reg.public_key, auth_data.as_slice(), auth_cd.as_bytes(), sig.as_slice(),
+        auth_chal.as_slice(), 0).is_err());
+    assert!(rp.verify_registration(att_obj.as_slice(), reg_cd.as_bytes(), reg_chal.as_slice()).is_err());
+    
+    let mut trailing = auth_data.clone();
+    trailing.push(0);
+    assert!(AuthenticatorData::parse(trailing.as_slice()).is_err());
+    assert!(AuthenticatorData::parse(&auth_data[..36]).is_err());
+    assert!(CoseKey::from_bytes(&[0xa1, 0x01, 0x01]).is_err());
+}
@@ -0,0 +1,283 @@
+//! The WebCrypto algorithm identifiers and the parameter dictionaries
+
+use crate::{CryptoError, CryptoErrorKind};
+use crate::jcs::JsonValue;
+
+fn param_err<T>(msg: &str) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::InvalidParameter, format!("Invalid WebCrypto algorithm: {}", msg)))
+}
+
+/// The WebCrypto hash algorithm
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// the algorithm name is case-insensitive as the WebCrypto normalization
+    pub fn from_name(name: &str) -> Option<Self> {
+        [HashAlgorithm::Sha1, HashAlgorithm::Sha256, HashAlgorithm::Sha384, HashAlgorithm::Sha512].iter()
+            .find(|h| h.name().eq_ignore_ascii_case(name)).copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha1 => "SHA-1",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha384 => "SHA-384",
+            HashAlgorithm::Sha512 => "SHA-512",
+        }
+    }
+
+    /// the digest length in bytes
+    pub fn digest_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+/// `AesCbcParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AesCbcParams {
+    pub iv: Vec<u8>,
+}
+
+/// `AesCtrParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AesCtrParams {
+    /// the initial counter block
+    pub counter: Vec<u8>,
+    /// the number of the rightmost bits of the counter block which are incremented, in the range `[1, 128]`
+    pub length: usize,
+}
+
+/// `AesGcmParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AesGcmParams {
+    pub iv: Vec<u8>,
+    pub additional_data: Vec<u8>,
+    /// the tag length in bits, it's 128 by default
+    pub tag_length: usize,
+}
+
+/// `HmacImportParams`, the hash algorithm belongs to the key in the WebCrypto, so it's specified here for the raw key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HmacParams {
+    pub hash: HashAlgorithm,
+}
+
+/// `Pbkdf2Params`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pbkdf2Params {
+    pub hash: HashAlgorithm,
+    pub salt: Vec<u8>,
+    pub iterations: usize,
+}
+
+/// `HkdfParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HkdfParams {
+    pub hash: HashAlgorithm,
+    pub salt: Vec<u8>,
+    pub info: Vec<u8>,
+}
+
+/// `RsaPssParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPssParams {
+    pub salt_length: usize,
+}
+
+/// `RsaOaepParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaOaepParams {
+    pub label: Vec<u8>,
+}
+
+/// `EcdsaParams`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcdsaParams {
+    pub hash: HashAlgorithm,
+}
+
+/// The normalized WebCrypto algorithm with its parameters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Digest(HashAlgorithm),
+    AesCbc(AesCbcParams),
+    AesCtr(AesCtrParams),
+    AesGcm(AesGcmParams),
+    AesKw,
+    Hmac(HmacParams),
+    Pbkdf2(Pbkdf2Params),
+    Hkdf(HkdfParams),
+    RsassaPkcs1v15,
+    RsaPss(RsaPssParams),
+    RsaOaep(RsaOaepParams),
+    Ecdsa(EcdsaParams),
+    Ecdh,
+}
+
+const GCM_TAG_LENGTHS: [usize; 7] = [32, 64, 96, 104, 112, 120, 128];
+
+/// the member of the algorithm dictionary
+struct Members<'a>(&'a [(String, JsonValue)]);
+
+impl<'a> Members<'a> {
+    fn get(&self, name: &str) -> Option<&'a JsonValue> {
+        self.0.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+    }
+
+    fn required(&self, name: &str) -> Result<&'a JsonValue, CryptoError> {
+        match self.get(name) {
+            Some(v) => Ok(v),
+            None => param_err(format!("the member {} is required", name).as_str()),
+        }
+    }
+
+    /// the `BufferSource` is represented as the array of the bytes
+    fn bytes(&self, name: &str) -> Result<Option<Vec<u8>>, CryptoError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(JsonValue::Array(a)) => a.iter().map(|x| match x {
+                JsonValue::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Ok(*n as u8),
+                _ => param_err(format!("the member {} must be an array of bytes", name).as_str()),
+            }).collect::<Result<Vec<_>, _>>().map(Some),
+            Some(_) => param_err(format!("the member {} must be an array of bytes", name).as_str()),
+        }
+    }
+
+    fn required_bytes(&self, name: &str) -> Result<Vec<u8>, CryptoError> {
+        self.required(name)?;
+        self.bytes(name).map(|x| x.unwrap_or_default())
+    }
+
+    fn integer(&self, name: &str) -> Result<Option<usize>, CryptoError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(JsonValue::Number(n)) if n.fract() == 0.0 && *n >= 0.0 && *n <= (u32::MAX as f64) => Ok(Some(*n as usize)),
+            Some(_) => param_err(format!("the member {} must be a non-negative integer", name).as_str()),
+        }
+    }
+
+    fn required_integer(&self, name: &str) -> Result<usize, CryptoError> {
+        self.required(name)?;
+        self.integer(name).map(|x| x.unwrap_or_default())
+    }
+
+    /// the `HashAlgorithmIdentifier` is the name or the dictionary `{name}`
+    fn hash(&self) -> Result<HashAlgorithm, CryptoError> {
+        let name = match self.required("hash")? {
+            JsonValue::String(s) => s.as_str(),
+            JsonValue::Object(o) => match Members(o.as_slice()).get("name") {
+                Some(JsonValue::String(s)) => s.as_str(),
+                _ => return param_err("the hash must have the name"),
+            },
+            _ => return param_err("the hash must be a name or an object"),
+        };
+
+        match HashAlgorithm::from_name(name) {
+            Some(h) => Ok(h),
+            None => Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The hash algorithm {} is not supported", name))),
+        }
+    }
+}
+
+impl Algorithm {
+    /// the canonical WebCrypto algorithm name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Digest(h) => h.name(),
+            Algorithm::AesCbc(_) => "AES-CBC",
+            Algorithm::AesCtr(_) => "AES-CTR",
+            Algorithm::AesGcm(_) => "AES-GCM",
+            Algorithm::AesKw => "AES-KW",
+            Algorithm::Hmac(_) => "HMAC",
+            Algorithm::Pbkdf2(_) => "PBKDF2",
+            Algorithm::Hkdf(_) => "HKDF",
+            Algorithm::RsassaPkcs1v15 => "RSASSA-PKCS1-v1_5",
+            Algorithm::RsaPss(_) => "RSA-PSS",
+            Algorithm::RsaOaep(_) => "RSA-OAEP",
+            Algorithm::Ecdsa(_) => "ECDSA",
+            Algorithm::Ecdh => "ECDH",
+        }
+    }
+
+    /// check the parameters as the WebCrypto `encrypt`/`sign`/`deriveBits` do
+    pub fn check(&self) -> Result<(), CryptoError> {
+        match self {
+            Algorithm::AesCbc(p) if p.iv.len() != 16 => param_err("the AES-CBC iv must be 16 bytes"),
+            Algorithm::AesCtr(p) if p.counter.len() != 16 => param_err("the AES-CTR counter must be 16 bytes"),
+            Algorithm::AesCtr(p) if p.length == 0 || p.length > 128 => param_err("the AES-CTR length must be in the range [1, 128]"),
+            Algorithm::AesGcm(p) if p.iv.is_empty() => param_err("the AES-GCM iv must not be empty"),
+            Algorithm::AesGcm(p) if !GCM_TAG_LENGTHS.contains(&p.tag_length) => param_err("the AES-GCM tagLength is invalid"),
+            Algorithm::Pbkdf2(p) if p.iterations == 0 => param_err("the PBKDF2 iterations must be great than 0"),
+            _ => Ok(()),
+        }
+    }
+
+    /// parse the `AlgorithmIdentifier` from the JSON value, which is the algorithm name(e.g. `"SHA-256"`) or the
+    /// algorithm dictionary(e.g. `{"name": "AES-GCM", "iv": [...], "tagLength": 128}`).
+    /// The name is case-insensitive, the `BufferSource` members are the arrays of the bytes.
+    pub fn from_json(alg: &JsonValue) -> Result<Self, CryptoError> {
+        let empty = Vec::new();
+        let (name, members) = match alg {
+            JsonValue::String(s) => (s.as_str(), Members(empty.as_slice())),
+            JsonValue::Object(o) => match Members(o.as_slice()).get("name") {
+                Some(JsonValue::String(s)) => (s.as_str(), Members(o.as_slice())),
+                _ => return param_err("the algorithm must have the name"),
+            },
+            _ => return param_err("the algorithm must be a name or an object"),
+        };
+
+        if let Some(h) = HashAlgorithm::from_name(name) {
+            return Ok(Algorithm::Digest(h));
+        }
+
+        let alg = match name.to_ascii_uppercase().as_str() {
+            "AES-CBC" => Algorithm::AesCbc(AesCbcParams { iv: members.required_bytes("iv")? }),
+            "AES-CTR" => Algorithm::AesCtr(AesCtrParams {
+                counter: members.required_bytes("counter")?,
+                length: members.required_integer("length")?,
+            }),
+            "AES-GCM" => Algorithm::AesGcm(AesGcmParams {
+                iv: members.required_bytes("iv")?,
+                additional_data: members.bytes("additionalData")?.unwrap_or_default(),
+                tag_length: members.integer("tagLength")?.unwrap_or(128),
+            }),
+            "AES-KW" => Algorithm::AesKw,
+            "HMAC" => Algorithm::Hmac(HmacParams { hash: members.hash()? }),
+            "PBKDF2" => Algorithm::Pbkdf2(Pbkdf2Params {
+                hash: members.hash()?,
+                salt: members.required_bytes("salt")?,
+                iterations: members.required_integer("iterations")?,
+            }),
+            "HKDF" => Algorithm::Hkdf(HkdfParams {
+                hash: members.hash()?,
+                salt: members.required_bytes("salt")?,
+                info: members.required_bytes("info")?,
+            }),
+            "RSASSA-PKCS1-V1_5" => Algorithm::RsassaPkcs1v15,
+            "RSA-PSS" => Algorithm::RsaPss(RsaPssParams { salt_length: members.required_integer("saltLength")? }),
+            "RSA-OAEP" => Algorithm::RsaOaep(RsaOaepParams { label: members.bytes("label")?.unwrap_or_default() }),
+            "ECDSA" => Algorithm::Ecdsa(EcdsaParams { hash: members.hash()? }),
+            "ECDH" => Algorithm::Ecdh,
+            _ => return Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The algorithm {} is not supported", name))),
+        };
+
+        alg.check()?;
+        Ok(alg)
+    }
+
+    /// parse the `AlgorithmIdentifier` from the JSON text
+    pub fn parse(json: &str) -> Result<Self, CryptoError> {
+        Self::from_json(&JsonValue::parse(json)?)
+    }
+}
@@ -0,0 +1,22 @@
+//! WebCrypto(W3C Web Cryptography API) compatibility layer
+//!
+//! The WebCrypto algorithm identifiers(e.g. `{name: "AES-CBC", iv}`) are normalized to the `Algorithm`, and the
+//! `SubtleCrypto` operations are mapped to the constructions of this crate, it simplifies porting the JS/TS designs.
+//!
+//! The operations take the raw keys(the `raw` import format), the supported algorithms are:
+//! - `digest`: SHA-1, SHA-256, SHA-384, SHA-512;
+//! - `encrypt`/`decrypt`: AES-CBC(PKCS#7 padding), AES-CTR, AES-GCM;
+//! - `sign`/`verify`: HMAC;
+//! - `deriveBits`: PBKDF2, HKDF;
+//! - `wrapKey`/`unwrapKey`: AES-KW, and the `encrypt`/`decrypt` algorithms;
+//!
+//! The other algorithms are parsed and checked, but their operations return the `NotSupportUsage`.
+
+mod algorithm;
+pub use algorithm::{Algorithm, HashAlgorithm, AesCbcParams, AesCtrParams, AesGcmParams, HmacParams, Pbkdf2Params,
+    HkdfParams, RsaPssParams, RsaOaepParams, EcdsaParams};
+
+pub mod subtle;
+
+#[cfg(test)]
+mod webcrypto_test;
@@ -0,0 +1,228 @@
+//! The `SubtleCrypto` operations with the raw keys
+
+use crate::{CryptoError, CryptoErrorKind, Cipher, Digest, AES, HMAC};
+use crate::sha::{SHA1, SHA256, SHA384, SHA512};
+use crate::cipher_mode::{CBC, CTR, GCM, Counter, PKCS7Padding, FixedInitialVec, KeyWrap};
+use crate::kdf::{PBKDF2, HKDF};
+use crate::webcrypto::{Algorithm, HashAlgorithm};
+
+const AES_BLOCK_LEN: usize = 16;
+
+fn not_support<T>(op: &str, alg: &Algorithm) -> Result<T, CryptoError> {
+    Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, format!("The {} operation of the {} is not supported", op, alg.name())))
+}
+
+/// The AES-CTR counter block which only increments the rightmost `length` bits, the other bits are the nonce.
+struct PartialCounter {
+    initial_val: Vec<u8>,
+    length: usize,
+    cur_val: Option<Vec<u8>>,
+}
+
+impl Counter for PartialCounter {
+    fn reset(&mut self) {
+        self.cur_val.take();
+    }
+
+    fn next(&mut self) -> Option<&Vec<u8>> {
+        match self.cur_val.as_mut() {
+            None => {
+                self.cur_val = Some(self.initial_val.clone());
+            },
+            Some(v) => {
+                // the counter wraps modulo 2^length
+                let (mut bits, mut carry) = (self.length, 1u16);
+                for b in v.iter_mut().rev() {
+                    if bits == 0 || carry == 0 {
+                        break;
+                    }
+                    let mask = if bits >= 8 { 0xffu16 } else { (1u16 << bits) - 1 };
+                    let x = ((*b as u16) & mask) + carry;
+                    carry = x >> bits.min(8);
+                    *b = (((*b as u16) & !mask) | (x & mask)) as u8;
+                    bits -= bits.min(8);
+                }
+            },
+        }
+        self.cur_val.as_ref()
+    }
+
+    fn bits_len(&self) -> usize {
+        self.initial_val.len() << 3
+    }
+
+    /// the counter block must not be reused in the same operation
+    fn remaining_blocks(&self) -> Option<u128> {
+        let total = if self.length >= 128 { u128::MAX } else { 1u128 << self.length };
+        let used = match self.cur_val.as_ref() {
+            None => 0,
+            Some(v) => {
+                let mask = if self.length >= 128 { u128::MAX } else { (1u128 << self.length) - 1 };
+                let val = |x: &[u8]| x.iter().fold(0u128, |a, &b| (a << 8) | (b as u128)) & mask;
+                (val(v.as_slice()).wrapping_sub(val(self.initial_val.as_slice())) & mask) + 1
+            },
+        };
+        Some(total - used.min(total))
+    }
+}
+
+fn hash_digest(hash: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+    match hash {
+        HashAlgorithm::Sha1 => SHA1::digest(data),
+        HashAlgorithm::Sha256 => SHA256::digest(data),
+        HashAlgorithm::Sha384 => SHA384::digest(data),
+        HashAlgorithm::Sha512 => SHA512::digest(data),
+    }
+}
+
+fn hmac_with<D: Digest>(digest: D, key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (mut hmac, mut mac) = (HMAC::new(key.to_vec(), digest)?, Vec::new());
+    hmac.write(data);
+    hmac.checksum(&mut mac);
+    Ok(mac)
+}
+
+fn hmac(hash: HashAlgorithm, key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if key.is_empty() {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The HMAC key must not be empty"));
+    }
+
+    match hash {
+        HashAlgorithm::Sha1 => hmac_with(SHA1::new(), key, data),
+        HashAlgorithm::Sha256 => hmac_with(SHA256::new(), key, data),
+        HashAlgorithm::Sha384 => hmac_with(SHA384::new(), key, data),
+        HashAlgorithm::Sha512 => hmac_with(SHA512::new(), key, data),
+    }
+}
+
+fn pbkdf2_with<D: Digest>(digest: D, iterations: usize, password: &[u8], salt: &[u8], key_len: usize) -> Result<Vec<u8>, CryptoError> {
+    let mut key = Vec::with_capacity(key_len);
+    PBKDF2::new(digest, iterations)?.derive(password, salt, key_len, &mut key)?;
+    Ok(key)
+}
+
+fn hkdf_with<D: Digest>(digest: D, key: &[u8], salt: &[u8], info: &[u8], key_len: usize) -> Result<Vec<u8>, CryptoError> {
+    let mut okm = Vec::with_capacity(key_len);
+    HKDF::new(digest)?.derive(salt, key, info, key_len, &mut okm)?;
+    Ok(okm)
+}
+
+fn aes_crypt(alg: &Algorithm, key: &[u8], data: &[u8], is_encrypt: bool) -> Result<Vec<u8>, CryptoError> {
+    alg.check()?;
+    let mut dst = Vec::with_capacity(data.len() + AES_BLOCK_LEN);
+    match alg {
+        Algorithm::AesCbc(p) => {
+            let aes = AES::new(key.to_vec())?;
+            let padding = PKCS7Padding::new(&aes);
+            let cbc = CBC::new(aes, padding, FixedInitialVec::new(p.iv.clone()))?;
+            if is_encrypt { cbc.encrypt(&mut dst, data)?; } else { cbc.decrypt(&mut dst, data)?; }
+        },
+        Algorithm::AesCtr(p) => {
+            let counter = PartialCounter { initial_val: p.counter.clone(), length: p.length, cur_val: None };
+            let ctr = CTR::new(AES::new(key.to_vec())?, counter)?;
+            if is_encrypt { ctr.encrypt(&mut dst, data)?; } else { ctr.decrypt(&mut dst, data)?; }
+        },
+        Algorithm::AesGcm(p) => {
+            // the `tagLength` is in bits, the tag is appended to the ciphertext
+            let gcm = GCM::new(AES::new(key.to_vec())?, p.tag_length >> 3)?;
+            let (iv, aad) = (p.iv.as_slice(), p.additional_data.as_slice());
+            if is_encrypt { gcm.encrypt(&mut dst, iv, aad, data)?; } else { gcm.decrypt(&mut dst, iv, aad, data)?; }
+        },
+        _ => return not_support(if is_encrypt { "encrypt" } else { "decrypt" }, alg),
+    }
+    Ok(dst)
+}
+
+/// `digest(algorithm, data)`
+pub fn digest(alg: &Algorithm, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    match alg {
+        Algorithm::Digest(h) => Ok(hash_digest(*h, data)),
+        _ => not_support("digest", alg),
+    }
+}
+
+/// `encrypt(algorithm, key, data)`, the `key` is the raw key.
+pub fn encrypt(alg: &Algorithm, key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    aes_crypt(alg, key, data, true)
+}
+
+/// `decrypt(algorithm, key, data)`, the `key` is the raw key.
+pub fn decrypt(alg: &Algorithm, key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    aes_crypt(alg, key, data, false)
+}
+
+/// `wrapKey("raw", key, wrappingKey, wrapAlgorithm)`, the `key_data` is the raw key to be wrapped, the
+/// `wrapping_key` is the raw key of the AES-KW, or the key of the `encrypt` algorithms.
+pub fn wrap_key(alg: &Algorithm, wrapping_key: &[u8], key_data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    match alg {
+        Algorithm::AesKw => {
+            let mut dst = Vec::with_capacity(key_data.len() + 8);
+            KeyWrap::new(AES::new(wrapping_key.to_vec())?)?.wrap(&mut dst, key_data)?;
+            Ok(dst)
+        },
+        _ => encrypt(alg, wrapping_key, key_data),
+    }
+}
+
+/// `unwrapKey("raw", wrappedKey, unwrappingKey, unwrapAlgorithm)`, return the raw key, the
+/// `CryptoErrorKind::VerificationFailed` is returned if the AES-KW integrity check failed.
+pub fn unwrap_key(alg: &Algorithm, unwrapping_key: &[u8], wrapped_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    match alg {
+        Algorithm::AesKw => {
+            let mut dst = Vec::with_capacity(wrapped_key.len());
+            KeyWrap::new(AES::new(unwrapping_key.to_vec())?)?.unwrap(&mut dst, wrapped_key)?;
+            Ok(dst)
+        },
+        _ => decrypt(alg, unwrapping_key, wrapped_key),
+    }
+}
+
+/// `sign(algorithm, key, data)`, the `key` is the raw key.
+pub fn sign(alg: &Algorithm, key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    match alg {
+        Algorithm::Hmac(p) => hmac(p.hash, key, data),
+        _ => not_support("sign", alg),
+    }
+}
+
+/// `verify(algorithm, key, signature, data)`, the `key` is the raw key, the MAC is compared in constant time.
+pub fn verify(alg: &Algorithm, key: &[u8], signature: &[u8], data: &[u8]) -> Result<bool, CryptoError> {
+    match alg {
+        Algorithm::Hmac(p) => {
+            let mac = hmac(p.hash, key, data)?;
+            Ok(mac.len() == signature.len() && mac.iter().zip(signature.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) == 0)
+        },
+        _ => not_support("verify", alg),
+    }
+}
+
+/// `deriveBits(algorithm, baseKey, length)`, the `key` is the raw key, the `length` is in bits and must be a
+/// multiple of 8.
+pub fn derive_bits(alg: &Algorithm, key: &[u8], length: usize) -> Result<Vec<u8>, CryptoError> {
+    alg.check()?;
+    if length == 0 || (length & 7) != 0 {
+        return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The length must be a non-zero multiple of 8"));
+    }
+
+    match alg {
+        Algorithm::Pbkdf2(p) => {
+            let (n, salt, c) = (length >> 3, p.salt.as_slice(), p.iterations);
+            match p.hash {
+                HashAlgorithm::Sha1 => pbkdf2_with(SHA1::new(), c, key, salt, n),
+                HashAlgorithm::Sha256 => pbkdf2_with(SHA256::new(), c, key, salt, n),
+                HashAlgorithm::Sha384 => pbkdf2_with(SHA384::new(), c, key, salt, n),
+                HashAlgorithm::Sha512 => pbkdf2_with(SHA512::new(), c, key, salt, n),
+            }
+        },
+        Algorithm::Hkdf(p) => {
+            let (n, salt, info) = (length >> 3, p.salt.as_slice(), p.info.as_slice());
+            match p.hash {
+                HashAlgorithm::Sha1 => hkdf_with(SHA1::new(), key, salt, info, n),
+                HashAlgorithm::Sha256 => hkdf_with(SHA256::new(), key, salt, info, n),
+                HashAlgorithm::Sha384 => hkdf_with(SHA384::new(), key, salt, info, n),
+                HashAlgorithm::Sha512 => hkdf_with(SHA512::new(), key, salt, info, n),
+            }
+        },
+        _ => not_support("deriveBits", alg),
+    }
+}
@@ -0,0 +1,117 @@
+use crate::webcrypto::{Algorithm, HashAlgorithm, AesCtrParams, subtle};
+use crate::CryptoErrorKind;
+use crate::test_util::hex;
+
+fn json_bytes(b: &[u8]) -> String {
+    format!("[{}]", b.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(","))
+}
+
+#[test]
+fn webcrypto_algorithm_parse() {
+    assert_eq!(Algorithm::parse("\"sha-256\"").unwrap(), Algorithm::Digest(HashAlgorithm::Sha256));
+    assert_eq!(Algorithm::parse("{\"name\": \"SHA-512\"}").unwrap().name(), "SHA-512");
+    
+    let gcm = Algorithm::parse("{\"name\": \"aes-gcm\", \"iv\": [1,2,3,4,5,6,7,8,9,10,11,12]}").unwrap();
+    match gcm {
+        Algorithm::AesGcm(ref p) => assert_eq!((p.iv.len(), p.tag_length, p.additional_data.len()), (12, 128, 0)),
+        _ => panic!("{:?}", gcm),
+    }
+    assert_eq!(gcm.name(), "AES-GCM");
+    assert!(Algorithm::parse("{\"name\": \"AES-GCM\", \"iv\": [1], \"tagLength\": 100}").is_err());
+    assert!(Algorithm::parse("{\"name\": \"AES-GCM\", \"iv\": [256]}").is_err());
+    assert!(Algorithm::parse("{\"name\": \"AES-GCM\"}").is_err());
+    
+    let hmac = Algorithm::parse("{\"name\": \"HMAC\", \"hash\": {\"name\": \"SHA-384\"}}").unwrap();
+    assert_eq!(hmac, Algorithm::parse("{\"name\": \"HMAC\", \"hash\": \"SHA-384\"}").unwrap());
+    let pss = Algorithm::parse("{\"name\": \"RSA-PSS\", \"saltLength\": 32}").unwrap();
+    assert_eq!(pss.name(), "RSA-PSS");
+    assert_eq!(Algorithm::parse("\"RSASSA-PKCS1-v1_5\"").unwrap(), Algorithm::RsassaPkcs1v15);
+    
+    assert_eq!(Algorithm::parse("\"SHA-3\"").unwrap_err().kind(), CryptoErrorKind::NotSupportUsage);
+    assert_eq!(Algorithm::parse("{\"name\": \"ECDSA\", \"hash\": \"MD5\"}").unwrap_err().kind(), CryptoErrorKind::NotSupportUsage);
+    assert!(Algorithm::parse("{\"name\": \"PBKDF2\", \"hash\": \"SHA-1\", \"salt\": [], \"iterations\": 0}").is_err());
+    assert!(Algorithm::parse("{\"iv\": []}").is_err());
+}
+
+#[test]
+fn webcrypto_subtle() {
+    let sha = Algorithm::parse("\"SHA-256\"").unwrap();
+    assert_eq!(subtle::digest(&sha, b"abc").unwrap(), hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+    
+    // NIST SP 800-38A F.2.1, F.5.1
+    let (key, data) = (hex("2b7e151628aed2a6abf7158809cf4f3c"), hex("6bc1bee22e409f96e93d7e117393172a"));
+    let cbc = Algorithm::parse(format!("{{\"name\": \"AES-CBC\", \"iv\": {}}}", 
+        json_bytes(hex("000102030405060708090a0b0c0d0e0f").as_slice())).as_str()).unwrap();
+    let c = subtle::encrypt(&cbc, key.as_slice(), data.as_slice()).unwrap();
+    assert_eq!((c.len(), &c[..16]), (32, hex("7649abac8119b246cee98e9b12e9197d").as_slice()));
+    assert_eq!(subtle::decrypt(&cbc, key.as_slice(), c.as_slice()).unwrap(), data);
+    
+    let ctr = Algorithm::parse(format!("{{\"name\": \"AES-CTR\", \"counter\": {}, \"length\": 64}}",
+        json_bytes(hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").as_slice())).as_str()).unwrap();
+    let c = subtle::encrypt(&ctr, key.as_slice(), data.as_slice()).unwrap();
+    assert_eq!(c, hex("874d6191b620e3261bef6864990db6ce"));
+    assert_eq!(subtle::decrypt(&ctr, key.as_slice(), c.as_slice()).unwrap(), data);
+    
+    // only the rightmost `length` bits are incremented
+    let mut counter = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+    let wrap = Algorithm::AesCtr(AesCtrParams { counter: counter.clone(), length: 4 });
+    let c = subtle::encrypt(&wrap, key.as_slice(), [0u8; 32].as_ref()).unwrap();
+    counter[15] = 0xf0;
+    let next = Algorithm::AesCtr(AesCtrParams { counter, length: 128 });
+    assert_eq!(&c[16..], subtle::encrypt(&next, key.as_slice(), [0u8; 16].as_ref()).unwrap().as_slice());
+    assert!(subtle::encrypt(&wrap, key.as_slice(), [0u8; 16 * 17].as_ref()).is_err());
+    
+    // RFC 4231 test case 2
+    let hmac = Algorithm::parse("{\"name\": \"HMAC\", \"hash\": \"SHA-256\"}").unwrap();
+    let mac = subtle::sign(&hmac, b"Jefe", b"what do ya want for nothing?").unwrap();
+    assert_eq!(mac, hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"));
+    assert!(subtle::verify(&hmac, b"Jefe", mac.as_slice(), b"what do ya want for nothing?").unwrap());
+    assert!(!subtle::verify(&hmac, b"Jefe", &mac[..31], b"what do ya want for nothing?").unwrap());
+    
+    // RFC 6070
+    let pbkdf2 = Algorithm::parse(format!("{{\"name\": \"PBKDF2\", \"hash\": \"SHA-1\", \"salt\": {}, \"iterations\": 2}}",
+        json_bytes(b"salt")).as_str()).unwrap();
+    assert_eq!(subtle::derive_bits(&pbkdf2, b"password", 160).unwrap(), hex("ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"));
+    assert!(subtle::derive_bits(&pbkdf2, b"password", 7).is_err());
+    
+    // RFC 3394 4.1
+    let kw = Algorithm::parse("\"AES-KW\"").unwrap();
+    let (kek, key_data) = (hex("000102030405060708090a0b0c0d0e0f"), hex("00112233445566778899aabbccddeeff"));
+    let wrapped = subtle::wrap_key(&kw, kek.as_slice(), key_data.as_slice()).unwrap();
+    assert_eq!(wrapped, hex("1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5"));
+    assert_eq!(subtle::unwrap_key(&kw, kek.as_slice(), wrapped.as_slice()).unwrap(), key_data);
+    assert_eq!(subtle::unwrap_key(&kw, key.as_slice(), wrapped.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    let wrapped = subtle::wrap_key(&cbc, key.as_slice(), key_data.as_slice()).unwrap();
+    assert_eq!(subtle::unwrap_key(&cbc, key.as_slice(), wrapped.as_slice()).unwrap(), key_data);
+    
+    // The Galois/Counter Mode of Operation(GCM), Appendix B test case 4
+    let gcm_key = hex("feffe9928665731c6d6a8f9467308308");
+    let gcm = Algorithm::parse(format!("{{\"name\": \"AES-GCM\", \"iv\": {}, \"additionalData\": {}}}",
+        json_bytes(hex("cafebabefacedbaddecaf888").as_slice()), json_bytes(hex("feedfacedeadbeeffeedfacedeadbeefabaddad2").as_slice())).as_str()).unwrap();
+    let pt = hex("d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39");
+    let mut c = subtle::encrypt(&gcm, gcm_key.as_slice(), pt.as_slice()).unwrap();
+    assert_eq!(c, hex("42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091\
+        5bc94fbc3221a5db94fae95ae7121a47"));
+    assert_eq!(subtle::decrypt(&gcm, gcm_key.as_slice(), c.as_slice()).unwrap(), pt);
+    let last = c.len() - 1;
+    c[last] ^= 1;
+    assert_eq!(subtle::decrypt(&gcm, gcm_key.as_slice(), c.as_slice()).unwrap_err().kind(), CryptoErrorKind::VerificationFailed);
+    // the truncated tag and the wrapped key
+    let gcm96 = Algorithm::parse("{\"name\": \"AES-GCM\", \"iv\": [1,2,3,4,5,6,7,8], \"tagLength\": 96}").unwrap();
+    let c = subtle::encrypt(&gcm96, key.as_slice(), data.as_slice()).unwrap();
+    assert_eq!(c.len(), data.len() + 12);
+    assert_eq!(subtle::decrypt(&gcm96, key.as_slice(), c.as_slice()).unwrap(), data);
+    let wrapped = subtle::wrap_key(&gcm96, key.as_slice(), key_data.as_slice()).unwrap();
+    assert_eq!(subtle::unwrap_key(&gcm96, key.as_slice(), wrapped.as_slice()).unwrap(), key_data);
+    
+    // RFC 5869 A.1
+    let hkdf = Algorithm::parse(format!("{{\"name\": \"HKDF\", \"hash\": \"SHA-256\", \"salt\": {}, \"info\": {}}}",
+        json_bytes(hex("000102030405060708090a0b0c").as_slice()), json_bytes(hex("f0f1f2f3f4f5f6f7f8f9").as_slice())).as_str()).unwrap();
+    assert_eq!(subtle::derive_bits(&hkdf, [0x0bu8; 22].as_ref(), 336).unwrap(),
+        hex("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"));
+    assert!(subtle::derive_bits(&hkdf, [0x0bu8; 22].as_ref(), 255 * 256 + 8).is_err());
+    
+    let ecdsa = Algorithm::parse("{\"name\": \"ECDSA\", \"hash\": \"SHA-256\"}").unwrap();
+    assert_eq!(subtle::sign(&ecdsa, key.as_slice(), data.as_slice()).unwrap_err().kind(), CryptoErrorKind::NotSupportUsage);
+    assert_eq!(subtle::digest(&hmac, b"").unwrap_err().kind(), CryptoErrorKind::NotSupportUsage);
+}
@@ -0,0 +1,173 @@
+//! The constant-time arithmetic of the GF(2^255 - 19), the element is represented by the 5 limbs of the 51 bits in the
+//! little-endian order, the limbs may be a little larger than the 51 bits between the operations.
+
+const MASK_51: u64 = (1u64 << 51) - 1;
+
+#[derive(Clone, Copy)]
+pub(super) struct FieldElement([u64; 5]);
+
+impl FieldElement {
+    pub(super) const ZERO: FieldElement = FieldElement([0; 5]);
+    pub(super) const ONE: FieldElement = FieldElement([1, 0, 0, 0, 0]);
+
+    #[inline]
+    fn load64(b: &[u8]) -> u64 {
+        u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]])
+    }
+
+    /// the little-endian 32 bytes, the most significant bit is ignored(RFC 7748 5)
+    pub(super) fn from_bytes(b: &[u8; 32]) -> Self {
+        FieldElement([
+            Self::load64(&b[0..]) & MASK_51,
+            (Self::load64(&b[6..]) >> 3) & MASK_51,
+            (Self::load64(&b[12..]) >> 6) & MASK_51,
+            (Self::load64(&b[19..]) >> 1) & MASK_51,
+            (Self::load64(&b[24..]) >> 12) & MASK_51,
+        ])
+    }
+
+    /// the canonical little-endian 32 bytes
+    pub(super) fn to_bytes(self) -> [u8; 32] {
+        let l = self.reduce().0;
+        let (mut out, mut acc, mut bits, mut i) = ([0u8; 32], 0u128, 0usize, 0usize);
+        for &x in l.iter() {
+            acc |= (x as u128) << bits;
+            bits += 51;
+            while bits >= 8 {
+                out[i] = acc as u8;
+                acc >>= 8;
+                bits -= 8;
+                i += 1;
+            }
+        }
+        out[i] = acc as u8;
+        out
+    }
+
+    #[inline]
+    fn carry_propagate(mut self) -> Self {
+        let l = &mut self.0;
+        let c = [l[0] >> 51, l[1] >> 51, l[2] >> 51, l[3] >> 51, l[4] >> 51];
+        l[0] = (l[0] & MASK_51) + c[4] * 19;
+        l[1] = (l[1] & MASK_51) + c[0];
+        l[2] = (l[2] & MASK_51) + c[1];
+        l[3] = (l[3] & MASK_51) + c[2];
+        l[4] = (l[4] & MASK_51) + c[3];
+        self
+    }
+
+    /// the unique representation in the [0, p)
+    fn reduce(&self) -> Self {
+        let mut v = self.carry_propagate();
+        let l = &mut v.0;
+        // c is 1 if v >= p, i.e. v + 19 >= 2^255
+        let mut c = (l[0] + 19) >> 51;
+        c = (l[1] + c) >> 51;
+        c = (l[2] + c) >> 51;
+        c = (l[3] + c) >> 51;
+        c = (l[4] + c) >> 51;
+
+        l[0] += 19 * c;
+        l[1] += l[0] >> 51;
+        l[0] &= MASK_51;
+        l[2] += l[1] >> 51;
+        l[1] &= MASK_51;
+        l[3] += l[2] >> 51;
+        l[2] &= MASK_51;
+        l[4] += l[3] >> 51;
+        l[3] &= MASK_51;
+        l[4] &= MASK_51;
+        v
+    }
+
+    pub(super) fn add(&self, rhs: &Self) -> Self {
+        let (a, b) = (&self.0, &rhs.0);
+        FieldElement([a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3], a[4] + b[4]]).carry_propagate()
+    }
+
+    pub(super) fn sub(&self, rhs: &Self) -> Self {
+        // a + 2p - b
+        let (a, b) = (&self.0, &rhs.0);
+        FieldElement([
+            (a[0] + 0xfffffffffffda) - b[0],
+            (a[1] + 0xffffffffffffe) - b[1],
+            (a[2] + 0xffffffffffffe) - b[2],
+            (a[3] + 0xffffffffffffe) - b[3],
+            (a[4] + 0xffffffffffffe) - b[4],
+        ]).carry_propagate()
+    }
+
+    /// reduce the 5 wide limbs to the 51 bits limbs
+    #[inline]
+    fn carry_wide(mut r: [u128; 5]) -> Self {
+        r[1] += r[0] >> 51;
+        r[2] += r[1] >> 51;
+        r[3] += r[2] >> 51;
+        r[4] += r[3] >> 51;
+        let t = (r[0] & (MASK_51 as u128)) + (r[4] >> 51) * 19;
+        FieldElement([
+            (t as u64) & MASK_51,
+            ((r[1] as u64) & MASK_51) + ((t >> 51) as u64),
+            (r[2] as u64) & MASK_51,
+            (r[3] as u64) & MASK_51,
+            (r[4] as u64) & MASK_51,
+        ])
+    }
+
+    pub(super) fn mul(&self, rhs: &Self) -> Self {
+        let (a, b) = (&self.0, &rhs.0);
+        let m = |x: u64, y: u64| (x as u128) * (y as u128);
+        let (b1, b2, b3, b4) = (b[1] * 19, b[2] * 19, b[3] * 19, b[4] * 19);
+
+        Self::carry_wide([
+            m(a[0], b[0]) + m(a[1], b4) + m(a[2], b3) + m(a[3], b2) + m(a[4], b1),
+            m(a[0], b[1]) + m(a[1], b[0]) + m(a[2], b4) + m(a[3], b3) + m(a[4], b2),
+            m(a[0], b[2]) + m(a[1], b[1]) + m(a[2], b[0]) + m(a[3], b4) + m(a[4], b3),
+            m(a[0], b[3]) + m(a[1], b[2]) + m(a[2], b[1]) + m(a[3], b[0]) + m(a[4], b4),
+            m(a[0], b[4]) + m(a[1], b[3]) + m(a[2], b[2]) + m(a[3], b[1]) + m(a[4], b[0]),
+        ])
+    }
+
+    pub(super) fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// the `n` times squaring
+    fn square_n(&self, n: usize) -> Self {
+        (0..n).fold(*self, |x, _| x.square())
+    }
+
+    /// multiply by the small integer `s` which is less than the 2^32
+    pub(super) fn mul_small(&self, s: u32) -> Self {
+        let a = &self.0;
+        let s = s as u128;
+        Self::carry_wide([(a[0] as u128) * s, (a[1] as u128) * s, (a[2] as u128) * s, (a[3] as u128) * s, (a[4] as u128) * s])
+    }
+
+    /// $z^{p-2}$, it's 0 if the z is 0
+    pub(super) fn invert(&self) -> Self {
+        let z2 = self.square();
+        let z9 = z2.square_n(2).mul(self);
+        let z11 = z9.mul(&z2);
+        let z_5_0 = z11.square().mul(&z9);
+        let z_10_0 = z_5_0.square_n(5).mul(&z_5_0);
+        let z_20_0 = z_10_0.square_n(10).mul(&z_10_0);
+        let z_40_0 = z_20_0.square_n(20).mul(&z_20_0);
+        let z_50_0 = z_40_0.square_n(10).mul(&z_10_0);
+        let z_100_0 = z_50_0.square_n(50).mul(&z_50_0);
+        let z_200_0 = z_100_0.square_n(100).mul(&z_100_0);
+        let z_250_0 = z_200_0.square_n(50).mul(&z_50_0);
+        // 2^255 - 2^5 + 11 = p - 2
+        z_250_0.square_n(5).mul(&z11)
+    }
+
+    /// swap the `a` and `b` if the `choice` is 1, the `choice` must be 0 or 1
+    pub(super) fn cswap(a: &mut Self, b: &mut Self, choice: u64) {
+        let mask = 0u64.wrapping_sub(choice);
+        a.0.iter_mut().zip(b.0.iter_mut()).for_each(|(x, y)| {
+            let t = mask & (*x ^ *y);
+            *x ^= t;
+            *y ^= t;
+        });
+    }
+}
@@ -0,0 +1,21 @@
+//! X25519 Diffie-Hellman key agreement on the Curve25519
+//! RFC 7748
+//!
+//! The field arithmetic and the Montgomery ladder are constant-time, the key agreement doesn't depend on the
+//! NIST curves. The X3DH key agreement of the Signal protocol is enabled with the `kdf` feature.
+
+mod field;
+
+mod x25519;
+pub use x25519::{PublicKey, StaticSecret, X25519_BASEPOINT, x25519, clamp_scalar};
+
+#[cfg(feature = "kdf")]
+mod x3dh;
+#[cfg(feature = "kdf")]
+pub use x3dh::{X3DH, X3DHPreKeyBundle, X3DHInitialMessage, X3DHSharedKey, X3DH_SHARED_KEY_LEN};
+
+#[cfg(test)]
+mod x25519_test;
+
+#[cfg(all(test, feature = "kdf"))]
+mod x3dh_test;
\ No newline at end of file
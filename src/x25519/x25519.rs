@@ -0,0 +1,134 @@
+use rmath::rand::IterSource;
+use crate::{CryptoError, CryptoErrorKind};
+use crate::x25519::field::FieldElement;
+
+/// the u-coordinate of the base point of the Curve25519
+pub const X25519_BASEPOINT: [u8; 32] = [9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// (A - 2) / 4 of the Curve25519 $v^2 = u^3 + A u^2 + u$
+const A24: u32 = 121665;
+
+/// clamp the scalar: clear the 3 low bits and the bit 255, set the bit 254(RFC 7748 5, `decodeScalar25519`)
+pub fn clamp_scalar(mut k: [u8; 32]) -> [u8; 32] {
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    k
+}
+
+/// X25519 function(RFC 7748 5): the constant-time Montgomery ladder on the u-coordinate, the `scalar` is clamped
+/// and the most significant bit of the `u` is ignored.
+pub fn x25519(scalar: [u8; 32], u: [u8; 32]) -> [u8; 32] {
+    let k = clamp_scalar(scalar);
+    let x1 = FieldElement::from_bytes(&u);
+    let (mut x2, mut z2, mut x3, mut z3) = (FieldElement::ONE, FieldElement::ZERO, x1, FieldElement::ONE);
+    let mut swap = 0u64;
+
+    for t in (0..255).rev() {
+        let k_t = ((k[t >> 3] >> (t & 7)) & 1) as u64;
+        swap ^= k_t;
+        FieldElement::cswap(&mut x2, &mut x3, swap);
+        FieldElement::cswap(&mut z2, &mut z3, swap);
+        swap = k_t;
+
+        let (a, b) = (x2.add(&z2), x2.sub(&z2));
+        let (aa, bb) = (a.square(), b.square());
+        let e = aa.sub(&bb);
+        let (c, d) = (x3.add(&z3), x3.sub(&z3));
+        let (da, cb) = (d.mul(&a), c.mul(&b));
+        x3 = da.add(&cb).square();
+        z3 = x1.mul(&da.sub(&cb).square());
+        x2 = aa.mul(&bb);
+        z2 = e.mul(&aa.add(&e.mul_small(A24)));
+    }
+    FieldElement::cswap(&mut x2, &mut x3, swap);
+    FieldElement::cswap(&mut z2, &mut z3, swap);
+
+    x2.mul(&z2.invert()).to_bytes()
+}
+
+/// The X25519 public key, the u-coordinate in the little-endian 32 bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey([u8; 32]);
+
+impl PublicKey {
+    pub fn new(u: [u8; 32]) -> Self {
+        PublicKey(u)
+    }
+
+    pub fn from_slice(u: &[u8]) -> Result<Self, CryptoError> {
+        if u.len() != 32 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey,
+                format!("The length of X25519 public key must be 32 in bytes, but got {}", u.len())))
+        } else {
+            let mut k = [0u8; 32];
+            k.copy_from_slice(u);
+            Ok(PublicKey(k))
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// The X25519 private key which can be used for many key agreements, the scalar is clamped when it's created
+#[derive(Clone)]
+pub struct StaticSecret([u8; 32]);
+
+impl StaticSecret {
+    pub fn new(scalar: [u8; 32]) -> Self {
+        StaticSecret(clamp_scalar(scalar))
+    }
+
+    pub fn from_slice(scalar: &[u8]) -> Result<Self, CryptoError> {
+        if scalar.len() != 32 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey,
+                format!("The length of X25519 private key must be 32 in bytes, but got {}", scalar.len())))
+        } else {
+            let mut k = [0u8; 32];
+            k.copy_from_slice(scalar);
+            Ok(Self::new(k))
+        }
+    }
+
+    /// generate the private key by the 32 random bytes
+    pub fn random<R: IterSource<u32>>(rd: &mut R) -> Result<Self, CryptoError> {
+        let mut k = [0u8; 32];
+        let mut n = 0;
+        k.chunks_mut(4).zip(rd.iter_mut()).for_each(|(b, x)| {
+            b.copy_from_slice(x.to_le_bytes().as_ref());
+            n += 4;
+        });
+
+        if n != k.len() {
+            Err(CryptoError::new(CryptoErrorKind::RandError, "The random source is exhausted"))
+        } else {
+            Ok(Self::new(k))
+        }
+    }
+
+    /// the clamped scalar
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(x25519(self.0, X25519_BASEPOINT))
+    }
+
+    /// the shared secret with the peer's public key, the all-zero shared secret is rejected(RFC 7748 6.1), it means
+    /// the peer's public key is in the small order subgroup.
+    pub fn diffie_hellman(&self, peer: &PublicKey) -> Result<[u8; 32], CryptoError> {
+        let shared = x25519(self.0, peer.0);
+        if shared.iter().fold(0u8, |a, &x| a | x) == 0 {
+            Err(CryptoError::new(CryptoErrorKind::InvalidPublicKey, "The X25519 shared secret is all-zero, the peer's public key has the small order"))
+        } else {
+            Ok(shared)
+        }
+    }
+}
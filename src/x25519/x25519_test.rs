@@ -0,0 +1,70 @@
+use crate::x25519::{PublicKey, StaticSecret, X25519_BASEPOINT, x25519};
+use crate::test_util::XorShiftRand;
+use crate::encoding::Hex;
+use crate::CryptoErrorKind;
+
+fn hex32(s: &str) -> [u8; 32] {
+    let mut k = [0u8; 32];
+    k.copy_from_slice(Hex::lower().decode(s).unwrap().as_slice());
+    k
+}
+
+#[test]
+fn x25519_function() {
+    // RFC 7748 5.2
+    let cases = [
+        (
+            "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+            "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+            "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+        ),
+        (
+            "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+            "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+            "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+        ),
+    ];
+    for (i, &(k, u, r)) in cases.iter().enumerate() {
+        assert_eq!(x25519(hex32(k), hex32(u)), hex32(r), "case: {}", i);
+    }
+
+    let (mut k, mut u) = (X25519_BASEPOINT, X25519_BASEPOINT);
+    for i in 1..=1000 {
+        let r = x25519(k, u);
+        u = k;
+        k = r;
+        if i == 1 {
+            assert_eq!(k, hex32("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079"));
+        }
+    }
+    assert_eq!(k, hex32("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51"));
+}
+
+#[test]
+fn x25519_diffie_hellman() {
+    // RFC 7748 6.1
+    let alice = StaticSecret::new(hex32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a"));
+    let bob = StaticSecret::from_slice(hex32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb").as_ref()).unwrap();
+    let (pa, pb) = (alice.public_key(), bob.public_key());
+    assert_eq!(pa.to_bytes(), hex32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(pb.as_bytes(), &hex32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+    let shared = hex32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(alice.diffie_hellman(&pb).unwrap(), shared);
+    assert_eq!(bob.diffie_hellman(&pa).unwrap(), shared);
+
+    let mut rd = XorShiftRand::new(0x25519);
+    let (a, b) = (StaticSecret::random(&mut rd).unwrap(), StaticSecret::random(&mut rd).unwrap());
+    assert_eq!(a.to_bytes()[0] & 7, 0);
+    assert_eq!(a.to_bytes()[31] & 0xc0, 0x40);
+    assert_eq!(a.diffie_hellman(&b.public_key()).unwrap(), b.diffie_hellman(&a.public_key()).unwrap());
+
+    // the small order points: 0, 1 and the order 8 point
+    let small = ["0000000000000000000000000000000000000000000000000000000000000000",
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800"];
+    for s in small.iter() {
+        assert_eq!(a.diffie_hellman(&PublicKey::new(hex32(s))).err().unwrap().kind(), CryptoErrorKind::InvalidPublicKey);
+    }
+    assert_eq!(PublicKey::from_slice(&[9u8; 31]).err().unwrap().kind(), CryptoErrorKind::InvalidPublicKey);
+    assert_eq!(StaticSecret::from_slice(&[9u8; 33]).err().unwrap().kind(), CryptoErrorKind::InvalidPrivateKey);
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+//! X3DH(the Extended Triple Diffie-Hellman) key agreement of the Signal protocol on the X25519
+//!
+//! DH1 = DH(IK_A, SPK_B), DH2 = DH(EK_A, IK_B), DH3 = DH(EK_A, SPK_B), DH4 = DH(EK_A, OPK_B)  
+//! SK = HKDF(0^HashLen, 0xff^32 || DH1 || DH2 || DH3 [|| DH4], info, 32), AD = IK_A || IK_B  
+//! where the `IK` is the identity key, the `SPK` is the signed prekey, the `OPK` is the optional one-time prekey
+//! and the `EK` is the ephemeral key of the initiator. The public keys are encoded as the 32 bytes u-coordinate.
+//!
+//! The signed prekey is signed by the XEdDSA of the identity key in the Signal, the XEdDSA isn't implemented by
+//! the crate, so the initiator verifies the signature by the caller's verifier before the key agreement. The double
+//! ratchet(and its header encryption variant) which consumes the `SK` and the `AD` isn't provided by the crate.
+
+use rmath::rand::IterSource;
+use crate::{Digest, CryptoError, CryptoErrorKind};
+use crate::kdf::HKDF;
+use crate::x25519::{PublicKey, StaticSecret};
+
+/// the length of the X3DH shared key in bytes
+pub const X3DH_SHARED_KEY_LEN: usize = 32;
+
+/// The prekey bundle which is published by the responder
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct X3DHPreKeyBundle {
+    pub identity_key: PublicKey,
+    pub signed_prekey: PublicKey,
+    /// the signature of the `signed_prekey` u-coordinate by the identity key
+    pub signed_prekey_signature: Vec<u8>,
+    pub one_time_prekey: Option<PublicKey>,
+}
+
+/// The initial message from the initiator to the responder, the `one_time_prekey` identifies the used one-time prekey
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct X3DHInitialMessage {
+    pub identity_key: PublicKey,
+    pub ephemeral_key: PublicKey,
+    pub one_time_prekey: Option<PublicKey>,
+}
+
+/// The shared key `SK` and the associated data `AD` for the following AEAD(e.g. the first message of the ratchet)
+#[derive(Clone)]
+pub struct X3DHSharedKey {
+    key: [u8; X3DH_SHARED_KEY_LEN],
+    ad: Vec<u8>,
+}
+
+impl X3DHSharedKey {
+    pub fn key(&self) -> &[u8] {
+        self.key.as_ref()
+    }
+
+    /// `IK_A || IK_B`
+    pub fn associated_data(&self) -> &[u8] {
+        self.ad.as_slice()
+    }
+}
+
+#[derive(Clone)]
+pub struct X3DH<D: Digest> {
+    hkdf: HKDF<D>,
+    info: Vec<u8>,
+}
+
+impl<D: Digest> X3DH<D> {
+    /// the `digest` is the SHA-256 or SHA-512 in the Signal, the `info` is the application identifier
+    pub fn new(digest: D, info: &[u8]) -> Result<Self, CryptoError> {
+        Ok(Self { hkdf: HKDF::new(digest)?, info: info.to_vec() })
+    }
+
+    /// the initiator generates the ephemeral key by the `rd`, the `verify_signed_prekey(identity_key, signed_prekey, signature)`
+    /// verifies the signed prekey of the `bundle` at first.
+    pub fn initiate<R, F>(&mut self, identity: &StaticSecret, bundle: &X3DHPreKeyBundle, rd: &mut R, verify_signed_prekey: F) -> Result<(X3DHInitialMessage, X3DHSharedKey), CryptoError>
+        where R: IterSource<u32>, F: FnOnce(&PublicKey, &PublicKey, &[u8]) -> Result<(), CryptoError> {
+        let ephemeral = StaticSecret::random(rd)?;
+        self.initiate_with_ephemeral(identity, &ephemeral, bundle, verify_signed_prekey)
+    }
+
+    /// the `initiate` with the caller's ephemeral key, the `ephemeral` must not be reused
+    pub fn initiate_with_ephemeral<F>(&mut self, identity: &StaticSecret, ephemeral: &StaticSecret, bundle: &X3DHPreKeyBundle, verify_signed_prekey: F) -> Result<(X3DHInitialMessage, X3DHSharedKey), CryptoError>
+        where F: FnOnce(&PublicKey, &PublicKey, &[u8]) -> Result<(), CryptoError> {
+        verify_signed_prekey(&bundle.identity_key, &bundle.signed_prekey, bundle.signed_prekey_signature.as_slice())?;
+
+        let mut dh = Vec::with_capacity(4);
+        dh.push(identity.diffie_hellman(&bundle.signed_prekey)?);
+        dh.push(ephemeral.diffie_hellman(&bundle.identity_key)?);
+        dh.push(ephemeral.diffie_hellman(&bundle.signed_prekey)?);
+        if let Some(opk) = bundle.one_time_prekey.as_ref() {
+            dh.push(ephemeral.diffie_hellman(opk)?);
+        }
+
+        let msg = X3DHInitialMessage {
+            identity_key: identity.public_key(),
+            ephemeral_key: ephemeral.public_key(),
+            one_time_prekey: bundle.one_time_prekey,
+        };
+        let sk = self.shared_key(dh.as_slice(), &msg.identity_key, &bundle.identity_key)?;
+        Ok((msg, sk))
+    }
+
+    /// the responder computes the same shared key by its private keys, the `one_time_prekey` must be the private key
+    /// of the `msg.one_time_prekey`, and it should be deleted after the key agreement.
+    pub fn respond(&mut self, identity: &StaticSecret, signed_prekey: &StaticSecret, one_time_prekey: Option<&StaticSecret>, msg: &X3DHInitialMessage) -> Result<X3DHSharedKey, CryptoError> {
+        let mut dh = Vec::with_capacity(4);
+        dh.push(signed_prekey.diffie_hellman(&msg.identity_key)?);
+        dh.push(identity.diffie_hellman(&msg.ephemeral_key)?);
+        dh.push(signed_prekey.diffie_hellman(&msg.ephemeral_key)?);
+        match (one_time_prekey, msg.one_time_prekey.as_ref()) {
+            (None, None) => {},
+            (Some(opk), Some(pk)) if &opk.public_key() == pk => {
+                dh.push(opk.diffie_hellman(&msg.ephemeral_key)?);
+            },
+            _ => {
+                return Err(CryptoError::new(CryptoErrorKind::InvalidPrivateKey,
+                    "The one-time prekey doesn't match the one-time prekey of the X3DH initial message"));
+            },
+        }
+
+        self.shared_key(dh.as_slice(), &msg.identity_key, &identity.public_key())
+    }
+
+    fn shared_key(&mut self, dh: &[[u8; 32]], initiator: &PublicKey, responder: &PublicKey) -> Result<X3DHSharedKey, CryptoError> {
+        let mut ikm = vec![0xffu8; 32];
+        dh.iter().for_each(|x| ikm.extend_from_slice(x.as_ref()));
+        let salt = vec![0u8; self.hkdf.hash_len()];
+        let mut okm = Vec::with_capacity(X3DH_SHARED_KEY_LEN);
+        self.hkdf.derive(salt.as_slice(), ikm.as_slice(), self.info.as_slice(), X3DH_SHARED_KEY_LEN, &mut okm)?;
+
+        let mut key = [0u8; X3DH_SHARED_KEY_LEN];
+        key.copy_from_slice(okm.as_slice());
+        let mut ad = initiator.to_bytes().to_vec();
+        ad.extend_from_slice(responder.as_bytes());
+        Ok(X3DHSharedKey { key, ad })
+    }
+}
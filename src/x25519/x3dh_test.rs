@@ -0,0 +1,79 @@
+use crate::x25519::{PublicKey, StaticSecret, X3DH, X3DHPreKeyBundle, X3DHInitialMessage};
+use crate::test_util::XorShiftRand;
+use crate::encoding::Hex;
+use crate::sha::{SHA256, SHA512};
+use crate::{CryptoError, CryptoErrorKind, Digest};
+
+fn secret(s: &str) -> StaticSecret {
+    StaticSecret::from_slice(Hex::lower().decode(s).unwrap().as_slice()).unwrap()
+}
+
+fn accept(_: &PublicKey, _: &PublicKey, _: &[u8]) -> Result<(), CryptoError> {
+    Ok(())
+}
+
+fn x3dh_case<D: Digest + Clone>(digest: D, tgt: [&str; 2]) {
+    // the RFC 7748 6.1 keys are the identity keys, the expected shared keys are computed by the Python code following
+    // the X3DH specification
+    let (ika, ikb) = (secret("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a"),
+        secret("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb"));
+    let (spk, eka, opk) = (secret("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+        secret("202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f"),
+        secret("404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f"));
+    let mut bundle = X3DHPreKeyBundle {
+        identity_key: ikb.public_key(),
+        signed_prekey: spk.public_key(),
+        signed_prekey_signature: vec![0x5a; 64],
+        one_time_prekey: None,
+    };
+
+    for (i, &tgt) in tgt.iter().enumerate() {
+        bundle.one_time_prekey = if i == 0 { None } else { Some(opk.public_key()) };
+        let (mut alice, mut bob) = (X3DH::new(digest.clone(), b"rcrypto X3DH").unwrap(), X3DH::new(digest.clone(), b"rcrypto X3DH").unwrap());
+        let (msg, ska) = alice.initiate_with_ephemeral(&ika, &eka, &bundle, accept).unwrap();
+        assert_eq!(msg.one_time_prekey, bundle.one_time_prekey);
+        assert_eq!(Hex::lower().encode(ska.key()), tgt, "case: {}", i);
+        let skb = bob.respond(&ikb, &spk, if i == 0 { None } else { Some(&opk) }, &msg).unwrap();
+        assert_eq!(skb.key(), ska.key(), "case: {}", i);
+        assert_eq!(skb.associated_data(), ska.associated_data());
+        assert_eq!(&ska.associated_data()[..32], ika.public_key().as_bytes());
+        assert_eq!(&ska.associated_data()[32..], ikb.public_key().as_bytes());
+
+        // the one-time prekey is missing or mismatched
+        let other = if i == 0 { Some(&opk) } else { None };
+        assert_eq!(bob.respond(&ikb, &spk, other, &msg).err().unwrap().kind(), CryptoErrorKind::InvalidPrivateKey);
+        assert_eq!(bob.respond(&ikb, &spk, Some(&eka), &X3DHInitialMessage { one_time_prekey: Some(opk.public_key()), ..msg.clone() }).err().unwrap().kind(),
+            CryptoErrorKind::InvalidPrivateKey);
+    }
+}
+
+#[test]
+fn x3dh_key_agreement() {
+    x3dh_case(SHA256::new(), ["acc2a9ff5c28eb310f4a93772d20f8b057dbae3fdd3bd5d52f3b108d3c9b2491",
+        "c7ed562276d30be7d43e2c3aa00bde3780be36fc856ec8ddd9b77252ba7c4e1a"]);
+    x3dh_case(SHA512::new(), ["593009344010c0ce38cba7ce6fba4aa327416344e2a7a4d7d5b895b6cf7d95d7",
+        "e4dc4a184d0faa7d7c46e46b1e4ff161a8124e432b7bc994012ac00d0c173755"]);
+
+    let mut rd = XorShiftRand::new(0x3d4);
+    let (ika, ikb, spk) = (StaticSecret::random(&mut rd).unwrap(), StaticSecret::random(&mut rd).unwrap(), StaticSecret::random(&mut rd).unwrap());
+    let bundle = X3DHPreKeyBundle {
+        identity_key: ikb.public_key(),
+        signed_prekey: spk.public_key(),
+        signed_prekey_signature: vec![1, 2, 3],
+        one_time_prekey: None,
+    };
+    let mut x3dh = X3DH::new(SHA256::new(), b"info").unwrap();
+    let (msg, ska) = x3dh.initiate(&ika, &bundle, &mut rd, |ik, spk, sig| {
+        assert_eq!((ik, spk, sig), (&bundle.identity_key, &bundle.signed_prekey, bundle.signed_prekey_signature.as_slice()));
+        Ok(())
+    }).unwrap();
+    assert_eq!(x3dh.respond(&ikb, &spk, None, &msg).unwrap().key(), ska.key());
+    // the other info
+    assert_ne!(X3DH::new(SHA256::new(), b"other").unwrap().respond(&ikb, &spk, None, &msg).unwrap().key(), ska.key());
+
+    // the signature of the signed prekey is rejected, the small order signed prekey
+    let e = x3dh.initiate(&ika, &bundle, &mut rd, |_, _, _| Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "")));
+    assert_eq!(e.err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+    let bad = X3DHPreKeyBundle { signed_prekey: PublicKey::new([0u8; 32]), ..bundle.clone() };
+    assert_eq!(x3dh.initiate(&ika, &bad, &mut rd, accept).err().unwrap().kind(), CryptoErrorKind::InvalidPublicKey);
+}
@@ -0,0 +1,189 @@
+use crate::{CryptoError, CryptoErrorKind};
+use crate::xoodyak::xoodoo::{xoodoo, XOODOO_STATE_LEN};
+
+pub(super) const XOODYAK_HASH_RATE: usize = 16;
+const XOODYAK_KEYED_ABSORB_RATE: usize = 44;
+const XOODYAK_KEYED_SQUEEZE_RATE: usize = 24;
+const XOODYAK_RATCHET_LEN: usize = 16;
+
+// the color of the down
+pub(super) const XOODYAK_CD_ABSORB: u8 = 0x03;
+const XOODYAK_CD_ABSORB_KEY: u8 = 0x02;
+// the color of the up
+const XOODYAK_CU_CRYPT: u8 = 0x80;
+const XOODYAK_CU_SQUEEZE: u8 = 0x40;
+const XOODYAK_CU_SQUEEZE_KEY: u8 = 0x20;
+const XOODYAK_CU_RATCHET: u8 = 0x10;
+
+/// The Cyclist duplex object of the Xoodyak
+/// Daemen, Hoffert, Mella, Peeters, Van Assche, Van Keer. Xoodyak, a lightweight cryptographic scheme, NIST LWC finalist.
+///
+/// The hash mode(`Cyclist::hash`) only supports the `absorb` and the `squeeze`, the keyed mode(`Cyclist::keyed`) supports
+/// all the operations. Every output of the `encrypt`/`decrypt`/`squeeze` depends on all the previous operations,
+/// e.g. the session encryption can be `absorb(nonce); absorb(aad); encrypt(m1); squeeze(tag1); encrypt(m2); squeeze(tag2)`.
+#[derive(Clone)]
+pub struct Cyclist {
+    state: [u8; XOODOO_STATE_LEN],
+    phase_up: bool,
+    keyed: bool,
+    absorb_rate: usize,
+    squeeze_rate: usize,
+}
+
+impl Cyclist {
+    /// the hash mode
+    pub fn hash() -> Self {
+        Self {
+            state: [0u8; XOODOO_STATE_LEN],
+            phase_up: true,
+            keyed: false,
+            absorb_rate: XOODYAK_HASH_RATE,
+            squeeze_rate: XOODYAK_HASH_RATE,
+        }
+    }
+
+    /// the keyed mode, the `key` must not be empty and `key.len() + id.len()` must not be great than 43 bytes.
+    /// The `id` is the optional key identifier(e.g. the nonce), the `counter` is the optional data which is absorbed
+    /// byte by byte for the resistance of the side channel attacks.
+    pub fn keyed(key: &[u8], id: &[u8], counter: &[u8]) -> Result<Self, CryptoError> {
+        if key.is_empty() || key.len() + id.len() >= XOODYAK_KEYED_ABSORB_RATE {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The Cyclist key must not be empty and the key with the id must be less than {} bytes, but got {} and {}",
+                    XOODYAK_KEYED_ABSORB_RATE, key.len(), id.len())));
+        }
+
+        let mut c = Self {
+            state: [0u8; XOODOO_STATE_LEN],
+            phase_up: true,
+            keyed: true,
+            absorb_rate: XOODYAK_KEYED_ABSORB_RATE,
+            squeeze_rate: XOODYAK_KEYED_SQUEEZE_RATE,
+        };
+
+        let mut k = Vec::with_capacity(key.len() + id.len() + 1);
+        k.extend_from_slice(key);
+        k.extend_from_slice(id);
+        k.push(id.len() as u8);
+        c.absorb_any(k.as_slice(), c.absorb_rate, XOODYAK_CD_ABSORB_KEY);
+        if !counter.is_empty() {
+            c.absorb_any(counter, 1, 0);
+        }
+        Ok(c)
+    }
+
+    pub fn is_keyed(&self) -> bool {
+        self.keyed
+    }
+
+    fn up(&mut self, dst: &mut Vec<u8>, len: usize, cu: u8) {
+        if self.keyed {
+            self.state[XOODOO_STATE_LEN - 1] ^= cu;
+        }
+        xoodoo(&mut self.state);
+        self.phase_up = true;
+        dst.extend_from_slice(&self.state[..len]);
+    }
+
+    fn down(&mut self, block: &[u8], cd: u8) {
+        self.state.iter_mut().zip(block.iter()).for_each(|(s, &b)| *s ^= b);
+        self.state[block.len()] ^= 0x01;
+        self.state[XOODOO_STATE_LEN - 1] ^= if self.keyed { cd } else { cd & 0x01 };
+        self.phase_up = false;
+    }
+
+    /// absorb one block which is not longer than the absorb rate
+    pub(super) fn absorb_block(&mut self, block: &[u8], cd: u8) {
+        if !self.phase_up {
+            self.up(&mut Vec::new(), 0, 0);
+        }
+        self.down(block, cd);
+    }
+
+    fn absorb_any(&mut self, data: &[u8], rate: usize, cd: u8) {
+        let mut blocks = data.chunks(rate);
+        self.absorb_block(blocks.next().unwrap_or(&[]), cd);
+        blocks.for_each(|block| self.absorb_block(block, 0));
+    }
+
+    fn squeeze_any(&mut self, dst: &mut Vec<u8>, len: usize, cu: u8) {
+        dst.clear();
+        self.up(dst, len.min(self.squeeze_rate), cu);
+        while dst.len() < len {
+            self.down(&[], 0);
+            let l = (len - dst.len()).min(self.squeeze_rate);
+            self.up(dst, l, 0);
+        }
+    }
+
+    fn check_keyed(&self, op: &str) -> Result<(), CryptoError> {
+        if self.keyed {
+            Ok(())
+        } else {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage,
+                format!("The Cyclist {} is only supported in the keyed mode", op)))
+        }
+    }
+
+    fn crypt(&mut self, dst: &mut Vec<u8>, data: &[u8], is_decrypt: bool) {
+        dst.clear();
+        let (mut cu, mut ks) = (XOODYAK_CU_CRYPT, Vec::with_capacity(XOODYAK_KEYED_SQUEEZE_RATE));
+        let mut blocks = data.chunks(XOODYAK_KEYED_SQUEEZE_RATE).peekable();
+        if blocks.peek().is_none() {
+            self.up(&mut ks, 0, cu);
+            self.down(&[], 0);
+        }
+
+        for block in blocks {
+            ks.clear();
+            self.up(&mut ks, block.len(), cu);
+            let start = dst.len();
+            dst.extend(block.iter().zip(ks.iter()).map(|(&a, &b)| a ^ b));
+            if is_decrypt {
+                let p = dst[start..].to_vec();
+                self.down(p.as_slice(), 0);
+            } else {
+                self.down(block, 0);
+            }
+            cu = 0;
+        }
+    }
+
+    pub fn absorb(&mut self, data: &[u8]) {
+        self.absorb_any(data, self.absorb_rate, XOODYAK_CD_ABSORB);
+    }
+
+    /// the ciphertext has the same length as the `plaintext`, it will output to the `dst`
+    pub fn encrypt(&mut self, dst: &mut Vec<u8>, plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.check_keyed("encrypt")?;
+        self.crypt(dst, plaintext, false);
+        Ok(())
+    }
+
+    /// the plaintext has the same length as the `ciphertext`, it will output to the `dst`
+    pub fn decrypt(&mut self, dst: &mut Vec<u8>, ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.check_keyed("decrypt")?;
+        self.crypt(dst, ciphertext, true);
+        Ok(())
+    }
+
+    /// squeeze the `len` bytes to the `dst`, e.g. the digest or the authentication tag
+    pub fn squeeze(&mut self, dst: &mut Vec<u8>, len: usize) {
+        self.squeeze_any(dst, len, XOODYAK_CU_SQUEEZE);
+    }
+
+    /// squeeze the `len` bytes derived key to the `dst`
+    pub fn squeeze_key(&mut self, dst: &mut Vec<u8>, len: usize) -> Result<(), CryptoError> {
+        self.check_keyed("squeeze_key")?;
+        self.squeeze_any(dst, len, XOODYAK_CU_SQUEEZE_KEY);
+        Ok(())
+    }
+
+    /// overwrite the part of the state irreversibly, the previous state can't be recovered from the current state
+    pub fn ratchet(&mut self) -> Result<(), CryptoError> {
+        self.check_keyed("ratchet")?;
+        let mut r = Vec::with_capacity(XOODYAK_RATCHET_LEN);
+        self.squeeze_any(&mut r, XOODYAK_RATCHET_LEN, XOODYAK_CU_RATCHET);
+        self.absorb_any(r.as_slice(), self.absorb_rate, 0);
+        Ok(())
+    }
+}
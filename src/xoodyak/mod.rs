@@ -0,0 +1,20 @@
+//! Xoodyak
+//! Daemen, Hoffert, Mella, Peeters, Van Assche, Van Keer. Xoodyak, a lightweight cryptographic scheme, NIST LWC finalist.
+//!
+//! The Xoodyak is the Cyclist duplex object on the Xoodoo[12] permutation, the hash mode gives the `XoodyakHash`
+//! and the keyed mode gives the `Xoodyak` AEAD. Unlike the bit-oriented `KeccakSponge`, the duplex absorbs and
+//! squeezes the bytes with the domain separation colors, and the `Cyclist` can be used directly for the sessions.
+
+mod xoodoo;
+
+mod cyclist;
+pub use cyclist::Cyclist;
+
+mod xoodyak_hash;
+pub use xoodyak_hash::XoodyakHash;
+
+mod xoodyak_aead;
+pub use xoodyak_aead::Xoodyak;
+
+#[cfg(test)]
+mod xoodyak_test;
\ No newline at end of file
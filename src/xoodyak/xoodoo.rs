@@ -0,0 +1,47 @@
+//! Xoodoo permutation
+//! Daemen, Hoffert, Van Assche, Van Keer. The design of Xoodoo and Xoofff, ToSC 2018.
+//!
+//! The 384 bits state is 3 planes of 4 32-bits lanes, the lane `(x, y)` is the little-endian word at the
+//! bytes `[4 * (x + 4y), 4 * (x + 4y) + 4)`.
+
+pub(super) const XOODOO_STATE_LEN: usize = 48;
+
+const XOODOO_RC: [u32; 12] = [0x058, 0x038, 0x3c0, 0x0d0, 0x120, 0x014, 0x060, 0x02c, 0x380, 0x0f0, 0x1a0, 0x012];
+
+/// Xoodoo[12] on the 48 bytes state
+pub(super) fn xoodoo(state: &mut [u8; XOODOO_STATE_LEN]) {
+    let mut a = [0u32; 12];
+    a.iter_mut().zip(state.chunks(4)).for_each(|(a, s)| *a = u32::from_le_bytes([s[0], s[1], s[2], s[3]]));
+
+    for &rc in XOODOO_RC.iter() {
+        // theta
+        let mut e = [0u32; 4];
+        for x in 0..4 {
+            let p = a[(x + 3) & 3] ^ a[4 + ((x + 3) & 3)] ^ a[8 + ((x + 3) & 3)];
+            e[x] = p.rotate_left(5) ^ p.rotate_left(14);
+        }
+        a.iter_mut().enumerate().for_each(|(i, a)| *a ^= e[i & 3]);
+
+        // rho west
+        a[4..8].rotate_right(1);
+        a[8..12].iter_mut().for_each(|a| *a = a.rotate_left(11));
+
+        // iota
+        a[0] ^= rc;
+
+        // chi
+        for x in 0..4 {
+            let (a0, a1, a2) = (a[x], a[4 + x], a[8 + x]);
+            a[x] ^= !a1 & a2;
+            a[4 + x] ^= !a2 & a0;
+            a[8 + x] ^= !a0 & a1;
+        }
+
+        // rho east
+        a[4..8].iter_mut().for_each(|a| *a = a.rotate_left(1));
+        a[8..12].rotate_right(2);
+        a[8..12].iter_mut().for_each(|a| *a = a.rotate_left(8));
+    }
+
+    state.chunks_mut(4).zip(a.iter()).for_each(|(s, a)| s.copy_from_slice(a.to_le_bytes().as_ref()));
+}
@@ -0,0 +1,94 @@
+use crate::{CryptoError, CryptoErrorKind, AEAD};
+use crate::xoodyak::Cyclist;
+
+const XOODYAK_KEY_LEN: usize = 16;
+const XOODYAK_NONCE_LEN: usize = 16;
+const XOODYAK_TAG_LEN: usize = 16;
+
+/// Xoodyak AEAD
+///
+/// the Cyclist keyed mode `Cyclist(K); absorb(N); absorb(A); encrypt(P); squeeze(16)` with the 128 bits key
+/// and the 128 bits nonce, the nonce must not be reused with the same key.
+#[derive(Clone)]
+pub struct Xoodyak {
+    key: [u8; XOODYAK_KEY_LEN],
+}
+
+impl Xoodyak {
+    pub fn new(key: [u8; 16]) -> Self {
+        Self { key }
+    }
+
+    pub fn from_slice(key: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() != XOODYAK_KEY_LEN {
+            Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong key length: {}, the Xoodyak key must be 16 bytes", key.len())))
+        } else {
+            let mut k = [0u8; XOODYAK_KEY_LEN];
+            k.copy_from_slice(key);
+            Ok(Self { key: k })
+        }
+    }
+
+    fn cyclist(&self, nonce: &[u8], aad: &[u8]) -> Result<Cyclist, CryptoError> {
+        if nonce.len() != XOODYAK_NONCE_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("Wrong nonce length: {}, the Xoodyak nonce must be 16 bytes", nonce.len())));
+        }
+
+        let mut cyclist = Cyclist::keyed(self.key.as_ref(), &[], &[])?;
+        cyclist.absorb(nonce);
+        cyclist.absorb(aad);
+        Ok(cyclist)
+    }
+
+    /// encrypt and authenticate the `plaintext` and `aad`, the `ciphertext||tag` will output to the `dst`.
+    pub fn encrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        let mut cyclist = self.cyclist(nonce, aad)?;
+        cyclist.encrypt(dst, plaintext)?;
+        let mut tag = Vec::with_capacity(XOODYAK_TAG_LEN);
+        cyclist.squeeze(&mut tag, XOODYAK_TAG_LEN);
+        dst.extend_from_slice(tag.as_slice());
+        Ok(())
+    }
+
+    /// verify and decrypt the `ciphertext||tag`, nothing will output to the `dst` if the tag not match.
+    pub fn decrypt(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        dst.clear();
+        if ciphertext.len() < XOODYAK_TAG_LEN {
+            return Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The ciphertext is shorter than the tag"));
+        }
+        let (ct, tag) = ciphertext.split_at(ciphertext.len() - XOODYAK_TAG_LEN);
+        let mut cyclist = self.cyclist(nonce, aad)?;
+
+        // the tag depends on the plaintext, so the plaintext is released after the verification
+        let (mut pt, mut expected) = (Vec::with_capacity(ct.len()), Vec::with_capacity(XOODYAK_TAG_LEN));
+        cyclist.decrypt(&mut pt, ct)?;
+        cyclist.squeeze(&mut expected, XOODYAK_TAG_LEN);
+        if expected.iter().zip(tag.iter()).fold(0u8, |a, (&x, &y)| a | (x ^ y)) != 0 {
+            pt.iter_mut().for_each(|x| *x = 0);
+            Err(CryptoError::new(CryptoErrorKind::VerificationFailed, "The Xoodyak tag not match"))
+        } else {
+            dst.append(&mut pt);
+            Ok(())
+        }
+    }
+}
+
+impl AEAD for Xoodyak {
+    fn nonce_len(&self) -> usize {
+        XOODYAK_NONCE_LEN
+    }
+
+    fn tag_len(&self) -> usize {
+        XOODYAK_TAG_LEN
+    }
+
+    fn seal(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, nonce, aad, plaintext)
+    }
+
+    fn open(&self, dst: &mut Vec<u8>, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<(), CryptoError> {
+        self.decrypt(dst, nonce, aad, ciphertext)
+    }
+}
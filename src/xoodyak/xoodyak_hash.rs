@@ -0,0 +1,94 @@
+use crate::{Digest, DigestXOF};
+use crate::xoodyak::cyclist::{Cyclist, XOODYAK_HASH_RATE, XOODYAK_CD_ABSORB};
+
+const XOODYAK_HASH_BITS_LEN: usize = 256;
+
+/// Xoodyak hash
+///
+/// the Cyclist hash mode `absorb(M); squeeze(32)`, the digest length can be changed by the `set_digest_len`
+/// (rounded up to bytes) as the XOF.
+#[derive(Clone)]
+pub struct XoodyakHash {
+    cyclist: Cyclist,
+    // the data which isn't absorbed, it's not empty except that no data has been written,
+    // because the last block is absorbed in the checksum.
+    buf: Vec<u8>,
+    is_first_block: bool,
+    bits_len: usize,
+    digest: Vec<u8>,
+    is_checked: bool,
+}
+
+impl XoodyakHash {
+    pub fn new() -> Self {
+        Self {
+            cyclist: Cyclist::hash(),
+            buf: Vec::with_capacity(XOODYAK_HASH_RATE << 1),
+            is_first_block: true,
+            bits_len: XOODYAK_HASH_BITS_LEN,
+            digest: Vec::new(),
+            is_checked: false,
+        }
+    }
+
+    fn cd(&self) -> u8 {
+        if self.is_first_block { XOODYAK_CD_ABSORB } else { 0 }
+    }
+}
+
+impl Default for XoodyakHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for XoodyakHash {
+    fn block_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn bits_len(&self) -> usize {
+        self.bits_len
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+        let mut i = 0;
+        while self.buf.len() - i > XOODYAK_HASH_RATE {
+            let cd = self.cd();
+            self.cyclist.absorb_block(&self.buf[i..(i + XOODYAK_HASH_RATE)], cd);
+            self.is_first_block = false;
+            i += XOODYAK_HASH_RATE;
+        }
+        self.buf.drain(..i);
+
+        self.is_checked = false;
+    }
+
+    fn checksum(&mut self, digest: &mut Vec<u8>) {
+        if !self.is_checked {
+            let mut cyclist = self.cyclist.clone();
+            cyclist.absorb_block(self.buf.as_slice(), self.cd());
+            cyclist.squeeze(&mut self.digest, self.bits_len.div_ceil(8));
+            self.is_checked = true;
+        }
+
+        digest.clear();
+        digest.extend_from_slice(self.digest.as_slice());
+    }
+
+    fn reset(&mut self) {
+        self.cyclist = Cyclist::hash();
+        self.buf.clear();
+        self.is_first_block = true;
+        self.digest.clear();
+        self.is_checked = false;
+    }
+}
+
+impl DigestXOF for XoodyakHash {
+    fn set_digest_len(&mut self, bits_len: usize) {
+        self.bits_len = bits_len;
+        self.reset();
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{Cyclist, Xoodyak, XoodyakHash, Digest, DigestXOF, AEAD, CryptoErrorKind};
+use crate::test_util::hex;
+
+#[test]
+fn xoodyak_hash() {
+    let cases = [
+        (vec![], "ea152f2b47bce24efb66c479d4adf17bd324d806e85ff75ee369ee50dc8f8bd1"),
+        (vec![0u8], "27921f8ddf392894460b70b3ed6c091e6421b7d2147dcd6031d7efebad3030cc"),
+        ((0u8..32).collect::<Vec<_>>(), "cebe4aff9eac2218017dda5f8207ba830e989187256539bd7d31ae5e94ff0c6e"),
+        ((0u8..33).collect::<Vec<_>>(), "249cfccd50d66e722e80e79002ce3b302b4ca067483ab9cdeb474dbf555b7633"),
+    ];
+
+    let (mut h, mut digest) = (XoodyakHash::new(), Vec::new());
+    for (msg, tgt) in cases.iter() {
+        for &step in [1usize, 5, 16, 64].iter() {
+            h.reset();
+            msg.chunks(step).for_each(|m| h.write(m));
+            h.checksum(&mut digest);
+            assert_eq!(digest, hex(tgt), "len: {}, step: {}", msg.len(), step);
+            h.checksum(&mut digest);
+            assert_eq!(digest, hex(tgt));
+        }
+    }
+
+    h.set_digest_len(512);
+    h.write((0u8..20).collect::<Vec<_>>().as_slice());
+    h.checksum(&mut digest);
+    assert_eq!(digest, hex("9bebe7579ec1d075b6768ae981c54c7d60db82931b074a618b0a68f84cbccfe6067629836efcdb1858cd75d2394baafccc2826fd0f05983b571bf6ce4de01a20"));
+}
+
+#[test]
+fn xoodyak_aead() {
+    let key = (0u8..16).collect::<Vec<_>>();
+    let cases = [
+        (key.clone(), vec![], vec![], "4bf0e393144cb58069fc1febcafcfb3c"),
+        (key.clone(), vec![], vec![0u8], "890788eac729d9539f401845b35a34d19f"),
+        ((16u8..32).collect(), (0u8..50).collect(), (0u8..60).collect(), "67b7189ab2245afa9251acd88891902f7924746a45b06a4e6654ca5a7418a90514b20b8bc5c13e9d9ca6306df330ddb329861d4fbfd1cd220250e0453ff0f70bdece79a06bc16a815a7f57f0"),
+    ];
+
+    let (x, mut ct, mut pt) = (Xoodyak::from_slice(key.as_slice()).unwrap(), Vec::new(), Vec::new());
+    for (nonce, aad, msg, tgt) in cases.iter() {
+        x.seal(&mut ct, nonce, aad, msg).unwrap();
+        assert_eq!(ct, hex(tgt));
+        x.open(&mut pt, nonce, aad, ct.as_slice()).unwrap();
+        assert_eq!(&pt, msg);
+
+        let mut tampered = ct.clone();
+        tampered[0] ^= 1;
+        assert_eq!(x.open(&mut pt, nonce, aad, tampered.as_slice()).err().unwrap().kind(), CryptoErrorKind::VerificationFailed);
+        assert!(pt.is_empty());
+    }
+
+    assert_eq!(x.seal(&mut ct, &key[1..], &[], &[]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    assert_eq!(Xoodyak::from_slice(&key[1..]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+}
+
+#[test]
+fn xoodyak_cyclist() {
+    let key = (0u8..16).collect::<Vec<_>>();
+    let (mut c, mut buf) = (Cyclist::keyed(key.as_slice(), b"id", &[1, 2]).unwrap(), Vec::new());
+    c.absorb(b"hi");
+    c.encrypt(&mut buf, b"message").unwrap();
+    assert_eq!(buf, hex("44255d74e2c807"));
+    c.ratchet().unwrap();
+    c.squeeze_key(&mut buf, 40).unwrap();
+    assert_eq!(buf, hex("783bd65d509c8d04a848e4b0040e00c48cae1dfb0f8563fe6b04a2f7e0204d193fe05cbaf725cf97"));
+    c.squeeze(&mut buf, 16);
+    assert_eq!(buf, hex("b52332ad445c2c7b6b2af68c436e02f9"));
+
+    let mut pt = Vec::new();
+    let mut c = Cyclist::keyed(key.as_slice(), b"id", &[1, 2]).unwrap();
+    c.absorb(b"hi");
+    c.decrypt(&mut pt, hex("44255d74e2c807").as_slice()).unwrap();
+    assert_eq!(pt, b"message");
+
+    let mut h = Cyclist::hash();
+    assert_eq!(h.encrypt(&mut buf, b"message").err().unwrap().kind(), CryptoErrorKind::NotSupportUsage);
+    assert_eq!(h.ratchet().err().unwrap().kind(), CryptoErrorKind::NotSupportUsage);
+    assert_eq!(Cyclist::keyed(&[0u8; 40], &[0u8; 4], &[]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+}
@@ -1,10 +1,12 @@
-use crate::{Cipher, CryptoError, CryptoErrorKind};
+use crate::{Cipher, StreamCipher, CryptoError, CryptoErrorKind};
 use std::cell::Cell;
 use crate::zuc::ZUC;
 
 /// ZUC stream cipher algorithm   
 /// GM/T 0001-2012
 /// 
+/// the 128-EEA3 confidentiality algorithm(3GPP ETSI/SAGE 128-EEA3 & 128-EIA3 Specification), the IV is built from the
+/// `COUNT`, `BEARER` and `DIRECTION`, the message of any bit length is encrypted by the `encrypt_bits`.
 pub struct ZUCCipher {
     zuc: Cell<ZUC>,
     ck: [u8; 16],
@@ -35,14 +37,7 @@ impl ZUCCipher {
             Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
                                  format!("The length of key and iv must be the {} in bytes", 16)))
         } else {
-            let mut iv = [0u8; 16];
-            let tmp = count.to_be_bytes();
-            iv[0] = tmp[0]; iv[1] = tmp[1]; iv[2] = tmp[2]; iv[3] = tmp[3];
-            iv[4] = (bearer << 3) | ((direction as u8) << 2);
-            for i in 0..5 {
-                iv[i + 8] = iv[i];
-            }
-            
+            let iv = Self::eea3_iv(count, bearer, direction);
             match ZUC::from_slice(ck, iv.as_ref()) {
                 Ok(z) => {
                     let mut tmp = [0u8; 16];
@@ -61,12 +56,78 @@ impl ZUCCipher {
         }
     }
     
+    /// the 128-EEA3 IV: `COUNT || BEARER || DIRECTION || 0 || COUNT || BEARER || DIRECTION || 0`
+    fn eea3_iv(count: u32, bearer: u8, direction: bool) -> [u8; 16] {
+        let mut iv = [0u8; 16];
+        let tmp = count.to_be_bytes();
+        iv[0] = tmp[0]; iv[1] = tmp[1]; iv[2] = tmp[2]; iv[3] = tmp[3];
+        iv[4] = (bearer << 3) | ((direction as u8) << 2);
+        for i in 0..5 {
+            iv[i + 8] = iv[i];
+        }
+        iv
+    }
+    
     /// this will reset to the initialization status
+    ///
+    /// # Security
+    ///
+    /// the key stream of the construction-time `COUNT` is generated again, only re-process the same message after the
+    /// `reset`, and encrypt the next message by the `reset_with_count` with a fresh `COUNT`.
     pub fn reset(&mut self) {
         self.zuc.get_mut().set_slice(self.ck.as_ref(), self.iv.as_ref()).unwrap();
         self.key.get_mut().clear();
     }
     
+    /// reset the key stream to the IV of the fresh `count`, `bearer` and `direction` under the same key, it's the way
+    /// to encrypt the next message. The IV equal to the current IV is rejected.
+    pub fn reset_with_count(&mut self, count: u32, bearer: u8, direction: bool) -> Result<(), CryptoError> {
+        let iv = Self::eea3_iv(count, bearer, direction);
+        if iv == self.iv {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter, "The 128-EEA3 COUNT must be fresh, the IV is the same as the current IV"));
+        }
+        
+        self.iv = iv;
+        self.reset();
+        Ok(())
+    }
+    
+    /// generate the next `n` 32-bit words of the 128-EEA3 keystream, it must be called at the word boundary,
+    /// i.e. the length of the data processed by the `encrypt`/`decrypt` must be a multiple of 4 bytes.
+    pub fn keystream(&mut self, n: usize, words: &mut Vec<u32>) -> Result<(), CryptoError> {
+        if !self.key.get_mut().is_empty() {
+            Err(CryptoError::new(CryptoErrorKind::NotSupportUsage, "The keystream is not at the word boundary"))
+        } else {
+            self.zuc.get_mut().keystream(n, words);
+            Ok(())
+        }
+    }
+    
+    /// encrypt the first `bits_len` bits of the `data`, the `dst` is the `ceil(bits_len / 8)` bytes and the bits after
+    /// the `bits_len` in the last byte are zeros. The message ends at the `bits_len`, the keystream of the last
+    /// partial byte is dropped.
+    pub fn encrypt_bits(&self, dst: &mut Vec<u8>, data: &[u8], bits_len: usize) -> Result<(), CryptoError> {
+        if bits_len > (data.len() << 3) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The bits length {} is greater than the data length {} bits", bits_len, data.len() << 3)));
+        }
+        
+        self.encrypt(dst, &data[..((bits_len + 7) >> 3)])?;
+        if bits_len & 7 != 0 {
+            if let Some(last) = dst.last_mut() {
+                *last &= 0xffu8 << (8 - (bits_len & 7));
+            }
+        }
+        Ok(())
+    }
+    
+    /// the 128-EEA3 encryption(or decryption) of the first `bits_len` bits of the `message`, see the `encrypt_bits`
+    pub fn eea3(count: u32, bearer: u8, direction: bool, ck: [u8; 16], message: &[u8], bits_len: usize) -> Result<Vec<u8>, CryptoError> {
+        let mut dst = Vec::with_capacity((bits_len + 7) >> 3);
+        Self::new(count, bearer, direction, ck).encrypt_bits(&mut dst, message, bits_len)?;
+        Ok(dst)
+    }
+    
     #[inline]
     fn get_zuc(&self) -> &mut ZUC {
         unsafe  {
@@ -103,7 +164,7 @@ impl Cipher for ZUCCipher {
         self.get_key().truncate(self.get_key().len() - len);
         
         if plaintext_block.is_empty() {
-            return Ok(0);
+            return Ok(dst.len());
         }
 
         let len = (plaintext_block.len() + 3) >> 2;
@@ -130,4 +191,14 @@ impl Cipher for ZUCCipher {
     fn decrypt(&self, dst: &mut Vec<u8>, cipher_block: &[u8]) -> Result<usize, CryptoError> {
         self.encrypt(dst, cipher_block)
     }
+}
+
+impl StreamCipher for ZUCCipher {
+    fn xor_key_stream(&mut self, dst: &mut Vec<u8>, data: &[u8]) -> Result<(), CryptoError> {
+        self.encrypt(dst, data).map(|_| ())
+    }
+
+    fn reset(&mut self) {
+        ZUCCipher::reset(self);
+    }
 }
\ No newline at end of file
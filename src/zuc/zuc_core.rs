@@ -185,6 +185,21 @@ impl ZUC {
         self.lfsr_with_work_mode();
         z
     }
+    
+    /// generate the next `n` 32-bit words of the raw keystream `z_1, z_2, ..., z_n` into the `words`
+    pub fn keystream(&mut self, n: usize, words: &mut Vec<u32>) {
+        words.clear();
+        words.extend(self.take(n));
+    }
+    
+    /// generate the next `n` 32-bit words of the raw keystream, and output each word to the `dst` in the
+    /// big-endian(the GM/T 0001-2012 output order) or little-endian byte order.
+    pub fn keystream_bytes(&mut self, n: usize, dst: &mut Vec<u8>, is_big_endian: bool) {
+        dst.clear();
+        for z in self.take(n) {
+            dst.extend_from_slice(if is_big_endian {z.to_be_bytes()} else {z.to_le_bytes()}.as_ref());
+        }
+    }
 }
 
 impl Iterator for ZUC {
@@ -1,6 +1,11 @@
 use crate::zuc::ZUC;
 use crate::{CryptoError, CryptoErrorKind, Digest};
 
+/// 128-EIA3 integrity algorithm  
+/// 3GPP ETSI/SAGE 128-EEA3 & 128-EIA3 Specification Version 1.7
+///
+/// the IV is built from the `COUNT`, `BEARER` and `DIRECTION`, the message is the bit string, the `write` writes the
+/// whole bytes and the `write_bits` writes the message of any bit length, e.g. the last partial byte of the PDCP PDU.
 #[derive(Clone)]
 pub struct ZUCMac {
     zuc: ZUC,
@@ -38,7 +43,8 @@ impl ZUCMac {
             iv[15] = iv[7];
 
             match ZUC::from_slice(ck, iv.as_ref()) {
-                Ok(z) => {
+                Ok(mut z) => {
+                    let (key0, key1) = (z.zuc(), z.zuc());
                     let mut tmp = [0u8; 16];
                     tmp.iter_mut().zip(ck.iter()).for_each(|(e, &k)| {
                         *e = k;
@@ -47,8 +53,8 @@ impl ZUCMac {
                         zuc: z,
                         ck: tmp,
                         iv,
-                        key0: 0,
-                        key1: 0,
+                        key0,
+                        key1,
                         length: 0,
                         t: 0,
                         is_check: false,
@@ -59,6 +65,51 @@ impl ZUCMac {
         }
     }
     
+    /// the 128-EIA3 MAC of the first `bits_len` bits of the `message`
+    pub fn eia3(count: u32, bearer: u8, direction: bool, ik: [u8; 16], message: &[u8], bits_len: usize) -> Result<u32, CryptoError> {
+        let (mut mac, mut buf) = (Self::new(count, bearer, direction, ik), Vec::with_capacity(4));
+        mac.write_bits(message, bits_len)?;
+        mac.checksum(&mut buf);
+        Ok(u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]))
+    }
+
+    /// write the first `bits_len` bits of the `data`, the bits are in the most significant bit first order, so the
+    /// message of the `LENGTH` bits is written by the `write_bits(message, LENGTH)`.
+    pub fn write_bits(&mut self, data: &[u8], bits_len: usize) -> Result<(), CryptoError> {
+        if bits_len > (data.len() << 3) {
+            return Err(CryptoError::new(CryptoErrorKind::InvalidParameter,
+                format!("The bits length {} is greater than the data length {} bits", bits_len, data.len() << 3)));
+        }
+        
+        if self.is_check {
+            self.reset();
+        }
+        
+        // the key0 is the keystream word at the bit `self.length & !31`, the key1 is the next word
+        for i in 0..bits_len {
+            let rem = (self.length + i) & 31;
+            if Self::is_one(data, i) {
+                self.t ^= Self::key_word(self.key0, self.key1, rem);
+            }
+            
+            if rem == 31 {
+                self.key0 = self.key1;
+                self.key1 = self.zuc.zuc();
+            }
+        }
+        self.length += bits_len;
+        Ok(())
+    }
+    
+    /// the keystream word at the bit `rem` of the `key0 || key1`
+    fn key_word(key0: u32, key1: u32, rem: usize) -> u32 {
+        if rem == 0 {
+            key0
+        } else {
+            (key0 << rem) | (key1 >> (32 - rem))
+        }
+    }
+    
     fn is_one(data: &[u8], idx: usize) -> bool {
         let eidx = idx >> 3;
         let tshl = 7 - (idx & 7);
@@ -76,43 +127,16 @@ impl Digest for ZUCMac {
     }
 
     fn write(&mut self, data: &[u8]) {
-        if self.is_check {
-            self.reset();
-        }
-        
-        let bound = data.len() << 3;
-        
-        self.key0 = self.zuc.zuc();
-        self.key1 = self.zuc.zuc();
-        for i in 0..bound {
-            let rem = i & 31;
-            if Self::is_one(data, i) {
-                if rem == 0 {
-                    self.t ^= self.key0;
-                } else {
-                    self.t ^= (self.key0 << rem) | (self.key1 >> (32 - rem));
-                }
-            }
-            
-            if rem == 31 {
-                self.key0 = self.key1;
-                self.key1 = self.zuc.zuc();
-            }
-        }
-        self.length += bound;
+        self.write_bits(data, data.len() << 3).unwrap();
     }
 
+    /// T = T xor z_{LENGTH}, MAC = T xor z_{32(L-1)}, where the L = ceil(LENGTH / 32) + 2
     fn checksum(&mut self, digest: &mut Vec<u8>) {
         if !self.is_check {
             let rem = self.length & 31;
-            if rem == 0 {
-                self.t ^= self.key0;
-                self.t ^= self.key1;
-            } else {
-                self.t ^= (self.key0 << rem) | (self.key1 >> (32 - rem));
-                self.t ^= self.zuc.zuc();
-            }
-
+            self.t ^= Self::key_word(self.key0, self.key1, rem);
+            self.t ^= if rem == 0 { self.key1 } else { self.zuc.zuc() };
+            self.is_check = true;
         }
         
         digest.clear();
@@ -121,9 +145,10 @@ impl Digest for ZUCMac {
 
     fn reset(&mut self) {
         self.zuc.set_slice(self.ck.as_ref(), self.iv.as_ref()).unwrap();
-        self.key0 = 0;
-        self.key1 = 0;
+        self.key0 = self.zuc.zuc();
+        self.key1 = self.zuc.zuc();
         self.length = 0;
         self.t = 0;
+        self.is_check = false;
     }
 }
\ No newline at end of file
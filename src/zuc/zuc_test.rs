@@ -1,4 +1,4 @@
-use crate::{ZUC, ZUCCipher, Cipher, ZUCMac, Digest};
+use crate::{ZUC, ZUCCipher, Cipher, ZUCMac, Digest, CryptoErrorKind};
 
 #[test]
 fn zuc_core() {
@@ -175,6 +175,16 @@ fn zuc_cipher() {
             } else {
             }
         });
+        
+        // the 128-EEA3 of the LENGTH bits message
+        tmp.clear();
+        ele.ibs.iter().for_each(|e| tmp.extend_from_slice(e.to_be_bytes().as_ref()));
+        let c = ZUCCipher::eea3(ele.count, ele.bearer, ele.direction == 1, ele.ck, tmp.as_slice(), ele.bits_len as usize).unwrap();
+        assert_eq!(c.len(), (ele.bits_len as usize + 7) >> 3, "case: {}", i);
+        let mask = 0xffu8 << ((8 - (ele.bits_len & 7)) & 7);
+        assert_eq!((&c[..(c.len() - 1)], c[c.len() - 1]), (&buf[..(c.len() - 1)], buf[c.len() - 1] & mask), "case: {}", i);
+        let m = ZUCCipher::eea3(ele.count, ele.bearer, ele.direction == 1, ele.ck, c.as_slice(), ele.bits_len as usize).unwrap();
+        assert_eq!((&m[..(m.len() - 1)], m[m.len() - 1]), (&tmp[..(m.len() - 1)], tmp[m.len() - 1] & mask), "case: {}", i);
     }
 }
 
@@ -185,9 +195,11 @@ fn zuc_mac() {
         count: u32,
         bearer: u8,
         direction: u8,
-        // bits_len: u32,
+        bits_len: usize,
         msg: Vec<u32>,
+        // the MAC of the whole words
         mac: u32,
+        eia3: u32,
     };
     
     let cases = [
@@ -197,9 +209,9 @@ fn zuc_mac() {
             count: 0,
             bearer: 0,
             direction: 0,
-            // bits_len: 1,
+            bits_len: 1,
             msg: vec![0],
-            // mac: 0xc8a9595e,
+            eia3: 0xc8a9595e,
             mac: 0x8654676c,
         },
         Cases {
@@ -208,13 +220,13 @@ fn zuc_mac() {
             count: 0xa94059da, 
             bearer: 0xa,
             direction: 1,
-            // bits_len: 0x241,
+            bits_len: 0x241,
             msg: vec![0x983b41d4, 0x7d780c9e, 0x1ad11d7e, 0xb70391b1,
                       0xde0b35da, 0x2dc62f83, 0xe7b78d63, 0x06ca0ea0,
                       0x7e941b7b, 0xe91348f9, 0xfcb170e2, 0x217fecd9,
                       0x7f9f68ad, 0xb16e5d7d, 0x21e569d2, 0x80ed775c,
                       0xebde3f40, 0x93c53881, 0x00000000],
-            // mac: 0xfae8ff0b,
+            eia3: 0xfae8ff0b,
             mac: 0x99d8a41f,
         },
         Cases {
@@ -223,7 +235,7 @@ fn zuc_mac() {
             count: 0x561eb2dd,
             bearer: 0x1c,
             direction: 0,
-            // bits_len: 0x1626,
+            bits_len: 0x1626,
             msg: vec![0x5bad7247, 0x10ba1c56, 0xd5a315f8, 0xd40f6e09,
                       0x3780be8e, 0x8de07b69, 0x92432018, 0xe08ed96a,
                       0x5734af8b, 0xad8a575d, 0x3a1f162f, 0x85045cc7,
@@ -269,7 +281,7 @@ fn zuc_mac() {
                       0xd9cd9578, 0x08f08571, 0xd9a4bb79, 0x2af271f6,
                       0xcc6dbb8d, 0xc7ec36e3, 0x6be1ed30, 0x8164c31c,
                       0x7c0afc54, 0x1c000000,],
-            // mac: 0x0ca12792,
+            eia3: 0x0ca12792,
             mac: 0x259299ab,
         },
     ];
@@ -288,5 +300,94 @@ fn zuc_mac() {
         let mut mac = 0u32;
         buf.iter().for_each(|&a| {mac <<= 8; mac |= a as u32;});
         assert_eq!(mac, ele.mac, "case: {}", i);
+        zuc_mac.checksum(&mut buf);
+        assert_eq!(buf, mac.to_be_bytes().to_vec(), "case: {}", i);
+        
+        // the 128-EIA3 of the LENGTH bits message
+        assert_eq!(ZUCMac::eia3(ele.count, ele.bearer, ele.direction == 1, ele.ik, tmp.as_slice(), ele.bits_len).unwrap(), ele.eia3, "case: {}", i);
+        for &step in [1usize, 5, 31, 32, 33, 100].iter() {
+            let mut zuc_mac = ZUCMac::new(ele.count, ele.bearer, ele.direction == 1, ele.ik);
+            let mut pos = 0;
+            while pos < ele.bits_len {
+                // the bits from the `pos` are shifted to the first byte
+                let n = step.min(ele.bits_len - pos);
+                let mut bits = vec![0u8; (n + 7) >> 3];
+                (0..n).filter(|&j| (tmp[(pos + j) >> 3] >> (7 - ((pos + j) & 7))) & 1 == 1).for_each(|j| bits[j >> 3] |= 0x80 >> (j & 7));
+                zuc_mac.write_bits(bits.as_slice(), n).unwrap();
+                pos += n;
+            }
+            zuc_mac.checksum(&mut buf);
+            assert_eq!(buf, ele.eia3.to_be_bytes().to_vec(), "case: {}, step: {}", i, step);
+        }
+        assert!(ZUCMac::eia3(ele.count, ele.bearer, ele.direction == 1, ele.ik, tmp.as_slice(), (tmp.len() << 3) + 1).is_err());
+    }
+}
+#[test]
+fn zuc_keystream() {
+    // GM/T 0001-2012 appendix A
+    let (key, iv) = ([0x3du8, 0x4c, 0x4b, 0xe9, 0x6a, 0x82, 0xfd, 0xae, 0xb5, 0x8f, 0x64, 0x1d, 0xb1, 0x7b, 0x45, 0x5b],
+        [0x84u8, 0x31, 0x9a, 0xa8, 0xde, 0x69, 0x15, 0xca, 0x1f, 0x6b, 0xda, 0x6b, 0xfb, 0xd8, 0xc7, 0x66]);
+    let (mut words, mut bytes) = (Vec::new(), Vec::new());
+    let mut zuc = ZUC::new(key, iv);
+    zuc.keystream(2, &mut words);
+    assert_eq!(words, vec![0x14f1c272, 0x3279c419]);
+    zuc.set(key, iv);
+    zuc.keystream_bytes(2, &mut bytes, true);
+    assert_eq!(bytes, vec![0x14, 0xf1, 0xc2, 0x72, 0x32, 0x79, 0xc4, 0x19]);
+    zuc.set(key, iv);
+    zuc.keystream_bytes(2, &mut bytes, false);
+    assert_eq!(bytes, vec![0x72, 0xc2, 0xf1, 0x14, 0x19, 0xc4, 0x79, 0x32]);
+    zuc.keystream(0, &mut words);
+    assert!(words.is_empty());
+    
+    // 128-EEA3 test set 1: keystream = IBS ^ OBS
+    let ck = [0x17, 0x3d, 0x14, 0xba, 0x50, 0x03, 0x73, 0x1d, 0x7a, 0x60, 0x04, 0x94, 0x70, 0xf0, 0x0a, 0x29];
+    let (ibs, obs) = ([0x6cf65340u32, 0x735552ab, 0x0c9752fa, 0x6f9025fe, 0x0bd675d9, 0x005875b2],
+        [0xa6c85fc6u32, 0x6afb8533, 0xaafc2518, 0xdfe78494, 0x0ee1e4b0, 0x30238cc8]);
+    let mut cipher = ZUCCipher::new(0x66035492, 0xf, false, ck);
+    cipher.keystream(6, &mut words).unwrap();
+    for (i, (&w, (&a, &b))) in words.iter().zip(ibs.iter().zip(obs.iter())).enumerate() {
+        assert_eq!(w, a ^ b, "case: {}", i);
     }
+    
+    cipher.reset();
+    cipher.encrypt(&mut bytes, &[0u8; 5]).unwrap();
+    assert!(cipher.keystream(1, &mut words).is_err());
+    cipher.encrypt(&mut bytes, &[0u8; 3]).unwrap();
+    cipher.keystream(1, &mut words).unwrap();
+    assert_eq!(words[0], ibs[2] ^ obs[2]);
+    
+    assert_eq!(cipher.reset_with_count(0x66035492, 0xf, false).unwrap_err().kind(), CryptoErrorKind::InvalidParameter);
+    cipher.reset_with_count(0x66035493, 0xf, false).unwrap();
+    let mut other = ZUCCipher::new(0x66035493, 0xf, false, ck);
+    cipher.keystream(6, &mut words).unwrap();
+    let mut tgt = Vec::new();
+    other.keystream(6, &mut tgt).unwrap();
+    assert_eq!(words, tgt);
+}
+
+
+#[test]
+fn zuc_length_error() {
+    for len in [0usize, 15, 17, 32].iter() {
+        let v = vec![0u8; *len];
+        assert_eq!(ZUC::from_slice(v.as_slice(), &[0u8; 16]).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+        assert_eq!(ZUC::from_slice(&[0u8; 16], v.as_slice()).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+        assert_eq!(ZUCCipher::from_slice(0, 0, false, v.as_slice()).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+        assert_eq!(ZUCMac::from_slice(0, 0, false, v.as_slice()).err().unwrap().kind(), CryptoErrorKind::InvalidParameter);
+    }
+
+    let mut zuc = ZUC::new([0u8; 16], [0u8; 16]);
+    assert!(zuc.set_slice(&[0u8; 16], &[0u8; 8]).is_err());
+    assert_eq!(zuc.zuc(), 0x27bede74);
+
+    // the data within the buffered key stream returns its length as the other data
+    let cipher = ZUCCipher::new(0, 0, false, [0u8; 16]);
+    let (mut a, mut b, mut c) = (Vec::new(), Vec::new(), Vec::new());
+    assert_eq!(cipher.encrypt(&mut a, &[0u8; 5]).unwrap(), 5);
+    assert_eq!(cipher.encrypt(&mut b, &[0u8; 3]).unwrap(), 3);
+    a.extend_from_slice(b.as_slice());
+    ZUCCipher::new(0, 0, false, [0u8; 16]).encrypt(&mut c, &[0u8; 8]).unwrap();
+    assert_eq!(a, c);
+    assert_eq!(cipher.encrypt(&mut b, &[]).unwrap(), 0);
 }
\ No newline at end of file